@@ -65,8 +65,12 @@ fn criterion_benchmark(c: &mut Criterion) {
                     state: ButtonState::JustPressed,
                     value: 0.0,
                     axis_pair: None,
+                    axis_triple: None,
                     timing: Timing::default(),
                     consumed: false,
+                    blocked_by_cooldown: false,
+                    timing_preserved: false,
+                    hold_history: None,
                 },
             )
         })
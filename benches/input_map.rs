@@ -8,7 +8,7 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use leafwing_input_manager::{
     action_state::ActionData,
     input_streams::InputStreams,
-    prelude::{ClashStrategy, InputMap, MockInput},
+    prelude::{ClashStrategy, InputMap, MockInput, Modifier},
     Actionlike,
 };
 
@@ -26,6 +26,53 @@ enum TestAction {
     J,
 }
 
+/// An action type with many more actions, to show how [`InputMap::which_pressed`] scales
+#[derive(Actionlike, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+struct NumberedAction(usize);
+
+const KEYS: [KeyCode; 30] = [
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+];
+
+/// 60 actions: one for each key, and one for each key while holding Control, which clash with each other
+fn construct_large_input_map() -> InputMap<NumberedAction> {
+    let mut input_map = InputMap::default();
+    for (i, key) in KEYS.into_iter().enumerate() {
+        input_map.insert(NumberedAction(i), key);
+        input_map.insert_modified(NumberedAction(KEYS.len() + i), Modifier::Control, key);
+    }
+    input_map
+}
+
 fn construct_input_map_from_iter() -> InputMap<TestAction> {
     black_box(InputMap::new([
         (TestAction::A, KeyCode::A),
@@ -90,6 +137,23 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         });
     }
     which_pressed_group.finish();
+
+    let mut large_input_map_group = c.benchmark_group("which_pressed_60_actions");
+    let mut input_map = construct_large_input_map();
+
+    // The possible clashes are computed once, then reused every frame
+    large_input_map_group.bench_function("unchanged_bindings", |b| {
+        b.iter(|| input_map.which_pressed(&input_streams, ClashStrategy::PrioritizeLongest))
+    });
+    // Changing the bindings every frame forces the possible clashes to be recomputed
+    large_input_map_group.bench_function("rebound_every_frame", |b| {
+        b.iter(|| {
+            input_map.clear_action(&NumberedAction(0));
+            input_map.insert(NumberedAction(0), KeyCode::A);
+            input_map.which_pressed(&input_streams, ClashStrategy::PrioritizeLongest)
+        })
+    });
+    large_input_map_group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
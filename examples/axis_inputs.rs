@@ -26,11 +26,9 @@ struct Player;
 
 fn spawn_player(mut commands: Commands) {
     commands
-        .spawn(InputManagerBundle::<Action> {
-            // Stores "which actions are currently activated"
-            action_state: ActionState::default(),
+        .spawn(InputManagerBundle::with_map(
             // Describes how to convert from player inputs into those actions
-            input_map: InputMap::default()
+            InputMap::default()
                 // Configure the left stick as a dual-axis
                 .insert(Action::Move, DualAxis::left_stick())
                 // Let's bind the right gamepad trigger to the throttle action
@@ -42,7 +40,7 @@ fn spawn_player(mut commands: Commands) {
                     SingleAxis::symmetric(GamepadAxisType::RightStickX, 0.1),
                 )
                 .build(),
-        })
+        ))
         .insert(Player);
 }
 
@@ -0,0 +1,128 @@
+//! Top-down aiming towards the cursor
+//!
+//! The game projects the cursor through its 2D camera into the `CursorWorldSource` resource each frame,
+//! and the `Aim` action reads the direction from the player to the cursor.
+//! The player moves with WASD, and its aiming line grows as the cursor moves away from it.
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use leafwing_input_manager::cursor_world::{CursorRelative, CursorWorldSource};
+use leafwing_input_manager::plugin::InputManagerSystem;
+use leafwing_input_manager::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        // The cursor must be projected before the actions are updated
+        .add_systems(
+            PreUpdate,
+            update_cursor_world_source
+                .after(InputSystem)
+                .before(InputManagerSystem::Update),
+        )
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_player, aim_player))
+        .run();
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+enum Action {
+    Move,
+    Aim,
+}
+
+#[derive(Component)]
+struct Player;
+
+#[derive(Component)]
+struct AimLine;
+
+impl Player {
+    /// How fast the player moves, in pixels per second
+    const SPEED: f32 = 300.0;
+    /// How far the cursor must be from the player to aim at full strength, in pixels
+    const AIM_DISTANCE: f32 = 200.0;
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let input_map = InputMap::new([
+        (Action::Move, UserInput::from(VirtualDPad::wasd())),
+        (
+            Action::Aim,
+            CursorRelative::new(Player::AIM_DISTANCE).into(),
+        ),
+    ]);
+
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: Color::TEAL,
+                custom_size: Some(Vec2::splat(40.0)),
+                ..default()
+            },
+            ..default()
+        })
+        // The `GlobalTransform` of the player is where it aims from
+        .insert(InputManagerBundle::with_map(input_map))
+        .insert(Player)
+        .with_children(|parent| {
+            parent
+                .spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::ORANGE_RED,
+                        custom_size: Some(Vec2::new(1.0, 4.0)),
+                        anchor: bevy::sprite::Anchor::CenterLeft,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(AimLine);
+        });
+}
+
+/// Stores the position of the cursor in the world, as seen through the 2D camera
+fn update_cursor_world_source(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut cursor_world_source: ResMut<CursorWorldSource>,
+) {
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), cameras.get_single())
+    else {
+        return;
+    };
+
+    // `None` while the cursor is outside of the window, which stops aiming
+    cursor_world_source.0 = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor));
+}
+
+fn move_player(
+    time: Res<Time>,
+    mut query: Query<(&ActionState<Action>, &mut Transform), With<Player>>,
+) {
+    let (action_state, mut transform) = query.single_mut();
+    if let Some(movement) = action_state.clamped_axis_pair(&Action::Move) {
+        transform.translation += movement.xy().extend(0.0) * Player::SPEED * time.delta_seconds();
+    }
+}
+
+fn aim_player(
+    player: Query<&ActionState<Action>, With<Player>>,
+    mut aim_line: Query<&mut Transform, With<AimLine>>,
+) {
+    let action_state = player.single();
+    let mut transform = aim_line.single_mut();
+
+    // The length of the axis pair grows with the distance to the cursor, up to `Player::AIM_DISTANCE`
+    let aim = action_state
+        .axis_pair(&Action::Aim)
+        .map(|axis_pair| axis_pair.xy())
+        .unwrap_or_default();
+    transform.rotation = Quat::from_rotation_z(aim.y.atan2(aim.x));
+    transform.scale.x = aim.length() * Player::AIM_DISTANCE;
+}
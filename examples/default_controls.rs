@@ -65,10 +65,7 @@ fn spawn_player(mut commands: Commands) {
 
     // Spawn the player with the populated input_map
     commands
-        .spawn(InputManagerBundle::<PlayerAction> {
-            input_map,
-            ..default()
-        })
+        .spawn(InputManagerBundle::with_map(input_map))
         .insert(Player);
 }
 
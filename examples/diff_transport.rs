@@ -0,0 +1,109 @@
+//! [`ActionDiff`](leafwing_input_manager::action_diff::ActionDiff) event streams can be sent
+//! through any message-passing transport that implements [`DiffTransport`].
+//!
+//! This example uses the [`InMemoryChannel`] to connect a client and a server running in the same process,
+//! but the same systems work unchanged with a wrapper around your networking crate of choice.
+//!
+//! Run with `cargo run --example diff_transport --features diff_transport`.
+
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::action_diff::ActionDiffEvent;
+use leafwing_input_manager::diff_transport::{
+    receive_diffs_via, send_diffs_via, DiffTransport, InMemoryChannel,
+};
+use leafwing_input_manager::plugin::InputManagerSystem;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::systems::generate_action_diffs;
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum FpsAction {
+    Jump,
+    Shoot,
+}
+
+type Channel = InMemoryChannel<FpsAction>;
+
+fn main() {
+    // In a real use case, these apps would be running on separate devices,
+    // and each end of the channel would wrap a network connection.
+    let (client_channel, server_channel) = Channel::pair();
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((
+            MinimalPlugins,
+            InputPlugin,
+            InputManagerPlugin::<FpsAction>::default(),
+        ))
+        .add_event::<ActionDiffEvent<FpsAction>>()
+        .insert_resource(client_channel)
+        // Generates the `ActionDiff`s, then sends them to the server
+        .add_systems(
+            PostUpdate,
+            (
+                generate_action_diffs::<FpsAction>,
+                send_diffs_via::<FpsAction, Channel>,
+            )
+                .chain(),
+        )
+        .add_systems(Startup, spawn_player);
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            MinimalPlugins,
+            InputManagerPlugin::<FpsAction>::server().apply_action_diffs(true),
+        ))
+        .insert_resource(server_channel)
+        // Receives the `ActionDiff`s sent by the client, which the server plugin then applies
+        // The player is spawned with the same id on both sides, so the owners need no mapping
+        .add_systems(
+            PreUpdate,
+            receive_diffs_via::<FpsAction, Channel>.before(InputManagerSystem::Update),
+        )
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(ActionState::<FpsAction>::default());
+        });
+
+    // Starting up the game
+    client_app.update();
+    server_app.update();
+
+    // Pressing buttons on the client sends a frame to the server
+    client_app.send_input(KeyCode::Space);
+    client_app.send_input(MouseButton::Left);
+    client_app.update();
+    assert_eq!(
+        server_app.world.resource::<Channel>().pending(),
+        1,
+        "The client should have sent a single frame"
+    );
+
+    // Which the server then receives and applies
+    server_app.update();
+    let mut player_state_query = server_app.world.query::<&ActionState<FpsAction>>();
+    let player_state = player_state_query.single(&server_app.world);
+    assert!(player_state.pressed(&FpsAction::Jump));
+    assert!(player_state.pressed(&FpsAction::Shoot));
+
+    // Releasing them works the same way
+    client_app.reset_inputs();
+    client_app.update();
+    server_app.update();
+    let player_state = player_state_query.single(&server_app.world);
+    assert!(player_state.released(&FpsAction::Jump));
+    assert!(player_state.released(&FpsAction::Shoot));
+
+    // Nothing is sent when nothing changes
+    client_app.update();
+    assert!(server_app.world.resource_mut::<Channel>().poll().is_empty());
+}
+
+fn spawn_player(mut commands: Commands) {
+    commands.spawn(InputManagerBundle::with_map(
+        InputMap::new([(FpsAction::Jump, KeyCode::Space)])
+            .insert(FpsAction::Shoot, MouseButton::Left)
+            .build(),
+    ));
+}
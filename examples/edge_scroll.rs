@@ -0,0 +1,68 @@
+//! Pans the camera when the cursor touches the edges of the window, or with WASD, as in real-time strategy games
+//!
+//! Both inputs are bound to the same action, so their axis pairs add up:
+//! holding A while the cursor is on the right edge stops the camera, and holding W there pans diagonally.
+
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::screen_edge::ScreenEdge;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputManagerPlugin::<CameraAction>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, pan_camera)
+        .run();
+}
+
+#[derive(Actionlike, Clone, Debug, Copy, PartialEq, Eq, Hash, Reflect)]
+enum CameraAction {
+    Pan,
+}
+
+/// How fast the camera pans, in pixels per second
+const PAN_SPEED: f32 = 500.0;
+
+fn setup(mut commands: Commands) {
+    let input_map = InputMap::new([
+        (CameraAction::Pan, UserInput::from(VirtualDPad::wasd())),
+        // Within 20 pixels of the edges of the window
+        (CameraAction::Pan, ScreenEdge::new(20.0).into()),
+    ]);
+    commands
+        .spawn(Camera2dBundle::default())
+        .insert(InputManagerBundle::with_map(input_map));
+
+    // A checkerboard to see the camera move
+    for x in -10..=10 {
+        for y in -10..=10 {
+            let color = if (x + y) % 2 == 0 {
+                Color::DARK_GREEN
+            } else {
+                Color::OLIVE
+            };
+            commands.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(100.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x as f32 * 100.0, y as f32 * 100.0, 0.0),
+                ..default()
+            });
+        }
+    }
+}
+
+fn pan_camera(
+    time: Res<Time>,
+    mut query: Query<(&ActionState<CameraAction>, &mut Transform), With<Camera>>,
+) {
+    let (action_state, mut transform) = query.single_mut();
+
+    // Clamped, so that holding D while the cursor is on the right edge isn't faster
+    if let Some(pan) = action_state.clamped_axis_pair(&CameraAction::Pan) {
+        transform.translation += pan.xy().extend(0.0) * PAN_SPEED * time.delta_seconds();
+    }
+}
@@ -0,0 +1,51 @@
+//! Demonstrates how actions hand the keyboard and mouse over to egui, using the `egui` feature
+//!
+//! Typing in the text field does not move the player, and clicking on the window does not shoot.
+//! Held actions are released while egui owns their inputs.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPlugin};
+use leafwing_input_manager::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EguiPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::new([
+            (Action::Move, UserInput::VirtualDPad(VirtualDPad::wasd())),
+            (Action::Shoot, MouseButton::Left.into()),
+        ]))
+        .init_resource::<ChatMessage>()
+        .add_systems(Update, (chat_window, report_actions))
+        .run()
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+enum Action {
+    Move,
+    Shoot,
+}
+
+#[derive(Resource, Default)]
+struct ChatMessage(String);
+
+fn chat_window(mut contexts: EguiContexts, mut message: ResMut<ChatMessage>) {
+    egui::Window::new("Chat").show(contexts.ctx_mut(), |ui| {
+        ui.label("Click here and type: WASD no longer moves the player");
+        ui.text_edit_singleline(&mut message.0);
+    });
+}
+
+fn report_actions(action_state: Res<ActionState<Action>>) {
+    if action_state.just_pressed(&Action::Move) {
+        println!("Started moving");
+    }
+    if action_state.just_released(&Action::Move) {
+        println!("Stopped moving");
+    }
+    if action_state.just_pressed(&Action::Shoot) {
+        println!("Bang!");
+    }
+}
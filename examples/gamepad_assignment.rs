@@ -0,0 +1,70 @@
+//! Demonstrates how to let local players join with their gamepad, using the `GamepadAssignmentPlugin`
+//!
+//! The first player uses the keyboard, and a second player joins by pressing Start on a gamepad.
+
+use bevy::prelude::*;
+use leafwing_input_manager::gamepad_assignment::{
+    GamepadAssigned, GamepadAssignmentPlugin, PlayerGamepadLost,
+};
+use leafwing_input_manager::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_plugins(
+            GamepadAssignmentPlugin::<Action>::default().with_spawner(spawn_gamepad_player),
+        )
+        .add_systems(Startup, spawn_keyboard_player)
+        .add_systems(Update, (announce_players, jump))
+        .run()
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+enum Action {
+    Jump,
+}
+
+#[derive(Component)]
+struct Player(&'static str);
+
+fn spawn_keyboard_player(mut commands: Commands) {
+    // The keyboard player has no gamepad inputs, so it does not react to the gamepads of other players
+    commands.spawn((
+        Player("Keyboard"),
+        InputManagerBundle::with_map(InputMap::new([(Action::Jump, KeyCode::Space)])),
+    ));
+}
+
+// Called by the plugin when a gamepad presses Start, and the plugin then assigns that gamepad to the new player
+fn spawn_gamepad_player(commands: &mut Commands, _gamepad: Gamepad) -> Entity {
+    commands
+        .spawn((
+            Player("Gamepad"),
+            InputManagerBundle::with_map(InputMap::new([(Action::Jump, GamepadButtonType::South)])),
+        ))
+        .id()
+}
+
+fn announce_players(
+    mut assigned: EventReader<GamepadAssigned>,
+    mut lost: EventReader<PlayerGamepadLost>,
+) {
+    for event in assigned.read() {
+        println!("Gamepad {} joined as {:?}", event.gamepad.id, event.entity);
+    }
+    for event in lost.read() {
+        println!(
+            "Gamepad {} of {:?} was disconnected: press Start on a gamepad to take over",
+            event.gamepad.id, event.entity
+        );
+    }
+}
+
+fn jump(query: Query<(&ActionState<Action>, &Player)>) {
+    for (action_state, player) in query.iter() {
+        if action_state.just_pressed(&Action::Jump) {
+            println!("{} player jumped!", player.0);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Demonstrates how to switch between the controls of a character and a vehicle, using an `InputMapStack`
+//!
+//! Press E to enter the vehicle, and E again to leave it.
+//! W walks on foot, and accelerates in the vehicle.
+
+use bevy::prelude::*;
+use leafwing_input_manager::input_map_stack::InputMapStack;
+use leafwing_input_manager::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player)
+        .add_systems(Update, (enter_or_leave_vehicle, report_movement))
+        .run()
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+enum Action {
+    Walk,
+    Jump,
+    Accelerate,
+    Brake,
+    Interact,
+}
+
+fn spawn_player(mut commands: Commands) {
+    // The stack inserts the `InputMap` of the player, using the map on top of the stack
+    commands.spawn((
+        ActionState::<Action>::default(),
+        InputMapStack::new(InputMap::new([
+            (Action::Walk, KeyCode::W),
+            (Action::Jump, KeyCode::Space),
+            (Action::Interact, KeyCode::E),
+        ])),
+    ));
+}
+
+fn enter_or_leave_vehicle(mut query: Query<(&ActionState<Action>, &mut InputMapStack<Action>)>) {
+    let (action_state, mut stack) = query.single_mut();
+    if !action_state.just_pressed(&Action::Interact) {
+        return;
+    }
+
+    if stack.pop().is_some() {
+        println!("Left the vehicle");
+    } else {
+        // E is also bound here, so the press that entered the vehicle does not immediately leave it
+        stack.push(InputMap::new([
+            (Action::Accelerate, KeyCode::W),
+            (Action::Brake, KeyCode::S),
+            (Action::Interact, KeyCode::E),
+        ]));
+        println!("Entered the vehicle");
+    }
+}
+
+fn report_movement(query: Query<&ActionState<Action>>) {
+    let action_state = query.single();
+    for action in [
+        Action::Walk,
+        Action::Jump,
+        Action::Accelerate,
+        Action::Brake,
+    ] {
+        if action_state.just_pressed(&action) {
+            println!("Started {action:?}");
+        }
+        if action_state.just_released(&action) {
+            println!("Stopped {action:?}");
+        }
+    }
+}
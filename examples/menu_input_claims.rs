@@ -0,0 +1,83 @@
+//! Demonstrates how to stop an open menu from leaking keys into gameplay, when both use the same keys
+//!
+//! Press Tab to open the inventory, and Escape to close it again.
+//! While the inventory is closed, Escape pauses the game instead.
+
+use bevy::prelude::*;
+use leafwing_input_manager::plugin::UpdateActionStateSet;
+use leafwing_input_manager::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Inputs used by the inventory are claimed with a higher priority,
+        // so they are hidden from the gameplay actions
+        .add_plugins(InputManagerPlugin::<InventoryAction>::default().claim_inputs(1))
+        .add_plugins(InputManagerPlugin::<GameAction>::default().claim_inputs(0))
+        // Claims are only seen by the action types that are updated afterwards
+        .configure_sets(
+            PreUpdate,
+            UpdateActionStateSet::<InventoryAction>::default()
+                .before(UpdateActionStateSet::<GameAction>::default()),
+        )
+        // The inventory starts closed
+        .insert_resource(ToggleActions::<InventoryAction>::DISABLED)
+        .add_systems(Startup, spawn_player)
+        .add_systems(Update, (play, browse_inventory))
+        .run()
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+enum GameAction {
+    Pause,
+    OpenInventory,
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+enum InventoryAction {
+    Close,
+}
+
+#[derive(Component)]
+struct Player;
+
+fn spawn_player(mut commands: Commands) {
+    commands.spawn((
+        Player,
+        InputManagerBundle::with_map(InputMap::new([
+            (GameAction::Pause, KeyCode::Escape),
+            (GameAction::OpenInventory, KeyCode::Tab),
+        ])),
+        // Both maps bind Escape
+        InputManagerBundle::with_map(InputMap::new([(InventoryAction::Close, KeyCode::Escape)])),
+    ));
+}
+
+fn play(
+    query: Query<&ActionState<GameAction>, With<Player>>,
+    mut inventory: ResMut<ToggleActions<InventoryAction>>,
+) {
+    let action_state = query.single();
+
+    if action_state.just_pressed(&GameAction::Pause) {
+        println!("The game is paused.");
+    }
+
+    if action_state.just_pressed(&GameAction::OpenInventory) && !inventory.enabled {
+        inventory.enabled = true;
+        println!("The inventory is now open.");
+    }
+}
+
+fn browse_inventory(
+    query: Query<&ActionState<InventoryAction>, With<Player>>,
+    mut inventory: ResMut<ToggleActions<InventoryAction>>,
+) {
+    let action_state = query.single();
+
+    // Escape is claimed by the inventory, so the game is not paused as well
+    if action_state.just_pressed(&InventoryAction::Close) {
+        inventory.enabled = false;
+        println!("The inventory is now closed.");
+    }
+}
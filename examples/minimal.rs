@@ -26,12 +26,12 @@ struct Player;
 
 fn spawn_player(mut commands: Commands) {
     commands
-        .spawn(InputManagerBundle::<Action> {
-            // Stores "which actions are currently pressed"
-            action_state: ActionState::default(),
-            // Describes how to convert from player inputs into those actions
-            input_map: InputMap::new([(Action::Jump, KeyCode::Space)]),
-        })
+        // The bundle stores "which actions are currently pressed" in an `ActionState`,
+        // and describes how to convert from player inputs into those actions with an `InputMap`
+        .spawn(InputManagerBundle::with_map(InputMap::new([(
+            Action::Jump,
+            KeyCode::Space,
+        )])))
         .insert(Player);
 }
 
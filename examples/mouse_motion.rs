@@ -18,14 +18,13 @@ enum CameraMovement {
 fn setup(mut commands: Commands) {
     commands
         .spawn(Camera2dBundle::default())
-        .insert(InputManagerBundle::<CameraMovement> {
-            input_map: InputMap::default()
+        .insert(InputManagerBundle::with_map(
+            InputMap::default()
                 // This will capture the total continuous value, for direct use.
                 // Note that you can also use discrete gesture-like motion, via the `MouseMotionDirection` enum.
                 .insert(CameraMovement::Pan, DualAxis::mouse_motion())
                 .build(),
-            ..default()
-        });
+        ));
 
     commands.spawn(SpriteBundle {
         transform: Transform::from_scale(Vec3::new(100., 100., 1.)),
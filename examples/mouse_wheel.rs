@@ -21,8 +21,8 @@ enum CameraMovement {
 fn setup(mut commands: Commands) {
     commands
         .spawn(Camera2dBundle::default())
-        .insert(InputManagerBundle::<CameraMovement> {
-            input_map: InputMap::default()
+        .insert(InputManagerBundle::with_map(
+            InputMap::default()
                 // This will capture the total continuous value, for direct use.
                 .insert(CameraMovement::Zoom, SingleAxis::mouse_wheel_y())
                 // This will return a binary button-like output.
@@ -34,8 +34,7 @@ fn setup(mut commands: Commands) {
                 // Or even a continuous `DualAxis`!
                 // .insert(DualAxis::mouse_wheel(), Pan)
                 .build(),
-            ..default()
-        });
+        ));
 
     commands.spawn(SpriteBundle {
         transform: Transform::from_scale(Vec3::new(100., 100., 1.)),
@@ -0,0 +1,106 @@
+//! Orbits around a cube while the right mouse button is held
+//!
+//! Moving the mouse without holding the button does nothing,
+//! and drags that start over the UI panel in the corner are ignored.
+//! The cube is drawn with a sprite for each of its faces, and turned as if the camera orbited around it.
+
+use bevy::prelude::*;
+use leafwing_input_manager::mouse_gestures::DragAxis;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::ui_blocking::{BlocksGameInput, UiBlockingPlugin};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            InputManagerPlugin::<CameraAction>::default(),
+            UiBlockingPlugin,
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, orbit)
+        .run();
+}
+
+#[derive(Actionlike, Clone, Debug, Copy, PartialEq, Eq, Hash, Reflect)]
+enum CameraAction {
+    Orbit,
+}
+
+#[derive(Component)]
+struct Cube;
+
+/// The length of the edges of the cube, in pixels
+const CUBE_SIZE: f32 = 200.0;
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera2dBundle::default())
+        .insert(InputManagerBundle::with_map(InputMap::new([(
+            CameraAction::Orbit,
+            // Radians per pixel of mouse motion
+            DragAxis::new(MouseButton::Right).with_sensitivity(0.01),
+        )])));
+
+    // The normal, orientation and color of each face
+    let faces = [
+        (Vec3::Z, Quat::IDENTITY, Color::TEAL),
+        (Vec3::NEG_Z, Quat::from_rotation_y(PI), Color::ORANGE),
+        (Vec3::X, Quat::from_rotation_y(FRAC_PI_2), Color::CRIMSON),
+        (Vec3::NEG_X, Quat::from_rotation_y(-FRAC_PI_2), Color::GOLD),
+        (
+            Vec3::Y,
+            Quat::from_rotation_x(-FRAC_PI_2),
+            Color::LIME_GREEN,
+        ),
+        (Vec3::NEG_Y, Quat::from_rotation_x(FRAC_PI_2), Color::PURPLE),
+    ];
+    commands
+        .spawn((SpatialBundle::default(), Cube))
+        .with_children(|parent| {
+            for (normal, rotation, color) in faces {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(Vec2::splat(CUBE_SIZE)),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(normal * CUBE_SIZE / 2.0)
+                        .with_rotation(rotation),
+                    ..default()
+                });
+            }
+        });
+
+    // Dragging from this panel is ignored
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Px(200.0),
+                height: Val::Px(150.0),
+                ..default()
+            },
+            background_color: Color::rgba(0.1, 0.1, 0.1, 0.8).into(),
+            ..default()
+        },
+        BlocksGameInput,
+    ));
+}
+
+fn orbit(
+    camera: Query<&ActionState<CameraAction>, With<Camera>>,
+    mut cube: Query<&mut Transform, With<Cube>>,
+) {
+    let action_state = camera.single();
+    let mut cube_transform = cube.single_mut();
+
+    // The axis pair is `None` while the right mouse button isn't held
+    let Some(orbit) = action_state.axis_pair(&CameraAction::Orbit) else {
+        return;
+    };
+
+    // Orbiting to the right turns the cube to the left, and mouse motion is positive downwards
+    let yaw = Quat::from_rotation_y(orbit.x());
+    let pitch = Quat::from_rotation_x(orbit.y());
+    cube_transform.rotation = yaw * pitch * cube_transform.rotation;
+}
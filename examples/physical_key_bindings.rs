@@ -39,20 +39,18 @@ struct Player;
 
 fn spawn_player(mut commands: Commands) {
     commands
-        .spawn(InputManagerBundle::<Action> {
-            // Stores "which actions are currently pressed"
-            action_state: ActionState::default(),
+        .spawn(InputManagerBundle::with_map(
             // We define the name of the keys based on the US QWERTY layout.
             // The keys the user will actually have to press depends on their selected keyboard layout.
             // However, the _position_ of the keys will be the same, regardless of layout.
             // This way, every player can use the classic triangle shaped key arrangement.
-            input_map: InputMap::new([
+            InputMap::new([
                 (Action::Forward, QwertyScanCode::W),
                 (Action::Left, QwertyScanCode::A),
                 (Action::Backward, QwertyScanCode::S),
                 (Action::Right, QwertyScanCode::D),
             ]),
-        })
+        ))
         .insert(Player);
 }
 
@@ -45,15 +45,14 @@ fn join(
                 println!("Player {} has joined the game!", gamepad.id);
 
                 let player = commands
-                    .spawn(InputManagerBundle::<Action> {
-                        action_state: ActionState::default(),
-                        input_map: InputMap::default()
+                    .spawn(InputManagerBundle::with_map(
+                        InputMap::default()
                             .insert(Action::Jump, GamepadButtonType::South)
                             .insert(Action::Disconnect, GamepadButtonType::Select)
                             // Make sure to set the gamepad or all gamepads will be used!
                             .set_gamepad(gamepad)
                             .build(),
-                    })
+                    ))
                     .insert(Player { gamepad })
                     .id();
 
@@ -0,0 +1,54 @@
+//! Rumbles the gamepad each time the player fires, using the `rumble` feature
+//!
+//! Run with `cargo run --example rumble --features rumble`, and fire with the right trigger or the space bar.
+
+use bevy::input::gamepad::GamepadRumbleIntensity;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::rumble::{ActionRumble, ActionRumbleEffect};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player)
+        .add_systems(Update, fire)
+        .run()
+}
+
+#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
+enum Action {
+    Fire,
+}
+
+#[derive(Component)]
+struct Player;
+
+fn spawn_player(mut commands: Commands) {
+    let mut input_map = InputMap::new([(Action::Fire, KeyCode::Space)]);
+    input_map.insert(Action::Fire, GamepadButtonType::RightTrigger2);
+
+    // Each shot is a short, strong pulse
+    let mut action_rumble = ActionRumble::default();
+    action_rumble.insert(
+        Action::Fire,
+        ActionRumbleEffect::on_just_pressed(
+            GamepadRumbleIntensity::STRONG_MAX,
+            Duration::from_millis(150),
+        ),
+    );
+
+    commands.spawn((
+        InputManagerBundle::with_map(input_map),
+        action_rumble,
+        Player,
+    ));
+}
+
+fn fire(query: Query<&ActionState<Action>, With<Player>>) {
+    let action_state = query.single();
+    if action_state.just_pressed(&Action::Fire) {
+        println!("Bang!");
+    }
+}
@@ -0,0 +1,127 @@
+//! [`Entity`] ids are local to a single [`World`], so they can't be used to identify players across the network.
+//!
+//! Instead, [`ActionDiffEvent`]s can be tagged with any serializable id, such as a `u64` assigned by your protocol.
+//! The client marks its players with a [`DiffOwner`] component,
+//! and the server maps the ids back to its own entities using a [`DiffOwnerMap`] resource.
+
+use bevy::ecs::event::{Events, ManualEventReader};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::action_diff::{ActionDiffEvent, DiffFrame, DiffOwner, DiffOwnerMap};
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::systems::{apply_action_diffs_by_id, generate_action_diffs_by_id};
+use serde::{Deserialize, Serialize};
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect, Serialize, Deserialize)]
+enum FpsAction {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Shoot,
+}
+
+/// The id that our networking protocol uses for the only player in this example
+const PLAYER_ID: u64 = 42;
+
+fn main() {
+    // In a real use case, these apps would be running on separate devices.
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((
+            MinimalPlugins,
+            InputPlugin,
+            InputManagerPlugin::<FpsAction>::default(),
+        ))
+        // Creates an event stream of `ActionDiffs`, tagged with the `DiffOwner` id of each player
+        .add_event::<ActionDiffEvent<FpsAction, u64>>()
+        .add_systems(PostUpdate, generate_action_diffs_by_id::<FpsAction, u64>)
+        .add_systems(Startup, spawn_client_player);
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((MinimalPlugins, InputManagerPlugin::<FpsAction>::server()))
+        .add_event::<ActionDiffEvent<FpsAction, u64>>()
+        .init_resource::<DiffOwnerMap<u64>>()
+        // Looks up the entity of each player id in the `DiffOwnerMap`, then applies the diffs to its `ActionState`
+        .add_systems(PreUpdate, apply_action_diffs_by_id::<FpsAction, u64>)
+        .add_systems(Startup, spawn_server_player);
+
+    // Starting up the game
+    client_app.update();
+    server_app.update();
+
+    // Sending inputs to the client
+    client_app.send_input(KeyCode::Space);
+    client_app.send_input(MouseButton::Left);
+    client_app.update();
+
+    // The diffs are serialized, sent over the "network", and deserialized on the server
+    let mut reader = ManualEventReader::default();
+    send_frame(&client_app, &mut server_app, &mut reader);
+    server_app.update();
+
+    // The actions are now pressed on the server's entity for this player
+    let player_state = server_player_state(&server_app);
+    assert!(player_state.pressed(&FpsAction::Jump));
+    assert!(player_state.pressed(&FpsAction::Shoot));
+
+    // Releasing the inputs works the same way
+    client_app.reset_inputs();
+    client_app.update();
+    send_frame(&client_app, &mut server_app, &mut reader);
+    server_app.update();
+
+    let player_state = server_player_state(&server_app);
+    assert!(player_state.released(&FpsAction::Jump));
+    assert!(player_state.released(&FpsAction::Shoot));
+}
+
+fn spawn_client_player(mut commands: Commands) {
+    use FpsAction::*;
+    use KeyCode::*;
+
+    commands.spawn((
+        InputManagerBundle::with_map(
+            InputMap::new([(MoveLeft, W), (MoveRight, D), (Jump, Space)])
+                .insert(Shoot, MouseButton::Left)
+                .build(),
+        ),
+        DiffOwner(PLAYER_ID),
+    ));
+}
+
+fn spawn_server_player(mut commands: Commands, mut owner_map: ResMut<DiffOwnerMap<u64>>) {
+    // The server's entity for this player is unrelated to the client's entity
+    let entity = commands.spawn(ActionState::<FpsAction>::default()).id();
+    owner_map.insert(PLAYER_ID, entity);
+}
+
+fn server_player_state(server_app: &App) -> &ActionState<FpsAction> {
+    let entity = server_app
+        .world
+        .resource::<DiffOwnerMap<u64>>()
+        .get(&PLAYER_ID)
+        .unwrap();
+    server_app
+        .world
+        .get::<ActionState<FpsAction>>(entity)
+        .unwrap()
+}
+
+/// A simple mock network interface that encodes the client's new events as a single [`DiffFrame`],
+/// then decodes them on the server
+fn send_frame(
+    client_app: &App,
+    server_app: &mut App,
+    reader: &mut ManualEventReader<ActionDiffEvent<FpsAction, u64>>,
+) {
+    let client_events: &Events<ActionDiffEvent<FpsAction, u64>> = client_app.world.resource();
+    let frame = DiffFrame::new(reader.read(client_events).cloned().collect());
+    let bytes = frame.encode();
+    println!("Sending {} bytes: {:?}", bytes.len(), frame.events);
+
+    let frame = DiffFrame::<FpsAction, u64>::decode(&bytes).unwrap();
+    let mut server_events: Mut<Events<ActionDiffEvent<FpsAction, u64>>> =
+        server_app.world.resource_mut();
+    server_events.extend(frame.events);
+}
@@ -27,25 +27,6 @@ enum FpsAction {
 #[derive(Component, Clone, PartialEq, Eq, Hash, Debug)]
 struct StableId(u64);
 
-/// Processes an [`Events`] stream of [`ActionDiff`] to update an [`ActionState`]
-///
-/// In a real scenario, you would have to map the entities between the server and client world.
-/// In this case, we will just use the fact that there is only a single entity.
-fn process_action_diffs<A: Actionlike>(
-    mut action_state_query: Query<&mut ActionState<A>>,
-    mut action_diff_events: EventReader<ActionDiffEvent<A>>,
-) {
-    for action_diff_event in action_diff_events.read() {
-        if action_diff_event.owner.is_some() {
-            let mut action_state = action_state_query.get_single_mut().unwrap();
-            action_diff_event
-                .action_diffs
-                .iter()
-                .for_each(|diff| action_state.apply_diff(diff));
-        }
-    }
-}
-
 fn main() {
     // In a real use case, these apps would be running on separate devices.
     let mut client_app = App::new();
@@ -62,10 +43,10 @@ fn main() {
     let mut server_app = App::new();
     server_app
         .add_plugins(MinimalPlugins)
-        .add_plugins(InputManagerPlugin::<FpsAction>::server())
-        .add_event::<ActionDiffEvent<FpsAction>>()
         // Reads in the event stream of `ActionDiffs` to update the `ActionState`
-        .add_systems(PreUpdate, process_action_diffs::<FpsAction>)
+        // In a real scenario, you would have to map the entities between the server and client world.
+        // In this case, we will just use the fact that the player is spawned with the same id in both worlds.
+        .add_plugins(InputManagerPlugin::<FpsAction>::server().apply_action_diffs(true))
         // Typically, the rest of this information would synchronized as well
         .add_systems(Startup, spawn_player);
 
@@ -126,12 +107,11 @@ fn spawn_player(mut commands: Commands) {
     use KeyCode::*;
 
     commands
-        .spawn(InputManagerBundle {
-            input_map: InputMap::new([(MoveLeft, W), (MoveRight, D), (Jump, Space)])
+        .spawn(InputManagerBundle::with_map(
+            InputMap::new([(MoveLeft, W), (MoveRight, D), (Jump, Space)])
                 .insert(Shoot, MouseButton::Left)
                 .build(),
-            ..default()
-        })
+        ))
         .insert(Player);
 }
 
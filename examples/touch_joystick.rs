@@ -0,0 +1,84 @@
+//! Mobile-style twin-stick controls, using virtual joysticks on the touch screen
+//!
+//! Touch the left half of the screen and drag to move, and touch its right half and drag to aim.
+//! Each joystick follows the finger that anchored it, so both can be used at the same time.
+
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::touch::TouchJoystick;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_player, aim_player))
+        .run();
+}
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+enum Action {
+    Move,
+    Aim,
+}
+
+#[derive(Component)]
+struct Player;
+
+impl Player {
+    /// How fast the player moves, in pixels per second
+    const SPEED: f32 = 300.0;
+    /// How far the finger moves to fully push a joystick, in logical pixels
+    const JOYSTICK_RADIUS: f32 = 75.0;
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    // Regions are in normalized coordinates, from the top left corner of the window to its bottom right corner
+    let input_map = InputMap::new([
+        (
+            Action::Move,
+            TouchJoystick::new(Rect::new(0.0, 0.0, 0.5, 1.0), Player::JOYSTICK_RADIUS),
+        ),
+        (
+            Action::Aim,
+            TouchJoystick::new(Rect::new(0.5, 0.0, 1.0, 1.0), Player::JOYSTICK_RADIUS),
+        ),
+    ])
+    // Also play with a gamepad, for testing on the desktop
+    .insert(Action::Move, DualAxis::left_stick())
+    .insert(Action::Aim, DualAxis::right_stick())
+    .build();
+
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: Color::TEAL,
+                custom_size: Some(Vec2::new(60.0, 30.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(InputManagerBundle::with_map(input_map))
+        .insert(Player);
+}
+
+fn move_player(
+    time: Res<Time>,
+    mut query: Query<(&ActionState<Action>, &mut Transform), With<Player>>,
+) {
+    let (action_state, mut transform) = query.single_mut();
+    if let Some(axis_pair) = action_state.axis_pair(&Action::Move) {
+        // The joystick is pushed further the further the finger moves from where it touched the screen
+        transform.translation += axis_pair.xy().extend(0.0) * Player::SPEED * time.delta_seconds();
+    }
+}
+
+fn aim_player(mut query: Query<(&ActionState<Action>, &mut Transform), With<Player>>) {
+    let (action_state, mut transform) = query.single_mut();
+    if action_state.pressed(&Action::Aim) {
+        let direction = action_state.axis_pair(&Action::Aim).unwrap().xy();
+        transform.rotation = Quat::from_rotation_z(direction.y.atan2(direction.x));
+    }
+}
@@ -40,10 +40,7 @@ fn spawn_player(mut commands: Commands) {
             },
             ..Default::default()
         })
-        .insert(InputManagerBundle::<Action> {
-            input_map,
-            ..Default::default()
-        })
+        .insert_input_manager(input_map)
         .insert(Player);
 }
 
@@ -24,21 +24,21 @@ struct Player;
 
 fn spawn_player(mut commands: Commands) {
     commands
-        .spawn(InputManagerBundle::<Action> {
+        .spawn(InputManagerBundle::with_map(
             // Stores "which actions are currently activated"
             // Map some arbitrary keys into a virtual direction pad that triggers our move action
-            input_map: InputMap::new([(
+            InputMap::new([(
                 Action::Move,
                 VirtualDPad {
                     up: KeyCode::W.into(),
                     down: KeyCode::S.into(),
                     left: KeyCode::A.into(),
                     right: KeyCode::D.into(),
+                    normalize_diagonals: false,
                 },
             )])
             .build(),
-            ..default()
-        })
+        ))
         .insert(Player);
 }
 
@@ -0,0 +1,222 @@
+//! Accessibility options that change how inputs press actions, for players who can't hold buttons or tap them quickly.
+//!
+//! The options are stored in the [`AccessibilitySettings`] resource, separately from the [`InputMap`](crate::input_map::InputMap),
+//! so that they can be saved and loaded with the rest of the player's settings.
+
+use crate::{action_state::ActionData, buttonlike::ButtonState, Actionlike};
+
+use bevy::{
+    ecs::{entity::Entity, event::Event, system::Resource},
+    utils::{Duration, HashMap, Instant},
+};
+use serde::{Deserialize, Serialize};
+
+/// How the inputs of a single action are transformed before they reach its [`ActionState`](crate::action_state::ActionState)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AccessibilityTransform {
+    /// Each press of the inputs toggles the action between pressed and released
+    ///
+    /// Consuming the action toggles it off.
+    HoldToToggle,
+    /// Each press of the inputs holds the action for at least the given duration, even if the inputs are released sooner
+    ///
+    /// Consuming the action cancels the rest of the hold.
+    TapToHoldFor(Duration),
+    /// Two presses of the inputs count as a double tap if the second one is within the given duration of the first,
+    /// sending an [`ActionDoubleTapEvent`]
+    ///
+    /// The action itself is pressed and released as usual.
+    SlowedDoubleTapWindow(Duration),
+}
+
+/// The [`AccessibilityTransform`] of each action
+///
+/// This is read by [`update_action_state`](crate::systems::update_action_state),
+/// which transforms the inputs read from the [`InputMap`](crate::input_map::InputMap) before updating the [`ActionState`](crate::action_state::ActionState).
+/// Systems that read the [`ActionState`](crate::action_state::ActionState) only ever see the transformed presses,
+/// and the [`InputMap`](crate::input_map::InputMap) is left untouched.
+/// Actions that are not in this map are unaffected.
+///
+/// This resource is inserted by [`InputManagerPlugin::with_accessibility`](crate::plugin::InputManagerPlugin::with_accessibility),
+/// and can be replaced at runtime, such as when the player changes their settings.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::Duration;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::accessibility::{AccessibilitySettings, AccessibilityTransform};
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Aim,
+///     Dash,
+/// }
+///
+/// let mut settings = AccessibilitySettings::default();
+/// settings
+///     .insert(Action::Aim, AccessibilityTransform::HoldToToggle)
+///     .insert(
+///         Action::Dash,
+///         AccessibilityTransform::SlowedDoubleTapWindow(Duration::from_millis(800)),
+///     );
+///
+/// assert_eq!(settings.get(&Action::Aim), Some(AccessibilityTransform::HoldToToggle));
+/// ```
+#[derive(Resource, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessibilitySettings<A: Actionlike> {
+    transforms: HashMap<A, AccessibilityTransform>,
+}
+
+impl<A: Actionlike> Default for AccessibilitySettings<A> {
+    fn default() -> Self {
+        Self {
+            transforms: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> AccessibilitySettings<A> {
+    /// Sets the [`AccessibilityTransform`] of the `action`, replacing any previous one
+    pub fn insert(&mut self, action: A, transform: AccessibilityTransform) -> &mut Self {
+        self.transforms.insert(action, transform);
+        self
+    }
+
+    /// Removes the [`AccessibilityTransform`] of the `action`, so that its inputs press it as usual
+    pub fn remove(&mut self, action: &A) -> &mut Self {
+        self.transforms.remove(action);
+        self
+    }
+
+    /// The [`AccessibilityTransform`] of the `action`, if it has one
+    #[must_use]
+    pub fn get(&self, action: &A) -> Option<AccessibilityTransform> {
+        self.transforms.get(action).copied()
+    }
+
+    /// Is this map empty?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+}
+
+/// Sent when an action with an [`AccessibilityTransform::SlowedDoubleTapWindow`] is double tapped
+///
+/// These are sent by [`update_action_state`](crate::systems::update_action_state).
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct ActionDoubleTapEvent<A: Actionlike> {
+    /// The action that was double tapped
+    pub action: A,
+    /// The entity whose [`ActionState`](crate::action_state::ActionState) holds the action,
+    /// or `None` if the `ActionState` is a resource
+    pub entity: Option<Entity>,
+}
+
+/// What the accessibility layer remembers about a single action between frames
+#[derive(Clone, Copy, Debug, Default)]
+struct TransformState {
+    /// Were the inputs of the action pressed last frame?
+    input_pressed: bool,
+    /// Is the action toggled on, for [`AccessibilityTransform::HoldToToggle`]
+    toggled: bool,
+    /// When the current tap stops holding the action, for [`AccessibilityTransform::TapToHoldFor`]
+    held_until: Option<Instant>,
+    /// When the first tap of a potential double tap happened, for [`AccessibilityTransform::SlowedDoubleTapWindow`]
+    first_tap: Option<Instant>,
+}
+
+/// Applies the [`AccessibilitySettings`] to the actions of a single [`ActionState`](crate::action_state::ActionState)
+///
+/// This is stored by [`update_action_state`](crate::systems::update_action_state) for each [`ActionState`](crate::action_state::ActionState), to remember toggles and taps between frames.
+#[derive(Clone, Debug)]
+pub struct AccessibilityLayer<A: Actionlike> {
+    states: HashMap<A, TransformState>,
+}
+
+impl<A: Actionlike> Default for AccessibilityLayer<A> {
+    fn default() -> Self {
+        Self {
+            states: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> AccessibilityLayer<A> {
+    /// Transforms the `action_data` read from the inputs, according to the `settings`
+    ///
+    /// `consumed` tells whether each action is currently consumed, which cancels toggles and holds.
+    /// Returns the actions that were double tapped.
+    pub(crate) fn apply(
+        &mut self,
+        settings: &AccessibilitySettings<A>,
+        action_data: &mut HashMap<A, ActionData>,
+        consumed: impl Fn(&A) -> bool,
+        now: Instant,
+    ) -> Vec<A> {
+        self.states
+            .retain(|action, _| settings.transforms.contains_key(action));
+
+        let mut double_taps = Vec::new();
+        for (action, transform) in settings.transforms.iter() {
+            let state = self.states.entry(action.clone()).or_default();
+            let input_pressed = action_data
+                .get(action)
+                .is_some_and(|action_datum| action_datum.state.pressed());
+            let input_just_pressed = input_pressed && !state.input_pressed;
+            state.input_pressed = input_pressed;
+
+            let pressed = match *transform {
+                AccessibilityTransform::HoldToToggle => {
+                    if consumed(action) {
+                        state.toggled = false;
+                    }
+                    if input_just_pressed {
+                        state.toggled = !state.toggled;
+                    }
+                    state.toggled
+                }
+                AccessibilityTransform::TapToHoldFor(duration) => {
+                    if consumed(action) {
+                        state.held_until = None;
+                    }
+                    if input_just_pressed {
+                        state.held_until = Some(now + duration);
+                    }
+                    if state.held_until.is_some_and(|held_until| held_until <= now) {
+                        state.held_until = None;
+                    }
+                    input_pressed || state.held_until.is_some()
+                }
+                AccessibilityTransform::SlowedDoubleTapWindow(window) => {
+                    if input_just_pressed {
+                        match state.first_tap {
+                            Some(first_tap)
+                                if now.saturating_duration_since(first_tap) <= window =>
+                            {
+                                state.first_tap = None;
+                                double_taps.push(action.clone());
+                            }
+                            _ => state.first_tap = Some(now),
+                        }
+                    }
+                    input_pressed
+                }
+            };
+
+            if pressed != input_pressed {
+                let action_datum = action_data.entry(action.clone()).or_default();
+                if pressed {
+                    action_datum.state = ButtonState::JustPressed;
+                    action_datum.value = 1.0;
+                } else {
+                    action_datum.state = ButtonState::Released;
+                    action_datum.value = 0.0;
+                }
+            }
+        }
+
+        double_taps
+    }
+}
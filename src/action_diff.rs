@@ -5,11 +5,20 @@
 //! about things like keybindings or input devices.
 
 use bevy::{
-    ecs::{entity::Entity, event::Event},
+    ecs::{component::Component, entity::Entity, event::Event, system::Resource},
     math::Vec2,
+    reflect::ReflectRef,
+    utils::HashMap,
 };
+#[cfg(feature = "diff_stats")]
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::hash::Hash;
 
+#[cfg(feature = "diff_stats")]
+use crate::errors::DiffDecodingError;
 use crate::Actionlike;
 
 /// Stores presses and releases of buttons without timing information
@@ -36,6 +45,16 @@ pub enum ActionDiff<A: Actionlike> {
         action: A,
         /// The new value of the action
         value: f32,
+        /// Is the action pressed?
+        ///
+        /// Analog actions can have a non-zero value while released,
+        /// such as a throttle that sits below its press threshold.
+        /// If this is `false`, applying the diff updates the value without pressing the action.
+        ///
+        /// Self-describing formats treat diffs serialized before this field existed as pressed.
+        /// Other formats must read them as a [`LegacyActionDiff`].
+        #[serde(default = "pressed_by_default")]
+        pressed: bool,
     },
     /// The axis pair of the action changed
     AxisPairChanged {
@@ -43,18 +62,911 @@ pub enum ActionDiff<A: Actionlike> {
         action: A,
         /// The new value of the axis
         axis_pair: Vec2,
+        /// Is the action pressed?
+        ///
+        /// Like [`ActionDiff::ValueChanged`], axis pairs can change while their action is released,
+        /// such as a stick moving within its deadzone.
+        ///
+        /// Self-describing formats treat diffs serialized before this field existed as pressed.
+        /// Other formats must read them as a [`LegacyActionDiff`].
+        #[serde(default = "pressed_by_default")]
+        pressed: bool,
     },
 }
 
-/// Will store an `ActionDiff` as well as what generated it (either an Entity, or nothing if the
+/// Used to deserialize [`ActionDiff::ValueChanged`] and [`ActionDiff::AxisPairChanged`] diffs that were serialized without a `pressed` field
+fn pressed_by_default() -> bool {
+    true
+}
+
+/// The layout of [`ActionDiff`] before [`ActionDiff::ValueChanged`] and [`ActionDiff::AxisPairChanged`] had a `pressed` field
+///
+/// Self-describing formats such as JSON fill in the missing field when reading old diffs as an [`ActionDiff`],
+/// but formats that rely on the layout of the type, such as `bincode`, can't.
+/// Deserialize the diffs stored in these formats as a [`LegacyActionDiff`] instead, then convert them into an [`ActionDiff`]:
+/// their changed values and axis pairs are treated as pressed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum LegacyActionDiff<A: Actionlike> {
+    /// The action was pressed
+    Pressed {
+        /// The value of the action
+        action: A,
+    },
+    /// The action was released
+    Released {
+        /// The value of the action
+        action: A,
+    },
+    /// The value of the action changed
+    ValueChanged {
+        /// The value of the action
+        action: A,
+        /// The new value of the action
+        value: f32,
+    },
+    /// The axis pair of the action changed
+    AxisPairChanged {
+        /// The value of the action
+        action: A,
+        /// The new value of the axis
+        axis_pair: Vec2,
+    },
+}
+
+impl<A: Actionlike> From<LegacyActionDiff<A>> for ActionDiff<A> {
+    fn from(legacy_diff: LegacyActionDiff<A>) -> Self {
+        match legacy_diff {
+            LegacyActionDiff::Pressed { action } => ActionDiff::Pressed { action },
+            LegacyActionDiff::Released { action } => ActionDiff::Released { action },
+            LegacyActionDiff::ValueChanged { action, value } => ActionDiff::ValueChanged {
+                action,
+                value,
+                pressed: true,
+            },
+            LegacyActionDiff::AxisPairChanged { action, axis_pair } => {
+                ActionDiff::AxisPairChanged {
+                    action,
+                    axis_pair,
+                    pressed: true,
+                }
+            }
+        }
+    }
+}
+
+impl<A: Actionlike> ActionDiff<A> {
+    /// Returns the action that this diff applies to
+    #[must_use]
+    pub fn action(&self) -> &A {
+        match self {
+            ActionDiff::Pressed { action }
+            | ActionDiff::Released { action }
+            | ActionDiff::ValueChanged { action, .. }
+            | ActionDiff::AxisPairChanged { action, .. } => action,
+        }
+    }
+}
+
+/// Sorts the `action_diffs` by the order in which their actions were declared
+///
+/// Diffs are often gathered from a [`HashMap`], whose iteration order is not stable.
+/// Sorting them makes the order (and so the encoded bytes) of equivalent diffs identical,
+/// which is required for checksums and content-addressed storage.
+///
+/// Actions are ordered by their enum variant index.
+/// Variants that hold data are then ordered by their [reflection hash](bevy::reflect::Reflect::reflect_hash),
+/// which is stable for a given build of your game.
+/// The relative order of diffs for the same action is preserved.
+///
+/// This order is only deterministic for enum actions whose variants are distinguished by their index or by their reflection hash.
+/// Actions that are not enums, or data-holding variants without a reflected [`Hash`] implementation, all compare equal,
+/// so they keep the order in which they were gathered.
+pub(crate) fn sort_by_action<A: Actionlike>(action_diffs: &mut [ActionDiff<A>]) {
+    action_diffs.sort_by_cached_key(|action_diff| action_order(action_diff.action()));
+}
+
+/// The key used by [`sort_by_action`] to order actions
+pub(crate) fn action_order<A: Actionlike>(action: &A) -> (usize, Option<u64>) {
+    let variant_index = match action.reflect_ref() {
+        ReflectRef::Enum(action) => action.variant_index(),
+        _ => 0,
+    };
+    (variant_index, action.reflect_hash())
+}
+
+/// An identifier for the owner of an [`ActionState`](crate::action_state::ActionState), used to tag [`ActionDiffEvent`]s
+///
+/// [`Entity`] is used by default, but entities do not survive being sent over the network.
+/// Any type that is stable across the network, such as a `u64` player id, can be used instead:
+/// see [`DiffOwner`] and [`DiffOwnerMap`].
+///
+/// This trait is automatically implemented for all types that meet its bounds.
+pub trait DiffOwnerId: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+impl<T: Clone + Eq + Hash + Debug + Send + Sync + 'static> DiffOwnerId for T {}
+
+/// Will store an `ActionDiff` as well as what generated it (either an owner id, or nothing if the
 /// input actions are represented by a `Resource`)
 ///
+/// By default, owners are identified by their [`Entity`].
+/// Use [`generate_action_diffs_by_id`](crate::systems::generate_action_diffs_by_id) to identify them by their [`DiffOwner`] id instead.
+///
 /// These are typically accessed using the `Events<ActionDiffEvent>` resource.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Event)]
-pub struct ActionDiffEvent<A: Actionlike> {
-    /// If some: the entity that has the `ActionState<A>` component
+pub struct ActionDiffEvent<A: Actionlike, Id: DiffOwnerId = Entity> {
+    /// If some: the id of the owner of the `ActionState<A>` component
     /// If none: `ActionState<A>` is a Resource, not a component
-    pub owner: Option<Entity>,
+    pub owner: Option<Id>,
     /// The `ActionDiff` that was generated
     pub action_diffs: Vec<ActionDiff<A>>,
 }
+
+/// All of the [`ActionDiffEvent`]s generated during a single frame
+///
+/// Batching events together like this allows them to be sent as a single network message.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiffFrame<A: Actionlike, Id: DiffOwnerId = Entity> {
+    /// The events generated during this frame
+    pub events: Vec<ActionDiffEvent<A, Id>>,
+    /// The position of this frame in the stream sent by its sender
+    ///
+    /// Sequence numbers start at 0 and increase by 1 for each frame sent.
+    /// They allow a [`DiffSequencer`] to detect frames that were reordered by the transport.
+    /// Frames without a sequence number are always applied immediately.
+    #[serde(default)]
+    pub sequence: Option<u64>,
+}
+
+impl<A: Actionlike, Id: DiffOwnerId> DiffFrame<A, Id> {
+    /// Creates a new [`DiffFrame`] from the provided `events`, without a sequence number
+    #[must_use]
+    pub fn new(events: Vec<ActionDiffEvent<A, Id>>) -> Self {
+        Self {
+            events,
+            sequence: None,
+        }
+    }
+
+    /// Sets the sequence number of this frame
+    #[must_use]
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        self.sequence = Some(sequence);
+        self
+    }
+
+    /// Does this frame contain any events?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Encodes this frame into a compact binary representation, suitable for sending over the network
+    ///
+    /// Use [`DiffFrame::decode`] to convert the bytes back into a [`DiffFrame`].
+    /// The diffs of each event are sorted by action first, so for enum actions equivalent frames always produce the same bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the serialization implementation of `A` or `Id` returns an error.
+    #[cfg(feature = "diff_stats")]
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8>
+    where
+        A: Serialize,
+        Id: Serialize,
+    {
+        let mut frame = self.clone();
+        for event in frame.events.iter_mut() {
+            sort_by_action(&mut event.action_diffs);
+        }
+        bincode::serialize(&frame).expect("DiffFrames can always be encoded")
+    }
+
+    /// Decodes a frame that was encoded using [`DiffFrame::encode`]
+    #[cfg(feature = "diff_stats")]
+    pub fn decode(bytes: &[u8]) -> Result<Self, DiffDecodingError>
+    where
+        A: DeserializeOwned,
+        Id: DeserializeOwned,
+    {
+        bincode::deserialize(bytes).map_err(|error| DiffDecodingError {
+            reason: error.to_string(),
+        })
+    }
+}
+
+/// How a [`DiffSequencer`] handles [`DiffFrame`]s that arrive out of order
+#[non_exhaustive]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, Default)]
+pub enum FrameOrdering {
+    /// Frames older than the last frame applied for the same owner are dropped
+    ///
+    /// Staleness is tracked per sending tick, so all of the events sent for an owner in the same frame are applied,
+    /// even if they are split across several [`DiffFrame`]s with the same sequence.
+    ///
+    /// This is the default ordering.
+    #[default]
+    DropStale,
+    /// Frames are held back until all earlier frames have arrived, then applied in order
+    ///
+    /// Once a frame `window` positions ahead of a missing frame has arrived,
+    /// the missing frames are given up on and the buffered frames are applied.
+    ///
+    /// Streams start at sequence number 0.
+    /// Receivers that join a stream in progress wait until `window` frames have arrived,
+    /// then start at the oldest frame they received.
+    Reorder {
+        /// How far ahead of a missing frame the sequencer is willing to buffer
+        window: u64,
+    },
+}
+
+/// Restores the order of [`DiffFrame`]s sent through transports that may reorder them
+///
+/// Applying an old [`ActionDiff::Released`] after a newer [`ActionDiff::Pressed`] would corrupt the mirrored [`ActionState`](crate::action_state::ActionState),
+/// so frames are ordered using their [`DiffFrame::sequence`].
+/// With [`FrameOrdering::DropStale`], frames are only dropped if a newer frame was applied for the same owner.
+/// With [`FrameOrdering::Reorder`], gaps are tracked across the whole stream,
+/// so owners that don't send diffs in every frame don't hold back the others.
+///
+/// Sequence numbers are only comparable within the stream of a single sender,
+/// so use a separate [`DiffSequencer`] for each sender.
+///
+/// When using the `diff_transport` feature, insert this as a resource to configure `receive_diffs_via`
+/// and read its diagnostic counters.
+#[derive(Resource, Debug)]
+pub struct DiffSequencer<A: Actionlike, Id: DiffOwnerId = Entity> {
+    /// How frames that arrive out of order are handled
+    pub ordering: FrameOrdering,
+    /// The number of events that were discarded because a newer frame had already been applied for their owner
+    pub dropped: u64,
+    /// The number of events that arrived after a newer frame, but were still applied in order
+    pub reordered: u64,
+    /// The sequence of the newest frame applied for each owner, used by [`FrameOrdering::DropStale`]
+    last_applied: HashMap<Option<Id>, u64>,
+    /// The sequence of the newest frame received
+    newest_received: Option<u64>,
+    /// The sequence of the next frame in the stream, used by [`FrameOrdering::Reorder`]
+    ///
+    /// This is [`None`] until the start of the stream is known.
+    next_sequence: Option<u64>,
+    /// The frames that are waiting for earlier frames to arrive, used by [`FrameOrdering::Reorder`]
+    pending: BTreeMap<u64, Vec<ActionDiffEvent<A, Id>>>,
+}
+
+impl<A: Actionlike, Id: DiffOwnerId> Default for DiffSequencer<A, Id> {
+    fn default() -> Self {
+        Self::new(FrameOrdering::default())
+    }
+}
+
+impl<A: Actionlike, Id: DiffOwnerId> DiffSequencer<A, Id> {
+    /// Creates a new [`DiffSequencer`] that uses the provided `ordering`
+    #[must_use]
+    pub fn new(ordering: FrameOrdering) -> Self {
+        Self {
+            ordering,
+            dropped: 0,
+            reordered: 0,
+            last_applied: HashMap::default(),
+            newest_received: None,
+            next_sequence: None,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Accepts a newly received `frame`, returning the events that are now ready to be applied, in order
+    ///
+    /// Events may be returned from earlier frames that were being buffered,
+    /// and events from this frame may be held back or discarded.
+    pub fn sequence(&mut self, frame: DiffFrame<A, Id>) -> Vec<ActionDiffEvent<A, Id>> {
+        let Some(sequence) = frame.sequence else {
+            return frame.events;
+        };
+
+        let arrived_late = self.newest_received.is_some_and(|newest| sequence < newest);
+        self.newest_received = self.newest_received.max(Some(sequence));
+
+        match self.ordering {
+            FrameOrdering::DropStale => {
+                let mut ready = Vec::new();
+                for event in frame.events {
+                    let last_applied = self.last_applied.get(&event.owner).copied();
+                    if last_applied.is_some_and(|last| sequence < last) {
+                        self.dropped += 1;
+                        continue;
+                    }
+                    if arrived_late {
+                        self.reordered += 1;
+                    }
+                    self.last_applied.insert(event.owner.clone(), sequence);
+                    ready.push(event);
+                }
+                ready
+            }
+            FrameOrdering::Reorder { window } => {
+                let event_count = frame.events.len() as u64;
+                if self.next_sequence.is_some_and(|next| sequence < next)
+                    || self.pending.contains_key(&sequence)
+                {
+                    self.dropped += event_count;
+                    return Vec::new();
+                }
+                if arrived_late {
+                    self.reordered += event_count;
+                }
+
+                self.pending.insert(sequence, frame.events);
+                let mut ready = Vec::new();
+                self.release(window, &mut ready);
+                ready
+            }
+        }
+    }
+
+    /// Returns all buffered events in order, without waiting for any missing frames
+    pub fn flush(&mut self) -> Vec<ActionDiffEvent<A, Id>> {
+        let mut ready = Vec::new();
+        self.release(0, &mut ready);
+        ready
+    }
+
+    /// Moves pending frames into `ready` for as long as they are in order,
+    /// skipping over missing frames once frames `window` positions further ahead have been received
+    fn release(&mut self, window: u64, ready: &mut Vec<ActionDiffEvent<A, Id>>) {
+        while let (Some(&oldest), Some(&newest)) =
+            (self.pending.keys().next(), self.pending.keys().next_back())
+        {
+            // Until the start of the stream is known, the oldest frame received may not be the first one sent
+            let next = self.next_sequence.unwrap_or(0);
+            let expected = self.next_sequence.unwrap_or(oldest);
+            if oldest != next && newest - expected < window {
+                return;
+            }
+
+            ready.extend(self.pending.remove(&oldest).unwrap());
+            self.next_sequence = Some(oldest + 1);
+        }
+    }
+}
+
+/// Identifies the owner of an entity's [`ActionState`](crate::action_state::ActionState) in [`ActionDiffEvent`]s
+///
+/// Add this component alongside the `ActionState`, then use [`generate_action_diffs_by_id`](crate::systems::generate_action_diffs_by_id)
+/// to tag the generated events with this id instead of the [`Entity`].
+/// Entities without this component are ignored by that system.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct DiffOwner<Id: DiffOwnerId>(pub Id);
+
+/// Maps the [`DiffOwner`] ids received in [`ActionDiffEvent`]s back to the local entities that they control
+///
+/// Insert this as a resource, and keep it up to date as players join and leave:
+/// [`apply_action_diffs_by_id`](crate::systems::apply_action_diffs_by_id) uses it to find the
+/// [`ActionState`](crate::action_state::ActionState) that each event should be applied to.
+#[derive(Resource, Clone, PartialEq, Eq, Debug)]
+pub struct DiffOwnerMap<Id: DiffOwnerId> {
+    entities: HashMap<Id, Entity>,
+}
+
+impl<Id: DiffOwnerId> Default for DiffOwnerMap<Id> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::default(),
+        }
+    }
+}
+
+impl<Id: DiffOwnerId> DiffOwnerMap<Id> {
+    /// Maps the owner `id` to the local `entity`, returning the entity it was previously mapped to
+    pub fn insert(&mut self, id: Id, entity: Entity) -> Option<Entity> {
+        self.entities.insert(id, entity)
+    }
+
+    /// Removes the mapping for the owner `id`, returning the entity it was mapped to
+    pub fn remove(&mut self, id: &Id) -> Option<Entity> {
+        self.entities.remove(id)
+    }
+
+    /// The local entity that the owner `id` is mapped to, if any
+    #[must_use]
+    pub fn get(&self, id: &Id) -> Option<Entity> {
+        self.entities.get(id).copied()
+    }
+}
+
+/// Controls which [`ActionDiff`]s are emitted by [`generate_action_diffs`](crate::systems::generate_action_diffs)
+///
+/// Transports that can lose messages may want to periodically resend the state of held actions,
+/// while reliable transports can get away with sending only the changes.
+///
+/// Insert this as a resource to change the policy: if it is missing, [`DiffPolicy::DeltasOnly`] is used.
+/// Each run of the generation system counts as a single tick.
+#[non_exhaustive]
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, Default)]
+pub enum DiffPolicy {
+    /// Only emit diffs when the state of an action changes
+    ///
+    /// This is the default policy.
+    #[default]
+    DeltasOnly,
+    /// Like [`DiffPolicy::DeltasOnly`], but every `n` ticks the current state of all pressed actions is sent again
+    ///
+    /// A value of 0 never sends keepalives.
+    KeepaliveEvery(u32),
+    /// Like [`DiffPolicy::DeltasOnly`], but every `n` ticks a full snapshot of the [`ActionState`](crate::action_state::ActionState) is sent instead,
+    /// as created by [`ActionState::summarize`](crate::action_state::ActionState::summarize)
+    ///
+    /// A value of 0 never sends snapshots.
+    FullEvery(u32),
+}
+
+impl DiffPolicy {
+    /// Should the state of held actions be resent on the provided `tick`?
+    #[must_use]
+    pub fn is_keepalive_tick(&self, tick: u32) -> bool {
+        match *self {
+            DiffPolicy::KeepaliveEvery(n) => tick.checked_rem(n) == Some(0),
+            _ => false,
+        }
+    }
+
+    /// Should a full snapshot be sent on the provided `tick`?
+    #[must_use]
+    pub fn is_snapshot_tick(&self, tick: u32) -> bool {
+        match *self {
+            DiffPolicy::FullEvery(n) => tick.checked_rem(n) == Some(0),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::action_state::ActionState;
+    use bevy::prelude::Reflect;
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(
+        Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect, Serialize, Deserialize,
+    )]
+    enum Action {
+        Run,
+        Jump,
+    }
+
+    /// Alternately presses and releases each action, one frame at a time
+    fn recorded_frames() -> Vec<DiffFrame<Action>> {
+        (0..12)
+            .map(|sequence| {
+                let action = if sequence % 4 < 2 {
+                    Action::Run
+                } else {
+                    Action::Jump
+                };
+                let action_diff = if sequence % 2 == 0 {
+                    ActionDiff::Pressed { action }
+                } else {
+                    ActionDiff::Released { action }
+                };
+                DiffFrame::new(vec![ActionDiffEvent {
+                    owner: None,
+                    action_diffs: vec![action_diff],
+                }])
+                .with_sequence(sequence)
+            })
+            .collect()
+    }
+
+    fn apply(action_state: &mut ActionState<Action>, events: Vec<ActionDiffEvent<Action>>) {
+        for event in events {
+            for action_diff in event.action_diffs.iter() {
+                action_state.apply_diff(action_diff);
+            }
+        }
+    }
+
+    /// Reverses each group of `n` frames
+    fn shuffle(frames: Vec<DiffFrame<Action>>, n: usize) -> Vec<DiffFrame<Action>> {
+        frames
+            .chunks(n)
+            .flat_map(|chunk| chunk.iter().rev().cloned())
+            .collect()
+    }
+
+    #[test]
+    fn stale_frames_are_dropped() {
+        let frames = recorded_frames();
+        let mut sequencer = DiffSequencer::default();
+        let mut action_state = ActionState::default();
+
+        apply(&mut action_state, sequencer.sequence(frames[0].clone()));
+        apply(&mut action_state, sequencer.sequence(frames[2].clone()));
+        // Arrives after a newer frame has already been applied
+        apply(&mut action_state, sequencer.sequence(frames[1].clone()));
+
+        assert!(action_state.pressed(&Action::Run));
+        assert!(action_state.pressed(&Action::Jump));
+        assert_eq!(sequencer.dropped, 1);
+        assert_eq!(sequencer.reordered, 0);
+    }
+
+    #[test]
+    fn reordered_frames_match_in_order_application() {
+        let frames = recorded_frames();
+
+        let mut expected = ActionState::default();
+        for frame in frames.iter() {
+            apply(&mut expected, frame.events.clone());
+        }
+
+        let mut sequencer = DiffSequencer::new(FrameOrdering::Reorder { window: 4 });
+        let mut action_state = ActionState::default();
+        for frame in shuffle(frames, 3) {
+            apply(&mut action_state, sequencer.sequence(frame));
+        }
+        apply(&mut action_state, sequencer.flush());
+
+        for action in [Action::Run, Action::Jump] {
+            assert_eq!(
+                action_state.pressed(&action),
+                expected.pressed(&action),
+                "{action:?}"
+            );
+        }
+        assert_eq!(sequencer.dropped, 0);
+        // Each group of three has two frames arriving after a later one
+        assert_eq!(sequencer.reordered, 8);
+    }
+
+    #[test]
+    fn missing_frames_are_skipped_after_the_window() {
+        let frames = recorded_frames();
+        let mut sequencer = DiffSequencer::new(FrameOrdering::Reorder { window: 2 });
+
+        assert_eq!(sequencer.sequence(frames[0].clone()).len(), 1);
+        // Frame 1 was lost
+        assert!(sequencer.sequence(frames[2].clone()).is_empty());
+        assert_eq!(sequencer.sequence(frames[3].clone()).len(), 2);
+        // Too late
+        assert!(sequencer.sequence(frames[1].clone()).is_empty());
+        assert_eq!(sequencer.dropped, 1);
+    }
+
+    /// A frame in which only the `owner` pressed or released the `action`
+    fn owner_frame(
+        sequence: u64,
+        owner: u64,
+        action: Action,
+        pressed: bool,
+    ) -> DiffFrame<Action, u64> {
+        let action_diff = if pressed {
+            ActionDiff::Pressed { action }
+        } else {
+            ActionDiff::Released { action }
+        };
+        DiffFrame::new(vec![ActionDiffEvent {
+            owner: Some(owner),
+            action_diffs: vec![action_diff],
+        }])
+        .with_sequence(sequence)
+    }
+
+    #[test]
+    fn owners_that_skip_frames_are_not_held_back() {
+        let mut sequencer = DiffSequencer::<Action, u64>::new(FrameOrdering::Reorder { window: 4 });
+
+        // Each owner only sends diffs every other frame, which are not gaps in the stream
+        for sequence in 0..8 {
+            let owner = sequence % 2;
+            let frame = owner_frame(sequence, owner, Action::Run, sequence % 4 < 2);
+            assert_eq!(sequencer.sequence(frame.clone()), frame.events);
+        }
+        assert_eq!(sequencer.dropped, 0);
+        assert_eq!(sequencer.reordered, 0);
+    }
+
+    #[test]
+    fn several_owners_are_reordered_together() {
+        let frames: Vec<_> = (0..12)
+            .map(|sequence| {
+                // Owner 2 only sends diffs now and then
+                let owner = if sequence % 5 == 0 { 2 } else { sequence % 2 };
+                let action = if sequence % 3 == 0 {
+                    Action::Jump
+                } else {
+                    Action::Run
+                };
+                owner_frame(sequence, owner, action, sequence % 4 < 2)
+            })
+            .collect();
+
+        let mut expected: HashMap<u64, ActionState<Action>> = HashMap::default();
+        for frame in frames.iter() {
+            for event in frame.events.iter() {
+                let action_state = expected.entry(event.owner.unwrap()).or_default();
+                for action_diff in event.action_diffs.iter() {
+                    action_state.apply_diff(action_diff);
+                }
+            }
+        }
+
+        let mut sequencer = DiffSequencer::<Action, u64>::new(FrameOrdering::Reorder { window: 4 });
+        let mut mirrored: HashMap<u64, ActionState<Action>> = HashMap::default();
+        let shuffled = frames
+            .chunks(3)
+            .flat_map(|chunk| chunk.iter().rev().cloned());
+        for frame in shuffled {
+            for event in sequencer.sequence(frame) {
+                let action_state = mirrored.entry(event.owner.unwrap()).or_default();
+                for action_diff in event.action_diffs.iter() {
+                    action_state.apply_diff(action_diff);
+                }
+            }
+        }
+        // Every frame arrived within the window, so nothing is left to flush
+        assert!(sequencer.flush().is_empty());
+
+        for owner in 0..3 {
+            for action in [Action::Run, Action::Jump] {
+                assert_eq!(
+                    mirrored[&owner].pressed(&action),
+                    expected[&owner].pressed(&action),
+                    "{owner} {action:?}"
+                );
+            }
+        }
+        assert_eq!(sequencer.dropped, 0);
+        assert_eq!(sequencer.reordered, 8);
+    }
+
+    #[test]
+    fn stale_frames_are_only_dropped_for_their_owner() {
+        let mut sequencer = DiffSequencer::<Action, u64>::default();
+
+        assert_eq!(
+            sequencer
+                .sequence(owner_frame(0, 0, Action::Run, true))
+                .len(),
+            1
+        );
+        assert_eq!(
+            sequencer
+                .sequence(owner_frame(2, 1, Action::Run, true))
+                .len(),
+            1
+        );
+        // Owner 0 has nothing newer, so its late frame is still applied
+        assert_eq!(
+            sequencer
+                .sequence(owner_frame(1, 0, Action::Run, false))
+                .len(),
+            1
+        );
+        // But owner 1 does
+        assert!(sequencer
+            .sequence(owner_frame(1, 1, Action::Run, false))
+            .is_empty());
+
+        assert_eq!(sequencer.dropped, 1);
+        assert_eq!(sequencer.reordered, 1);
+    }
+
+    #[test]
+    fn events_from_the_same_frame_are_not_stale() {
+        let mut sequencer = DiffSequencer::<Action, u64>::default();
+        let run = owner_frame(1, 0, Action::Run, true);
+        let jump = owner_frame(1, 0, Action::Jump, true);
+
+        // Two diffs for the same owner, sent in the same frame
+        let mut frame = run.clone();
+        frame.events.extend(jump.events.clone());
+        assert_eq!(sequencer.sequence(frame.clone()), frame.events);
+
+        // Or split across two frames with the same sequence
+        assert_eq!(
+            sequencer.sequence(owner_frame(2, 0, Action::Run, false)),
+            owner_frame(2, 0, Action::Run, false).events
+        );
+        assert_eq!(
+            sequencer.sequence(owner_frame(2, 0, Action::Jump, false)),
+            owner_frame(2, 0, Action::Jump, false).events
+        );
+
+        // Older frames are still stale
+        assert!(sequencer.sequence(jump).is_empty());
+        assert_eq!(sequencer.dropped, 1);
+        assert_eq!(sequencer.reordered, 0);
+    }
+
+    #[test]
+    fn receivers_can_join_mid_stream() {
+        let frames = recorded_frames();
+        let mut sequencer = DiffSequencer::new(FrameOrdering::Reorder { window: 4 });
+
+        // The frames received while joining may have been reordered, so they are held back for a window
+        assert!(sequencer.sequence(frames[6].clone()).is_empty());
+        assert!(sequencer.sequence(frames[5].clone()).is_empty());
+        assert!(sequencer.sequence(frames[7].clone()).is_empty());
+        assert_eq!(sequencer.sequence(frames[9].clone()).len(), 3);
+        // Frames from before joining are too old to be applied
+        assert!(sequencer.sequence(frames[4].clone()).is_empty());
+        // From then on, frames are applied as soon as they are in order
+        assert_eq!(sequencer.sequence(frames[8].clone()).len(), 2);
+        assert_eq!(sequencer.sequence(frames[10].clone()).len(), 1);
+        assert_eq!(sequencer.dropped, 1);
+    }
+
+    #[test]
+    fn unsequenced_frames_are_passed_through() {
+        let mut sequencer = DiffSequencer::<Action>::default();
+        let frame = DiffFrame::new(vec![ActionDiffEvent {
+            owner: None,
+            action_diffs: vec![ActionDiff::Pressed {
+                action: Action::Run,
+            }],
+        }]);
+
+        assert_eq!(sequencer.sequence(frame.clone()), frame.events.clone());
+        assert_eq!(sequencer.sequence(frame.clone()), frame.events);
+    }
+
+    #[test]
+    fn legacy_diffs_can_be_decoded() {
+        // `ValueChanged` and `AxisPairChanged` diffs for `Action::Jump`, encoded by bincode without a `pressed` field
+        let mut value_changed = vec![2, 0, 0, 0, 1, 0, 0, 0];
+        value_changed.extend(0.5_f32.to_le_bytes());
+        let mut axis_pair_changed = vec![3, 0, 0, 0, 1, 0, 0, 0];
+        axis_pair_changed.extend(0.5_f32.to_le_bytes());
+        axis_pair_changed.extend((-1.0_f32).to_le_bytes());
+
+        // The layout of the current diffs can't read them
+        assert!(bincode::deserialize::<ActionDiff<Action>>(&value_changed).is_err());
+        assert!(bincode::deserialize::<ActionDiff<Action>>(&axis_pair_changed).is_err());
+
+        let legacy_diff: LegacyActionDiff<Action> = bincode::deserialize(&value_changed).unwrap();
+        assert_eq!(
+            ActionDiff::from(legacy_diff),
+            ActionDiff::ValueChanged {
+                action: Action::Jump,
+                value: 0.5,
+                pressed: true,
+            }
+        );
+
+        let legacy_diff: LegacyActionDiff<Action> =
+            bincode::deserialize(&axis_pair_changed).unwrap();
+        assert_eq!(
+            ActionDiff::from(legacy_diff),
+            ActionDiff::AxisPairChanged {
+                action: Action::Jump,
+                axis_pair: Vec2::new(0.5, -1.0),
+                pressed: true,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "diff_stats")]
+    fn frames_can_be_encoded() {
+        let frame = DiffFrame::<Action>::new(vec![ActionDiffEvent {
+            owner: None,
+            action_diffs: vec![
+                ActionDiff::Pressed {
+                    action: Action::Run,
+                },
+                ActionDiff::AxisPairChanged {
+                    action: Action::Jump,
+                    axis_pair: Vec2::new(0.5, -1.0),
+                    pressed: true,
+                },
+            ],
+        }])
+        .with_sequence(7);
+
+        let bytes = frame.encode();
+        assert_eq!(DiffFrame::decode(&bytes), Ok(frame));
+        assert!(DiffFrame::<Action>::decode(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[cfg(feature = "diff_stats")]
+    #[derive(
+        Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect, Serialize, Deserialize,
+    )]
+    enum Ability {
+        Fireball,
+        Frostbolt,
+        Heal,
+        Shield,
+        Blink,
+        Slow,
+        Haste,
+        Summon,
+        Charm,
+        Stun,
+        Silence,
+        Ultimate,
+    }
+
+    #[cfg(feature = "diff_stats")]
+    const ABILITIES: [Ability; 12] = [
+        Ability::Fireball,
+        Ability::Frostbolt,
+        Ability::Heal,
+        Ability::Shield,
+        Ability::Blink,
+        Ability::Slow,
+        Ability::Haste,
+        Ability::Summon,
+        Ability::Charm,
+        Ability::Stun,
+        Ability::Silence,
+        Ability::Ultimate,
+    ];
+
+    /// Builds an equivalent state, inserting the actions in the provided order
+    #[cfg(feature = "diff_stats")]
+    fn build_state(order: impl Iterator<Item = Ability>) -> ActionState<Ability> {
+        let mut action_state = ActionState::default();
+        for ability in order {
+            if matches!(ability, Ability::Fireball | Ability::Blink | Ability::Charm) {
+                action_state.release(&ability);
+            } else {
+                action_state.press(&ability);
+                action_state.action_data_mut(&ability).unwrap().value = ability as usize as f32;
+            }
+        }
+        action_state
+    }
+
+    #[cfg(feature = "diff_stats")]
+    fn encoded_hash(action_state: &ActionState<Ability>) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let frame = DiffFrame::<Ability>::new(vec![ActionDiffEvent {
+            owner: None,
+            action_diffs: action_state.summarize(),
+        }]);
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        frame.encode().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    #[cfg(feature = "diff_stats")]
+    fn encoding_is_deterministic() {
+        let forwards = build_state(ABILITIES.into_iter());
+        let backwards = build_state(ABILITIES.into_iter().rev());
+        let interleaved = build_state(
+            ABILITIES
+                .into_iter()
+                .step_by(2)
+                .chain(ABILITIES.into_iter().skip(1).step_by(2)),
+        );
+
+        assert_eq!(encoded_hash(&forwards), encoded_hash(&backwards));
+        assert_eq!(encoded_hash(&forwards), encoded_hash(&interleaved));
+
+        // Summaries are listed in declaration order
+        let summarized: Vec<Ability> = backwards
+            .summarize()
+            .iter()
+            .map(|action_diff| *action_diff.action())
+            .collect();
+        assert_eq!(summarized, ABILITIES);
+
+        // Encoding sorts the diffs of hand-built frames too
+        let mut reversed = backwards.summarize();
+        reversed.reverse();
+        let frame = DiffFrame::<Ability>::new(vec![ActionDiffEvent {
+            owner: None,
+            action_diffs: reversed,
+        }]);
+        let expected = DiffFrame::<Ability>::new(vec![ActionDiffEvent {
+            owner: None,
+            action_diffs: forwards.summarize(),
+        }]);
+        assert_eq!(frame.encode(), expected.encode());
+    }
+}
@@ -1,15 +1,25 @@
 //! This module contains [`ActionState`] and its supporting methods and impls.
 
-use crate::action_diff::ActionDiff;
-use crate::timing::Timing;
+use crate::action_diff::{sort_by_action, ActionDiff};
+use crate::timing::{
+    ConsumePolicy, DoubleTickPolicy, HoldHistory, TimeScalePolicies, TimeScalePolicy, Timing,
+};
 use crate::Actionlike;
-use crate::{axislike::DualAxisData, buttonlike::ButtonState};
+use crate::{
+    axislike::{DualAxisData, TripleAxisData},
+    buttonlike::ButtonState,
+};
 
 use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+#[cfg(debug_assertions)]
+use bevy::log::warn;
 use bevy::prelude::Resource;
 use bevy::reflect::Reflect;
-use bevy::utils::{Duration, Entry, HashMap, Instant};
+use bevy::utils::{Duration, Entry, HashMap, HashSet, Instant};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
 
 /// Metadata about an [`Actionlike`] action
 ///
@@ -27,6 +37,9 @@ pub struct ActionData {
     pub value: f32,
     /// The [`DualAxisData`] of the binding that triggered the action.
     pub axis_pair: Option<DualAxisData>,
+    /// The [`TripleAxisData`] of the binding that triggered the action.
+    #[serde(default)]
+    pub axis_triple: Option<TripleAxisData>,
     /// When was the button pressed / released, and how long has it been held for?
     pub timing: Timing,
     /// Was this action consumed by [`ActionState::consume`]?
@@ -34,6 +47,30 @@ pub struct ActionData {
     /// Actions that are consumed cannot be pressed again until they are explicitly released.
     /// This ensures that consumed actions are not immediately re-pressed by continued inputs.
     pub consumed: bool,
+    /// Was a press of this action swallowed by its [`Cooldowns`](crate::cooldowns::Cooldowns) since the last tick?
+    #[serde(default)]
+    pub blocked_by_cooldown: bool,
+    /// Was this action consumed under [`ConsumePolicy::PreserveTiming`] while it was pressed?
+    ///
+    /// If so, its [`Timing`] is flipped when it is released.
+    #[serde(default)]
+    pub timing_preserved: bool,
+    /// The durations of the last few times this action was held, if they are tracked
+    ///
+    /// See [`ActionState::track_recent_holds`].
+    #[serde(skip)]
+    #[reflect(ignore)]
+    pub hold_history: Option<HoldHistory>,
+}
+
+impl ActionData {
+    /// Flips the [`Timing`] of this action, recording it in the [`HoldHistory`] if the action was `held`
+    fn flip_timing(&mut self, held: bool) {
+        if let (true, Some(hold_history)) = (held, &mut self.hold_history) {
+            hold_history.record(&self.timing);
+        }
+        self.timing.flip();
+    }
 }
 
 /// Stores the canonical input-method-agnostic representation of the inputs received
@@ -81,10 +118,50 @@ pub struct ActionData {
 /// assert!(action_state.released(&Action::Jump));
 /// assert!(!action_state.just_released(&Action::Jump));
 /// ```
-#[derive(Resource, Component, Clone, Debug, PartialEq, Serialize, Deserialize, Reflect)]
+#[derive(Resource, Component, Clone, Debug, Serialize, Deserialize, Reflect)]
 pub struct ActionState<A: Actionlike> {
     /// The [`ActionData`] of each action
     action_data: HashMap<A, ActionData>,
+    /// The frame passed to the last call of [`ActionState::tick_in_frame`]
+    #[serde(skip)]
+    #[reflect(ignore)]
+    tick_frame: Option<u32>,
+    /// The number of ticks since the `tick_frame` last changed
+    #[serde(skip)]
+    #[reflect(ignore)]
+    ticks_this_frame: u32,
+    /// The unscaled `current_instant` of the last tick
+    #[serde(skip)]
+    #[reflect(ignore)]
+    last_tick_instant: Option<Instant>,
+    /// Was any action pressed or released since the last tick?
+    #[serde(skip)]
+    #[reflect(ignore)]
+    activity_pending: bool,
+    /// The unscaled instant of the tick in which any action was last pressed or released
+    #[serde(skip)]
+    #[reflect(ignore)]
+    idle_since: Option<Instant>,
+    /// The [`ConsumePolicy`] of this state, overriding the `default_consume_policy`
+    #[serde(skip)]
+    #[reflect(ignore)]
+    consume_policy: Option<ConsumePolicy>,
+    /// The [`ActionConsumePolicy`](crate::timing::ActionConsumePolicy) resource, as of the last tick
+    #[serde(skip)]
+    #[reflect(ignore)]
+    default_consume_policy: ConsumePolicy,
+    /// The time between the scaled instants of the last tick
+    #[serde(skip)]
+    #[reflect(ignore)]
+    last_tick_delta: Duration,
+    /// The time between the unscaled instants of the last tick
+    #[serde(skip)]
+    #[reflect(ignore)]
+    unscaled_tick_delta: Duration,
+    /// The actions that were timed using the unscaled instants in the last tick
+    #[serde(skip, default = "HashSet::default")]
+    #[reflect(ignore)]
+    unscaled_actions: HashSet<A>,
 }
 
 // The derive does not work unless A: Default,
@@ -93,33 +170,80 @@ impl<A: Actionlike> Default for ActionState<A> {
     fn default() -> Self {
         Self {
             action_data: HashMap::default(),
+            tick_frame: None,
+            ticks_this_frame: 0,
+            last_tick_instant: None,
+            activity_pending: false,
+            idle_since: None,
+            consume_policy: None,
+            default_consume_policy: ConsumePolicy::default(),
+            last_tick_delta: Duration::ZERO,
+            unscaled_tick_delta: Duration::ZERO,
+            unscaled_actions: HashSet::default(),
         }
     }
 }
 
+// The tick accounting is only used for diagnostics, so it is not part of the state
+impl<A: Actionlike> PartialEq for ActionState<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.action_data == other.action_data
+    }
+}
+
 impl<A: Actionlike> ActionState<A> {
     /// Updates the [`ActionState`] based on a vector of [`ActionData`], ordered by [`Actionlike::id`](Actionlike).
     ///
     /// The `action_data` is typically constructed from [`InputMap::which_pressed`](crate::input_map::InputMap),
     /// which reads from the assorted [`Input`](bevy::input::Input) resources.
+    ///
+    /// Newly pressed actions record the current instant as their [`Timing::pressed_at_raw`].
     pub fn update(&mut self, action_data: HashMap<A, ActionData>) {
+        self.update_at(action_data, Instant::now());
+    }
+
+    /// Updates the [`ActionState`] like [`ActionState::update`], recording `raw_instant` as the [`Timing::pressed_at_raw`] of newly pressed actions
+    pub fn update_at(&mut self, action_data: HashMap<A, ActionData>, raw_instant: Instant) {
         for (action, action_datum) in action_data {
             match self.action_data.entry(action) {
                 Entry::Occupied(occupied_entry) => {
                     let entry = occupied_entry.into_mut();
+                    let was_pressed = entry.state.pressed();
+
+                    if action_datum.state.pressed() {
+                        // Consumed actions cannot be pressed until they are released
+                        if !entry.consumed {
+                            entry.state.press();
+                        }
+                    } else {
+                        // Once released, consumed actions can be pressed again
+                        entry.consumed = false;
+                        if entry.timing_preserved {
+                            entry.timing_preserved = false;
+                            entry.flip_timing(true);
+                        }
+                        entry.state.release();
+                    }
 
-                    match action_datum.state {
-                        ButtonState::JustPressed => entry.state.press(),
-                        ButtonState::Pressed => entry.state.press(),
-                        ButtonState::JustReleased => entry.state.release(),
-                        ButtonState::Released => entry.state.release(),
+                    // Like `press` and `release`, state changes restart the timing
+                    if entry.state.pressed() != was_pressed {
+                        self.activity_pending = true;
+                        entry.flip_timing(was_pressed);
+                        if entry.state.pressed() {
+                            entry.timing.pressed_at_raw = Some(raw_instant);
+                        }
                     }
 
                     entry.axis_pair = action_datum.axis_pair;
+                    entry.axis_triple = action_datum.axis_triple;
                     entry.value = action_datum.value;
                 }
                 Entry::Vacant(empty_entry) => {
-                    empty_entry.insert(action_datum.clone());
+                    let entry = empty_entry.insert(action_datum);
+                    if entry.state.pressed() {
+                        self.activity_pending = true;
+                        entry.timing.pressed_at_raw = Some(raw_instant);
+                    }
                 }
             }
         }
@@ -170,20 +294,230 @@ impl<A: Actionlike> ActionState<A> {
     /// assert!(!action_state.just_pressed(&Action::Jump));
     /// ```
     pub fn tick(&mut self, current_instant: Instant, previous_instant: Instant) {
+        let delta = current_instant.saturating_duration_since(previous_instant);
+        self.unscaled_tick_delta = delta;
+        self.unscaled_actions.clear();
+        self.tick_with(current_instant, delta, |_| {
+            (current_instant, previous_instant)
+        });
+    }
+
+    /// Advances the time for all actions, like [`ActionState::tick`], using unscaled instants for some of them
+    ///
+    /// Actions whose [`TimeScalePolicy`] is [`TimeScalePolicy::Unscaled`] in the `policies` are timed using the
+    /// `unscaled_current_instant` and `unscaled_previous_instant`, which are typically read from [`Time<Real>`](bevy::time::Real).
+    /// All other actions are timed using the `current_instant` and `previous_instant`.
+    ///
+    /// An action should always be ticked with the same kind of instants, or its durations will jump.
+    pub fn tick_with_time_scales(
+        &mut self,
+        current_instant: Instant,
+        previous_instant: Instant,
+        unscaled_current_instant: Instant,
+        unscaled_previous_instant: Instant,
+        policies: &TimeScalePolicies<A>,
+    ) {
+        let delta = current_instant.saturating_duration_since(previous_instant);
+        self.unscaled_tick_delta =
+            unscaled_current_instant.saturating_duration_since(unscaled_previous_instant);
+        self.unscaled_actions = self
+            .action_data
+            .keys()
+            .filter(|&action| policies.get(action) == TimeScalePolicy::Unscaled)
+            .cloned()
+            .collect();
+        self.tick_with(unscaled_current_instant, delta, |action| {
+            match policies.get(action) {
+                TimeScalePolicy::Scaled => (current_instant, previous_instant),
+                TimeScalePolicy::Unscaled => (unscaled_current_instant, unscaled_previous_instant),
+            }
+        });
+    }
+
+    /// Advances the time for all actions, using the current and previous instants returned by `instants` for each action
+    ///
+    /// The `unscaled_current_instant` is stored for [`ActionState::sub_frame_offset`],
+    /// and the `delta` for [`ActionState::last_tick_delta`].
+    fn tick_with(
+        &mut self,
+        unscaled_current_instant: Instant,
+        delta: Duration,
+        instants: impl Fn(&A) -> (Instant, Instant),
+    ) {
+        self.ticks_this_frame = self.ticks_this_frame.saturating_add(1);
+        self.last_tick_delta = delta;
+        // Activity is recorded as happening at the start of the tick in which it was seen
+        if self.activity_pending || self.idle_since.is_none() {
+            self.activity_pending = false;
+            self.idle_since = Some(self.last_tick_instant.unwrap_or(unscaled_current_instant));
+        }
+        self.last_tick_instant = Some(unscaled_current_instant);
+
         // Advanced the ButtonState
-        self.action_data
-            .iter_mut()
-            .for_each(|(_, ad)| ad.state.tick());
+        self.action_data.iter_mut().for_each(|(_, ad)| {
+            ad.state.tick();
+            ad.blocked_by_cooldown = false;
+        });
 
         // Advance the Timings
-        self.action_data.iter_mut().for_each(|(_, ad)| {
+        self.action_data.iter_mut().for_each(|(action, ad)| {
             // Durations should not advance while actions are consumed
             if !ad.consumed {
+                let (current_instant, previous_instant) = instants(action);
                 ad.timing.tick(current_instant, previous_instant);
             }
         });
     }
 
+    /// Restarts the timings of all actions from their current durations, so that the time since the last tick is not counted
+    ///
+    /// Call this before ticking a state that was not ticked for a while on purpose,
+    /// such as an entity whose [`PausedInputs`] were removed.
+    /// Durations then carry on from where they were frozen, rather than jumping ahead.
+    pub fn resume_timings(&mut self) {
+        for action_data in self.action_data.values_mut() {
+            action_data.timing.instant_started = None;
+        }
+    }
+
+    /// Advances the time for all actions, like [`ActionState::tick`], unless this state was already ticked during this `frame`
+    ///
+    /// Ticking twice in the same frame clears the `just_pressed` and `just_released` values before they could be read,
+    /// which typically happens when another system calls [`ActionState::tick`] alongside the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+    /// The `policy` controls what happens to these extra ticks.
+    ///
+    /// The `frame` is typically the [`FrameCount`](bevy::core::FrameCount), but any number that changes once per frame will do.
+    /// Returns `true` if the state was ticked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use bevy::utils::Instant;
+    /// use leafwing_input_manager::prelude::*;
+    /// use leafwing_input_manager::timing::DoubleTickPolicy;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Jump,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let now = Instant::now();
+    ///
+    /// action_state.tick_in_frame(1, now, now, DoubleTickPolicy::Skip);
+    /// action_state.press(&Action::Jump);
+    ///
+    /// // Ticking again in the same frame is skipped
+    /// assert!(!action_state.tick_in_frame(1, now, now, DoubleTickPolicy::Skip));
+    /// assert!(action_state.just_pressed(&Action::Jump));
+    /// assert_eq!(action_state.ticks_this_frame(), 1);
+    ///
+    /// // But the next frame is ticked as usual
+    /// assert!(action_state.tick_in_frame(2, now, now, DoubleTickPolicy::Skip));
+    /// assert!(!action_state.just_pressed(&Action::Jump));
+    /// ```
+    pub fn tick_in_frame(
+        &mut self,
+        frame: u32,
+        current_instant: Instant,
+        previous_instant: Instant,
+        policy: DoubleTickPolicy,
+    ) -> bool {
+        if !self.accepts_tick_in_frame(frame, policy) {
+            return false;
+        }
+
+        self.tick(current_instant, previous_instant);
+        true
+    }
+
+    /// Should this state be ticked during this `frame`, according to the [`DoubleTickPolicy`]?
+    ///
+    /// If so, the tick must follow immediately, as it is counted by [`ActionState::ticks_this_frame`].
+    pub(crate) fn accepts_tick_in_frame(&mut self, frame: u32, policy: DoubleTickPolicy) -> bool {
+        if self.tick_frame != Some(frame) {
+            self.tick_frame = Some(frame);
+            self.ticks_this_frame = 0;
+        } else if self.ticks_this_frame > 0 {
+            match policy {
+                DoubleTickPolicy::Skip => return false,
+                DoubleTickPolicy::Warn => {
+                    #[cfg(debug_assertions)]
+                    warn!(
+                        "ActionState<{}> was ticked more than once in frame {frame}, so its `just_pressed` and `just_released` values were lost. Is another system calling `ActionState::tick`?",
+                        std::any::type_name::<A>()
+                    );
+                }
+                DoubleTickPolicy::Allow => (),
+            }
+        }
+
+        true
+    }
+
+    /// The number of times this state was ticked since the frame passed to [`ActionState::tick_in_frame`] last changed
+    ///
+    /// This counts calls to both [`ActionState::tick`] and [`ActionState::tick_in_frame`].
+    /// When the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) is the only thing ticking this state, this is 1 after each tick.
+    /// Larger values mean that something else is also ticking it, which is useful for diagnosing missing `just_pressed` values.
+    #[inline]
+    #[must_use]
+    pub fn ticks_this_frame(&self) -> u32 {
+        self.ticks_this_frame
+    }
+
+    /// The time between the `previous_instant` and `current_instant` of the last tick
+    ///
+    /// This is the same delta that the durations of actions were advanced by, so systems that integrate values over time
+    /// can use it instead of [`Time`](bevy::time::Time), and stay in sync with the input system whatever order they run in.
+    /// For states ticked with [`ActionState::tick_with_time_scales`], this is the scaled delta.
+    ///
+    /// This is [`Duration::ZERO`] before the first tick. Extra ticks skipped by [`ActionState::tick_in_frame`] don't change it.
+    #[inline]
+    #[must_use]
+    pub fn last_tick_delta(&self) -> Duration {
+        self.last_tick_delta
+    }
+
+    /// The [`value`](Self::value) of the `action`, multiplied by the [`last_tick_delta`](Self::last_tick_delta) in seconds
+    ///
+    /// This is how much an analog action moved something over the last tick.
+    /// Actions that were timed as [`TimeScalePolicy::Unscaled`] by [`ActionState::tick_with_time_scales`]
+    /// are multiplied by the unscaled delta of the tick instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    /// enum CameraAction {
+    ///     Rotate,
+    /// }
+    ///
+    /// const RADIANS_PER_SECOND: f32 = 2.0;
+    ///
+    /// // No need for `Res<Time>`, which may disagree with the input system about how much time has passed
+    /// fn rotate_camera(
+    ///     mut query: Query<(&ActionState<CameraAction>, &mut Transform), With<Camera>>,
+    /// ) {
+    ///     for (action_state, mut transform) in query.iter_mut() {
+    ///         let angle = action_state.integrated_value(&CameraAction::Rotate) * RADIANS_PER_SECOND;
+    ///         transform.rotate_y(angle);
+    ///     }
+    /// }
+    /// # bevy::ecs::system::assert_is_system(rotate_camera);
+    /// ```
+    #[must_use]
+    pub fn integrated_value(&self, action: &A) -> f32 {
+        let delta = if self.unscaled_actions.contains(action) {
+            self.unscaled_tick_delta
+        } else {
+            self.last_tick_delta
+        };
+        self.value(action) * delta.as_secs_f32()
+    }
+
     /// A reference to the [`ActionData`] of the corresponding `action` if populated.
     ///
     /// Generally, it'll be clearer to call `pressed` or so on directly on the [`ActionState`].
@@ -209,15 +543,21 @@ impl<A: Actionlike> ActionState<A> {
     /// Different kinds of bindings have different ways of calculating the value:
     ///
     /// - Binary buttons will have a value of `0.0` when the button is not pressed, and a value of
-    /// `1.0` when the button is pressed.
+    ///   `1.0` when the button is pressed.
     /// - Some axes, such as an analog stick, will have a value in the range `-1.0..=1.0`.
     /// - Some axes, such as a variable trigger, will have a value in the range `0.0..=1.0`.
     /// - Some buttons will also return a value in the range `0.0..=1.0`, such as analog gamepad
-    /// triggers which may be tracked as buttons or axes. Examples of these include the Xbox LT/RT
-    /// triggers and the Playstation L2/R2 triggers. See also the `axis_inputs` example in the
-    /// repository.
+    ///   triggers which may be tracked as buttons or axes. Examples of these include the Xbox LT/RT
+    ///   triggers and the Playstation L2/R2 triggers. See also the `axis_inputs` example in the
+    ///   repository.
+    /// - Gamepad buttons read their value from [`Axis<GamepadButton>`](bevy::input::Axis), falling back to
+    ///   `0.0` or `1.0` when the gamepad doesn't report one. Their value is reported even while the action is released,
+    ///   such as for a trigger that is pulled less than its press threshold. The threshold is set by Bevy's
+    ///   [`GamepadSettings`](bevy::input::gamepad::GamepadSettings), by the
+    ///   [`GamepadSettingsOverride`](crate::gamepad_settings::GamepadSettingsOverride),
+    ///   or for each binding using an [`AnalogButton`](crate::buttonlike::AnalogButton).
     /// - Dual axis inputs will return the magnitude of its [`DualAxisData`] and will be in the range
-    /// `0.0..=1.0`.
+    ///   `0.0..=1.0`.
     /// - Chord inputs will return the value of its first input.
     ///
     /// If multiple inputs trigger the same game action at the same time, the value of each
@@ -273,6 +613,18 @@ impl<A: Actionlike> ActionState<A> {
             .map(|pair| DualAxisData::new(pair.x().clamp(-1.0, 1.0), pair.y().clamp(-1.0, 1.0)))
     }
 
+    /// Get the [`TripleAxisData`] from the binding that triggered the corresponding `action`.
+    ///
+    /// Only actions bound to a [`TripleAxis`](crate::axislike::TripleAxis), such as a gyroscope, will return values.
+    /// Chord inputs will return the [`TripleAxisData`] of their first `TripleAxis` while their other inputs are pressed.
+    ///
+    /// If multiple inputs with an axis triple trigger the same game action at the same time,
+    /// the value of each axis triple will be added together.
+    pub fn axis_triple(&self, action: &A) -> Option<TripleAxisData> {
+        let action_data = self.action_data(action)?;
+        action_data.axis_triple
+    }
+
     /// Manually sets the [`ActionData`] of the corresponding `action`
     ///
     /// You should almost always use more direct methods, as they are simpler and less error-prone.
@@ -314,12 +666,89 @@ impl<A: Actionlike> ActionState<A> {
         self.action_data.insert(action, data);
     }
 
+    /// The unscaled [`Instant`] passed to the most recent [tick](ActionState::tick), if any
+    #[must_use]
+    pub(crate) fn last_tick_instant(&self) -> Option<Instant> {
+        self.last_tick_instant
+    }
+
+    /// Treats the press of a just pressed `action` as having started at `started`, when the current tick began at `now`
+    ///
+    /// Used by [`ClashStrategy::DelayForChords`](crate::clashing_inputs::ClashStrategy::DelayForChords)
+    /// for actions that were withheld while waiting for a chord.
+    pub(crate) fn backdate_press(&mut self, action: &A, started: Instant, now: Instant) {
+        let Some(action_datum) = self.action_data.get_mut(action) else {
+            return;
+        };
+        if !action_datum.state.just_pressed() {
+            return;
+        }
+
+        // Like deserialized timings, the `instant_started` is recomputed from the duration on the next tick
+        action_datum.timing.current_duration = now.saturating_duration_since(started);
+        action_datum.timing.instant_started = None;
+        action_datum.timing.pressed_at_raw = Some(started);
+    }
+
+    /// Makes this state a copy of the `source`, only writing the actions that differ
+    ///
+    /// Returns `true` if any action was written or removed.
+    /// The tick accounting is copied as well, but is not compared.
+    pub(crate) fn mirror(&mut self, source: &Self) -> bool {
+        let ActionState {
+            action_data,
+            tick_frame,
+            ticks_this_frame,
+            last_tick_instant,
+            activity_pending,
+            idle_since,
+            consume_policy,
+            default_consume_policy,
+            last_tick_delta,
+            unscaled_tick_delta,
+            unscaled_actions,
+        } = source;
+
+        let mut changed = false;
+        self.action_data.retain(|action, _| {
+            let kept = action_data.contains_key(action);
+            changed |= !kept;
+            kept
+        });
+        for (action, action_datum) in action_data.iter() {
+            if self.action_data.get(action) != Some(action_datum) {
+                self.action_data
+                    .insert(action.clone(), action_datum.clone());
+                changed = true;
+            }
+        }
+
+        self.tick_frame = *tick_frame;
+        self.ticks_this_frame = *ticks_this_frame;
+        self.last_tick_instant = *last_tick_instant;
+        self.activity_pending = *activity_pending;
+        self.idle_since = *idle_since;
+        self.consume_policy = *consume_policy;
+        self.default_consume_policy = *default_consume_policy;
+        self.last_tick_delta = *last_tick_delta;
+        self.unscaled_tick_delta = *unscaled_tick_delta;
+        self.unscaled_actions.clone_from(unscaled_actions);
+        changed
+    }
+
     /// Press the `action`
     ///
     /// No initial instant or reasons why the button was pressed will be recorded
     /// Instead, this is set through [`ActionState::tick()`]
+    ///
+    /// If the action was released, the current instant is recorded as its [`Timing::pressed_at_raw`].
     #[inline]
     pub fn press(&mut self, action: &A) {
+        self.press_at(action, Instant::now());
+    }
+
+    /// Press the `action`, like [`ActionState::press`], recording `raw_instant` as its [`Timing::pressed_at_raw`]
+    pub fn press_at(&mut self, action: &A, raw_instant: Instant) {
         let action_data = match self.action_data_mut(action) {
             Some(action_data) => action_data,
             None => {
@@ -333,11 +762,14 @@ impl<A: Actionlike> ActionState<A> {
             return;
         }
 
-        if action_data.state.released() {
-            action_data.timing.flip();
+        let was_released = action_data.state.released();
+        if was_released {
+            action_data.flip_timing(false);
+            action_data.timing.pressed_at_raw = Some(raw_instant);
         }
 
         action_data.state.press();
+        self.activity_pending |= was_released;
     }
 
     /// Release the `action`
@@ -357,11 +789,14 @@ impl<A: Actionlike> ActionState<A> {
         // Once released, consumed actions can be pressed again
         action_data.consumed = false;
 
-        if action_data.state.pressed() {
-            action_data.timing.flip();
+        let was_pressed = action_data.state.pressed();
+        if was_pressed || action_data.timing_preserved {
+            action_data.flip_timing(true);
         }
 
+        action_data.timing_preserved = false;
         action_data.state.release();
+        self.activity_pending |= was_pressed;
     }
 
     /// Consumes the `action`
@@ -373,6 +808,8 @@ impl<A: Actionlike> ActionState<A> {
     /// No initial instant will be recorded
     /// Instead, this is set through [`ActionState::tick()`]
     ///
+    /// What happens to the [`Timing`] of the action depends on the [`ActionState::consume_policy`].
+    ///
     /// # Example
     ///
     /// ```rust
@@ -405,6 +842,7 @@ impl<A: Actionlike> ActionState<A> {
     /// ```
     #[inline]
     pub fn consume(&mut self, action: &A) {
+        let consume_policy = self.consume_policy();
         let action_data = match self.action_data_mut(action) {
             Some(action_data) => action_data,
             None => {
@@ -415,8 +853,52 @@ impl<A: Actionlike> ActionState<A> {
 
         // This is the only difference from action_state.release(&action)
         action_data.consumed = true;
+        match consume_policy {
+            ConsumePolicy::FlipTiming => {
+                let held = action_data.state.pressed();
+                action_data.flip_timing(held);
+            }
+            // The timing is flipped on release instead
+            ConsumePolicy::PreserveTiming => {
+                action_data.timing_preserved |= action_data.state.pressed();
+            }
+        }
         action_data.state.release();
-        action_data.timing.flip();
+    }
+
+    /// The [`ConsumePolicy`] used by [`ActionState::consume`]
+    ///
+    /// Unless it was overridden by [`ActionState::set_consume_policy`],
+    /// this is the [`ActionConsumePolicy`](crate::timing::ActionConsumePolicy) resource as of the last tick, or [`ConsumePolicy::FlipTiming`] before the first tick.
+    #[must_use]
+    pub fn consume_policy(&self) -> ConsumePolicy {
+        self.consume_policy.unwrap_or(self.default_consume_policy)
+    }
+
+    /// Overrides the [`ConsumePolicy`] of this state
+    ///
+    /// Passing [`None`] uses the [`ActionConsumePolicy`](crate::timing::ActionConsumePolicy) resource again.
+    /// Actions that are already consumed keep the behavior they were consumed with.
+    pub fn set_consume_policy(&mut self, consume_policy: Option<ConsumePolicy>) {
+        self.consume_policy = consume_policy;
+    }
+
+    /// Sets the [`ConsumePolicy`] used when this state does not override it
+    pub(crate) fn set_default_consume_policy(&mut self, consume_policy: ConsumePolicy) {
+        self.default_consume_policy = consume_policy;
+    }
+
+    /// Swallows a press of the `action`, because it is still on cooldown
+    ///
+    /// The action is consumed without being just released, and [`ActionState::blocked_by_cooldown`] is set until the next tick.
+    pub(crate) fn block_by_cooldown(&mut self, action: &A) {
+        let Some(action_data) = self.action_data_mut(action) else {
+            return;
+        };
+
+        action_data.consumed = true;
+        action_data.blocked_by_cooldown = true;
+        action_data.state = ButtonState::Released;
     }
 
     /// Consumes all actions
@@ -444,6 +926,61 @@ impl<A: Actionlike> ActionState<A> {
         }
     }
 
+    /// Was a press of this `action` swallowed by its [`Cooldowns`](crate::cooldowns::Cooldowns) since the last tick?
+    ///
+    /// This is useful for giving feedback to players, such as flashing the cooldown indicator of an ability.
+    #[inline]
+    #[must_use]
+    pub fn blocked_by_cooldown(&self, action: &A) -> bool {
+        match self.action_data(action) {
+            Some(action_data) => action_data.blocked_by_cooldown,
+            None => false,
+        }
+    }
+
+    /// How long after the start of its frame was this `action` pressed?
+    ///
+    /// This is the time between the tick of the frame in which the action was pressed,
+    /// and the [`Timing::pressed_at_raw`] recorded by [`ActionState::update`] or [`ActionState::press`].
+    /// It is available as soon as the action is pressed, and stays the same until it is released.
+    /// Returns `None` if the action is released, or if this state has never been ticked.
+    ///
+    /// This is intended for sub-frame timing, such as judging presses in rhythm games, but has some precision caveats:
+    /// - Bevy does not timestamp input events, so the raw instant is when the press was processed, not when the key was physically pressed.
+    ///   When using the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin), this is during [`PreUpdate`](bevy::app::PreUpdate),
+    ///   so the offset measures the delay between the start of the frame and input processing, rather than the player's timing.
+    ///   Presses from [`ActionState::press_at`] or [`ActionState::update_at`] can supply more accurate instants.
+    /// - Raw instants come from the wall clock, so the offset is only meaningful when the tick instants do too,
+    ///   as with [`TickClock::RealTime`](crate::timing::TickClock::RealTime) or unscaled actions.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use bevy::utils::{Duration, Instant};
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Drum,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let frame_start = Instant::now();
+    /// action_state.tick(frame_start, frame_start - Duration::from_millis(16));
+    ///
+    /// // The drum was hit 5 ms into the frame
+    /// action_state.press_at(&Action::Drum, frame_start + Duration::from_millis(5));
+    /// assert_eq!(action_state.sub_frame_offset(&Action::Drum), Some(Duration::from_millis(5)));
+    /// ```
+    #[must_use]
+    pub fn sub_frame_offset(&self, action: &A) -> Option<Duration> {
+        let timing = &self.action_data(action)?.timing;
+        let pressed_at_raw = timing.pressed_at_raw?;
+        // Once ticked, the timing starts from the tick of the frame in which the action was pressed
+        let frame_instant = timing.instant_started.or(self.last_tick_instant)?;
+        Some(pressed_at_raw.saturating_duration_since(frame_instant))
+    }
+
     /// Is this `action` currently pressed?
     #[inline]
     #[must_use]
@@ -551,6 +1088,60 @@ impl<A: Actionlike> ActionState<A> {
         action_data.timing.current_duration
     }
 
+    /// Has the `action` been released for at least `duration`?
+    ///
+    /// Like [`ActionState::current_duration`], this is measured from the start of the tick in which the action was released.
+    #[must_use]
+    pub fn released_for_at_least(&self, action: &A, duration: Duration) -> bool {
+        self.released(action) && self.current_duration(action) >= duration
+    }
+
+    /// The [`Duration`] since any action was last pressed or released
+    ///
+    /// This is measured between the unscaled instants passed to [`ActionState::tick`],
+    /// starting from the tick in which any action was last pressed or released, or from the first tick if no action ever was.
+    /// Presses and releases from [`ActionState::update`], [`ActionState::press`], [`ActionState::release`]
+    /// and [`ActionState::apply_diff`] all count as activity, but changes to the values of released actions
+    /// (such as analog noise below the deadzone) do not.
+    ///
+    /// This will be [`Duration::ZERO`] if any action was pressed or released since the last tick,
+    /// or if this state was never ticked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use bevy::utils::{Duration, Instant};
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Start,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let t0 = Instant::now();
+    /// let t1 = t0 + Duration::from_secs(60);
+    ///
+    /// action_state.tick(t0, t0);
+    /// action_state.tick(t1, t0);
+    /// assert_eq!(action_state.idle_duration(), Duration::from_secs(60));
+    ///
+    /// // Time to leave the attract mode
+    /// action_state.press(&Action::Start);
+    /// assert_eq!(action_state.idle_duration(), Duration::ZERO);
+    /// ```
+    #[must_use]
+    pub fn idle_duration(&self) -> Duration {
+        if self.activity_pending {
+            return Duration::ZERO;
+        }
+
+        match (self.idle_since, self.last_tick_instant) {
+            (Some(idle_since), Some(last_tick)) => last_tick.saturating_duration_since(idle_since),
+            _ => Duration::ZERO,
+        }
+    }
+
     /// The [`Duration`] for which the action was last held or released
     ///
     /// This is a snapshot of the [`ActionState::current_duration`] state at the time
@@ -564,13 +1155,101 @@ impl<A: Actionlike> ActionState<A> {
         action_data.timing.previous_duration
     }
 
+    /// Starts remembering the durations of the last [`HoldHistory::DEFAULT_LEN`] times the `action` was held
+    ///
+    /// Use [`ActionState::set_recent_holds_len`] to remember a different number of holds.
+    pub fn track_recent_holds(&mut self, action: &A) {
+        self.set_recent_holds_len(action, HoldHistory::DEFAULT_LEN);
+    }
+
+    /// Remembers the durations of the last `len` times the `action` was held, forgetting any holds that were already remembered
+    ///
+    /// Setting the `len` to 0 stops tracking the holds of the `action`, freeing their memory.
+    pub fn set_recent_holds_len(&mut self, action: &A, len: usize) {
+        let action_data = self.action_data.entry(action.clone()).or_default();
+        action_data.hold_history = (len > 0).then(|| HoldHistory::new(len));
+    }
+
+    /// The durations of the last few times the `action` was held, from oldest to newest
+    ///
+    /// Holds are only remembered once [`ActionState::track_recent_holds`] has been called for the `action`:
+    /// until then, this is empty.
+    /// The instants at which each hold ended can be read from the [`ActionData::hold_history`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use bevy::utils::{Duration, Instant};
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Drum,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// action_state.track_recent_holds(&Action::Drum);
+    /// let mut now = Instant::now();
+    ///
+    /// for beat in [100, 100, 300] {
+    ///     action_state.press(&Action::Drum);
+    ///     action_state.tick(now, now);
+    ///     action_state.tick(now + Duration::from_millis(beat), now);
+    ///     action_state.release(&Action::Drum);
+    ///     now += Duration::from_millis(beat);
+    /// }
+    ///
+    /// assert_eq!(
+    ///     action_state.recent_hold_durations(&Action::Drum),
+    ///     [100, 100, 300].map(Duration::from_millis)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn recent_hold_durations(&self, action: &A) -> &[Duration] {
+        self.action_data(action)
+            .and_then(|action_data| action_data.hold_history.as_ref())
+            .map_or(&[], HoldHistory::durations)
+    }
+
+    /// The number of ticks for which the action has been held or released
+    ///
+    /// This counts the calls to [`ActionState::tick`] since the action was last pressed or released,
+    /// and does not advance while the action is consumed.
+    ///
+    /// This will be 0 if the action was never pressed or released.
+    pub fn current_frame_count(&self, action: &A) -> u32 {
+        let Some(action_data) = self.action_data(action) else {
+            return 0;
+        };
+        action_data.timing.current_frame_count
+    }
+
+    /// The number of ticks for which the action was last held or released
+    ///
+    /// This is a snapshot of the [`ActionState::current_frame_count`] state at the time
+    /// the action was last pressed or released.
+    ///
+    /// This will be 0 if the action was never pressed or released.
+    pub fn previous_frame_count(&self, action: &A) -> u32 {
+        let Some(action_data) = self.action_data(action) else {
+            return 0;
+        };
+        action_data.timing.previous_frame_count
+    }
+
     /// Applies an [`ActionDiff`] (usually received over the network) to the [`ActionState`].
     ///
     /// This lets you reconstruct an [`ActionState`] from a stream of [`ActionDiff`]s
+    ///
+    /// [`ActionDiff::ValueChanged`] and [`ActionDiff::AxisPairChanged`] diffs whose `pressed` field is `false`
+    /// only change the value and axis pair of the action, leaving its [`ButtonState`] untouched.
+    ///
+    /// Diffs that press an action which is already pressed (such as the keepalives sent by [`DiffPolicy::KeepaliveEvery`](crate::action_diff::DiffPolicy::KeepaliveEvery))
+    /// only update its value and axis pair: they will not cause the action to be [`just_pressed`](Self::just_pressed) again.
     pub fn apply_diff(&mut self, action_diff: &ActionDiff<A>) {
         match action_diff {
             ActionDiff::Pressed { action } => {
-                self.press(action);
+                self.press_if_released(action);
                 // Pressing will initialize the ActionData if it doesn't exist
                 self.action_data_mut(action).unwrap().value = 1.;
             }
@@ -580,22 +1259,95 @@ impl<A: Actionlike> ActionState<A> {
                 let action_data = self.action_data_mut(action).unwrap();
                 action_data.value = 0.;
                 action_data.axis_pair = None;
+                action_data.axis_triple = None;
             }
-            ActionDiff::ValueChanged { action, value } => {
-                self.press(action);
-                // Pressing will initialize the ActionData if it doesn't exist
-                self.action_data_mut(action).unwrap().value = *value;
+            ActionDiff::ValueChanged {
+                action,
+                value,
+                pressed,
+            } => {
+                if *pressed {
+                    self.press_if_released(action);
+                }
+                // Released actions keep their state, but still need their ActionData initialized
+                self.action_data.entry(action.clone()).or_default().value = *value;
             }
-            ActionDiff::AxisPairChanged { action, axis_pair } => {
-                self.press(action);
-                let action_data = self.action_data_mut(action).unwrap();
-                // Pressing will initialize the ActionData if it doesn't exist
+            ActionDiff::AxisPairChanged {
+                action,
+                axis_pair,
+                pressed,
+            } => {
+                if *pressed {
+                    self.press_if_released(action);
+                }
+                let action_data = self.action_data.entry(action.clone()).or_default();
                 action_data.axis_pair = Some(DualAxisData::from_xy(*axis_pair));
                 action_data.value = axis_pair.length();
             }
         };
     }
 
+    /// Presses the `action`, unless it is already pressed
+    ///
+    /// Used when applying [`ActionDiff`]s, which may redundantly report that an action is pressed.
+    /// The [`ActionData`] for the `action` is always initialized after calling this.
+    fn press_if_released(&mut self, action: &A) {
+        if !self.pressed(action) {
+            self.press(action);
+        }
+    }
+
+    /// Creates a list of [`ActionDiff`]s that describes the current state of every action in this [`ActionState`].
+    ///
+    /// Applying these diffs to a default [`ActionState`] with [`ActionState::apply_diff`]
+    /// will reproduce the [`ButtonState`], value and axis pair of each action (but not their timing information).
+    /// This is useful to periodically resynchronize clients, or to join a game that is already in progress.
+    ///
+    /// The diffs are sorted by action, so for enum actions equivalent states always produce the same list.
+    #[must_use]
+    pub fn summarize(&self) -> Vec<ActionDiff<A>> {
+        let mut diffs = Vec::with_capacity(self.action_data.len());
+        for (action, action_data) in self.action_data.iter() {
+            let action = action.clone();
+            if action_data.state.released() {
+                diffs.push(ActionDiff::Released {
+                    action: action.clone(),
+                });
+                if let Some(axis_pair) = action_data.axis_pair {
+                    if axis_pair.length() != 0. {
+                        diffs.push(ActionDiff::AxisPairChanged {
+                            action,
+                            axis_pair: axis_pair.into(),
+                            pressed: false,
+                        });
+                    }
+                } else if action_data.value != 0. {
+                    diffs.push(ActionDiff::ValueChanged {
+                        action,
+                        value: action_data.value,
+                        pressed: false,
+                    });
+                }
+            } else if let Some(axis_pair) = action_data.axis_pair {
+                diffs.push(ActionDiff::AxisPairChanged {
+                    action,
+                    axis_pair: axis_pair.into(),
+                    pressed: true,
+                });
+            } else if action_data.value == 1. {
+                diffs.push(ActionDiff::Pressed { action });
+            } else {
+                diffs.push(ActionDiff::ValueChanged {
+                    action,
+                    value: action_data.value,
+                    pressed: true,
+                });
+            }
+        }
+        sort_by_action(&mut diffs);
+        diffs
+    }
+
     /// Returns an owned list of the [`Actionlike`] keys in this [`ActionState`].
     #[inline]
     #[must_use]
@@ -604,10 +1356,148 @@ impl<A: Actionlike> ActionState<A> {
     }
 }
 
+/// A copy of an [`ActionState`] that is advanced once per fixed timestep, for use in [`FixedUpdate`](bevy::app::FixedUpdate)
+///
+/// The [`ActionState`] is ticked once per frame, so systems in `FixedUpdate` will either miss `just_pressed` and `just_released`
+/// (when several frames pass between fixed timesteps), or see them repeatedly (when several fixed timesteps run in a single frame).
+/// Instead, this state follows the presses and releases of the [`ActionState`] it is paired with,
+/// but is only ticked at the start of each fixed timestep:
+/// every press and release is `just_pressed` or `just_released` for exactly one fixed timestep,
+/// regardless of the ratio between the frame rate and the fixed timestep.
+/// If an action is pressed and released again before the next fixed timestep,
+/// the release is delayed until the timestep after the press was observed.
+///
+/// This is added to each entity with an [`ActionState`] (and as a resource, if the [`ActionState`] is a resource)
+/// by plugins configured with [`InputManagerPlugin::use_fixed_action_state`](crate::plugin::InputManagerPlugin::use_fixed_action_state).
+/// Systems reading it should run in `FixedUpdate`, after [`InputManagerSystem::FixedTick`](crate::plugin::InputManagerSystem::FixedTick).
+/// Durations are measured using [`Time<Fixed>`](bevy::time::Fixed).
+#[derive(Resource, Component, Clone, Debug, PartialEq)]
+pub struct FixedActionState<A: Actionlike> {
+    /// The state seen by systems in `FixedUpdate`
+    action_state: ActionState<A>,
+    /// The presses (`true`) and releases (`false`) that have not been applied to `action_state` yet, in order
+    pending: HashMap<A, VecDeque<bool>>,
+}
+
+impl<A: Actionlike> Default for FixedActionState<A> {
+    fn default() -> Self {
+        Self {
+            action_state: ActionState::default(),
+            pending: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> FixedActionState<A> {
+    /// Records any presses and releases of the `action_state` since it was last recorded
+    ///
+    /// These are applied one at a time by [`FixedActionState::step`].
+    pub fn record_transitions(&mut self, action_state: &ActionState<A>) {
+        for action in action_state.keys() {
+            let pressed = action_state.pressed(&action);
+            let pending = self.pending.entry(action.clone()).or_default();
+            let last_pressed = pending
+                .back()
+                .copied()
+                .unwrap_or_else(|| self.action_state.pressed(&action));
+            if pressed != last_pressed {
+                pending.push_back(pressed);
+            }
+        }
+        self.pending.retain(|_, pending| !pending.is_empty());
+    }
+
+    /// Advances this state by a single fixed timestep, following the paired `action_state`
+    ///
+    /// Any presses or releases made during the previous timestep stop being `just_pressed` or `just_released`,
+    /// and the oldest pending transition of each action is applied.
+    /// Values and axis pairs are copied from the `action_state` whenever their button states agree.
+    pub fn step(
+        &mut self,
+        action_state: &ActionState<A>,
+        current_instant: Instant,
+        previous_instant: Instant,
+    ) {
+        self.record_transitions(action_state);
+        self.action_state.tick(current_instant, previous_instant);
+
+        for (action, pending) in self.pending.iter_mut() {
+            match pending.pop_front() {
+                Some(true) => self.action_state.press(action),
+                Some(false) => self.action_state.release(action),
+                None => (),
+            }
+        }
+        self.pending.retain(|_, pending| !pending.is_empty());
+
+        for action in action_state.keys() {
+            if self.action_state.pressed(&action) != action_state.pressed(&action) {
+                continue;
+            }
+            let Some(source) = action_state.action_data(&action) else {
+                continue;
+            };
+            let action_data = self.action_state.action_data.entry(action).or_default();
+            action_data.value = source.value;
+            action_data.axis_pair = source.axis_pair;
+            action_data.axis_triple = source.axis_triple;
+        }
+    }
+}
+
+impl<A: Actionlike> Deref for FixedActionState<A> {
+    type Target = ActionState<A>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.action_state
+    }
+}
+
+impl<A: Actionlike> DerefMut for FixedActionState<A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.action_state
+    }
+}
+
+/// Marks the entity whose [`ActionState`]s are copied into the [`ActionState`] resource
+///
+/// Only used by plugins configured with [`ActionStateAggregation::PrimaryEntity`](crate::plugin::ActionStateAggregation::PrimaryEntity).
+/// If several entities are marked, their states are combined as for [`ActionStateAggregation::AllEntities`](crate::plugin::ActionStateAggregation::AllEntities).
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrimaryInputSource;
+
+/// Mirrors the [`ActionState`]s of another entity onto this entity
+///
+/// The [`ActionState`]s of the source entity are copied onto this entity in [`InputManagerSystem::Relay`](crate::plugin::InputManagerSystem::Relay),
+/// so that the entities of a hierarchy (such as a weapon or a camera rig) see the same presses on the same frame as the entity that owns the [`InputMap`](crate::input_map::InputMap).
+/// The [`ActionState`] is inserted on this entity if it does not have one yet, and is then kept in sync from the next frame onwards.
+///
+/// Don't give this entity its own [`InputMap`](crate::input_map::InputMap), as the actions it presses would be overwritten.
+/// Sources can't be mirrors themselves.
+///
+/// Once the source entity is despawned or loses its [`ActionState`], or this component is removed,
+/// this entity stops being updated and its actions are released, unless it is marked with [`KeepMirroredActionState`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionStateSource(pub Entity);
+
+/// Keeps the [`ActionState`] last copied onto an [`ActionStateSource`] entity once its source is lost, rather than releasing its actions
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeepMirroredActionState;
+
+/// Freezes the [`ActionState`]s of this entity, without releasing their actions
+///
+/// While this component is present, the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) neither updates these states from inputs nor ticks them:
+/// held actions keep their durations, and `just_pressed` and `just_released` actions stay that way.
+/// Once it is removed, the states carry on from where they were frozen, and only inputs that changed in the meantime cause new presses or releases.
+///
+/// This is useful for characters driven by cutscenes or AI, which should pick up where they left off.
+/// To release the actions instead, use [`ToggleActions`](crate::plugin::ToggleActions).
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PausedInputs;
+
 #[cfg(test)]
 mod tests {
     use crate as leafwing_input_manager;
-    use crate::input_mocking::MockInput;
     use bevy::prelude::Reflect;
     use leafwing_input_manager_macros::Actionlike;
 
@@ -623,14 +1513,10 @@ mod tests {
         use crate::action_state::ActionState;
         use crate::clashing_inputs::ClashStrategy;
         use crate::input_map::InputMap;
-        use crate::input_streams::InputStreams;
-        use bevy::input::InputPlugin;
+        use crate::input_streams::{InputStreams, InputStreamsBuilder};
         use bevy::prelude::*;
         use bevy::utils::{Duration, Instant};
 
-        let mut app = App::new();
-        app.add_plugins(InputPlugin);
-
         // Action state
         let mut action_state = ActionState::<Action>::default();
 
@@ -639,7 +1525,8 @@ mod tests {
         input_map.insert(Action::Run, KeyCode::R);
 
         // Starting state
-        let input_streams = InputStreams::from_world(&app.world, None);
+        let owned_streams = InputStreamsBuilder::new().build();
+        let input_streams = InputStreams::from(&owned_streams);
         action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
 
         assert!(!action_state.pressed(&Action::Run));
@@ -648,10 +1535,8 @@ mod tests {
         assert!(!action_state.just_released(&Action::Run));
 
         // Pressing
-        app.send_input(KeyCode::R);
-        // Process the input events into Input<KeyCode> data
-        app.update();
-        let input_streams = InputStreams::from_world(&app.world, None);
+        let owned_streams = InputStreamsBuilder::new().press_key(KeyCode::R).build();
+        let input_streams = InputStreams::from(&owned_streams);
 
         action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
 
@@ -670,9 +1555,8 @@ mod tests {
         assert!(!action_state.just_released(&Action::Run));
 
         // Releasing
-        app.release_input(KeyCode::R);
-        app.update();
-        let input_streams = InputStreams::from_world(&app.world, None);
+        let owned_streams = InputStreamsBuilder::new().build();
+        let input_streams = InputStreams::from(&owned_streams);
 
         action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
 
@@ -690,4 +1574,436 @@ mod tests {
         assert!(action_state.released(&Action::Run));
         assert!(!action_state.just_released(&Action::Run));
     }
+
+    #[test]
+    fn redundant_pressed_diffs_are_idempotent() {
+        use crate::action_diff::ActionDiff;
+        use crate::action_state::ActionState;
+        use bevy::utils::{Duration, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+        let pressed = ActionDiff::Pressed {
+            action: Action::Run,
+        };
+
+        action_state.apply_diff(&pressed);
+        assert!(action_state.just_pressed(&Action::Run));
+
+        action_state.tick(Instant::now(), Instant::now() - Duration::from_micros(1));
+        action_state.apply_diff(&pressed);
+        assert!(action_state.pressed(&Action::Run));
+        assert!(!action_state.just_pressed(&Action::Run));
+
+        action_state.apply_diff(&ActionDiff::ValueChanged {
+            action: Action::Run,
+            value: 0.5,
+            pressed: true,
+        });
+        assert!(!action_state.just_pressed(&Action::Run));
+        assert_eq!(action_state.value(&Action::Run), 0.5);
+    }
+
+    #[test]
+    fn summarize_reconstructs_action_state() {
+        use crate::action_state::ActionState;
+        use crate::axislike::DualAxisData;
+        use bevy::math::Vec2;
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(&Action::Run);
+        action_state.action_data_mut(&Action::Run).unwrap().value = 1.0;
+        action_state.press(&Action::Jump);
+        action_state
+            .action_data_mut(&Action::Jump)
+            .unwrap()
+            .axis_pair = Some(DualAxisData::new(0.6, 0.8));
+        action_state.release(&Action::Hide);
+
+        let mut mirror = ActionState::<Action>::default();
+        for action_diff in action_state.summarize() {
+            mirror.apply_diff(&action_diff);
+        }
+
+        assert!(mirror.pressed(&Action::Run));
+        assert_eq!(mirror.value(&Action::Run), 1.0);
+        assert!(mirror.pressed(&Action::Jump));
+        assert_eq!(
+            mirror.axis_pair(&Action::Jump).unwrap().xy(),
+            Vec2::new(0.6, 0.8)
+        );
+        assert!(mirror.released(&Action::Hide));
+    }
+
+    #[test]
+    fn released_value_diffs_do_not_press() {
+        use crate::action_diff::ActionDiff;
+        use crate::action_state::ActionState;
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.apply_diff(&ActionDiff::ValueChanged {
+            action: Action::Run,
+            value: 0.4,
+            pressed: false,
+        });
+        assert!(action_state.released(&Action::Run));
+        assert!(!action_state.just_released(&Action::Run));
+        assert_eq!(action_state.value(&Action::Run), 0.4);
+
+        // Released values are included in summaries
+        let mut mirror = ActionState::<Action>::default();
+        for action_diff in action_state.summarize() {
+            mirror.apply_diff(&action_diff);
+        }
+        assert!(mirror.released(&Action::Run));
+        assert_eq!(mirror.value(&Action::Run), 0.4);
+    }
+
+    #[test]
+    fn sub_frame_offsets() {
+        use crate::action_state::{ActionData, ActionState};
+        use crate::buttonlike::ButtonState;
+        use bevy::utils::{Duration, HashMap, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::from_millis(16);
+        let t2 = t1 + Duration::from_millis(16);
+
+        // Nothing to measure from before the first tick
+        action_state.press_at(&Action::Run, t0);
+        assert_eq!(action_state.sub_frame_offset(&Action::Run), None);
+        action_state.release(&Action::Run);
+
+        // Pressed by inputs 7 ms into the frame
+        action_state.tick(t1, t0);
+        let mut action_data = HashMap::new();
+        let pressed = ActionData {
+            state: ButtonState::JustPressed,
+            ..Default::default()
+        };
+        action_data.insert(Action::Jump, pressed.clone());
+        action_state.update_at(action_data.clone(), t1 + Duration::from_millis(7));
+        assert!(action_state.just_pressed(&Action::Jump));
+        assert_eq!(
+            action_state.sub_frame_offset(&Action::Jump),
+            Some(Duration::from_millis(7))
+        );
+
+        // The normalized timing is unchanged
+        assert_eq!(action_state.instant_started(&Action::Jump), None);
+        assert_eq!(action_state.current_duration(&Action::Jump), Duration::ZERO);
+
+        // The offset stays the same while held
+        action_state.tick(t2, t1);
+        action_state.update_at(action_data, t2 + Duration::from_millis(3));
+        assert_eq!(action_state.instant_started(&Action::Jump), Some(t1));
+        assert_eq!(
+            action_state.sub_frame_offset(&Action::Jump),
+            Some(Duration::from_millis(7))
+        );
+
+        // And is cleared on release
+        action_state.release(&Action::Jump);
+        assert_eq!(action_state.sub_frame_offset(&Action::Jump), None);
+        assert_eq!(action_state.sub_frame_offset(&Action::Hide), None);
+    }
+
+    #[test]
+    fn idle_duration_tracks_activity() {
+        use crate::action_diff::ActionDiff;
+        use crate::action_state::{ActionData, ActionState};
+        use bevy::utils::{Duration, HashMap, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::from_secs(1);
+        let t2 = t1 + Duration::from_secs(2);
+        let t3 = t2 + Duration::from_secs(3);
+        let t4 = t3 + Duration::from_secs(4);
+
+        assert_eq!(action_state.idle_duration(), Duration::ZERO);
+        action_state.tick(t0, t0);
+        action_state.tick(t1, t0);
+        assert_eq!(action_state.idle_duration(), Duration::from_secs(1));
+
+        // Analog noise on released actions is not activity
+        let mut action_data = HashMap::new();
+        action_data.insert(
+            Action::Run,
+            ActionData {
+                value: 0.05,
+                ..Default::default()
+            },
+        );
+        action_state.update(action_data);
+        action_state.apply_diff(&ActionDiff::ValueChanged {
+            action: Action::Jump,
+            value: 0.05,
+            pressed: false,
+        });
+        assert_eq!(action_state.idle_duration(), Duration::from_secs(1));
+        assert!(action_state.released_for_at_least(&Action::Run, Duration::ZERO));
+        assert!(!action_state.released_for_at_least(&Action::Run, Duration::from_secs(1)));
+
+        // Presses from diffs are
+        action_state.apply_diff(&ActionDiff::Pressed {
+            action: Action::Jump,
+        });
+        assert_eq!(action_state.idle_duration(), Duration::ZERO);
+        action_state.tick(t2, t1);
+        assert_eq!(action_state.idle_duration(), Duration::from_secs(2));
+
+        // Redundant diffs are not
+        action_state.apply_diff(&ActionDiff::Pressed {
+            action: Action::Jump,
+        });
+        action_state.tick(t3, t2);
+        assert_eq!(action_state.idle_duration(), Duration::from_secs(5));
+
+        // Releases are, and the released duration starts from the next tick
+        action_state.apply_diff(&ActionDiff::Released {
+            action: Action::Jump,
+        });
+        assert_eq!(action_state.idle_duration(), Duration::ZERO);
+        assert!(!action_state.released_for_at_least(&Action::Jump, Duration::from_secs(1)));
+        action_state.tick(t4, t3);
+        assert_eq!(action_state.idle_duration(), Duration::from_secs(4));
+        assert!(action_state.released_for_at_least(&Action::Jump, Duration::from_secs(4)));
+        assert!(!action_state.released_for_at_least(&Action::Jump, Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn consume_policies() {
+        use crate::action_state::{ActionData, ActionState};
+        use crate::buttonlike::ButtonState;
+        use crate::timing::ConsumePolicy;
+        use bevy::utils::{Duration, HashMap, Instant};
+
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::from_secs(1);
+        let t2 = t1 + Duration::from_secs(2);
+        let t3 = t2 + Duration::from_secs(3);
+
+        let mut pressed = HashMap::new();
+        pressed.insert(
+            Action::Run,
+            ActionData {
+                state: ButtonState::JustPressed,
+                ..Default::default()
+            },
+        );
+        let mut released = HashMap::new();
+        released.insert(Action::Run, ActionData::default());
+
+        for consume_policy in [ConsumePolicy::FlipTiming, ConsumePolicy::PreserveTiming] {
+            let mut action_state = ActionState::<Action>::default();
+            action_state.set_consume_policy(Some(consume_policy));
+            assert_eq!(action_state.consume_policy(), consume_policy);
+
+            // Released for 1 s, then held for 2 s
+            action_state.update(released.clone());
+            action_state.tick(t0, t0);
+            action_state.tick(t1, t0);
+            action_state.update(pressed.clone());
+            action_state.tick(t2, t1);
+            action_state.consume(&Action::Run);
+
+            let (current_duration, previous_duration) = match consume_policy {
+                ConsumePolicy::FlipTiming => (Duration::ZERO, Duration::from_secs(2)),
+                _ => (Duration::from_secs(2), Duration::from_secs(1)),
+            };
+            assert_eq!(
+                action_state.current_duration(&Action::Run),
+                current_duration
+            );
+            assert_eq!(
+                action_state.previous_duration(&Action::Run),
+                previous_duration
+            );
+
+            // Durations don't advance while the inputs are held
+            action_state.update(pressed.clone());
+            action_state.tick(t3, t2);
+            assert_eq!(
+                action_state.current_duration(&Action::Run),
+                current_duration
+            );
+            assert_eq!(
+                action_state.previous_duration(&Action::Run),
+                previous_duration
+            );
+
+            // Once released, both policies agree
+            action_state.update(released.clone());
+            assert!(action_state.released(&Action::Run));
+            assert_eq!(action_state.current_duration(&Action::Run), Duration::ZERO);
+            assert_eq!(
+                action_state.previous_duration(&Action::Run),
+                Duration::from_secs(2)
+            );
+            assert_eq!(action_state.previous_frame_count(&Action::Run), 1);
+        }
+
+        // Consuming released actions doesn't preserve anything
+        let mut action_state = ActionState::<Action>::default();
+        action_state.set_consume_policy(Some(ConsumePolicy::PreserveTiming));
+        action_state.consume(&Action::Jump);
+        action_state.release(&Action::Jump);
+        assert!(
+            !action_state
+                .action_data(&Action::Jump)
+                .unwrap()
+                .timing_preserved
+        );
+    }
+
+    #[test]
+    fn recent_hold_durations() {
+        use crate::action_state::{ActionData, ActionState};
+        use crate::buttonlike::ButtonState;
+        use crate::timing::ConsumePolicy;
+        use bevy::utils::{Duration, HashMap, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.set_recent_holds_len(&Action::Run, 2);
+        let mut now = Instant::now();
+        let hold = |action_state: &mut ActionState<Action>, now: &mut Instant, millis: u64| {
+            let mut action_data = HashMap::new();
+            action_data.insert(
+                Action::Run,
+                ActionData {
+                    state: ButtonState::JustPressed,
+                    ..Default::default()
+                },
+            );
+            action_state.update(action_data);
+            action_state.tick(*now, *now);
+            let previous = *now;
+            *now += Duration::from_millis(millis);
+            action_state.tick(*now, previous);
+        };
+
+        // Holds are recorded on release, and only the last two are kept
+        for millis in [10, 20, 30] {
+            hold(&mut action_state, &mut now, millis);
+            action_state.release(&Action::Run);
+        }
+        assert_eq!(
+            action_state.recent_hold_durations(&Action::Run),
+            [20, 30].map(Duration::from_millis)
+        );
+        let hold_history = action_state
+            .action_data(&Action::Run)
+            .unwrap()
+            .hold_history
+            .as_ref()
+            .unwrap();
+        assert_eq!(hold_history.release_instants().last(), Some(&Some(now)));
+
+        // Consumed holds are recorded once, whatever the consume policy
+        hold(&mut action_state, &mut now, 40);
+        action_state.consume(&Action::Run);
+        action_state.release(&Action::Run);
+        action_state.set_consume_policy(Some(ConsumePolicy::PreserveTiming));
+        hold(&mut action_state, &mut now, 50);
+        action_state.consume(&Action::Run);
+        assert_eq!(
+            action_state.recent_hold_durations(&Action::Run),
+            [30, 40].map(Duration::from_millis)
+        );
+        action_state.release(&Action::Run);
+        assert_eq!(
+            action_state.recent_hold_durations(&Action::Run),
+            [40, 50].map(Duration::from_millis)
+        );
+
+        // Untracked actions never allocate a history
+        action_state.press(&Action::Jump);
+        action_state.release(&Action::Jump);
+        assert!(action_state.recent_hold_durations(&Action::Jump).is_empty());
+        assert!(action_state
+            .action_data(&Action::Jump)
+            .unwrap()
+            .hold_history
+            .is_none());
+
+        action_state.set_recent_holds_len(&Action::Run, 0);
+        assert!(action_state.recent_hold_durations(&Action::Run).is_empty());
+    }
+
+    #[test]
+    fn last_tick_delta() {
+        use crate::action_state::{ActionData, ActionState};
+        use crate::timing::{DoubleTickPolicy, TimeScalePolicies, TimeScalePolicy};
+        use bevy::utils::{Duration, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.set_action_data(
+            Action::Run,
+            ActionData {
+                value: 0.5,
+                ..Default::default()
+            },
+        );
+        assert_eq!(action_state.last_tick_delta(), Duration::ZERO);
+        assert_eq!(action_state.integrated_value(&Action::Run), 0.0);
+
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::from_millis(500);
+        action_state.tick(t1, t0);
+        assert_eq!(action_state.last_tick_delta(), Duration::from_millis(500));
+        assert_eq!(action_state.integrated_value(&Action::Run), 0.25);
+        assert_eq!(action_state.integrated_value(&Action::Jump), 0.0);
+
+        // Skipped ticks don't change the delta
+        let t2 = t1 + Duration::from_millis(100);
+        assert!(action_state.tick_in_frame(1, t2, t1, DoubleTickPolicy::Skip));
+        assert!(!action_state.tick_in_frame(1, t2, t2, DoubleTickPolicy::Skip));
+        assert_eq!(action_state.last_tick_delta(), Duration::from_millis(100));
+
+        // Time scales use the scaled delta
+        let mut policies = TimeScalePolicies::default();
+        policies.insert(Action::Run, TimeScalePolicy::Unscaled);
+        let t3 = t2 + Duration::from_millis(20);
+        action_state.tick_with_time_scales(t3, t2, t3 + Duration::from_millis(20), t2, &policies);
+        assert_eq!(action_state.last_tick_delta(), Duration::from_millis(20));
+    }
+
+    #[test]
+    fn unscaled_actions_integrate_over_real_time() {
+        use crate::action_state::{ActionData, ActionState};
+        use crate::timing::{TimeScalePolicies, TimeScalePolicy};
+        use bevy::utils::{Duration, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+        for action in [Action::Run, Action::Jump] {
+            action_state.set_action_data(
+                action,
+                ActionData {
+                    value: 0.5,
+                    ..Default::default()
+                },
+            );
+        }
+        let mut policies = TimeScalePolicies::default();
+        policies.insert(Action::Run, TimeScalePolicy::Unscaled);
+
+        // Virtual time runs at a quarter of the speed of real time
+        let t0 = Instant::now();
+        action_state.tick_with_time_scales(
+            t0 + Duration::from_millis(250),
+            t0,
+            t0 + Duration::from_secs(1),
+            t0,
+            &policies,
+        );
+        assert_eq!(action_state.integrated_value(&Action::Run), 0.5);
+        assert_eq!(action_state.integrated_value(&Action::Jump), 0.125);
+
+        // Plain ticks aren't scaled
+        let t1 = t0 + Duration::from_secs(1);
+        action_state.tick(t1 + Duration::from_millis(500), t1);
+        assert_eq!(action_state.integrated_value(&Action::Run), 0.25);
+        assert_eq!(action_state.integrated_value(&Action::Jump), 0.25);
+    }
 }
@@ -7,8 +7,9 @@ use crate::user_input::InputKind;
 use bevy::input::{
     gamepad::{GamepadAxisType, GamepadButtonType},
     keyboard::KeyCode,
+    touch::ForceTouch,
 };
-use bevy::math::Vec2;
+use bevy::math::{Vec2, Vec3};
 use bevy::reflect::Reflect;
 use bevy::utils::FloatOrd;
 use serde::{Deserialize, Serialize};
@@ -124,6 +125,90 @@ impl SingleAxis {
         }
     }
 
+    /// Creates a [`SingleAxis`] corresponding to how hard a pen is pressed against the touch screen
+    ///
+    /// See [`PenAxisType::Pressure`].
+    #[must_use]
+    pub const fn pen_pressure() -> SingleAxis {
+        SingleAxis {
+            axis_type: AxisType::Pen(PenAxisType::Pressure),
+            positive_low: 0.,
+            negative_low: 0.,
+            inverted: false,
+            sensitivity: 1.0,
+            value: None,
+        }
+    }
+
+    /// Creates a [`SingleAxis`] corresponding to how far a pen is tilted away from the touch screen's normal
+    ///
+    /// See [`PenAxisType::Tilt`].
+    #[must_use]
+    pub const fn pen_tilt() -> SingleAxis {
+        SingleAxis {
+            axis_type: AxisType::Pen(PenAxisType::Tilt),
+            positive_low: 0.,
+            negative_low: 0.,
+            inverted: false,
+            sensitivity: 1.0,
+            value: None,
+        }
+    }
+
+    /// Creates a [`SingleAxis`] corresponding to how far a pen leans towards the right of the touch screen
+    ///
+    /// See [`PenAxisType::TiltX`].
+    #[must_use]
+    pub const fn pen_tilt_x() -> SingleAxis {
+        SingleAxis {
+            axis_type: AxisType::Pen(PenAxisType::TiltX),
+            positive_low: 0.,
+            negative_low: 0.,
+            inverted: false,
+            sensitivity: 1.0,
+            value: None,
+        }
+    }
+
+    /// Creates a [`SingleAxis`] corresponding to how far a pen leans towards the top of the touch screen
+    ///
+    /// See [`PenAxisType::TiltY`].
+    #[must_use]
+    pub const fn pen_tilt_y() -> SingleAxis {
+        SingleAxis {
+            axis_type: AxisType::Pen(PenAxisType::TiltY),
+            positive_low: 0.,
+            negative_low: 0.,
+            inverted: false,
+            sensitivity: 1.0,
+            value: None,
+        }
+    }
+
+    /// Creates a [`SingleAxis`] for the horizontal axis of the left analogue stick of the gamepad.
+    #[must_use]
+    pub fn left_stick_x() -> SingleAxis {
+        SingleAxis::symmetric(GamepadAxisType::LeftStickX, DualAxis::DEFAULT_DEADZONE)
+    }
+
+    /// Creates a [`SingleAxis`] for the vertical axis of the left analogue stick of the gamepad.
+    #[must_use]
+    pub fn left_stick_y() -> SingleAxis {
+        SingleAxis::symmetric(GamepadAxisType::LeftStickY, DualAxis::DEFAULT_DEADZONE)
+    }
+
+    /// Creates a [`SingleAxis`] for the horizontal axis of the right analogue stick of the gamepad.
+    #[must_use]
+    pub fn right_stick_x() -> SingleAxis {
+        SingleAxis::symmetric(GamepadAxisType::RightStickX, DualAxis::DEFAULT_DEADZONE)
+    }
+
+    /// Creates a [`SingleAxis`] for the vertical axis of the right analogue stick of the gamepad.
+    #[must_use]
+    pub fn right_stick_y() -> SingleAxis {
+        SingleAxis::symmetric(GamepadAxisType::RightStickY, DualAxis::DEFAULT_DEADZONE)
+    }
+
     /// Creates a [`SingleAxis`] with the `axis_type` and `negative_low` set to `threshold`.
     ///
     /// Positive values will not trigger the input.
@@ -160,6 +245,35 @@ impl SingleAxis {
         self
     }
 
+    /// Returns this [`SingleAxis`] triggered only by values higher than `threshold`, like [`SingleAxis::positive_only`]
+    ///
+    /// This is useful to add a direction to a chord, such as pushing the stick forward while clicking it:
+    ///
+    /// ```rust
+    /// use bevy::input::gamepad::GamepadButtonType;
+    /// use leafwing_input_manager::prelude::*;
+    /// use leafwing_input_manager::user_input::InputKind;
+    ///
+    /// let sprint = UserInput::chord([
+    ///     InputKind::from(GamepadButtonType::LeftThumb),
+    ///     SingleAxis::left_stick_y().above(0.5).into(),
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn above(mut self, threshold: f32) -> SingleAxis {
+        self.negative_low = f32::MIN;
+        self.positive_low = threshold;
+        self
+    }
+
+    /// Returns this [`SingleAxis`] triggered only by values lower than `threshold`, like [`SingleAxis::negative_only`]
+    #[must_use]
+    pub fn below(mut self, threshold: f32) -> SingleAxis {
+        self.negative_low = threshold;
+        self.positive_low = f32::MAX;
+        self
+    }
+
     /// Returns this [`SingleAxis`] with the sensitivity set to the specified value
     #[must_use]
     pub fn with_sensitivity(mut self, sensitivity: f32) -> SingleAxis {
@@ -305,6 +419,28 @@ impl DualAxis {
         }
     }
 
+    /// Creates a [`DualAxis`] corresponding to the direction a pen leans towards, whose length is how far it is tilted
+    ///
+    /// See [`PenAxisType::TiltX`] and [`PenAxisType::TiltY`].
+    pub const fn pen_tilt() -> DualAxis {
+        DualAxis {
+            x: SingleAxis::pen_tilt_x(),
+            y: SingleAxis::pen_tilt_y(),
+            deadzone: Self::ZERO_DEADZONE_SHAPE,
+        }
+    }
+
+    #[allow(clippy::doc_markdown)] // False alarm because it thinks DPad is an un-quoted item
+    /// Creates a [`VirtualDPad`] for the DPad of the gamepad, like [`VirtualDPad::dpad`] but [normalized](VirtualDPad::normalized)
+    ///
+    /// The DPad is made of buttons rather than analog axes, so it can't be read as a [`DualAxis`].
+    /// This is provided for discoverability next to [`DualAxis::left_stick`] and [`DualAxis::right_stick`],
+    /// and like them never reads a diagonal longer than 1.
+    #[must_use]
+    pub fn dpad() -> VirtualDPad {
+        VirtualDPad::dpad().normalized()
+    }
+
     /// Returns this [`DualAxis`] with the deadzone set to the specified values and shape
     #[must_use]
     pub fn with_deadzone(mut self, deadzone: DeadZoneShape) -> DualAxis {
@@ -360,6 +496,13 @@ pub struct VirtualDPad {
     pub left: InputKind,
     /// The input that represents the right direction in this virtual DPad
     pub right: InputKind,
+    /// Should diagonals be scaled down to a length of 1?
+    ///
+    /// Otherwise, pressing two directions at once produces a diagonal of length `sqrt(2)`,
+    /// which moves faster than pressing a single direction.
+    /// Set this using [`VirtualDPad::normalized`].
+    #[serde(default)]
+    pub normalize_diagonals: bool,
 }
 
 impl VirtualDPad {
@@ -370,6 +513,7 @@ impl VirtualDPad {
             down: InputKind::Keyboard(KeyCode::Down),
             left: InputKind::Keyboard(KeyCode::Left),
             right: InputKind::Keyboard(KeyCode::Right),
+            normalize_diagonals: false,
         }
     }
 
@@ -385,17 +529,22 @@ impl VirtualDPad {
             down: InputKind::KeyLocation(QwertyScanCode::S.into()),
             left: InputKind::KeyLocation(QwertyScanCode::A.into()),
             right: InputKind::KeyLocation(QwertyScanCode::D.into()),
+            normalize_diagonals: false,
         }
     }
 
     #[allow(clippy::doc_markdown)] // False alarm because it thinks DPad is an un-quoted item
     /// Generates a [`VirtualDPad`] corresponding to the DPad on a gamepad
+    ///
+    /// Like any other gamepad input, only the gamepad associated with the [`InputMap`](crate::input_map::InputMap) is read, if there is one.
+    /// This is displayed as "D-Pad".
     pub fn dpad() -> VirtualDPad {
         VirtualDPad {
             up: InputKind::GamepadButton(GamepadButtonType::DPadUp),
             down: InputKind::GamepadButton(GamepadButtonType::DPadDown),
             left: InputKind::GamepadButton(GamepadButtonType::DPadLeft),
             right: InputKind::GamepadButton(GamepadButtonType::DPadRight),
+            normalize_diagonals: false,
         }
     }
 
@@ -408,6 +557,7 @@ impl VirtualDPad {
             down: InputKind::GamepadButton(GamepadButtonType::South),
             left: InputKind::GamepadButton(GamepadButtonType::West),
             right: InputKind::GamepadButton(GamepadButtonType::East),
+            normalize_diagonals: false,
         }
     }
 
@@ -418,6 +568,7 @@ impl VirtualDPad {
             down: InputKind::MouseWheel(MouseWheelDirection::Down),
             left: InputKind::MouseWheel(MouseWheelDirection::Left),
             right: InputKind::MouseWheel(MouseWheelDirection::Right),
+            normalize_diagonals: false,
         }
     }
 
@@ -428,6 +579,7 @@ impl VirtualDPad {
             down: InputKind::MouseMotion(MouseMotionDirection::Down),
             left: InputKind::MouseMotion(MouseMotionDirection::Left),
             right: InputKind::MouseMotion(MouseMotionDirection::Right),
+            normalize_diagonals: false,
         }
     }
 
@@ -449,6 +601,21 @@ impl VirtualDPad {
         std::mem::swap(&mut self.left, &mut self.right);
         self
     }
+
+    /// Returns this [`VirtualDPad`] but with its diagonals scaled down to a length of 1
+    ///
+    /// Inputs shorter than 1, such as partially pulled analog buttons, are left unchanged.
+    #[must_use]
+    pub fn normalized(mut self) -> Self {
+        self.normalize_diagonals = true;
+        self
+    }
+
+    /// Are the inputs of this [`VirtualDPad`] the d-pad buttons of a gamepad, as in [`VirtualDPad::dpad`]?
+    pub(crate) fn is_gamepad_dpad(&self) -> bool {
+        let dpad = VirtualDPad::dpad();
+        [self.up, self.down, self.left, self.right] == [dpad.up, dpad.down, dpad.left, dpad.right]
+    }
 }
 
 /// A virtual Axis that you can get a value between -1 and 1 from.
@@ -521,6 +688,180 @@ impl VirtualAxis {
     }
 }
 
+/// Two [`DualAxis`] read as a single input, such as both analog sticks of a gamepad, only one of which is used at a time
+///
+/// Binding two [`DualAxis`] to the same action adds up their values, which rarely makes sense for a direction.
+/// Instead, the [`DualAxisPolicy`] picks which of the two is read each frame.
+///
+/// This input is pressed whenever either [`DualAxis`] is outside of its deadzone.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Reflect)]
+pub struct EitherDualAxis {
+    /// The first [`DualAxis`], which is preferred by [`DualAxisPolicy::PreferFirstActive`]
+    pub first: DualAxis,
+    /// The second [`DualAxis`]
+    pub second: DualAxis,
+    /// How to pick which [`DualAxis`] is read when both are active
+    pub policy: DualAxisPolicy,
+}
+
+impl EitherDualAxis {
+    /// Creates an [`EitherDualAxis`] that reads either `first` or `second` according to the `policy`
+    #[must_use]
+    pub fn new(first: DualAxis, second: DualAxis, policy: DualAxisPolicy) -> EitherDualAxis {
+        EitherDualAxis {
+            first,
+            second,
+            policy,
+        }
+    }
+
+    /// Creates an [`EitherDualAxis`] for the left and right analogue sticks of the gamepad, preferring the left stick
+    #[must_use]
+    pub fn sticks(policy: DualAxisPolicy) -> EitherDualAxis {
+        EitherDualAxis::new(DualAxis::left_stick(), DualAxis::right_stick(), policy)
+    }
+
+    /// Picks the value to read, given the values of the `first` and `second` [`DualAxis`]
+    ///
+    /// Each value is [`None`] if its [`DualAxis`] is within its deadzone.
+    #[must_use]
+    pub fn pick(
+        &self,
+        first: Option<DualAxisData>,
+        second: Option<DualAxisData>,
+    ) -> Option<DualAxisData> {
+        match (first, second) {
+            (Some(first), Some(second)) => match self.policy {
+                DualAxisPolicy::LargestMagnitude => {
+                    if second.length_squared() > first.length_squared() {
+                        Some(second)
+                    } else {
+                        Some(first)
+                    }
+                }
+                DualAxisPolicy::PreferFirstActive => Some(first),
+            },
+            (first, second) => first.or(second),
+        }
+    }
+}
+
+/// How an [`EitherDualAxis`] picks which of its [`DualAxis`] is read when both are active
+///
+/// When only one of them is outside of its deadzone, that one is always read,
+/// so switching from one stick to the other never reads a centered stick in between.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Reflect)]
+pub enum DualAxisPolicy {
+    /// Read the [`DualAxis`] that is pushed the furthest, preferring the first one on ties
+    #[default]
+    LargestMagnitude,
+    /// Read the first [`DualAxis`] whenever it is active, and the second one otherwise
+    PreferFirstActive,
+}
+
+/// Three [`SingleAxis`] read together, such as the pitch, yaw and roll of a gyroscope
+///
+/// The resulting values are stored in [`ActionState::axis_triple`](crate::action_state::ActionState::axis_triple).
+/// Unlike [`DualAxis`], each axis uses its own deadzone, set by the thresholds of its [`SingleAxis`].
+///
+/// This input is pressed whenever any of its axes is outside of its deadzone.
+/// In a chord, the other inputs act as a gate: the [`TripleAxis`] is only read while they are all pressed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Reflect)]
+pub struct TripleAxis {
+    /// The axis representing movement along or around the x-axis.
+    pub x: SingleAxis,
+    /// The axis representing movement along or around the y-axis.
+    pub y: SingleAxis,
+    /// The axis representing movement along or around the z-axis.
+    pub z: SingleAxis,
+}
+
+impl TripleAxis {
+    /// Creates a [`TripleAxis`] with both `positive_low` and `negative_low` in all axes set to `threshold`.
+    #[must_use]
+    pub fn symmetric(
+        x_axis_type: impl Into<AxisType>,
+        y_axis_type: impl Into<AxisType>,
+        z_axis_type: impl Into<AxisType>,
+        threshold: f32,
+    ) -> TripleAxis {
+        TripleAxis {
+            x: SingleAxis::symmetric(x_axis_type, threshold),
+            y: SingleAxis::symmetric(y_axis_type, threshold),
+            z: SingleAxis::symmetric(z_axis_type, threshold),
+        }
+    }
+
+    /// Creates a [`TripleAxis`] with the specified axis types and values.
+    ///
+    /// All thresholds are set to 0.0.
+    /// Primarily useful for [input mocking](crate::input_mocking).
+    #[must_use]
+    pub fn from_value(
+        x_axis_type: impl Into<AxisType>,
+        y_axis_type: impl Into<AxisType>,
+        z_axis_type: impl Into<AxisType>,
+        xyz: Vec3,
+    ) -> TripleAxis {
+        TripleAxis {
+            x: SingleAxis::from_value(x_axis_type, xyz.x),
+            y: SingleAxis::from_value(y_axis_type, xyz.y),
+            z: SingleAxis::from_value(z_axis_type, xyz.z),
+        }
+    }
+
+    /// Creates a [`TripleAxis`] for the rotation rate of a gyroscope, without any deadzone
+    ///
+    /// Gyroscopes are usually exposed by the gamepad backend or a companion crate as [`GamepadAxisType::Other`] axes.
+    /// The pitch is stored in the x-axis, the yaw in the y-axis and the roll in the z-axis.
+    ///
+    /// To only aim with the gyroscope while the left trigger is pulled, bind it in a chord,
+    /// such as `UserInput::chord([InputKind::from(AnalogButton::left_trigger(0.1)), InputKind::from(gyro)])`.
+    #[must_use]
+    pub fn gyro(
+        pitch_axis: GamepadAxisType,
+        yaw_axis: GamepadAxisType,
+        roll_axis: GamepadAxisType,
+    ) -> TripleAxis {
+        TripleAxis::symmetric(pitch_axis, yaw_axis, roll_axis, 0.0)
+    }
+
+    /// Returns this [`TripleAxis`] with the sensitivity set to the specified values
+    #[must_use]
+    pub fn with_sensitivity(
+        mut self,
+        x_sensitivity: f32,
+        y_sensitivity: f32,
+        z_sensitivity: f32,
+    ) -> TripleAxis {
+        self.x.sensitivity = x_sensitivity;
+        self.y.sensitivity = y_sensitivity;
+        self.z.sensitivity = z_sensitivity;
+        self
+    }
+
+    /// Returns this [`TripleAxis`] with an inverted X-axis.
+    #[must_use]
+    pub fn inverted_x(mut self) -> TripleAxis {
+        self.x = self.x.inverted();
+        self
+    }
+
+    /// Returns this [`TripleAxis`] with an inverted Y-axis.
+    #[must_use]
+    pub fn inverted_y(mut self) -> TripleAxis {
+        self.y = self.y.inverted();
+        self
+    }
+
+    /// Returns this [`TripleAxis`] with an inverted Z-axis.
+    #[must_use]
+    pub fn inverted_z(mut self) -> TripleAxis {
+        self.z = self.z.inverted();
+        self
+    }
+}
+
 /// The type of axis used by a [`UserInput`](crate::user_input::UserInput).
 ///
 /// This is stored in either a [`SingleAxis`] or [`DualAxis`].
@@ -532,6 +873,8 @@ pub enum AxisType {
     MouseWheel(MouseWheelAxisType),
     /// Input associated with movement of the mouse
     MouseMotion(MouseMotionAxisType),
+    /// Input associated with a pen, or stylus, on the touch screen
+    Pen(PenAxisType),
 }
 
 /// The direction of motion of the mouse wheel.
@@ -560,6 +903,75 @@ pub enum MouseMotionAxisType {
     Y,
 }
 
+/// The measurements of a pen, or stylus, on the touch screen
+///
+/// These are read from the [`ForceTouch`](bevy::input::touch::ForceTouch) of the pressed [`Touch`](bevy::input::touch::Touch) with the most force,
+/// and are `0.0` on platforms that don't report the force of touches.
+///
+/// Stored in the [`AxisType`] enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+pub enum PenAxisType {
+    /// How hard the pen is pressed, from `0.0` to `1.0` at the maximum force the device can measure
+    Pressure,
+    /// How far the pen is tilted, from `0.0` while it is perpendicular to the screen to `1.0` while it lies flat on it
+    Tilt,
+    /// How far the pen leans towards the right of the screen, from `-1.0` to `1.0`
+    ///
+    /// Together with [`PenAxisType::TiltY`], this makes up [`DualAxis::pen_tilt`], whose length is the [`PenAxisType::Tilt`].
+    /// Bevy only reports the altitude of the pen, and not its azimuth, which is the direction it leans towards.
+    /// Until it does, the pen is read as leaning to the right, so this is equal to the [`PenAxisType::Tilt`].
+    TiltX,
+    /// How far the pen leans towards the top of the screen, from `-1.0` to `1.0`
+    ///
+    /// This is always `0.0` for now, as the direction the pen leans towards is unknown. See [`PenAxisType::TiltX`].
+    TiltY,
+}
+
+impl PenAxisType {
+    /// The value of this axis for a touch with the `force`
+    ///
+    /// Devices that report a maximum force of zero read their raw force as the pressure.
+    #[must_use]
+    pub fn value(self, force: ForceTouch) -> f32 {
+        let value = match (self, force) {
+            (
+                PenAxisType::Pressure,
+                ForceTouch::Calibrated {
+                    force,
+                    max_possible_force,
+                    ..
+                },
+            ) => {
+                if max_possible_force.is_finite() && max_possible_force > 0.0 {
+                    force / max_possible_force
+                } else {
+                    force
+                }
+            }
+            (PenAxisType::Pressure, ForceTouch::Normalized(force)) => force,
+            (
+                PenAxisType::Tilt | PenAxisType::TiltX,
+                ForceTouch::Calibrated {
+                    altitude_angle: Some(altitude_angle),
+                    ..
+                },
+            ) => 1.0 - altitude_angle / std::f64::consts::FRAC_PI_2,
+            (PenAxisType::Tilt | PenAxisType::TiltX | PenAxisType::TiltY, _) => 0.0,
+        };
+
+        let min = match self {
+            PenAxisType::Pressure | PenAxisType::Tilt => 0.0,
+            PenAxisType::TiltX | PenAxisType::TiltY => -1.0,
+        };
+        let value = value as f32;
+        if value.is_finite() {
+            value.clamp(min, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
 impl From<GamepadAxisType> for AxisType {
     fn from(axis_type: GamepadAxisType) -> Self {
         AxisType::Gamepad(axis_type)
@@ -578,6 +990,12 @@ impl From<MouseMotionAxisType> for AxisType {
     }
 }
 
+impl From<PenAxisType> for AxisType {
+    fn from(axis_type: PenAxisType) -> Self {
+        AxisType::Pen(axis_type)
+    }
+}
+
 impl TryFrom<AxisType> for GamepadAxisType {
     type Error = AxisConversionError;
 
@@ -611,6 +1029,17 @@ impl TryFrom<AxisType> for MouseMotionAxisType {
     }
 }
 
+impl TryFrom<AxisType> for PenAxisType {
+    type Error = AxisConversionError;
+
+    fn try_from(axis_type: AxisType) -> Result<Self, AxisConversionError> {
+        match axis_type {
+            AxisType::Pen(inner) => Ok(inner),
+            _ => Err(AxisConversionError),
+        }
+    }
+}
+
 /// An [`AxisType`] could not be converted into a more specialized variant
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct AxisConversionError;
@@ -738,6 +1167,76 @@ impl From<DualAxisData> for Vec2 {
     }
 }
 
+/// A wrapped [`Vec3`] that represents the combination of three input axes.
+///
+/// The neutral origin is always at 0, 0, 0.
+/// When working with gamepad axes, the values are typically bounded by [-1.0, 1.0],
+/// but gyroscopes report rotation rates, which may not be.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Deserialize, Serialize, Reflect)]
+pub struct TripleAxisData {
+    xyz: Vec3,
+}
+
+impl TripleAxisData {
+    /// Creates a new [`TripleAxisData`] from the provided (x, y, z) coordinates
+    pub fn new(x: f32, y: f32, z: f32) -> TripleAxisData {
+        TripleAxisData {
+            xyz: Vec3::new(x, y, z),
+        }
+    }
+
+    /// Creates a new [`TripleAxisData`] directly from a [`Vec3`]
+    pub fn from_xyz(xyz: Vec3) -> TripleAxisData {
+        TripleAxisData { xyz }
+    }
+
+    /// Merge the state of this [`TripleAxisData`] with another, by adding them together
+    pub fn merged_with(&self, other: TripleAxisData) -> TripleAxisData {
+        TripleAxisData::from_xyz(self.xyz + other.xyz)
+    }
+
+    /// The value along the x-axis
+    #[must_use]
+    #[inline]
+    pub fn x(&self) -> f32 {
+        self.xyz.x
+    }
+
+    /// The value along the y-axis
+    #[must_use]
+    #[inline]
+    pub fn y(&self) -> f32 {
+        self.xyz.y
+    }
+
+    /// The value along the z-axis
+    #[must_use]
+    #[inline]
+    pub fn z(&self) -> f32 {
+        self.xyz.z
+    }
+
+    /// The (x, y, z) values
+    #[must_use]
+    #[inline]
+    pub fn xyz(&self) -> Vec3 {
+        self.xyz
+    }
+
+    /// How far from the origin is this position?
+    #[must_use]
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.xyz.length()
+    }
+}
+
+impl From<TripleAxisData> for Vec3 {
+    fn from(data: TripleAxisData) -> Vec3 {
+        data.xyz
+    }
+}
+
 /// The shape of the deadzone for a [`DualAxis`] input.
 ///
 /// Input values that are on the boundary of the shape are counted as inside.
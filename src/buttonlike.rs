@@ -1,6 +1,8 @@
 //! Tools for working with button-like user inputs (mouse clicks, gamepad button, keyboard inputs and so on)
 //!
+use bevy::input::gamepad::GamepadButtonType;
 use bevy::reflect::Reflect;
+use bevy::utils::FloatOrd;
 use serde::{Deserialize, Serialize};
 
 /// The current state of a particular button,
@@ -113,3 +115,106 @@ pub enum MouseMotionDirection {
     /// Corresponds to `-x`
     Left,
 }
+
+/// A gamepad button with an analog value, such as a trigger, that is pressed once its value reaches the `press_threshold`
+///
+/// Unlike [`InputKind::GamepadButton`](crate::user_input::InputKind::GamepadButton),
+/// which is pressed according to Bevy's [`GamepadSettings`](bevy::input::gamepad::GamepadSettings),
+/// each binding chooses its own thresholds.
+/// This lets several actions share the same trigger, such as aiming when it is pulled halfway and firing when it is pulled all the way.
+/// Pulling the trigger all the way presses both actions: they don't clash, since neither is a chord.
+///
+/// Once pressed, the button stays pressed until its value falls to the `release_threshold` or below,
+/// so that a value hovering around the `press_threshold` doesn't flicker between pressed and released.
+/// This relies on the state kept by [`update_action_state`](crate::systems::update_action_state):
+/// when evaluated on its own, such as through [`InputMap::which_pressed`](crate::input_map::InputMap::which_pressed),
+/// only the `press_threshold` is used.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Reflect)]
+pub struct AnalogButton {
+    /// The button whose value is read
+    pub button_type: GamepadButtonType,
+    /// The value from which the button is pressed
+    pub press_threshold: f32,
+    /// Once pressed, the button is released when its value falls to this value or below
+    ///
+    /// This should be lower than the `press_threshold`.
+    pub release_threshold: f32,
+}
+
+impl AnalogButton {
+    /// The gap between the `press_threshold` and the `release_threshold` used by constructor methods
+    ///
+    /// This cannot be changed, but the struct can be easily manually constructed.
+    pub const DEFAULT_HYSTERESIS: f32 = 0.05;
+
+    /// Creates an [`AnalogButton`] for the `button_type`, pressed from the `press_threshold`
+    ///
+    /// The `release_threshold` is [`AnalogButton::DEFAULT_HYSTERESIS`] below the `press_threshold`.
+    #[must_use]
+    pub fn new(button_type: GamepadButtonType, press_threshold: f32) -> AnalogButton {
+        AnalogButton {
+            button_type,
+            press_threshold,
+            release_threshold: (press_threshold - Self::DEFAULT_HYSTERESIS).max(0.0),
+        }
+    }
+
+    /// Creates an [`AnalogButton`] for the left trigger of the gamepad, pressed from the `press_threshold`
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Aim,
+    ///     Fire,
+    /// }
+    ///
+    /// // Aim with the trigger pulled halfway, and fire with it pulled all the way
+    /// let input_map = InputMap::new([
+    ///     (Action::Aim, AnalogButton::left_trigger(0.3)),
+    ///     (Action::Fire, AnalogButton::left_trigger(0.8)),
+    /// ]);
+    /// ```
+    #[must_use]
+    pub fn left_trigger(press_threshold: f32) -> AnalogButton {
+        AnalogButton::new(GamepadButtonType::LeftTrigger2, press_threshold)
+    }
+
+    /// Creates an [`AnalogButton`] for the right trigger of the gamepad, pressed from the `press_threshold`
+    #[must_use]
+    pub fn right_trigger(press_threshold: f32) -> AnalogButton {
+        AnalogButton::new(GamepadButtonType::RightTrigger2, press_threshold)
+    }
+
+    /// Returns this [`AnalogButton`] with the `release_threshold` set to the specified value
+    #[must_use]
+    pub fn with_release_threshold(mut self, release_threshold: f32) -> AnalogButton {
+        self.release_threshold = release_threshold;
+        self
+    }
+
+    /// Is the button pressed, given its `value` and whether it was `held` until now?
+    #[must_use]
+    pub fn pressed(&self, value: f32, held: bool) -> bool {
+        value >= self.press_threshold || (held && value > self.release_threshold)
+    }
+}
+
+impl PartialEq for AnalogButton {
+    fn eq(&self, other: &Self) -> bool {
+        self.button_type == other.button_type
+            && FloatOrd(self.press_threshold) == FloatOrd(other.press_threshold)
+            && FloatOrd(self.release_threshold) == FloatOrd(other.release_threshold)
+    }
+}
+impl Eq for AnalogButton {}
+impl std::hash::Hash for AnalogButton {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.button_type.hash(state);
+        FloatOrd(self.press_threshold).hash(state);
+        FloatOrd(self.release_threshold).hash(state);
+    }
+}
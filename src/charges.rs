@@ -0,0 +1,233 @@
+//! Charges let actions be used a few times in a row, with each use recharging over time.
+
+use crate::{action_state::ActionState, Actionlike};
+
+use bevy::{
+    ecs::{component::Component, system::Resource},
+    utils::{Duration, HashMap},
+};
+
+/// The charges of a single action
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionCharges {
+    /// The number of charges when the action is fully recharged
+    pub max: u32,
+    /// The number of charges that can be used right now
+    pub current: u32,
+    /// The time it takes to regain a single charge
+    pub recharge: Duration,
+    /// The time spent recharging the next charge
+    progress: Duration,
+}
+
+impl ActionCharges {
+    /// Creates fully recharged charges, which regain one charge each `recharge`
+    #[must_use]
+    pub fn new(max: u32, recharge: Duration) -> Self {
+        Self {
+            max,
+            current: max,
+            recharge,
+            progress: Duration::ZERO,
+        }
+    }
+
+    /// The time left before the next charge is regained
+    ///
+    /// This is [`Duration::ZERO`] when the action is fully recharged.
+    #[must_use]
+    pub fn until_next_charge(&self) -> Duration {
+        if self.current >= self.max {
+            Duration::ZERO
+        } else {
+            self.recharge.saturating_sub(self.progress)
+        }
+    }
+
+    /// Recharges by `delta`, regaining every charge that was completed
+    pub fn tick(&mut self, delta: Duration) {
+        if self.current >= self.max {
+            self.progress = Duration::ZERO;
+            return;
+        }
+
+        if self.recharge.is_zero() {
+            self.current = self.max;
+            self.progress = Duration::ZERO;
+            return;
+        }
+
+        self.progress += delta;
+        while self.progress >= self.recharge && self.current < self.max {
+            self.progress -= self.recharge;
+            self.current += 1;
+        }
+        if self.current >= self.max {
+            self.progress = Duration::ZERO;
+        }
+    }
+}
+
+/// The charges of each action, which let them be used a few times in a row before they must recharge
+///
+/// Add this as a component alongside an [`ActionState`], or as a resource alongside the [`ActionState`] resource.
+/// Charges are regained by [`tick_action_state`](crate::systems::tick_action_state),
+/// using the same [`TickClock`](crate::timing::TickClock) as the durations of the actions,
+/// and are spent by [`apply_charges`](crate::systems::apply_charges).
+///
+/// Each time an action with charges is just pressed, a charge is spent.
+/// If no charge is left, the press is swallowed: the action is [consumed](ActionState::consume),
+/// so it must be released before it can be pressed again.
+/// Actions without charges are never affected.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::Duration;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::charges::Charges;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Dodge,
+/// }
+///
+/// let mut charges = Charges::default();
+/// charges.set(Action::Dodge, 2, Duration::from_secs(3));
+///
+/// let mut action_state = ActionState::<Action>::default();
+///
+/// // Dodging twice spends both charges
+/// for _ in 0..2 {
+///     action_state.press(&Action::Dodge);
+///     charges.apply(&mut action_state);
+///     assert!(action_state.just_pressed(&Action::Dodge));
+///     action_state.release(&Action::Dodge);
+/// }
+/// assert_eq!(charges.available(&Action::Dodge), 0);
+///
+/// // A third dodge has to wait for a charge
+/// action_state.press(&Action::Dodge);
+/// charges.apply(&mut action_state);
+/// assert!(!action_state.pressed(&Action::Dodge));
+///
+/// charges.tick(Duration::from_secs(3));
+/// assert_eq!(charges.available(&Action::Dodge), 1);
+/// ```
+#[derive(Resource, Component, Clone, Debug, PartialEq)]
+pub struct Charges<A: Actionlike> {
+    charges: HashMap<A, ActionCharges>,
+}
+
+impl<A: Actionlike> Default for Charges<A> {
+    fn default() -> Self {
+        Self {
+            charges: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> Charges<A> {
+    /// Gives the `action` `max` charges, which are regained one at a time each `recharge`
+    ///
+    /// The action starts fully recharged.
+    pub fn set(&mut self, action: A, max: u32, recharge: Duration) -> &mut Self {
+        self.charges
+            .insert(action, ActionCharges::new(max, recharge));
+        self
+    }
+
+    /// Removes the charges of the `action`, so that its presses are never swallowed
+    pub fn remove(&mut self, action: &A) -> &mut Self {
+        self.charges.remove(action);
+        self
+    }
+
+    /// The charges of the `action`, if it has any
+    #[must_use]
+    pub fn get(&self, action: &A) -> Option<&ActionCharges> {
+        self.charges.get(action)
+    }
+
+    /// A mutable reference to the charges of the `action`, if it has any
+    #[must_use]
+    pub fn get_mut(&mut self, action: &A) -> Option<&mut ActionCharges> {
+        self.charges.get_mut(action)
+    }
+
+    /// The number of charges of the `action` that can be used right now
+    ///
+    /// This is zero for actions without charges.
+    #[must_use]
+    pub fn available(&self, action: &A) -> u32 {
+        self.charges
+            .get(action)
+            .map(|charges| charges.current)
+            .unwrap_or_default()
+    }
+
+    /// Fully recharges the `action`
+    pub fn refill(&mut self, action: &A) {
+        if let Some(charges) = self.charges.get_mut(action) {
+            *charges = ActionCharges::new(charges.max, charges.recharge);
+        }
+    }
+
+    /// Fully recharges all actions
+    pub fn refill_all(&mut self) {
+        for charges in self.charges.values_mut() {
+            *charges = ActionCharges::new(charges.max, charges.recharge);
+        }
+    }
+
+    /// Recharges all actions by `delta`
+    pub fn tick(&mut self, delta: Duration) {
+        for charges in self.charges.values_mut() {
+            charges.tick(delta);
+        }
+    }
+
+    /// Spends a charge for each action that was just pressed, and swallows the presses of actions without any charge left
+    ///
+    /// This should be called each tick, after the `action_state` has been updated.
+    pub fn apply(&mut self, action_state: &mut ActionState<A>) {
+        for (action, charges) in self.charges.iter_mut() {
+            if !action_state.just_pressed(action) {
+                continue;
+            }
+
+            if charges.current > 0 {
+                charges.current -= 1;
+            } else {
+                action_state.consume(action);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charges_are_regained_one_at_a_time() {
+        let mut charges = ActionCharges::new(2, Duration::from_secs(3));
+        charges.current = 0;
+
+        charges.tick(Duration::from_secs(2));
+        assert_eq!(charges.current, 0);
+        assert_eq!(charges.until_next_charge(), Duration::from_secs(1));
+
+        // The leftover time counts towards the following charge
+        charges.tick(Duration::from_secs(2));
+        assert_eq!(charges.current, 1);
+        assert_eq!(charges.until_next_charge(), Duration::from_secs(2));
+
+        // No progress is kept once fully recharged
+        charges.tick(Duration::from_secs(10));
+        assert_eq!(charges.current, 2);
+        assert_eq!(charges.until_next_charge(), Duration::ZERO);
+        charges.current = 1;
+        assert_eq!(charges.until_next_charge(), Duration::from_secs(3));
+    }
+}
@@ -1,16 +1,23 @@
 //! Handles clashing inputs into a [`InputMap`] in a configurable fashion.
 
 use crate::action_state::ActionData;
-use crate::axislike::{VirtualAxis, VirtualDPad};
+use crate::axislike::{EitherDualAxis, VirtualAxis, VirtualDPad};
+use crate::buttonlike::ButtonState;
 use crate::input_map::InputMap;
 use crate::input_streams::InputStreams;
-use crate::user_input::{InputKind, UserInput};
+use crate::scan_codes::qwerty_key_code;
+use crate::user_input::{InputKind, Modifier, UserInput};
 use crate::Actionlike;
 
+use bevy::input::keyboard::KeyCode;
 use bevy::prelude::Resource;
-use bevy::utils::HashMap;
+use bevy::reflect::ReflectRef;
+use bevy::utils::{Duration, HashMap, Instant};
 use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::sync::OnceLock;
 
 /// How should clashing inputs by handled by an [`InputMap`]?
 ///
@@ -22,6 +29,28 @@ use std::cmp::Ordering;
 /// - `S` and `S`: does not clash
 /// - `ControlLeft + S` and ` AltLeft + S`: clashes
 /// - `ControlLeft + S`, `AltLeft + S` and `ControlLeft + AltLeft + S`: clashes
+/// - `Control + S` and `ControlLeft + ShiftLeft + S`: clashes, as pressing `ControlLeft` also presses the [`Modifier`](crate::user_input::Modifier)
+///
+/// Chords are compared as sets, regardless of the kind of input they are made of:
+/// a chord clashes with any chord that it is a strict subset of, but not with chords that contain the same inputs in a different order.
+///
+/// Logical keys ([`KeyCode`](bevy::input::keyboard::KeyCode)s) and physical key locations ([`ScanCode`](bevy::input::keyboard::ScanCode)s)
+/// are pressed by the same keystroke when they match on the QWERTY layout, as described by [`QwertyScanCode`](crate::scan_codes::QwertyScanCode):
+///
+/// - `S` and `QwertyScanCode::S`: clashes, as they are the same key on the QWERTY layout
+/// - `ControlLeft + QwertyScanCode::S` and `S`: clashes
+/// - `QwertyScanCode::ControlLeft + S` and `Control + S`: does not clash, as they are the same chord
+///
+/// Shifted characters that have their own [`KeyCode`], such as [`KeyCode::At`], are typed by holding Shift,
+/// so they are chords of [`Modifier::Shift`] and the key that types them on the QWERTY layout:
+///
+/// - `At` and `Key2`: clashes, as `At` is `Shift + Key2`
+/// - `At` and `Shift + Key2`: does not clash, as they are the same chord
+/// - `At` and `Control + Shift + Key2`: clashes
+///
+/// When the strategy can't tell two clashing actions apart, such as for two single keys under [`ClashStrategy::PrioritizeLongest`],
+/// the tie is broken in favor of the action that is declared first in its enum, then of the binding that was inserted first.
+/// This makes the outcome the same every time, regardless of the order in which the map stores its bindings.
 ///
 /// This strategy is only used when assessing the actions and input holistically,
 /// in [`InputMap::which_pressed`], using [`InputMap::handle_clashes`].
@@ -32,9 +61,31 @@ pub enum ClashStrategy {
     PressAll,
     /// Only press the action that corresponds to the longest chord
     ///
+    /// Virtual inputs such as [`VirtualDPad`] and [`VirtualAxis`] are made of the buttons they contain,
+    /// so they beat single buttons that they contain, but lose to chords of two or more buttons.
+    ///
     /// This is the default strategy.
     #[default]
     PrioritizeLongest,
+    /// Only press the action whose clashing binding was inserted into the [`InputMap`] first
+    ///
+    /// This matches configuration files where earlier lines take priority over later ones.
+    /// See [`InputMap::ordered_bindings`] for the order that is used.
+    PrioritizeFirst,
+    /// Resolve clashes like [`ClashStrategy::PrioritizeLongest`], but give chords a moment to complete
+    ///
+    /// People rarely press every button of a chord on the same frame,
+    /// so an action bound to a single button that is also part of a chord is withheld for up to `window` after it is pressed.
+    /// If the chord completes in time, only the chord's action is pressed.
+    /// Otherwise, the single button's action is pressed late,
+    /// with its [`Timing`](crate::timing::Timing) backdated to the moment the button was pressed.
+    ///
+    /// Withheld actions are tracked across frames by [`update_action_state`](crate::systems::update_action_state),
+    /// so this has no effect when calling [`InputMap::which_pressed`] directly.
+    DelayForChords {
+        /// How long single buttons are withheld for, waiting for the rest of a chord
+        window: Duration,
+    },
 }
 
 impl ClashStrategy {
@@ -42,7 +93,90 @@ impl ClashStrategy {
     pub fn variants() -> &'static [ClashStrategy] {
         use ClashStrategy::*;
 
-        &[PressAll, PrioritizeLongest]
+        const VARIANTS: &[ClashStrategy] = &[
+            PressAll,
+            PrioritizeLongest,
+            PrioritizeFirst,
+            DelayForChords {
+                window: Duration::from_millis(50),
+            },
+        ];
+
+        VARIANTS
+    }
+}
+
+/// Lets the [`InputMap`]s of different [`Actionlike`] types claim the inputs that they use, hiding them from each other
+///
+/// Within a single [`InputMap`], clashes are resolved by the [`ClashStrategy`].
+/// Maps of different action types can't see each other, so a key that is bound in both of them presses both actions.
+/// When an action type is given a priority, using [`InputClaims::set_priority`] or [`InputManagerPlugin::claim_inputs`](crate::plugin::InputManagerPlugin::claim_inputs),
+/// its [`update_action_state`](crate::systems::update_action_state) system claims every input that presses one of its actions.
+/// Actions of types with a lower priority are not pressed by inputs that were already claimed this frame.
+///
+/// Claims are only visible to the systems that run after them, so action types with a higher priority must be updated first.
+/// Use the [`UpdateActionStateSet`](crate::plugin::UpdateActionStateSet) of each type to order them.
+///
+/// Actions that were not pressed because of a claim are consumed, so they are not pressed by the same inputs until they are released,
+/// even if the claim ends first.
+///
+/// Action types without a priority neither claim inputs nor respect the claims of others.
+/// Claims are cleared at the start of each frame, during [`InputManagerSystem::Tick`](crate::plugin::InputManagerSystem::Tick).
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct InputClaims {
+    priorities: HashMap<TypeId, i32>,
+    claims: Vec<(InputKind, i32)>,
+}
+
+impl InputClaims {
+    /// Lets the actions of type `A` claim inputs with the given `priority`
+    ///
+    /// Higher priorities win over lower ones.
+    /// Action types with the same priority can press actions using the same inputs.
+    pub fn set_priority<A: Actionlike>(&mut self, priority: i32) -> &mut Self {
+        self.priorities.insert(TypeId::of::<A>(), priority);
+        self
+    }
+
+    /// Stops the actions of type `A` from claiming inputs, or respecting the claims of others
+    pub fn remove_priority<A: Actionlike>(&mut self) -> &mut Self {
+        self.priorities.remove(&TypeId::of::<A>());
+        self
+    }
+
+    /// The priority with which the actions of type `A` claim inputs, if any
+    #[must_use]
+    pub fn priority<A: Actionlike>(&self) -> Option<i32> {
+        self.priorities.get(&TypeId::of::<A>()).copied()
+    }
+
+    /// The highest priority with which the `input` was claimed this frame, if it was claimed at all
+    ///
+    /// Claiming either key of a [`Modifier`](crate::user_input::Modifier) also claims the modifier, and the other way around.
+    #[must_use]
+    pub fn claimed_by(&self, input: &InputKind) -> Option<i32> {
+        self.claims
+            .iter()
+            .filter(|(claimed, _)| pressed_together(claimed, input))
+            .map(|(_, priority)| *priority)
+            .max()
+    }
+
+    /// Forgets all of the inputs that were claimed, but not the priorities of each action type
+    pub fn clear(&mut self) {
+        self.claims.clear();
+    }
+
+    /// Claims the `input` with the given `priority`
+    fn claim(&mut self, input: InputKind, priority: i32) {
+        match self
+            .claims
+            .iter_mut()
+            .find(|(claimed, _)| *claimed == input)
+        {
+            Some((_, claimed_priority)) => *claimed_priority = (*claimed_priority).max(priority),
+            None => self.claims.push((input, priority)),
+        }
     }
 }
 
@@ -52,31 +186,40 @@ impl UserInput {
     fn clashes(&self, other: &UserInput) -> bool {
         use UserInput::*;
 
-        match self {
+        let this = with_implied_shift(self);
+        let other = with_implied_shift(other);
+        let other = other.as_ref();
+
+        match this.as_ref() {
             Single(self_button) => match other {
-                Single(_) => false,
+                Single(other_button) => same_key(self_button, other_button),
                 Chord(other_chord) => button_chord_clash(self_button, other_chord),
                 VirtualDPad(other_dpad) => dpad_button_clash(other_dpad, self_button),
                 VirtualAxis(other_axis) => virtual_axis_button_clash(other_axis, self_button),
+                EitherDualAxis(other_either) => either_dual_axis_clash(other_either, &this),
             },
             Chord(self_chord) => match other {
                 Single(other_button) => button_chord_clash(other_button, self_chord),
                 Chord(other_chord) => chord_chord_clash(self_chord, other_chord),
                 VirtualDPad(other_dpad) => dpad_chord_clash(other_dpad, self_chord),
                 VirtualAxis(other_axis) => virtual_axis_chord_clash(other_axis, self_chord),
+                EitherDualAxis(other_either) => either_dual_axis_clash(other_either, &this),
             },
             VirtualDPad(self_dpad) => match other {
                 Single(other_button) => dpad_button_clash(self_dpad, other_button),
                 Chord(other_chord) => dpad_chord_clash(self_dpad, other_chord),
                 VirtualDPad(other_dpad) => dpad_dpad_clash(self_dpad, other_dpad),
                 VirtualAxis(other_axis) => virtual_axis_dpad_clash(other_axis, self_dpad),
+                EitherDualAxis(other_either) => either_dual_axis_clash(other_either, &this),
             },
             VirtualAxis(self_axis) => match other {
                 Single(other_button) => virtual_axis_button_clash(self_axis, other_button),
                 Chord(other_chord) => virtual_axis_chord_clash(self_axis, other_chord),
                 VirtualDPad(other_dpad) => virtual_axis_dpad_clash(self_axis, other_dpad),
                 VirtualAxis(other_axis) => virtual_axis_virtual_axis_clash(self_axis, other_axis),
+                EitherDualAxis(other_either) => either_dual_axis_clash(other_either, &this),
             },
+            EitherDualAxis(self_either) => either_dual_axis_clash(self_either, other),
         }
     }
 }
@@ -84,6 +227,9 @@ impl UserInput {
 impl<A: Actionlike> InputMap<A> {
     /// Resolve clashing inputs, removing action presses that have been overruled
     ///
+    /// Actions that are [clash exempt](InputMap::set_clash_exempt) are never removed,
+    /// and only suppress other actions if they [block clashes](InputMap::blocks_clashes).
+    ///
     /// The `usize` stored in `pressed_actions` corresponds to `Actionlike::index`
     pub fn handle_clashes(
         &self,
@@ -91,16 +237,299 @@ impl<A: Actionlike> InputMap<A> {
         input_streams: &InputStreams,
         clash_strategy: ClashStrategy,
     ) {
-        for clash in self.get_clashes(action_data, input_streams) {
+        self.suppress_clashing_actions(
+            action_data,
+            &|input| input_streams.input_pressed(input),
+            clash_strategy,
+        );
+    }
+
+    /// Predicts which actions would be pressed or suppressed if only the `hypothetical_pressed` inputs were pressed
+    ///
+    /// Every button that makes up each of the `hypothetical_pressed` inputs is treated as pressed,
+    /// so including a [`VirtualDPad`] presses all four of its directions.
+    /// Axes are only pressed if they are listed exactly.
+    ///
+    /// Unlike [`InputMap::which_pressed`], this does not read any inputs, so it can be used without a running [`App`](bevy::app::App),
+    /// such as to warn about bindings that would be suppressed before they are confirmed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Scroll,
+    ///     Save,
+    /// }
+    ///
+    /// let mut input_map = InputMap::new([(Action::Scroll, KeyCode::S)]);
+    /// input_map.insert_chord(Action::Save, [KeyCode::ControlLeft, KeyCode::S]);
+    ///
+    /// let report = input_map.simulate_clashes(
+    ///     &[UserInput::chord([KeyCode::ControlLeft, KeyCode::S])],
+    ///     ClashStrategy::PrioritizeLongest,
+    /// );
+    /// assert!(report.is_pressed(&Action::Save));
+    /// assert_eq!(report.suppressed_by(&Action::Scroll), vec![&Action::Save]);
+    /// ```
+    #[must_use]
+    pub fn simulate_clashes(
+        &self,
+        hypothetical_pressed: &[UserInput],
+        clash_strategy: ClashStrategy,
+    ) -> ClashReport<A> {
+        let pressed_buttons: Vec<InputKind> =
+            hypothetical_pressed.iter().flat_map(input_kinds).collect();
+        let button_pressed = |button: &InputKind| {
+            pressed_buttons
+                .iter()
+                .any(|pressed| pressed_together(pressed, button))
+        };
+        let input_pressed = |input: &UserInput| match input {
+            UserInput::Single(button) => button_pressed(button),
+            UserInput::Chord(chord) => chord.iter().all(button_pressed),
+            UserInput::VirtualDPad(_)
+            | UserInput::VirtualAxis(_)
+            | UserInput::EitherDualAxis(_) => input_kinds(input).iter().any(button_pressed),
+        };
+
+        let mut action_data = HashMap::new();
+        for (action, inputs) in self.iter() {
+            if inputs.iter().any(input_pressed) {
+                let action_datum = ActionData {
+                    state: ButtonState::JustPressed,
+                    ..Default::default()
+                };
+                action_data.insert(action.clone(), action_datum);
+            }
+        }
+
+        let suppressed =
+            self.suppress_clashing_actions(&mut action_data, &input_pressed, clash_strategy);
+
+        ClashReport {
+            pressed: action_data.into_keys().collect(),
+            suppressed,
+        }
+    }
+
+    /// Removes the actions that were overruled from `action_data`, returning a record of each removal
+    pub(crate) fn suppress_clashing_actions(
+        &self,
+        action_data: &mut HashMap<A, ActionData>,
+        input_pressed: &impl Fn(&UserInput) -> bool,
+        clash_strategy: ClashStrategy,
+    ) -> Vec<SuppressedAction<A>> {
+        let mut suppressed = Vec::new();
+        let map_strategy = self.clash_strategy().unwrap_or(clash_strategy);
+
+        for clash in self.get_clashes(action_data, input_pressed) {
+            let clash_strategy = self
+                .clash_override(&clash.action_a, &clash.action_b)
+                .unwrap_or(map_strategy);
+
             // Remove the action in the pair that was overruled, if any
-            if let Some(culled_action) = resolve_clash(&clash, clash_strategy, input_streams) {
-                action_data.remove(&culled_action);
+            if let Some(culled_action) = resolve_clash(&clash, clash_strategy, input_pressed) {
+                let (winning_action, culled_inputs) = if culled_action == clash.action_a {
+                    (&clash.action_b, &clash.inputs_a)
+                } else {
+                    (&clash.action_a, &clash.inputs_b)
+                };
+
+                if !self.is_clash_exempt(&culled_action) && self.blocks_clashes(winning_action) {
+                    action_data.remove(&culled_action);
+
+                    // Each pair of actions is checked in both orders
+                    let already_recorded = suppressed.iter().any(|record: &SuppressedAction<A>| {
+                        record.action == culled_action && &record.suppressed_by == winning_action
+                    });
+                    if already_recorded {
+                        continue;
+                    }
+
+                    suppressed.push(SuppressedAction {
+                        inputs: culled_inputs
+                            .iter()
+                            .filter(|input| input_pressed(input))
+                            .cloned()
+                            .collect(),
+                        suppressed_by: winning_action.clone(),
+                        action: culled_action,
+                    });
+                }
             }
         }
+
+        suppressed
+    }
+
+    /// Withholds newly pressed actions that could be the start of a chord, for [`ClashStrategy::DelayForChords`]
+    ///
+    /// `pending` stores the instant at which each withheld action was first pressed, and must be kept between frames.
+    /// Actions are withheld until the `window` has elapsed, until their inputs are released,
+    /// or until they are suppressed by the completed chord, in which case they are forgotten.
+    ///
+    /// Returns the withheld actions that are pressed this frame, with the instant their press should be backdated to.
+    pub(crate) fn delay_for_chords(
+        &self,
+        action_data: &mut HashMap<A, ActionData>,
+        was_pressed: impl Fn(&A) -> bool,
+        input_streams: &InputStreams,
+        pending: &mut HashMap<A, Instant>,
+        window: Duration,
+        now: Instant,
+    ) -> Vec<(A, Instant)> {
+        let mut backdated = Vec::new();
+
+        pending.retain(|action, started| {
+            let Some(action_datum) = action_data.get_mut(action) else {
+                return false;
+            };
+            if action_datum.state.pressed() {
+                return true;
+            }
+
+            // The chord was not completed, but the button was tapped: press the action for a single frame
+            let held = self.get(action).is_some_and(|inputs| {
+                inputs
+                    .iter()
+                    .any(|input| input_streams.input_pressed(input))
+            });
+            if !held {
+                action_datum.state = ButtonState::JustPressed;
+                action_datum.value = 1.0;
+                backdated.push((action.clone(), *started));
+            }
+            false
+        });
+
+        for (action, action_datum) in action_data.iter_mut() {
+            if !action_datum.state.pressed() || backdated.iter().any(|(a, _)| a == action) {
+                continue;
+            }
+
+            let started = match pending.get(action) {
+                Some(started) => *started,
+                None if !window.is_zero()
+                    && !was_pressed(action)
+                    && self.could_start_chord(action, input_streams) =>
+                {
+                    pending.insert(action.clone(), now);
+                    now
+                }
+                None => continue,
+            };
+
+            if now.saturating_duration_since(started) >= window {
+                pending.remove(action);
+                backdated.push((action.clone(), started));
+            } else {
+                *action_datum = ActionData::default();
+            }
+        }
+
+        backdated
     }
 
-    /// Updates the cache of possible input clashes
-    pub(crate) fn possible_clashes(&self) -> Vec<Clash<A>> {
+    /// Releases the actions that are only pressed by inputs claimed with a higher `priority`, then claims the inputs of the remaining actions
+    ///
+    /// Returns the actions that were released.
+    /// See [`InputClaims`] for more details.
+    pub(crate) fn respect_claims(
+        &self,
+        action_data: &mut HashMap<A, ActionData>,
+        input_streams: &InputStreams,
+        claims: &mut InputClaims,
+        priority: i32,
+    ) -> Vec<A> {
+        let mut suppressed = Vec::new();
+        let mut newly_claimed = Vec::new();
+
+        for (action, action_datum) in action_data.iter_mut() {
+            if !action_datum.state.pressed() {
+                continue;
+            }
+            let Some(inputs) = self.get(action) else {
+                continue;
+            };
+
+            // The buttons behind each of the reasons that this action is pressed
+            let reasons: Vec<Vec<InputKind>> = inputs
+                .iter()
+                .filter(|input| input_streams.input_pressed(input))
+                .map(|input| {
+                    input_kinds(input)
+                        .into_iter()
+                        .filter(|button| input_streams.input_pressed(&UserInput::Single(*button)))
+                        .collect()
+                })
+                .collect();
+
+            let is_claimed = |buttons: &Vec<InputKind>| {
+                buttons.iter().any(|button| {
+                    claims
+                        .claimed_by(button)
+                        .is_some_and(|claimed_priority| claimed_priority > priority)
+                })
+            };
+
+            if !reasons.is_empty() && reasons.iter().all(is_claimed) {
+                *action_datum = ActionData::default();
+                suppressed.push(action.clone());
+            } else {
+                newly_claimed.extend(reasons.into_iter().flatten());
+            }
+        }
+
+        for button in newly_claimed {
+            claims.claim(button, priority);
+        }
+
+        suppressed
+    }
+
+    /// Is the `action` only pressed by single buttons that are each part of some chord in this map?
+    #[must_use]
+    fn could_start_chord(&self, action: &A, input_streams: &InputStreams) -> bool {
+        let Some(inputs) = self.get(action) else {
+            return false;
+        };
+
+        let mut pressed_inputs = inputs
+            .iter()
+            .filter(|input| input_streams.input_pressed(input))
+            .peekable();
+        if pressed_inputs.peek().is_none() {
+            return false;
+        }
+
+        pressed_inputs.all(|input| match input {
+            UserInput::Single(button) => self.iter().any(|(_, other_inputs)| {
+                other_inputs.iter().any(|other_input| match other_input {
+                    UserInput::Chord(chord) => {
+                        chord.iter().any(|member| pressed_together(member, button))
+                    }
+                    _ => false,
+                })
+            }),
+            _ => false,
+        })
+    }
+
+    /// The pairs of actions that could clash, and the inputs they could clash on
+    ///
+    /// These only depend on the bindings, so they are cached until the map is next changed.
+    pub(crate) fn possible_clashes(&self) -> &[Clash<A>] {
+        self.clash_cache
+            .0
+            .get_or_init(|| self.compute_possible_clashes())
+    }
+
+    /// Finds every pair of actions that could clash, ignoring the cache
+    #[must_use]
+    fn compute_possible_clashes(&self) -> Vec<Clash<A>> {
         let mut clashes = Vec::default();
 
         for (action_a, _) in self.iter() {
@@ -111,6 +540,16 @@ impl<A: Actionlike> InputMap<A> {
             }
         }
 
+        // Clashes are resolved one by one, so they are sorted to make the outcome independent of the order of the map
+        clashes.sort_by_cached_key(|clash| {
+            (
+                action_index(&clash.action_a),
+                clash.binding_indices_a.iter().min().copied(),
+                action_index(&clash.action_b),
+                clash.binding_indices_b.iter().min().copied(),
+            )
+        });
+
         clashes
     }
 
@@ -121,7 +560,7 @@ impl<A: Actionlike> InputMap<A> {
     fn get_clashes(
         &self,
         action_data: &HashMap<A, ActionData>,
-        input_streams: &InputStreams,
+        input_pressed: &impl Fn(&UserInput) -> bool,
     ) -> Vec<Clash<A>> {
         let mut clashes = Vec::default();
 
@@ -139,7 +578,7 @@ impl<A: Actionlike> InputMap<A> {
             // This is not strictly necessary, but saves work
             if data_a.state.pressed() && data_b.state.pressed() {
                 // Check if the potential clash occurred based on the pressed inputs
-                if let Some(clash) = check_clash(&clash, input_streams) {
+                if let Some(clash) = check_clash(clash, input_pressed) {
                     clashes.push(clash)
                 }
             }
@@ -158,6 +597,12 @@ impl<A: Actionlike> InputMap<A> {
                 if input_a.clashes(input_b) {
                     clash.inputs_a.push(input_a.clone());
                     clash.inputs_b.push(input_b.clone());
+                    clash
+                        .binding_indices_a
+                        .push(self.binding_index(action_a, input_a));
+                    clash
+                        .binding_indices_b
+                        .push(self.binding_index(action_b, input_b));
                 }
             }
         }
@@ -170,6 +615,83 @@ impl<A: Actionlike> InputMap<A> {
     }
 }
 
+/// The outcome of [`InputMap::simulate_clashes`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClashReport<A: Actionlike> {
+    /// The actions that would be pressed, in no particular order
+    pub pressed: Vec<A>,
+    /// The actions that would be suppressed by a clash
+    ///
+    /// An action appears once for each action that it loses a clash to.
+    pub suppressed: Vec<SuppressedAction<A>>,
+}
+
+impl<A: Actionlike> ClashReport<A> {
+    /// Would the `action` be pressed?
+    #[must_use]
+    pub fn is_pressed(&self, action: &A) -> bool {
+        self.pressed.contains(action)
+    }
+
+    /// Would the `action` be suppressed by a clash?
+    #[must_use]
+    pub fn is_suppressed(&self, action: &A) -> bool {
+        self.suppressed
+            .iter()
+            .any(|suppressed| &suppressed.action == action)
+    }
+
+    /// The actions that would suppress the `action`
+    #[must_use]
+    pub fn suppressed_by(&self, action: &A) -> Vec<&A> {
+        self.suppressed
+            .iter()
+            .filter(|suppressed| &suppressed.action == action)
+            .map(|suppressed| &suppressed.suppressed_by)
+            .collect()
+    }
+}
+
+/// An action that was not pressed because it lost a clash to another action
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuppressedAction<A: Actionlike> {
+    /// The action that was suppressed
+    pub action: A,
+    /// The pressed inputs of the `action` that clashed with the winning action
+    pub inputs: Vec<UserInput>,
+    /// The action that won the clash
+    pub suppressed_by: A,
+}
+
+/// The [`InputMap::possible_clashes`], computed when first needed
+///
+/// The cache is not part of the input map's data: it is ignored when comparing, serializing or reflecting,
+/// and must be [invalidated](ClashCache::invalidate) whenever the bindings change.
+#[derive(Debug, Clone)]
+pub(crate) struct ClashCache<A: Actionlike>(OnceLock<Vec<Clash<A>>>);
+
+impl<A: Actionlike> ClashCache<A> {
+    /// Forgets the cached clashes, so they are recomputed when next needed
+    pub(crate) fn invalidate(&mut self) {
+        self.0.take();
+    }
+}
+
+impl<A: Actionlike> Default for ClashCache<A> {
+    fn default() -> Self {
+        Self(OnceLock::new())
+    }
+}
+
+// The cache is derived from the bindings, which are compared instead
+impl<A: Actionlike> PartialEq for ClashCache<A> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<A: Actionlike> Eq for ClashCache<A> {}
+
 /// A user-input clash, which stores the actions that are being clashed on,
 /// as well as the corresponding user inputs
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -178,6 +700,10 @@ pub(crate) struct Clash<A: Actionlike> {
     action_b: A,
     inputs_a: Vec<UserInput>,
     inputs_b: Vec<UserInput>,
+    /// The [`InputMap::binding_index`] of each of the `inputs_a`
+    binding_indices_a: Vec<usize>,
+    /// The [`InputMap::binding_index`] of each of the `inputs_b`
+    binding_indices_b: Vec<usize>,
 }
 
 impl<A: Actionlike> Clash<A> {
@@ -189,6 +715,8 @@ impl<A: Actionlike> Clash<A> {
             action_b,
             inputs_a: Vec::default(),
             inputs_b: Vec::default(),
+            binding_indices_a: Vec::default(),
+            binding_indices_b: Vec::default(),
         }
     }
 }
@@ -200,7 +728,7 @@ fn button_chord_clash(button: &InputKind, chord: &[InputKind]) -> bool {
         return false;
     }
 
-    chord.contains(button)
+    chord.iter().any(|member| pressed_whenever(button, member))
 }
 
 // Does the `dpad` clash with the `chord`?
@@ -274,58 +802,200 @@ fn virtual_axis_virtual_axis_clash(axis1: &VirtualAxis, axis2: &VirtualAxis) ->
         || axis1.positive == axis2.positive
 }
 
+/// Is the `other` input either [`DualAxis`](crate::axislike::DualAxis) of the `either` input, or does it clash with one of them?
+#[must_use]
+fn either_dual_axis_clash(either: &EitherDualAxis, other: &UserInput) -> bool {
+    [either.first, either.second].into_iter().any(|dual_axis| {
+        let dual_axis = UserInput::from(dual_axis);
+        dual_axis == *other || dual_axis.clashes(other)
+    })
+}
+
 /// Does the `chord_a` clash with `chord_b`?
+///
+/// Chords clash when one is a strict subset of the other: chords that are made of the same inputs do not clash.
 #[must_use]
-fn chord_chord_clash(chord_a: &Vec<InputKind>, chord_b: &Vec<InputKind>) -> bool {
+fn chord_chord_clash(chord_a: &[InputKind], chord_b: &[InputKind]) -> bool {
     if chord_a.len() <= 1 || chord_b.len() <= 1 {
         return false;
     }
 
-    if chord_a == chord_b {
-        return false;
+    is_subset(chord_a, chord_b) != is_subset(chord_b, chord_a)
+}
+
+/// Is every input in `slice_a` pressed whenever all of the inputs in `slice_b` are?
+fn is_subset(slice_a: &[InputKind], slice_b: &[InputKind]) -> bool {
+    slice_a
+        .iter()
+        .all(|a| slice_b.iter().any(|b| pressed_whenever(a, b)))
+}
+
+/// Are `input_a` and `input_b` pressed by the same keystroke?
+///
+/// This is true for identical inputs, for a [`Modifier`](crate::user_input::Modifier) and either of its keys,
+/// and for a logical and a physical binding of the same key (see [`same_key`]).
+#[must_use]
+fn pressed_together(input_a: &InputKind, input_b: &InputKind) -> bool {
+    match (input_a, input_b) {
+        (InputKind::Modifier(modifier), InputKind::Keyboard(key_code))
+        | (InputKind::Keyboard(key_code), InputKind::Modifier(modifier)) => {
+            modifier.key_codes().contains(key_code)
+        }
+        _ => input_a == input_b || same_key(input_a, input_b),
     }
+}
 
-    is_subset(chord_a, chord_b) || is_subset(chord_b, chord_a)
+/// Is `input_a` pressed whenever `input_b` is?
+///
+/// Unlike [`pressed_together`], this is also true for a [`SingleAxis`](crate::axislike::SingleAxis)
+/// and a stricter condition on the same axis, such as a stick pushed above `0.1` and above `0.5`,
+/// and for an [`AnalogButton`](crate::buttonlike::AnalogButton) and the same button with a higher press threshold.
+#[must_use]
+fn pressed_whenever(input_a: &InputKind, input_b: &InputKind) -> bool {
+    match (input_a, input_b) {
+        (InputKind::SingleAxis(axis_a), InputKind::SingleAxis(axis_b)) => {
+            axis_a.axis_type == axis_b.axis_type
+                && axis_a.inverted == axis_b.inverted
+                && axis_a.sensitivity == axis_b.sensitivity
+                && axis_a.positive_low <= axis_b.positive_low
+                && axis_a.negative_low >= axis_b.negative_low
+        }
+        (InputKind::AnalogButton(button_a), InputKind::AnalogButton(button_b)) => {
+            button_a.button_type == button_b.button_type
+                && button_a.press_threshold <= button_b.press_threshold
+        }
+        _ => pressed_together(input_a, input_b),
+    }
 }
 
-fn is_subset(slice_a: &[InputKind], slice_b: &[InputKind]) -> bool {
-    for a in slice_a {
-        if !slice_b.contains(a) {
-            return false;
+/// Is `input_a` the logical key and `input_b` the physical key location of the same keystroke, or the other way around?
+///
+/// Logical keys ([`InputKind::Keyboard`] and [`InputKind::Modifier`]) are matched to key locations ([`InputKind::KeyLocation`])
+/// using the QWERTY layout, like [`QwertyScanCode`](crate::scan_codes::QwertyScanCode).
+/// Inputs of the same kind are never the same key by this definition, even if they are equal.
+#[must_use]
+fn same_key(input_a: &InputKind, input_b: &InputKind) -> bool {
+    match (input_a, input_b) {
+        (InputKind::KeyLocation(scan_code), logical_key)
+        | (logical_key, InputKind::KeyLocation(scan_code)) => {
+            let Some(key_code) = qwerty_key_code(*scan_code) else {
+                return false;
+            };
+
+            match logical_key {
+                InputKind::Keyboard(logical_key_code) => *logical_key_code == key_code,
+                InputKind::Modifier(modifier) => modifier.key_codes().contains(&key_code),
+                _ => false,
+            }
         }
+        _ => false,
     }
+}
 
-    true
+/// The key that types the shifted character `input` on the QWERTY layout, when it is held with [`Modifier::Shift`]
+///
+/// Bevy has no logical key type, so only the shifted characters that have their own [`KeyCode`] are known:
+/// characters such as `!` (`Shift + Key1`) can't be bound on their own, and never clash as a result.
+#[must_use]
+fn unshifted_key(input: &InputKind) -> Option<KeyCode> {
+    let InputKind::Keyboard(key_code) = input else {
+        return None;
+    };
+
+    match key_code {
+        KeyCode::At => Some(KeyCode::Key2),
+        KeyCode::Caret => Some(KeyCode::Key6),
+        KeyCode::Asterisk => Some(KeyCode::Key8),
+        KeyCode::Underline => Some(KeyCode::Minus),
+        KeyCode::Plus => Some(KeyCode::Equals),
+        KeyCode::Colon => Some(KeyCode::Semicolon),
+        _ => None,
+    }
 }
 
-/// Given the `input_streams`, does the provided clash actually occur?
+/// The `input`, with its shifted characters replaced by the implicit chord of [`Modifier::Shift`] and the key that types them
+///
+/// See [`unshifted_key`] for the characters that are replaced.
+#[must_use]
+fn with_implied_shift(input: &UserInput) -> Cow<'_, UserInput> {
+    let buttons = match input {
+        UserInput::Single(button) => std::slice::from_ref(button),
+        UserInput::Chord(chord) => chord.as_slice(),
+        _ => return Cow::Borrowed(input),
+    };
+
+    if !buttons.iter().any(|button| unshifted_key(button).is_some()) {
+        return Cow::Borrowed(input);
+    }
+
+    let shift = InputKind::Modifier(Modifier::Shift);
+    let mut chord = Vec::with_capacity(buttons.len() + 1);
+    for button in buttons {
+        let expanded = match unshifted_key(button) {
+            Some(key_code) => vec![shift, InputKind::Keyboard(key_code)],
+            None => vec![*button],
+        };
+
+        for button in expanded {
+            if !chord.contains(&button) {
+                chord.push(button);
+            }
+        }
+    }
+
+    Cow::Owned(UserInput::Chord(chord))
+}
+
+/// The buttons and axes that make up the `input`
+#[must_use]
+pub(crate) fn input_kinds(input: &UserInput) -> Vec<InputKind> {
+    match input {
+        UserInput::Single(button) => vec![*button],
+        UserInput::Chord(chord) => chord.clone(),
+        UserInput::VirtualDPad(dpad) => vec![dpad.up, dpad.down, dpad.left, dpad.right],
+        UserInput::VirtualAxis(axis) => vec![axis.negative, axis.positive],
+        UserInput::EitherDualAxis(either) => vec![
+            InputKind::DualAxis(either.first),
+            InputKind::DualAxis(either.second),
+        ],
+    }
+}
+
+/// Given which inputs are pressed, does the provided clash actually occur?
 ///
 /// Returns `Some(clash)` if they are clashing, and `None` if they are not.
 #[must_use]
-fn check_clash<A: Actionlike>(clash: &Clash<A>, input_streams: &InputStreams) -> Option<Clash<A>> {
-    let mut actual_clash: Clash<A> = clash.clone();
+fn check_clash<A: Actionlike>(
+    clash: &Clash<A>,
+    input_pressed: &impl Fn(&UserInput) -> bool,
+) -> Option<Clash<A>> {
+    let mut actual_clash = Clash::new(clash.action_a.clone(), clash.action_b.clone());
 
     // For all inputs that were actually pressed that match action A
-    for input_a in clash
+    for (input_a, index_a) in clash
         .inputs_a
         .iter()
-        .filter(|&input| input_streams.input_pressed(input))
+        .zip(&clash.binding_indices_a)
+        .filter(|(input, _)| input_pressed(input))
     {
         // For all inputs that were actually pressed that match action B
-        for input_b in clash
+        for (input_b, index_b) in clash
             .inputs_b
             .iter()
-            .filter(|&input| input_streams.input_pressed(input))
+            .zip(&clash.binding_indices_b)
+            .filter(|(input, _)| input_pressed(input))
         {
             // If a clash was detected,
             if input_a.clashes(input_b) {
                 actual_clash.inputs_a.push(input_a.clone());
                 actual_clash.inputs_b.push(input_b.clone());
+                actual_clash.binding_indices_a.push(*index_a);
+                actual_clash.binding_indices_b.push(*index_b);
             }
         }
     }
 
-    if !clash.inputs_a.is_empty() {
+    if !actual_clash.inputs_a.is_empty() {
         Some(actual_clash)
     } else {
         None
@@ -337,19 +1007,19 @@ fn check_clash<A: Actionlike>(clash: &Clash<A>, input_streams: &InputStreams) ->
 fn resolve_clash<A: Actionlike>(
     clash: &Clash<A>,
     clash_strategy: ClashStrategy,
-    input_streams: &InputStreams,
+    input_pressed: &impl Fn(&UserInput) -> bool,
 ) -> Option<A> {
     // Figure out why the actions are pressed
     let reasons_a_is_pressed: Vec<&UserInput> = clash
         .inputs_a
         .iter()
-        .filter(|&input| input_streams.input_pressed(input))
+        .filter(|&input| input_pressed(input))
         .collect();
 
     let reasons_b_is_pressed: Vec<&UserInput> = clash
         .inputs_b
         .iter()
-        .filter(|&input| input_streams.input_pressed(input))
+        .filter(|&input| input_pressed(input))
         .collect();
 
     // Clashes are spurious if the actions are pressed for any non-clashing reason
@@ -368,28 +1038,106 @@ fn resolve_clash<A: Actionlike>(
         // Do nothing
         ClashStrategy::PressAll => None,
         // Remove the clashing action with the shorter chord
-        ClashStrategy::PrioritizeLongest => {
-            let longest_a: usize = reasons_a_is_pressed
+        ClashStrategy::PrioritizeLongest | ClashStrategy::DelayForChords { .. } => {
+            let longest_a = reasons_a_is_pressed
                 .iter()
-                .map(|input| input.len())
+                .map(|input| clash_length(input))
                 .reduce(|a, b| a.max(b))
                 .unwrap_or_default();
 
-            let longest_b: usize = reasons_b_is_pressed
+            let longest_b = reasons_b_is_pressed
                 .iter()
-                .map(|input| input.len())
+                .map(|input| clash_length(input))
                 .reduce(|a, b| a.max(b))
                 .unwrap_or_default();
 
             match longest_a.cmp(&longest_b) {
                 Ordering::Greater => Some(clash.action_b.clone()),
                 Ordering::Less => Some(clash.action_a.clone()),
-                Ordering::Equal => None,
+                Ordering::Equal => break_tie(clash, input_pressed),
+            }
+        }
+        // Remove the clashing action whose binding was inserted later
+        ClashStrategy::PrioritizeFirst => {
+            let first_a =
+                first_pressed_binding(&clash.inputs_a, &clash.binding_indices_a, input_pressed);
+            let first_b =
+                first_pressed_binding(&clash.inputs_b, &clash.binding_indices_b, input_pressed);
+
+            match first_a.cmp(&first_b) {
+                Ordering::Less => Some(clash.action_b.clone()),
+                Ordering::Greater => Some(clash.action_a.clone()),
+                Ordering::Equal => break_tie(clash, input_pressed),
             }
         }
     }
 }
 
+/// Removes one of two actions that are tied according to the [`ClashStrategy`]
+///
+/// The action that is declared first in its enum wins, followed by the action whose pressed binding was inserted first.
+/// Actions that are not enums are only ordered by their bindings.
+#[must_use]
+fn break_tie<A: Actionlike>(
+    clash: &Clash<A>,
+    input_pressed: &impl Fn(&UserInput) -> bool,
+) -> Option<A> {
+    let order_a = (
+        action_index(&clash.action_a),
+        first_pressed_binding(&clash.inputs_a, &clash.binding_indices_a, input_pressed),
+    );
+    let order_b = (
+        action_index(&clash.action_b),
+        first_pressed_binding(&clash.inputs_b, &clash.binding_indices_b, input_pressed),
+    );
+
+    match order_a.cmp(&order_b) {
+        Ordering::Less => Some(clash.action_b.clone()),
+        Ordering::Greater => Some(clash.action_a.clone()),
+        Ordering::Equal => None,
+    }
+}
+
+/// The position of the `action` in the declaration of its enum, or [`usize::MAX`] if it is not an enum
+#[must_use]
+fn action_index<A: Actionlike>(action: &A) -> usize {
+    match action.reflect_ref() {
+        ReflectRef::Enum(action) => action.variant_index(),
+        _ => usize::MAX,
+    }
+}
+
+/// How long is the `input`, when resolving clashes with [`ClashStrategy::PrioritizeLongest`]?
+///
+/// Virtual inputs are made of several buttons, so they are longer than any single button they contain,
+/// but shorter than chords of the same [`UserInput::len`].
+/// Shifted characters are as long as their implicit chord with [`Modifier::Shift`].
+#[must_use]
+fn clash_length(input: &UserInput) -> (usize, bool) {
+    let is_virtual = matches!(
+        input,
+        UserInput::VirtualDPad(_) | UserInput::VirtualAxis(_) | UserInput::EitherDualAxis(_)
+    );
+
+    (with_implied_shift(input).len(), is_virtual)
+}
+
+/// The earliest [`InputMap::binding_index`] of the `inputs` that are pressed
+#[must_use]
+fn first_pressed_binding(
+    inputs: &[UserInput],
+    binding_indices: &[usize],
+    input_pressed: &impl Fn(&UserInput) -> bool,
+) -> usize {
+    inputs
+        .iter()
+        .zip(binding_indices)
+        .filter(|(input, _)| input_pressed(input))
+        .map(|(_, index)| *index)
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -433,6 +1181,7 @@ mod tests {
                 down: Down.into(),
                 left: Left.into(),
                 right: Right.into(),
+                normalize_diagonals: false,
             },
         );
         input_map.insert_chord(CtrlUp, [ControlLeft, Up]);
@@ -461,6 +1210,7 @@ mod tests {
                 down: X.into(),
                 left: Y.into(),
                 right: Z.into(),
+                normalize_diagonals: false,
             }
             .into();
             let abcd_dpad: UserInput = VirtualDPad {
@@ -468,6 +1218,7 @@ mod tests {
                 down: B.into(),
                 left: C.into(),
                 right: D.into(),
+                normalize_diagonals: false,
             }
             .into();
 
@@ -477,6 +1228,7 @@ mod tests {
                 down: Down.into(),
                 left: Left.into(),
                 right: Right.into(),
+                normalize_diagonals: false,
             }
             .into();
 
@@ -492,6 +1244,168 @@ mod tests {
             assert!(ctrl_up.clashes(&directions_dpad));
         }
 
+        #[test]
+        fn chord_chord_clashes() {
+            use crate::buttonlike::MouseWheelDirection;
+            use crate::user_input::Modifier;
+            use bevy::input::mouse::MouseButton;
+
+            let ctrl_s = [Modifier::Control.into(), S.into()];
+            let ctrl_shift_s = [Modifier::Control.into(), Modifier::Shift.into(), S.into()];
+            let left_ctrl_shift_s = [ControlLeft.into(), ShiftLeft.into(), S.into()];
+            let shift_ctrl_s = [S.into(), Modifier::Shift.into(), Modifier::Control.into()];
+            let ctrl_wheel = [
+                Modifier::Control.into(),
+                InputKind::MouseWheel(MouseWheelDirection::Up),
+            ];
+            let ctrl_shift_wheel = [
+                Modifier::Shift.into(),
+                Modifier::Control.into(),
+                InputKind::MouseWheel(MouseWheelDirection::Up),
+            ];
+            let ctrl_click = [ControlLeft.into(), MouseButton::Left.into()];
+            let a_b = [A.into(), B.into()];
+
+            // Strict subsets clash, in either order
+            assert!(chord_chord_clash(&ctrl_s, &ctrl_shift_s));
+            assert!(chord_chord_clash(&ctrl_shift_s, &ctrl_s));
+            assert!(chord_chord_clash(&ctrl_wheel, &ctrl_shift_wheel));
+            assert!(chord_chord_clash(&ctrl_shift_wheel, &ctrl_wheel));
+
+            // Modifiers are pressed by either of their keys
+            assert!(chord_chord_clash(&ctrl_s, &left_ctrl_shift_s));
+            assert!(chord_chord_clash(&left_ctrl_shift_s, &ctrl_s));
+
+            // Equal chords don't clash, whatever their order
+            assert!(!chord_chord_clash(&ctrl_s, &ctrl_s));
+            assert!(!chord_chord_clash(&ctrl_shift_s, &shift_ctrl_s));
+            assert!(!chord_chord_clash(&ctrl_shift_wheel, &ctrl_shift_wheel));
+
+            // Disjoint and partially overlapping chords don't clash
+            assert!(!chord_chord_clash(&ctrl_s, &a_b));
+            assert!(!chord_chord_clash(&ctrl_s, &ctrl_wheel));
+            assert!(!chord_chord_clash(&ctrl_shift_s, &ctrl_shift_wheel));
+            assert!(!chord_chord_clash(&ctrl_click, &ctrl_s));
+        }
+
+        #[test]
+        fn logical_physical_clashes() {
+            use crate::scan_codes::QwertyScanCode;
+            use crate::user_input::Modifier;
+
+            let logical_s = UserInput::from(S);
+            let physical_s = UserInput::from(QwertyScanCode::S);
+            let physical_w = UserInput::from(QwertyScanCode::W);
+            let ctrl_physical_s = UserInput::chord([
+                InputKind::Modifier(Modifier::Control),
+                QwertyScanCode::S.into(),
+            ]);
+            let physical_ctrl_s =
+                UserInput::chord([InputKind::from(QwertyScanCode::ControlLeft), S.into()]);
+            let ctrl_s = UserInput::modified(Modifier::Control, S);
+
+            // The logical and physical bindings of the same key clash
+            assert!(logical_s.clashes(&physical_s));
+            assert!(physical_s.clashes(&logical_s));
+            assert!(!logical_s.clashes(&physical_w));
+            assert!(!physical_s.clashes(&physical_s));
+
+            // They can be mixed in chords
+            assert!(ctrl_physical_s.clashes(&logical_s));
+            assert!(logical_s.clashes(&ctrl_physical_s));
+            assert!(ctrl_s.clashes(&physical_s));
+            assert!(!physical_ctrl_s.clashes(&ctrl_s));
+            assert!(!ctrl_physical_s.clashes(&ctrl_s));
+            assert!(!ctrl_physical_s.clashes(&physical_w));
+        }
+
+        #[test]
+        fn shifted_character_clashes() {
+            use crate::scan_codes::QwertyScanCode;
+
+            let at = UserInput::from(At);
+            let two = UserInput::from(Key2);
+            let shift_two = UserInput::modified(Modifier::Shift, Key2);
+            let ctrl_shift_two = UserInput::chord([
+                InputKind::Modifier(Modifier::Control),
+                InputKind::Modifier(Modifier::Shift),
+                Key2.into(),
+            ]);
+
+            // Shifted characters are chords with Shift
+            assert!(at.clashes(&two));
+            assert!(two.clashes(&at));
+            assert!(at.clashes(&QwertyScanCode::Key2.into()));
+            assert!(at.clashes(&ctrl_shift_two));
+            assert!(!at.clashes(&shift_two));
+            assert!(!at.clashes(&at));
+            assert!(!at.clashes(&Key6.into()));
+
+            assert_eq!(clash_length(&at), clash_length(&shift_two));
+            assert!(clash_length(&at) > clash_length(&two));
+        }
+
+        #[test]
+        fn axis_threshold_clashes() {
+            use crate::axislike::SingleAxis;
+            use bevy::input::gamepad::GamepadButtonType;
+
+            let stick_click = InputKind::from(GamepadButtonType::LeftThumb);
+            let walk = SingleAxis::left_stick_y().above(0.1);
+            let sprint =
+                UserInput::chord([stick_click, SingleAxis::left_stick_y().above(0.5).into()]);
+            let sneak =
+                UserInput::chord([stick_click, SingleAxis::left_stick_y().above(0.05).into()]);
+            let back = SingleAxis::left_stick_y().below(-0.1);
+
+            // Pushing the stick above 0.5 also pushes it above 0.1
+            assert!(UserInput::from(stick_click).clashes(&sprint));
+            assert!(UserInput::from(walk).clashes(&sprint));
+            assert!(!UserInput::from(back).clashes(&sprint));
+            assert!(!UserInput::from(SingleAxis::left_stick_x()).clashes(&sprint));
+
+            // But not the other way around
+            assert!(!UserInput::from(walk).clashes(&sneak));
+            assert!(sprint.clashes(&sneak));
+        }
+
+        #[test]
+        fn either_dual_axis_clashes() {
+            use crate::axislike::{DualAxis, DualAxisPolicy, EitherDualAxis};
+            use bevy::input::gamepad::GamepadButtonType;
+
+            let aim = UserInput::from(EitherDualAxis::sticks(DualAxisPolicy::LargestMagnitude));
+            let stick_click = InputKind::from(GamepadButtonType::RightThumb);
+            let precise_aim = UserInput::chord([stick_click, DualAxis::right_stick().into()]);
+
+            assert!(aim.clashes(&DualAxis::left_stick().into()));
+            assert!(UserInput::from(DualAxis::right_stick()).clashes(&aim));
+            assert!(aim.clashes(&precise_aim));
+            assert!(precise_aim.clashes(&aim));
+            assert!(!aim.clashes(&stick_click.into()));
+            assert!(!aim.clashes(&Key1.into()));
+        }
+
+        #[test]
+        fn analog_button_clashes() {
+            use crate::buttonlike::AnalogButton;
+            use bevy::input::gamepad::GamepadButtonType;
+
+            let aim = UserInput::from(AnalogButton::left_trigger(0.3));
+            let fire = UserInput::from(AnalogButton::left_trigger(0.8));
+            let modifier = InputKind::from(GamepadButtonType::LeftTrigger);
+            let alt_fire = UserInput::chord([modifier, AnalogButton::left_trigger(0.8).into()]);
+
+            // Both actions are pressed by a full pull
+            assert!(!aim.clashes(&fire));
+            assert!(!fire.clashes(&aim));
+
+            // Pulling the trigger all the way also pulls it halfway
+            assert!(aim.clashes(&alt_fire));
+            assert!(fire.clashes(&alt_fire));
+            assert!(!UserInput::from(AnalogButton::right_trigger(0.3)).clashes(&alt_fire));
+        }
+
         #[test]
         fn button_chord_clash_construction() {
             let input_map = test_input_map();
@@ -502,6 +1416,8 @@ mod tests {
                 action_b: OneAndTwo,
                 inputs_a: vec![Key1.into()],
                 inputs_b: vec![UserInput::chord([Key1, Key2])],
+                binding_indices_a: vec![0],
+                binding_indices_b: vec![2],
             };
 
             assert_eq!(observed_clash, correct_clash);
@@ -519,6 +1435,8 @@ mod tests {
                 action_b: OneAndTwo,
                 inputs_a: vec![UserInput::chord([Key1, Key2, Key3])],
                 inputs_b: vec![UserInput::chord([Key1, Key2])],
+                binding_indices_a: vec![4],
+                binding_indices_b: vec![2],
             };
 
             assert_eq!(observed_clash, correct_clash);
@@ -551,11 +1469,9 @@ mod tests {
             let input_streams = InputStreams::from_world(&app.world, None);
 
             assert_eq!(
-                resolve_clash(
-                    &simple_clash,
-                    ClashStrategy::PrioritizeLongest,
-                    &input_streams,
-                ),
+                resolve_clash(&simple_clash, ClashStrategy::PrioritizeLongest, &|input| {
+                    input_streams.input_pressed(input)
+                },),
                 Some(One)
             );
 
@@ -564,7 +1480,7 @@ mod tests {
                 resolve_clash(
                     &reversed_clash,
                     ClashStrategy::PrioritizeLongest,
-                    &input_streams,
+                    &|input| input_streams.input_pressed(input),
                 ),
                 Some(One)
             );
@@ -578,11 +1494,9 @@ mod tests {
             let input_streams = InputStreams::from_world(&app.world, None);
 
             assert_eq!(
-                resolve_clash(
-                    &chord_clash,
-                    ClashStrategy::PrioritizeLongest,
-                    &input_streams,
-                ),
+                resolve_clash(&chord_clash, ClashStrategy::PrioritizeLongest, &|input| {
+                    input_streams.input_pressed(input)
+                },),
                 Some(OneAndTwo)
             );
         }
@@ -670,5 +1584,91 @@ mod tests {
                 }
             }
         }
+
+        #[test]
+        fn cached_clashes_match_uncached() {
+            let mut input_map = test_input_map();
+            let assert_cache_matches = |input_map: &InputMap<Action>| {
+                assert_eq!(
+                    input_map.possible_clashes(),
+                    input_map.compute_possible_clashes()
+                );
+            };
+            assert_cache_matches(&input_map);
+
+            input_map.insert(Two, Key1);
+            assert_cache_matches(&input_map);
+
+            input_map.remove(&Two, Key1);
+            assert_cache_matches(&input_map);
+
+            input_map.remove_at(&OneAndTwo, 0);
+            assert_cache_matches(&input_map);
+
+            input_map.clear_action(&CtrlOne);
+            assert_cache_matches(&input_map);
+
+            input_map
+                .get_mut(&AltOne)
+                .unwrap()
+                .push(UserInput::chord([ControlLeft, Key2]));
+            assert_cache_matches(&input_map);
+
+            input_map.merge(&test_input_map());
+            assert_cache_matches(&input_map);
+
+            input_map.clear();
+            assert_cache_matches(&input_map);
+            assert!(input_map.possible_clashes().is_empty());
+        }
+
+        #[test]
+        fn clash_cache_is_not_compared() {
+            let cold = test_input_map();
+            let warm = test_input_map();
+            assert!(!warm.possible_clashes().is_empty());
+
+            assert_eq!(warm, cold);
+        }
+
+        #[test]
+        fn cached_which_pressed_matches_uncached() {
+            // Fill the cache before rebinding, so stale clashes would be noticed
+            let mut rebound = test_input_map();
+            let _ = rebound.possible_clashes();
+            rebound.clear_action(&OneAndTwo);
+            rebound.insert_chord(OneAndTwo, [Key1, Key3]);
+            rebound.insert(Two, Key3);
+
+            let mut fresh = InputMap::default();
+            for (action, input) in rebound.ordered_bindings() {
+                fresh.insert(*action, input.clone());
+            }
+
+            let pressed_sets = [
+                vec![Key1],
+                vec![Key1, Key3],
+                vec![Key1, Key2, Key3],
+                vec![ControlLeft, AltLeft, Key1],
+                vec![ControlLeft, Up],
+            ];
+
+            for pressed in pressed_sets {
+                let mut app = App::new();
+                app.add_plugins(InputPlugin);
+                for key in pressed {
+                    app.send_input(key);
+                }
+                app.update();
+                let input_streams = InputStreams::from_world(&app.world, None);
+
+                for clash_strategy in ClashStrategy::variants() {
+                    assert_eq!(
+                        rebound.which_pressed(&input_streams, *clash_strategy),
+                        fresh.which_pressed(&input_streams, *clash_strategy)
+                    );
+                }
+            }
+        }
     }
 }
@@ -1,40 +1,156 @@
 //! Run conditions for actions.
+//!
+//! These read the [`ActionState`] resource, and are never active if it does not exist.
 
 use crate::{prelude::ActionState, Actionlike};
 use bevy::prelude::Res;
 
 /// Stateful run condition that can be toggled via an action press using [`ActionState::just_pressed`].
-pub fn action_toggle_active<A>(default: bool, action: A) -> impl FnMut(Res<ActionState<A>>) -> bool
+///
+/// While the [`ActionState`] resource does not exist, this condition is inactive and cannot be toggled.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::common_conditions::action_toggle_active;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum DebugAction {
+///     ToggleOverlay,
+/// }
+///
+/// fn draw_debug_overlay() {
+///     // Draw colliders, paths, frame times...
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .add_plugins(InputManagerPlugin::<DebugAction>::default())
+///     .init_resource::<ActionState<DebugAction>>()
+///     .insert_resource(InputMap::new([(DebugAction::ToggleOverlay, KeyCode::F3)]))
+///     // The overlay starts hidden, and is shown or hidden each time F3 is pressed
+///     .add_systems(
+///         Update,
+///         draw_debug_overlay.run_if(action_toggle_active(false, DebugAction::ToggleOverlay)),
+///     );
+/// ```
+pub fn action_toggle_active<A>(
+    default: bool,
+    action: A,
+) -> impl FnMut(Option<Res<ActionState<A>>>) -> bool
 where
     A: Actionlike + Clone,
 {
     let mut active = default;
-    move |action_state: Res<ActionState<A>>| {
+    move |action_state: Option<Res<ActionState<A>>>| {
+        let Some(action_state) = action_state else {
+            return false;
+        };
         active ^= action_state.just_pressed(&action);
         active
     }
 }
 
 /// Run condition that is active if [`ActionState::pressed`] is true for the given action.
-pub fn action_pressed<A>(action: A) -> impl FnMut(Res<ActionState<A>>) -> bool
+pub fn action_pressed<A>(action: A) -> impl FnMut(Option<Res<ActionState<A>>>) -> bool
 where
     A: Actionlike + Clone,
 {
-    move |action_state: Res<ActionState<A>>| action_state.pressed(&action)
+    move |action_state: Option<Res<ActionState<A>>>| {
+        action_state.is_some_and(|action_state| action_state.pressed(&action))
+    }
 }
 
 /// Run condition that is active if [`ActionState::just_pressed`] is true for the given action.
-pub fn action_just_pressed<A>(action: A) -> impl FnMut(Res<ActionState<A>>) -> bool
+pub fn action_just_pressed<A>(action: A) -> impl FnMut(Option<Res<ActionState<A>>>) -> bool
 where
     A: Actionlike + Clone,
 {
-    move |action_state: Res<ActionState<A>>| action_state.just_pressed(&action)
+    move |action_state: Option<Res<ActionState<A>>>| {
+        action_state.is_some_and(|action_state| action_state.just_pressed(&action))
+    }
 }
 
 /// Run condition that is active if [`ActionState::just_released`] is true for the given action.
-pub fn action_just_released<A>(action: A) -> impl FnMut(Res<ActionState<A>>) -> bool
+pub fn action_just_released<A>(action: A) -> impl FnMut(Option<Res<ActionState<A>>>) -> bool
 where
     A: Actionlike + Clone,
 {
-    move |action_state: Res<ActionState<A>>| action_state.just_released(&action)
+    move |action_state: Option<Res<ActionState<A>>>| {
+        action_state.is_some_and(|action_state| action_state.just_released(&action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::*;
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Toggle,
+    }
+
+    #[derive(Resource, Default)]
+    struct Runs(u32);
+
+    fn count_runs(mut runs: ResMut<Runs>) {
+        runs.0 += 1;
+    }
+
+    fn runs(app: &App) -> u32 {
+        app.world.resource::<Runs>().0
+    }
+
+    #[test]
+    fn conditions_are_inactive_without_action_state() {
+        let mut app = App::new();
+        app.init_resource::<Runs>().add_systems(
+            Update,
+            (
+                count_runs.run_if(action_pressed(Action::Toggle)),
+                count_runs.run_if(action_just_pressed(Action::Toggle)),
+                count_runs.run_if(action_just_released(Action::Toggle)),
+                count_runs.run_if(action_toggle_active(true, Action::Toggle)),
+            ),
+        );
+
+        app.update();
+        assert_eq!(runs(&app), 0);
+    }
+
+    #[test]
+    fn toggle_flips_on_each_press() {
+        let mut app = App::new();
+        app.init_resource::<Runs>()
+            .init_resource::<ActionState<Action>>()
+            .add_systems(
+                Update,
+                count_runs.run_if(action_toggle_active(false, Action::Toggle)),
+            );
+
+        app.update();
+        assert_eq!(runs(&app), 0);
+
+        app.world
+            .resource_mut::<ActionState<Action>>()
+            .press(&Action::Toggle);
+        app.update();
+        assert_eq!(runs(&app), 1);
+
+        // Holding the action does not toggle it again
+        let mut action_state = app.world.resource_mut::<ActionState<Action>>();
+        action_state.tick(bevy::utils::Instant::now(), bevy::utils::Instant::now());
+        app.update();
+        assert_eq!(runs(&app), 2);
+
+        let mut action_state = app.world.resource_mut::<ActionState<Action>>();
+        action_state.release(&Action::Toggle);
+        action_state.press(&Action::Toggle);
+        app.update();
+        assert_eq!(runs(&app), 2);
+    }
 }
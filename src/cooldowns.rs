@@ -0,0 +1,283 @@
+//! Cooldowns prevent actions from being pressed again too soon after they were last used.
+
+use crate::{action_state::ActionState, Actionlike};
+
+use bevy::{
+    ecs::{component::Component, system::Resource},
+    utils::{Duration, HashMap},
+};
+
+/// When does the cooldown of an action begin?
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CooldownTrigger {
+    /// The cooldown begins when the action is just pressed
+    #[default]
+    OnPress,
+    /// The cooldown begins when the action is just released
+    OnRelease,
+}
+
+/// The cooldowns of each action, which stop them from being pressed again until enough time has passed
+///
+/// Add this as a component alongside an [`ActionState`], or as a resource alongside the [`ActionState`] resource.
+/// The remaining cooldowns are counted down by [`tick_action_state`](crate::systems::tick_action_state),
+/// using the same [`TickClock`](crate::timing::TickClock) as the durations of the actions,
+/// and are enforced by [`apply_cooldowns`](crate::systems::apply_cooldowns).
+///
+/// When an action that is on cooldown is just pressed, the press is swallowed:
+/// the action is consumed, and [`ActionState::blocked_by_cooldown`] reports it for that tick.
+/// Like any other consumed action, it must be released before it can be pressed again.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::Duration;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::cooldowns::Cooldowns;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Jump,
+///     Fireball,
+/// }
+///
+/// let mut cooldowns = Cooldowns::default();
+/// cooldowns.set(Action::Fireball, Duration::from_secs(3));
+///
+/// let mut action_state = ActionState::<Action>::default();
+///
+/// // Casting a fireball starts its cooldown
+/// action_state.press(&Action::Fireball);
+/// cooldowns.apply(&mut action_state);
+/// assert!(action_state.just_pressed(&Action::Fireball));
+/// assert_eq!(cooldowns.remaining(&Action::Fireball), Duration::from_secs(3));
+///
+/// // Casting it again too soon is blocked
+/// action_state.release(&Action::Fireball);
+/// cooldowns.tick(Duration::from_secs(1));
+/// action_state.press(&Action::Fireball);
+/// cooldowns.apply(&mut action_state);
+/// assert!(!action_state.pressed(&Action::Fireball));
+/// assert!(action_state.blocked_by_cooldown(&Action::Fireball));
+/// ```
+#[derive(Resource, Component, Clone, Debug, PartialEq)]
+pub struct Cooldowns<A: Actionlike> {
+    /// When the cooldowns of actions begin
+    pub trigger: CooldownTrigger,
+    /// Should presses of actions that are on cooldown be swallowed?
+    ///
+    /// If this is false, cooldowns are only tracked, and presses are never blocked.
+    pub block_presses: bool,
+    durations: HashMap<A, Duration>,
+    remaining: HashMap<A, Duration>,
+}
+
+impl<A: Actionlike> Default for Cooldowns<A> {
+    fn default() -> Self {
+        Self {
+            trigger: CooldownTrigger::default(),
+            block_presses: true,
+            durations: HashMap::default(),
+            remaining: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> Cooldowns<A> {
+    /// Sets the cooldown `duration` of the `action`
+    ///
+    /// Any cooldown that is already running is unaffected.
+    pub fn set(&mut self, action: A, duration: Duration) -> &mut Self {
+        self.durations.insert(action, duration);
+        self
+    }
+
+    /// Removes the cooldown of the `action`, making it ready immediately
+    pub fn remove(&mut self, action: &A) -> &mut Self {
+        self.durations.remove(action);
+        self.remaining.remove(action);
+        self
+    }
+
+    /// The cooldown duration of the `action`, if it has one
+    #[must_use]
+    pub fn duration(&self, action: &A) -> Option<Duration> {
+        self.durations.get(action).copied()
+    }
+
+    /// The time left before the `action` is off cooldown
+    ///
+    /// This is [`Duration::ZERO`] for actions that are ready.
+    #[must_use]
+    pub fn remaining(&self, action: &A) -> Duration {
+        self.remaining.get(action).copied().unwrap_or_default()
+    }
+
+    /// Is the `action` off cooldown?
+    #[must_use]
+    pub fn ready(&self, action: &A) -> bool {
+        self.remaining(action) == Duration::ZERO
+    }
+
+    /// Starts the cooldown of the `action`, even if it is already running
+    ///
+    /// This is done automatically by [`Cooldowns::apply`], according to the [`CooldownTrigger`].
+    pub fn trigger(&mut self, action: &A) {
+        if let Some(duration) = self.durations.get(action) {
+            self.remaining.insert(action.clone(), *duration);
+        }
+    }
+
+    /// Makes the `action` ready, ending its cooldown early
+    pub fn reset(&mut self, action: &A) {
+        self.remaining.remove(action);
+    }
+
+    /// Makes all actions ready, ending their cooldowns early
+    pub fn reset_all(&mut self) {
+        self.remaining.clear();
+    }
+
+    /// Counts down the remaining cooldowns by `delta`
+    pub fn tick(&mut self, delta: Duration) {
+        self.remaining.retain(|_, remaining| {
+            *remaining = remaining.saturating_sub(delta);
+            *remaining > Duration::ZERO
+        });
+    }
+
+    /// Swallows the presses of actions that are on cooldown, and starts the cooldowns of actions that were used
+    ///
+    /// This should be called each tick, after the `action_state` has been updated.
+    pub fn apply(&mut self, action_state: &mut ActionState<A>) {
+        for (action, duration) in self.durations.iter() {
+            let on_cooldown = self.remaining.contains_key(action);
+
+            if action_state.just_pressed(action) {
+                if on_cooldown && self.block_presses {
+                    action_state.block_by_cooldown(action);
+                } else if self.trigger == CooldownTrigger::OnPress {
+                    self.remaining.insert(action.clone(), *duration);
+                }
+            } else if action_state.just_released(action)
+                && self.trigger == CooldownTrigger::OnRelease
+            {
+                self.remaining.insert(action.clone(), *duration);
+            }
+        }
+
+        // Zero cooldowns never need to be tracked
+        self.remaining
+            .retain(|_, remaining| *remaining > Duration::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Dash,
+        Shield,
+    }
+
+    #[test]
+    fn blocked_presses_must_be_released() {
+        let mut cooldowns = Cooldowns::default();
+        cooldowns.set(Action::Dash, Duration::from_secs(1));
+        let mut action_state = ActionState::<Action>::default();
+        let now = Instant::now();
+
+        action_state.press(&Action::Dash);
+        cooldowns.apply(&mut action_state);
+        assert!(action_state.just_pressed(&Action::Dash));
+        assert!(!cooldowns.ready(&Action::Dash));
+
+        // Pressing again during the cooldown is swallowed, without a just_released edge
+        action_state.release(&Action::Dash);
+        action_state.tick(now, now);
+        action_state.press(&Action::Dash);
+        cooldowns.apply(&mut action_state);
+        assert!(action_state.released(&Action::Dash));
+        assert!(!action_state.just_released(&Action::Dash));
+        assert!(action_state.blocked_by_cooldown(&Action::Dash));
+
+        // Blocked presses don't restart the cooldown, and are only reported for a single tick
+        assert_eq!(cooldowns.remaining(&Action::Dash), Duration::from_secs(1));
+        action_state.tick(now, now);
+        assert!(!action_state.blocked_by_cooldown(&Action::Dash));
+
+        // Once the cooldown is over, the action must be released before it can be pressed again
+        cooldowns.tick(Duration::from_secs(1));
+        assert!(cooldowns.ready(&Action::Dash));
+        action_state.press(&Action::Dash);
+        assert!(action_state.released(&Action::Dash));
+        action_state.release(&Action::Dash);
+        action_state.press(&Action::Dash);
+        cooldowns.apply(&mut action_state);
+        assert!(action_state.just_pressed(&Action::Dash));
+    }
+
+    #[test]
+    fn cooldown_on_release() {
+        let mut cooldowns = Cooldowns {
+            trigger: CooldownTrigger::OnRelease,
+            ..Default::default()
+        };
+        cooldowns.set(Action::Shield, Duration::from_secs(2));
+        let mut action_state = ActionState::<Action>::default();
+        let now = Instant::now();
+
+        // Holding the action does not start the cooldown
+        action_state.press(&Action::Shield);
+        cooldowns.apply(&mut action_state);
+        action_state.tick(now, now);
+        cooldowns.tick(Duration::from_secs(1));
+        cooldowns.apply(&mut action_state);
+        assert!(cooldowns.ready(&Action::Shield));
+
+        // Releasing it does
+        action_state.release(&Action::Shield);
+        cooldowns.apply(&mut action_state);
+        assert_eq!(cooldowns.remaining(&Action::Shield), Duration::from_secs(2));
+        cooldowns.tick(Duration::from_millis(500));
+        assert_eq!(
+            cooldowns.remaining(&Action::Shield),
+            Duration::from_millis(1500)
+        );
+
+        // Actions without a cooldown are never blocked
+        action_state.press(&Action::Dash);
+        cooldowns.apply(&mut action_state);
+        assert!(action_state.just_pressed(&Action::Dash));
+    }
+
+    #[test]
+    fn tracking_without_blocking() {
+        let mut cooldowns = Cooldowns {
+            block_presses: false,
+            ..Default::default()
+        };
+        cooldowns.set(Action::Dash, Duration::from_secs(1));
+        let mut action_state = ActionState::<Action>::default();
+        let now = Instant::now();
+
+        action_state.press(&Action::Dash);
+        cooldowns.apply(&mut action_state);
+        action_state.release(&Action::Dash);
+        action_state.tick(now, now);
+        cooldowns.tick(Duration::from_millis(400));
+
+        // Presses during the cooldown go through, and restart it
+        action_state.press(&Action::Dash);
+        cooldowns.apply(&mut action_state);
+        assert!(action_state.just_pressed(&Action::Dash));
+        assert!(!action_state.blocked_by_cooldown(&Action::Dash));
+        assert_eq!(cooldowns.remaining(&Action::Dash), Duration::from_secs(1));
+    }
+}
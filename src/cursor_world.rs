@@ -0,0 +1,108 @@
+//! Aiming from an entity towards the cursor, for top-down games
+//!
+//! This crate can't know which camera shows the world, so the game projects the cursor into the world itself,
+//! and stores the result in the [`CursorWorldSource`] resource each frame.
+//! A [`CursorRelative`] input, bound with [`InputKind::CursorRelative`](crate::user_input::InputKind::CursorRelative),
+//! then reads the direction from the entity with the [`InputMap`](crate::input_map::InputMap) to the cursor,
+//! using the [`GlobalTransform`](bevy::transform::components::GlobalTransform) of that entity.
+//!
+//! # Example
+//! ```rust
+//! use bevy::input::InputSystem;
+//! use bevy::prelude::*;
+//! use bevy::window::PrimaryWindow;
+//! use leafwing_input_manager::cursor_world::{CursorRelative, CursorWorldSource};
+//! use leafwing_input_manager::plugin::InputManagerSystem;
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+//! enum Action {
+//!     Aim,
+//! }
+//!
+//! /// Projects the cursor through the 2D camera, before the actions are updated
+//! fn update_cursor_world_source(
+//!     windows: Query<&Window, With<PrimaryWindow>>,
+//!     cameras: Query<(&Camera, &GlobalTransform)>,
+//!     mut cursor_world_source: ResMut<CursorWorldSource>,
+//! ) {
+//!     let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), cameras.get_single()) else {
+//!         return;
+//!     };
+//!
+//!     cursor_world_source.0 = window
+//!         .cursor_position()
+//!         .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor));
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(InputManagerPlugin::<Action>::default())
+//!     .add_systems(
+//!         PreUpdate,
+//!         update_cursor_world_source
+//!             .after(InputSystem)
+//!             .before(InputManagerSystem::Update),
+//!     );
+//!
+//! // The player aims at full strength once the cursor is 200 units away from it
+//! let input_map = InputMap::new([(Action::Aim, CursorRelative::new(200.0))]);
+//! ```
+
+use crate::axislike::DualAxisData;
+
+use bevy::ecs::system::Resource;
+use bevy::math::Vec2;
+use bevy::reflect::Reflect;
+use bevy::utils::FloatOrd;
+use serde::{Deserialize, Serialize};
+
+/// The position of the cursor in the world, kept up to date by the game
+///
+/// [`CursorRelative`] inputs read nothing while it is [`None`], such as when the cursor is outside of the window.
+/// Update it in [`PreUpdate`](bevy::app::PreUpdate), before [`InputManagerSystem::Update`](crate::plugin::InputManagerSystem::Update),
+/// so that the actions see the position of the cursor on the same frame.
+///
+/// The [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) adds this resource.
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq)]
+pub struct CursorWorldSource(pub Option<Vec2>);
+
+/// The direction from the entity with the [`InputMap`](crate::input_map::InputMap) to the cursor, read like a [`DualAxis`](crate::axislike::DualAxis)
+///
+/// The length of its axis pair grows from `0.0` on the entity to `1.0` at `max_distance` from it, and stays at `1.0` beyond.
+/// It reads nothing for the [`InputMap`](crate::input_map::InputMap) resource, or for entities without a [`GlobalTransform`](bevy::transform::components::GlobalTransform),
+/// and while the [`CursorWorldSource`] is [`None`].
+///
+/// The `x` and `y` axes of the world are used, as in 2D games.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct CursorRelative {
+    /// How far the cursor must be from the entity for the axis pair to reach a length of `1.0`, in world units
+    pub max_distance: f32,
+}
+
+impl CursorRelative {
+    /// Creates a [`CursorRelative`] that reaches its full length `max_distance` away from the entity
+    #[must_use]
+    pub const fn new(max_distance: f32) -> CursorRelative {
+        CursorRelative { max_distance }
+    }
+
+    /// The direction from the `anchor` to the `cursor`, scaled by how far apart they are
+    #[must_use]
+    pub fn axis_pair(&self, cursor: Vec2, anchor: Vec2) -> DualAxisData {
+        let offset = cursor - anchor;
+        let magnitude = if self.max_distance > 0.0 {
+            (offset.length() / self.max_distance).min(1.0)
+        } else {
+            1.0
+        };
+
+        DualAxisData::from_xy(offset.normalize_or_zero() * magnitude)
+    }
+}
+
+impl Eq for CursorRelative {}
+impl std::hash::Hash for CursorRelative {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        FloatOrd(self.max_distance).hash(state);
+    }
+}
@@ -0,0 +1,151 @@
+//! Reports how many actions are pressed, and how long it takes to evaluate the [`InputMap`](crate::input_map::InputMap)s,
+//! through Bevy's diagnostics
+//!
+//! Add the [`InputManagerDiagnosticsPlugin`] next to the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin),
+//! and read the measurements from the [`DiagnosticsStore`](bevy::diagnostic::DiagnosticsStore),
+//! or log them with the [`LogDiagnosticsPlugin`](bevy::diagnostic::LogDiagnosticsPlugin).
+//!
+//! The evaluation time is only measured while this plugin is added.
+
+use crate::action_state::ActionState;
+use crate::plugin::InputManagerSystem;
+use crate::Actionlike;
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::diagnostic::{Diagnostic, DiagnosticId, Diagnostics, RegisterDiagnostic};
+use bevy::ecs::prelude::*;
+use bevy::utils::Duration;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use std::collections::hash_map::DefaultHasher;
+
+/// The number of measurements kept by each diagnostic
+const MAX_HISTORY_LENGTH: usize = 20;
+
+/// Registers diagnostics for the actions of type `A`, measured every frame once the [`ActionState`]s are updated
+///
+/// - the number of pressed actions, in [`pressed_actions`](Self::pressed_actions)
+/// - the number of just pressed actions, in [`just_pressed_actions`](Self::just_pressed_actions)
+/// - the time spent evaluating the [`InputMap`](crate::input_map::InputMap)s, in microseconds, in [`evaluation_time`](Self::evaluation_time)
+///
+/// Actions are counted across every [`ActionState`] component, and the [`ActionState`] resource.
+pub struct InputManagerDiagnosticsPlugin<A: Actionlike> {
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for InputManagerDiagnosticsPlugin<A> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> InputManagerDiagnosticsPlugin<A> {
+    /// The number of actions that are pressed
+    #[must_use]
+    pub fn pressed_actions() -> DiagnosticId {
+        diagnostic_id::<A>(0x8e5e_2d0b_54f1_4c3b_9a41_6f0c_1d52_e7a1)
+    }
+
+    /// The number of actions that were pressed this frame
+    #[must_use]
+    pub fn just_pressed_actions() -> DiagnosticId {
+        diagnostic_id::<A>(0x2b7c_91e4_0a3d_4f86_b5d2_c8e1_7f40_396d)
+    }
+
+    /// The time spent evaluating the [`InputMap`](crate::input_map::InputMap)s this frame, in microseconds
+    #[must_use]
+    pub fn evaluation_time() -> DiagnosticId {
+        diagnostic_id::<A>(0x5d03_f6a8_e21b_47c9_8c7e_03b9_a64f_d215)
+    }
+}
+
+/// Combines the `base` id with the type of the actions, so that each action type gets its own diagnostics
+fn diagnostic_id<A: Actionlike>(base: u128) -> DiagnosticId {
+    let mut hasher = DefaultHasher::new();
+    A::type_path().hash(&mut hasher);
+    DiagnosticId::from_u128(base ^ u128::from(hasher.finish()))
+}
+
+impl<A: Actionlike> Plugin for InputManagerDiagnosticsPlugin<A> {
+    fn build(&self, app: &mut App) {
+        let name = A::short_type_path();
+        app.register_diagnostic(Diagnostic::new(
+            Self::pressed_actions(),
+            format!("{name}/pressed"),
+            MAX_HISTORY_LENGTH,
+        ))
+        .register_diagnostic(Diagnostic::new(
+            Self::just_pressed_actions(),
+            format!("{name}/just_pressed"),
+            MAX_HISTORY_LENGTH,
+        ))
+        .register_diagnostic(
+            Diagnostic::new(
+                Self::evaluation_time(),
+                format!("{name}/evaluation_time"),
+                MAX_HISTORY_LENGTH,
+            )
+            .with_suffix("µs"),
+        )
+        .init_resource::<InputMapEvaluationTime<A>>()
+        .add_systems(
+            PreUpdate,
+            record_action_diagnostics::<A>.after(InputManagerSystem::Update),
+        );
+    }
+}
+
+/// The time spent evaluating the [`InputMap`](crate::input_map::InputMap)s of type `A` since the last measurement
+///
+/// This resource is added by the [`InputManagerDiagnosticsPlugin`],
+/// and the time is only measured by [`update_action_state`](crate::systems::update_action_state) while it exists.
+#[derive(Resource)]
+pub struct InputMapEvaluationTime<A: Actionlike> {
+    /// The accumulated evaluation time
+    pub elapsed: Duration,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for InputMapEvaluationTime<A> {
+    fn default() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Measures the diagnostics registered by the [`InputManagerDiagnosticsPlugin`], and resets the [`InputMapEvaluationTime`]
+pub fn record_action_diagnostics<A: Actionlike>(
+    mut diagnostics: Diagnostics,
+    action_state: Option<Res<ActionState<A>>>,
+    query: Query<&ActionState<A>>,
+    mut evaluation_time: ResMut<InputMapEvaluationTime<A>>,
+) {
+    let action_states = || query.iter().chain(action_state.as_deref());
+
+    diagnostics.add_measurement(
+        InputManagerDiagnosticsPlugin::<A>::pressed_actions(),
+        || {
+            action_states()
+                .map(|action_state| action_state.get_pressed().len())
+                .sum::<usize>() as f64
+        },
+    );
+    diagnostics.add_measurement(
+        InputManagerDiagnosticsPlugin::<A>::just_pressed_actions(),
+        || {
+            action_states()
+                .map(|action_state| action_state.get_just_pressed().len())
+                .sum::<usize>() as f64
+        },
+    );
+    diagnostics.add_measurement(
+        InputManagerDiagnosticsPlugin::<A>::evaluation_time(),
+        || evaluation_time.elapsed.as_secs_f64() * 1_000_000.0,
+    );
+
+    evaluation_time.elapsed = Duration::ZERO;
+}
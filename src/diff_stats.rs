@@ -0,0 +1,258 @@
+//! Bandwidth statistics for [`ActionDiff`](crate::action_diff::ActionDiff) streams
+//!
+//! Insert a [`DiffStats`] resource and add the [`record_diff_stats`] system after [`generate_action_diffs`](crate::systems::generate_action_diffs)
+//! to measure how many diffs are being sent, and how large they are once encoded.
+//! This is useful when tuning a [`DiffPolicy`](crate::action_diff::DiffPolicy).
+
+use crate::action_diff::{ActionDiffEvent, DiffOwnerId};
+use crate::Actionlike;
+
+use bevy::ecs::prelude::*;
+use bevy::time::{Real, Time};
+use bevy::utils::{Duration, HashMap};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Write};
+
+/// The number of diffs and encoded bytes sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffCounters {
+    /// The number of [`ActionDiff`](crate::action_diff::ActionDiff)s
+    pub diffs: u64,
+    /// The number of bytes used by those diffs, once encoded
+    pub bytes: u64,
+}
+
+impl std::ops::AddAssign for DiffCounters {
+    fn add_assign(&mut self, rhs: Self) {
+        self.diffs += rhs.diffs;
+        self.bytes += rhs.bytes;
+    }
+}
+
+/// Statistics about the [`ActionDiffEvent`]s generated for the [`Actionlike`] type `A`
+///
+/// These are only collected if this resource exists, and [`record_diff_stats`] is added to your app.
+///
+/// Byte counts use the same compact encoding as [`DiffFrame::encode`](crate::action_diff::DiffFrame::encode).
+/// The per-action breakdown only counts the bytes of each diff,
+/// so the totals are slightly larger due to the overhead of each event.
+#[derive(Resource, Debug, Clone)]
+pub struct DiffStats<A: Actionlike> {
+    per_action: HashMap<A, DiffCounters>,
+    total: DiffCounters,
+    /// The counters recorded at each point in time, for the last second
+    recent: VecDeque<(Duration, DiffCounters)>,
+    now: Duration,
+}
+
+impl<A: Actionlike> Default for DiffStats<A> {
+    fn default() -> Self {
+        Self {
+            per_action: HashMap::default(),
+            total: DiffCounters::default(),
+            recent: VecDeque::default(),
+            now: Duration::ZERO,
+        }
+    }
+}
+
+impl<A: Actionlike> DiffStats<A> {
+    /// The length of the window used for the per-second counters
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    /// Advances the rolling per-second counters to `now`, discarding anything older than a second
+    pub fn update(&mut self, now: Duration) {
+        self.now = now;
+        while let Some((recorded_at, _)) = self.recent.front() {
+            if now.saturating_sub(*recorded_at) < Self::WINDOW {
+                break;
+            }
+            self.recent.pop_front();
+        }
+    }
+
+    /// Records an [`ActionDiffEvent`] that was sent at the time `now`
+    pub fn record<Id>(&mut self, now: Duration, action_diff_event: &ActionDiffEvent<A, Id>)
+    where
+        A: Serialize,
+        Id: DiffOwnerId + Serialize,
+    {
+        self.update(now);
+
+        for action_diff in action_diff_event.action_diffs.iter() {
+            *self
+                .per_action
+                .entry(action_diff.action().clone())
+                .or_default() += DiffCounters {
+                diffs: 1,
+                bytes: encoded_size(action_diff),
+            };
+        }
+
+        let counters = DiffCounters {
+            diffs: action_diff_event.action_diffs.len() as u64,
+            bytes: encoded_size(action_diff_event),
+        };
+        self.total += counters;
+        self.recent.push_back((now, counters));
+    }
+
+    /// The number of diffs sent during the last second
+    #[must_use]
+    pub fn diffs_per_second(&self) -> u64 {
+        self.recent.iter().map(|(_, counters)| counters.diffs).sum()
+    }
+
+    /// The number of encoded bytes sent during the last second
+    #[must_use]
+    pub fn bytes_per_second(&self) -> u64 {
+        self.recent.iter().map(|(_, counters)| counters.bytes).sum()
+    }
+
+    /// The total number of diffs and bytes sent since these statistics were created or last [reset](Self::reset)
+    #[must_use]
+    pub fn total(&self) -> DiffCounters {
+        self.total
+    }
+
+    /// The total number of diffs and bytes sent for each action
+    #[must_use]
+    pub fn per_action(&self) -> &HashMap<A, DiffCounters> {
+        &self.per_action
+    }
+
+    /// The total number of diffs and bytes sent for the `action`
+    #[must_use]
+    pub fn action(&self, action: &A) -> DiffCounters {
+        self.per_action.get(action).copied().unwrap_or_default()
+    }
+
+    /// Clears all of the recorded statistics
+    pub fn reset(&mut self) {
+        self.per_action.clear();
+        self.total = DiffCounters::default();
+        self.recent.clear();
+    }
+
+    /// Creates a human-readable summary of these statistics
+    ///
+    /// Actions are listed from the most to the least bytes sent.
+    #[must_use]
+    pub fn report(&self) -> String
+    where
+        A: Debug,
+    {
+        let mut report = format!(
+            "{} diffs/s, {} bytes/s ({} diffs, {} bytes in total)",
+            self.diffs_per_second(),
+            self.bytes_per_second(),
+            self.total.diffs,
+            self.total.bytes
+        );
+
+        let mut per_action: Vec<(String, DiffCounters)> = self
+            .per_action
+            .iter()
+            .map(|(action, counters)| (format!("{action:?}"), *counters))
+            .collect();
+        per_action.sort_by(|(a_name, a), (b_name, b)| {
+            b.bytes.cmp(&a.bytes).then_with(|| a_name.cmp(b_name))
+        });
+        for (action, counters) in per_action {
+            // Writing to a String cannot fail
+            let _ = write!(
+                report,
+                "\n  {action}: {} diffs, {} bytes",
+                counters.diffs, counters.bytes
+            );
+        }
+
+        report
+    }
+}
+
+/// The number of bytes used by the `value` in the compact encoding used by [`DiffFrame::encode`](crate::action_diff::DiffFrame::encode)
+fn encoded_size(value: &impl Serialize) -> u64 {
+    bincode::serialized_size(value).unwrap_or_default()
+}
+
+/// Records the [`ActionDiffEvent`]s sent this frame in the [`DiffStats`] resource, if it exists
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually,
+/// after [`generate_action_diffs`](crate::systems::generate_action_diffs).
+/// If the [`DiffStats`] resource does not exist, this system does nothing.
+pub fn record_diff_stats<A: Actionlike + Serialize>(
+    diff_stats: Option<ResMut<DiffStats<A>>>,
+    time: Res<Time<Real>>,
+    mut action_diff_events: EventReader<ActionDiffEvent<A>>,
+) {
+    let Some(mut diff_stats) = diff_stats else {
+        return;
+    };
+
+    let now = time.elapsed();
+    diff_stats.update(now);
+    for action_diff_event in action_diff_events.read() {
+        diff_stats.record(now, action_diff_event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::action_diff::ActionDiff;
+    use bevy::prelude::Reflect;
+    use leafwing_input_manager_macros::Actionlike;
+    use serde::Deserialize;
+
+    #[derive(
+        Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect, Serialize, Deserialize,
+    )]
+    enum Action {
+        Run,
+        Aim,
+    }
+
+    fn event(action_diffs: Vec<ActionDiff<Action>>) -> ActionDiffEvent<Action> {
+        ActionDiffEvent {
+            owner: None,
+            action_diffs,
+        }
+    }
+
+    #[test]
+    fn counters_roll_over_after_a_second() {
+        let mut diff_stats = DiffStats::<Action>::default();
+        let run = event(vec![ActionDiff::Pressed {
+            action: Action::Run,
+        }]);
+        let aim = event(vec![ActionDiff::AxisPairChanged {
+            action: Action::Aim,
+            axis_pair: bevy::math::Vec2::ONE,
+            pressed: true,
+        }]);
+
+        diff_stats.record(Duration::from_millis(0), &run);
+        diff_stats.record(Duration::from_millis(500), &aim);
+        assert_eq!(diff_stats.diffs_per_second(), 2);
+        assert_eq!(diff_stats.action(&Action::Run).diffs, 1);
+        // Axis pairs are larger than button presses
+        assert!(diff_stats.action(&Action::Aim).bytes > diff_stats.action(&Action::Run).bytes);
+        assert!(diff_stats.bytes_per_second() > diff_stats.action(&Action::Aim).bytes);
+
+        diff_stats.update(Duration::from_millis(1200));
+        assert_eq!(diff_stats.diffs_per_second(), 1);
+        assert_eq!(diff_stats.total().diffs, 2);
+
+        let report = diff_stats.report();
+        assert!(report.starts_with("1 diffs/s"));
+        assert!(report.find("Aim").unwrap() < report.find("Run").unwrap());
+
+        diff_stats.reset();
+        assert_eq!(diff_stats.total(), DiffCounters::default());
+        assert_eq!(diff_stats.diffs_per_second(), 0);
+        assert!(diff_stats.per_action().is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+//! Adapters for sending [`ActionDiffEvent`]s through any message-passing transport
+//!
+//! Implement [`DiffTransport`] for a [`Resource`] that wraps your networking crate,
+//! then add [`send_diffs_via`] to the app producing the diffs and [`receive_diffs_via`] to the app consuming them.
+//! All of the events generated in a single frame are sent together, as a [`DiffFrame`].
+//!
+//! These are only available with the `diff_transport` feature enabled.
+
+use crate::action_diff::{ActionDiffEvent, DiffFrame, DiffOwnerId, DiffSequencer};
+use crate::Actionlike;
+
+use bevy::ecs::prelude::*;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// A way to send [`DiffFrame`]s to another app, and receive the frames that it sent back
+///
+/// Implementors are stored as a [`Resource`], and bridged to the [`ActionDiffEvent`] streams
+/// by the [`send_diffs_via`] and [`receive_diffs_via`] systems.
+pub trait DiffTransport<A: Actionlike>: Resource {
+    /// The type used to identify the owner of each [`ActionDiffEvent`]
+    ///
+    /// This is typically [`Entity`], or a [`DiffOwner`](crate::action_diff::DiffOwner) id
+    /// when using [`generate_action_diffs_by_id`](crate::systems::generate_action_diffs_by_id).
+    type Id: DiffOwnerId;
+
+    /// Sends the `frame` to the other end of the transport
+    fn send(&mut self, frame: &DiffFrame<A, Self::Id>);
+
+    /// Returns all of the frames received since the last call, in the order they were received
+    fn poll(&mut self) -> Vec<DiffFrame<A, Self::Id>>;
+}
+
+/// Sends all of the [`ActionDiffEvent`]s generated this frame through the [`DiffTransport`] `T`
+///
+/// This should be run after [`generate_action_diffs`](crate::systems::generate_action_diffs).
+/// No frame is sent if no events were generated.
+///
+/// Each frame sent is given the next [`DiffFrame::sequence`] number, starting from 0.
+pub fn send_diffs_via<A: Actionlike, T: DiffTransport<A>>(
+    mut transport: ResMut<T>,
+    mut action_diff_events: EventReader<ActionDiffEvent<A, T::Id>>,
+    mut sequence: Local<u64>,
+) {
+    let frame = DiffFrame::new(action_diff_events.read().cloned().collect());
+    if !frame.is_empty() {
+        transport.send(&frame.with_sequence(*sequence));
+        *sequence += 1;
+    }
+}
+
+/// Sends an [`ActionDiffEvent`] for each event in the [`DiffFrame`]s received by the [`DiffTransport`] `T`
+///
+/// Events should then be applied using [`ActionState::apply_diff`](crate::action_state::ActionState::apply_diff).
+///
+/// Frames with a [`DiffFrame::sequence`] number are passed through a [`DiffSequencer`],
+/// so events from frames older than the last one applied for the same owner are dropped.
+/// Insert a [`DiffSequencer<A, T::Id>`](DiffSequencer) resource to reorder frames instead, or to read its counters.
+pub fn receive_diffs_via<A: Actionlike, T: DiffTransport<A>>(
+    mut transport: ResMut<T>,
+    mut action_diff_events: EventWriter<ActionDiffEvent<A, T::Id>>,
+    sequencer: Option<ResMut<DiffSequencer<A, T::Id>>>,
+    mut default_sequencer: Local<DiffSequencer<A, T::Id>>,
+) {
+    let sequencer = match sequencer {
+        Some(sequencer) => sequencer.into_inner(),
+        None => &mut *default_sequencer,
+    };
+
+    for frame in transport.poll() {
+        action_diff_events.send_batch(sequencer.sequence(frame));
+    }
+}
+
+/// A [`DiffTransport`] that passes frames between two apps in the same process
+///
+/// Create a connected pair of channels with [`InMemoryChannel::pair`], and insert one into each app.
+/// This is primarily useful for testing and prototyping code that will later be networked.
+#[derive(Resource, Debug)]
+pub struct InMemoryChannel<A: Actionlike, Id: DiffOwnerId = Entity> {
+    outbox: Arc<Mutex<VecDeque<DiffFrame<A, Id>>>>,
+    inbox: Arc<Mutex<VecDeque<DiffFrame<A, Id>>>>,
+}
+
+impl<A: Actionlike, Id: DiffOwnerId> InMemoryChannel<A, Id> {
+    /// Creates two channels: frames sent by one are received by the other
+    #[must_use]
+    pub fn pair() -> (Self, Self) {
+        let a_to_b = Arc::<Mutex<VecDeque<DiffFrame<A, Id>>>>::default();
+        let b_to_a = Arc::<Mutex<VecDeque<DiffFrame<A, Id>>>>::default();
+
+        (
+            Self {
+                outbox: a_to_b.clone(),
+                inbox: b_to_a.clone(),
+            },
+            Self {
+                outbox: b_to_a,
+                inbox: a_to_b,
+            },
+        )
+    }
+
+    /// The number of frames waiting to be received by this channel
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.inbox.lock().unwrap().len()
+    }
+}
+
+impl<A: Actionlike, Id: DiffOwnerId> DiffTransport<A> for InMemoryChannel<A, Id> {
+    type Id = Id;
+
+    fn send(&mut self, frame: &DiffFrame<A, Id>) {
+        self.outbox.lock().unwrap().push_back(frame.clone());
+    }
+
+    fn poll(&mut self) -> Vec<DiffFrame<A, Id>> {
+        self.inbox.lock().unwrap().drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::action_diff::FrameOrdering;
+    use crate::action_state::ActionState;
+    use crate::input_map::InputMap;
+    use crate::input_mocking::MockInput;
+    use crate::plugin::{InputManagerPlugin, InputManagerSystem};
+    use crate::systems::generate_action_diffs;
+    use crate::InputManagerBundle;
+    use bevy::input::InputPlugin;
+    use bevy::prelude::*;
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Jump,
+    }
+
+    #[test]
+    fn in_memory_channel_is_bidirectional() {
+        let (mut a, mut b) = InMemoryChannel::<Action>::pair();
+        let frame = DiffFrame::new(vec![ActionDiffEvent {
+            owner: None,
+            action_diffs: Vec::new(),
+        }]);
+
+        a.send(&frame);
+        assert_eq!(a.pending(), 0);
+        assert_eq!(b.pending(), 1);
+        assert_eq!(b.poll(), vec![frame.clone()]);
+        assert!(b.poll().is_empty());
+
+        b.send(&frame);
+        assert_eq!(a.poll(), vec![frame]);
+    }
+
+    #[test]
+    fn diffs_are_bridged_between_apps() {
+        let (client_channel, server_channel) = InMemoryChannel::<Action>::pair();
+
+        let mut client = App::new();
+        client
+            .add_plugins((
+                MinimalPlugins,
+                InputPlugin,
+                InputManagerPlugin::<Action>::default(),
+            ))
+            .add_event::<ActionDiffEvent<Action>>()
+            .insert_resource(client_channel)
+            .add_systems(
+                PostUpdate,
+                (
+                    generate_action_diffs::<Action>,
+                    send_diffs_via::<Action, InMemoryChannel<Action>>,
+                )
+                    .chain(),
+            );
+        client.world.spawn(InputManagerBundle {
+            input_map: InputMap::new([(Action::Jump, KeyCode::Space)]),
+            action_state: ActionState::default(),
+        });
+
+        let mut server = App::new();
+        server
+            .add_plugins((
+                MinimalPlugins,
+                InputManagerPlugin::<Action>::server().apply_action_diffs(true),
+            ))
+            .insert_resource(server_channel)
+            .add_systems(
+                PreUpdate,
+                receive_diffs_via::<Action, InMemoryChannel<Action>>
+                    .before(InputManagerSystem::Update),
+            );
+        server.world.spawn(ActionState::<Action>::default());
+
+        client.send_input(KeyCode::Space);
+        client.update();
+        server.update();
+
+        let mut query = server.world.query::<&ActionState<Action>>();
+        assert!(query.single(&server.world).pressed(&Action::Jump));
+
+        client.release_input(KeyCode::Space);
+        client.update();
+        server.update();
+
+        let mut query = server.world.query::<&ActionState<Action>>();
+        assert!(query.single(&server.world).released(&Action::Jump));
+    }
+
+    fn server(channel: InMemoryChannel<Action>) -> App {
+        let mut server = App::new();
+        server
+            .add_plugins((
+                MinimalPlugins,
+                InputManagerPlugin::<Action>::server().apply_action_diffs(true),
+            ))
+            .insert_resource(channel)
+            .add_systems(
+                PreUpdate,
+                receive_diffs_via::<Action, InMemoryChannel<Action>>
+                    .before(InputManagerSystem::Update),
+            );
+        server.world.spawn(ActionState::<Action>::default());
+        server
+    }
+
+    #[test]
+    fn shuffled_frames_are_reordered() {
+        let (client_channel, mut recorder) = InMemoryChannel::<Action>::pair();
+        let mut client = App::new();
+        client
+            .add_plugins((
+                MinimalPlugins,
+                InputPlugin,
+                InputManagerPlugin::<Action>::default(),
+            ))
+            .add_event::<ActionDiffEvent<Action>>()
+            .insert_resource(client_channel)
+            .add_systems(
+                PostUpdate,
+                (
+                    generate_action_diffs::<Action>,
+                    send_diffs_via::<Action, InMemoryChannel<Action>>,
+                )
+                    .chain(),
+            );
+        client.world.spawn(InputManagerBundle {
+            input_map: InputMap::new([(Action::Jump, KeyCode::Space)]),
+            action_state: ActionState::default(),
+        });
+
+        for _ in 0..3 {
+            client.send_input(KeyCode::Space);
+            client.update();
+            client.release_input(KeyCode::Space);
+            client.update();
+        }
+        client.send_input(KeyCode::Space);
+        client.update();
+
+        let frames = recorder.poll();
+        assert_eq!(frames.len(), 7);
+
+        let (mut sender, receiver) = InMemoryChannel::pair();
+        let mut server = server(receiver);
+        server.insert_resource(DiffSequencer::<Action>::new(FrameOrdering::Reorder {
+            window: 4,
+        }));
+        // Swap each pair of frames
+        for pair in frames.chunks(2) {
+            for frame in pair.iter().rev() {
+                sender.send(frame);
+            }
+        }
+        server.update();
+
+        let mut query = server.world.query::<&ActionState<Action>>();
+        assert!(query.single(&server.world).pressed(&Action::Jump));
+        let sequencer = server.world.resource::<DiffSequencer<Action>>();
+        assert_eq!(sequencer.reordered, 3);
+        assert_eq!(sequencer.dropped, 0);
+    }
+}
@@ -1,7 +1,11 @@
 //! Containment module for boring implementations of the [`Display`] trait
 
-use crate::axislike::{VirtualAxis, VirtualDPad};
+use crate::axislike::{EitherDualAxis, VirtualAxis, VirtualDPad};
+use crate::gamepad_kind::GamepadKind;
+use crate::touch::TouchRegion;
 use crate::user_input::{InputKind, UserInput};
+use bevy::input::gamepad::GamepadButtonType;
+use itertools::Itertools;
 use std::fmt::Display;
 
 impl Display for UserInput {
@@ -18,11 +22,13 @@ impl Display for UserInput {
                 }
                 write!(f, "{string}")
             }
+            UserInput::VirtualDPad(dpad) if dpad.is_gamepad_dpad() => write!(f, "D-Pad"),
             UserInput::VirtualDPad(VirtualDPad {
                 up,
                 down,
                 left,
                 right,
+                ..
             }) => {
                 write!(
                     f,
@@ -32,6 +38,9 @@ impl Display for UserInput {
             UserInput::VirtualAxis(VirtualAxis { negative, positive }) => {
                 write!(f, "VirtualDPad(negative: {negative}, positive: {positive})")
             }
+            UserInput::EitherDualAxis(EitherDualAxis { first, second, .. }) => {
+                write!(f, "EitherDualAxis(first: {first:?}, second: {second:?})")
+            }
         }
     }
 }
@@ -41,10 +50,27 @@ impl Display for InputKind {
         match self {
             InputKind::SingleAxis(axis) => write!(f, "{axis:?}"),
             InputKind::DualAxis(axis) => write!(f, "{axis:?}"),
+            InputKind::TripleAxis(axis) => write!(f, "{axis:?}"),
+            InputKind::GamepadButton(GamepadButtonType::Other(code)) => write!(f, "Button {code}"),
             InputKind::GamepadButton(button) => write!(f, "{button:?}"),
+            InputKind::AnalogButton(button) => {
+                write!(f, "{:?} ({})", button.button_type, button.press_threshold)
+            }
+            #[cfg(feature = "gamepad_extras")]
+            InputKind::GamepadExtra(extra) => write!(f, "{extra:?}"),
             InputKind::Mouse(button) => write!(f, "{button:?}"),
             InputKind::MouseWheel(button) => write!(f, "{button:?}"),
             InputKind::MouseMotion(button) => write!(f, "{button:?}"),
+            InputKind::MouseClick(click) => write!(f, "{:?} click", click.button),
+            InputKind::MouseDrag(drag) => write!(f, "{:?} drag", drag.button),
+            InputKind::DragAxis(drag_axis) => write!(f, "{:?} drag axis", drag_axis.button),
+            InputKind::CursorRelative(_) => write!(f, "Cursor"),
+            InputKind::ScreenEdge(_) => write!(f, "Screen edge"),
+            InputKind::Touch(gesture) => write!(f, "{gesture:?}"),
+            InputKind::TouchJoystick(joystick) => write!(f, "{joystick:?}"),
+            InputKind::TouchWithin(gesture, TouchRegion(rect)) => {
+                write!(f, "{gesture:?} within {:?} to {:?}", rect.min, rect.max)
+            }
             InputKind::Keyboard(button) => write!(f, "{button:?}"),
             // TODO: We probably want to display the key on the currently active layout
             InputKind::KeyLocation(scan_code) => write!(f, "{scan_code:?}"),
@@ -52,3 +78,69 @@ impl Display for InputKind {
         }
     }
 }
+
+impl UserInput {
+    /// The name of this input, with gamepad buttons labeled as printed on a gamepad of the given `gamepad_kind`
+    ///
+    /// Use [`InputMap::associated_gamepad_kind`](crate::input_map::InputMap::associated_gamepad_kind) to find out which kind of gamepad is used.
+    /// Inputs that aren't gamepad buttons are named like their [`Display`] implementation.
+    #[must_use]
+    pub fn name(&self, gamepad_kind: GamepadKind) -> String {
+        match self {
+            UserInput::Single(button) => button.name(gamepad_kind),
+            UserInput::Chord(button_set) => button_set
+                .iter()
+                .map(|button| button.name(gamepad_kind))
+                .join("+"),
+            UserInput::VirtualDPad(dpad) if dpad.is_gamepad_dpad() => "D-Pad".to_string(),
+            UserInput::VirtualDPad(VirtualDPad {
+                up,
+                down,
+                left,
+                right,
+                ..
+            }) => format!(
+                "VirtualDPad(up: {}, down: {}, left: {}, right: {})",
+                up.name(gamepad_kind),
+                down.name(gamepad_kind),
+                left.name(gamepad_kind),
+                right.name(gamepad_kind)
+            ),
+            UserInput::VirtualAxis(VirtualAxis { negative, positive }) => format!(
+                "VirtualAxis(negative: {}, positive: {})",
+                negative.name(gamepad_kind),
+                positive.name(gamepad_kind)
+            ),
+            UserInput::EitherDualAxis(EitherDualAxis { first, second, .. }) => format!(
+                "EitherDualAxis(first: {}, second: {})",
+                InputKind::DualAxis(*first).name(gamepad_kind),
+                InputKind::DualAxis(*second).name(gamepad_kind)
+            ),
+        }
+    }
+}
+
+impl InputKind {
+    /// The name of this input, with gamepad buttons labeled as printed on a gamepad of the given `gamepad_kind`
+    ///
+    /// See [`UserInput::name`] for more details.
+    #[must_use]
+    pub fn name(&self, gamepad_kind: GamepadKind) -> String {
+        match self {
+            InputKind::GamepadButton(button) => gamepad_kind
+                .button_label(*button)
+                .map_or_else(|| self.to_string(), str::to_string),
+            InputKind::AnalogButton(button) => {
+                match gamepad_kind.button_label(button.button_type) {
+                    Some(label) => format!("{label} ({})", button.press_threshold),
+                    None => self.to_string(),
+                }
+            }
+            #[cfg(feature = "gamepad_extras")]
+            InputKind::GamepadExtra(extra) => extra
+                .label(gamepad_kind)
+                .map_or_else(|| self.to_string(), str::to_string),
+            _ => self.to_string(),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-//! Errors that may occur when working with 2D coordinates
+//! Errors that may occur when working with 2D coordinates, decoding networked data, or parsing gamepad inputs
 
 use derive_more::{Display, Error};
 
@@ -11,3 +11,24 @@ use derive_more::{Display, Error};
 /// In almost all cases, the correct way to handle this error is to simply not change the rotation.
 #[derive(Debug, Clone, Copy, Error, Display, PartialEq, Eq)]
 pub struct NearlySingularConversion;
+
+/// The bytes passed to [`DiffFrame::decode`](crate::action_diff::DiffFrame::decode) were not a valid encoded [`DiffFrame`](crate::action_diff::DiffFrame)
+///
+/// This typically means that the data was corrupted in transit,
+/// or was encoded using a different `Actionlike` type.
+#[cfg(feature = "diff_stats")]
+#[derive(Debug, Clone, Error, Display, PartialEq, Eq)]
+#[display(fmt = "could not decode the DiffFrame: {reason}")]
+pub struct DiffDecodingError {
+    /// A description of what went wrong
+    pub reason: String,
+}
+
+/// The string passed to [`GamepadInputAliases::parse_button`](crate::gamepad_aliases::GamepadInputAliases::parse_button)
+/// or [`GamepadInputAliases::parse_axis`](crate::gamepad_aliases::GamepadInputAliases::parse_axis) doesn't name a gamepad input
+#[derive(Debug, Clone, Error, Display, PartialEq, Eq)]
+#[display(fmt = "unknown gamepad input: {input}")]
+pub struct GamepadInputParseError {
+    /// The string that could not be parsed
+    pub input: String,
+}
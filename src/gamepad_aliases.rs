@@ -0,0 +1,289 @@
+//! Names for gamepad buttons and axes, including those that are only known by their `Other` code
+//!
+//! Flight sticks, racing wheels and other unusual devices expose many buttons and axes that Bevy can't name,
+//! which show up as [`GamepadButtonType::Other`] and [`GamepadAxisType::Other`].
+//! They can be bound like any other gamepad input, and are written as `GamepadButton(17)` and `GamepadAxis(21)`
+//! when converted to and from strings, such as in configuration files.
+//!
+//! Insert the [`GamepadInputAliases`] resource to give them human-readable names such as `TrimUp`,
+//! which are accepted when parsing and used when displaying them.
+
+use crate::axislike::AxisType;
+use crate::errors::GamepadInputParseError;
+use crate::gamepad_kind::GamepadKind;
+use crate::user_input::{InputKind, UserInput};
+
+use bevy::ecs::prelude::*;
+use bevy::input::gamepad::{GamepadAxisType, GamepadButtonType};
+use bevy::utils::HashMap;
+use itertools::Itertools;
+
+/// The gamepad buttons that have a name of their own
+const NAMED_BUTTONS: [GamepadButtonType; 19] = [
+    GamepadButtonType::South,
+    GamepadButtonType::East,
+    GamepadButtonType::North,
+    GamepadButtonType::West,
+    GamepadButtonType::C,
+    GamepadButtonType::Z,
+    GamepadButtonType::LeftTrigger,
+    GamepadButtonType::LeftTrigger2,
+    GamepadButtonType::RightTrigger,
+    GamepadButtonType::RightTrigger2,
+    GamepadButtonType::Select,
+    GamepadButtonType::Start,
+    GamepadButtonType::Mode,
+    GamepadButtonType::LeftThumb,
+    GamepadButtonType::RightThumb,
+    GamepadButtonType::DPadUp,
+    GamepadButtonType::DPadDown,
+    GamepadButtonType::DPadLeft,
+    GamepadButtonType::DPadRight,
+];
+
+/// The gamepad axes that have a name of their own
+const NAMED_AXES: [GamepadAxisType; 6] = [
+    GamepadAxisType::LeftStickX,
+    GamepadAxisType::LeftStickY,
+    GamepadAxisType::LeftZ,
+    GamepadAxisType::RightStickX,
+    GamepadAxisType::RightStickY,
+    GamepadAxisType::RightZ,
+];
+
+/// Human-readable names for gamepad buttons and axes, used when parsing and displaying them
+///
+/// Without an alias, buttons and axes are written with their variant name, such as `South` or `LeftStickX`,
+/// and `Other` codes are written as `GamepadButton(17)` or `GamepadAxis(21)`.
+/// These names are always accepted when parsing, even for inputs that have an alias.
+///
+/// This resource isn't added by any plugin: insert it yourself if you need it.
+///
+/// # Example
+/// ```rust
+/// use bevy::input::gamepad::GamepadButtonType;
+/// use leafwing_input_manager::gamepad_aliases::GamepadInputAliases;
+///
+/// let mut aliases = GamepadInputAliases::default();
+/// aliases.alias_button("TrimUp", GamepadButtonType::Other(17));
+///
+/// assert_eq!(aliases.parse_button("TrimUp"), Ok(GamepadButtonType::Other(17)));
+/// assert_eq!(aliases.parse_button("GamepadButton(17)"), Ok(GamepadButtonType::Other(17)));
+/// assert_eq!(aliases.button_to_string(GamepadButtonType::Other(17)), "TrimUp");
+/// assert_eq!(aliases.button_to_string(GamepadButtonType::Other(18)), "GamepadButton(18)");
+/// ```
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct GamepadInputAliases {
+    buttons: HashMap<String, GamepadButtonType>,
+    button_names: HashMap<GamepadButtonType, String>,
+    axes: HashMap<String, GamepadAxisType>,
+    axis_names: HashMap<GamepadAxisType, String>,
+}
+
+impl GamepadInputAliases {
+    /// Names the `button` after the `alias`
+    ///
+    /// If the `button` already had an alias, the new one is used for display, but both are accepted when parsing.
+    pub fn alias_button(
+        &mut self,
+        alias: impl Into<String>,
+        button: GamepadButtonType,
+    ) -> &mut Self {
+        let alias = alias.into();
+        self.buttons.insert(alias.clone(), button);
+        self.button_names.insert(button, alias);
+        self
+    }
+
+    /// Names the `axis` after the `alias`
+    ///
+    /// If the `axis` already had an alias, the new one is used for display, but both are accepted when parsing.
+    pub fn alias_axis(&mut self, alias: impl Into<String>, axis: GamepadAxisType) -> &mut Self {
+        let alias = alias.into();
+        self.axes.insert(alias.clone(), axis);
+        self.axis_names.insert(axis, alias);
+        self
+    }
+
+    /// The alias of the `button`, if any
+    #[must_use]
+    pub fn button_alias(&self, button: GamepadButtonType) -> Option<&str> {
+        self.button_names.get(&button).map(String::as_str)
+    }
+
+    /// The alias of the `axis`, if any
+    #[must_use]
+    pub fn axis_alias(&self, axis: GamepadAxisType) -> Option<&str> {
+        self.axis_names.get(&axis).map(String::as_str)
+    }
+
+    /// Parses a gamepad button from its alias, its variant name, or its `GamepadButton(n)` code
+    pub fn parse_button(&self, input: &str) -> Result<GamepadButtonType, GamepadInputParseError> {
+        let input = input.trim();
+        if let Some(button) = self.buttons.get(input) {
+            return Ok(*button);
+        }
+
+        if let Some(code) = parse_code(input, "GamepadButton") {
+            return Ok(GamepadButtonType::Other(code));
+        }
+
+        NAMED_BUTTONS
+            .into_iter()
+            .find(|button| format!("{button:?}") == input)
+            .ok_or_else(|| GamepadInputParseError {
+                input: input.to_string(),
+            })
+    }
+
+    /// Parses a gamepad axis from its alias, its variant name, or its `GamepadAxis(n)` code
+    pub fn parse_axis(&self, input: &str) -> Result<GamepadAxisType, GamepadInputParseError> {
+        let input = input.trim();
+        if let Some(axis) = self.axes.get(input) {
+            return Ok(*axis);
+        }
+
+        if let Some(code) = parse_code(input, "GamepadAxis") {
+            return Ok(GamepadAxisType::Other(code));
+        }
+
+        NAMED_AXES
+            .into_iter()
+            .find(|axis| format!("{axis:?}") == input)
+            .ok_or_else(|| GamepadInputParseError {
+                input: input.to_string(),
+            })
+    }
+
+    /// Writes the `button` as a string that [`GamepadInputAliases::parse_button`] understands
+    #[must_use]
+    pub fn button_to_string(&self, button: GamepadButtonType) -> String {
+        match (self.button_alias(button), button) {
+            (Some(alias), _) => alias.to_string(),
+            (None, GamepadButtonType::Other(code)) => format!("GamepadButton({code})"),
+            (None, button) => format!("{button:?}"),
+        }
+    }
+
+    /// Writes the `axis` as a string that [`GamepadInputAliases::parse_axis`] understands
+    #[must_use]
+    pub fn axis_to_string(&self, axis: GamepadAxisType) -> String {
+        match (self.axis_alias(axis), axis) {
+            (Some(alias), _) => alias.to_string(),
+            (None, GamepadAxisType::Other(code)) => format!("GamepadAxis({code})"),
+            (None, axis) => format!("{axis:?}"),
+        }
+    }
+
+    /// The name of the `input`, like [`UserInput::name`], but using the aliases of its gamepad buttons and axes
+    #[must_use]
+    pub fn name(&self, input: &UserInput, gamepad_kind: GamepadKind) -> String {
+        match input {
+            UserInput::Single(button) => self.input_kind_name(button, gamepad_kind),
+            UserInput::Chord(button_set) => button_set
+                .iter()
+                .map(|button| self.input_kind_name(button, gamepad_kind))
+                .join("+"),
+            _ => input.name(gamepad_kind),
+        }
+    }
+
+    /// The name of a single `input`, using the aliases of its gamepad buttons and axes
+    fn input_kind_name(&self, input: &InputKind, gamepad_kind: GamepadKind) -> String {
+        let alias = match input {
+            InputKind::GamepadButton(button) => self.button_alias(*button),
+            InputKind::AnalogButton(button) => {
+                return match self.button_alias(button.button_type) {
+                    Some(alias) => format!("{alias} ({})", button.press_threshold),
+                    None => input.name(gamepad_kind),
+                };
+            }
+            InputKind::SingleAxis(axis) => match axis.axis_type {
+                AxisType::Gamepad(axis_type) => self.axis_alias(axis_type),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        alias.map_or_else(|| input.name(gamepad_kind), str::to_string)
+    }
+}
+
+/// Parses the `n` out of a `prefix(n)` code
+fn parse_code(input: &str, prefix: &str) -> Option<u8> {
+    input
+        .strip_prefix(prefix)?
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axislike::SingleAxis;
+
+    #[test]
+    fn round_trip() {
+        let aliases = GamepadInputAliases::default();
+
+        for button in NAMED_BUTTONS
+            .into_iter()
+            .chain([GamepadButtonType::Other(0), GamepadButtonType::Other(255)])
+        {
+            let string = aliases.button_to_string(button);
+            assert_eq!(aliases.parse_button(&string), Ok(button), "{string}");
+        }
+
+        for axis in NAMED_AXES
+            .into_iter()
+            .chain([GamepadAxisType::Other(0), GamepadAxisType::Other(255)])
+        {
+            let string = aliases.axis_to_string(axis);
+            assert_eq!(aliases.parse_axis(&string), Ok(axis), "{string}");
+        }
+
+        assert_eq!(
+            aliases.parse_button("GamepadButton( 21 )"),
+            Ok(GamepadButtonType::Other(21))
+        );
+        assert!(aliases.parse_button("GamepadButton(256)").is_err());
+        assert!(aliases.parse_button("GamepadAxis(21)").is_err());
+        assert!(aliases.parse_axis("TrimUp").is_err());
+    }
+
+    #[test]
+    fn aliases() {
+        let mut aliases = GamepadInputAliases::default();
+        aliases
+            .alias_button("TrimUp", GamepadButtonType::Other(17))
+            .alias_axis("Throttle", GamepadAxisType::Other(21));
+
+        assert_eq!(
+            aliases.parse_button("TrimUp"),
+            Ok(GamepadButtonType::Other(17))
+        );
+        assert_eq!(
+            aliases.parse_axis("Throttle"),
+            Ok(GamepadAxisType::Other(21))
+        );
+        assert_eq!(
+            aliases.axis_to_string(GamepadAxisType::Other(21)),
+            "Throttle"
+        );
+
+        let chord = UserInput::chord([
+            InputKind::GamepadButton(GamepadButtonType::Other(17)),
+            InputKind::SingleAxis(SingleAxis::symmetric(GamepadAxisType::Other(21), 0.1)),
+        ]);
+        assert_eq!(
+            aliases.name(&chord, GamepadKind::Generic),
+            "TrimUp+Throttle"
+        );
+
+        let unaliased = UserInput::from(GamepadButtonType::Other(18));
+        assert_eq!(aliases.name(&unaliased, GamepadKind::Xbox), "Button 18");
+    }
+}
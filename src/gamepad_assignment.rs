@@ -0,0 +1,264 @@
+//! Assigns gamepads to players in local multiplayer games
+//!
+//! Add the [`GamepadAssignmentPlugin`], and players join by pressing its join button on an unassigned gamepad.
+//! The gamepad is then set as the [`InputMap::gamepad`] of the player entity,
+//! which is either an entity marked with [`AwaitingGamepad`], or an entity spawned by [`GamepadAssignmentPlugin::with_spawner`].
+//!
+//! With [`GamepadAssignmentPlugin::with_template`], each player is instead given a copy of the same [`InputMap`] that only reads their gamepad.
+//!
+//! Players controlled by the keyboard are unaffected: don't mark them with [`AwaitingGamepad`],
+//! and don't add gamepad inputs to their [`InputMap`], as an [`InputMap`] without a gamepad reads every gamepad.
+
+use crate::input_map::InputMap;
+use crate::plugin::InputManagerSystem;
+use crate::Actionlike;
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::ecs::prelude::*;
+use bevy::input::gamepad::{
+    Gamepad, GamepadButton, GamepadButtonType, GamepadConnection, GamepadConnectionEvent, Gamepads,
+};
+use bevy::input::{Input, InputSystem};
+use bevy::utils::HashMap;
+use core::marker::PhantomData;
+
+/// A function that spawns a new player entity with an [`InputMap<A>`], when a gamepad joins and no entity is [`AwaitingGamepad`]
+///
+/// The gamepad is set on the [`InputMap<A>`] of the returned entity once the commands are applied.
+pub type PlayerSpawner = fn(&mut Commands, Gamepad) -> Entity;
+
+/// A [`Plugin`] that assigns gamepads to the players using the actions of type `A`, when they press its join button
+///
+/// See the [module documentation](self) for more details.
+pub struct GamepadAssignmentPlugin<A: Actionlike> {
+    join_button: GamepadButtonType,
+    spawner: Option<PlayerSpawner>,
+    template: Option<InputMap<A>>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for GamepadAssignmentPlugin<A> {
+    fn default() -> Self {
+        Self {
+            join_button: GamepadButtonType::Start,
+            spawner: None,
+            template: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> GamepadAssignmentPlugin<A> {
+    /// Sets the button that must be pressed on an unassigned gamepad to join, which is [`GamepadButtonType::Start`] by default
+    #[must_use]
+    pub fn with_join_button(mut self, join_button: GamepadButtonType) -> Self {
+        self.join_button = join_button;
+        self
+    }
+
+    /// Spawns a new player using the `spawner` when a gamepad joins while no entity is [`AwaitingGamepad`]
+    ///
+    /// Without a spawner, gamepads can only join if an entity is waiting for one.
+    #[must_use]
+    pub fn with_spawner(mut self, spawner: PlayerSpawner) -> Self {
+        self.spawner = Some(spawner);
+        self
+    }
+
+    /// Gives every player that joins a copy of the `template`, which only reads their gamepad
+    ///
+    /// The copy is made with [`InputMap::clone_for_gamepad`], and its keyboard and mouse bindings are removed
+    /// with [`InputMap::remove_non_gamepad_inputs`], as players usually don't share the keyboard.
+    /// It replaces the [`InputMap`] of the player entity, or is inserted into the entities spawned by the [`PlayerSpawner`].
+    #[must_use]
+    pub fn with_template(mut self, template: InputMap<A>) -> Self {
+        self.template = Some(template);
+        self
+    }
+}
+
+impl<A: Actionlike> Plugin for GamepadAssignmentPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GamepadAssignments::<A> {
+            join_button: self.join_button,
+            spawner: self.spawner,
+            template: self.template.clone(),
+            assignments: HashMap::default(),
+            _phantom: PhantomData,
+        })
+        .add_event::<GamepadAssigned>()
+        .add_event::<PlayerGamepadLost>()
+        .add_systems(
+            PreUpdate,
+            assign_gamepads::<A>
+                .after(InputSystem)
+                // Players only start reading their gamepad on the next frame, so the join button does not press any action
+                .after(InputManagerSystem::Update),
+        );
+    }
+}
+
+/// Marks a player entity with an [`InputMap<A>`] that should be assigned the next gamepad that joins
+///
+/// If several entities are waiting, the lowest [`Entity`] is assigned first.
+/// The marker is removed once the entity has a gamepad, and added back if that gamepad is disconnected.
+#[derive(Component)]
+pub struct AwaitingGamepad<A: Actionlike> {
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for AwaitingGamepad<A> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// The gamepads assigned to the players using the actions of type `A`
+///
+/// This resource is added by the [`GamepadAssignmentPlugin`].
+#[derive(Resource)]
+pub struct GamepadAssignments<A: Actionlike> {
+    join_button: GamepadButtonType,
+    spawner: Option<PlayerSpawner>,
+    template: Option<InputMap<A>>,
+    assignments: HashMap<Gamepad, Entity>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> GamepadAssignments<A> {
+    /// The player entity that the `gamepad` is assigned to, if any
+    #[must_use]
+    pub fn entity(&self, gamepad: Gamepad) -> Option<Entity> {
+        self.assignments.get(&gamepad).copied()
+    }
+
+    /// The gamepad assigned to the player `entity`, if any
+    #[must_use]
+    pub fn gamepad(&self, entity: Entity) -> Option<Gamepad> {
+        self.assignments
+            .iter()
+            .find(|(_, &assigned)| assigned == entity)
+            .map(|(&gamepad, _)| gamepad)
+    }
+
+    /// Iterates over the assigned gamepads, and the player entities they are assigned to
+    pub fn iter(&self) -> impl Iterator<Item = (Gamepad, Entity)> + '_ {
+        self.assignments
+            .iter()
+            .map(|(&gamepad, &entity)| (gamepad, entity))
+    }
+
+    /// The copy of the template given to the player who joined with the `gamepad`, if there is a template
+    fn input_map_for(&self, gamepad: Gamepad) -> Option<InputMap<A>> {
+        let mut input_map = self.template.as_ref()?.clone_for_gamepad(gamepad);
+        input_map.remove_non_gamepad_inputs();
+        Some(input_map)
+    }
+}
+
+/// Sent when a gamepad is assigned to a player entity by the [`GamepadAssignmentPlugin`]
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GamepadAssigned {
+    /// The player entity
+    pub entity: Entity,
+    /// The gamepad that joined
+    pub gamepad: Gamepad,
+}
+
+/// Sent when the gamepad assigned to a player entity by the [`GamepadAssignmentPlugin`] is disconnected
+///
+/// The player is marked with [`AwaitingGamepad`] again, so the next gamepad to join is assigned to it.
+/// Until then, its [`InputMap`] keeps the disconnected gamepad, so that it does not read the gamepads of other players.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerGamepadLost {
+    /// The player entity
+    pub entity: Entity,
+    /// The gamepad that was disconnected
+    pub gamepad: Gamepad,
+}
+
+/// Assigns gamepads that press the join button to player entities, and frees the assignments of disconnected gamepads
+///
+/// Assignments of despawned player entities are freed silently.
+#[allow(clippy::too_many_arguments)]
+pub fn assign_gamepads<A: Actionlike>(
+    mut commands: Commands,
+    mut assignments: ResMut<GamepadAssignments<A>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut input_maps: Query<&mut InputMap<A>>,
+    awaiting: Query<Entity, (With<AwaitingGamepad<A>>, With<InputMap<A>>)>,
+    mut assigned_events: EventWriter<GamepadAssigned>,
+    mut lost_events: EventWriter<PlayerGamepadLost>,
+) {
+    assignments
+        .assignments
+        .retain(|_, &mut entity| input_maps.contains(entity));
+
+    for event in connection_events.read() {
+        if event.connection != GamepadConnection::Disconnected {
+            continue;
+        }
+        if let Some(entity) = assignments.assignments.remove(&event.gamepad) {
+            commands
+                .entity(entity)
+                .insert(AwaitingGamepad::<A>::default());
+            lost_events.send(PlayerGamepadLost {
+                entity,
+                gamepad: event.gamepad,
+            });
+        }
+    }
+
+    let mut awaiting: Vec<Entity> = awaiting.iter().collect();
+    // Popped from the back, so the lowest entity is assigned first
+    awaiting.sort_unstable_by(|a, b| b.cmp(a));
+
+    for gamepad in gamepads.iter() {
+        if assignments.assignments.contains_key(&gamepad)
+            || !gamepad_buttons.just_pressed(GamepadButton::new(gamepad, assignments.join_button))
+        {
+            continue;
+        }
+
+        let template = assignments.input_map_for(gamepad);
+        let entity = if let Some(entity) = awaiting.pop() {
+            commands.entity(entity).remove::<AwaitingGamepad<A>>();
+            if let Ok(mut input_map) = input_maps.get_mut(entity) {
+                match template {
+                    Some(template) => *input_map = template,
+                    None => {
+                        input_map.set_gamepad(gamepad);
+                    }
+                }
+            }
+            entity
+        } else if let Some(spawner) = assignments.spawner {
+            let entity = spawner(&mut commands, gamepad);
+            commands.add(move |world: &mut World| {
+                let Some(mut player) = world.get_entity_mut(entity) else {
+                    return;
+                };
+                match template {
+                    Some(template) => {
+                        player.insert(template);
+                    }
+                    None => {
+                        if let Some(mut input_map) = player.get_mut::<InputMap<A>>() {
+                            input_map.set_gamepad(gamepad);
+                        }
+                    }
+                }
+            });
+            entity
+        } else {
+            continue;
+        };
+
+        assignments.assignments.insert(gamepad, entity);
+        assigned_events.send(GamepadAssigned { entity, gamepad });
+    }
+}
@@ -0,0 +1,360 @@
+//! Guided calibration of the axes of gamepads, such as worn sticks that don't rest at the center or don't reach their ends
+//!
+//! Add the [`GamepadCalibrationPlugin`], then send a [`StartCalibration`] event for the gamepad to calibrate.
+//! The calibration goes through the [`CalibrationPhase`]s, which you should prompt the player for:
+//! first the player leaves the gamepad at rest, so that the center of each axis is measured,
+//! then they move each stick and trigger to its ends, so that their range is measured.
+//!
+//! Send a [`FinishCalibration`] event once the player is done, or once the [`CalibrationPhase::Done`] phase is reached.
+//! The resulting [`GamepadCalibration`] is stored in the [`GamepadSettingsOverride`] resource,
+//! keyed by the name of the gamepad so that it still applies once the gamepad is reconnected,
+//! and a [`CalibrationComplete`] event is sent with the [`CalibrationReport`].
+//!
+//! Spikes in the values read from the gamepad are ignored,
+//! by clipping the most extreme values of each axis as set by [`CalibrationSettings::clipped_fraction`].
+
+use crate::gamepad_settings::{AxisCalibration, GamepadCalibration, GamepadSettingsOverride};
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::ecs::prelude::*;
+use bevy::input::gamepad::{Gamepad, GamepadAxis, GamepadAxisType, Gamepads};
+use bevy::input::{Axis, InputSystem};
+use bevy::time::Time;
+use bevy::utils::{Duration, HashMap};
+
+/// Adds the [`GamepadCalibrator`] resource, and the system that calibrates gamepads when a [`StartCalibration`] event is sent
+///
+/// See the [module documentation](self) for more details.
+#[derive(Debug, Default)]
+pub struct GamepadCalibrationPlugin {
+    settings: CalibrationSettings,
+}
+
+impl GamepadCalibrationPlugin {
+    /// Sets how long each phase of the calibration lasts, and how many spikes are ignored
+    #[must_use]
+    pub fn with_settings(mut self, settings: CalibrationSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+}
+
+impl Plugin for GamepadCalibrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GamepadCalibrator {
+            settings: self.settings,
+            sessions: HashMap::default(),
+        })
+        .add_event::<StartCalibration>()
+        .add_event::<FinishCalibration>()
+        .add_event::<CalibrationComplete>()
+        .add_systems(PreUpdate, calibrate_gamepads.after(InputSystem));
+    }
+}
+
+/// How long each phase of the calibration lasts, and how many spikes are ignored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationSettings {
+    /// How long the center of the axes is measured, during [`CalibrationPhase::Resting`]
+    pub rest_duration: Duration,
+    /// How long the range of the axes is measured, during [`CalibrationPhase::Sampling`]
+    pub sample_duration: Duration,
+    /// The fraction of the values of each axis that is ignored at each end of its range, to reject spikes
+    ///
+    /// This should be between `0.0` and `0.5`.
+    pub clipped_fraction: f32,
+}
+
+impl Default for CalibrationSettings {
+    fn default() -> Self {
+        Self {
+            rest_duration: Duration::from_secs(1),
+            sample_duration: Duration::from_secs(5),
+            clipped_fraction: 0.02,
+        }
+    }
+}
+
+/// The phase of the calibration of a gamepad, which the player should be prompted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CalibrationPhase {
+    /// The player should leave the gamepad at rest
+    Resting,
+    /// The player should move each stick and trigger to its ends
+    Sampling,
+    /// The calibration is ready to be finished with a [`FinishCalibration`] event
+    Done,
+}
+
+/// Starts calibrating the `gamepad`, discarding any calibration of it that is in progress
+///
+/// Gamepads that aren't connected are ignored.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StartCalibration {
+    /// The gamepad to calibrate
+    pub gamepad: Gamepad,
+}
+
+/// Finishes calibrating the `gamepad`, and stores the result in the [`GamepadSettingsOverride`]
+///
+/// The calibration can be finished before it reaches [`CalibrationPhase::Done`],
+/// using the values measured so far.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinishCalibration {
+    /// The gamepad being calibrated
+    pub gamepad: Gamepad,
+}
+
+/// Sent when the calibration of the `gamepad` is stored in the [`GamepadSettingsOverride`]
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct CalibrationComplete {
+    /// The gamepad that was calibrated
+    pub gamepad: Gamepad,
+    /// What was measured
+    pub report: CalibrationReport,
+}
+
+/// What was measured during the calibration of a gamepad
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationReport {
+    /// The name of the gamepad, which keys its calibration in the [`GamepadSettingsOverride`]
+    pub name: String,
+    /// The calibration of each axis that was measured
+    pub calibration: GamepadCalibration,
+    /// The number of values read from each axis
+    pub samples: usize,
+    /// The number of values of each axis that were ignored as spikes
+    pub clipped_samples: usize,
+}
+
+/// The gamepads being calibrated
+///
+/// This resource is added by the [`GamepadCalibrationPlugin`].
+#[derive(Resource, Debug)]
+pub struct GamepadCalibrator {
+    /// How long each phase of the calibration lasts, and how many spikes are ignored
+    pub settings: CalibrationSettings,
+    sessions: HashMap<Gamepad, CalibrationSession>,
+}
+
+impl GamepadCalibrator {
+    /// The phase of the calibration of the `gamepad`, or [`None`] if it isn't being calibrated
+    #[must_use]
+    pub fn phase(&self, gamepad: Gamepad) -> Option<CalibrationPhase> {
+        let session = self.sessions.get(&gamepad)?;
+        Some(if session.elapsed < self.settings.rest_duration {
+            CalibrationPhase::Resting
+        } else if session.elapsed < self.settings.rest_duration + self.settings.sample_duration {
+            CalibrationPhase::Sampling
+        } else {
+            CalibrationPhase::Done
+        })
+    }
+
+    /// How far the calibration of the `gamepad` has progressed, from `0.0` to `1.0`, or [`None`] if it isn't being calibrated
+    #[must_use]
+    pub fn progress(&self, gamepad: Gamepad) -> Option<f32> {
+        let session = self.sessions.get(&gamepad)?;
+        let total = self.settings.rest_duration + self.settings.sample_duration;
+        if total.is_zero() {
+            return Some(1.0);
+        }
+        Some((session.elapsed.as_secs_f32() / total.as_secs_f32()).min(1.0))
+    }
+}
+
+/// The values read from a gamepad being calibrated
+#[derive(Debug)]
+struct CalibrationSession {
+    name: String,
+    elapsed: Duration,
+    rest_samples: HashMap<GamepadAxisType, Vec<f32>>,
+    samples: HashMap<GamepadAxisType, Vec<f32>>,
+}
+
+impl CalibrationSession {
+    /// Measures the calibration of each axis that was read, ignoring the `clipped_fraction` of the values at each end of their range
+    fn report(self, clipped_fraction: f32) -> CalibrationReport {
+        let mut report = CalibrationReport {
+            name: self.name,
+            calibration: GamepadCalibration::default(),
+            samples: 0,
+            clipped_samples: 0,
+        };
+
+        for (axis_type, mut samples) in self.samples {
+            let mut rest_samples = self
+                .rest_samples
+                .get(&axis_type)
+                .cloned()
+                .unwrap_or_default();
+            let center = median(&mut rest_samples).unwrap_or_default();
+
+            samples.extend(rest_samples);
+            samples.sort_unstable_by(f32::total_cmp);
+            let clipped = clipped_count(samples.len(), clipped_fraction);
+            let (Some(min), Some(max)) = (
+                samples.get(clipped),
+                samples.len().checked_sub(clipped + 1).map(|i| samples[i]),
+            ) else {
+                continue;
+            };
+
+            report.samples = report.samples.max(samples.len());
+            report.clipped_samples = report.clipped_samples.max(2 * clipped);
+            report.calibration.axes.insert(
+                axis_type,
+                AxisCalibration {
+                    min: min.min(center),
+                    center,
+                    max: max.max(center),
+                },
+            );
+        }
+
+        report
+    }
+}
+
+/// The number of values ignored at each end of the range of `len` values
+fn clipped_count(len: usize, clipped_fraction: f32) -> usize {
+    let clipped = (len as f32 * clipped_fraction.clamp(0.0, 0.5)) as usize;
+    // Always keep at least one value
+    clipped.min(len.saturating_sub(1) / 2)
+}
+
+/// The median of the `values`, or [`None`] if there are none
+fn median(values: &mut [f32]) -> Option<f32> {
+    values.sort_unstable_by(f32::total_cmp);
+    values.get(values.len() / 2).copied()
+}
+
+/// Starts, advances and finishes the calibration of gamepads, according to the [`StartCalibration`] and [`FinishCalibration`] events
+///
+/// Calibrations of gamepads that are disconnected are discarded.
+#[allow(clippy::too_many_arguments)]
+pub fn calibrate_gamepads(
+    mut commands: Commands,
+    mut calibrator: ResMut<GamepadCalibrator>,
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut settings: Option<ResMut<GamepadSettingsOverride>>,
+    mut start_events: EventReader<StartCalibration>,
+    mut finish_events: EventReader<FinishCalibration>,
+    mut complete_events: EventWriter<CalibrationComplete>,
+) {
+    calibrator
+        .sessions
+        .retain(|&gamepad, _| gamepads.contains(gamepad));
+
+    for &StartCalibration { gamepad } in start_events.read() {
+        let Some(name) = gamepads.name(gamepad) else {
+            continue;
+        };
+        calibrator.sessions.insert(
+            gamepad,
+            CalibrationSession {
+                name: name.to_string(),
+                elapsed: Duration::ZERO,
+                rest_samples: HashMap::default(),
+                samples: HashMap::default(),
+            },
+        );
+    }
+
+    let phases: HashMap<Gamepad, CalibrationPhase> = calibrator
+        .sessions
+        .keys()
+        .filter_map(|&gamepad| Some((gamepad, calibrator.phase(gamepad)?)))
+        .collect();
+    for axis in gamepad_axes.devices() {
+        let Some(session) = calibrator.sessions.get_mut(&axis.gamepad) else {
+            continue;
+        };
+        let value = gamepad_axes.get(*axis).unwrap_or_default();
+        // Every axis is sampled from the start, so that the range of the axes that never move is known
+        session.samples.entry(axis.axis_type).or_default();
+        match phases[&axis.gamepad] {
+            CalibrationPhase::Resting => session
+                .rest_samples
+                .entry(axis.axis_type)
+                .or_default()
+                .push(value),
+            CalibrationPhase::Sampling => session
+                .samples
+                .entry(axis.axis_type)
+                .or_default()
+                .push(value),
+            CalibrationPhase::Done => (),
+        }
+    }
+    for session in calibrator.sessions.values_mut() {
+        session.elapsed += time.delta();
+    }
+
+    let mut inserted_settings: Option<GamepadSettingsOverride> = None;
+    for &FinishCalibration { gamepad } in finish_events.read() {
+        let Some(session) = calibrator.sessions.remove(&gamepad) else {
+            continue;
+        };
+        let report = session.report(calibrator.settings.clipped_fraction);
+
+        let calibrations = match settings.as_mut() {
+            Some(settings) => &mut settings.calibrations,
+            None => {
+                &mut inserted_settings
+                    .get_or_insert_with(Default::default)
+                    .calibrations
+            }
+        };
+        calibrations.insert(report.name.clone(), report.calibration.clone());
+
+        complete_events.send(CalibrationComplete { gamepad, report });
+    }
+
+    if let Some(inserted_settings) = inserted_settings {
+        commands.insert_resource(inserted_settings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spikes_are_clipped() {
+        let mut session = CalibrationSession {
+            name: "Gamepad".to_string(),
+            elapsed: Duration::ZERO,
+            rest_samples: HashMap::from_iter([(GamepadAxisType::LeftStickX, vec![0.25; 5])]),
+            samples: HashMap::from_iter([(GamepadAxisType::LeftStickX, vec![1.0, -1.0])]),
+        };
+        let samples = session
+            .samples
+            .get_mut(&GamepadAxisType::LeftStickX)
+            .unwrap();
+        samples.extend([0.75; 10]);
+        samples.extend([-0.5; 10]);
+
+        let report = session.report(0.05);
+        assert_eq!(report.samples, 27);
+        assert_eq!(report.clipped_samples, 2);
+        assert_eq!(
+            report.calibration.axes[&GamepadAxisType::LeftStickX],
+            AxisCalibration {
+                min: -0.5,
+                center: 0.25,
+                max: 0.75,
+            }
+        );
+    }
+
+    #[test]
+    fn clipping_keeps_a_value() {
+        assert_eq!(clipped_count(0, 0.5), 0);
+        assert_eq!(clipped_count(1, 0.5), 0);
+        assert_eq!(clipped_count(3, 0.5), 1);
+        assert_eq!(clipped_count(100, 0.02), 2);
+    }
+}
@@ -0,0 +1,193 @@
+//! Extra buttons found on some gamepads, such as touchpads or the back buttons of the Steam Deck
+//!
+//! Bevy doesn't name these buttons: they are reported as [`GamepadButtonType::Other`],
+//! with codes that differ between kinds of gamepads and between backends.
+//! A [`GamepadExtra`] names one of these buttons, and is turned into the code used by the [`GamepadKind`] of each gamepad when it is read.
+//! Bind it with [`InputKind::GamepadExtra`] like any other button: the binding works across controllers and is serialized by name.
+//!
+//! The codes follow the button indices of SDL's game controller API, which most backends that report these buttons share.
+//! Buttons of gamepads that aren't known are left as their raw [`GamepadButtonType::Other`] code by [`normalize_button`].
+
+use crate::gamepad_kind::GamepadKind;
+use crate::user_input::InputKind;
+
+use bevy::input::gamepad::GamepadButtonType;
+use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::doc_markdown)] // False alarm because it thinks PlayStation is an un-quoted item
+/// An extra button found on some gamepads, which Bevy reports as [`GamepadButtonType::Other`]
+///
+/// The back buttons are numbered from the top: `BackLeft` is above `BackLeft2`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Reflect,
+)]
+pub enum GamepadExtra {
+    /// Clicking the touchpad of a PlayStation controller
+    TouchpadClick,
+    /// The microphone mute button of a DualSense controller
+    Mute,
+    /// The share button of an Xbox Series controller
+    Share,
+    /// The quick access button of a Steam Deck
+    QuickAccess,
+    /// The upper left back button, such as L4 on a Steam Deck or P3 on an Xbox Elite controller
+    BackLeft,
+    /// The upper right back button, such as R4 on a Steam Deck or P1 on an Xbox Elite controller
+    BackRight,
+    /// The lower left back button, such as L5 on a Steam Deck or P4 on an Xbox Elite controller
+    BackLeft2,
+    /// The lower right back button, such as R5 on a Steam Deck or P2 on an Xbox Elite controller
+    BackRight2,
+}
+
+/// The code of each extra button, for each kind of gamepad that has it
+const EXTRA_BUTTONS: [(GamepadKind, GamepadExtra, u8); 12] = [
+    (GamepadKind::PlayStation, GamepadExtra::TouchpadClick, 20),
+    (GamepadKind::PlayStation, GamepadExtra::Mute, 15),
+    (GamepadKind::Xbox, GamepadExtra::Share, 15),
+    (GamepadKind::Xbox, GamepadExtra::BackRight, 16),
+    (GamepadKind::Xbox, GamepadExtra::BackLeft, 17),
+    (GamepadKind::Xbox, GamepadExtra::BackRight2, 18),
+    (GamepadKind::Xbox, GamepadExtra::BackLeft2, 19),
+    (GamepadKind::SteamDeck, GamepadExtra::QuickAccess, 15),
+    (GamepadKind::SteamDeck, GamepadExtra::BackRight, 16),
+    (GamepadKind::SteamDeck, GamepadExtra::BackLeft, 17),
+    (GamepadKind::SteamDeck, GamepadExtra::BackRight2, 18),
+    (GamepadKind::SteamDeck, GamepadExtra::BackLeft2, 19),
+];
+
+impl GamepadExtra {
+    /// The button that reports this extra button on a gamepad of the given `kind`
+    ///
+    /// Returns [`None`] if this kind of gamepad doesn't have this button, or if it isn't known.
+    #[must_use]
+    pub fn button_type(self, kind: GamepadKind) -> Option<GamepadButtonType> {
+        EXTRA_BUTTONS
+            .iter()
+            .find(|&&(extra_kind, extra, _)| extra_kind == kind && extra == self)
+            .map(|&(.., code)| GamepadButtonType::Other(code))
+    }
+
+    /// The extra button reported as the `button_type` by a gamepad of the given `kind`, if any
+    #[must_use]
+    pub fn from_button_type(kind: GamepadKind, button_type: GamepadButtonType) -> Option<Self> {
+        let GamepadButtonType::Other(code) = button_type else {
+            return None;
+        };
+
+        EXTRA_BUTTONS
+            .iter()
+            .find(|&&(extra_kind, _, extra_code)| extra_kind == kind && extra_code == code)
+            .map(|&(_, extra, _)| extra)
+    }
+
+    /// The label printed on this button for the given `kind` of gamepad, if it is known
+    #[must_use]
+    pub fn label(self, kind: GamepadKind) -> Option<&'static str> {
+        use GamepadExtra::*;
+
+        match (kind, self) {
+            (GamepadKind::PlayStation, TouchpadClick) => Some("Touchpad"),
+            (GamepadKind::PlayStation, Mute) => Some("Mute"),
+            (GamepadKind::Xbox, Share) => Some("Share"),
+            (GamepadKind::Xbox, BackRight) => Some("P1"),
+            (GamepadKind::Xbox, BackRight2) => Some("P2"),
+            (GamepadKind::Xbox, BackLeft) => Some("P3"),
+            (GamepadKind::Xbox, BackLeft2) => Some("P4"),
+            (GamepadKind::SteamDeck, QuickAccess) => Some("..."),
+            (GamepadKind::SteamDeck, BackLeft) => Some("L4"),
+            (GamepadKind::SteamDeck, BackRight) => Some("R4"),
+            (GamepadKind::SteamDeck, BackLeft2) => Some("L5"),
+            (GamepadKind::SteamDeck, BackRight2) => Some("R5"),
+            _ => None,
+        }
+    }
+}
+
+/// The input to bind for the `button_type` pressed on a gamepad of the given `kind`
+///
+/// Known extra buttons become an [`InputKind::GamepadExtra`], and every other button is left as an [`InputKind::GamepadButton`].
+///
+/// # Example
+/// ```rust
+/// use bevy::input::gamepad::GamepadButtonType;
+/// use leafwing_input_manager::gamepad_extras::{normalize_button, GamepadExtra};
+/// use leafwing_input_manager::gamepad_kind::GamepadKind;
+/// use leafwing_input_manager::user_input::InputKind;
+///
+/// assert_eq!(
+///     normalize_button(GamepadKind::PlayStation, GamepadButtonType::Other(20)),
+///     InputKind::GamepadExtra(GamepadExtra::TouchpadClick)
+/// );
+/// assert_eq!(
+///     normalize_button(GamepadKind::Generic, GamepadButtonType::Other(20)),
+///     InputKind::GamepadButton(GamepadButtonType::Other(20))
+/// );
+/// ```
+#[must_use]
+pub fn normalize_button(kind: GamepadKind, button_type: GamepadButtonType) -> InputKind {
+    match GamepadExtra::from_button_type(kind, button_type) {
+        Some(extra) => InputKind::GamepadExtra(extra),
+        None => InputKind::GamepadButton(button_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_buttons_are_normalized() {
+        for (kind, extra, code) in EXTRA_BUTTONS {
+            let button_type = GamepadButtonType::Other(code);
+            assert_eq!(extra.button_type(kind), Some(button_type));
+            assert_eq!(
+                normalize_button(kind, button_type),
+                InputKind::GamepadExtra(extra),
+                "{kind:?} {code}"
+            );
+            assert!(extra.label(kind).is_some());
+        }
+    }
+
+    #[test]
+    fn unknown_buttons_are_left_raw() {
+        for (kind, button_type) in [
+            (GamepadKind::Generic, GamepadButtonType::Other(20)),
+            (GamepadKind::SwitchPro, GamepadButtonType::Other(15)),
+            (GamepadKind::PlayStation, GamepadButtonType::Other(16)),
+            (GamepadKind::Xbox, GamepadButtonType::Other(20)),
+            (GamepadKind::PlayStation, GamepadButtonType::South),
+        ] {
+            assert_eq!(
+                normalize_button(kind, button_type),
+                InputKind::GamepadButton(button_type)
+            );
+        }
+
+        assert_eq!(
+            GamepadExtra::TouchpadClick.button_type(GamepadKind::SteamDeck),
+            None
+        );
+    }
+
+    #[test]
+    fn serialized_by_name() {
+        use serde_test::{assert_tokens, Token};
+
+        assert_tokens(
+            &InputKind::GamepadExtra(GamepadExtra::BackLeft2),
+            &[
+                Token::NewtypeVariant {
+                    name: "InputKind",
+                    variant: "GamepadExtra",
+                },
+                Token::UnitVariant {
+                    name: "GamepadExtra",
+                    variant: "BackLeft2",
+                },
+            ],
+        );
+    }
+}
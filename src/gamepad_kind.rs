@@ -0,0 +1,268 @@
+//! Detects which kind of gamepad is used, so that buttons can be displayed with the labels printed on it
+//!
+//! The [`GamepadKind`] is guessed from the name of the gamepad in the [`Gamepads`] resource, which is reported by the driver.
+//! These names are not standardized, so use the [`GamepadKindOverride`] resource when the guess is wrong,
+//! or to let players choose which labels they see.
+
+use crate::input_map::InputMap;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::*;
+use bevy::input::gamepad::{Gamepad, GamepadButtonType, Gamepads};
+use bevy::reflect::Reflect;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+#[allow(clippy::doc_markdown)] // False alarm because it thinks PlayStation is an un-quoted item
+/// The family of a gamepad, which determines the labels printed on its buttons
+///
+/// Buttons are named after their position: [`GamepadButtonType::South`] is labeled "A" on an Xbox controller,
+/// "╳" on a PlayStation controller and "B" on a Nintendo Switch Pro controller.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+pub enum GamepadKind {
+    /// An Xbox controller, or one that imitates it through XInput
+    Xbox,
+    /// A PlayStation controller, such as a DualShock or a DualSense
+    PlayStation,
+    /// A Nintendo Switch Pro controller
+    SwitchPro,
+    /// The built-in controls of a Steam Deck
+    SteamDeck,
+    /// Any other gamepad, whose buttons are displayed with their [`GamepadButtonType`]
+    #[default]
+    Generic,
+}
+
+impl GamepadKind {
+    /// Guesses the [`GamepadKind`] from the `name` of the gamepad, as found in the [`Gamepads`] resource
+    ///
+    /// The name is matched case-insensitively against well-known brand and model names,
+    /// and gamepads that aren't recognized are [`GamepadKind::Generic`].
+    #[must_use]
+    pub fn from_name(name: &str) -> GamepadKind {
+        let name = name.to_lowercase();
+        let contains_any =
+            |patterns: &[&str]| patterns.iter().any(|pattern| name.contains(pattern));
+
+        if contains_any(&["steam deck"]) {
+            GamepadKind::SteamDeck
+        } else if contains_any(&["xbox", "x-box", "xinput"]) {
+            GamepadKind::Xbox
+        } else if contains_any(&[
+            "playstation",
+            "dualshock",
+            "dualsense",
+            "sony",
+            "ps3",
+            "ps4",
+            "ps5",
+        ]) {
+            GamepadKind::PlayStation
+        } else if contains_any(&["switch", "pro controller", "nintendo"]) {
+            GamepadKind::SwitchPro
+        } else {
+            GamepadKind::Generic
+        }
+    }
+
+    /// The [`GamepadKind`] of the `gamepad`, or [`None`] if it isn't connected
+    #[must_use]
+    pub fn of(gamepad: Gamepad, gamepads: &Gamepads) -> Option<GamepadKind> {
+        gamepads.name(gamepad).map(GamepadKind::from_name)
+    }
+
+    /// The label printed on the `button` for this kind of gamepad
+    ///
+    /// Returns [`None`] for [`GamepadKind::Generic`] gamepads, and for buttons that this kind of gamepad doesn't have.
+    #[must_use]
+    pub fn button_label(&self, button: GamepadButtonType) -> Option<&'static str> {
+        use GamepadButtonType::*;
+
+        // The d-pad looks the same on every gamepad
+        let dpad = match button {
+            DPadUp => Some("D-Pad Up"),
+            DPadDown => Some("D-Pad Down"),
+            DPadLeft => Some("D-Pad Left"),
+            DPadRight => Some("D-Pad Right"),
+            _ => None,
+        };
+
+        match self {
+            GamepadKind::Xbox => match button {
+                South => Some("A"),
+                East => Some("B"),
+                West => Some("X"),
+                North => Some("Y"),
+                LeftTrigger => Some("LB"),
+                LeftTrigger2 => Some("LT"),
+                RightTrigger => Some("RB"),
+                RightTrigger2 => Some("RT"),
+                Select => Some("View"),
+                Start => Some("Menu"),
+                Mode => Some("Xbox"),
+                LeftThumb => Some("LS"),
+                RightThumb => Some("RS"),
+                _ => dpad,
+            },
+            GamepadKind::PlayStation => match button {
+                South => Some("╳"),
+                East => Some("○"),
+                West => Some("□"),
+                North => Some("△"),
+                LeftTrigger => Some("L1"),
+                LeftTrigger2 => Some("L2"),
+                RightTrigger => Some("R1"),
+                RightTrigger2 => Some("R2"),
+                Select => Some("Share"),
+                Start => Some("Options"),
+                Mode => Some("PS"),
+                LeftThumb => Some("L3"),
+                RightThumb => Some("R3"),
+                _ => dpad,
+            },
+            GamepadKind::SwitchPro => match button {
+                South => Some("B"),
+                East => Some("A"),
+                West => Some("Y"),
+                North => Some("X"),
+                LeftTrigger => Some("L"),
+                LeftTrigger2 => Some("ZL"),
+                RightTrigger => Some("R"),
+                RightTrigger2 => Some("ZR"),
+                Select => Some("-"),
+                Start => Some("+"),
+                Mode => Some("Home"),
+                LeftThumb => Some("LS"),
+                RightThumb => Some("RS"),
+                _ => dpad,
+            },
+            GamepadKind::SteamDeck => match button {
+                South => Some("A"),
+                East => Some("B"),
+                West => Some("X"),
+                North => Some("Y"),
+                LeftTrigger => Some("L1"),
+                LeftTrigger2 => Some("L2"),
+                RightTrigger => Some("R1"),
+                RightTrigger2 => Some("R2"),
+                Select => Some("View"),
+                Start => Some("Menu"),
+                Mode => Some("Steam"),
+                LeftThumb => Some("L3"),
+                RightThumb => Some("R3"),
+                _ => dpad,
+            },
+            GamepadKind::Generic => None,
+        }
+    }
+}
+
+/// Forces the [`GamepadKind`] of some gamepads, for when it is detected incorrectly
+///
+/// This resource isn't added by any plugin: insert it yourself if you need it.
+///
+/// # Example
+/// ```rust
+/// use bevy::input::gamepad::Gamepad;
+/// use leafwing_input_manager::gamepad_kind::{GamepadKind, GamepadKindOverride};
+///
+/// let mut kind_override = GamepadKindOverride::default();
+/// kind_override.set(Gamepad::new(0), GamepadKind::PlayStation);
+///
+/// assert_eq!(kind_override.get(Gamepad::new(0)), Some(GamepadKind::PlayStation));
+/// assert_eq!(kind_override.get(Gamepad::new(1)), None);
+/// ```
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct GamepadKindOverride {
+    kinds: HashMap<Gamepad, GamepadKind>,
+}
+
+impl GamepadKindOverride {
+    /// Forces the `gamepad` to be treated as the given `kind`
+    pub fn set(&mut self, gamepad: Gamepad, kind: GamepadKind) -> &mut Self {
+        self.kinds.insert(gamepad, kind);
+        self
+    }
+
+    /// Goes back to detecting the kind of the `gamepad`, returning its previous override if any
+    pub fn remove(&mut self, gamepad: Gamepad) -> Option<GamepadKind> {
+        self.kinds.remove(&gamepad)
+    }
+
+    /// The [`GamepadKind`] forced for the `gamepad`, if any
+    #[must_use]
+    pub fn get(&self, gamepad: Gamepad) -> Option<GamepadKind> {
+        self.kinds.get(&gamepad).copied()
+    }
+
+    /// The [`GamepadKind`] of the gamepad used by the `input_map`, preferring the overridden kind over the detected one
+    ///
+    /// See [`InputMap::associated_gamepad_kind`] for how the gamepad is chosen.
+    #[must_use]
+    pub fn associated_gamepad_kind<A: Actionlike>(
+        &self,
+        input_map: &InputMap<A>,
+        gamepads: &Gamepads,
+    ) -> Option<GamepadKind> {
+        let gamepad = input_map.guess_gamepad(gamepads)?;
+        self.get(gamepad)
+            .or_else(|| GamepadKind::of(gamepad, gamepads))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_input::{InputKind, UserInput};
+    use bevy::input::keyboard::KeyCode;
+
+    #[test]
+    fn kind_from_name() {
+        for (name, kind) in [
+            ("Xbox Wireless Controller", GamepadKind::Xbox),
+            ("Microsoft X-Box 360 pad", GamepadKind::Xbox),
+            ("XInput Controller #1", GamepadKind::Xbox),
+            ("Sony PLAYSTATION(R)3 Controller", GamepadKind::PlayStation),
+            ("PS4 Controller", GamepadKind::PlayStation),
+            ("DualSense Wireless Controller", GamepadKind::PlayStation),
+            ("Nintendo Switch Pro Controller", GamepadKind::SwitchPro),
+            ("Pro Controller", GamepadKind::SwitchPro),
+            ("Steam Deck", GamepadKind::SteamDeck),
+            ("Logitech Dual Action", GamepadKind::Generic),
+            ("", GamepadKind::Generic),
+        ] {
+            assert_eq!(GamepadKind::from_name(name), kind, "{name}");
+        }
+    }
+
+    #[test]
+    fn button_labels() {
+        use GamepadButtonType::*;
+
+        assert_eq!(GamepadKind::Xbox.button_label(South), Some("A"));
+        assert_eq!(GamepadKind::PlayStation.button_label(South), Some("╳"));
+        assert_eq!(GamepadKind::SwitchPro.button_label(South), Some("B"));
+        assert_eq!(GamepadKind::SteamDeck.button_label(Mode), Some("Steam"));
+        assert_eq!(GamepadKind::Generic.button_label(South), None);
+
+        assert_eq!(GamepadKind::Xbox.button_label(DPadUp), Some("D-Pad Up"));
+        assert_eq!(GamepadKind::Xbox.button_label(C), None);
+    }
+
+    #[test]
+    fn user_input_names() {
+        let jump = UserInput::from(GamepadButtonType::South);
+        assert_eq!(jump.name(GamepadKind::Xbox), "A");
+        assert_eq!(jump.name(GamepadKind::PlayStation), "╳");
+        assert_eq!(jump.name(GamepadKind::Generic), "South");
+
+        let chord = UserInput::chord([
+            InputKind::GamepadButton(GamepadButtonType::LeftTrigger),
+            InputKind::GamepadButton(GamepadButtonType::East),
+        ]);
+        assert_eq!(chord.name(GamepadKind::PlayStation), "L1+○");
+
+        let key = UserInput::from(KeyCode::Space);
+        assert_eq!(key.name(GamepadKind::Xbox), key.to_string());
+    }
+}
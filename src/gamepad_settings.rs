@@ -0,0 +1,330 @@
+//! Gamepad settings chosen by the players, such as the deadzone of the sticks, which apply to every [`InputMap`](crate::input_map::InputMap)
+//!
+//! Insert the [`GamepadSettingsOverride`] resource to adjust the values read from each gamepad,
+//! before they are processed by the bindings of any input map.
+//! Per-binding settings, such as the deadzone of a [`DualAxis`](crate::axislike::DualAxis)
+//! or the thresholds and inversion of a [`SingleAxis`](crate::axislike::SingleAxis), still apply afterwards, to the adjusted values.
+//! They have the final say, so a binding inverted on top of an inverted axis reads the axis as it was originally.
+//!
+//! The [`GamepadCalibration`] of each gamepad, such as measured by the [`GamepadCalibrationPlugin`](crate::gamepad_calibration::GamepadCalibrationPlugin),
+//! applies first, to the values read from the gamepad before any of the [`GamepadPreferences`].
+
+use bevy::ecs::prelude::*;
+use bevy::input::gamepad::{Gamepad, GamepadAxisType, GamepadButtonType, Gamepads};
+use bevy::math::Vec2;
+use bevy::reflect::Reflect;
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+/// How the values read from a single gamepad are adjusted, as stored in the [`GamepadSettingsOverride`]
+///
+/// The default settings leave the values untouched.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct GamepadPreferences {
+    /// Stick positions closer to the center than this distance are read as centered
+    ///
+    /// The deadzone is circular, and positions outside of it are rescaled so that the stick still reaches `1.0`.
+    /// This should be between `0.0` and `1.0`.
+    pub stick_deadzone: f32,
+    /// How far [`GamepadButtonType::LeftTrigger2`] and [`GamepadButtonType::RightTrigger2`] must be pulled to be pressed
+    ///
+    /// If [`None`], the triggers are pressed according to Bevy's [`GamepadSettings`](bevy::input::gamepad::GamepadSettings).
+    pub trigger_threshold: Option<f32>,
+    /// The axes whose values are inverted, such as [`GamepadAxisType::RightStickY`] for an inverted camera
+    pub inverted_axes: HashSet<GamepadAxisType>,
+}
+
+impl GamepadPreferences {
+    /// Adjusts the `value` of the `axis_type`, given the `other_value` of the other axis of the same stick
+    ///
+    /// The `other_value` is only used for the axes of the sticks, to apply the circular [`GamepadPreferences::stick_deadzone`].
+    #[must_use]
+    pub fn adjust_axis_value(
+        &self,
+        axis_type: GamepadAxisType,
+        value: f32,
+        other_value: f32,
+    ) -> f32 {
+        let mut value = value;
+
+        if self.stick_deadzone > 0.0 && stick_partner(axis_type).is_some() {
+            let length = Vec2::new(value, other_value).length();
+            value = if length <= self.stick_deadzone {
+                0.0
+            } else {
+                value * (length - self.stick_deadzone) / (1.0 - self.stick_deadzone) / length
+            };
+        }
+
+        if self.inverted_axes.contains(&axis_type) {
+            value = -value;
+        }
+
+        value
+    }
+
+    /// Is the `button_type` pressed, given its `value`?
+    ///
+    /// Returns [`None`] if these preferences don't change when the `button_type` is pressed.
+    #[must_use]
+    pub fn button_pressed(&self, button_type: GamepadButtonType, value: f32) -> Option<bool> {
+        match button_type {
+            GamepadButtonType::LeftTrigger2 | GamepadButtonType::RightTrigger2 => self
+                .trigger_threshold
+                .map(|trigger_threshold| value >= trigger_threshold),
+            _ => None,
+        }
+    }
+}
+
+/// The range of values that a single gamepad axis was measured to reach
+///
+/// Calibrated values are rescaled so that the `center` reads as `0.0`, and the `min` and `max` read as `-1.0` and `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct AxisCalibration {
+    /// The lowest value reached by the axis
+    pub min: f32,
+    /// The value of the axis at rest
+    pub center: f32,
+    /// The highest value reached by the axis
+    pub max: f32,
+}
+
+impl AxisCalibration {
+    /// The distance from the center under which a side of the axis is considered not to have been measured, and is left unscaled
+    const MIN_RANGE: f32 = 0.1;
+
+    /// Rescales the `value` read from the axis
+    #[must_use]
+    pub fn calibrate(&self, value: f32) -> f32 {
+        let offset = value - self.center;
+        let range = if offset >= 0.0 {
+            self.max - self.center
+        } else {
+            self.center - self.min
+        };
+
+        let value = if range < Self::MIN_RANGE {
+            offset
+        } else {
+            offset / range
+        };
+        value.clamp(-1.0, 1.0)
+    }
+}
+
+/// The [`AxisCalibration`] of each axis of a single gamepad, as stored in the [`GamepadSettingsOverride`]
+///
+/// Axes without a calibration are left untouched.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct GamepadCalibration {
+    /// The calibration of each axis
+    pub axes: HashMap<GamepadAxisType, AxisCalibration>,
+}
+
+impl GamepadCalibration {
+    /// Rescales the `value` read from the `axis_type`, according to its [`AxisCalibration`] if any
+    #[must_use]
+    pub fn calibrate(&self, axis_type: GamepadAxisType, value: f32) -> f32 {
+        self.axes
+            .get(&axis_type)
+            .map_or(value, |calibration| calibration.calibrate(value))
+    }
+}
+
+/// The [`GamepadPreferences`] of each gamepad, which adjust the values read by every [`InputMap`](crate::input_map::InputMap)
+///
+/// This resource isn't added by any plugin: insert it to apply the settings, such as once they are loaded from the player's save.
+/// See the [module documentation](self) for how these interact with the settings of each binding.
+///
+/// # Example
+/// ```rust
+/// use bevy::input::gamepad::{Gamepad, GamepadAxisType};
+/// use leafwing_input_manager::gamepad_settings::{GamepadPreferences, GamepadSettingsOverride};
+///
+/// let mut settings = GamepadSettingsOverride::default();
+/// settings.default.stick_deadzone = 0.2;
+///
+/// // The second player plays with an inverted camera
+/// let mut inverted = settings.default.clone();
+/// inverted.inverted_axes.insert(GamepadAxisType::RightStickY);
+/// settings.gamepads.insert(Gamepad::new(1), inverted);
+///
+/// assert_eq!(settings.preferences(Gamepad::new(0)).stick_deadzone, 0.2);
+/// assert!(settings.preferences(Gamepad::new(1)).inverted_axes.contains(&GamepadAxisType::RightStickY));
+/// ```
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct GamepadSettingsOverride {
+    /// The preferences of the gamepads that don't have their own entry
+    pub default: GamepadPreferences,
+    /// The preferences of specific gamepads, which replace the default entry
+    pub gamepads: HashMap<Gamepad, GamepadPreferences>,
+    /// The calibration of each gamepad, keyed by the name of the gamepad
+    ///
+    /// Unlike their [`Gamepad`] id, the name of gamepads stays the same when they are reconnected.
+    #[serde(default)]
+    pub calibrations: HashMap<String, GamepadCalibration>,
+}
+
+impl GamepadSettingsOverride {
+    /// The [`GamepadPreferences`] of the `gamepad`, which are the default entry unless it has its own
+    #[must_use]
+    pub fn preferences(&self, gamepad: Gamepad) -> &GamepadPreferences {
+        self.gamepads.get(&gamepad).unwrap_or(&self.default)
+    }
+
+    /// The [`GamepadCalibration`] of the `gamepad`, looked up by its name in the `gamepads`
+    #[must_use]
+    pub fn calibration(
+        &self,
+        gamepad: Gamepad,
+        gamepads: &Gamepads,
+    ) -> Option<&GamepadCalibration> {
+        self.calibrations.get(gamepads.name(gamepad)?)
+    }
+}
+
+/// The other axis of the stick that the `axis_type` belongs to, if it is the axis of a stick
+#[must_use]
+pub(crate) fn stick_partner(axis_type: GamepadAxisType) -> Option<GamepadAxisType> {
+    match axis_type {
+        GamepadAxisType::LeftStickX => Some(GamepadAxisType::LeftStickY),
+        GamepadAxisType::LeftStickY => Some(GamepadAxisType::LeftStickX),
+        GamepadAxisType::RightStickX => Some(GamepadAxisType::RightStickY),
+        GamepadAxisType::RightStickY => Some(GamepadAxisType::RightStickX),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circular_stick_deadzone() {
+        let preferences = GamepadPreferences {
+            stick_deadzone: 0.5,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            preferences.adjust_axis_value(GamepadAxisType::LeftStickX, 0.3, 0.3),
+            0.0
+        );
+        assert_eq!(
+            preferences.adjust_axis_value(GamepadAxisType::LeftStickX, 0.75, 0.0),
+            0.5
+        );
+        assert_eq!(
+            preferences.adjust_axis_value(GamepadAxisType::RightStickY, -1.0, 0.0),
+            -1.0
+        );
+        // The triggers are not sticks
+        assert_eq!(
+            preferences.adjust_axis_value(GamepadAxisType::LeftZ, 0.3, 0.0),
+            0.3
+        );
+    }
+
+    #[test]
+    fn inverted_axes_and_triggers() {
+        let mut preferences = GamepadPreferences {
+            trigger_threshold: Some(0.5),
+            ..Default::default()
+        };
+        preferences
+            .inverted_axes
+            .insert(GamepadAxisType::RightStickY);
+
+        assert_eq!(
+            preferences.adjust_axis_value(GamepadAxisType::RightStickY, 0.5, 0.0),
+            -0.5
+        );
+        assert_eq!(
+            preferences.adjust_axis_value(GamepadAxisType::RightStickX, 0.5, 0.0),
+            0.5
+        );
+
+        assert_eq!(
+            preferences.button_pressed(GamepadButtonType::LeftTrigger2, 0.4),
+            Some(false)
+        );
+        assert_eq!(
+            preferences.button_pressed(GamepadButtonType::RightTrigger2, 0.5),
+            Some(true)
+        );
+        assert_eq!(
+            preferences.button_pressed(GamepadButtonType::South, 0.0),
+            None
+        );
+        assert_eq!(
+            GamepadPreferences::default().button_pressed(GamepadButtonType::LeftTrigger2, 0.4),
+            None
+        );
+    }
+
+    #[test]
+    fn axis_calibration() {
+        let calibration = AxisCalibration {
+            min: -0.75,
+            center: 0.25,
+            max: 0.75,
+        };
+
+        assert_eq!(calibration.calibrate(0.25), 0.0);
+        assert_eq!(calibration.calibrate(0.5), 0.5);
+        assert_eq!(calibration.calibrate(0.75), 1.0);
+        assert_eq!(calibration.calibrate(1.0), 1.0);
+        assert_eq!(calibration.calibrate(-0.25), -0.5);
+        assert_eq!(calibration.calibrate(-1.0), -1.0);
+
+        // A trigger at rest is at one end of its range
+        let trigger = AxisCalibration {
+            min: 0.0,
+            center: 0.0,
+            max: 0.5,
+        };
+        assert_eq!(trigger.calibrate(0.25), 0.5);
+        assert_eq!(trigger.calibrate(-0.05), -0.05);
+    }
+
+    #[test]
+    fn serialization() {
+        let mut settings = GamepadSettingsOverride::default();
+        settings.default.stick_deadzone = 0.3;
+        settings.gamepads.insert(
+            Gamepad::new(1),
+            GamepadPreferences {
+                stick_deadzone: 0.1,
+                trigger_threshold: Some(0.2),
+                inverted_axes: HashSet::from_iter([GamepadAxisType::LeftStickY]),
+            },
+        );
+        settings.calibrations.insert(
+            "Flight Stick".to_string(),
+            GamepadCalibration {
+                axes: HashMap::from_iter([(
+                    GamepadAxisType::Other(3),
+                    AxisCalibration {
+                        min: -0.9,
+                        center: 0.05,
+                        max: 0.95,
+                    },
+                )]),
+            },
+        );
+
+        let bytes = bincode::serialize(&settings).unwrap();
+        let deserialized: GamepadSettingsOverride = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(deserialized, settings);
+        assert_eq!(
+            deserialized.preferences(Gamepad::new(0)).stick_deadzone,
+            0.3
+        );
+        assert_eq!(
+            deserialized.preferences(Gamepad::new(1)).stick_deadzone,
+            0.1
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Captures the next input pressed by the player, such as to let them rebind an action
+//!
+//! Add the [`InputCapturePlugin`], then insert an [`InputCapture`] resource when the player should press their new input.
+//! Once they do, the [`CapturedInput`] is available from [`InputCapture::captured`],
+//! and can be inserted into the [`InputMap`](crate::input_map::InputMap) of the action being rebound.
+//! Remove the resource to stop listening.
+//!
+//! [`InputCapture::gamepad_only`] only listens to a single gamepad, ignoring the keyboard, the mouse and every other gamepad,
+//! so that the rebinding screen of one player can't be answered by another.
+
+use crate::axislike::{DualAxis, SingleAxis};
+#[cfg(feature = "gamepad_extras")]
+use crate::gamepad_extras::normalize_button;
+#[cfg(feature = "gamepad_extras")]
+use crate::gamepad_kind::GamepadKind;
+use crate::user_input::UserInput;
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::ecs::prelude::*;
+use bevy::input::gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, Gamepads};
+use bevy::input::keyboard::KeyCode;
+use bevy::input::mouse::MouseButton;
+use bevy::input::{Axis, Input, InputSystem};
+
+/// Adds the system that fills in the [`InputCapture`] resource, whenever it exists
+pub struct InputCapturePlugin;
+
+impl Plugin for InputCapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, capture_input.after(InputSystem));
+    }
+}
+
+/// Listens for the next input pressed by the player
+///
+/// Keys and buttons are captured when they are just pressed.
+/// With the `gamepad_extras` feature, the extra buttons of known gamepads are captured as a `GamepadExtra`.
+/// Pressure-sensitive buttons, such as triggers, are captured as buttons once they are pressed past the `button_threshold`,
+/// and gamepad axes are captured once they are pushed past the `axis_threshold`, as described by the [`StickCapture`].
+///
+/// This resource isn't added by any plugin: insert it when the player should press an input,
+/// and remove it once you are done with the [`CapturedInput`].
+///
+/// # Example
+/// ```rust
+/// use bevy::input::gamepad::Gamepad;
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::input_capture::InputCapture;
+///
+/// fn start_rebinding(mut commands: Commands) {
+///     // Only the first player's gamepad can answer
+///     commands.insert_resource(InputCapture::gamepad_only(Gamepad { id: 0 }));
+/// }
+///
+/// fn finish_rebinding(mut commands: Commands, capture: Res<InputCapture>) {
+///     if let Some(captured) = capture.captured() {
+///         println!("Bound to {}", captured.input);
+///         commands.remove_resource::<InputCapture>();
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputCapture {
+    /// The only gamepad that is listened to, if any
+    gamepad: Option<Gamepad>,
+    /// How far a pressure-sensitive button must be pressed to be captured
+    pub button_threshold: f32,
+    /// How far a gamepad axis must be pushed to be captured
+    pub axis_threshold: f32,
+    /// How the axes of analog sticks are captured
+    pub stick_capture: StickCapture,
+    captured: Option<CapturedInput>,
+}
+
+impl Default for InputCapture {
+    fn default() -> Self {
+        Self {
+            gamepad: None,
+            button_threshold: Self::DEFAULT_THRESHOLD,
+            axis_threshold: Self::DEFAULT_THRESHOLD,
+            stick_capture: StickCapture::default(),
+            captured: None,
+        }
+    }
+}
+
+impl InputCapture {
+    /// The default `button_threshold` and `axis_threshold`
+    pub const DEFAULT_THRESHOLD: f32 = 0.5;
+
+    /// Listens to the keyboard, the mouse and every gamepad
+    #[must_use]
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Listens to the `gamepad` only, ignoring the keyboard, the mouse and every other gamepad
+    #[must_use]
+    pub fn gamepad_only(gamepad: Gamepad) -> Self {
+        Self {
+            gamepad: Some(gamepad),
+            ..Default::default()
+        }
+    }
+
+    /// Sets how far a pressure-sensitive button must be pressed to be captured
+    #[must_use]
+    pub fn with_button_threshold(mut self, button_threshold: f32) -> Self {
+        self.button_threshold = button_threshold;
+        self
+    }
+
+    /// Sets how far a gamepad axis must be pushed to be captured
+    #[must_use]
+    pub fn with_axis_threshold(mut self, axis_threshold: f32) -> Self {
+        self.axis_threshold = axis_threshold;
+        self
+    }
+
+    /// Sets how the axes of analog sticks are captured
+    #[must_use]
+    pub fn with_stick_capture(mut self, stick_capture: StickCapture) -> Self {
+        self.stick_capture = stick_capture;
+        self
+    }
+
+    /// The only gamepad that is listened to, or [`None`] if every device is listened to
+    #[must_use]
+    pub fn gamepad(&self) -> Option<Gamepad> {
+        self.gamepad
+    }
+
+    /// The input pressed by the player, once they pressed one
+    #[must_use]
+    pub fn captured(&self) -> Option<&CapturedInput> {
+        self.captured.as_ref()
+    }
+
+    /// Takes the input pressed by the player, and starts listening for another one
+    ///
+    /// Held gamepad axes and pressure-sensitive buttons are captured again on the next frame.
+    pub fn take(&mut self) -> Option<CapturedInput> {
+        self.captured.take()
+    }
+
+    /// Does this capture listen to the `gamepad`?
+    fn listens_to(&self, gamepad: Gamepad) -> bool {
+        self.gamepad.unwrap_or(gamepad) == gamepad
+    }
+
+    /// The input to bind for the `axis_type` pushed to `value`
+    fn axis_input(&self, axis_type: GamepadAxisType, value: f32) -> UserInput {
+        let stick = match axis_type {
+            GamepadAxisType::LeftStickX | GamepadAxisType::LeftStickY => {
+                Some(DualAxis::left_stick())
+            }
+            GamepadAxisType::RightStickX | GamepadAxisType::RightStickY => {
+                Some(DualAxis::right_stick())
+            }
+            _ => None,
+        };
+
+        match (self.stick_capture, stick) {
+            (StickCapture::DualAxis, Some(stick)) => stick.into(),
+            _ if value > 0.0 => {
+                SingleAxis::positive_only(axis_type, DualAxis::DEFAULT_DEADZONE).into()
+            }
+            _ => SingleAxis::negative_only(axis_type, -DualAxis::DEFAULT_DEADZONE).into(),
+        }
+    }
+}
+
+/// How [`InputCapture`] captures the axes of analog sticks
+///
+/// Other gamepad axes are always captured as a [`SingleAxis`] in the direction they were pushed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StickCapture {
+    /// Capture the whole stick as a [`DualAxis`], such as to rebind movement
+    #[default]
+    DualAxis,
+    /// Capture the axis that was pushed as a [`SingleAxis`] in the direction it was pushed, such as to rebind a button-like action
+    SingleAxis,
+}
+
+/// An input captured by [`InputCapture`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedInput {
+    /// The input pressed by the player
+    pub input: UserInput,
+    /// The gamepad that pressed the input, or [`None`] for the keyboard and mouse
+    ///
+    /// The captured input reads any gamepad, like every other [`UserInput`]:
+    /// set this gamepad on the [`InputMap`](crate::input_map::InputMap) to only read this player's gamepad.
+    pub gamepad: Option<Gamepad>,
+}
+
+/// Captures the first input pressed by the player into the [`InputCapture`] resource, if it exists and hasn't captured one yet
+pub fn capture_input(
+    capture: Option<ResMut<InputCapture>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_button_axes: Res<Axis<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    keycodes: Option<Res<Input<KeyCode>>>,
+    mouse_buttons: Option<Res<Input<MouseButton>>>,
+) {
+    let Some(mut capture) = capture else {
+        return;
+    };
+    if capture.captured.is_some() {
+        return;
+    }
+
+    if capture.gamepad.is_none() {
+        let keycode = keycodes.and_then(|keycodes| keycodes.get_just_pressed().next().copied());
+        let mouse_button = mouse_buttons
+            .and_then(|mouse_buttons| mouse_buttons.get_just_pressed().next().copied());
+        let input = keycode
+            .map(UserInput::from)
+            .or_else(|| mouse_button.map(UserInput::from));
+        if let Some(input) = input {
+            capture.captured = Some(CapturedInput {
+                input,
+                gamepad: None,
+            });
+            return;
+        }
+    }
+
+    let just_pressed = gamepad_buttons
+        .get_just_pressed()
+        .find(|button| gamepads.contains(button.gamepad) && capture.listens_to(button.gamepad));
+    if let Some(button) = just_pressed {
+        #[cfg(feature = "gamepad_extras")]
+        let input = {
+            let kind = GamepadKind::of(button.gamepad, &gamepads).unwrap_or_default();
+            normalize_button(kind, button.button_type).into()
+        };
+        #[cfg(not(feature = "gamepad_extras"))]
+        let input = button.button_type.into();
+
+        capture.captured = Some(CapturedInput {
+            input,
+            gamepad: Some(button.gamepad),
+        });
+        return;
+    }
+
+    // Of the analog inputs pushed past their threshold, capture the one pushed the furthest
+    let mut furthest: Option<(f32, Gamepad, UserInput)> = None;
+    let furthest_value = |furthest: &Option<(f32, Gamepad, UserInput)>| {
+        furthest
+            .as_ref()
+            .map_or(f32::NEG_INFINITY, |(value, ..)| *value)
+    };
+    for button in gamepad_button_axes.devices() {
+        let value = gamepad_button_axes.get(*button).unwrap_or_default();
+        if gamepads.contains(button.gamepad)
+            && capture.listens_to(button.gamepad)
+            && value >= capture.button_threshold
+            && value > furthest_value(&furthest)
+        {
+            let input = UserInput::from(button.button_type);
+            furthest = Some((value, button.gamepad, input));
+        }
+    }
+    for axis in gamepad_axes.devices() {
+        let value = gamepad_axes.get(*axis).unwrap_or_default();
+        if gamepads.contains(axis.gamepad)
+            && capture.listens_to(axis.gamepad)
+            && value.abs() >= capture.axis_threshold
+            && value.abs() > furthest_value(&furthest)
+        {
+            let input = capture.axis_input(axis.axis_type, value);
+            furthest = Some((value.abs(), axis.gamepad, input));
+        }
+    }
+
+    if let Some((_, gamepad, input)) = furthest {
+        capture.captured = Some(CapturedInput {
+            input,
+            gamepad: Some(gamepad),
+        });
+    }
+}
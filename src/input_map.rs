@@ -1,18 +1,23 @@
 //! This module contains [`InputMap`] and its supporting methods and impls.
 
 use crate::action_state::ActionData;
-use crate::buttonlike::ButtonState;
-use crate::clashing_inputs::ClashStrategy;
+use crate::axislike::AxisType;
+use crate::buttonlike::{AnalogButton, ButtonState};
+use crate::clashing_inputs::{input_kinds, ClashCache, ClashStrategy, SuppressedAction};
+use crate::gamepad_kind::GamepadKind;
 use crate::input_streams::InputStreams;
+use crate::touch::TouchGesture;
 use crate::user_input::{InputKind, Modifier, UserInput};
 use crate::Actionlike;
 
 use bevy::asset::Asset;
 use bevy::ecs::component::Component;
 use bevy::ecs::system::Resource;
-use bevy::input::gamepad::Gamepad;
-use bevy::reflect::Reflect;
-use bevy::utils::{Entry, HashMap};
+use bevy::input::gamepad::{Gamepad, Gamepads};
+use bevy::math::Rect;
+use bevy::reflect::{DynamicEnum, DynamicVariant, Reflect, TypeInfo, Typed};
+use bevy::utils::{Entry, HashMap, HashSet};
+use bevy::window::CursorGrabMode;
 use serde::{Deserialize, Serialize};
 
 use core::fmt::Debug;
@@ -71,13 +76,44 @@ input_map.insert(Action::Run, MouseButton::Left)
 input_map.clear_action(&Action::Hide);
 ```
 **/
-#[derive(
-    Resource, Component, Debug, Clone, PartialEq, Eq, Asset, Reflect, Serialize, Deserialize,
-)]
+#[derive(Resource, Component, Debug, Clone, Asset, Reflect, Serialize, Deserialize)]
 pub struct InputMap<A: Actionlike> {
     /// The usize stored here is the index of the input in the Actionlike iterator
     map: HashMap<A, Vec<UserInput>>,
     associated_gamepad: Option<Gamepad>,
+    /// The bindings in the order they were inserted, used by [`ClashStrategy::PrioritizeFirst`]
+    ///
+    /// This is serialized, so maps loaded from a file resolve their clashes in the same way,
+    /// but ignored when comparing maps.
+    #[serde(default = "Vec::new")]
+    #[reflect(ignore)]
+    binding_order: Vec<(A, UserInput)>,
+    /// The actions that are never suppressed by clashes, and whether they can still suppress other actions
+    #[serde(default = "HashMap::default")]
+    clash_exemptions: HashMap<A, bool>,
+    /// The [`ClashStrategy`] used by this map instead of the one it is given, if any
+    #[serde(default)]
+    #[reflect(ignore)]
+    clash_strategy: Option<ClashStrategy>,
+    /// The groups of actions whose clashes with each other are resolved using their own [`ClashStrategy`]
+    #[serde(default = "Vec::new")]
+    #[reflect(ignore)]
+    clash_overrides: Vec<(Vec<A>, ClashStrategy)>,
+    /// The actions whose mouse buttons are not blocked while the pointer is over the UI
+    #[serde(default = "Vec::new")]
+    clicks_through_ui: Vec<A>,
+    /// The bindings that read other gamepads than the associated gamepad
+    #[serde(default = "Vec::new")]
+    #[reflect(ignore)]
+    gamepad_scopes: Vec<(A, UserInput, GamepadScope)>,
+    /// The bindings that are only read while the cursor is grabbed, or while it is free
+    #[serde(default = "Vec::new")]
+    #[reflect(ignore)]
+    cursor_grab_conditions: Vec<(A, UserInput, CursorGrabCondition)>,
+    /// The pairs of actions that could clash, which only change when the bindings do
+    #[serde(skip, default = "ClashCache::default")]
+    #[reflect(ignore)]
+    pub(crate) clash_cache: ClashCache<A>,
 }
 
 impl<A: Actionlike> Default for InputMap<A> {
@@ -85,10 +121,54 @@ impl<A: Actionlike> Default for InputMap<A> {
         InputMap {
             map: HashMap::default(),
             associated_gamepad: None,
+            binding_order: Vec::default(),
+            clash_exemptions: HashMap::default(),
+            clash_strategy: None,
+            clash_overrides: Vec::default(),
+            clicks_through_ui: Vec::default(),
+            gamepad_scopes: Vec::default(),
+            cursor_grab_conditions: Vec::default(),
+            clash_cache: ClashCache::default(),
         }
     }
 }
 
+// Maps with the same bindings are equal, no matter the order they were inserted in
+impl<A: Actionlike> PartialEq for InputMap<A> {
+    fn eq(&self, other: &Self) -> bool {
+        let InputMap {
+            map,
+            associated_gamepad,
+            binding_order: _,
+            clash_exemptions,
+            clash_strategy,
+            clash_overrides,
+            clicks_through_ui,
+            gamepad_scopes,
+            cursor_grab_conditions,
+            clash_cache: _,
+        } = self;
+
+        *map == other.map
+            && *associated_gamepad == other.associated_gamepad
+            && *clash_exemptions == other.clash_exemptions
+            && *clash_strategy == other.clash_strategy
+            && *clash_overrides == other.clash_overrides
+            && same_entries(clicks_through_ui, &other.clicks_through_ui)
+            && same_entries(gamepad_scopes, &other.gamepad_scopes)
+            && same_entries(cursor_grab_conditions, &other.cursor_grab_conditions)
+    }
+}
+
+/// Do the lists contain the same entries, in any order?
+///
+/// Each entry may only appear once in each list.
+fn same_entries<T: PartialEq>(a: &[T], b: &[T]) -> bool {
+    a.len() == b.len() && a.iter().all(|entry| b.contains(entry))
+}
+
+impl<A: Actionlike> Eq for InputMap<A> {}
+
 // Constructors
 impl<A: Actionlike> InputMap<A> {
     /// Creates a new [`InputMap`] from an iterator of `(user_input, action)` pairs
@@ -169,6 +249,10 @@ impl<A: Actionlike> InputMap<A> {
             }
         }
 
+        self.clash_cache.invalidate();
+        // Bindings that were removed through `get_mut` may still be in the insertion order
+        self.forget_binding_order(&action, &input);
+        self.binding_order.push((action.clone(), input.clone()));
         match self.map.entry(action) {
             Entry::Occupied(mut entry) => {
                 entry.get_mut().push(input);
@@ -242,6 +326,9 @@ impl<A: Actionlike> InputMap<A> {
     ///
     /// This adds both of their bindings to the resulting [`InputMap`].
     /// Like usual, any duplicate bindings are ignored.
+    /// The new bindings are inserted after the existing ones, in the order they were inserted into `other`.
+    /// Clash exemptions, clash overrides, the clash strategy and the actions that click through the UI are merged too,
+    /// keeping the existing settings when both maps configure the same actions.
     ///
     /// If the associated gamepads do not match, the resulting associated gamepad will be set to `None`.
     pub fn merge(&mut self, other: &InputMap<A>) -> &mut Self {
@@ -249,9 +336,36 @@ impl<A: Actionlike> InputMap<A> {
             self.associated_gamepad = None;
         }
 
-        for other_action in other.map.iter() {
-            for input in other_action.1.iter() {
-                self.insert(other_action.0.clone(), input.clone());
+        for (action, input) in other.ordered_bindings() {
+            self.insert(action.clone(), input.clone());
+        }
+
+        for (action, blocks) in other.clash_exemptions.iter() {
+            self.clash_exemptions
+                .entry(action.clone())
+                .or_insert(*blocks);
+        }
+
+        for action in other.clicks_through_ui.iter() {
+            self.set_clicks_through_ui(action, true);
+        }
+
+        for (action, input, scope) in other.gamepad_scopes.iter() {
+            self.set_gamepad_scope(action, input.clone(), *scope);
+        }
+
+        for (action, input, condition) in other.cursor_grab_conditions.iter() {
+            self.set_cursor_grab_condition(action, input.clone(), *condition);
+        }
+
+        self.clash_strategy = self.clash_strategy.or(other.clash_strategy);
+        for (group, clash_strategy) in other.clash_overrides.iter() {
+            let already_overridden = self
+                .clash_overrides
+                .iter()
+                .any(|(existing, _)| same_group(existing, group));
+            if !already_overridden {
+                self.clash_overrides.push((group.clone(), *clash_strategy));
             }
         }
 
@@ -287,6 +401,367 @@ impl<A: Actionlike> InputMap<A> {
         self.associated_gamepad = None;
         self
     }
+
+    /// Clones this input map for another player, who uses the `gamepad`
+    ///
+    /// The clone reads only the `gamepad`, and keeps every binding and setting of this map,
+    /// so that both players share the same control scheme.
+    /// Call [`InputMap::remove_non_gamepad_inputs`] on the clone if the players shouldn't share the keyboard and mouse.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::input::gamepad::{Gamepad, GamepadButtonType};
+    /// use bevy::input::keyboard::KeyCode;
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Jump,
+    /// }
+    ///
+    /// let mut player_one = InputMap::new([(Action::Jump, UserInput::from(GamepadButtonType::South))]);
+    /// player_one.insert(Action::Jump, KeyCode::Space);
+    /// player_one.set_gamepad(Gamepad { id: 0 });
+    ///
+    /// let mut player_two = player_one.clone_for_gamepad(Gamepad { id: 1 });
+    /// player_two.remove_non_gamepad_inputs();
+    ///
+    /// assert_eq!(player_two.gamepad(), Some(Gamepad { id: 1 }));
+    /// assert_eq!(player_two.get(&Action::Jump), Some(&vec![GamepadButtonType::South.into()]));
+    /// ```
+    #[must_use]
+    pub fn clone_for_gamepad(&self, gamepad: Gamepad) -> InputMap<A> {
+        let mut input_map = self.clone();
+        input_map.set_gamepad(gamepad);
+        input_map
+    }
+
+    /// Inserts a binding between the `action` and the `input`, which reads the gamepads of the `scope`
+    ///
+    /// This overrides the associated gamepad of this map for a single binding,
+    /// such as to let every player pause the game while only the associated gamepad moves the character.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::input::gamepad::{Gamepad, GamepadButtonType};
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::input_map::GamepadScope;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Move,
+    ///     Pause,
+    /// }
+    ///
+    /// let mut input_map = InputMap::new([(Action::Move, DualAxis::left_stick())]);
+    /// input_map
+    ///     .insert_scoped(Action::Pause, GamepadButtonType::Start, GamepadScope::Any)
+    ///     .set_gamepad(Gamepad { id: 0 });
+    ///
+    /// assert_eq!(
+    ///     input_map.gamepad_scope(&Action::Pause, &GamepadButtonType::Start.into()),
+    ///     GamepadScope::Any
+    /// );
+    /// ```
+    pub fn insert_scoped(
+        &mut self,
+        action: A,
+        input: impl Into<UserInput>,
+        scope: GamepadScope,
+    ) -> &mut Self {
+        let input = input.into();
+        self.insert(action.clone(), input.clone());
+        self.set_gamepad_scope(&action, input, scope)
+    }
+
+    /// Sets the gamepads read by the binding between the `action` and the `input`
+    ///
+    /// Use [`GamepadScope::Associated`] to read the associated gamepad again.
+    pub fn set_gamepad_scope(
+        &mut self,
+        action: &A,
+        input: impl Into<UserInput>,
+        scope: GamepadScope,
+    ) -> &mut Self {
+        let input = input.into();
+        self.gamepad_scopes
+            .retain(|(scoped_action, scoped_input, _)| {
+                scoped_action != action || *scoped_input != input
+            });
+        if scope != GamepadScope::Associated {
+            self.gamepad_scopes.push((action.clone(), input, scope));
+        }
+        self
+    }
+
+    /// The gamepads read by the binding between the `action` and the `input`
+    #[must_use]
+    pub fn gamepad_scope(&self, action: &A, input: &UserInput) -> GamepadScope {
+        self.gamepad_scopes
+            .iter()
+            .find(|(scoped_action, scoped_input, _)| {
+                scoped_action == action && scoped_input == input
+            })
+            .map_or(GamepadScope::Associated, |(.., scope)| *scope)
+    }
+
+    /// Inserts a binding between the `action` and the `input`, which is only read while the cursor is grabbed by the primary window
+    ///
+    /// The cursor is grabbed while its [`CursorGrabMode`] is [`Locked`](CursorGrabMode::Locked) or [`Confined`](CursorGrabMode::Confined),
+    /// as first-person games do to look around with the mouse.
+    /// Together with [`InputMap::only_when_cursor_free`], a single map can switch between looking around and pointing at menus
+    /// when the game grabs or releases the cursor.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::{MouseButton, Reflect};
+    /// use leafwing_input_manager::input_map::CursorGrabCondition;
+    /// use leafwing_input_manager::mouse_gestures::DragAxis;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    /// enum Action {
+    ///     Look,
+    /// }
+    ///
+    /// let mut input_map = InputMap::default();
+    /// input_map
+    ///     // Looks around with the mouse while the cursor is grabbed
+    ///     .only_when_cursor_locked(Action::Look, DualAxis::mouse_motion())
+    ///     // And by dragging with the right mouse button while it is free
+    ///     .only_when_cursor_free(Action::Look, DragAxis::new(MouseButton::Right));
+    ///
+    /// assert_eq!(
+    ///     input_map.cursor_grab_condition(&Action::Look, &DualAxis::mouse_motion().into()),
+    ///     CursorGrabCondition::Locked
+    /// );
+    /// ```
+    pub fn only_when_cursor_locked(&mut self, action: A, input: impl Into<UserInput>) -> &mut Self {
+        let input = input.into();
+        self.insert(action.clone(), input.clone());
+        self.set_cursor_grab_condition(&action, input, CursorGrabCondition::Locked)
+    }
+
+    /// Inserts a binding between the `action` and the `input`, which is only read while the cursor is not grabbed by the primary window
+    ///
+    /// See [`InputMap::only_when_cursor_locked`].
+    pub fn only_when_cursor_free(&mut self, action: A, input: impl Into<UserInput>) -> &mut Self {
+        let input = input.into();
+        self.insert(action.clone(), input.clone());
+        self.set_cursor_grab_condition(&action, input, CursorGrabCondition::Free)
+    }
+
+    /// Sets whether the binding between the `action` and the `input` depends on the cursor being grabbed
+    ///
+    /// Use [`CursorGrabCondition::Always`] to read it whether the cursor is grabbed or not again.
+    pub fn set_cursor_grab_condition(
+        &mut self,
+        action: &A,
+        input: impl Into<UserInput>,
+        condition: CursorGrabCondition,
+    ) -> &mut Self {
+        let input = input.into();
+        self.cursor_grab_conditions
+            .retain(|(conditioned_action, conditioned_input, _)| {
+                conditioned_action != action || *conditioned_input != input
+            });
+        if condition != CursorGrabCondition::Always {
+            self.cursor_grab_conditions
+                .push((action.clone(), input, condition));
+        }
+        self
+    }
+
+    /// Whether the binding between the `action` and the `input` depends on the cursor being grabbed
+    #[must_use]
+    pub fn cursor_grab_condition(&self, action: &A, input: &UserInput) -> CursorGrabCondition {
+        self.cursor_grab_conditions
+            .iter()
+            .find(|(conditioned_action, conditioned_input, _)| {
+                conditioned_action == action && conditioned_input == input
+            })
+            .map_or(CursorGrabCondition::Always, |(.., condition)| *condition)
+    }
+
+    /// Guesses the [`GamepadKind`] of the gamepad used by this input map, such as to display its buttons with [`UserInput::name`]
+    ///
+    /// This is the kind of the associated gamepad, or of the first connected gamepad if there is none.
+    /// Returns [`None`] if that gamepad isn't connected.
+    ///
+    /// This doesn't take the [`GamepadKindOverride`](crate::gamepad_kind::GamepadKindOverride) into account:
+    /// use [`GamepadKindOverride::associated_gamepad_kind`](crate::gamepad_kind::GamepadKindOverride::associated_gamepad_kind) for that.
+    #[must_use]
+    pub fn associated_gamepad_kind(&self, gamepads: &Gamepads) -> Option<GamepadKind> {
+        GamepadKind::of(self.guess_gamepad(gamepads)?, gamepads)
+    }
+
+    /// The associated gamepad, or the first connected gamepad if there is none
+    pub(crate) fn guess_gamepad(&self, gamepads: &Gamepads) -> Option<Gamepad> {
+        self.associated_gamepad.or_else(|| gamepads.iter().next())
+    }
+
+    /// The [`AnalogButton`]s used by the bindings of this map, including those that are part of a chord or a virtual input
+    pub(crate) fn analog_buttons(&self) -> HashSet<AnalogButton> {
+        self.iter()
+            .flat_map(|(_, inputs)| inputs.iter().flat_map(input_kinds))
+            .filter_map(|input_kind| match input_kind {
+                InputKind::AnalogButton(analog_button) => Some(analog_button),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The regions of the [`TouchJoystick`](crate::touch::TouchJoystick)s used by the bindings of this map, including those that are part of a chord
+    pub(crate) fn touch_joystick_regions(&self) -> Vec<Rect> {
+        self.iter()
+            .flat_map(|(_, inputs)| inputs.iter().flat_map(input_kinds))
+            .filter_map(|input_kind| match input_kind {
+                InputKind::TouchJoystick(joystick) => Some(joystick.region),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The [`TouchGesture::DoubleTap`](crate::touch::TouchGesture::DoubleTap)s used by the bindings of this map, including those that are part of a chord
+    pub(crate) fn double_taps(&self) -> Vec<TouchGesture> {
+        self.iter()
+            .flat_map(|(_, inputs)| inputs.iter().flat_map(input_kinds))
+            .filter_map(|input_kind| match input_kind {
+                InputKind::Touch(gesture @ TouchGesture::DoubleTap { .. })
+                | InputKind::TouchWithin(gesture @ TouchGesture::DoubleTap { .. }, _) => {
+                    Some(gesture)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Sets whether the `action` is exempt from clash handling
+    ///
+    /// Exempt actions are always reported as pressed by [`InputMap::which_pressed`] when their inputs are pressed,
+    /// whatever the [`ClashStrategy`].
+    /// By default, they can still suppress the other actions they clash with: see [`InputMap::set_clash_exempt_blocks`].
+    pub fn set_clash_exempt(&mut self, action: &A, exempt: bool) -> &mut Self {
+        if exempt {
+            self.clash_exemptions.entry(action.clone()).or_insert(true);
+        } else {
+            self.clash_exemptions.remove(action);
+        }
+        self
+    }
+
+    /// Sets whether the clash-exempt `action` can still suppress the other actions it clashes with
+    ///
+    /// This has no effect unless the `action` was made exempt with [`InputMap::set_clash_exempt`].
+    pub fn set_clash_exempt_blocks(&mut self, action: &A, blocks: bool) -> &mut Self {
+        if let Some(exempt_blocks) = self.clash_exemptions.get_mut(action) {
+            *exempt_blocks = blocks;
+        }
+        self
+    }
+
+    /// Is the `action` exempt from clash handling?
+    #[must_use]
+    pub fn is_clash_exempt(&self, action: &A) -> bool {
+        self.clash_exemptions.contains_key(action)
+    }
+
+    /// Can the `action` suppress the other actions it clashes with?
+    ///
+    /// This is always true for actions that are not exempt from clash handling.
+    #[must_use]
+    pub fn blocks_clashes(&self, action: &A) -> bool {
+        self.clash_exemptions.get(action).copied().unwrap_or(true)
+    }
+
+    /// Sets whether the mouse buttons bound to the `action` still press it while the pointer is over the UI
+    ///
+    /// By default, mouse buttons are ignored while the [`PointerOverUi`](crate::ui_blocking::PointerOverUi) resource says the pointer is over the UI.
+    /// This has no effect unless the [`UiBlockingPlugin`](crate::ui_blocking::UiBlockingPlugin) was added.
+    pub fn set_clicks_through_ui(&mut self, action: &A, clicks_through: bool) -> &mut Self {
+        let index = self.clicks_through_ui.iter().position(|a| a == action);
+        match (index, clicks_through) {
+            (None, true) => self.clicks_through_ui.push(action.clone()),
+            (Some(index), false) => {
+                self.clicks_through_ui.swap_remove(index);
+            }
+            _ => (),
+        }
+        self
+    }
+
+    /// Do the mouse buttons bound to the `action` still press it while the pointer is over the UI?
+    ///
+    /// See [`InputMap::set_clicks_through_ui`].
+    #[must_use]
+    pub fn clicks_through_ui(&self, action: &A) -> bool {
+        self.clicks_through_ui.contains(action)
+    }
+
+    /// The actions whose mouse buttons still press them while the pointer is over the UI
+    pub(crate) fn actions_clicking_through_ui(&self) -> &[A] {
+        &self.clicks_through_ui
+    }
+
+    /// Resolves the clashes of this map using the `clash_strategy`, instead of the [`ClashStrategy`] passed to [`InputMap::which_pressed`]
+    ///
+    /// When the map is updated by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin), this takes precedence over the [`ClashStrategy`] resource.
+    /// Clash overrides set using [`InputMap::set_clash_override`] take precedence over both.
+    pub fn set_clash_strategy(&mut self, clash_strategy: ClashStrategy) -> &mut Self {
+        self.clash_strategy = Some(clash_strategy);
+        self
+    }
+
+    /// Clears the [`ClashStrategy`] of this map, so that the strategy it is given is used instead
+    pub fn clear_clash_strategy(&mut self) -> &mut Self {
+        self.clash_strategy = None;
+        self
+    }
+
+    /// Fetches the [`ClashStrategy`] used by this map instead of the one it is given, if any
+    #[must_use]
+    pub fn clash_strategy(&self) -> Option<ClashStrategy> {
+        self.clash_strategy
+    }
+
+    /// Resolves the clashes between any two of the `actions` using the `clash_strategy`
+    ///
+    /// Clashes between one of the `actions` and any other action are still resolved using the strategy of the map.
+    /// Setting an override for the same group of actions replaces the previous one,
+    /// and when several groups contain both actions of a clash, the most recently set override is used.
+    ///
+    /// [`ClashStrategy::DelayForChords`] is only delayed when used for the whole map,
+    /// so overrides using it resolve clashes like [`ClashStrategy::PrioritizeLongest`].
+    pub fn set_clash_override(
+        &mut self,
+        actions: impl IntoIterator<Item = A>,
+        clash_strategy: ClashStrategy,
+    ) -> &mut Self {
+        let actions: Vec<A> = actions.into_iter().collect();
+        self.remove_clash_override(actions.iter().cloned());
+        self.clash_overrides.push((actions, clash_strategy));
+        self
+    }
+
+    /// Removes the clash override for exactly this group of `actions`, if any
+    pub fn remove_clash_override(&mut self, actions: impl IntoIterator<Item = A>) -> &mut Self {
+        let actions: Vec<A> = actions.into_iter().collect();
+        self.clash_overrides
+            .retain(|(group, _)| !same_group(group, &actions));
+        self
+    }
+
+    /// Fetches the [`ClashStrategy`] that overrides clashes between `action_a` and `action_b`, if any
+    #[must_use]
+    pub fn clash_override(&self, action_a: &A, action_b: &A) -> Option<ClashStrategy> {
+        self.clash_overrides
+            .iter()
+            .rev()
+            .find(|(group, _)| group.contains(action_a) && group.contains(action_b))
+            .map(|(_, clash_strategy)| *clash_strategy)
+    }
 }
 
 // Check whether buttons are pressed
@@ -313,19 +788,51 @@ impl<A: Actionlike> InputMap<A> {
     /// Returns the actions that are currently pressed, and the responsible [`UserInput`] for each action
     ///
     /// Accounts for clashing inputs according to the [`ClashStrategy`].
+    /// The `clash_strategy` is only used if this map does not [have its own](InputMap::set_clash_strategy).
     #[must_use]
     pub fn which_pressed(
         &self,
         input_streams: &InputStreams,
         clash_strategy: ClashStrategy,
     ) -> HashMap<A, ActionData> {
+        let (action_data, _) = self.which_pressed_verbose(input_streams, clash_strategy);
+        action_data
+    }
+
+    /// Returns the same actions as [`InputMap::which_pressed`], along with the actions that were suppressed by clashes
+    ///
+    /// Each [`SuppressedAction`] names the action that was released, its pressed inputs, and the action that it lost to.
+    /// This is useful to find out why a binding does not seem to work.
+    #[must_use]
+    pub fn which_pressed_verbose(
+        &self,
+        input_streams: &InputStreams,
+        clash_strategy: ClashStrategy,
+    ) -> (HashMap<A, ActionData>, Vec<SuppressedAction<A>>) {
         let mut action_data = HashMap::new();
 
         // Generate the raw action presses
         for (action, input_vec) in self.iter() {
             let mut action_datum = ActionData::default();
+            let mut released_value = 0.0;
 
             for input in input_vec {
+                if !self
+                    .cursor_grab_condition(action, input)
+                    .allows(input_streams.cursor_grab_mode)
+                {
+                    continue;
+                }
+
+                let scoped_streams;
+                let input_streams = match self.gamepad_scope(action, input) {
+                    GamepadScope::Associated => input_streams,
+                    scope => {
+                        scoped_streams = scope.input_streams(input_streams);
+                        &scoped_streams
+                    }
+                };
+
                 // Merge axis pair into action datum
                 if let Some(axis_pair) = input_streams.input_axis_pair(input) {
                     action_datum.axis_pair = action_datum
@@ -335,19 +842,55 @@ impl<A: Actionlike> InputMap<A> {
                         });
                 }
 
+                if let Some(axis_triple) = input_streams.input_axis_triple(input) {
+                    action_datum.axis_triple = Some(
+                        action_datum
+                            .axis_triple
+                            .map_or(axis_triple, |current_axis_triple| {
+                                current_axis_triple.merged_with(axis_triple)
+                            }),
+                    );
+                }
+
                 if input_streams.input_pressed(input) {
                     action_datum.state = ButtonState::JustPressed;
                     action_datum.value += input_streams.input_value(input, true);
+                } else if matches!(
+                    input,
+                    UserInput::Single(InputKind::GamepadButton(_) | InputKind::AnalogButton(_))
+                ) {
+                    // Pressure-sensitive buttons report how far they are pressed, even before they are pressed
+                    released_value += input_streams.input_value(input, true);
                 }
             }
 
+            if !action_datum.state.pressed() {
+                action_datum.value = released_value;
+            }
+
             action_data.insert(action.clone(), action_datum);
         }
 
         // Handle clashing inputs, possibly removing some pressed actions from the list
-        self.handle_clashes(&mut action_data, input_streams, clash_strategy);
+        let suppressed = self.suppress_clashing_actions(
+            &mut action_data,
+            &|input| {
+                input_streams.input_pressed(input)
+                    || self
+                        .gamepad_scopes
+                        .iter()
+                        .filter(|(_, scoped_input, _)| scoped_input == input)
+                        .any(|(.., scope)| scope.input_streams(input_streams).input_pressed(input))
+            },
+            clash_strategy,
+        );
 
-        action_data
+        // Actions that lost a clash are released, rather than keeping their previous state
+        for (action, _) in self.iter() {
+            action_data.entry(action.clone()).or_default();
+        }
+
+        (action_data, suppressed)
     }
 }
 
@@ -363,9 +906,53 @@ impl<A: Actionlike> InputMap<A> {
         self.map.get(action)
     }
 
+    /// Returns an iterator over all bindings, in the order they were inserted
+    ///
+    /// Bindings added through [`InputMap::get_mut`] come last.
+    pub fn ordered_bindings(&self) -> impl Iterator<Item = (&A, &UserInput)> {
+        let ordered = self
+            .binding_order
+            .iter()
+            .filter(|(action, input)| {
+                self.map
+                    .get(action)
+                    .is_some_and(|inputs| inputs.contains(input))
+            })
+            .map(|(action, input)| (action, input));
+
+        let unordered = self.map.iter().flat_map(|(action, inputs)| {
+            inputs
+                .iter()
+                .filter(|&input| {
+                    !self
+                        .binding_order
+                        .contains(&(action.clone(), input.clone()))
+                })
+                .map(move |input| (action, input))
+        });
+
+        ordered.chain(unordered)
+    }
+
+    /// The position of the binding between the `action` and `input` in the insertion order
+    ///
+    /// Bindings that were not inserted through the [`InputMap`] methods, such as those added with [`InputMap::get_mut`],
+    /// are placed after all other bindings.
+    #[must_use]
+    pub(crate) fn binding_index(&self, action: &A, input: &UserInput) -> usize {
+        self.binding_order
+            .iter()
+            .position(|(ordered_action, ordered_input)| {
+                ordered_action == action && ordered_input == input
+            })
+            .unwrap_or(usize::MAX)
+    }
+
     /// Returns a mutable reference to the inputs mapped to `action`
     #[must_use]
     pub fn get_mut(&mut self, action: &A) -> Option<&mut Vec<UserInput>> {
+        // The inputs may be changed through the returned reference
+        self.clash_cache.invalidate();
         self.map.get_mut(action)
     }
 
@@ -390,15 +977,89 @@ impl<A: Actionlike> InputMap<A> {
     ///
     /// Keeps the allocated memory for reuse.
     pub fn clear(&mut self) {
+        self.clash_cache.invalidate();
         self.map.clear();
+        self.binding_order.clear();
+        self.gamepad_scopes.clear();
+        self.cursor_grab_conditions.clear();
+    }
+}
+
+// Validation
+impl<A: Actionlike + Typed> InputMap<A> {
+    /// Returns the variants of `A` that have no inputs bound to them, in the order they are declared
+    ///
+    /// Only unit variants are checked, as variants carrying data cannot be listed.
+    #[must_use]
+    pub fn unbound_actions(&self) -> Vec<A> {
+        let TypeInfo::Enum(enum_info) = A::type_info() else {
+            return Vec::new();
+        };
+
+        enum_info
+            .iter()
+            .filter_map(|variant| {
+                let variant = DynamicEnum::new(variant.name(), DynamicVariant::Unit);
+                A::from_reflect(&variant)
+            })
+            .filter(|action| !matches!(self.map.get(action), Some(inputs) if !inputs.is_empty()))
+            .collect()
+    }
+}
+
+/// The actions that are intentionally left without bindings, and are not reported by [`warn_unbound_actions`](crate::systems::warn_unbound_actions)
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct AllowedUnboundActions<A: Actionlike>(pub HashSet<A>);
+
+impl<A: Actionlike> Default for AllowedUnboundActions<A> {
+    fn default() -> Self {
+        Self(HashSet::default())
+    }
+}
+
+impl<A: Actionlike> AllowedUnboundActions<A> {
+    /// Creates an allowlist from the provided `actions`
+    #[must_use]
+    pub fn new(actions: impl IntoIterator<Item = A>) -> Self {
+        Self(actions.into_iter().collect())
     }
 }
 
+/// The canonical [`InputMap`] chosen in the player's settings, shared by every entity marked with [`UseSharedInputMap`]
+///
+/// Whenever this resource changes, [`sync_shared_input_maps`](crate::systems::sync_shared_input_maps)
+/// copies it into the [`InputMap`] of each marked entity, so that changes made in a settings menu apply everywhere at once.
+/// Newly marked entities, such as respawned players, receive a copy right away.
+/// Entities without the marker keep their own [`InputMap`].
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct SharedInputMap<A: Actionlike>(pub InputMap<A>);
+
+impl<A: Actionlike> Default for SharedInputMap<A> {
+    fn default() -> Self {
+        Self(InputMap::default())
+    }
+}
+
+/// Marks entities whose [`InputMap`] is kept in sync with the [`SharedInputMap`] resource
+///
+/// The [`InputMap`] is inserted if the entity does not have one yet.
+/// The gamepad of its [`InputMap`] is kept if the [`SharedInputMap`] does not choose one,
+/// so that each player can still use their own gamepad.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UseSharedInputMap;
+
 // Removing
 impl<A: Actionlike> InputMap<A> {
     /// Clears all inputs registered for the `action`
     pub fn clear_action(&mut self, action: &A) {
+        self.clash_cache.invalidate();
         self.map.remove(action);
+        self.binding_order
+            .retain(|(ordered_action, _)| ordered_action != action);
+        self.gamepad_scopes
+            .retain(|(scoped_action, ..)| scoped_action != action);
+        self.cursor_grab_conditions
+            .retain(|(conditioned_action, ..)| conditioned_action != action);
     }
 
     /// Removes the input for the `action` at the provided index
@@ -409,7 +1070,10 @@ impl<A: Actionlike> InputMap<A> {
         if input_vec.len() <= index {
             None
         } else {
-            Some(input_vec.remove(index))
+            let input = input_vec.remove(index);
+            self.clash_cache.invalidate();
+            self.forget_binding_order(action, &input);
+            Some(input)
         }
     }
 
@@ -421,8 +1085,131 @@ impl<A: Actionlike> InputMap<A> {
         let user_input = input.into();
         let index = input_vec.iter().position(|i| i == &user_input)?;
         input_vec.remove(index);
+        self.clash_cache.invalidate();
+        self.forget_binding_order(action, &user_input);
         Some(index)
     }
+
+    /// Removes every binding that reads the keyboard, the mouse or the touch screen, keeping only those read entirely from gamepads
+    ///
+    /// Chords that mix gamepad inputs with keyboard, mouse or touch inputs are removed too.
+    /// Actions left without any input are removed from the map.
+    pub fn remove_non_gamepad_inputs(&mut self) -> &mut Self {
+        self.clash_cache.invalidate();
+        self.map.retain(|_, input_vec| {
+            input_vec.retain(is_gamepad_input);
+            !input_vec.is_empty()
+        });
+        self.binding_order
+            .retain(|(_, input)| is_gamepad_input(input));
+        self.gamepad_scopes
+            .retain(|(_, input, _)| is_gamepad_input(input));
+        self.cursor_grab_conditions
+            .retain(|(_, input, _)| is_gamepad_input(input));
+        self
+    }
+
+    /// Removes the binding between the `action` and `input` from the insertion order, the gamepad scopes and the cursor grab conditions
+    fn forget_binding_order(&mut self, action: &A, input: &UserInput) {
+        self.binding_order
+            .retain(|(ordered_action, ordered_input)| {
+                ordered_action != action || ordered_input != input
+            });
+        self.gamepad_scopes
+            .retain(|(scoped_action, scoped_input, _)| {
+                scoped_action != action || scoped_input != input
+            });
+        self.cursor_grab_conditions
+            .retain(|(conditioned_action, conditioned_input, _)| {
+                conditioned_action != action || conditioned_input != input
+            });
+    }
+}
+
+/// The gamepads read by a binding of an [`InputMap`]
+///
+/// See [`InputMap::insert_scoped`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+pub enum GamepadScope {
+    /// Read the gamepad associated with the [`InputMap`], or every gamepad if there is none
+    #[default]
+    Associated,
+    /// Read every gamepad, even if the [`InputMap`] has an associated gamepad
+    Any,
+    /// Read this gamepad only, whichever gamepad is associated with the [`InputMap`]
+    Specific(Gamepad),
+}
+
+impl GamepadScope {
+    /// The gamepad read with this scope, given the `associated_gamepad` of the [`InputMap`]
+    ///
+    /// Returns [`None`] if every gamepad is read.
+    #[must_use]
+    pub fn gamepad(self, associated_gamepad: Option<Gamepad>) -> Option<Gamepad> {
+        match self {
+            GamepadScope::Associated => associated_gamepad,
+            GamepadScope::Any => None,
+            GamepadScope::Specific(gamepad) => Some(gamepad),
+        }
+    }
+
+    /// The `input_streams`, reading the gamepads of this scope instead of their associated gamepad
+    fn input_streams<'a>(self, input_streams: &InputStreams<'a>) -> InputStreams<'a> {
+        InputStreams {
+            associated_gamepad: self.gamepad(input_streams.associated_gamepad),
+            ..input_streams.clone()
+        }
+    }
+}
+
+/// Whether a binding of an [`InputMap`] depends on the cursor being grabbed by the primary window
+///
+/// See [`InputMap::only_when_cursor_locked`] and [`InputMap::only_when_cursor_free`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+pub enum CursorGrabCondition {
+    /// Read the binding whether the cursor is grabbed or not
+    #[default]
+    Always,
+    /// Read the binding only while the [`CursorGrabMode`] is [`Locked`](CursorGrabMode::Locked) or [`Confined`](CursorGrabMode::Confined)
+    Locked,
+    /// Read the binding only while the [`CursorGrabMode`] is [`None`](CursorGrabMode::None)
+    Free,
+}
+
+impl CursorGrabCondition {
+    /// Is a binding with this condition read while the cursor has the `cursor_grab_mode`?
+    #[must_use]
+    pub fn allows(self, cursor_grab_mode: CursorGrabMode) -> bool {
+        match self {
+            CursorGrabCondition::Always => true,
+            CursorGrabCondition::Locked => cursor_grab_mode != CursorGrabMode::None,
+            CursorGrabCondition::Free => cursor_grab_mode == CursorGrabMode::None,
+        }
+    }
+}
+
+/// Is the `input` read entirely from gamepads?
+fn is_gamepad_input(input: &UserInput) -> bool {
+    let raw_inputs = input.raw_inputs();
+    raw_inputs.keycodes.is_empty()
+        && raw_inputs.scan_codes.is_empty()
+        && raw_inputs.mouse_buttons.is_empty()
+        && raw_inputs.mouse_wheel.is_empty()
+        && raw_inputs.mouse_motion.is_empty()
+        && raw_inputs.touch_gestures.is_empty()
+        && raw_inputs.touch_joysticks.is_empty()
+        && raw_inputs.cursor_relative.is_empty()
+        && raw_inputs.screen_edges.is_empty()
+        && raw_inputs
+            .axis_data
+            .iter()
+            .all(|(axis_type, _)| matches!(axis_type, AxisType::Gamepad(_)))
+}
+
+/// Do the two groups contain the same actions, in any order?
+fn same_group<A: Actionlike>(group_a: &[A], group_b: &[A]) -> bool {
+    group_a.iter().all(|action| group_b.contains(action))
+        && group_b.iter().all(|action| group_a.contains(action))
 }
 
 impl<A: Actionlike> From<HashMap<A, Vec<UserInput>>> for InputMap<A> {
@@ -607,4 +1394,90 @@ mod tests {
         input_map.clear_gamepad();
         assert_eq!(input_map.gamepad(), None);
     }
+
+    #[test]
+    fn clash_exemptions_are_serialized() {
+        use bevy::input::keyboard::KeyCode;
+
+        let mut input_map = InputMap::new([(Action::Run, KeyCode::ShiftLeft)]);
+        input_map
+            .set_clash_exempt(&Action::Run, true)
+            .set_clash_exempt_blocks(&Action::Run, false)
+            .set_clash_exempt(&Action::Jump, true);
+
+        let bytes = bincode::serialize(&input_map).unwrap();
+        let input_map: InputMap<Action> = bincode::deserialize(&bytes).unwrap();
+        assert!(input_map.is_clash_exempt(&Action::Run));
+        assert!(!input_map.blocks_clashes(&Action::Run));
+        assert!(input_map.is_clash_exempt(&Action::Jump));
+        assert!(input_map.blocks_clashes(&Action::Jump));
+        assert!(!input_map.is_clash_exempt(&Action::Hide));
+        assert!(input_map.blocks_clashes(&Action::Hide));
+    }
+
+    #[test]
+    fn gamepad_scopes_are_serialized() {
+        use crate::input_map::GamepadScope;
+        use bevy::input::gamepad::{Gamepad, GamepadButtonType};
+
+        let mut input_map = InputMap::default();
+        input_map
+            .insert_scoped(Action::Hide, GamepadButtonType::Start, GamepadScope::Any)
+            .insert_scoped(
+                Action::Jump,
+                GamepadButtonType::South,
+                GamepadScope::Specific(Gamepad { id: 1 }),
+            )
+            .insert(Action::Run, GamepadButtonType::East);
+
+        let bytes = bincode::serialize(&input_map).unwrap();
+        let input_map: InputMap<Action> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(
+            input_map.gamepad_scope(&Action::Hide, &GamepadButtonType::Start.into()),
+            GamepadScope::Any
+        );
+        assert_eq!(
+            input_map.gamepad_scope(&Action::Jump, &GamepadButtonType::South.into()),
+            GamepadScope::Specific(Gamepad { id: 1 })
+        );
+        assert_eq!(
+            input_map.gamepad_scope(&Action::Run, &GamepadButtonType::East.into()),
+            GamepadScope::Associated
+        );
+    }
+
+    #[test]
+    fn clash_overrides_are_serialized() {
+        use bevy::input::keyboard::KeyCode;
+
+        let mut input_map = InputMap::new([(Action::Run, KeyCode::ShiftLeft)]);
+        input_map
+            .set_clash_strategy(ClashStrategy::PrioritizeFirst)
+            .set_clash_override([Action::Run, Action::Hide], ClashStrategy::PressAll);
+
+        let bytes = bincode::serialize(&input_map).unwrap();
+        let input_map: InputMap<Action> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(
+            input_map.clash_strategy(),
+            Some(ClashStrategy::PrioritizeFirst)
+        );
+        assert_eq!(
+            input_map.clash_override(&Action::Hide, &Action::Run),
+            Some(ClashStrategy::PressAll)
+        );
+        assert_eq!(input_map.clash_override(&Action::Run, &Action::Jump), None);
+    }
+
+    #[test]
+    fn unbound_actions() {
+        use bevy::input::keyboard::KeyCode;
+
+        let mut input_map = InputMap::new([(Action::Jump, KeyCode::Space)]);
+        assert_eq!(input_map.unbound_actions(), vec![Action::Run, Action::Hide]);
+
+        // Actions whose inputs were all removed are unbound again
+        input_map.insert(Action::Run, KeyCode::ShiftLeft);
+        input_map.remove(&Action::Run, KeyCode::ShiftLeft);
+        assert_eq!(input_map.unbound_actions(), vec![Action::Run, Action::Hide]);
+    }
 }
@@ -0,0 +1,215 @@
+//! Modal layers of [`InputMap`]s, for pause menus, dialogs and vehicles
+//!
+//! Add an [`InputMapStack`] to an entity, and its [`InputMap`] is replaced by the map on top of the stack
+//! whenever the stack changes. Popping a layer restores the map that was there before.
+//!
+//! Layers pushed with [`InputMapStack::push_passthrough`] keep the bindings of the layers below them
+//! for the actions that they do not bind themselves.
+
+use crate::input_map::InputMap;
+use crate::Actionlike;
+
+use bevy::ecs::component::Component;
+
+/// A stack of [`InputMap`] layers, of which only the top layer is used to press actions
+///
+/// The [`InputMap`] of the entity is kept up to date by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin),
+/// before the [`ActionState`](crate::action_state::ActionState) is updated.
+/// Actions that are no longer bound once a layer is pushed or popped are released.
+///
+/// The base layer cannot be popped, so the entity always has a map.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::input_map_stack::InputMapStack;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+/// enum Action {
+///     Walk,
+///     Pause,
+///     Resume,
+/// }
+///
+/// let mut stack = InputMapStack::new(InputMap::new([
+///     (Action::Walk, KeyCode::W),
+///     (Action::Pause, KeyCode::Escape),
+/// ]));
+///
+/// // While paused, the player cannot walk
+/// stack.push(InputMap::new([(Action::Resume, KeyCode::Escape)]));
+/// assert!(stack.effective_map().get(&Action::Walk).is_none());
+///
+/// stack.pop();
+/// assert!(stack.effective_map().get(&Action::Walk).is_some());
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct InputMapStack<A: Actionlike> {
+    /// The layers from the bottom to the top, and whether each of them passes unbound actions through
+    layers: Vec<(InputMap<A>, bool)>,
+}
+
+impl<A: Actionlike> InputMapStack<A> {
+    /// Creates a stack with only the `base` layer
+    #[must_use]
+    pub fn new(base: InputMap<A>) -> Self {
+        Self {
+            layers: vec![(base, false)],
+        }
+    }
+
+    /// Pushes a layer that hides every binding of the layers below it
+    pub fn push(&mut self, map: InputMap<A>) -> &mut Self {
+        self.layers.push((map, false));
+        self
+    }
+
+    /// Pushes a layer that keeps the bindings of the layers below it, for the actions that it does not bind
+    pub fn push_passthrough(&mut self, map: InputMap<A>) -> &mut Self {
+        self.layers.push((map, true));
+        self
+    }
+
+    /// Removes the top layer and returns it, restoring the layer below
+    ///
+    /// Returns [`None`] if only the base layer is left, which is never removed.
+    pub fn pop(&mut self) -> Option<InputMap<A>> {
+        if self.layers.len() > 1 {
+            self.layers.pop().map(|(map, _)| map)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the map of the top layer, keeping whether it passes unbound actions through
+    ///
+    /// Returns the map that was replaced.
+    pub fn replace_top(&mut self, map: InputMap<A>) -> InputMap<A> {
+        let (top, _) = self
+            .layers
+            .last_mut()
+            .expect("The base layer is never removed");
+        core::mem::replace(top, map)
+    }
+
+    /// The map of the top layer
+    #[must_use]
+    pub fn top(&self) -> &InputMap<A> {
+        let (top, _) = self.layers.last().expect("The base layer is never removed");
+        top
+    }
+
+    /// The number of layers pushed on top of the base layer
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// The map that is used to press actions, combining the top layer with the layers it passes through to
+    ///
+    /// Actions bound by a higher layer keep only the bindings of that layer.
+    #[must_use]
+    pub fn effective_map(&self) -> InputMap<A> {
+        let mut layers = self.layers.iter().rev();
+        let (top, mut passthrough) = layers
+            .next()
+            .map(|(map, passthrough)| (map.clone(), *passthrough))
+            .expect("The base layer is never removed");
+        let mut effective_map = top;
+
+        for (map, layer_passthrough) in layers {
+            if !passthrough {
+                break;
+            }
+
+            let mut unbound = map.clone();
+            for (action, _) in effective_map.iter() {
+                unbound.clear_action(action);
+            }
+            // The gamepad is chosen by the top layer
+            unbound.clear_gamepad();
+            if let Some(gamepad) = effective_map.gamepad() {
+                unbound.set_gamepad(gamepad);
+            }
+            effective_map.merge(&unbound);
+
+            passthrough = *layer_passthrough;
+        }
+
+        effective_map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::{KeyCode, Reflect};
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Walk,
+        Jump,
+        Drive,
+    }
+
+    fn base() -> InputMap<Action> {
+        InputMap::new([(Action::Walk, KeyCode::W), (Action::Jump, KeyCode::Space)])
+    }
+
+    #[test]
+    fn base_layer_is_never_popped() {
+        let mut stack = InputMapStack::new(base());
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.depth(), 0);
+        assert_eq!(stack.effective_map(), base());
+    }
+
+    #[test]
+    fn opaque_layer_hides_lower_bindings() {
+        let mut stack = InputMapStack::new(base());
+        let vehicle = InputMap::new([(Action::Drive, KeyCode::W)]);
+        stack.push(vehicle.clone());
+
+        assert_eq!(stack.effective_map(), vehicle);
+        assert_eq!(stack.pop(), Some(vehicle));
+        assert_eq!(stack.effective_map(), base());
+    }
+
+    #[test]
+    fn passthrough_layer_keeps_unbound_actions() {
+        let mut stack = InputMapStack::new(base());
+        stack.push_passthrough(InputMap::new([(Action::Jump, KeyCode::J)]));
+
+        let effective_map = stack.effective_map();
+        assert_eq!(
+            effective_map.get(&Action::Walk),
+            Some(&vec![KeyCode::W.into()])
+        );
+        assert_eq!(
+            effective_map.get(&Action::Jump),
+            Some(&vec![KeyCode::J.into()])
+        );
+    }
+
+    #[test]
+    fn opaque_layer_stops_passthrough() {
+        let mut stack = InputMapStack::new(base());
+        stack
+            .push(InputMap::new([(Action::Drive, KeyCode::D)]))
+            .push_passthrough(InputMap::new([(Action::Jump, KeyCode::K)]));
+        assert_eq!(stack.depth(), 2);
+
+        let effective_map = stack.effective_map();
+        assert_eq!(effective_map.get(&Action::Walk), None);
+        assert_eq!(
+            effective_map.get(&Action::Drive),
+            Some(&vec![KeyCode::D.into()])
+        );
+        assert_eq!(
+            effective_map.get(&Action::Jump),
+            Some(&vec![KeyCode::K.into()])
+        );
+    }
+}
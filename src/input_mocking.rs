@@ -7,32 +7,47 @@
 //! These are then parsed down to their [`UserInput::raw_inputs()`],
 //! which are then sent as [`bevy::input`] events of the appropriate types.
 
-use crate::axislike::{AxisType, MouseMotionAxisType, MouseWheelAxisType};
+use crate::axislike::{
+    AxisType, DualAxis, MouseMotionAxisType, MouseWheelAxisType, PenAxisType, SingleAxis,
+};
 use crate::buttonlike::{MouseMotionDirection, MouseWheelDirection};
 use crate::input_streams::{InputStreams, MutableInputStreams};
-use crate::user_input::UserInput;
+use crate::scan_codes::qwerty_scan_code;
+use crate::timing::TickClock;
+use crate::user_input::{InputKind, UserInput};
 
-use bevy::app::App;
+use bevy::app::{App, Main};
 use bevy::ecs::event::Events;
 use bevy::ecs::system::{ResMut, SystemState};
 use bevy::ecs::world::World;
 #[cfg(feature = "ui")]
 use bevy::ecs::{component::Component, query::With, system::Query};
-use bevy::input::gamepad::{GamepadAxisChangedEvent, GamepadButtonChangedEvent};
+use bevy::input::gamepad::{
+    GamepadAxis, GamepadAxisChangedEvent, GamepadAxisType, GamepadButtonChangedEvent,
+    GamepadButtonType, GamepadConnection, GamepadConnectionEvent, GamepadInfo,
+};
 use bevy::input::mouse::MouseScrollUnit;
 use bevy::input::ButtonState;
 use bevy::input::{
     gamepad::{Gamepad, GamepadButton, GamepadEvent},
-    keyboard::{KeyCode, KeyboardInput},
+    keyboard::{KeyCode, KeyboardInput, ScanCode},
     mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseWheel},
-    touch::{TouchInput, Touches},
-    Input,
+    touch::{ForceTouch, Touch, TouchInput, TouchPhase, Touches},
+    Axis, Input,
 };
 use bevy::math::Vec2;
 use bevy::prelude::Entity;
 #[cfg(feature = "ui")]
 use bevy::ui::Interaction;
+use bevy::utils::Duration;
 use bevy::window::CursorMoved;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::FRAC_PI_2;
+
+/// The id of the touch used by [`MockInput::send_input`] to send [`PenAxisType`] axes
+///
+/// The pen touches the top-left corner of the window, and can be lifted with [`MockInput::release_touch`].
+pub const MOCK_PEN_ID: u64 = u64::MAX;
 
 /// Send fake input events for testing purposes
 ///
@@ -83,6 +98,11 @@ pub trait MockInput {
     ///
     /// Gamepad input will be sent by the first registered controller found.
     /// If none are found, gamepad input will be silently skipped.
+    /// Use [`MockInput::connect_gamepad`] to register a mocked controller.
+    ///
+    /// [`PenAxisType`] axes are sent as the force of a touch with the id [`MOCK_PEN_ID`].
+    /// As a touch has a single force, sending the tilt of the pen presses it with its maximum force.
+    /// The direction the pen leans towards can't be sent, so [`PenAxisType::TiltY`] is ignored.
     ///
     /// # Warning
     ///
@@ -109,16 +129,154 @@ pub trait MockInput {
     /// Provide the [`Gamepad`] identifier to control which gamepad you are emulating.
     fn release_input_as_gamepad(&mut self, input: impl Into<UserInput>, gamepad: Option<Gamepad>);
 
+    /// Presses the `key_code` the way a real keyboard would
+    ///
+    /// Unlike [`MockInput::send_input`], a single [`KeyboardInput`] event is sent,
+    /// carrying both the logical [`KeyCode`] and the [`ScanCode`] of the key that emits it on the QWERTY layout.
+    /// This presses bindings to either the key or its [`QwertyScanCode`](crate::scan_codes::QwertyScanCode).
+    ///
+    /// Keys that are not described by [`QwertyScanCode`](crate::scan_codes::QwertyScanCode) are sent without a scan code.
+    fn send_key(&mut self, key_code: KeyCode);
+
+    /// Releases the `key_code` the way a real keyboard would
+    ///
+    /// This is the counterpart of [`MockInput::send_key`].
+    fn release_key(&mut self, key_code: KeyCode);
+
+    /// Moves the gamepad `axis_type` to the given `value`
+    ///
+    /// The value is written into the [`Axis<GamepadAxis>`](bevy::input::Axis) resource directly,
+    /// and a matching [`GamepadEvent`] is sent for any systems that read gamepad events.
+    /// It persists until it is changed again, or [`MockInput::reset_inputs`] is called.
+    ///
+    /// Gamepad input will be sent by the first registered controller found.
+    /// If none are found, gamepad input will be silently skipped.
+    fn send_axis_values(&mut self, axis_type: GamepadAxisType, value: f32);
+
+    /// Presses the `button_type` on the provided `gamepad` only
+    ///
+    /// Other gamepads are left untouched, unlike [`MockInput::send_input`],
+    /// which presses the button on the first registered controller.
+    fn send_gamepad_input(&mut self, gamepad: Gamepad, button_type: GamepadButtonType) {
+        self.send_input_as_gamepad(button_type, Some(gamepad));
+    }
+
+    /// Moves the `axis_type` of the provided `gamepad` only to the given `value`
+    ///
+    /// Other gamepads are left untouched, unlike [`MockInput::send_axis_values`],
+    /// which moves the axis of the first registered controller.
+    /// Like [`MockInput::send_axis_values`], the value persists until it is changed again,
+    /// or [`MockInput::reset_inputs`] is called.
+    fn send_gamepad_axis(&mut self, gamepad: Gamepad, axis_type: GamepadAxisType, value: f32);
+
+    /// Sets the analog value of the `button_type`, such as how far a trigger is pulled
+    ///
+    /// The value is written into the [`Axis<GamepadButton>`](bevy::input::Axis) resource directly,
+    /// and a matching [`GamepadEvent`] is sent, which presses or releases the button according to Bevy's
+    /// [`GamepadSettings`](bevy::input::gamepad::GamepadSettings).
+    /// Like [`MockInput::send_axis_values`], the value persists until it is changed again,
+    /// or [`MockInput::reset_inputs`] is called.
+    ///
+    /// Gamepad input will be sent by the first registered controller found.
+    /// If none are found, gamepad input will be silently skipped.
+    fn send_button_value(&mut self, button_type: GamepadButtonType, value: f32);
+
+    /// Sets the analog value of the `button_type` of the provided `gamepad` only
+    ///
+    /// Other gamepads are left untouched, unlike [`MockInput::send_button_value`],
+    /// which sets the value of the first registered controller.
+    fn send_gamepad_button_value(
+        &mut self,
+        gamepad: Gamepad,
+        button_type: GamepadButtonType,
+        value: f32,
+    );
+
+    /// Moves both axes of the `dual_axis` to the given `value`
+    ///
+    /// Gamepad axes are moved using [`MockInput::send_axis_values`], so their values persist across frames.
+    /// Mouse axes are sent as events, like [`MockInput::send_input`], so they only last for a single frame.
+    fn send_dual_axis(&mut self, dual_axis: DualAxis, value: Vec2);
+
+    /// Moves the gamepad `stick` to the given `value`
+    ///
+    /// This is a shorthand for [`MockInput::send_dual_axis`] using [`DualAxis::left_stick`] or [`DualAxis::right_stick`].
+    fn send_stick(&mut self, stick: Stick, value: Vec2) {
+        let dual_axis = match stick {
+            Stick::Left => DualAxis::left_stick(),
+            Stick::Right => DualAxis::right_stick(),
+        };
+        self.send_dual_axis(dual_axis, value);
+    }
+
+    /// Moves the mouse by `delta`
+    ///
+    /// A [`MouseMotion`] event is sent, which is seen by the [`InputStreams`] on the next update.
+    /// Like real hardware, several calls within a single frame add up to their total movement.
+    /// The movement only lasts for a single frame, and can be discarded early using [`MockInput::reset_mouse`].
+    fn send_mouse_motion(&mut self, delta: Vec2);
+
+    /// Scrolls the mouse wheel by `x` and `y`, measured in the given `unit`
+    ///
+    /// A [`MouseWheel`] event is sent, which is seen by the [`InputStreams`] on the next update.
+    /// Like real hardware, several calls within a single frame add up to their total movement.
+    /// The movement only lasts for a single frame, and can be discarded early using [`MockInput::reset_mouse`].
+    fn send_mouse_wheel(&mut self, x: f32, y: f32, unit: MouseScrollUnit);
+
+    /// Discards all mouse motion and mouse wheel movement that has not been read yet
+    fn reset_mouse(&mut self);
+
+    /// Puts a finger with the given `id` on the touch screen at `position`
+    ///
+    /// A [`TouchInput`] event is sent, which updates the [`Touches`] resource on the next update.
+    /// The touch stays pressed until [`MockInput::release_touch`] is called,
+    /// and several touches with distinct ids can be held at the same time.
+    fn send_touch(&mut self, id: u64, position: Vec2);
+
+    /// Moves the finger with the given `id` to `position`
+    ///
+    /// The finger must already have been put on the touch screen using [`MockInput::send_touch`].
+    fn move_touch(&mut self, id: u64, position: Vec2);
+
+    /// Lifts the finger with the given `id` from the touch screen, at its last known position
+    ///
+    /// If no touch with this `id` is known, nothing happens.
+    fn release_touch(&mut self, id: u64);
+
+    /// Connects the provided `gamepad`, as if it had just been plugged in
+    ///
+    /// A [`GamepadConnectionEvent`] is sent, and the gamepad is added to the [`Gamepads`](bevy::input::gamepad::Gamepads) resource
+    /// on the next update. Gamepad input sent without an explicit [`Gamepad`] will then use it
+    /// if it is the first registered controller.
+    fn connect_gamepad(&mut self, gamepad: Gamepad);
+
+    /// Disconnects the provided `gamepad`, as if it had just been unplugged
+    ///
+    /// A [`GamepadConnectionEvent`] is sent, and on the next update the gamepad is removed from
+    /// the [`Gamepads`](bevy::input::gamepad::Gamepads) resource and all of its buttons and axes are released.
+    fn disconnect_gamepad(&mut self, gamepad: Gamepad);
+
     /// Clears all user input streams, resetting them to their default state
     ///
     /// All buttons are released, and `just_pressed` and `just_released` information on the [`Input`] type are lost.
     /// `just_pressed` and `just_released` on the [`ActionState`](crate::action_state::ActionState) will be kept.
     ///
-    /// This will clear all [`KeyCode`], [`GamepadButton`] and [`MouseButton`] input streams,
+    /// This will clear all [`KeyCode`], [`ScanCode`], [`GamepadButton`], [`GamepadAxis`] and [`MouseButton`] input streams,
+    /// any pending mouse wheel and mouse motion, the [`Touches`] resource,
     /// as well as any [`Interaction`] components and all input [`Events`].
+    /// Registered gamepads stay connected.
     fn reset_inputs(&mut self);
 }
 
+/// One of the two analog sticks of a gamepad, used by [`MockInput::send_stick`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stick {
+    /// The left stick, made of [`GamepadAxisType::LeftStickX`] and [`GamepadAxisType::LeftStickY`]
+    Left,
+    /// The right stick, made of [`GamepadAxisType::RightStickX`] and [`GamepadAxisType::RightStickY`]
+    Right,
+}
+
 /// Query [`Input`] state directly for testing purposes.
 ///
 /// In game code, you should (almost) always be using [`ActionState`](crate::action_state::ActionState)
@@ -135,6 +293,296 @@ pub trait QueryInput {
     /// This method is intended as a convenience for testing; check the [`Input`] resource directly,
     /// or use an [`InputMap`](crate::input_map::InputMap) in real code.
     fn pressed_for_gamepad(&self, input: impl Into<UserInput>, gamepad: Option<Gamepad>) -> bool;
+
+    /// Lists every button-like input that is currently pressed, in no particular order
+    ///
+    /// This covers keys and key locations, mouse buttons, gamepad buttons of any registered gamepad,
+    /// and mouse wheel and mouse motion directions.
+    /// Analog axes are not listed, as they can only be considered pressed relative to a threshold:
+    /// check them using [`QueryInput::pressed`] instead.
+    fn pressed_inputs(&self) -> Vec<InputKind>;
+
+    /// Is any button-like input pressed?
+    ///
+    /// This checks the same inputs as [`QueryInput::pressed_inputs`].
+    fn any_pressed(&self) -> bool {
+        !self.pressed_inputs().is_empty()
+    }
+}
+
+/// A frame-by-frame sequence of mocked inputs
+///
+/// Inputs are recorded into the current frame until [`InputScript::wait_frames`] is called,
+/// which advances the script to a later frame.
+/// Every frame in the script corresponds to a single `app.update()`:
+/// use [`RunInputScript::run_script`] to perform them in order.
+///
+/// Scripts can be serialized, so recorded input sessions can be stored and replayed later.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::input_mocking::InputScript;
+///
+/// // Hold W for 3 frames, tapping Space on the second one
+/// let script = InputScript::new()
+///     .press(KeyCode::W)
+///     .wait_frames(1)
+///     .press(KeyCode::Space)
+///     .wait_frames(1)
+///     .release(KeyCode::Space)
+///     .wait_frames(1)
+///     .release(KeyCode::W);
+///
+/// assert_eq!(script.len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputScript {
+    frames: Vec<Vec<InputScriptStep>>,
+}
+
+/// A single mocked input action, performed as part of an [`InputScript`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputScriptStep {
+    /// Sends the input using [`MockInput::send_input`]
+    Press(UserInput),
+    /// Releases the input using [`MockInput::release_input`]
+    Release(UserInput),
+    /// Sends the axis with the given value using [`MockInput::send_input`]
+    Axis(SingleAxis, f32),
+}
+
+impl InputScript {
+    /// Creates a new [`InputScript`], containing a single empty frame
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            frames: vec![Vec::new()],
+        }
+    }
+
+    /// Presses the `input` during the current frame
+    #[must_use]
+    pub fn press(mut self, input: impl Into<UserInput>) -> Self {
+        self.push(InputScriptStep::Press(input.into()));
+        self
+    }
+
+    /// Releases the `input` during the current frame
+    #[must_use]
+    pub fn release(mut self, input: impl Into<UserInput>) -> Self {
+        self.push(InputScriptStep::Release(input.into()));
+        self
+    }
+
+    /// Moves the `axis` to the `value` during the current frame
+    ///
+    /// Any `value` already stored on the `axis` is ignored.
+    #[must_use]
+    pub fn axis(mut self, axis: SingleAxis, value: f32) -> Self {
+        self.push(InputScriptStep::Axis(axis, value));
+        self
+    }
+
+    /// Advances the script by `n` frames
+    ///
+    /// Any inputs added afterwards will be sent `n` frames after the inputs that came before.
+    #[must_use]
+    pub fn wait_frames(mut self, n: usize) -> Self {
+        self.frames.extend((0..n).map(|_| Vec::new()));
+        self
+    }
+
+    /// Adds a `step` to the current frame
+    pub fn push(&mut self, step: InputScriptStep) {
+        // There is always at least one frame, as guaranteed by the constructor
+        self.frames.last_mut().unwrap().push(step);
+    }
+
+    /// The steps of the script, grouped by frame
+    #[must_use]
+    pub fn frames(&self) -> &[Vec<InputScriptStep>] {
+        &self.frames
+    }
+
+    /// The number of frames in the script
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Does this script contain any steps at all?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.iter().all(Vec::is_empty)
+    }
+}
+
+impl Default for InputScript {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputScriptStep {
+    /// Performs this step on the provided `mock_input`
+    pub fn apply(&self, mock_input: &mut impl MockInput) {
+        match self {
+            InputScriptStep::Press(input) => mock_input.send_input(input.clone()),
+            InputScriptStep::Release(input) => mock_input.release_input(input.clone()),
+            InputScriptStep::Axis(axis, value) => mock_input.send_input(SingleAxis {
+                value: Some(*value),
+                ..*axis
+            }),
+        }
+    }
+}
+
+/// Run an [`InputScript`] for testing purposes
+///
+/// This is only implemented for [`App`], as running a script requires updating the app between frames.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy::input::InputPlugin;
+/// use leafwing_input_manager::input_mocking::{InputScript, QueryInput, RunInputScript};
+///
+/// let mut app = App::new();
+/// app.add_plugins(InputPlugin);
+///
+/// // Hold W for 3 frames, then tap Space, then release W
+/// app.run_script(
+///     InputScript::new()
+///         .press(KeyCode::W)
+///         .wait_frames(3)
+///         .press(KeyCode::Space)
+///         .wait_frames(1)
+///         .release(KeyCode::Space)
+///         .release(KeyCode::W),
+/// );
+///
+/// assert!(!app.pressed(KeyCode::W));
+/// ```
+pub trait RunInputScript {
+    /// Performs each frame of the `script` in order
+    ///
+    /// The steps of each frame are sent using [`MockInput`], and then the app is updated once.
+    fn run_script(&mut self, script: InputScript);
+}
+
+/// Control the clock used to time actions for testing purposes
+///
+/// Timing-dependent features can't be tested reliably against the wall clock.
+/// Instead, insert a [`TickClock::manual`] before the first update, and advance it by an exact amount before each update.
+/// Every [`ActionState`](crate::action_state::ActionState) is then ticked using the instants of this clock.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy::utils::Duration;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::timing::TickClock;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Jump,
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .add_plugins(InputManagerPlugin::<Action>::server())
+///     .insert_resource(TickClock::manual())
+///     .init_resource::<ActionState<Action>>();
+/// app.update();
+///
+/// app.world.resource_mut::<ActionState<Action>>().press(&Action::Jump);
+/// app.update();
+///
+/// app.advance_time(Duration::from_millis(150));
+/// app.update();
+///
+/// let action_state = app.world.resource::<ActionState<Action>>();
+/// assert_eq!(action_state.current_duration(&Action::Jump), Duration::from_millis(150));
+///
+/// // Hold for 30 more frames, at 60 frames per second
+/// app.advance_frames_with(30, Duration::from_micros(16_667));
+///
+/// let action_state = app.world.resource::<ActionState<Action>>();
+/// assert_eq!(action_state.current_duration(&Action::Jump), Duration::from_micros(150_000 + 30 * 16_667));
+/// ```
+pub trait MockTime {
+    /// Moves the [`TickClock`] forwards by `duration`, which is measured by the next tick
+    ///
+    /// If the [`TickClock`] is not [`TickClock::Manual`], it is replaced by [`TickClock::manual`] first.
+    /// As the previous tick was measured using another clock, the next tick will not be exact:
+    /// insert the manual clock before the first update instead.
+    fn advance_time(&mut self, duration: Duration);
+
+    /// Runs `n` frames, without changing the [`TickClock`]
+    ///
+    /// Each frame is a full update: in [`PreUpdate`](bevy::app::PreUpdate), every [`ActionState`](crate::action_state::ActionState)
+    /// is first ticked by [`InputManagerSystem::Tick`](crate::plugin::InputManagerSystem::Tick),
+    /// and then updated from the inputs by [`InputManagerSystem::Update`](crate::plugin::InputManagerSystem::Update).
+    /// As a result, inputs sent before calling this method are seen by the first frame,
+    /// but actions pressed by them only start counting their duration from the second frame.
+    ///
+    /// For a [`World`], this runs the [`Main`] schedule and clears the change trackers, like [`App::update`],
+    /// but any sub-apps are not updated.
+    fn advance_frames(&mut self, n: usize);
+
+    /// Runs `n` frames, moving the [`TickClock`] forwards by `delta` before each one
+    ///
+    /// This uses [`MockTime::advance_time`] and [`MockTime::advance_frames`],
+    /// so each tick measures exactly `delta`, and an action that is pressed on the first frame
+    /// has a [`current_duration`](crate::action_state::ActionState::current_duration) of `(n - 1) * delta` afterwards.
+    fn advance_frames_with(&mut self, n: usize, delta: Duration) {
+        for _ in 0..n {
+            self.advance_time(delta);
+            self.advance_frames(1);
+        }
+    }
+}
+
+impl MockTime for World {
+    fn advance_time(&mut self, duration: Duration) {
+        let mut clock = match self.get_resource::<TickClock>() {
+            Some(clock @ TickClock::Manual { .. }) => *clock,
+            _ => TickClock::manual(),
+        };
+        clock.advance(duration);
+        self.insert_resource(clock);
+    }
+
+    fn advance_frames(&mut self, n: usize) {
+        for _ in 0..n {
+            self.run_schedule(Main);
+            self.clear_trackers();
+        }
+    }
+}
+
+impl MockTime for App {
+    fn advance_time(&mut self, duration: Duration) {
+        self.world.advance_time(duration);
+    }
+
+    fn advance_frames(&mut self, n: usize) {
+        for _ in 0..n {
+            self.update();
+        }
+    }
+}
+
+impl RunInputScript for App {
+    fn run_script(&mut self, script: InputScript) {
+        for steps in script.frames() {
+            for step in steps {
+                step.apply(self);
+            }
+            self.update();
+        }
+    }
 }
 
 /// Send fake UI interaction for testing purposes.
@@ -171,6 +619,16 @@ impl MockInput for MutableInputStreams<'_> {
             });
         }
 
+        // Physical key locations
+        for scan_code in raw_inputs.scan_codes {
+            self.keyboard_events.send(KeyboardInput {
+                scan_code: scan_code.0,
+                key_code: None,
+                state: ButtonState::Pressed,
+                window: Entity::PLACEHOLDER,
+            });
+        }
+
         // Mouse buttons
         for button in raw_inputs.mouse_buttons {
             self.mouse_button_events.send(MouseButtonInput {
@@ -231,6 +689,9 @@ impl MockInput for MutableInputStreams<'_> {
         // Gamepad buttons
         for button_type in raw_inputs.gamepad_buttons {
             if let Some(gamepad) = gamepad {
+                // Only `bevy_gilrs` sets the value of gamepad buttons, so we have to do it ourselves
+                self.gamepad_button_axes
+                    .set(GamepadButton::new(gamepad, button_type), 1.0);
                 self.gamepad_events
                     .send(GamepadEvent::Button(GamepadButtonChangedEvent {
                         gamepad,
@@ -285,6 +746,31 @@ impl MockInput for MutableInputStreams<'_> {
                             },
                         }),
                     },
+                    AxisType::Pen(axis_type) => {
+                        let force = match axis_type {
+                            PenAxisType::Pressure => ForceTouch::Normalized(position_data.into()),
+                            // Only the altitude of the pen can be sent, which is read by both of these
+                            PenAxisType::Tilt | PenAxisType::TiltX => ForceTouch::Calibrated {
+                                force: 1.0,
+                                max_possible_force: 1.0,
+                                altitude_angle: Some(
+                                    (1.0 - f64::from(position_data.abs())) * FRAC_PI_2,
+                                ),
+                            },
+                            PenAxisType::TiltY => continue,
+                        };
+                        let phase = if self.touches.get_pressed(MOCK_PEN_ID).is_some() {
+                            TouchPhase::Moved
+                        } else {
+                            TouchPhase::Started
+                        };
+                        self.touch_events.send(TouchInput {
+                            phase,
+                            position: Vec2::ZERO,
+                            force: Some(force),
+                            id: MOCK_PEN_ID,
+                        });
+                    }
                 }
             }
         }
@@ -302,11 +788,13 @@ impl MockInput for MutableInputStreams<'_> {
 
         for button_type in raw_inputs.gamepad_buttons {
             if let Some(gamepad) = gamepad {
+                self.gamepad_button_axes
+                    .set(GamepadButton::new(gamepad, button_type), 0.0);
                 self.gamepad_events
                     .send(GamepadEvent::Button(GamepadButtonChangedEvent {
                         gamepad,
                         button_type,
-                        value: 1.0,
+                        value: 0.0,
                     }));
             }
         }
@@ -320,6 +808,15 @@ impl MockInput for MutableInputStreams<'_> {
             });
         }
 
+        for scan_code in raw_inputs.scan_codes {
+            self.keyboard_events.send(KeyboardInput {
+                scan_code: scan_code.0,
+                key_code: None,
+                state: ButtonState::Released,
+                window: Entity::PLACEHOLDER,
+            });
+        }
+
         for button in raw_inputs.mouse_buttons {
             self.mouse_button_events.send(MouseButtonInput {
                 button,
@@ -329,16 +826,168 @@ impl MockInput for MutableInputStreams<'_> {
         }
     }
 
+    fn send_key(&mut self, key_code: KeyCode) {
+        self.keyboard_events.send(KeyboardInput {
+            scan_code: qwerty_scan_code(key_code).map_or(u32::MAX, |scan_code| scan_code.0),
+            key_code: Some(key_code),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    fn release_key(&mut self, key_code: KeyCode) {
+        self.keyboard_events.send(KeyboardInput {
+            scan_code: qwerty_scan_code(key_code).map_or(u32::MAX, |scan_code| scan_code.0),
+            key_code: Some(key_code),
+            state: ButtonState::Released,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    fn send_axis_values(&mut self, axis_type: GamepadAxisType, value: f32) {
+        if let Some(gamepad) = self.guess_gamepad() {
+            self.send_gamepad_axis(gamepad, axis_type, value);
+        }
+    }
+
+    fn send_gamepad_axis(&mut self, gamepad: Gamepad, axis_type: GamepadAxisType, value: f32) {
+        self.gamepad_axes
+            .set(GamepadAxis { gamepad, axis_type }, value);
+        self.gamepad_events
+            .send(GamepadEvent::Axis(GamepadAxisChangedEvent {
+                gamepad,
+                axis_type,
+                value,
+            }));
+    }
+
+    fn send_button_value(&mut self, button_type: GamepadButtonType, value: f32) {
+        if let Some(gamepad) = self.guess_gamepad() {
+            self.send_gamepad_button_value(gamepad, button_type, value);
+        }
+    }
+
+    fn send_gamepad_button_value(
+        &mut self,
+        gamepad: Gamepad,
+        button_type: GamepadButtonType,
+        value: f32,
+    ) {
+        self.gamepad_button_axes
+            .set(GamepadButton::new(gamepad, button_type), value);
+        self.gamepad_events
+            .send(GamepadEvent::Button(GamepadButtonChangedEvent {
+                gamepad,
+                button_type,
+                value,
+            }));
+    }
+
+    fn send_dual_axis(&mut self, dual_axis: DualAxis, value: Vec2) {
+        for (axis, value) in [(dual_axis.x, value.x), (dual_axis.y, value.y)] {
+            match axis.axis_type {
+                AxisType::Gamepad(axis_type) => self.send_axis_values(axis_type, value),
+                _ => self.send_input(SingleAxis {
+                    value: Some(value),
+                    ..axis
+                }),
+            }
+        }
+    }
+
+    fn send_mouse_motion(&mut self, delta: Vec2) {
+        self.mouse_motion.send(MouseMotion { delta });
+    }
+
+    fn send_mouse_wheel(&mut self, x: f32, y: f32, unit: MouseScrollUnit) {
+        self.mouse_wheel.send(MouseWheel {
+            unit,
+            x,
+            y,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    fn reset_mouse(&mut self) {
+        self.mouse_motion.clear();
+        self.mouse_wheel.clear();
+    }
+
+    fn send_touch(&mut self, id: u64, position: Vec2) {
+        self.touch_events.send(TouchInput {
+            phase: TouchPhase::Started,
+            position,
+            force: None,
+            id,
+        });
+    }
+
+    fn move_touch(&mut self, id: u64, position: Vec2) {
+        self.touch_events.send(TouchInput {
+            phase: TouchPhase::Moved,
+            position,
+            force: None,
+            id,
+        });
+    }
+
+    fn release_touch(&mut self, id: u64) {
+        // Touch events that have not been processed yet are more recent than the `Touches` resource
+        let pending_position = self
+            .touch_events
+            .iter_current_update_events()
+            .filter(|touch_input| touch_input.id == id)
+            .last()
+            .map(|touch_input| touch_input.position);
+        let Some(position) =
+            pending_position.or_else(|| self.touches.get_pressed(id).map(Touch::position))
+        else {
+            return;
+        };
+
+        self.touch_events.send(TouchInput {
+            phase: TouchPhase::Ended,
+            position,
+            force: None,
+            id,
+        });
+    }
+
+    fn connect_gamepad(&mut self, gamepad: Gamepad) {
+        self.gamepad_events
+            .send(GamepadEvent::Connection(GamepadConnectionEvent {
+                gamepad,
+                connection: GamepadConnection::Connected(GamepadInfo {
+                    name: "TestController".into(),
+                }),
+            }));
+    }
+
+    fn disconnect_gamepad(&mut self, gamepad: Gamepad) {
+        self.gamepad_events
+            .send(GamepadEvent::Connection(GamepadConnectionEvent {
+                gamepad,
+                connection: GamepadConnection::Disconnected,
+            }));
+    }
+
     fn reset_inputs(&mut self) {
         // WARNING: this *must* be updated when MutableInputStreams's fields change
         // Note that we deliberately are not resetting either Gamepads or associated_gamepad
         // as they are not actually input data
         *self.gamepad_buttons = Default::default();
+        *self.gamepad_button_axes = Default::default();
         *self.gamepad_axes = Default::default();
+        *self.gamepad_events = Default::default();
         *self.keycodes = Default::default();
+        *self.scan_codes = Default::default();
+        *self.keyboard_events = Default::default();
         *self.mouse_buttons = Default::default();
+        *self.mouse_button_events = Default::default();
         *self.mouse_wheel = Default::default();
         *self.mouse_motion = Default::default();
+        *self.touches = Default::default();
+        *self.touch_events = Default::default();
     }
 }
 
@@ -353,6 +1002,59 @@ impl QueryInput for InputStreams<'_> {
 
         input_streams.input_pressed(&input.into())
     }
+
+    fn pressed_inputs(&self) -> Vec<InputKind> {
+        let mut pressed_inputs = Vec::new();
+
+        if let Some(keycodes) = self.keycodes {
+            pressed_inputs.extend(keycodes.get_pressed().copied().map(InputKind::Keyboard));
+        }
+
+        if let Some(scan_codes) = self.scan_codes {
+            // Mocked key codes are sent with a placeholder scan code, which is not a real key
+            let real_scan_codes = scan_codes
+                .get_pressed()
+                .filter(|scan_code| scan_code.0 != u32::MAX);
+            pressed_inputs.extend(real_scan_codes.copied().map(InputKind::KeyLocation));
+        }
+
+        if let Some(mouse_buttons) = self.mouse_buttons {
+            pressed_inputs.extend(mouse_buttons.get_pressed().copied().map(InputKind::Mouse));
+        }
+
+        for gamepad_button in self.gamepad_buttons.get_pressed() {
+            let from_gamepad = match self.associated_gamepad {
+                Some(gamepad) => gamepad_button.gamepad == gamepad,
+                None => self.gamepads.contains(gamepad_button.gamepad),
+            };
+            let input = InputKind::GamepadButton(gamepad_button.button_type);
+
+            // The same button may be pressed on several gamepads
+            if from_gamepad && !pressed_inputs.contains(&input) {
+                pressed_inputs.push(input);
+            }
+        }
+
+        let mouse_wheel_directions = [
+            MouseWheelDirection::Up,
+            MouseWheelDirection::Down,
+            MouseWheelDirection::Right,
+            MouseWheelDirection::Left,
+        ];
+        let mouse_motion_directions = [
+            MouseMotionDirection::Up,
+            MouseMotionDirection::Down,
+            MouseMotionDirection::Right,
+            MouseMotionDirection::Left,
+        ];
+        let mouse_directions = mouse_wheel_directions
+            .map(InputKind::MouseWheel)
+            .into_iter()
+            .chain(mouse_motion_directions.map(InputKind::MouseMotion));
+        pressed_inputs.extend(mouse_directions.filter(|input| self.button_pressed(*input)));
+
+        pressed_inputs
+    }
 }
 
 impl MockInput for World {
@@ -380,6 +1082,101 @@ impl MockInput for World {
         mutable_input_streams.release_input_as_gamepad(input, gamepad);
     }
 
+    fn send_key(&mut self, key_code: KeyCode) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.send_key(key_code);
+    }
+
+    fn release_key(&mut self, key_code: KeyCode) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.release_key(key_code);
+    }
+
+    fn send_axis_values(&mut self, axis_type: GamepadAxisType, value: f32) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.send_axis_values(axis_type, value);
+    }
+
+    fn send_gamepad_axis(&mut self, gamepad: Gamepad, axis_type: GamepadAxisType, value: f32) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, Some(gamepad));
+
+        mutable_input_streams.send_gamepad_axis(gamepad, axis_type, value);
+    }
+
+    fn send_button_value(&mut self, button_type: GamepadButtonType, value: f32) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.send_button_value(button_type, value);
+    }
+
+    fn send_gamepad_button_value(
+        &mut self,
+        gamepad: Gamepad,
+        button_type: GamepadButtonType,
+        value: f32,
+    ) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, Some(gamepad));
+
+        mutable_input_streams.send_gamepad_button_value(gamepad, button_type, value);
+    }
+
+    fn send_dual_axis(&mut self, dual_axis: DualAxis, value: Vec2) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.send_dual_axis(dual_axis, value);
+    }
+
+    fn send_mouse_motion(&mut self, delta: Vec2) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.send_mouse_motion(delta);
+    }
+
+    fn send_mouse_wheel(&mut self, x: f32, y: f32, unit: MouseScrollUnit) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.send_mouse_wheel(x, y, unit);
+    }
+
+    fn reset_mouse(&mut self) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.reset_mouse();
+    }
+
+    fn send_touch(&mut self, id: u64, position: Vec2) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.send_touch(id, position);
+    }
+
+    fn move_touch(&mut self, id: u64, position: Vec2) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.move_touch(id, position);
+    }
+
+    fn release_touch(&mut self, id: u64) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.release_touch(id);
+    }
+
+    fn connect_gamepad(&mut self, gamepad: Gamepad) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.connect_gamepad(gamepad);
+    }
+
+    fn disconnect_gamepad(&mut self, gamepad: Gamepad) {
+        let mut mutable_input_streams = MutableInputStreams::from_world(self, None);
+
+        mutable_input_streams.disconnect_gamepad(gamepad);
+    }
+
     fn reset_inputs(&mut self) {
         #[cfg(feature = "ui")]
         {
@@ -394,30 +1191,55 @@ impl MockInput for World {
 
         let mut input_system_state: SystemState<(
             Option<ResMut<Input<GamepadButton>>>,
+            Option<ResMut<Axis<GamepadButton>>>,
+            Option<ResMut<Axis<GamepadAxis>>>,
             Option<ResMut<Input<KeyCode>>>,
+            Option<ResMut<Input<ScanCode>>>,
             Option<ResMut<Input<MouseButton>>>,
         )> = SystemState::new(self);
 
-        let (maybe_gamepad, maybe_keyboard, maybe_mouse) = input_system_state.get_mut(self);
+        let (
+            maybe_gamepad,
+            maybe_gamepad_button_axes,
+            maybe_gamepad_axes,
+            maybe_keyboard,
+            maybe_scan_codes,
+            maybe_mouse,
+        ) = input_system_state.get_mut(self);
 
         if let Some(mut gamepad) = maybe_gamepad {
             *gamepad = Default::default();
         }
 
+        if let Some(mut gamepad_button_axes) = maybe_gamepad_button_axes {
+            *gamepad_button_axes = Default::default();
+        }
+
+        if let Some(mut gamepad_axes) = maybe_gamepad_axes {
+            *gamepad_axes = Default::default();
+        }
+
         if let Some(mut keyboard) = maybe_keyboard {
             *keyboard = Default::default();
         }
 
+        if let Some(mut scan_codes) = maybe_scan_codes {
+            *scan_codes = Default::default();
+        }
+
         if let Some(mut mouse) = maybe_mouse {
             *mouse = Default::default();
         }
 
         self.insert_resource(Events::<GamepadEvent>::default());
+        self.insert_resource(Events::<GamepadButtonChangedEvent>::default());
+        self.insert_resource(Events::<GamepadAxisChangedEvent>::default());
 
         self.insert_resource(Events::<KeyboardInput>::default());
 
         self.insert_resource(Events::<MouseButtonInput>::default());
         self.insert_resource(Events::<MouseWheel>::default());
+        self.insert_resource(Events::<MouseMotion>::default());
         self.insert_resource(Events::<CursorMoved>::default());
 
         self.insert_resource(Touches::default());
@@ -435,6 +1257,12 @@ impl QueryInput for World {
 
         input_streams.input_pressed(&input.into())
     }
+
+    fn pressed_inputs(&self) -> Vec<InputKind> {
+        let input_streams = InputStreams::from_world(self, None);
+
+        input_streams.pressed_inputs()
+    }
 }
 
 #[cfg(feature = "ui")]
@@ -473,6 +1301,72 @@ impl MockInput for App {
         self.world.release_input_as_gamepad(input, gamepad);
     }
 
+    fn send_key(&mut self, key_code: KeyCode) {
+        self.world.send_key(key_code);
+    }
+
+    fn release_key(&mut self, key_code: KeyCode) {
+        self.world.release_key(key_code);
+    }
+
+    fn send_axis_values(&mut self, axis_type: GamepadAxisType, value: f32) {
+        self.world.send_axis_values(axis_type, value);
+    }
+
+    fn send_gamepad_axis(&mut self, gamepad: Gamepad, axis_type: GamepadAxisType, value: f32) {
+        self.world.send_gamepad_axis(gamepad, axis_type, value);
+    }
+
+    fn send_button_value(&mut self, button_type: GamepadButtonType, value: f32) {
+        self.world.send_button_value(button_type, value);
+    }
+
+    fn send_gamepad_button_value(
+        &mut self,
+        gamepad: Gamepad,
+        button_type: GamepadButtonType,
+        value: f32,
+    ) {
+        self.world
+            .send_gamepad_button_value(gamepad, button_type, value);
+    }
+
+    fn send_dual_axis(&mut self, dual_axis: DualAxis, value: Vec2) {
+        self.world.send_dual_axis(dual_axis, value);
+    }
+
+    fn send_mouse_motion(&mut self, delta: Vec2) {
+        self.world.send_mouse_motion(delta);
+    }
+
+    fn send_mouse_wheel(&mut self, x: f32, y: f32, unit: MouseScrollUnit) {
+        self.world.send_mouse_wheel(x, y, unit);
+    }
+
+    fn reset_mouse(&mut self) {
+        self.world.reset_mouse();
+    }
+
+    fn send_touch(&mut self, id: u64, position: Vec2) {
+        self.world.send_touch(id, position);
+    }
+
+    fn move_touch(&mut self, id: u64, position: Vec2) {
+        self.world.move_touch(id, position);
+    }
+
+    fn release_touch(&mut self, id: u64) {
+        self.world.release_touch(id);
+    }
+
+    fn connect_gamepad(&mut self, gamepad: Gamepad) {
+        self.world.connect_gamepad(gamepad);
+    }
+
+    fn disconnect_gamepad(&mut self, gamepad: Gamepad) {
+        self.world.disconnect_gamepad(gamepad);
+    }
+
     fn reset_inputs(&mut self) {
         self.world.reset_inputs();
     }
@@ -486,6 +1380,10 @@ impl QueryInput for App {
     fn pressed_for_gamepad(&self, input: impl Into<UserInput>, gamepad: Option<Gamepad>) -> bool {
         self.world.pressed_for_gamepad(input, gamepad)
     }
+
+    fn pressed_inputs(&self) -> Vec<InputKind> {
+        self.world.pressed_inputs()
+    }
 }
 
 #[cfg(feature = "ui")]
@@ -501,14 +1399,11 @@ impl MockUIInteraction for App {
 
 #[cfg(test)]
 mod test {
-    use crate::input_mocking::{MockInput, MockUIInteraction, QueryInput};
-    use bevy::{
-        input::{
-            gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo},
-            InputPlugin,
-        },
-        prelude::*,
-    };
+    use crate::axislike::{DualAxis, SingleAxis};
+    use crate::input_mocking::{InputScript, MockInput, MockUIInteraction, QueryInput, Stick};
+    use crate::scan_codes::QwertyScanCode;
+    use crate::user_input::{InputKind, UserInput};
+    use bevy::{input::InputPlugin, prelude::*};
 
     #[test]
     fn ordinary_button_inputs() {
@@ -540,19 +1435,149 @@ mod test {
         assert!(!app.pressed(MouseButton::Right));
     }
 
+    #[test]
+    fn key_inputs() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        // Both the logical key and its physical location are pressed by the same keystroke
+        app.send_key(KeyCode::W);
+        app.update();
+
+        assert!(app.pressed(KeyCode::W));
+        assert!(app.pressed(QwertyScanCode::W));
+        assert!(!app.pressed(QwertyScanCode::S));
+
+        app.release_key(KeyCode::W);
+        app.update();
+
+        assert!(!app.pressed(KeyCode::W));
+        assert!(!app.pressed(QwertyScanCode::W));
+    }
+
+    #[test]
+    fn input_script_serialization() {
+        let script = InputScript::new()
+            .press(KeyCode::W)
+            .wait_frames(2)
+            .axis(SingleAxis::mouse_wheel_y(), 2.0)
+            .release(KeyCode::W);
+
+        let bytes = bincode::serialize(&script).unwrap();
+        let deserialized: InputScript = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(deserialized, script);
+    }
+
+    #[test]
+    fn chord_inputs_are_queried() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let chord = UserInput::chord([KeyCode::ControlLeft, KeyCode::S]);
+        assert!(!app.pressed(chord.clone()));
+        assert!(!app.any_pressed());
+
+        app.send_input(chord.clone());
+        app.send_input(MouseButton::Left);
+        app.update();
+
+        assert!(app.pressed(chord));
+        assert!(app.any_pressed());
+        let pressed_inputs = app.pressed_inputs();
+        assert_eq!(pressed_inputs.len(), 3);
+        assert!(pressed_inputs.contains(&InputKind::Keyboard(KeyCode::ControlLeft)));
+        assert!(pressed_inputs.contains(&InputKind::Keyboard(KeyCode::S)));
+        assert!(pressed_inputs.contains(&InputKind::Mouse(MouseButton::Left)));
+
+        // After resetting, nothing is pressed on any device
+        app.reset_inputs();
+        app.update();
+
+        assert!(!app.any_pressed());
+    }
+
+    #[test]
+    fn dual_axis_inputs_are_queried() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+        app.connect_gamepad(Gamepad { id: 0 });
+        app.update();
+
+        let left_stick = DualAxis::left_stick();
+
+        // Within the deadzone
+        app.send_stick(Stick::Left, Vec2::new(0.05, 0.0));
+        app.update();
+        assert!(!app.pressed(left_stick));
+
+        // Past the deadzone
+        app.send_stick(Stick::Left, Vec2::new(0.5, 0.5));
+        app.update();
+        assert!(app.pressed(left_stick));
+
+        // Axes are not button-like, so they are not listed
+        assert!(!app.any_pressed());
+
+        app.send_input(GamepadButtonType::South);
+        app.update();
+        assert_eq!(
+            app.pressed_inputs(),
+            vec![InputKind::GamepadButton(GamepadButtonType::South)]
+        );
+    }
+
+    #[test]
+    fn touch_inputs() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        // Two fingers are held at the same time
+        app.send_touch(0, Vec2::new(10.0, 10.0));
+        app.send_touch(1, Vec2::new(50.0, 50.0));
+        app.update();
+
+        let touches = app.world.resource::<Touches>();
+        assert!(touches.just_pressed(0));
+        assert!(touches.just_pressed(1));
+
+        app.move_touch(0, Vec2::new(30.0, 10.0));
+        app.update();
+
+        let touches = app.world.resource::<Touches>();
+        let touch = touches.get_pressed(0).unwrap();
+        assert_eq!(touch.start_position(), Vec2::new(10.0, 10.0));
+        assert_eq!(touch.position(), Vec2::new(30.0, 10.0));
+        assert_eq!(touch.delta(), Vec2::new(20.0, 0.0));
+        assert!(touches.get_pressed(1).is_some());
+
+        // Touches stay pressed across updates
+        app.update();
+        assert_eq!(app.world.resource::<Touches>().iter().count(), 2);
+
+        app.release_touch(0);
+        app.update();
+
+        let touches = app.world.resource::<Touches>();
+        assert!(touches.just_released(0));
+        assert_eq!(
+            touches.get_released(0).unwrap().position(),
+            Vec2::new(30.0, 10.0)
+        );
+        assert!(touches.get_pressed(1).is_some());
+
+        app.reset_inputs();
+        app.update();
+
+        assert_eq!(app.world.resource::<Touches>().iter().count(), 0);
+    }
+
     #[test]
     fn explicit_gamepad_button_inputs() {
         let mut app = App::new();
         app.add_plugins(InputPlugin);
 
         let gamepad = Gamepad { id: 0 };
-        let mut gamepad_events = app.world.resource_mut::<Events<GamepadEvent>>();
-        gamepad_events.send(GamepadEvent::Connection(GamepadConnectionEvent {
-            gamepad,
-            connection: GamepadConnection::Connected(GamepadInfo {
-                name: "TestController".into(),
-            }),
-        }));
+        app.connect_gamepad(gamepad);
         app.update();
 
         // Test that buttons are unpressed by default
@@ -586,13 +1611,7 @@ mod test {
         app.add_plugins(InputPlugin);
 
         let gamepad = Gamepad { id: 0 };
-        let mut gamepad_events = app.world.resource_mut::<Events<GamepadEvent>>();
-        gamepad_events.send(GamepadEvent::Connection(GamepadConnectionEvent {
-            gamepad,
-            connection: GamepadConnection::Connected(GamepadInfo {
-                name: "TestController".into(),
-            }),
-        }));
+        app.connect_gamepad(gamepad);
         app.update();
 
         // Test that buttons are unpressed by default
@@ -612,6 +1631,29 @@ mod test {
         assert!(!app.pressed(GamepadButtonType::North));
     }
 
+    #[test]
+    fn gamepad_button_values() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let gamepad = Gamepad { id: 0 };
+        app.connect_gamepad(gamepad);
+        app.update();
+
+        let north = GamepadButton::new(gamepad, GamepadButtonType::North);
+        let value = |app: &App| app.world.resource::<Axis<GamepadButton>>().get(north);
+
+        app.send_input(GamepadButtonType::North);
+        app.update();
+        assert_eq!(value(&app), Some(1.0));
+
+        // Releasing a button sets its value back to 0, rather than pressing it again
+        app.release_input(GamepadButtonType::North);
+        app.update();
+        assert_eq!(value(&app), Some(0.0));
+        assert!(!app.pressed(GamepadButtonType::North));
+    }
+
     #[test]
     #[cfg(feature = "ui")]
     fn ui_inputs() {
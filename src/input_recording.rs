@@ -0,0 +1,245 @@
+//! Recordings of [`ActionDiff`] streams that can be scrubbed to any tick.
+//!
+//! An [`InputRecording`] stores the diffs generated on each tick, along with periodic keyframes.
+//! Reconstructing the [`ActionState`] at a given tick starts from the nearest prior keyframe,
+//! so jumping around a long recording (such as for a kill-cam or replay viewer) stays cheap.
+
+use crate::action_diff::ActionDiff;
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::utils::Instant;
+use serde::{Deserialize, Serialize};
+
+/// A tick-by-tick recording of the [`ActionDiff`]s applied to a single [`ActionState`]
+///
+/// Every `keyframe_interval` ticks, a snapshot of the state is stored,
+/// created using [`ActionState::summarize`].
+/// Smaller intervals make [`InputRecording::state_at`] faster, at the cost of a larger recording.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::action_diff::ActionDiff;
+/// use leafwing_input_manager::input_recording::InputRecording;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Jump,
+/// }
+///
+/// let mut recording = InputRecording::new(30);
+/// recording.push_tick(vec![ActionDiff::Pressed { action: Action::Jump }]);
+/// recording.push_tick(Vec::new());
+/// recording.push_tick(vec![ActionDiff::Released { action: Action::Jump }]);
+///
+/// assert!(recording.state_at(1).pressed(&Action::Jump));
+/// assert!(recording.state_at(2).just_released(&Action::Jump));
+/// // Scrubbing backwards works too
+/// assert!(recording.state_at(0).just_pressed(&Action::Jump));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InputRecording<A: Actionlike> {
+    /// The number of ticks between each keyframe
+    keyframe_interval: usize,
+    /// The diffs applied on each tick
+    ticks: Vec<Vec<ActionDiff<A>>>,
+    /// The summarized state before the diffs of every `keyframe_interval`th tick were applied
+    keyframes: Vec<Vec<ActionDiff<A>>>,
+}
+
+impl<A: Actionlike> InputRecording<A> {
+    /// Creates a new, empty [`InputRecording`] that stores a keyframe every `keyframe_interval` ticks
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keyframe_interval` is 0.
+    #[must_use]
+    pub fn new(keyframe_interval: usize) -> Self {
+        assert!(
+            keyframe_interval > 0,
+            "The keyframe interval must be at least 1"
+        );
+
+        Self {
+            keyframe_interval,
+            ticks: Vec::new(),
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// The number of ticks between each keyframe
+    #[must_use]
+    pub fn keyframe_interval(&self) -> usize {
+        self.keyframe_interval
+    }
+
+    /// The number of ticks recorded
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    /// Have no ticks been recorded?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    /// The diffs that were applied on the provided `tick`, if it has been recorded
+    #[must_use]
+    pub fn diffs_at(&self, tick: usize) -> Option<&[ActionDiff<A>]> {
+        self.ticks.get(tick).map(Vec::as_slice)
+    }
+
+    /// Records the `action_diffs` applied on the next tick
+    ///
+    /// Ticks without any changes should still be recorded, using an empty list.
+    pub fn push_tick(&mut self, action_diffs: Vec<ActionDiff<A>>) {
+        let tick = self.ticks.len();
+        // Keyframes are due every `keyframe_interval` ticks
+        if tick == self.keyframes.len() * self.keyframe_interval {
+            let keyframe = match tick.checked_sub(1) {
+                Some(previous_tick) => self.state_at(previous_tick).summarize(),
+                None => Vec::new(),
+            };
+            self.keyframes.push(keyframe);
+        }
+
+        self.ticks.push(action_diffs);
+    }
+
+    /// Reconstructs the [`ActionState`] after the diffs of the provided `tick` were applied
+    ///
+    /// Only the ticks since the nearest prior keyframe are replayed, so this is cheap to call repeatedly while scrubbing,
+    /// in either direction.
+    /// The [`ButtonState`](crate::buttonlike::ButtonState)s, values and axis pairs of each action are reconstructed,
+    /// with actions only being `just_pressed` or `just_released` on the tick that they changed.
+    /// Timing information is not recorded.
+    ///
+    /// Ticks past the end of the recording return the final state.
+    #[must_use]
+    pub fn state_at(&self, tick: usize) -> ActionState<A> {
+        let mut action_state = ActionState::default();
+        if self.is_empty() {
+            return action_state;
+        }
+        let tick = tick.min(self.ticks.len() - 1);
+
+        let keyframe_index = tick / self.keyframe_interval;
+        for action_diff in self.keyframes[keyframe_index].iter() {
+            action_state.apply_diff(action_diff);
+        }
+
+        let now = Instant::now();
+        for action_diffs in &self.ticks[keyframe_index * self.keyframe_interval..=tick] {
+            // Clears the just_pressed and just_released state from the previous tick
+            action_state.tick(now, now);
+            for action_diff in action_diffs {
+                action_state.apply_diff(action_diff);
+            }
+        }
+
+        action_state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Run,
+        Jump,
+        Throttle,
+    }
+
+    /// A deterministic stream of diffs, with some empty ticks
+    fn diff_stream(length: usize) -> Vec<Vec<ActionDiff<Action>>> {
+        (0..length)
+            .map(|tick| match tick % 7 {
+                0 => vec![ActionDiff::Pressed {
+                    action: Action::Run,
+                }],
+                2 => vec![
+                    ActionDiff::Pressed {
+                        action: Action::Jump,
+                    },
+                    ActionDiff::ValueChanged {
+                        action: Action::Throttle,
+                        value: tick as f32 / length as f32,
+                        pressed: tick % 2 == 0,
+                    },
+                ],
+                3 => vec![ActionDiff::Released {
+                    action: Action::Jump,
+                }],
+                5 => vec![ActionDiff::Released {
+                    action: Action::Run,
+                }],
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Replays the stream from the start
+    fn replay(stream: &[Vec<ActionDiff<Action>>], tick: usize) -> ActionState<Action> {
+        let mut action_state = ActionState::default();
+        let now = Instant::now();
+        for action_diffs in &stream[..=tick] {
+            action_state.tick(now, now);
+            for action_diff in action_diffs {
+                action_state.apply_diff(action_diff);
+            }
+        }
+        action_state
+    }
+
+    fn assert_states_match(actual: &ActionState<Action>, expected: &ActionState<Action>) {
+        for action in [Action::Run, Action::Jump, Action::Throttle] {
+            let button_state = |action_state: &ActionState<Action>| {
+                action_state
+                    .action_data(&action)
+                    .map(|action_data| action_data.state)
+                    .unwrap_or_default()
+            };
+            assert_eq!(button_state(actual), button_state(expected), "{action:?}");
+            assert_eq!(actual.value(&action), expected.value(&action), "{action:?}");
+        }
+    }
+
+    #[test]
+    fn state_at_matches_full_replay() {
+        let stream = diff_stream(50);
+
+        for keyframe_interval in [1, 4, 7, 100] {
+            let mut recording = InputRecording::new(keyframe_interval);
+            for action_diffs in stream.iter() {
+                recording.push_tick(action_diffs.clone());
+            }
+            assert_eq!(recording.len(), 50);
+
+            // Scrub forwards, then backwards
+            for tick in (0..50).chain((0..50).rev()) {
+                assert_states_match(&recording.state_at(tick), &replay(&stream, tick));
+            }
+        }
+    }
+
+    #[test]
+    fn state_at_past_the_end() {
+        let stream = diff_stream(10);
+        let mut recording = InputRecording::new(3);
+        assert_states_match(&recording.state_at(5), &ActionState::default());
+
+        for action_diffs in stream.iter() {
+            recording.push_tick(action_diffs.clone());
+        }
+        assert_states_match(&recording.state_at(1000), &replay(&stream, 9));
+    }
+}
@@ -1,21 +1,34 @@
 //! Unified input streams for working with [`bevy::input`] data.
 
-use bevy::ecs::prelude::{Events, ResMut, World};
+use bevy::ecs::prelude::{Entity, Events, ResMut, World};
 use bevy::ecs::system::SystemState;
 use bevy::input::{
-    gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadEvent, Gamepads},
+    gamepad::{
+        Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
+        Gamepads,
+    },
     keyboard::{KeyCode, KeyboardInput, ScanCode},
-    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseWheel},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    touch::{Touch, TouchInput, Touches},
     Axis, Input,
 };
-use bevy::utils::HashSet;
+use bevy::math::{Rect, Vec2};
+use bevy::utils::{FloatOrd, HashSet};
+use bevy::window::{CursorGrabMode, PrimaryWindow, Window};
 
 use crate::axislike::{
-    AxisType, DualAxisData, MouseMotionAxisType, MouseWheelAxisType, SingleAxis, VirtualAxis,
-    VirtualDPad,
+    AxisType, DualAxisData, EitherDualAxis, MouseMotionAxisType, MouseWheelAxisType, PenAxisType,
+    SingleAxis, TripleAxis, TripleAxisData, VirtualAxis, VirtualDPad,
 };
-use crate::buttonlike::{MouseMotionDirection, MouseWheelDirection};
+use crate::buttonlike::{AnalogButton, MouseMotionDirection, MouseWheelDirection};
+use crate::cursor_world::{CursorRelative, CursorWorldSource};
+#[cfg(feature = "gamepad_extras")]
+use crate::gamepad_kind::GamepadKind;
+use crate::gamepad_settings::{stick_partner, GamepadSettingsOverride};
+use crate::mouse_gestures::{DragAxis, MouseDrag, MousePressHistory};
 use crate::prelude::DualAxis;
+use crate::screen_edge::ScreenEdge;
+use crate::touch::{DoubleTapTracker, TouchHistory, TouchJoystick};
 use crate::user_input::{InputKind, UserInput};
 
 /// A collection of [`Input`] structs, which can be used to update an [`InputMap`](crate::input_map::InputMap).
@@ -41,8 +54,50 @@ pub struct InputStreams<'a> {
     pub mouse_wheel: Option<Vec<MouseWheel>>,
     /// A [`MouseMotion`] event stream
     pub mouse_motion: Vec<MouseMotion>,
+    /// When each mouse button was pressed, and how far the cursor moved since, used to tell [`MouseClick`](crate::mouse_gestures::MouseClick)s and [`MouseDrag`]s apart
+    ///
+    /// If [`None`], every release of a mouse button is a click, and there are no drags.
+    pub mouse_presses: Option<&'a MousePressHistory>,
+    /// The mouse buttons that were pressed while they were blocked by the UI or by egui, which [`DragAxis`] ignore until they are released
+    ///
+    /// If [`None`], no press is ignored.
+    pub mouse_presses_over_ui: Option<&'a HashSet<MouseButton>>,
+    /// The position of the cursor in the world, read by [`CursorRelative`] inputs
+    pub cursor_world_position: Option<Vec2>,
+    /// The position of the entity with the input map in the world, from which [`CursorRelative`] inputs aim
+    ///
+    /// If [`None`], [`CursorRelative`] inputs read nothing.
+    pub anchor_position: Option<Vec2>,
+    /// The position of the cursor in the primary window, in logical pixels from its top-left corner, read by [`ScreenEdge`] inputs
+    ///
+    /// [`None`] while the cursor is outside of the window, or the window is unfocused.
+    pub cursor_window_position: Option<Vec2>,
+    /// The size of the primary window, in logical pixels
+    pub window_size: Option<Vec2>,
+    /// How the cursor is grabbed by the primary window, which decides the bindings read with a [`CursorGrabCondition`](crate::input_map::CursorGrabCondition)
+    ///
+    /// This is [`CursorGrabMode::None`] if there is no primary window.
+    pub cursor_grab_mode: CursorGrabMode,
+    /// The state of every finger on the touch screen
+    pub touches: Option<&'a Touches>,
+    /// When each finger touched the screen, used to recognize [`TouchGesture`](crate::touch::TouchGesture)s
+    ///
+    /// If [`None`], every finger is treated as if it just touched the screen.
+    pub touch_history: Option<&'a TouchHistory>,
+    /// The regions of the [`TouchJoystick`]s bound in the input map, whose fingers don't swipe
+    pub touch_joystick_regions: &'a [Rect],
+    /// The first taps of the [`TouchGesture::DoubleTap`](crate::touch::TouchGesture::DoubleTap)s bound in the input map, and the single taps pressed on this frame
+    ///
+    /// If [`None`], taps are never delayed, and double taps are never performed.
+    pub double_taps: Option<&'a DoubleTapTracker>,
     /// The [`Gamepad`] that this struct will detect inputs from
     pub associated_gamepad: Option<Gamepad>,
+    /// The settings that adjust the values read from each gamepad, if any
+    pub gamepad_settings: Option<&'a GamepadSettingsOverride>,
+    /// The [`AnalogButton`]s that were pressed until now, which stay pressed until they fall to their release threshold
+    ///
+    /// If [`None`], [`AnalogButton`]s are only pressed from their press threshold.
+    pub held_analog_buttons: Option<&'a HashSet<AnalogButton>>,
 }
 
 // Constructors
@@ -58,6 +113,17 @@ impl<'a> InputStreams<'a> {
         let mouse_buttons = world.get_resource::<Input<MouseButton>>();
         let mouse_wheel = world.resource::<Events<MouseWheel>>();
         let mouse_motion = world.resource::<Events<MouseMotion>>();
+        let gamepad_settings = world.get_resource::<GamepadSettingsOverride>();
+        let mouse_presses = world.get_resource::<MousePressHistory>();
+        let cursor_world_position = world
+            .get_resource::<CursorWorldSource>()
+            .and_then(|source| source.0);
+        let touches = world.get_resource::<Touches>();
+        let touch_history = world.get_resource::<TouchHistory>();
+        let primary_window = world
+            .iter_entities()
+            .find(|entity| entity.contains::<PrimaryWindow>())
+            .and_then(|entity| entity.get::<Window>());
 
         let mouse_wheel: Vec<MouseWheel> = mouse_wheel
             .get_reader()
@@ -80,7 +146,23 @@ impl<'a> InputStreams<'a> {
             mouse_buttons,
             mouse_wheel: Some(mouse_wheel),
             mouse_motion,
+            mouse_presses,
+            mouse_presses_over_ui: None,
+            cursor_world_position,
+            anchor_position: None,
+            cursor_window_position: primary_window
+                .filter(|window| window.focused)
+                .and_then(Window::cursor_position),
+            window_size: primary_window.map(|window| Vec2::new(window.width(), window.height())),
+            cursor_grab_mode: primary_window
+                .map_or(CursorGrabMode::None, |window| window.cursor.grab_mode),
+            touches,
+            touch_history,
+            touch_joystick_regions: &[],
+            double_taps: None,
             associated_gamepad: gamepad,
+            gamepad_settings,
+            held_analog_buttons: None,
         }
     }
 }
@@ -97,6 +179,7 @@ impl<'a> InputStreams<'a> {
                 down,
                 left,
                 right,
+                ..
             }) => {
                 for button in [up, down, left, right] {
                     if self.button_pressed(*button) {
@@ -108,6 +191,10 @@ impl<'a> InputStreams<'a> {
             UserInput::VirtualAxis(VirtualAxis { negative, positive }) => {
                 self.button_pressed(*negative) || self.button_pressed(*positive)
             }
+            UserInput::EitherDualAxis(EitherDualAxis { first, second, .. }) => {
+                self.button_pressed(InputKind::DualAxis(*first))
+                    || self.button_pressed(InputKind::DualAxis(*second))
+            }
         }
     }
 
@@ -142,18 +229,15 @@ impl<'a> InputStreams<'a> {
 
                 value < axis.negative_low || value > axis.positive_low
             }
+            InputKind::TripleAxis(axis) => [axis.x, axis.y, axis.z]
+                .into_iter()
+                .any(|single_axis| self.button_pressed(InputKind::SingleAxis(single_axis))),
             InputKind::GamepadButton(gamepad_button) => {
                 if let Some(gamepad) = self.associated_gamepad {
-                    self.gamepad_buttons.pressed(GamepadButton {
-                        gamepad,
-                        button_type: gamepad_button,
-                    })
+                    self.gamepad_button_pressed(gamepad, gamepad_button)
                 } else {
                     for gamepad in self.gamepads.iter() {
-                        if self.gamepad_buttons.pressed(GamepadButton {
-                            gamepad,
-                            button_type: gamepad_button,
-                        }) {
+                        if self.gamepad_button_pressed(gamepad, gamepad_button) {
                             // Return early if *any* gamepad is pressing this button
                             return true;
                         }
@@ -163,6 +247,28 @@ impl<'a> InputStreams<'a> {
                     false
                 }
             }
+            #[cfg(feature = "gamepad_extras")]
+            InputKind::GamepadExtra(extra) => {
+                let pressed = |gamepad| {
+                    GamepadKind::of(gamepad, self.gamepads)
+                        .and_then(|kind| extra.button_type(kind))
+                        .is_some_and(|button_type| {
+                            self.gamepad_button_pressed(gamepad, button_type)
+                        })
+                };
+
+                match self.associated_gamepad {
+                    Some(gamepad) => pressed(gamepad),
+                    None => self.gamepads.iter().any(pressed),
+                }
+            }
+            InputKind::AnalogButton(analog_button) => {
+                let held = self.held_analog_buttons.is_some_and(|held_analog_buttons| {
+                    held_analog_buttons.contains(&analog_button)
+                });
+
+                analog_button.pressed(self.analog_button_value(analog_button.button_type), held)
+            }
             InputKind::Keyboard(keycode) => {
                 matches!(self.keycodes, Some(keycodes) if keycodes.pressed(keycode))
             }
@@ -177,6 +283,19 @@ impl<'a> InputStreams<'a> {
             InputKind::Mouse(mouse_button) => {
                 matches!(self.mouse_buttons, Some(mouse_buttons) if mouse_buttons.pressed(mouse_button))
             }
+            InputKind::MouseClick(click) => self
+                .mouse_buttons
+                .is_some_and(|mouse_buttons| click.performed(mouse_buttons, self.mouse_presses)),
+            InputKind::MouseDrag(drag) => self.mouse_drag_data(&drag).is_some(),
+            InputKind::DragAxis(drag_axis) => self
+                .drag_axis_data(&drag_axis)
+                .is_some_and(|axis_pair| axis_pair.xy() != Vec2::ZERO),
+            InputKind::CursorRelative(cursor_relative) => self
+                .cursor_relative_data(&cursor_relative)
+                .is_some_and(|axis_pair| axis_pair.xy() != Vec2::ZERO),
+            InputKind::ScreenEdge(screen_edge) => self
+                .screen_edge_data(&screen_edge)
+                .is_some_and(|axis_pair| axis_pair.xy() != Vec2::ZERO),
             InputKind::MouseWheel(mouse_wheel_direction) => {
                 let Some(mouse_wheel) = &self.mouse_wheel else {
                     return false;
@@ -229,6 +348,25 @@ impl<'a> InputStreams<'a> {
                     }
                 }
             }
+            InputKind::Touch(gesture) => self.touches.is_some_and(|touches| {
+                gesture.performed(
+                    touches,
+                    self.touch_history,
+                    None,
+                    self.touch_joystick_regions,
+                    self.double_taps,
+                )
+            }),
+            InputKind::TouchWithin(gesture, region) => self.touches.is_some_and(|touches| {
+                gesture.performed(
+                    touches,
+                    self.touch_history,
+                    Some(region),
+                    self.touch_joystick_regions,
+                    self.double_taps,
+                )
+            }),
+            InputKind::TouchJoystick(joystick) => self.touch_joystick_data(&joystick).is_some(),
         }
     }
 
@@ -250,6 +388,10 @@ impl<'a> InputStreams<'a> {
     /// For binary inputs such as buttons, this will always be either `0.0` or `1.0`. For analog
     /// inputs such as axes, this will be the axis value.
     ///
+    /// Gamepad buttons are read from [`Axis<GamepadButton>`], so pressure-sensitive buttons such as triggers
+    /// report how far they are pressed, whether or not they are pressed.
+    /// They fall back to `0.0` or `1.0` if the gamepad doesn't report their value.
+    ///
     /// [`UserInput::Chord`] inputs are also considered binary and will return `0.0` or `1.0` based
     /// on whether the chord has been pressed.
     ///
@@ -293,18 +435,12 @@ impl<'a> InputStreams<'a> {
                 match single_axis.axis_type {
                     AxisType::Gamepad(axis_type) => {
                         if let Some(gamepad) = self.associated_gamepad {
-                            let value = self
-                                .gamepad_axes
-                                .get(GamepadAxis { gamepad, axis_type })
-                                .unwrap_or_default();
+                            let value = self.gamepad_axis_value(gamepad, axis_type);
 
                             value_in_axis_range(single_axis, value)
                         } else {
                             for gamepad in self.gamepads.iter() {
-                                let value = self
-                                    .gamepad_axes
-                                    .get(GamepadAxis { gamepad, axis_type })
-                                    .unwrap_or_default();
+                                let value = self.gamepad_axis_value(gamepad, axis_type);
 
                                 // Return early if *any* gamepad is pressing this axis
                                 if value != 0.0 {
@@ -343,16 +479,27 @@ impl<'a> InputStreams<'a> {
                         }
                         value_in_axis_range(single_axis, total_mouse_motion_movement)
                     }
+                    AxisType::Pen(axis_type) => {
+                        value_in_axis_range(single_axis, self.pen_axis_value(axis_type))
+                    }
                 }
             }
             UserInput::VirtualAxis(VirtualAxis { negative, positive }) => {
                 self.input_value(&UserInput::Single(*positive), true).abs()
                     - self.input_value(&UserInput::Single(*negative), true).abs()
             }
-            UserInput::Single(InputKind::DualAxis(_)) => {
-                self.input_axis_pair(input).unwrap_or_default().length()
+            UserInput::Single(
+                InputKind::DualAxis(_)
+                | InputKind::TouchJoystick(_)
+                | InputKind::MouseDrag(_)
+                | InputKind::DragAxis(_)
+                | InputKind::CursorRelative(_)
+                | InputKind::ScreenEdge(_),
+            ) => self.input_axis_pair(input).unwrap_or_default().length(),
+            UserInput::Single(InputKind::TripleAxis(_)) => {
+                self.input_axis_triple(input).unwrap_or_default().length()
             }
-            UserInput::VirtualDPad { .. } => {
+            UserInput::VirtualDPad { .. } | UserInput::EitherDualAxis { .. } => {
                 self.input_axis_pair(input).unwrap_or_default().length()
             }
             UserInput::Chord(inputs) => {
@@ -377,6 +524,10 @@ impl<'a> InputStreams<'a> {
                                 true,
                             )
                         }
+                        InputKind::TripleAxis(axis) => {
+                            has_axis = true;
+                            self.extract_triple_axis_data(axis).length()
+                        }
                         _ => 0.0,
                     }
                 }
@@ -417,6 +568,29 @@ impl<'a> InputStreams<'a> {
                     0.0
                 }
             }
+            UserInput::Single(InputKind::AnalogButton(analog_button)) => {
+                self.analog_button_value(analog_button.button_type)
+            }
+            UserInput::Single(InputKind::Touch(gesture)) => self.touches.map_or(0.0, |touches| {
+                gesture.value(
+                    touches,
+                    self.touch_history,
+                    None,
+                    self.touch_joystick_regions,
+                    self.double_taps,
+                )
+            }),
+            UserInput::Single(InputKind::TouchWithin(gesture, region)) => {
+                self.touches.map_or(0.0, |touches| {
+                    gesture.value(
+                        touches,
+                        self.touch_history,
+                        Some(*region),
+                        self.touch_joystick_regions,
+                        self.double_taps,
+                    )
+                })
+            }
             _ => use_button_value(),
         }
     }
@@ -425,7 +599,9 @@ impl<'a> InputStreams<'a> {
     ///
     /// If `input` is a chord, returns result of the first dual axis in the chord.
 
-    /// If `input` is not a [`DualAxis`] or [`VirtualDPad`], returns [`None`].
+    /// If `input` is an [`EitherDualAxis`], returns the data of the [`DualAxis`] picked by its policy.
+    ///
+    /// If `input` is not a [`DualAxis`], [`TouchJoystick`], [`MouseDrag`], [`DragAxis`], [`CursorRelative`], [`ScreenEdge`], [`VirtualDPad`] or [`EitherDualAxis`], returns [`None`].
     ///
     /// # Warning
     ///
@@ -441,8 +617,26 @@ impl<'a> InputStreams<'a> {
                     }
 
                     // Return result of the first dual axis in the chord.
-                    if let InputKind::DualAxis(dual_axis) = input_kind {
-                        return Some(self.extract_dual_axis_data(dual_axis).unwrap_or_default());
+                    match input_kind {
+                        InputKind::DualAxis(dual_axis) => {
+                            return Some(
+                                self.extract_dual_axis_data(dual_axis).unwrap_or_default(),
+                            );
+                        }
+                        InputKind::TouchJoystick(joystick) => {
+                            return Some(self.touch_joystick_data(joystick).unwrap_or_default());
+                        }
+                        InputKind::MouseDrag(drag) => {
+                            return Some(self.mouse_drag_data(drag).unwrap_or_default());
+                        }
+                        InputKind::DragAxis(drag_axis) => return self.drag_axis_data(drag_axis),
+                        InputKind::CursorRelative(cursor_relative) => {
+                            return self.cursor_relative_data(cursor_relative);
+                        }
+                        InputKind::ScreenEdge(screen_edge) => {
+                            return self.screen_edge_data(screen_edge);
+                        }
+                        _ => (),
                     }
                 }
                 None
@@ -450,22 +644,140 @@ impl<'a> InputStreams<'a> {
             UserInput::Single(InputKind::DualAxis(dual_axis)) => {
                 Some(self.extract_dual_axis_data(dual_axis).unwrap_or_default())
             }
+            UserInput::Single(InputKind::TouchJoystick(joystick)) => {
+                Some(self.touch_joystick_data(joystick).unwrap_or_default())
+            }
+            UserInput::Single(InputKind::MouseDrag(drag)) => {
+                Some(self.mouse_drag_data(drag).unwrap_or_default())
+            }
+            UserInput::Single(InputKind::DragAxis(drag_axis)) => self.drag_axis_data(drag_axis),
+            UserInput::Single(InputKind::CursorRelative(cursor_relative)) => {
+                self.cursor_relative_data(cursor_relative)
+            }
+            UserInput::Single(InputKind::ScreenEdge(screen_edge)) => {
+                self.screen_edge_data(screen_edge)
+            }
             UserInput::VirtualDPad(VirtualDPad {
                 up,
                 down,
                 left,
                 right,
+                normalize_diagonals,
             }) => {
                 let x = self.input_value(&UserInput::Single(*right), true).abs()
                     - self.input_value(&UserInput::Single(*left), true).abs();
                 let y = self.input_value(&UserInput::Single(*up), true).abs()
                     - self.input_value(&UserInput::Single(*down), true).abs();
-                Some(DualAxisData::new(x, y))
+                let mut xy = Vec2::new(x, y);
+                if *normalize_diagonals {
+                    xy = xy.clamp_length_max(1.0);
+                }
+                Some(DualAxisData::from_xy(xy))
+            }
+            UserInput::EitherDualAxis(either) => Some(
+                either
+                    .pick(
+                        self.extract_dual_axis_data(&either.first),
+                        self.extract_dual_axis_data(&either.second),
+                    )
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Get the axis triple associated to the user input.
+    ///
+    /// If `input` is a chord, returns the data of the first [`TripleAxis`] in the chord while its other inputs are pressed.
+    ///
+    /// If `input` does not contain a [`TripleAxis`], returns [`None`].
+    pub fn input_axis_triple(&self, input: &UserInput) -> Option<TripleAxisData> {
+        match input {
+            UserInput::Chord(inputs) => {
+                let mut triple_axis = None;
+                for input_kind in inputs.iter() {
+                    match input_kind {
+                        InputKind::TripleAxis(axis) => {
+                            triple_axis.get_or_insert(axis);
+                        }
+                        // The other inputs gate the triple axis, which is only read while they are all pressed
+                        _ if !self.button_pressed(*input_kind) => return None,
+                        _ => (),
+                    }
+                }
+                triple_axis.map(|axis| self.extract_triple_axis_data(axis))
+            }
+            UserInput::Single(InputKind::TripleAxis(axis)) => {
+                Some(self.extract_triple_axis_data(axis))
             }
             _ => None,
         }
     }
 
+    /// Is the `button_type` of the `gamepad` pressed, according to the [`GamepadSettingsOverride`] if any?
+    fn gamepad_button_pressed(&self, gamepad: Gamepad, button_type: GamepadButtonType) -> bool {
+        let button = GamepadButton {
+            gamepad,
+            button_type,
+        };
+
+        self.gamepad_settings
+            .and_then(|settings| {
+                let value = self.gamepad_button_axes.get(button).unwrap_or_default();
+                settings
+                    .preferences(gamepad)
+                    .button_pressed(button_type, value)
+            })
+            .unwrap_or_else(|| self.gamepad_buttons.pressed(button))
+    }
+
+    /// The analog value of the `button_type`, read from the associated gamepad or from any gamepad that is pressing it
+    fn analog_button_value(&self, button_type: GamepadButtonType) -> f32 {
+        let read = |gamepad| {
+            self.gamepad_button_axes
+                .get(GamepadButton {
+                    gamepad,
+                    button_type,
+                })
+                .unwrap_or_default()
+        };
+
+        if let Some(gamepad) = self.associated_gamepad {
+            read(gamepad)
+        } else {
+            self.gamepads
+                .iter()
+                .map(read)
+                .find(|&value| value != 0.0)
+                .unwrap_or_default()
+        }
+    }
+
+    /// The value of the `axis_type` of the `gamepad`, calibrated and adjusted by the [`GamepadSettingsOverride`] if any
+    fn gamepad_axis_value(&self, gamepad: Gamepad, axis_type: GamepadAxisType) -> f32 {
+        let read = |axis_type| {
+            self.gamepad_axes
+                .get(GamepadAxis { gamepad, axis_type })
+                .unwrap_or_default()
+        };
+
+        let Some(settings) = self.gamepad_settings else {
+            return read(axis_type);
+        };
+        let calibration = settings.calibration(gamepad, self.gamepads);
+        let read = |axis_type| {
+            let value = read(axis_type);
+            calibration.map_or(value, |calibration| calibration.calibrate(axis_type, value))
+        };
+
+        let value = read(axis_type);
+        let other_value = stick_partner(axis_type).map(read).unwrap_or_default();
+
+        settings
+            .preferences(gamepad)
+            .adjust_axis_value(axis_type, value, other_value)
+    }
+
     fn extract_dual_axis_data(&self, dual_axis: &DualAxis) -> Option<DualAxisData> {
         let x = self.input_value(
             &UserInput::Single(InputKind::SingleAxis(dual_axis.x)),
@@ -478,6 +790,62 @@ impl<'a> InputStreams<'a> {
 
         dual_axis.deadzone.deadzone_input_value(x, y)
     }
+
+    /// The position of the `joystick`, or [`None`] if it isn't anchored by any finger, or is within its deadzone
+    fn touch_joystick_data(&self, joystick: &TouchJoystick) -> Option<DualAxisData> {
+        joystick.axis_pair(self.touches?, self.touch_history?)
+    }
+
+    /// How far the cursor was dragged by the `drag`, or [`None`] if it isn't performed
+    fn mouse_drag_data(&self, drag: &MouseDrag) -> Option<DualAxisData> {
+        drag.axis_pair(self.mouse_buttons?, self.mouse_presses)
+    }
+
+    /// The mouse motion read by the `drag_axis`, or [`None`] if its button isn't held, or its press started over the UI
+    fn drag_axis_data(&self, drag_axis: &DragAxis) -> Option<DualAxisData> {
+        let over_ui = self
+            .mouse_presses_over_ui
+            .is_some_and(|presses| presses.contains(&drag_axis.button));
+        if over_ui {
+            return None;
+        }
+
+        drag_axis.axis_pair(self.mouse_buttons?, &self.mouse_motion)
+    }
+
+    /// The direction from the anchor to the cursor read by the `cursor_relative`, or [`None`] if either position isn't known
+    fn cursor_relative_data(&self, cursor_relative: &CursorRelative) -> Option<DualAxisData> {
+        Some(cursor_relative.axis_pair(self.cursor_world_position?, self.anchor_position?))
+    }
+
+    /// The edges of the window near the cursor read by the `screen_edge`, or [`None`] if the cursor isn't in the window
+    fn screen_edge_data(&self, screen_edge: &ScreenEdge) -> Option<DualAxisData> {
+        Some(screen_edge.axis_pair(self.cursor_window_position?, self.window_size?))
+    }
+
+    /// The `axis_type` of the pressed touch with the most force, which is assumed to be the pen
+    ///
+    /// This is `0.0` if no pressed touch reports its force.
+    fn pen_axis_value(&self, axis_type: PenAxisType) -> f32 {
+        let Some(touches) = self.touches else {
+            return 0.0;
+        };
+
+        touches
+            .iter()
+            .filter_map(Touch::force)
+            .max_by_key(|force| FloatOrd(PenAxisType::Pressure.value(*force)))
+            .map_or(0.0, |force| axis_type.value(force))
+    }
+
+    /// Reads each axis of the `triple_axis`, using its own deadzone
+    fn extract_triple_axis_data(&self, triple_axis: &TripleAxis) -> TripleAxisData {
+        let [x, y, z] = [triple_axis.x, triple_axis.y, triple_axis.z].map(|single_axis| {
+            self.input_value(&UserInput::Single(InputKind::SingleAxis(single_axis)), true)
+        });
+
+        TripleAxisData::new(x, y, z)
+    }
 }
 
 /// A mutable collection of [`Input`] structs, which can be used for mocking user inputs.
@@ -513,6 +881,11 @@ pub struct MutableInputStreams<'a> {
     /// A [`MouseMotion`] event stream
     pub mouse_motion: &'a mut Events<MouseMotion>,
 
+    /// The state of every finger on the touch screen
+    pub touches: &'a mut Touches,
+    /// Events used for mocking touch inputs
+    pub touch_events: &'a mut Events<TouchInput>,
+
     /// The [`Gamepad`] that this struct will detect inputs from
     pub associated_gamepad: Option<Gamepad>,
 }
@@ -533,6 +906,8 @@ impl<'a> MutableInputStreams<'a> {
             ResMut<Events<MouseButtonInput>>,
             ResMut<Events<MouseWheel>>,
             ResMut<Events<MouseMotion>>,
+            ResMut<Touches>,
+            ResMut<Events<TouchInput>>,
         )> = SystemState::new(world);
 
         let (
@@ -548,6 +923,8 @@ impl<'a> MutableInputStreams<'a> {
             mouse_button_events,
             mouse_wheel,
             mouse_motion,
+            touches,
+            touch_events,
         ) = input_system_state.get_mut(world);
 
         MutableInputStreams {
@@ -563,6 +940,8 @@ impl<'a> MutableInputStreams<'a> {
             mouse_button_events: mouse_button_events.into_inner(),
             mouse_wheel: mouse_wheel.into_inner(),
             mouse_motion: mouse_motion.into_inner(),
+            touches: touches.into_inner(),
+            touch_events: touch_events.into_inner(),
             associated_gamepad: gamepad,
         }
     }
@@ -603,7 +982,20 @@ impl<'a> From<MutableInputStreams<'a>> for InputStreams<'a> {
                 .read(mutable_streams.mouse_motion)
                 .cloned()
                 .collect(),
+            mouse_presses: None,
+            mouse_presses_over_ui: None,
+            cursor_world_position: None,
+            anchor_position: None,
+            cursor_window_position: None,
+            window_size: None,
+            cursor_grab_mode: CursorGrabMode::None,
+            touches: Some(mutable_streams.touches),
+            touch_history: None,
+            touch_joystick_regions: &[],
+            double_taps: None,
             associated_gamepad: mutable_streams.associated_gamepad,
+            gamepad_settings: None,
+            held_analog_buttons: None,
         }
     }
 }
@@ -632,14 +1024,208 @@ impl<'a> From<&'a MutableInputStreams<'a>> for InputStreams<'a> {
                 .read(mutable_streams.mouse_motion)
                 .cloned()
                 .collect(),
+            mouse_presses: None,
+            mouse_presses_over_ui: None,
+            cursor_world_position: None,
+            anchor_position: None,
+            cursor_window_position: None,
+            window_size: None,
+            cursor_grab_mode: CursorGrabMode::None,
+            touches: Some(mutable_streams.touches),
+            touch_history: None,
+            touch_joystick_regions: &[],
+            double_taps: None,
             associated_gamepad: mutable_streams.associated_gamepad,
+            gamepad_settings: None,
+            held_analog_buttons: None,
+        }
+    }
+}
+
+/// Builds an [`OwnedInputStreams`] by hand, without needing a [`World`]
+///
+/// This is useful for unit testing [`InputMap::which_pressed`](crate::input_map::InputMap::which_pressed)
+/// and other methods that take an [`InputStreams`].
+/// All gamepad inputs are sent to the same [`Gamepad`], which is `Gamepad { id: 0 }` unless changed with [`InputStreamsBuilder::gamepad`].
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::input_streams::{InputStreams, InputStreamsBuilder};
+/// use leafwing_input_manager::input_mocking::QueryInput;
+///
+/// let owned_streams = InputStreamsBuilder::new()
+///     .press_key(KeyCode::W)
+///     .set_axis(GamepadAxisType::LeftStickX, 0.8)
+///     .build();
+/// let input_streams = InputStreams::from(&owned_streams);
+///
+/// assert!(input_streams.pressed(KeyCode::W));
+/// assert!(!input_streams.pressed(KeyCode::S));
+/// ```
+#[derive(Debug, Default)]
+#[must_use]
+pub struct InputStreamsBuilder {
+    streams: OwnedInputStreams,
+}
+
+impl InputStreamsBuilder {
+    /// Creates a builder where no inputs are pressed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [`Gamepad`] that gamepad inputs are sent to and read from
+    ///
+    /// Inputs sent before calling this method stay on the previous gamepad.
+    pub fn gamepad(mut self, gamepad: Gamepad) -> Self {
+        self.streams.gamepad = gamepad;
+        self
+    }
+
+    /// Presses the `key_code`
+    pub fn press_key(mut self, key_code: KeyCode) -> Self {
+        self.streams.keycodes.press(key_code);
+        self
+    }
+
+    /// Presses the key at the physical location of the `scan_code`
+    pub fn press_scan_code(mut self, scan_code: impl Into<ScanCode>) -> Self {
+        self.streams.scan_codes.press(scan_code.into());
+        self
+    }
+
+    /// Presses the `mouse_button`
+    pub fn press_mouse_button(mut self, mouse_button: MouseButton) -> Self {
+        self.streams.mouse_buttons.press(mouse_button);
+        self
+    }
+
+    /// Fully presses the `button_type` on the gamepad
+    pub fn press_gamepad_button(self, button_type: GamepadButtonType) -> Self {
+        self.set_button_value(button_type, 1.0)
+    }
+
+    /// Sets the analog value of the `button_type` on the gamepad, such as for triggers
+    ///
+    /// The button is pressed if the `value` is not zero.
+    pub fn set_button_value(mut self, button_type: GamepadButtonType, value: f32) -> Self {
+        let button = GamepadButton::new(self.streams.gamepad, button_type);
+        self.streams.gamepad_button_axes.set(button, value);
+        if value != 0.0 {
+            self.streams.gamepad_buttons.press(button);
+        }
+        self
+    }
+
+    /// Sets the `value` of the `axis_type` on the gamepad
+    pub fn set_axis(mut self, axis_type: GamepadAxisType, value: f32) -> Self {
+        let axis = GamepadAxis::new(self.streams.gamepad, axis_type);
+        self.streams.gamepad_axes.set(axis, value);
+        self
+    }
+
+    /// Scrolls the mouse wheel by the `delta`, in pixels
+    pub fn mouse_wheel(mut self, delta: Vec2) -> Self {
+        self.streams.mouse_wheel.push(MouseWheel {
+            unit: MouseScrollUnit::Pixel,
+            x: delta.x,
+            y: delta.y,
+            window: Entity::PLACEHOLDER,
+        });
+        self
+    }
+
+    /// Moves the mouse by the `delta`
+    pub fn mouse_motion(mut self, delta: Vec2) -> Self {
+        self.streams.mouse_motion.push(MouseMotion { delta });
+        self
+    }
+
+    /// Finishes building the [`OwnedInputStreams`]
+    pub fn build(self) -> OwnedInputStreams {
+        self.streams
+    }
+}
+
+/// The input data read by an [`InputStreams`], owned rather than borrowed from a [`World`]
+///
+/// Created using an [`InputStreamsBuilder`], and read by converting a reference to it into an [`InputStreams`].
+/// No [`Gamepads`] are registered: the [`InputStreams`] reads gamepad inputs from its associated gamepad instead.
+#[derive(Debug)]
+pub struct OwnedInputStreams {
+    /// A [`GamepadButton`] [`Input`] stream
+    pub gamepad_buttons: Input<GamepadButton>,
+    /// A [`GamepadButton`] [`Axis`] stream
+    pub gamepad_button_axes: Axis<GamepadButton>,
+    /// A [`GamepadAxis`] [`Axis`] stream
+    pub gamepad_axes: Axis<GamepadAxis>,
+    /// A list of registered gamepads, which is always empty
+    pub gamepads: Gamepads,
+    /// A [`KeyCode`] [`Input`] stream
+    pub keycodes: Input<KeyCode>,
+    /// A [`ScanCode`] [`Input`] stream
+    pub scan_codes: Input<ScanCode>,
+    /// A [`MouseButton`] [`Input`] stream
+    pub mouse_buttons: Input<MouseButton>,
+    /// A [`MouseWheel`] event stream
+    pub mouse_wheel: Vec<MouseWheel>,
+    /// A [`MouseMotion`] event stream
+    pub mouse_motion: Vec<MouseMotion>,
+    /// The [`Gamepad`] that gamepad inputs are sent to and read from
+    pub gamepad: Gamepad,
+}
+
+impl Default for OwnedInputStreams {
+    fn default() -> Self {
+        Self {
+            gamepad_buttons: Input::default(),
+            gamepad_button_axes: Axis::default(),
+            gamepad_axes: Axis::default(),
+            gamepads: Gamepads::default(),
+            keycodes: Input::default(),
+            scan_codes: Input::default(),
+            mouse_buttons: Input::default(),
+            mouse_wheel: Vec::new(),
+            mouse_motion: Vec::new(),
+            gamepad: Gamepad { id: 0 },
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedInputStreams> for InputStreams<'a> {
+    fn from(owned_streams: &'a OwnedInputStreams) -> Self {
+        InputStreams {
+            gamepad_buttons: &owned_streams.gamepad_buttons,
+            gamepad_button_axes: &owned_streams.gamepad_button_axes,
+            gamepad_axes: &owned_streams.gamepad_axes,
+            gamepads: &owned_streams.gamepads,
+            keycodes: Some(&owned_streams.keycodes),
+            scan_codes: Some(&owned_streams.scan_codes),
+            mouse_buttons: Some(&owned_streams.mouse_buttons),
+            mouse_wheel: Some(owned_streams.mouse_wheel.clone()),
+            mouse_motion: owned_streams.mouse_motion.clone(),
+            mouse_presses: None,
+            mouse_presses_over_ui: None,
+            cursor_world_position: None,
+            anchor_position: None,
+            cursor_window_position: None,
+            window_size: None,
+            cursor_grab_mode: CursorGrabMode::None,
+            touches: None,
+            touch_history: None,
+            touch_joystick_regions: &[],
+            double_taps: None,
+            associated_gamepad: Some(owned_streams.gamepad),
+            gamepad_settings: None,
+            held_analog_buttons: None,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{InputStreams, MutableInputStreams};
+    use super::{InputStreams, InputStreamsBuilder, MutableInputStreams};
     use crate::prelude::{MockInput, QueryInput};
     use bevy::input::InputPlugin;
     use bevy::prelude::*;
@@ -671,4 +1257,47 @@ mod tests {
         let input_streams = MutableInputStreams::from_world(&mut app.world, None);
         assert!(InputStreams::from(&input_streams).pressed(Modifier::Control));
     }
+
+    #[test]
+    fn built_streams_cover_every_input() {
+        use crate::axislike::{DualAxis, SingleAxis};
+        use crate::buttonlike::MouseMotionDirection;
+        use crate::prelude::MouseWheelDirection;
+
+        let gamepad = Gamepad { id: 3 };
+        let owned_streams = InputStreamsBuilder::new()
+            .gamepad(gamepad)
+            .press_key(KeyCode::W)
+            .press_scan_code(crate::prelude::QwertyScanCode::S)
+            .press_mouse_button(MouseButton::Right)
+            .press_gamepad_button(GamepadButtonType::South)
+            .set_button_value(GamepadButtonType::RightTrigger2, 0.4)
+            .set_axis(GamepadAxisType::LeftStickX, 0.8)
+            .mouse_wheel(Vec2::new(0.0, 2.0))
+            .mouse_motion(Vec2::new(-3.0, 0.0))
+            .build();
+        let input_streams = InputStreams::from(&owned_streams);
+        assert_eq!(input_streams.associated_gamepad, Some(gamepad));
+
+        assert!(input_streams.pressed(KeyCode::W));
+        assert!(input_streams.pressed(crate::prelude::QwertyScanCode::S));
+        assert!(input_streams.pressed(MouseButton::Right));
+        assert!(input_streams.pressed(GamepadButtonType::South));
+        assert!(input_streams.pressed(GamepadButtonType::RightTrigger2));
+        assert_eq!(
+            input_streams.input_value(&GamepadButtonType::RightTrigger2.into(), false),
+            0.4
+        );
+        assert_eq!(
+            input_streams.input_value(
+                &SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0).into(),
+                false
+            ),
+            0.8
+        );
+        assert!(input_streams.pressed(MouseWheelDirection::Up));
+        assert!(input_streams.pressed(MouseMotionDirection::Left));
+        assert!(!input_streams.pressed(DualAxis::right_stick()));
+        assert!(!input_streams.pressed(KeyCode::S));
+    }
 }
@@ -9,23 +9,54 @@ use bevy::ecs::prelude::*;
 use bevy::reflect::{FromReflect, Reflect, TypePath};
 use std::hash::Hash;
 
+pub mod accessibility;
 pub mod action_diff;
 pub mod action_driver;
 pub mod action_state;
 pub mod axislike;
 pub mod buttonlike;
+pub mod charges;
 pub mod clashing_inputs;
 pub mod common_conditions;
+pub mod cooldowns;
+pub mod cursor_world;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+#[cfg(feature = "diff_stats")]
+pub mod diff_stats;
+#[cfg(feature = "diff_transport")]
+pub mod diff_transport;
 mod display_impl;
 pub mod errors;
+pub mod gamepad_aliases;
+pub mod gamepad_assignment;
+pub mod gamepad_calibration;
+#[cfg(feature = "gamepad_extras")]
+pub mod gamepad_extras;
+pub mod gamepad_kind;
+pub mod gamepad_settings;
+pub mod input_capture;
 pub mod input_map;
+pub mod input_map_stack;
 pub mod input_mocking;
+pub mod input_recording;
 pub mod input_streams;
+pub mod mouse_gestures;
 pub mod orientation;
 pub mod plugin;
+#[cfg(feature = "rumble")]
+pub mod rumble;
 pub mod scan_codes;
+pub mod screen_edge;
+#[cfg(any(test, feature = "script_recording"))]
+pub mod script_recording;
 pub mod systems;
+#[cfg(any(test, feature = "test_utils"))]
+pub mod test_utils;
 pub mod timing;
+pub mod touch;
+#[cfg(feature = "ui")]
+pub mod ui_blocking;
 pub mod user_input;
 
 // Importing the derive macro
@@ -34,22 +65,25 @@ pub use leafwing_input_manager_macros::Actionlike;
 /// Everything you need to get started
 pub mod prelude {
     pub use crate::action_driver::ActionStateDriver;
-    pub use crate::action_state::ActionState;
+    pub use crate::action_state::{
+        ActionState, ActionStateSource, FixedActionState, PausedInputs, PrimaryInputSource,
+    };
     pub use crate::axislike::{
-        DeadZoneShape, DualAxis, MouseWheelAxisType, SingleAxis, VirtualAxis, VirtualDPad,
+        DeadZoneShape, DualAxis, DualAxisPolicy, EitherDualAxis, MouseWheelAxisType, SingleAxis,
+        TripleAxis, VirtualAxis, VirtualDPad,
     };
-    pub use crate::buttonlike::MouseWheelDirection;
+    pub use crate::buttonlike::{AnalogButton, MouseWheelDirection};
     pub use crate::clashing_inputs::ClashStrategy;
     pub use crate::input_map::InputMap;
     #[cfg(feature = "ui")]
     pub use crate::input_mocking::MockUIInteraction;
-    pub use crate::input_mocking::{MockInput, QueryInput};
+    pub use crate::input_mocking::{MockInput, MockTime, QueryInput, RunInputScript};
     pub use crate::scan_codes::QwertyScanCode;
     pub use crate::user_input::{Modifier, UserInput};
 
     pub use crate::plugin::InputManagerPlugin;
     pub use crate::plugin::ToggleActions;
-    pub use crate::{Actionlike, InputManagerBundle};
+    pub use crate::{Actionlike, InputManagerBundle, InsertInputManagerExt};
 }
 
 /// Allows a type to be used as a gameplay action in an input-agnostic fashion
@@ -109,3 +143,54 @@ impl<A: Actionlike> Default for InputManagerBundle<A> {
         }
     }
 }
+
+impl<A: Actionlike> InputManagerBundle<A> {
+    /// Creates a bundle with the provided `input_map` and a default [`ActionState`]
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Jump,
+    /// }
+    ///
+    /// let bundle = InputManagerBundle::with_map(InputMap::new([(Action::Jump, KeyCode::Space)]));
+    /// assert!(bundle.input_map.get(&Action::Jump).is_some());
+    /// ```
+    #[must_use]
+    pub fn with_map(input_map: InputMap<A>) -> Self {
+        Self {
+            input_map,
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the [`ActionState`] of this bundle, such as to start with some actions consumed
+    #[must_use]
+    pub fn with_state(mut self, action_state: ActionState<A>) -> Self {
+        self.action_state = action_state;
+        self
+    }
+
+    /// Replaces the [`InputMap`] of this bundle
+    #[must_use]
+    pub fn with_input_map(mut self, input_map: InputMap<A>) -> Self {
+        self.input_map = input_map;
+        self
+    }
+}
+
+/// Inserts an [`InputManagerBundle`] using [`EntityCommands`](bevy::ecs::system::EntityCommands)
+pub trait InsertInputManagerExt {
+    /// Inserts an [`InputManagerBundle`] with the provided `input_map` and a default [`ActionState`] on this entity
+    fn insert_input_manager<A: Actionlike>(&mut self, input_map: InputMap<A>) -> &mut Self;
+}
+
+impl InsertInputManagerExt for bevy::ecs::system::EntityCommands<'_, '_, '_> {
+    fn insert_input_manager<A: Actionlike>(&mut self, input_map: InputMap<A>) -> &mut Self {
+        self.insert(InputManagerBundle::with_map(input_map))
+    }
+}
@@ -0,0 +1,313 @@
+//! Mouse clicks and drags, told apart by how far the cursor moves while a mouse button is held
+//!
+//! Binding a [`MouseButton`] directly presses the action as soon as the button is pressed,
+//! before it is known whether the player is clicking or starting to drag.
+//! Bind a [`MouseClick`] and a [`MouseDrag`] of the same button instead, with [`InputKind::MouseClick`](crate::user_input::InputKind::MouseClick) and [`InputKind::MouseDrag`](crate::user_input::InputKind::MouseDrag),
+//! and each press of the button will perform one or the other, never both.
+//! Presses are tracked in the [`MousePressHistory`] resource, which is kept up to date by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+//!
+//! For controls that follow the cursor while a button is held, like orbiting a camera, bind a [`DragAxis`] instead.
+
+use crate::axislike::DualAxisData;
+
+use bevy::ecs::system::Resource;
+use bevy::input::mouse::{MouseButton, MouseMotion};
+use bevy::input::Input;
+use bevy::math::Vec2;
+use bevy::reflect::Reflect;
+use bevy::utils::{Duration, FloatOrd, HashMap};
+use serde::{Deserialize, Serialize};
+
+/// How far the cursor can move while a mouse button is held for its release to be a [`MouseClick`], and must move for it to be a [`MouseDrag`], by default
+///
+/// In the same units as [`MouseMotion`](bevy::input::mouse::MouseMotion) events.
+pub const DEFAULT_DRAG_THRESHOLD: f32 = 5.0;
+
+/// A mouse button released without moving the cursor much
+///
+/// Pressed on the frame the button is released, if it was held for at most `max_duration`,
+/// and the cursor never moved `max_movement` or more away from where it was when the button was pressed.
+/// Use the same threshold as the [`MouseDrag`] of the same button, so that each press is either a click or a drag.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::{MouseButton, Reflect};
+/// use leafwing_input_manager::mouse_gestures::{MouseClick, MouseDrag};
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+/// enum Action {
+///     Select,
+///     BoxSelect,
+/// }
+///
+/// let input_map = InputMap::new([
+///     (Action::Select, UserInput::from(MouseClick::new(MouseButton::Left))),
+///     (Action::BoxSelect, MouseDrag::new(MouseButton::Left).into()),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct MouseClick {
+    /// The mouse button that is clicked
+    pub button: MouseButton,
+    /// How far the cursor must stay from where it was when the button was pressed
+    pub max_movement: f32,
+    /// The longest the button can be held
+    pub max_duration: Duration,
+}
+
+impl MouseClick {
+    /// The default `max_duration` of a [`MouseClick`]
+    pub const DEFAULT_MAX_DURATION: Duration = Duration::from_millis(500);
+
+    /// Creates a [`MouseClick`] of the `button`, with the [`DEFAULT_DRAG_THRESHOLD`] and [`MouseClick::DEFAULT_MAX_DURATION`]
+    #[must_use]
+    pub const fn new(button: MouseButton) -> MouseClick {
+        MouseClick {
+            button,
+            max_movement: DEFAULT_DRAG_THRESHOLD,
+            max_duration: Self::DEFAULT_MAX_DURATION,
+        }
+    }
+
+    /// Is this click performed on this frame?
+    ///
+    /// Without a [`MousePressHistory`], every release of the button is a click.
+    #[must_use]
+    pub fn performed(
+        &self,
+        mouse_buttons: &Input<MouseButton>,
+        history: Option<&MousePressHistory>,
+    ) -> bool {
+        if !mouse_buttons.just_released(self.button) {
+            return false;
+        }
+
+        let Some(history) = history else {
+            return true;
+        };
+        history.duration(self.button) <= self.max_duration
+            && history.max_distance(self.button) < self.max_movement
+    }
+}
+
+impl Eq for MouseClick {}
+impl std::hash::Hash for MouseClick {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.button.hash(state);
+        FloatOrd(self.max_movement).hash(state);
+        self.max_duration.hash(state);
+    }
+}
+
+/// A mouse button held while moving the cursor
+///
+/// Pressed once the cursor moved `min_movement` away from where it was when the button was pressed,
+/// and stays pressed until the button is released, even if the cursor moves back.
+/// Its axis pair is how far the cursor moved since the button was pressed, in the same units as [`MouseMotion`](bevy::input::mouse::MouseMotion) events.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct MouseDrag {
+    /// The mouse button that is held
+    pub button: MouseButton,
+    /// How far the cursor must move from where it was when the button was pressed
+    pub min_movement: f32,
+}
+
+impl MouseDrag {
+    /// Creates a [`MouseDrag`] of the `button`, with the [`DEFAULT_DRAG_THRESHOLD`]
+    #[must_use]
+    pub const fn new(button: MouseButton) -> MouseDrag {
+        MouseDrag {
+            button,
+            min_movement: DEFAULT_DRAG_THRESHOLD,
+        }
+    }
+
+    /// How far the cursor was dragged since the button was pressed, or [`None`] if this drag isn't performed
+    ///
+    /// Without a [`MousePressHistory`], drags are never performed.
+    #[must_use]
+    pub fn axis_pair(
+        &self,
+        mouse_buttons: &Input<MouseButton>,
+        history: Option<&MousePressHistory>,
+    ) -> Option<DualAxisData> {
+        let history = history?;
+        (mouse_buttons.pressed(self.button)
+            && history.max_distance(self.button) >= self.min_movement)
+            .then(|| DualAxisData::from_xy(history.offset(self.button)))
+    }
+}
+
+impl Eq for MouseDrag {}
+impl std::hash::Hash for MouseDrag {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.button.hash(state);
+        FloatOrd(self.min_movement).hash(state);
+    }
+}
+
+/// The mouse motion on each frame, read like a [`DualAxis`](crate::axislike::DualAxis) only while a mouse button is held
+///
+/// Its axis pair is [`None`] while the button isn't held.
+/// Presses that started while the mouse buttons were blocked by the UI, or by egui, stay with them until the button is released,
+/// so dragging the cursor off a panel doesn't leak into the game.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::{MouseButton, Reflect};
+/// use leafwing_input_manager::mouse_gestures::DragAxis;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+/// enum CameraAction {
+///     Orbit,
+/// }
+///
+/// // Orbit the camera by dragging with the right mouse button
+/// let input_map = InputMap::new([(
+///     CameraAction::Orbit,
+///     DragAxis::new(MouseButton::Right).with_sensitivity(0.01),
+/// )]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct DragAxis {
+    /// The mouse button that must be held
+    pub button: MouseButton,
+    /// How much the axis pair changes for each unit of [`MouseMotion`]
+    pub sensitivity: f32,
+    /// Whether the y axis is inverted
+    ///
+    /// [`MouseMotion`] is positive when the cursor moves down.
+    pub invert_y: bool,
+}
+
+impl DragAxis {
+    /// Creates a [`DragAxis`] read while the `button` is held, with a sensitivity of `1.0`
+    #[must_use]
+    pub const fn new(button: MouseButton) -> DragAxis {
+        DragAxis {
+            button,
+            sensitivity: 1.0,
+            invert_y: false,
+        }
+    }
+
+    /// Returns this [`DragAxis`] with the `sensitivity` set to the specified value
+    #[must_use]
+    pub fn with_sensitivity(mut self, sensitivity: f32) -> DragAxis {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// Returns this [`DragAxis`] with its y axis inverted or not
+    #[must_use]
+    pub fn with_inverted_y(mut self, invert_y: bool) -> DragAxis {
+        self.invert_y = invert_y;
+        self
+    }
+
+    /// The total `mouse_motion` of this frame, or [`None`] if the button isn't held
+    #[must_use]
+    pub fn axis_pair(
+        &self,
+        mouse_buttons: &Input<MouseButton>,
+        mouse_motion: &[MouseMotion],
+    ) -> Option<DualAxisData> {
+        if !mouse_buttons.pressed(self.button) {
+            return None;
+        }
+
+        let mut delta: Vec2 = mouse_motion.iter().map(|event| event.delta).sum();
+        if self.invert_y {
+            delta.y = -delta.y;
+        }
+        Some(DualAxisData::from_xy(delta * self.sensitivity))
+    }
+}
+
+impl Eq for DragAxis {}
+impl std::hash::Hash for DragAxis {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.button.hash(state);
+        FloatOrd(self.sensitivity).hash(state);
+        self.invert_y.hash(state);
+    }
+}
+
+/// When each mouse button was pressed, and how far the cursor moved since
+///
+/// Used to tell [`MouseClick`]s and [`MouseDrag`]s apart.
+/// Updated each frame by the [`track_mouse_presses`](crate::systems::track_mouse_presses) system.
+#[derive(Resource, Default, Clone, Debug, PartialEq)]
+pub struct MousePressHistory {
+    /// The held mouse buttons, and those released on this frame
+    presses: HashMap<MouseButton, MousePress>,
+    /// The time of the last update
+    now: Duration,
+}
+
+impl MousePressHistory {
+    /// Records the mouse buttons that were pressed, forgets those that were released before this frame, and adds the `motion` of the cursor to the held buttons
+    ///
+    /// `now` is the time elapsed since the app started.
+    /// The motion on the frame a button is pressed doesn't count towards its press.
+    pub fn update(&mut self, mouse_buttons: &Input<MouseButton>, motion: Vec2, now: Duration) {
+        self.now = now;
+        self.presses.retain(|&button, _| {
+            mouse_buttons.pressed(button) || mouse_buttons.just_released(button)
+        });
+
+        for (&button, press) in self.presses.iter_mut() {
+            if mouse_buttons.pressed(button) {
+                press.offset += motion;
+                press.max_distance = press.max_distance.max(press.offset.length());
+            }
+        }
+        for &button in mouse_buttons.get_just_pressed() {
+            self.presses.insert(
+                button,
+                MousePress {
+                    started: now,
+                    offset: Vec2::ZERO,
+                    max_distance: 0.0,
+                },
+            );
+        }
+    }
+
+    /// How long the `button` has been held, or zero if it isn't known
+    #[must_use]
+    pub fn duration(&self, button: MouseButton) -> Duration {
+        self.presses.get(&button).map_or(Duration::ZERO, |press| {
+            self.now.saturating_sub(press.started)
+        })
+    }
+
+    /// How far the cursor moved since the `button` was pressed, or zero if it isn't known
+    #[must_use]
+    pub fn offset(&self, button: MouseButton) -> Vec2 {
+        self.presses
+            .get(&button)
+            .map_or(Vec2::ZERO, |press| press.offset)
+    }
+
+    /// The furthest the cursor got from where it was when the `button` was pressed, or zero if it isn't known
+    #[must_use]
+    pub fn max_distance(&self, button: MouseButton) -> f32 {
+        self.presses
+            .get(&button)
+            .map_or(0.0, |press| press.max_distance)
+    }
+}
+
+/// When a mouse button was pressed, and how far the cursor moved since
+#[derive(Debug, Clone, PartialEq)]
+struct MousePress {
+    /// The time at which the button was pressed
+    started: Duration,
+    /// How far the cursor moved since the button was pressed
+    offset: Vec2,
+    /// The furthest the cursor got from where it was when the button was pressed
+    max_distance: f32,
+}
@@ -1,14 +1,20 @@
 //! Contains main plugin exported by this crate.
 
+use crate::accessibility::{AccessibilitySettings, AccessibilityTransform, ActionDoubleTapEvent};
+use crate::action_diff::ActionDiffEvent;
 use crate::action_state::{ActionData, ActionState};
 use crate::axislike::{
-    AxisType, DeadZoneShape, DualAxis, DualAxisData, MouseMotionAxisType, MouseWheelAxisType,
-    SingleAxis, VirtualAxis, VirtualDPad,
+    AxisType, DeadZoneShape, DualAxis, DualAxisData, DualAxisPolicy, EitherDualAxis,
+    MouseMotionAxisType, MouseWheelAxisType, SingleAxis, TripleAxis, TripleAxisData, VirtualAxis,
+    VirtualDPad,
 };
 use crate::buttonlike::{MouseMotionDirection, MouseWheelDirection};
-use crate::clashing_inputs::ClashStrategy;
+use crate::clashing_inputs::{ClashStrategy, InputClaims};
 use crate::input_map::InputMap;
-use crate::timing::Timing;
+use crate::timing::{
+    ActionConsumePolicy, ActionHoldEvent, ConsumePolicy, DoubleTickPolicy, TickClock,
+    TimeScalePolicies, TimeScalePolicy, Timing,
+};
 use crate::user_input::{InputKind, Modifier, UserInput};
 use crate::Actionlike;
 use core::hash::Hash;
@@ -17,11 +23,14 @@ use std::fmt::Debug;
 
 use bevy::app::{App, Plugin};
 use bevy::ecs::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::input::gamepad::Gamepad;
 use bevy::input::{ButtonState, InputSystem};
-use bevy::prelude::{PostUpdate, PreUpdate};
+use bevy::prelude::{FixedUpdate, PostUpdate, PreUpdate};
 use bevy::reflect::TypePath;
 #[cfg(feature = "ui")]
 use bevy::ui::UiSystem;
+use bevy::window::WindowFocused;
 
 /// A [`Plugin`] that collects [`Input`](bevy::input::Input) from disparate sources, producing an [`ActionState`] that can be conveniently checked
 ///
@@ -37,24 +46,66 @@ use bevy::ui::UiSystem;
 ///
 /// ## Systems
 ///
-/// All systems added by this plugin can be dynamically enabled and disabled by setting the value of the [`ToggleActions<A>`] resource is set.
+/// Actions can be dynamically disabled and enabled, in whole or in part, by changing the [`ToggleActions<A>`] resource.
 /// This can be useful when working with states to pause the game, navigate menus or so on.
 ///
-/// **WARNING:** These systems run during [`PreUpdate`].
+/// **WARNING:** These systems run during [`PreUpdate`], unless configured otherwise with [`InputManagerPlugin::in_schedule`].
 /// If you have systems that care about inputs and actions that also run during this stage,
 /// you must define an ordering between your systems or behavior will be very erratic.
-/// The stable system sets for these systems are available under [`InputManagerSystem`] enum.
+/// The stable system sets for these systems are available under [`InputManagerSystem`] enum,
+/// which also describes the order in which they run.
+///
+/// ## Fixed timesteps
+///
+/// The [`ActionState`] is updated once per frame, and should be read in [`Update`](bevy::app::Update).
+/// Systems in [`FixedUpdate`] can't rely on its `just_pressed` and `just_released` values,
+/// as the number of fixed timesteps per frame varies.
+/// If you need them, configure the plugin with [`InputManagerPlugin::use_fixed_action_state`]
+/// and read the [`FixedActionState`](crate::action_state::FixedActionState) in `FixedUpdate` instead.
 ///
 /// Complete list:
 ///
 /// - [`tick_action_state`](crate::systems::tick_action_state), which resets the `pressed` and `just_pressed` fields of the [`ActionState`] each frame
+///    - durations are measured using the [`TickClock`] resource, if it exists
+/// - [`resume_action_states`](crate::systems::resume_action_states), which lets the [`ActionState`] of entities whose [`PausedInputs`](crate::action_state::PausedInputs) were removed carry on from where it was frozen
+/// - [`insert_missing_action_states`](crate::systems::insert_missing_action_states), which gives entities with an [`InputMap`] the [`ActionState`] they are missing, unless disabled with [`InputManagerPlugin::insert_missing_action_states`]
+/// - [`warn_orphaned_action_states`](crate::systems::warn_orphaned_action_states), which warns about [`ActionState`]s that nothing presses, when enabled with [`InputManagerPlugin::warn_orphaned_action_states`]
+/// - [`apply_input_map_stacks`](crate::systems::apply_input_map_stacks), which replaces the [`InputMap`] of entities whose [`InputMapStack`](crate::input_map_stack::InputMapStack) changed
+/// - [`sync_shared_input_maps`](crate::systems::sync_shared_input_maps), which copies the [`SharedInputMap`](crate::input_map::SharedInputMap) into the [`InputMap`] of entities marked with [`UseSharedInputMap`](crate::input_map::UseSharedInputMap)
+/// - [`track_touches`](crate::systems::track_touches), which times the fingers on the touch screen in the [`TouchHistory`](crate::touch::TouchHistory), to recognize [`TouchGesture`](crate::touch::TouchGesture)s
+/// - [`track_mouse_presses`](crate::systems::track_mouse_presses), which measures how far the cursor moves while each mouse button is held in the [`MousePressHistory`](crate::mouse_gestures::MousePressHistory), to tell [`MouseClick`](crate::mouse_gestures::MouseClick)s and [`MouseDrag`](crate::mouse_gestures::MouseDrag)s apart
 /// - [`update_action_state`](crate::systems::update_action_state), which collects [`Input`](bevy::input::Input) resources to update the [`ActionState`]
+///    - inputs are transformed by the [`AccessibilitySettings`] first, when configured with [`InputManagerPlugin::with_accessibility`]
 /// - [`update_action_state_from_interaction`](crate::systems::update_action_state_from_interaction), for triggering actions from buttons
 ///    - powers the [`ActionStateDriver`](crate::action_driver::ActionStateDriver) component based on an [`Interaction`](bevy::ui::Interaction) component
-/// - [`release_on_disable`](crate::systems::release_on_disable), which resets action states when [`ToggleActions`] is flipped, to avoid persistent presses.
+/// - [`apply_cooldowns`](crate::systems::apply_cooldowns), which swallows the presses of actions that are still on cooldown
+/// - [`apply_charges`](crate::systems::apply_charges), which spends the [`Charges`](crate::charges::Charges) of actions that were used, and swallows the presses of actions without any charge left
+/// - [`release_on_disable`](crate::systems::release_on_disable), which releases the actions disabled by [`ToggleActions`], to avoid persistent presses.
+/// - [`update_window_focus`](crate::systems::update_window_focus) and [`release_on_focus_lost`](crate::systems::release_on_focus_lost),
+///   which pause updating the actions while the window is unfocused, when configured with [`InputManagerPlugin::on_focus_lost`]
+/// - [`relay_action_states`](crate::systems::relay_action_states), which copies [`ActionState`]s onto the entities that mirror them
+///    - powers the [`ActionStateSource`](crate::action_state::ActionStateSource) component
+/// - [`record_fixed_action_state`](crate::systems::record_fixed_action_state) and [`tick_fixed_action_state`](crate::systems::tick_fixed_action_state),
+///   which maintain the [`FixedActionState`](crate::action_state::FixedActionState), when configured with [`InputManagerPlugin::use_fixed_action_state`]
+/// - [`aggregate_action_states`](crate::systems::aggregate_action_states) or [`aggregate_primary_action_state`](crate::systems::aggregate_primary_action_state),
+///   which maintain the [`ActionState`] resource, when configured with [`InputManagerPlugin::aggregate_action_state`]
+/// - `log_clash_suppressions`, which logs the actions that are suppressed by clashes, when the `clash_debug` feature is enabled
+/// - `collect_action_rumbles`, `collect_held_action_rumbles` and `send_gamepad_rumbles`, which rumble gamepads according to the `ActionRumble` of each [`ActionState`], when the `rumble` feature is enabled
 pub struct InputManagerPlugin<A: Actionlike> {
     _phantom: PhantomData<A>,
     machine: Machine,
+    virtual_time: bool,
+    time_scale_policies: Option<TimeScalePolicies<A>>,
+    consume_policy: Option<ConsumePolicy>,
+    accessibility: Option<AccessibilitySettings<A>>,
+    fixed_action_state: bool,
+    claim_priority: Option<i32>,
+    aggregation: Option<ActionStateAggregation>,
+    schedule: InternedScheduleLabel,
+    insert_missing_action_states: bool,
+    warn_orphaned_action_states: bool,
+    focus_policy: FocusPolicy,
+    apply_action_diffs: bool,
 }
 
 // Deriving default induces an undesired bound on the generic
@@ -63,6 +114,18 @@ impl<A: Actionlike> Default for InputManagerPlugin<A> {
         Self {
             _phantom: PhantomData,
             machine: Machine::Client,
+            virtual_time: false,
+            time_scale_policies: None,
+            consume_policy: None,
+            accessibility: None,
+            fixed_action_state: false,
+            claim_priority: None,
+            aggregation: None,
+            schedule: PreUpdate.intern(),
+            insert_missing_action_states: true,
+            warn_orphaned_action_states: false,
+            focus_policy: FocusPolicy::Ignore,
+            apply_action_diffs: false,
         }
     }
 }
@@ -73,13 +136,254 @@ impl<A: Actionlike> InputManagerPlugin<A> {
     /// Inputs will not be processed; instead, [`ActionState`]
     /// should be copied directly from the state provided by the client,
     /// or constructed from [`ActionDiff`](crate::action_diff::ActionDiff) event streams.
+    ///
+    /// The [`ActionDiffEvent`](crate::action_diff::ActionDiffEvent)s sent to this app can be applied by [`apply_action_diffs`](crate::systems::apply_action_diffs),
+    /// when enabled with [`InputManagerPlugin::apply_action_diffs`].
+    /// No input resources are read, so this runs in headless apps without Bevy's [`InputPlugin`](bevy::input::InputPlugin).
     #[must_use]
     pub fn server() -> Self {
         Self {
             _phantom: PhantomData,
             machine: Machine::Server,
+            virtual_time: false,
+            time_scale_policies: None,
+            consume_policy: None,
+            accessibility: None,
+            fixed_action_state: false,
+            claim_priority: None,
+            aggregation: None,
+            schedule: PreUpdate.intern(),
+            insert_missing_action_states: true,
+            warn_orphaned_action_states: false,
+            focus_policy: FocusPolicy::Ignore,
+            apply_action_diffs: false,
         }
     }
+
+    /// Measures the durations of actions using [`Time<Virtual>`](bevy::time::Virtual), rather than the wall clock
+    ///
+    /// Durations will stop accumulating while virtual time is paused, and are scaled by its relative speed,
+    /// so charging an attack can't be done from the pause menu.
+    /// Actions are still only `just_pressed` or `just_released` for a single tick while paused.
+    ///
+    /// This inserts [`TickClock::Virtual`], which is shared by the plugins of every action type.
+    #[must_use]
+    pub fn use_virtual_time(mut self) -> Self {
+        self.virtual_time = true;
+        self
+    }
+
+    /// Sets whether the durations of the `action` follow the relative speed of virtual time, when using [`InputManagerPlugin::use_virtual_time`]
+    ///
+    /// Actions are [`TimeScalePolicy::Scaled`] by default, so charging an attack slows down with the game.
+    /// Make actions [`TimeScalePolicy::Unscaled`] to keep measuring them in real time, such as for UI actions that are used while the game is paused.
+    ///
+    /// The policies are stored in the [`TimeScalePolicies`] resource.
+    #[must_use]
+    pub fn with_time_scale_policy(mut self, action: A, policy: TimeScalePolicy) -> Self {
+        self.time_scale_policies
+            .get_or_insert_with(TimeScalePolicies::default)
+            .insert(action, policy);
+        self
+    }
+
+    /// Sets what happens to the [`Timing`](crate::timing::Timing) of the actions of type `A` when they are consumed, which is [`ConsumePolicy::FlipTiming`] by default
+    ///
+    /// Each [`ActionState`] can override this using [`ActionState::set_consume_policy`].
+    /// The policy is stored in the [`ActionConsumePolicy`] resource.
+    #[must_use]
+    pub fn with_consume_policy(mut self, policy: ConsumePolicy) -> Self {
+        self.consume_policy = Some(policy);
+        self
+    }
+
+    /// Transforms the inputs of the `action` with the given [`AccessibilityTransform`], such as for players who can't hold buttons
+    ///
+    /// The transforms are stored in the [`AccessibilitySettings`] resource, which can be serialized separately from the [`InputMap`],
+    /// and replaced at runtime.
+    /// They are applied by [`update_action_state`](crate::systems::update_action_state),
+    /// so every system that reads the [`ActionState`] afterwards, including the [`Cooldowns`](crate::cooldowns::Cooldowns), sees the transformed presses.
+    #[must_use]
+    pub fn with_accessibility(mut self, action: A, transform: AccessibilityTransform) -> Self {
+        self.accessibility
+            .get_or_insert_with(AccessibilitySettings::default)
+            .insert(action, transform);
+        self
+    }
+
+    /// Maintains a [`FixedActionState`](crate::action_state::FixedActionState) alongside each [`ActionState`], for use in [`FixedUpdate`]
+    ///
+    /// The [`ActionState`] is ticked once per frame, so `just_pressed` and `just_released` are unreliable in `FixedUpdate`:
+    /// they may be missed entirely, or seen during several fixed timesteps.
+    /// In this mode, systems in `FixedUpdate` should read the [`FixedActionState`](crate::action_state::FixedActionState) instead,
+    /// and be ordered after [`InputManagerSystem::FixedTick`].
+    /// Each press and release is then `just_pressed` or `just_released` for exactly one fixed timestep.
+    ///
+    /// Systems in [`Update`](bevy::app::Update) should keep reading the [`ActionState`], which is unaffected.
+    #[must_use]
+    pub fn use_fixed_action_state(mut self) -> Self {
+        self.fixed_action_state = true;
+        self
+    }
+
+    /// Claims the inputs that press actions of type `A` with the given `priority`, hiding them from action types with a lower priority
+    ///
+    /// This is useful when several [`InputMap`]s share the same keys, such as a menu that should swallow the keys used for gameplay while it is open.
+    /// The priority is stored in the [`InputClaims`] resource, and can be changed there at runtime.
+    ///
+    /// Claims are only seen by the action types that are updated afterwards,
+    /// so order the [`UpdateActionStateSet`] of each type from the highest priority to the lowest.
+    #[must_use]
+    pub fn claim_inputs(mut self, priority: i32) -> Self {
+        self.claim_priority = Some(priority);
+        self
+    }
+
+    /// Maintains the [`ActionState`] resource by combining the [`ActionState`]s of entities, as chosen by the `aggregation`
+    ///
+    /// This is useful when the same actions are read both from the player entity and from systems that don't know about it.
+    /// The resource is inserted if it does not exist, and is updated in [`InputManagerSystem::Aggregate`], after each entity was updated.
+    /// It is still ticked like any other [`ActionState`], so its `just_pressed` and `just_released` values follow the combined state.
+    ///
+    /// Don't insert an [`InputMap`] resource alongside this, as the aggregation overwrites the actions it presses.
+    #[must_use]
+    pub fn aggregate_action_state(mut self, aggregation: ActionStateAggregation) -> Self {
+        self.aggregation = Some(aggregation);
+        self
+    }
+
+    /// Runs the systems that tick and update the [`ActionState`]s in the given `schedule`, rather than in [`PreUpdate`]
+    ///
+    /// Each action type can use its own schedule.
+    /// For example, actions that are only read in [`FixedUpdate`] can be ticked and updated once per fixed timestep,
+    /// so that their `just_pressed` and `just_released` values last for exactly one fixed timestep.
+    /// Inputs are still collected by Bevy in [`PreUpdate`], so presses and releases are only seen by the next run of the `schedule`,
+    /// and an input that is pressed and released between two runs is missed.
+    ///
+    /// Systems that read these actions should run in the same `schedule`, ordered after the [`InputManagerSystem`] sets,
+    /// or in a schedule that runs after it.
+    ///
+    /// If the `schedule` can run several times per frame, such as when sub-stepping a simulation,
+    /// each [`ActionState`] is still only ticked by the first run of the frame, as extra ticks are skipped by default.
+    /// Their `just_pressed` and `just_released` values are cleared on that first run, and are seen by every later run in the same frame.
+    /// Insert [`DoubleTickPolicy::Allow`] to tick them on every run instead,
+    /// or use [`InputManagerPlugin::use_fixed_action_state`] for edges that last for exactly one fixed timestep.
+    #[must_use]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+
+    /// Sets whether entities with an [`InputMap`] but no [`ActionState`] are given a default [`ActionState`], which is the default
+    ///
+    /// Without an [`ActionState`], an entity silently never presses any action.
+    /// Disable this if your [`ActionState`]s are managed by hand.
+    #[must_use]
+    pub fn insert_missing_action_states(mut self, insert: bool) -> Self {
+        self.insert_missing_action_states = insert;
+        self
+    }
+
+    /// Sets whether a warning is logged for entities that are given an [`ActionState`] without anything to press its actions, which is disabled by default
+    ///
+    /// Entities without an [`InputMap`] or an [`ActionStateDriver`](crate::action_driver::ActionStateDriver) are reported,
+    /// so leave this disabled if some [`ActionState`]s are pressed by hand, such as those mirrored from [`ActionDiff`](crate::action_diff::ActionDiff)s or driven by AI.
+    /// This has no effect on the server.
+    #[must_use]
+    pub fn warn_orphaned_action_states(mut self, warn: bool) -> Self {
+        self.warn_orphaned_action_states = warn;
+        self
+    }
+
+    /// Sets whether the server applies the [`ActionDiffEvent`](crate::action_diff::ActionDiffEvent)s sent to it, which is disabled by default
+    ///
+    /// Leave this disabled when the events are applied by hand, such as when the owners are mapped to local entities on the way,
+    /// as each diff would otherwise be applied twice.
+    /// This has no effect on the client.
+    #[must_use]
+    pub fn apply_action_diffs(mut self, apply: bool) -> Self {
+        self.apply_action_diffs = apply;
+        self
+    }
+
+    /// Sets what happens to the actions when the window loses focus, which is [`FocusPolicy::Ignore`] by default
+    ///
+    /// The operating system does not tell unfocused windows about released keys, so keys held while alt-tabbing away stay pressed.
+    /// Focus is tracked in the [`WindowFocus`] resource, from the [`WindowFocused`] events.
+    #[must_use]
+    pub fn on_focus_lost(mut self, focus_policy: FocusPolicy) -> Self {
+        self.focus_policy = focus_policy;
+        self
+    }
+}
+
+/// Which entities are combined into the [`ActionState`] resource, when using [`InputManagerPlugin::aggregate_action_state`]
+///
+/// Actions are pressed if they are pressed for any of the combined entities,
+/// and take the value and axis pair with the largest magnitude among them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionStateAggregation {
+    /// Combines the [`ActionState`]s of every entity
+    AllEntities,
+    /// Copies the [`ActionState`] of the entity marked with the [`PrimaryInputSource`](crate::action_state::PrimaryInputSource) component
+    PrimaryEntity,
+}
+
+/// What happens to the actions of an [`InputManagerPlugin`] when the window loses focus
+///
+/// See [`InputManagerPlugin::on_focus_lost`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FocusPolicy {
+    /// Releases every action once, and keeps them released until the window regains focus
+    ReleaseAll,
+    /// Keeps the actions as they are until the window regains focus
+    ///
+    /// The actions are not ticked either, so their durations stop growing and `just_pressed` and `just_released` actions stay that way.
+    /// Once the window regains focus, the durations carry on from where they were frozen.
+    Freeze,
+    /// Keeps updating the actions from the inputs, even though keys held while the window loses focus may never be released
+    #[default]
+    Ignore,
+}
+
+/// Is the window focused?
+///
+/// With several windows, this follows the last [`WindowFocused`] event.
+/// This resource is added by the plugins configured with [`InputManagerPlugin::on_focus_lost`],
+/// and is updated by [`update_window_focus`](crate::systems::update_window_focus) before the [`ActionState`]s are updated.
+/// Once the window regains focus, actions are updated from the input resources again on that frame.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowFocus {
+    /// Is the window focused?
+    pub focused: bool,
+}
+
+impl Default for WindowFocus {
+    fn default() -> Self {
+        Self { focused: true }
+    }
+}
+
+/// Stops [`tick_action_state`](crate::systems::tick_action_state) from ticking the [`ActionState<A>`]s while the [`WindowFocus`] resource says that the window is unfocused
+///
+/// This resource is inserted by the plugins configured with [`FocusPolicy::Freeze`].
+#[derive(Resource, Debug)]
+pub struct FreezeOnFocusLost<A: Actionlike>(PhantomData<A>);
+
+/// Sent when a gamepad read by an [`InputMap`] is disconnected, such as to pause the game until it is reconnected
+///
+/// This is sent for input maps associated with that gamepad,
+/// and for input maps that read any gamepad and have gamepad bindings.
+/// An entity with input maps for several action types receives one event for each of them.
+///
+/// The actions pressed by that gamepad are released on the same frame, so they are `just_released`,
+/// while actions that are also held with other inputs stay pressed.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssociatedGamepadDisconnected {
+    /// The entity with the [`InputMap`], or `None` if the [`InputMap`] is a resource
+    pub entity: Option<Entity>,
+    /// The gamepad that was disconnected
+    pub gamepad: Gamepad,
 }
 
 /// Which machine is this plugin running on?
@@ -92,17 +396,37 @@ impl<A: Actionlike + TypePath> Plugin for InputManagerPlugin<A> {
     fn build(&self, app: &mut App) {
         use crate::systems::*;
 
+        if self.insert_missing_action_states {
+            // The inserted `ActionState`s are updated from the next frame onwards
+            app.add_systems(
+                self.schedule,
+                insert_missing_action_states::<A>.before(InputManagerSystem::Tick),
+            );
+        }
+
+        if self.warn_orphaned_action_states && matches!(self.machine, Machine::Client) {
+            app.add_systems(
+                self.schedule,
+                warn_orphaned_action_states::<A>.after(InputManagerSystem::Update),
+            );
+        }
+
+        app.add_systems(
+            self.schedule,
+            resume_action_states::<A>.before(InputManagerSystem::Tick),
+        );
+
         match self.machine {
             Machine::Client => {
                 app.add_systems(
-                    PreUpdate,
+                    self.schedule,
+                    // Disabled actions are still ticked, so that they are only `just_released` once
                     tick_action_state::<A>
-                        .run_if(run_if_enabled::<A>)
                         .in_set(InputManagerSystem::Tick)
                         .before(InputManagerSystem::Update),
                 )
                 .add_systems(
-                    PreUpdate,
+                    self.schedule,
                     release_on_disable::<A>
                         .in_set(InputManagerSystem::ReleaseOnDisable)
                         .after(InputManagerSystem::Update),
@@ -110,26 +434,149 @@ impl<A: Actionlike + TypePath> Plugin for InputManagerPlugin<A> {
                 .add_systems(PostUpdate, release_on_input_map_removed::<A>);
 
                 app.add_systems(
-                    PreUpdate,
-                    update_action_state::<A>
+                    self.schedule,
+                    (apply_input_map_stacks::<A>, sync_shared_input_maps::<A>)
+                        .chain()
+                        .after(InputManagerSystem::Tick)
+                        .before(InputManagerSystem::Update),
+                );
+
+                let update_action_state = update_action_state::<A>.run_if(run_if_enabled::<A>);
+                match self.focus_policy {
+                    FocusPolicy::Ignore => app.add_systems(
+                        self.schedule,
+                        update_action_state
+                            .in_set(InputManagerSystem::Update)
+                            .in_set(UpdateActionStateSet::<A>::default()),
+                    ),
+                    FocusPolicy::ReleaseAll | FocusPolicy::Freeze => app.add_systems(
+                        self.schedule,
+                        update_action_state
+                            .run_if(run_if_window_focused)
+                            .in_set(InputManagerSystem::Update)
+                            .in_set(UpdateActionStateSet::<A>::default()),
+                    ),
+                };
+
+                app.add_event::<AssociatedGamepadDisconnected>()
+                    .add_systems(
+                        self.schedule,
+                        notify_associated_gamepad_disconnected::<A>.after(InputSystem),
+                    );
+
+                match self.focus_policy {
+                    FocusPolicy::ReleaseAll => {
+                        app.add_systems(
+                            self.schedule,
+                            release_on_focus_lost::<A>.in_set(InputManagerSystem::ReleaseOnDisable),
+                        );
+                    }
+                    FocusPolicy::Freeze => {
+                        app.insert_resource(FreezeOnFocusLost::<A>(PhantomData));
+                    }
+                    FocusPolicy::Ignore => (),
+                }
+
+                // Some resources and systems are shared by the plugins of every action type,
+                // so only the first plugin to be built adds them
+                if self.focus_policy != FocusPolicy::Ignore
+                    && !app.world.contains_resource::<WindowFocus>()
+                {
+                    app.init_resource::<WindowFocus>()
+                        .add_event::<WindowFocused>()
+                        .add_systems(
+                            self.schedule,
+                            update_window_focus.before(InputManagerSystem::Tick),
+                        );
+                }
+
+                #[cfg(feature = "clash_debug")]
+                app.add_systems(
+                    self.schedule,
+                    log_clash_suppressions::<A>
                         .run_if(run_if_enabled::<A>)
-                        .in_set(InputManagerSystem::Update),
+                        .after(InputManagerSystem::Update),
+                );
+
+                #[cfg(feature = "rumble")]
+                {
+                    if !app.is_plugin_added::<GamepadRumblePlugin>() {
+                        app.add_plugins(GamepadRumblePlugin);
+                    }
+                    app.add_systems(
+                        self.schedule,
+                        collect_action_rumbles::<A>
+                            .after(InputManagerSystem::ReleaseOnDisable)
+                            .after(InputManagerSystem::Relay),
+                    )
+                    .add_systems(
+                        PostUpdate,
+                        collect_held_action_rumbles::<A>.before(send_gamepad_rumbles),
+                    );
+                }
+
+                app.init_resource::<crate::cursor_world::CursorWorldSource>();
+                if !app.is_plugin_added::<InputClaimsPlugin>() {
+                    app.add_plugins(InputClaimsPlugin);
+                }
+                if !app.is_plugin_added::<TouchHistoryPlugin>() {
+                    app.add_plugins(TouchHistoryPlugin);
+                }
+                if !app.is_plugin_added::<MousePressHistoryPlugin>() {
+                    app.add_plugins(MousePressHistoryPlugin);
+                }
+                if let Some(priority) = self.claim_priority {
+                    app.world
+                        .resource_mut::<InputClaims>()
+                        .set_priority::<A>(priority);
+                }
+
+                app.add_systems(
+                    self.schedule,
+                    (
+                        apply_cooldowns::<A>,
+                        // Presses that are blocked by a cooldown don't spend a charge
+                        apply_charges::<A>.after(apply_cooldowns::<A>),
+                    )
+                        .in_set(InputManagerSystem::ApplyCooldowns),
                 );
 
-                app.configure_sets(PreUpdate, InputManagerSystem::Update.after(InputSystem));
+                app.configure_sets(self.schedule, InputManagerSystem::Update.after(InputSystem));
+
+                app.configure_sets(
+                    self.schedule,
+                    InputManagerSystem::ApplyCooldowns
+                        // Presses from both inputs and buttons can be blocked
+                        .after(InputManagerSystem::Update)
+                        .after(InputManagerSystem::ManualControl)
+                        .before(InputManagerSystem::ReleaseOnDisable),
+                );
 
                 #[cfg(feature = "egui")]
                 app.configure_sets(
-                    PreUpdate,
+                    self.schedule,
                     InputManagerSystem::Update.after(bevy_egui::EguiSet::ProcessInput),
                 );
 
+                #[cfg(feature = "egui")]
+                if !app.world.contains_resource::<EguiWantsInput>() {
+                    app.init_resource::<EguiWantsInput>().add_systems(
+                        PreUpdate,
+                        update_egui_wants_input
+                            .after(bevy_egui::EguiSet::ProcessInput)
+                            .before(InputManagerSystem::Update),
+                    );
+                }
+
                 #[cfg(feature = "ui")]
-                app.configure_sets(PreUpdate, InputManagerSystem::Update.after(UiSystem::Focus));
+                app.configure_sets(
+                    self.schedule,
+                    InputManagerSystem::Update.after(UiSystem::Focus),
+                );
 
                 #[cfg(feature = "ui")]
                 app.configure_sets(
-                    PreUpdate,
+                    self.schedule,
                     InputManagerSystem::ManualControl
                         .before(InputManagerSystem::ReleaseOnDisable)
                         .after(InputManagerSystem::Tick)
@@ -142,7 +589,7 @@ impl<A: Actionlike + TypePath> Plugin for InputManagerPlugin<A> {
 
                 #[cfg(feature = "ui")]
                 app.add_systems(
-                    PreUpdate,
+                    self.schedule,
                     update_action_state_from_interaction::<A>
                         .run_if(run_if_enabled::<A>)
                         .in_set(InputManagerSystem::ManualControl),
@@ -150,14 +597,102 @@ impl<A: Actionlike + TypePath> Plugin for InputManagerPlugin<A> {
             }
             Machine::Server => {
                 app.add_systems(
-                    PreUpdate,
+                    self.schedule,
                     tick_action_state::<A>
                         .run_if(run_if_enabled::<A>)
                         .in_set(InputManagerSystem::Tick),
+                )
+                .add_systems(
+                    self.schedule,
+                    (
+                        apply_cooldowns::<A>,
+                        apply_charges::<A>.after(apply_cooldowns::<A>),
+                    )
+                        .in_set(InputManagerSystem::ApplyCooldowns)
+                        .after(InputManagerSystem::Tick)
+                        .after(InputManagerSystem::Update),
                 );
+
+                if self.apply_action_diffs {
+                    app.add_event::<ActionDiffEvent<A>>().add_systems(
+                        self.schedule,
+                        apply_action_diffs::<A>
+                            .in_set(InputManagerSystem::Update)
+                            .after(InputManagerSystem::Tick),
+                    );
+                }
             }
         };
 
+        if let Some(aggregation) = self.aggregation {
+            app.init_resource::<ActionState<A>>();
+            match aggregation {
+                ActionStateAggregation::AllEntities => app.add_systems(
+                    self.schedule,
+                    aggregate_action_states::<A>.in_set(InputManagerSystem::Aggregate),
+                ),
+                ActionStateAggregation::PrimaryEntity => app.add_systems(
+                    self.schedule,
+                    aggregate_primary_action_state::<A>.in_set(InputManagerSystem::Aggregate),
+                ),
+            };
+            app.configure_sets(
+                self.schedule,
+                InputManagerSystem::Aggregate
+                    .after(InputManagerSystem::ApplyCooldowns)
+                    .before(InputManagerSystem::ReleaseOnDisable),
+            );
+        }
+
+        let relayed_after = match self.machine {
+            Machine::Client => InputManagerSystem::ReleaseOnDisable,
+            Machine::Server => InputManagerSystem::ApplyCooldowns,
+        };
+        app.add_systems(
+            self.schedule,
+            relay_action_states::<A>.in_set(InputManagerSystem::Relay),
+        )
+        .configure_sets(
+            self.schedule,
+            InputManagerSystem::Relay
+                .after(relayed_after)
+                .after(InputManagerSystem::Aggregate),
+        );
+
+        if self.virtual_time {
+            app.insert_resource(TickClock::Virtual);
+        }
+
+        if let Some(time_scale_policies) = &self.time_scale_policies {
+            app.insert_resource(time_scale_policies.clone());
+        }
+
+        if let Some(consume_policy) = self.consume_policy {
+            app.insert_resource(ActionConsumePolicy::<A>::new(consume_policy));
+        }
+
+        if let Some(accessibility) = &self.accessibility {
+            app.insert_resource(accessibility.clone());
+        }
+
+        if self.fixed_action_state {
+            let recorded_after = match self.machine {
+                Machine::Client => InputManagerSystem::ReleaseOnDisable,
+                Machine::Server => InputManagerSystem::ApplyCooldowns,
+            };
+            app.add_systems(
+                self.schedule,
+                record_fixed_action_state::<A>
+                    .after(recorded_after)
+                    .after(InputManagerSystem::Aggregate)
+                    .after(InputManagerSystem::Relay),
+            )
+            .add_systems(
+                FixedUpdate,
+                tick_fixed_action_state::<A>.in_set(InputManagerSystem::FixedTick),
+            );
+        }
+
         app.register_type::<ActionState<A>>()
             .register_type::<InputMap<A>>()
             .register_type::<UserInput>()
@@ -168,73 +703,290 @@ impl<A: Actionlike + TypePath> Plugin for InputManagerPlugin<A> {
             .register_type::<Timing>()
             .register_type::<VirtualDPad>()
             .register_type::<VirtualAxis>()
+            .register_type::<EitherDualAxis>()
+            .register_type::<DualAxisPolicy>()
             .register_type::<SingleAxis>()
             .register_type::<DualAxis>()
+            .register_type::<TripleAxis>()
             .register_type::<AxisType>()
             .register_type::<MouseWheelAxisType>()
             .register_type::<MouseMotionAxisType>()
             .register_type::<DualAxisData>()
+            .register_type::<TripleAxisData>()
             .register_type::<DeadZoneShape>()
             .register_type::<ButtonState>()
             .register_type::<MouseWheelDirection>()
             .register_type::<MouseMotionDirection>()
             // Resources
             .init_resource::<ToggleActions<A>>()
-            .init_resource::<ClashStrategy>();
+            .init_resource::<ClashStrategy>()
+            .init_resource::<DoubleTickPolicy>()
+            // Events
+            .add_event::<ActionHoldEvent<A>>()
+            .add_event::<ActionDoubleTapEvent<A>>();
     }
 }
 
 /// Controls whether or not the [`ActionState`] / [`InputMap`] pairs of type `A` are active
 ///
 /// If this resource does not exist, actions work normally, as if `ToggleActions::enabled == true`.
-#[derive(Resource)]
+///
+/// When disabled, the affected actions are released once, so they are `just_released` for a single tick.
+/// They then stay released, even if their inputs are still held.
+/// Once re-enabled, actions whose inputs are still held are not pressed again until those inputs are released,
+/// unless [`ToggleActions::repress_on_enable`] is set.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Move,
+///     Jump,
+///     Pause,
+/// }
+///
+/// // Stop the player from moving during a cutscene, while still allowing the game to be paused
+/// fn start_cutscene(mut toggle_actions: ResMut<ToggleActions<Action>>) {
+///     toggle_actions.enabled = false;
+///     toggle_actions.disable_during = Some(vec![Action::Move, Action::Jump]);
+/// }
+///
+/// fn end_cutscene(mut toggle_actions: ResMut<ToggleActions<Action>>) {
+///     toggle_actions.enabled = true;
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
 pub struct ToggleActions<A: Actionlike> {
     /// When this is false, [`ActionState`]'s corresponding to `A` will ignore user inputs
     ///
     /// When this is set to false, all corresponding [`ActionState`]s are released
     pub enabled: bool,
-    /// Marker that stores the type of action to toggle
-    pub phantom: PhantomData<A>,
+    /// The actions that are disabled while [`ToggleActions::enabled`] is false
+    ///
+    /// If this is `None`, every action is disabled, and the systems of the plugin stop updating the [`ActionState`]s from inputs.
+    /// Otherwise, only these actions are disabled, and the others keep working normally.
+    pub disable_during: Option<Vec<A>>,
+    /// Should actions whose inputs are still held be pressed again when re-enabled?
+    ///
+    /// If this is false, these actions stay released until their inputs are released and pressed again.
+    /// If this is true, they are `just_pressed` on the tick after being re-enabled.
+    pub repress_on_enable: bool,
 }
 
 impl<A: Actionlike> ToggleActions<A> {
     /// A [`ToggleActions`] in enabled state.
     pub const ENABLED: ToggleActions<A> = ToggleActions::<A> {
         enabled: true,
-        phantom: PhantomData::<A>,
+        disable_during: None,
+        repress_on_enable: false,
     };
     /// A [`ToggleActions`] in disabled state.
     pub const DISABLED: ToggleActions<A> = ToggleActions::<A> {
         enabled: false,
-        phantom: PhantomData::<A>,
+        disable_during: None,
+        repress_on_enable: false,
     };
+
+    /// Is the `action` currently disabled?
+    #[must_use]
+    pub fn disabled(&self, action: &A) -> bool {
+        if self.enabled {
+            return false;
+        }
+
+        match &self.disable_during {
+            Some(actions) => actions.contains(action),
+            None => true,
+        }
+    }
 }
 
 // Implement manually to not require [`Default`] for `A`
 impl<A: Actionlike> Default for ToggleActions<A> {
     fn default() -> Self {
-        Self {
-            enabled: true,
-            phantom: PhantomData::<A>,
-        }
+        Self::ENABLED
     }
 }
 
+/// Does egui want to own the keyboard or the pointer?
+///
+/// While it does, the corresponding inputs are hidden from every [`InputMap`], so that typing in a text field or dragging a window does not trigger actions.
+/// Actions that were pressed by these inputs are released, and are pressed again once egui no longer wants them, if their inputs are still held.
+///
+/// This resource is updated by [`update_egui_wants_input`](crate::systems::update_egui_wants_input) each frame,
+/// before the [`ActionState`]s are updated.
+#[cfg(feature = "egui")]
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EguiWantsInput {
+    /// Does egui want the keyboard, such as when a text field has focus?
+    ///
+    /// Keys and scan codes are ignored while this is true.
+    pub keyboard: bool,
+    /// Does egui want the pointer, such as when it is over an egui window?
+    ///
+    /// Mouse buttons and the mouse wheel are ignored while this is true.
+    pub pointer: bool,
+}
+
 /// [`SystemSet`]s for the [`crate::systems`] used by this crate
 ///
-/// `Reset` must occur before `Update`
+/// These sets are in [`PreUpdate`], or in the schedule chosen with [`InputManagerPlugin::in_schedule`].
+/// On the client, they run in the following order each time the schedule runs:
+///
+/// 1. [`InputManagerSystem::Tick`]
+/// 2. [`InputManagerSystem::Update`], after Bevy's [`InputSystem`] has read the input events of the frame
+/// 3. [`InputManagerSystem::ManualControl`]
+/// 4. [`InputManagerSystem::ApplyCooldowns`]
+/// 5. [`InputManagerSystem::Aggregate`]
+/// 6. [`InputManagerSystem::ReleaseOnDisable`]
+/// 7. [`InputManagerSystem::Relay`]
+///
+/// On the server, [`InputManagerSystem::Update`] applies the received [`ActionDiffEvent`]s instead of reading inputs,
+/// and [`InputManagerSystem::ManualControl`] and [`InputManagerSystem::ReleaseOnDisable`] are not used.
+///
+/// The ordering relative to [`InputSystem`] only applies when the plugin runs in [`PreUpdate`], where [`InputSystem`] runs.
+/// Systems ordered after [`InputManagerSystem::Update`] see the inputs of the current frame,
+/// with newly pressed actions `just_pressed` and a [`ActionState::current_duration`] of zero.
+/// They will be ticked the next time [`InputManagerSystem::Tick`] runs.
 #[derive(SystemSet, Clone, Hash, Debug, PartialEq, Eq)]
 pub enum InputManagerSystem {
     /// Advances action timers.
     ///
-    /// Cleans up the state of the input manager, clearing `just_pressed` and just_released`
+    /// Cleans up the state of the input manager, clearing `just_pressed` and `just_released`
     Tick,
     /// Collects input data to update the [`ActionState`]
+    ///
+    /// On the server, applies the received [`ActionDiffEvent`]s instead.
     Update,
-    /// Release all actions in all [`ActionState`]s if [`ToggleActions`] was added
+    /// Releases the actions disabled by [`ToggleActions`] in all [`ActionState`]s, and keeps them released
     ReleaseOnDisable,
     /// Manually control the [`ActionState`]
     ///
     /// Must run after [`InputManagerSystem::Update`] or the action state will be overridden
     ManualControl,
+    /// Swallows presses of actions that are on cooldown or out of [`Charges`](crate::charges::Charges), and starts the [`Cooldowns`](crate::cooldowns::Cooldowns) of actions that were used
+    ///
+    /// Systems that read `just_pressed` in [`PreUpdate`] should run after this set.
+    ApplyCooldowns,
+    /// Combines the [`ActionState`]s of entities into the [`ActionState`] resource
+    ///
+    /// Only used when the plugin is configured with [`InputManagerPlugin::aggregate_action_state`].
+    Aggregate,
+    /// Copies the [`ActionState`]s of entities onto the entities that mirror them with an [`ActionStateSource`](crate::action_state::ActionStateSource)
+    ///
+    /// Systems in [`PreUpdate`] that read mirrored [`ActionState`]s should run after this set.
+    Relay,
+    /// Advances the [`FixedActionState`](crate::action_state::FixedActionState)s at the start of each fixed timestep
+    ///
+    /// Only used when the plugin is configured with [`InputManagerPlugin::use_fixed_action_state`].
+    /// Systems in [`FixedUpdate`] that read the [`FixedActionState`](crate::action_state::FixedActionState) must run after this set.
+    FixedTick,
+}
+
+/// The [`SystemSet`] containing the [`update_action_state`](crate::systems::update_action_state) system for actions of type `A`
+///
+/// This is part of [`InputManagerSystem::Update`], and is used to order the plugins of different action types with respect to each other,
+/// such as when they use [`InputClaims`].
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::plugin::UpdateActionStateSet;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+/// enum MenuAction {
+///     Close,
+/// }
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+/// enum GameAction {
+///     Pause,
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(InputManagerPlugin::<MenuAction>::default().claim_inputs(1))
+///     .add_plugins(InputManagerPlugin::<GameAction>::default().claim_inputs(0))
+///     .configure_sets(
+///         PreUpdate,
+///         UpdateActionStateSet::<MenuAction>::default()
+///             .before(UpdateActionStateSet::<GameAction>::default()),
+///     );
+/// ```
+#[derive(SystemSet, Clone, Hash, PartialEq, Eq)]
+pub struct UpdateActionStateSet<A: Actionlike> {
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for UpdateActionStateSet<A> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> Debug for UpdateActionStateSet<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UpdateActionStateSet<{}>", A::short_type_path())
+    }
+}
+
+/// Adds the [`InputClaims`] resource, and clears it at the start of each frame
+struct InputClaimsPlugin;
+
+impl Plugin for InputClaimsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputClaims>().add_systems(
+            PreUpdate,
+            crate::systems::clear_input_claims.in_set(InputManagerSystem::Tick),
+        );
+    }
+}
+
+/// Adds the [`TouchHistory`](crate::touch::TouchHistory) and [`TapArbitration`](crate::touch::TapArbitration) resources, and updates the history each frame before the [`ActionState`]s are updated
+struct TouchHistoryPlugin;
+
+impl Plugin for TouchHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<crate::touch::TouchHistory>()
+            .init_resource::<crate::touch::TapArbitration>()
+            .add_systems(
+                PreUpdate,
+                crate::systems::track_touches
+                    .after(InputSystem)
+                    .before(InputManagerSystem::Update),
+            );
+    }
+}
+
+/// Adds the [`MousePressHistory`](crate::mouse_gestures::MousePressHistory) resource, and updates it each frame before the [`ActionState`]s are updated
+struct MousePressHistoryPlugin;
+
+impl Plugin for MousePressHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<crate::mouse_gestures::MousePressHistory>()
+            .add_systems(
+                PreUpdate,
+                crate::systems::track_mouse_presses
+                    .after(InputSystem)
+                    .before(InputManagerSystem::Update),
+            );
+    }
+}
+
+/// Adds the [`GamepadRumbles`](crate::rumble::GamepadRumbles) resource, and sends its requests at the end of each frame
+#[cfg(feature = "rumble")]
+struct GamepadRumblePlugin;
+
+#[cfg(feature = "rumble")]
+impl Plugin for GamepadRumblePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<crate::rumble::GamepadRumbles>()
+            .add_systems(
+                PostUpdate,
+                crate::systems::send_gamepad_rumbles.before(bevy::gilrs::RumbleSystem),
+            );
+    }
 }
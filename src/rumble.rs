@@ -0,0 +1,326 @@
+//! Rumbles gamepads when actions are used, such as a short strong pulse for each shot, or a weak continuous rumble while an action is held.
+//!
+//! Add an [`ActionRumble`] next to an [`ActionState`], as a component or as a resource.
+//! The gamepad associated with the [`InputMap`](crate::input_map::InputMap) rumbles, or the first gamepad if there is none.
+//!
+//! Rumbles that last while an action is held are renewed periodically, and stop on the same frame as the action is released,
+//! even if it is consumed, or its entity is despawned, by the systems of the game.
+//!
+//! Rumbles that overlap on the same gamepad are not added up: the gamepad rumbles with the strongest intensity of each motor,
+//! as tracked by the [`GamepadRumbles`] resource.
+
+use crate::{action_state::ActionState, Actionlike};
+
+use bevy::{
+    ecs::{component::Component, system::Resource},
+    input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest},
+    utils::{Duration, HashMap},
+};
+
+/// How long a gamepad is asked to rumble for actions that are held, before the request is renewed
+const HELD_RUMBLE_DURATION: Duration = Duration::from_secs(1);
+
+/// When an [`ActionRumbleEffect`] makes the gamepad rumble
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RumbleTrigger {
+    /// Rumble for the duration of the effect each time the action is just pressed
+    JustPressed,
+    /// Rumble for as long as the action is pressed
+    ///
+    /// The rumble stops at the end of the frame on which the action is released or consumed,
+    /// or on which the [`ActionRumble`] or its entity is removed.
+    WhilePressed,
+}
+
+/// How the gamepad rumbles for a single action
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ActionRumbleEffect {
+    /// The intensity of each motor
+    pub intensity: GamepadRumbleIntensity,
+    /// How long the gamepad rumbles for, which is ignored for [`RumbleTrigger::WhilePressed`]
+    pub duration: Duration,
+    /// When the gamepad rumbles
+    pub trigger: RumbleTrigger,
+}
+
+impl ActionRumbleEffect {
+    /// Rumbles for `duration` each time the action is just pressed
+    #[must_use]
+    pub fn on_just_pressed(intensity: GamepadRumbleIntensity, duration: Duration) -> Self {
+        Self {
+            intensity,
+            duration,
+            trigger: RumbleTrigger::JustPressed,
+        }
+    }
+
+    /// Rumbles for as long as the action is pressed
+    #[must_use]
+    pub fn while_pressed(intensity: GamepadRumbleIntensity) -> Self {
+        Self {
+            intensity,
+            duration: Duration::ZERO,
+            trigger: RumbleTrigger::WhilePressed,
+        }
+    }
+}
+
+/// The [`ActionRumbleEffect`] of each action, which makes the gamepad rumble when the action is used
+///
+/// Add this as a component alongside an [`ActionState`], or as a resource alongside the [`ActionState`] resource.
+/// The effects are collected by [`collect_action_rumbles`](crate::systems::collect_action_rumbles)
+/// once the [`ActionState`] is updated, and sent as [`GamepadRumbleRequest`]s at the end of the frame.
+///
+/// # Example
+/// ```rust
+/// use bevy::input::gamepad::GamepadRumbleIntensity;
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::Duration;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::rumble::{ActionRumble, ActionRumbleEffect};
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Shoot,
+///     Brace,
+/// }
+///
+/// let mut action_rumble = ActionRumble::default();
+/// action_rumble
+///     .insert(
+///         Action::Shoot,
+///         ActionRumbleEffect::on_just_pressed(
+///             GamepadRumbleIntensity::STRONG_MAX,
+///             Duration::from_millis(150),
+///         ),
+///     )
+///     .insert(
+///         Action::Brace,
+///         ActionRumbleEffect::while_pressed(GamepadRumbleIntensity::weak_motor(0.3)),
+///     );
+/// ```
+#[derive(Resource, Component, Clone, Debug, PartialEq)]
+pub struct ActionRumble<A: Actionlike> {
+    effects: HashMap<A, ActionRumbleEffect>,
+}
+
+impl<A: Actionlike> Default for ActionRumble<A> {
+    fn default() -> Self {
+        Self {
+            effects: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> ActionRumble<A> {
+    /// Sets the [`ActionRumbleEffect`] of the `action`, replacing any previous one
+    pub fn insert(&mut self, action: A, effect: ActionRumbleEffect) -> &mut Self {
+        self.effects.insert(action, effect);
+        self
+    }
+
+    /// Removes the [`ActionRumbleEffect`] of the `action`
+    pub fn remove(&mut self, action: &A) -> &mut Self {
+        self.effects.remove(action);
+        self
+    }
+
+    /// The [`ActionRumbleEffect`] of the `action`, if it has one
+    #[must_use]
+    pub fn get(&self, action: &A) -> Option<&ActionRumbleEffect> {
+        self.effects.get(action)
+    }
+
+    /// Adds the rumbles caused by the `action_state` on the `gamepad` to the [`GamepadRumbles`]
+    ///
+    /// This is the same as calling both [`ActionRumble::collect_pulses`] and [`ActionRumble::collect_held`].
+    pub fn collect(
+        &self,
+        action_state: &ActionState<A>,
+        gamepad: Gamepad,
+        rumbles: &mut GamepadRumbles,
+    ) {
+        self.collect_pulses(action_state, gamepad, rumbles);
+        self.collect_held(action_state, gamepad, rumbles);
+    }
+
+    /// Adds the rumbles of the [`RumbleTrigger::JustPressed`] actions that were just pressed in the `action_state` to the [`GamepadRumbles`]
+    pub fn collect_pulses(
+        &self,
+        action_state: &ActionState<A>,
+        gamepad: Gamepad,
+        rumbles: &mut GamepadRumbles,
+    ) {
+        for (action, effect) in self.effects.iter() {
+            if effect.trigger == RumbleTrigger::JustPressed && action_state.just_pressed(action) {
+                rumbles.pulse(gamepad, effect.intensity, effect.duration);
+            }
+        }
+    }
+
+    /// Adds the rumbles of the [`RumbleTrigger::WhilePressed`] actions that are pressed in the `action_state` to the [`GamepadRumbles`]
+    ///
+    /// These rumbles only last for the current frame, so this must be called each frame until the actions are released.
+    pub fn collect_held(
+        &self,
+        action_state: &ActionState<A>,
+        gamepad: Gamepad,
+        rumbles: &mut GamepadRumbles,
+    ) {
+        for (action, effect) in self.effects.iter() {
+            if effect.trigger == RumbleTrigger::WhilePressed && action_state.pressed(action) {
+                rumbles.hold(gamepad, effect.intensity);
+            }
+        }
+    }
+}
+
+/// A rumble that lasts for a fixed duration
+#[derive(Clone, Copy, Debug)]
+struct Pulse {
+    gamepad: Gamepad,
+    intensity: GamepadRumbleIntensity,
+    remaining: Duration,
+    /// Pulses are only shortened from the frame after they started
+    started: bool,
+}
+
+/// The rumble that was last requested for a gamepad
+#[derive(Clone, Copy, Debug)]
+struct Playing {
+    intensity: GamepadRumbleIntensity,
+    remaining: Duration,
+}
+
+/// The rumbles requested for each gamepad, which are combined into [`GamepadRumbleRequest`]s once per frame
+///
+/// This is shared by the [`ActionRumble`]s of every action type.
+/// Each motor of a gamepad rumbles with the strongest of the intensities requested for it,
+/// and a new [`GamepadRumbleRequest`] is only sent when that changes.
+#[derive(Resource, Debug, Default)]
+pub struct GamepadRumbles {
+    pulses: Vec<Pulse>,
+    held: HashMap<Gamepad, GamepadRumbleIntensity>,
+    playing: HashMap<Gamepad, Playing>,
+}
+
+impl GamepadRumbles {
+    /// Rumbles the `gamepad` with the `intensity` for the given `duration`
+    pub fn pulse(
+        &mut self,
+        gamepad: Gamepad,
+        intensity: GamepadRumbleIntensity,
+        duration: Duration,
+    ) {
+        if duration.is_zero() {
+            return;
+        }
+
+        self.pulses.push(Pulse {
+            gamepad,
+            intensity,
+            remaining: duration,
+            started: false,
+        });
+    }
+
+    /// Rumbles the `gamepad` with the `intensity` during this frame
+    pub fn hold(&mut self, gamepad: Gamepad, intensity: GamepadRumbleIntensity) {
+        let held = self.held.entry(gamepad).or_insert(NO_RUMBLE);
+        *held = strongest(*held, intensity);
+    }
+
+    /// The intensity the `gamepad` is rumbling with, as of the last [`GamepadRumbleRequest`]
+    #[must_use]
+    pub fn intensity(&self, gamepad: Gamepad) -> Option<GamepadRumbleIntensity> {
+        self.playing.get(&gamepad).map(|playing| playing.intensity)
+    }
+
+    /// Advances the rumbles by `delta`, and returns the [`GamepadRumbleRequest`]s needed to play the strongest ones
+    ///
+    /// Rumbles requested with [`GamepadRumbles::hold`] are forgotten, and must be requested again next frame.
+    pub fn requests(&mut self, delta: Duration) -> Vec<GamepadRumbleRequest> {
+        for pulse in self.pulses.iter_mut() {
+            if pulse.started {
+                pulse.remaining = pulse.remaining.saturating_sub(delta);
+            }
+            pulse.started = true;
+        }
+        self.pulses.retain(|pulse| !pulse.remaining.is_zero());
+        for playing in self.playing.values_mut() {
+            playing.remaining = playing.remaining.saturating_sub(delta);
+        }
+
+        let mut targets: HashMap<Gamepad, Playing> = self
+            .held
+            .drain()
+            .map(|(gamepad, intensity)| {
+                (
+                    gamepad,
+                    Playing {
+                        intensity,
+                        remaining: HELD_RUMBLE_DURATION,
+                    },
+                )
+            })
+            .collect();
+        for pulse in self.pulses.iter() {
+            let target = targets.entry(pulse.gamepad).or_insert(Playing {
+                intensity: NO_RUMBLE,
+                remaining: Duration::ZERO,
+            });
+            target.intensity = strongest(target.intensity, pulse.intensity);
+            target.remaining = target.remaining.max(pulse.remaining);
+        }
+        targets.retain(|_, target| target.intensity != NO_RUMBLE);
+
+        let mut requests = Vec::new();
+        self.playing.retain(|&gamepad, _| {
+            let stopped = !targets.contains_key(&gamepad);
+            if stopped {
+                requests.push(GamepadRumbleRequest::Stop { gamepad });
+            }
+            !stopped
+        });
+
+        for (gamepad, target) in targets {
+            if let Some(playing) = self.playing.get(&gamepad) {
+                // Held rumbles are renewed well before they run out
+                let margin = if target.remaining >= HELD_RUMBLE_DURATION {
+                    HELD_RUMBLE_DURATION / 2
+                } else {
+                    Duration::ZERO
+                };
+                if playing.intensity == target.intensity
+                    && target.remaining <= playing.remaining + margin
+                {
+                    continue;
+                }
+                requests.push(GamepadRumbleRequest::Stop { gamepad });
+            }
+
+            requests.push(GamepadRumbleRequest::Add {
+                duration: target.remaining,
+                intensity: target.intensity,
+                gamepad,
+            });
+            self.playing.insert(gamepad, target);
+        }
+
+        requests
+    }
+}
+
+/// The intensity of a gamepad that isn't rumbling
+const NO_RUMBLE: GamepadRumbleIntensity = GamepadRumbleIntensity {
+    strong_motor: 0.0,
+    weak_motor: 0.0,
+};
+
+/// The strongest intensity of each motor
+fn strongest(a: GamepadRumbleIntensity, b: GamepadRumbleIntensity) -> GamepadRumbleIntensity {
+    GamepadRumbleIntensity {
+        strong_motor: a.strong_motor.max(b.strong_motor),
+        weak_motor: a.weak_motor.max(b.weak_motor),
+    }
+}
@@ -1,5 +1,5 @@
 //! Helper enums to easily obtain the scan code of a key.
-use bevy::prelude::ScanCode;
+use bevy::prelude::{KeyCode, ScanCode};
 
 // Wasm
 #[cfg(target_family = "wasm")]
@@ -38,3 +38,49 @@ impl From<QwertyScanCode> for ScanCode {
         ScanCode(value as u32)
     }
 }
+
+/// Defines [`qwerty_key_code`] and [`qwerty_scan_code`], mapping between each [`QwertyScanCode`] and the [`KeyCode`] it emits on the QWERTY layout
+macro_rules! qwerty_key_codes {
+    ($($same_name:ident),* ; $($scan_code:ident => $key_code:ident),*) => {
+        /// The [`KeyCode`] emitted by the key at the `scan_code`, assuming the QWERTY keyboard layout
+        ///
+        /// Returns [`None`] for keys that are not described by [`QwertyScanCode`].
+        #[must_use]
+        pub(crate) fn qwerty_key_code(scan_code: ScanCode) -> Option<KeyCode> {
+            $(
+                if scan_code.0 == QwertyScanCode::$same_name as u32 {
+                    return Some(KeyCode::$same_name);
+                }
+            )*
+            $(
+                if scan_code.0 == QwertyScanCode::$scan_code as u32 {
+                    return Some(KeyCode::$key_code);
+                }
+            )*
+            None
+        }
+
+        /// The scan code of the key that emits the `key_code`, assuming the QWERTY keyboard layout
+        ///
+        /// Returns [`None`] for keys that are not described by [`QwertyScanCode`].
+        #[must_use]
+        pub(crate) fn qwerty_scan_code(key_code: KeyCode) -> Option<ScanCode> {
+            match key_code {
+                $(KeyCode::$same_name => Some(QwertyScanCode::$same_name.into()),)*
+                $(KeyCode::$key_code => Some(QwertyScanCode::$scan_code.into()),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+qwerty_key_codes!(
+    A, AltLeft, AltRight, Apostrophe, B, Backslash, BracketLeft, BracketRight, C, Comma,
+    ControlLeft, D, Delete, Down, E, End, Equals, Escape, F, F1, F10, F11, F12, F2, F3, F4, F5,
+    F6, F7, F8, F9, G, H, Home, I, Insert, J, K, Key0, Key1, Key2, Key3, Key4, Key5, Key6,
+    Key7, Key8, Key9, L, Left, M, Minus, N, Numlock, Numpad0, Numpad1, Numpad2, Numpad3,
+    Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9, NumpadAdd, NumpadDecimal,
+    NumpadDivide, NumpadMultiply, NumpadSubtract, O, P, PageDown, PageUp, Pause, Period, Q, R,
+    Right, S, Scroll, ShiftLeft, Space, SuperLeft, SuperRight, T, Tab, U, Up, V, W, X, Y, Z;
+    Backtick => Grave, CapsLock => Capital, Enter => Return, SemiColon => Semicolon
+);
@@ -0,0 +1,84 @@
+//! Scrolling when the cursor touches the edges of the window, as real-time strategy games pan their camera
+//!
+//! A [`ScreenEdge`], bound with [`InputKind::ScreenEdge`](crate::user_input::InputKind::ScreenEdge),
+//! reads the position of the cursor in the [`PrimaryWindow`](bevy::window::PrimaryWindow).
+//! Bind it to the same action as a [`VirtualDPad`](crate::axislike::VirtualDPad) to pan with both the keyboard and the edges of the window:
+//! the axis pairs of every input bound to an action are added together.
+//!
+//! # Example
+//! ```rust
+//! use bevy::prelude::Reflect;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::screen_edge::ScreenEdge;
+//!
+//! #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+//! enum CameraAction {
+//!     Pan,
+//! }
+//!
+//! let input_map = InputMap::new([
+//!     (CameraAction::Pan, UserInput::from(VirtualDPad::wasd())),
+//!     (CameraAction::Pan, ScreenEdge::new(20.0).into()),
+//! ]);
+//! ```
+
+use crate::axislike::DualAxisData;
+
+use bevy::math::Vec2;
+use bevy::reflect::Reflect;
+use bevy::utils::FloatOrd;
+use serde::{Deserialize, Serialize};
+
+/// The edges of the window that the cursor is near, read like a [`DualAxis`](crate::axislike::DualAxis)
+///
+/// Each axis is `1.0` or `-1.0` while the cursor is within `margin_px` of the matching edge, and `0.0` otherwise,
+/// so the corners of the window produce diagonals.
+/// As with the [`VirtualDPad::arrow_keys`](crate::axislike::VirtualDPad::arrow_keys), the top edge is positive along the y axis.
+///
+/// It reads nothing while the cursor is outside of the window, or the window is unfocused.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct ScreenEdge {
+    /// How close to an edge of the window the cursor must be, in logical pixels
+    pub margin_px: f32,
+}
+
+impl ScreenEdge {
+    /// Creates a [`ScreenEdge`] that scrolls while the cursor is within `margin_px` of an edge of the window
+    #[must_use]
+    pub const fn new(margin_px: f32) -> ScreenEdge {
+        ScreenEdge { margin_px }
+    }
+
+    /// The direction of the edges that the `cursor` is near
+    ///
+    /// The `cursor` is in logical pixels from the top-left corner of the window, as given by [`Window::cursor_position`](bevy::window::Window::cursor_position),
+    /// and the `window_size` is in logical pixels.
+    #[must_use]
+    pub fn axis_pair(&self, cursor: Vec2, window_size: Vec2) -> DualAxisData {
+        let x = if cursor.x < self.margin_px {
+            -1.0
+        } else if cursor.x > window_size.x - self.margin_px {
+            1.0
+        } else {
+            0.0
+        };
+
+        // The y axis of the window points down
+        let y = if cursor.y < self.margin_px {
+            1.0
+        } else if cursor.y > window_size.y - self.margin_px {
+            -1.0
+        } else {
+            0.0
+        };
+
+        DualAxisData::new(x, y)
+    }
+}
+
+impl Eq for ScreenEdge {}
+impl std::hash::Hash for ScreenEdge {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        FloatOrd(self.margin_px).hash(state);
+    }
+}
@@ -0,0 +1,342 @@
+//! Records the raw inputs received by an app into an [`InputScript`], so that they can be replayed in tests
+//!
+//! Add the [`InputScriptRecorderPlugin`], then call [`InputScriptRecorder::start`] and [`InputScriptRecorder::stop`]
+//! to capture the keyboard, mouse and gamepad events received in between.
+//! The resulting script can be saved to a file with [`InputScript::save_to_file`],
+//! and replayed with [`RunInputScript::run_script`](crate::input_mocking::RunInputScript::run_script).
+//!
+//! These are only available with the `script_recording` feature enabled.
+
+use crate::axislike::{MouseMotionAxisType, MouseWheelAxisType, SingleAxis};
+use crate::input_mocking::{InputScript, InputScriptStep};
+use crate::user_input::UserInput;
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::ecs::prelude::*;
+use bevy::input::gamepad::{GamepadButton, GamepadEvent, GamepadSettings};
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseWheel};
+use bevy::input::{ButtonState, InputSystem};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// Adds the [`InputScriptRecorder`] resource, and the system that records inputs into it
+///
+/// Recording does not start until [`InputScriptRecorder::start`] is called.
+pub struct InputScriptRecorderPlugin;
+
+impl Plugin for InputScriptRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputScriptRecorder>()
+            .add_systems(PreUpdate, record_input_script.before(InputSystem));
+    }
+}
+
+/// Captures the raw inputs received each frame into an [`InputScript`]
+///
+/// Each frame of the app becomes a frame of the script, even if no inputs were received.
+///
+/// Only the information that [`MockInput`](crate::input_mocking::MockInput) can send is recorded:
+/// - keys are recorded by their [`KeyCode`](bevy::input::keyboard::KeyCode), or by their [`ScanCode`](bevy::input::keyboard::ScanCode) if they have none
+/// - mouse wheel and mouse motion events are recorded as [`SingleAxis`] movements
+/// - gamepad buttons are pressed and released according to the [`GamepadSettings`] thresholds,
+///   and are replayed on whichever gamepad is used by the replaying app
+///
+/// Gamepad connections are not recorded: connect a gamepad before replaying scripts that use one.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct InputScriptRecorder {
+    recording: bool,
+    frames_recorded: usize,
+    script: InputScript,
+}
+
+impl InputScriptRecorder {
+    /// Starts recording into a new, empty [`InputScript`]
+    ///
+    /// Any inputs recorded previously are discarded.
+    pub fn start(&mut self) {
+        self.recording = true;
+        self.frames_recorded = 0;
+        self.script = InputScript::new();
+    }
+
+    /// Stops recording, returning the [`InputScript`] that was recorded
+    pub fn stop(&mut self) -> InputScript {
+        self.recording = false;
+        self.frames_recorded = 0;
+        std::mem::take(&mut self.script)
+    }
+
+    /// Are inputs currently being recorded?
+    #[must_use]
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// The [`InputScript`] recorded so far
+    #[must_use]
+    pub fn script(&self) -> &InputScript {
+        &self.script
+    }
+
+    /// Records the `steps` of the next frame
+    fn record_frame(&mut self, steps: Vec<InputScriptStep>) {
+        if self.frames_recorded > 0 {
+            self.script = std::mem::take(&mut self.script).wait_frames(1);
+        }
+        for step in steps {
+            self.script.push(step);
+        }
+        self.frames_recorded += 1;
+    }
+}
+
+/// Records the input events received this frame into the [`InputScriptRecorder`], if it is recording
+///
+/// Events are always read, so that events received before recording started are not recorded later.
+#[allow(clippy::too_many_arguments)]
+pub fn record_input_script(
+    mut recorder: ResMut<InputScriptRecorder>,
+    gamepad_settings: Option<Res<GamepadSettings>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut mouse_button_events: EventReader<MouseButtonInput>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut gamepad_events: EventReader<GamepadEvent>,
+) {
+    if !recorder.recording {
+        keyboard_events.clear();
+        mouse_button_events.clear();
+        mouse_wheel_events.clear();
+        mouse_motion_events.clear();
+        gamepad_events.clear();
+        return;
+    }
+
+    let button_step = |input: UserInput, state: ButtonState| match state {
+        ButtonState::Pressed => InputScriptStep::Press(input),
+        ButtonState::Released => InputScriptStep::Release(input),
+    };
+    let mut steps = Vec::new();
+
+    for event in keyboard_events.read() {
+        let input = match event.key_code {
+            Some(key_code) => UserInput::from(key_code),
+            None => UserInput::from(bevy::input::keyboard::ScanCode(event.scan_code)),
+        };
+        steps.push(button_step(input, event.state));
+    }
+
+    for event in mouse_button_events.read() {
+        steps.push(button_step(event.button.into(), event.state));
+    }
+
+    // FIXME: MouseScrollUnit is not recorded and is always assumed to be Pixel
+    for event in mouse_wheel_events.read() {
+        for (axis_type, value) in [
+            (MouseWheelAxisType::X, event.x),
+            (MouseWheelAxisType::Y, event.y),
+        ] {
+            if value != 0.0 {
+                steps.push(InputScriptStep::Axis(
+                    SingleAxis::from_value(axis_type, value),
+                    value,
+                ));
+            }
+        }
+    }
+
+    for event in mouse_motion_events.read() {
+        for (axis_type, value) in [
+            (MouseMotionAxisType::X, event.delta.x),
+            (MouseMotionAxisType::Y, event.delta.y),
+        ] {
+            if value != 0.0 {
+                steps.push(InputScriptStep::Axis(
+                    SingleAxis::from_value(axis_type, value),
+                    value,
+                ));
+            }
+        }
+    }
+
+    let default_settings = GamepadSettings::default();
+    let gamepad_settings = gamepad_settings.as_deref().unwrap_or(&default_settings);
+    for event in gamepad_events.read() {
+        match event {
+            GamepadEvent::Button(button_event) => {
+                let button_settings = gamepad_settings.get_button_settings(GamepadButton::new(
+                    button_event.gamepad,
+                    button_event.button_type,
+                ));
+                // Values between the thresholds do not change the state of the button
+                if button_event.value >= button_settings.press_threshold() {
+                    steps.push(InputScriptStep::Press(button_event.button_type.into()));
+                } else if button_event.value <= button_settings.release_threshold() {
+                    steps.push(InputScriptStep::Release(button_event.button_type.into()));
+                }
+            }
+            GamepadEvent::Axis(axis_event) => steps.push(InputScriptStep::Axis(
+                SingleAxis::from_value(axis_event.axis_type, axis_event.value),
+                axis_event.value,
+            )),
+            GamepadEvent::Connection(_) => (),
+        }
+    }
+
+    recorder.record_frame(steps);
+}
+
+impl InputScript {
+    /// Saves this script to the file at `path`, overwriting it if it exists
+    ///
+    /// The script is stored in the `bincode` format, and can be loaded again using [`InputScript::load_from_file`].
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        bincode::serialize_into(writer, self)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+    }
+
+    /// Loads a script saved by [`InputScript::save_to_file`] from the file at `path`
+    pub fn load_from_file(path: impl AsRef<Path>) -> std::io::Result<InputScript> {
+        let reader = BufReader::new(File::open(path)?);
+        bincode::deserialize_from(reader)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::action_state::ActionState;
+    use crate::axislike::DualAxis;
+    use crate::input_map::InputMap;
+    use crate::input_mocking::MockInput;
+    use crate::plugin::InputManagerPlugin;
+    use crate::test_utils::compare_networked_fields;
+    use bevy::input::gamepad::{Gamepad, GamepadAxisType};
+    use bevy::input::keyboard::KeyCode;
+    use bevy::input::mouse::MouseButton;
+    use bevy::input::InputPlugin;
+    use bevy::prelude::{MinimalPlugins, Reflect};
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Jump,
+        Shoot,
+        Scroll,
+        Look,
+        Throttle,
+    }
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            InputPlugin,
+            InputManagerPlugin::<Action>::default(),
+            InputScriptRecorderPlugin,
+        ));
+
+        let mut input_map = InputMap::new([
+            (Action::Jump, UserInput::from(KeyCode::Space)),
+            (Action::Shoot, MouseButton::Left.into()),
+            (Action::Scroll, SingleAxis::mouse_wheel_y().into()),
+            (
+                Action::Throttle,
+                SingleAxis::symmetric(GamepadAxisType::RightZ, 0.1).into(),
+            ),
+        ]);
+        input_map.insert(Action::Look, DualAxis::mouse_motion());
+        app.insert_resource(input_map)
+            .init_resource::<ActionState<Action>>();
+        app.connect_gamepad(Gamepad { id: 0 });
+        app.update();
+
+        app
+    }
+
+    /// Runs the `script` frame by frame, returning the [`ActionState`] after each frame
+    fn run_frames(app: &mut App, script: &InputScript) -> Vec<ActionState<Action>> {
+        script
+            .frames()
+            .iter()
+            .map(|steps| {
+                for step in steps {
+                    step.apply(app);
+                }
+                app.update();
+                app.world.resource::<ActionState<Action>>().clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recorded_inputs_replay_identically() {
+        let original = InputScript::new()
+            .press(KeyCode::Space)
+            .wait_frames(2)
+            .press(MouseButton::Left)
+            .axis(SingleAxis::mouse_wheel_y(), 3.0)
+            .wait_frames(1)
+            .release(KeyCode::Space)
+            .axis(SingleAxis::mouse_motion_x(), -4.0)
+            .axis(SingleAxis::symmetric(GamepadAxisType::RightZ, 0.1), 0.7)
+            .wait_frames(1)
+            .release(MouseButton::Left)
+            .axis(SingleAxis::symmetric(GamepadAxisType::RightZ, 0.1), 0.0)
+            .wait_frames(2);
+
+        let mut recording_app = test_app();
+        recording_app
+            .world
+            .resource_mut::<InputScriptRecorder>()
+            .start();
+        let original_states = run_frames(&mut recording_app, &original);
+        let recorded = recording_app
+            .world
+            .resource_mut::<InputScriptRecorder>()
+            .stop();
+        assert_eq!(recorded.len(), original.len());
+        assert!(original_states[0].just_pressed(&Action::Jump));
+        assert!(original_states[3].pressed(&Action::Throttle));
+
+        let path = std::env::temp_dir().join("leafwing_recorded_inputs_replay_identically.bin");
+        recorded.save_to_file(&path).unwrap();
+        let loaded = InputScript::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, recorded);
+
+        let mut replaying_app = test_app();
+        let replayed_states = run_frames(&mut replaying_app, &loaded);
+
+        for (frame, (original_state, replayed_state)) in original_states
+            .iter()
+            .zip(replayed_states.iter())
+            .enumerate()
+        {
+            if let Some(mismatch) = compare_networked_fields(original_state, replayed_state) {
+                panic!("frame {frame}: {mismatch}");
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_is_recorded_while_stopped() {
+        let mut app = test_app();
+        app.send_input(KeyCode::Space);
+        app.update();
+
+        let mut recorder = app.world.resource_mut::<InputScriptRecorder>();
+        assert!(!recorder.is_recording());
+        recorder.start();
+        app.update();
+
+        let recorded = app.world.resource_mut::<InputScriptRecorder>().stop();
+        assert_eq!(recorded.len(), 1);
+        assert!(recorded.is_empty());
+    }
+}
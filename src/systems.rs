@@ -1,30 +1,73 @@
 //! The systems that power each [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
 
-#[cfg(feature = "ui")]
-use crate::action_driver::ActionStateDriver;
 use crate::{
-    action_state::ActionState, clashing_inputs::ClashStrategy, input_map::InputMap,
-    input_streams::InputStreams, plugin::ToggleActions, Actionlike,
+    accessibility::{AccessibilityLayer, AccessibilitySettings, ActionDoubleTapEvent},
+    action_driver::ActionStateDriver,
+    action_state::{
+        ActionData, ActionState, ActionStateSource, FixedActionState, KeepMirroredActionState,
+        PausedInputs, PrimaryInputSource,
+    },
+    axislike::{AxisType, DualAxisData, TripleAxisData},
+    buttonlike::{AnalogButton, ButtonState},
+    charges::Charges,
+    clashing_inputs::{ClashStrategy, InputClaims},
+    cooldowns::Cooldowns,
+    cursor_world::CursorWorldSource,
+    gamepad_settings::GamepadSettingsOverride,
+    input_map::{AllowedUnboundActions, InputMap, SharedInputMap, UseSharedInputMap},
+    input_map_stack::InputMapStack,
+    input_streams::InputStreams,
+    mouse_gestures::MousePressHistory,
+    plugin::{AssociatedGamepadDisconnected, FreezeOnFocusLost, ToggleActions, WindowFocus},
+    timing::{
+        ActionConsumePolicy, ActionHoldEvent, ConsumePolicy, DoubleTickPolicy, HoldThresholds,
+        TickClock, TimeScalePolicies,
+    },
+    touch::{DoubleTapTracker, TapArbitration, TouchHistory},
+    user_input::InputKind,
+    Actionlike,
 };
 
-use bevy::{ecs::prelude::*, prelude::ScanCode};
 use bevy::{
+    core::FrameCount,
     input::{
-        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        gamepad::{
+            GamepadAxis, GamepadButton, GamepadConnection, GamepadConnectionEvent, Gamepads,
+        },
         keyboard::KeyCode,
         mouse::{MouseButton, MouseMotion, MouseWheel},
+        touch::Touches,
         Axis, Input,
     },
     log::warn,
     math::Vec2,
-    time::{Real, Time},
-    utils::{HashMap, Instant},
+    reflect::{ReflectRef, Typed},
+    time::{Fixed, Real, Time, Virtual},
+    transform::components::GlobalTransform,
+    utils::{HashMap, HashSet, Instant},
+    window::{CursorGrabMode, PrimaryWindow, Window, WindowFocused},
+};
+use bevy::{ecs::prelude::*, prelude::ScanCode};
+#[cfg(feature = "rumble")]
+use {
+    crate::rumble::{ActionRumble, GamepadRumbles},
+    bevy::input::gamepad::{Gamepad, GamepadRumbleRequest},
 };
 
-use crate::action_diff::{ActionDiff, ActionDiffEvent};
+use crate::action_diff::{
+    sort_by_action, ActionDiff, ActionDiffEvent, DiffOwner, DiffOwnerId, DiffOwnerMap, DiffPolicy,
+};
 
+#[cfg(feature = "diagnostics")]
+use crate::diagnostics::InputMapEvaluationTime;
+#[cfg(feature = "egui")]
+use crate::plugin::EguiWantsInput;
+#[cfg(feature = "ui")]
+use crate::ui_blocking::PointerOverUi;
 #[cfg(feature = "ui")]
 use bevy::ui::Interaction;
+#[cfg(feature = "clash_debug")]
+use bevy::{log::debug, utils::Duration};
 #[cfg(feature = "egui")]
 use bevy_egui::EguiContext;
 
@@ -32,35 +75,388 @@ use bevy_egui::EguiContext;
 ///
 /// Clears the just-pressed and just-released values of all [`ActionState`]s.
 /// Also resets the internal `pressed_this_tick` field, used to track whether or not to release an action.
+///
+/// Durations are measured using the [`TickClock`] resource, or [`Time<Real>`] if it does not exist.
+/// Actions that are [`TimeScalePolicy::Unscaled`](crate::timing::TimeScalePolicy::Unscaled) in the [`TimeScalePolicies`] resource
+/// are always measured using [`Time<Real>`].
+///
+/// If the [`HoldThresholds`] resource exists, an [`ActionHoldEvent`] is sent for each threshold crossed by a held action.
+/// Any [`Cooldowns`] are counted down, and any [`Charges`] are recharged, by the same amount of time.
+///
+/// Each [`ActionState`] is ticked at most once per [`FrameCount`], unless the [`DoubleTickPolicy`] resource says otherwise.
+/// Entities with [`PausedInputs`] are not ticked.
+/// If the [`FreezeOnFocusLost`] resource exists, nothing is ticked while the window is unfocused,
+/// and the durations carry on from where they were frozen once it regains focus.
+#[allow(clippy::too_many_arguments)]
 pub fn tick_action_state<A: Actionlike>(
-    mut query: Query<&mut ActionState<A>>,
+    mut query: Query<
+        (
+            Entity,
+            &mut ActionState<A>,
+            Option<&mut Cooldowns<A>>,
+            Option<&mut Charges<A>>,
+        ),
+        Without<PausedInputs>,
+    >,
     action_state: Option<ResMut<ActionState<A>>>,
-    time: Res<Time<Real>>,
-    mut stored_previous_instant: Local<Option<Instant>>,
+    (cooldowns, charges): (Option<ResMut<Cooldowns<A>>>, Option<ResMut<Charges<A>>>),
+    clock: Option<Res<TickClock>>,
+    real_time: Res<Time<Real>>,
+    virtual_time: Res<Time<Virtual>>,
+    frame_count: Option<Res<FrameCount>>,
+    double_tick_policy: Option<Res<DoubleTickPolicy>>,
+    consume_policy: Option<Res<ActionConsumePolicy<A>>>,
+    time_scale_policies: Option<Res<TimeScalePolicies<A>>>,
+    hold_thresholds: Option<Res<HoldThresholds<A>>>,
+    mut hold_events: EventWriter<ActionHoldEvent<A>>,
+    (window_focus, freeze_on_focus_lost, mut frozen): (
+        Option<Res<WindowFocus>>,
+        Option<Res<FreezeOnFocusLost<A>>>,
+        Local<bool>,
+    ),
+    mut stored_previous_instants: Local<Option<(Instant, Instant)>>,
 ) {
     // If this is the very first tick, measure from the start of the app
-    let current_instant = time.last_update().unwrap_or_else(|| time.startup());
-    let previous_instant = stored_previous_instant.unwrap_or_else(|| time.startup());
+    let clock = clock.as_deref().copied().unwrap_or_default();
+    let (current_instant, first_previous_instant) = match clock {
+        TickClock::RealTime => (
+            real_time
+                .last_update()
+                .unwrap_or_else(|| real_time.startup()),
+            real_time.startup(),
+        ),
+        // Timings are stored as instants, so virtual time is measured from the start of the app
+        TickClock::Virtual => (
+            real_time.startup() + virtual_time.elapsed(),
+            real_time.startup(),
+        ),
+        // The manual clock starts wherever it was created, so no time has passed before the first tick
+        TickClock::Manual { now } => (now, now),
+    };
+    // Only virtual time is scaled, so unscaled actions follow the wall clock instead
+    let unscaled_current_instant = match clock {
+        TickClock::Virtual => real_time
+            .last_update()
+            .unwrap_or_else(|| real_time.startup()),
+        _ => current_instant,
+    };
+    // If the clock was swapped for one that is behind, restart from the current instant
+    // rather than measuring a negative duration
+    let (previous_instant, unscaled_previous_instant) = match *stored_previous_instants {
+        Some((previous_instant, unscaled_previous_instant))
+            if previous_instant <= current_instant =>
+        {
+            (previous_instant, unscaled_previous_instant)
+        }
+        _ => (first_previous_instant, first_previous_instant),
+    };
+    let delta = current_instant.saturating_duration_since(previous_instant);
+    let hold_thresholds = hold_thresholds.as_deref();
+
+    // The clock keeps running while frozen, so the time spent unfocused is skipped once focus returns
+    if freeze_on_focus_lost.is_some() && !run_if_window_focused(window_focus) {
+        *frozen = true;
+        *stored_previous_instants = Some((current_instant, unscaled_current_instant));
+        return;
+    }
+    let resumed = std::mem::take(&mut *frozen);
+
+    // Without a frame count, every tick is applied
+    let double_tick_policy = double_tick_policy.as_deref().copied().unwrap_or_default();
+    let time_scale_policies = time_scale_policies.as_deref();
+    let consume_policy = consume_policy.map_or_else(ConsumePolicy::default, |policy| policy.get());
+    let tick = |action_state: &mut ActionState<A>| {
+        action_state.set_default_consume_policy(consume_policy);
+        if resumed {
+            action_state.resume_timings();
+        }
+
+        if let Some(frame_count) = frame_count.as_deref() {
+            if !action_state.accepts_tick_in_frame(frame_count.0, double_tick_policy) {
+                return false;
+            }
+        }
+
+        match time_scale_policies {
+            Some(time_scale_policies) => action_state.tick_with_time_scales(
+                current_instant,
+                previous_instant,
+                unscaled_current_instant,
+                unscaled_previous_instant,
+                time_scale_policies,
+            ),
+            None => action_state.tick(current_instant, previous_instant),
+        }
+        true
+    };
 
     // Only tick the ActionState resource if it exists
+    let mut ticked = true;
     if let Some(mut action_state) = action_state {
-        action_state.tick(current_instant, previous_instant);
+        ticked = tick_and_send_hold_events(
+            None,
+            &mut action_state,
+            tick,
+            hold_thresholds,
+            &mut hold_events,
+        );
+    }
+
+    // Cooldowns and charges are skipped along with extra ticks of their actions
+    if let (true, Some(mut cooldowns)) = (ticked, cooldowns) {
+        cooldowns.tick(delta);
+    }
+    if let (true, Some(mut charges)) = (ticked, charges) {
+        charges.tick(delta);
     }
 
     // Only tick the ActionState components if they exist
-    for mut action_state in query.iter_mut() {
+    for (entity, mut action_state, cooldowns, charges) in query.iter_mut() {
         // If `Time` has not ever been advanced, something has gone horribly wrong
         // and the user probably forgot to add the `core_plugin`.
-        action_state.tick(current_instant, previous_instant);
+        let ticked = tick_and_send_hold_events(
+            Some(entity),
+            &mut action_state,
+            tick,
+            hold_thresholds,
+            &mut hold_events,
+        );
+
+        if let (true, Some(mut cooldowns)) = (ticked, cooldowns) {
+            cooldowns.tick(delta);
+        }
+        if let (true, Some(mut charges)) = (ticked, charges) {
+            charges.tick(delta);
+        }
     }
 
     // Store the previous time in the system
-    *stored_previous_instant = time.last_update();
+    *stored_previous_instants = Some((current_instant, unscaled_current_instant));
+}
+
+/// Ticks the `action_state`, sending an [`ActionHoldEvent`] for each of the `hold_thresholds` crossed by its held actions
+///
+/// Returns `false` if the tick was skipped.
+fn tick_and_send_hold_events<A: Actionlike>(
+    entity: Option<Entity>,
+    action_state: &mut ActionState<A>,
+    tick: impl Fn(&mut ActionState<A>) -> bool,
+    hold_thresholds: Option<&HoldThresholds<A>>,
+    hold_events: &mut EventWriter<ActionHoldEvent<A>>,
+) -> bool {
+    let Some(hold_thresholds) = hold_thresholds else {
+        return tick(action_state);
+    };
+
+    // Consumed actions are released, so they are skipped here too
+    let held: Vec<_> = hold_thresholds
+        .iter()
+        .filter(|(action, _)| action_state.pressed(action))
+        .map(|(action, _)| (action.clone(), action_state.current_duration(action)))
+        .collect();
+
+    // Extra ticks in the same frame don't move the durations
+    if !tick(action_state) {
+        return false;
+    }
+
+    for (action, previous_duration) in held {
+        let current_duration = action_state.current_duration(&action);
+        for threshold in hold_thresholds.crossed(&action, previous_duration, current_duration) {
+            hold_events.send(ActionHoldEvent {
+                action: action.clone(),
+                entity,
+                threshold,
+            });
+        }
+    }
+
+    true
+}
+
+/// Swallows the presses of actions that are on cooldown, and starts the cooldowns of actions that were used
+///
+/// See [`Cooldowns::apply`] for more details.
+pub fn apply_cooldowns<A: Actionlike>(
+    mut query: Query<(&mut ActionState<A>, &mut Cooldowns<A>)>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    cooldowns: Option<ResMut<Cooldowns<A>>>,
+) {
+    if let (Some(mut action_state), Some(mut cooldowns)) = (action_state, cooldowns) {
+        cooldowns.apply(&mut action_state);
+    }
+
+    for (mut action_state, mut cooldowns) in query.iter_mut() {
+        cooldowns.apply(&mut action_state);
+    }
+}
+
+/// Spends the charges of actions that were just pressed, and swallows the presses of actions without any charge left
+///
+/// See [`Charges::apply`] for more details.
+pub fn apply_charges<A: Actionlike>(
+    mut query: Query<(&mut ActionState<A>, &mut Charges<A>)>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    charges: Option<ResMut<Charges<A>>>,
+) {
+    if let (Some(mut action_state), Some(mut charges)) = (action_state, charges) {
+        charges.apply(&mut action_state);
+    }
+
+    for (mut action_state, mut charges) in query.iter_mut() {
+        charges.apply(&mut action_state);
+    }
+}
+
+/// Combines the [`ActionState`]s of every entity into the [`ActionState`] resource
+///
+/// An action is pressed if it is pressed for any entity, and takes the value and axis pair with the largest magnitude.
+/// Actions are released if there are no entities.
+///
+/// This system is used by plugins configured with [`ActionStateAggregation::AllEntities`](crate::plugin::ActionStateAggregation::AllEntities).
+pub fn aggregate_action_states<A: Actionlike>(
+    query: Query<&ActionState<A>>,
+    action_state: Option<ResMut<ActionState<A>>>,
+) {
+    if let Some(mut action_state) = action_state {
+        let action_data = aggregate_action_data(query.iter(), &action_state);
+        action_state.update(action_data);
+    }
+}
+
+/// Copies the [`ActionState`] of the entity marked with [`PrimaryInputSource`] into the [`ActionState`] resource
+///
+/// Actions are released if there is no such entity.
+///
+/// This system is used by plugins configured with [`ActionStateAggregation::PrimaryEntity`](crate::plugin::ActionStateAggregation::PrimaryEntity).
+pub fn aggregate_primary_action_state<A: Actionlike>(
+    query: Query<&ActionState<A>, With<PrimaryInputSource>>,
+    action_state: Option<ResMut<ActionState<A>>>,
+) {
+    if let Some(mut action_state) = action_state {
+        let action_data = aggregate_action_data(query.iter(), &action_state);
+        action_state.update(action_data);
+    }
+}
+
+/// Combines the `sources` into the data used to update the `aggregate` [`ActionState`]
+///
+/// Actions known to the `aggregate` that no source has are released.
+fn aggregate_action_data<'a, A: Actionlike>(
+    sources: impl Iterator<Item = &'a ActionState<A>>,
+    aggregate: &ActionState<A>,
+) -> HashMap<A, ActionData> {
+    let mut action_data: HashMap<A, ActionData> = aggregate
+        .keys()
+        .into_iter()
+        .map(|action| (action, ActionData::default()))
+        .collect();
+
+    for source in sources {
+        for action in source.keys() {
+            let Some(source_datum) = source.action_data(&action) else {
+                continue;
+            };
+            let action_datum = action_data.entry(action).or_default();
+            if source_datum.state.pressed() {
+                action_datum.state = ButtonState::JustPressed;
+            }
+            if source_datum.value.abs() > action_datum.value.abs() {
+                action_datum.value = source_datum.value;
+            }
+            let length = |axis_pair: Option<DualAxisData>| {
+                axis_pair.map_or(-1.0, |axis_pair| axis_pair.length())
+            };
+            if length(source_datum.axis_pair) > length(action_datum.axis_pair) {
+                action_datum.axis_pair = source_datum.axis_pair;
+            }
+            let triple_length = |axis_triple: Option<TripleAxisData>| {
+                axis_triple.map_or(-1.0, |axis_triple| axis_triple.length())
+            };
+            if triple_length(source_datum.axis_triple) > triple_length(action_datum.axis_triple) {
+                action_datum.axis_triple = source_datum.axis_triple;
+            }
+        }
+    }
+
+    action_data
+}
+
+/// Records the presses and releases of each [`ActionState`] in its paired [`FixedActionState`]
+///
+/// A [`FixedActionState`] is added to each entity with an [`ActionState`] that is missing one,
+/// and as a resource if the [`ActionState`] resource exists.
+///
+/// This system is used by plugins configured with [`InputManagerPlugin::use_fixed_action_state`](crate::plugin::InputManagerPlugin::use_fixed_action_state).
+pub fn record_fixed_action_state<A: Actionlike>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &ActionState<A>, Option<&mut FixedActionState<A>>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    fixed_action_state: Option<ResMut<FixedActionState<A>>>,
+) {
+    if let Some(action_state) = action_state {
+        match fixed_action_state {
+            Some(mut fixed_action_state) => fixed_action_state.record_transitions(&action_state),
+            None => {
+                let mut fixed_action_state = FixedActionState::default();
+                fixed_action_state.record_transitions(&action_state);
+                commands.insert_resource(fixed_action_state);
+            }
+        }
+    }
+
+    for (entity, action_state, fixed_action_state) in query.iter_mut() {
+        match fixed_action_state {
+            Some(mut fixed_action_state) => fixed_action_state.record_transitions(action_state),
+            None => {
+                let mut fixed_action_state = FixedActionState::default();
+                fixed_action_state.record_transitions(action_state);
+                commands.entity(entity).insert(fixed_action_state);
+            }
+        }
+    }
+}
+
+/// Advances each [`FixedActionState`] by a single fixed timestep, following its paired [`ActionState`]
+///
+/// Durations are measured using [`Time<Fixed>`].
+///
+/// This system runs in [`FixedUpdate`](bevy::app::FixedUpdate) for plugins configured with
+/// [`InputManagerPlugin::use_fixed_action_state`](crate::plugin::InputManagerPlugin::use_fixed_action_state).
+pub fn tick_fixed_action_state<A: Actionlike>(
+    mut query: Query<(&ActionState<A>, &mut FixedActionState<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    fixed_action_state: Option<ResMut<FixedActionState<A>>>,
+    real_time: Res<Time<Real>>,
+    fixed_time: Res<Time<Fixed>>,
+    mut stored_previous_instant: Local<Option<Instant>>,
+) {
+    // Timings are stored as instants, so fixed time is measured from the start of the app
+    let current_instant = real_time.startup() + fixed_time.elapsed();
+    let previous_instant = stored_previous_instant.unwrap_or_else(|| real_time.startup());
+
+    if let (Some(action_state), Some(mut fixed_action_state)) = (action_state, fixed_action_state) {
+        fixed_action_state.step(&action_state, current_instant, previous_instant);
+    }
+
+    for (action_state, mut fixed_action_state) in query.iter_mut() {
+        fixed_action_state.step(action_state, current_instant, previous_instant);
+    }
+
+    *stored_previous_instant = Some(current_instant);
 }
 
 /// Fetches all of the relevant [`Input`] resources to update [`ActionState`] according to the [`InputMap`].
 ///
 /// Missing resources will be ignored, and treated as if none of the corresponding inputs were pressed.
+///
+/// Under [`ClashStrategy::DelayForChords`], the actions that are being withheld while waiting for a chord are stored here between frames.
+/// So are the first taps of [`TouchGesture::DoubleTap`](crate::touch::TouchGesture::DoubleTap)s, whose single taps are pressed according to the [`TapArbitration`] resource.
+/// If the [`InputClaims`] resource gives `A` a priority, inputs claimed by action types with a higher priority are ignored.
+/// The inputs are then transformed according to the [`AccessibilitySettings`] resource, if it exists.
+/// [`CursorRelative`](crate::cursor_world::CursorRelative) inputs aim from the [`GlobalTransform`] of each entity towards the [`CursorWorldSource`].
+/// [`ScreenEdge`](crate::screen_edge::ScreenEdge) inputs read the cursor in the [`PrimaryWindow`],
+/// whose [`CursorGrabMode`] decides which bindings with a [`CursorGrabCondition`](crate::input_map::CursorGrabCondition) are read.
 #[allow(clippy::too_many_arguments)]
 pub fn update_action_state<A: Actionlike>(
     gamepad_buttons: Res<Input<GamepadButton>>,
@@ -70,17 +466,63 @@ pub fn update_action_state<A: Actionlike>(
     keycodes: Option<Res<Input<KeyCode>>>,
     scan_codes: Option<Res<Input<ScanCode>>>,
     mouse_buttons: Option<Res<Input<MouseButton>>>,
-    mut mouse_wheel: EventReader<MouseWheel>,
-    mut mouse_motion: EventReader<MouseMotion>,
+    (mut mouse_wheel, mut mouse_motion): (EventReader<MouseWheel>, EventReader<MouseMotion>),
     clash_strategy: Res<ClashStrategy>,
-    #[cfg(all(feature = "ui", feature = "block_ui_interactions"))] interactions: Query<
-        &Interaction,
-    >,
-    #[cfg(feature = "egui")] mut maybe_egui: Query<(Entity, &'static mut EguiContext)>,
+    #[cfg(feature = "ui")] (interactions, pointer_over_ui): (
+        Query<(&Interaction, Option<&ActionStateDriver<A>>)>,
+        Option<Res<PointerOverUi>>,
+    ),
+    #[cfg(feature = "egui")] egui_wants_input: Option<Res<EguiWantsInput>>,
+    #[cfg(feature = "diagnostics")] mut evaluation_time: Option<ResMut<InputMapEvaluationTime<A>>>,
     action_state: Option<ResMut<ActionState<A>>>,
     input_map: Option<Res<InputMap<A>>>,
-    mut query: Query<(&mut ActionState<A>, &InputMap<A>)>,
+    mut query: Query<
+        (
+            Entity,
+            &mut ActionState<A>,
+            &InputMap<A>,
+            Option<&GlobalTransform>,
+        ),
+        Without<PausedInputs>,
+    >,
+    // Grouped together to stay within the limit on the number of system parameters
+    (
+        mut pending_chords,
+        mut input_claims,
+        accessibility_settings,
+        mut accessibility_layers,
+        mut double_taps,
+        gamepad_settings,
+        mut held_analog_buttons,
+        (touches, touch_history, mut double_tap_trackers, tap_arbitration),
+        mouse_presses,
+        mut mouse_presses_over_ui,
+        cursor_world_source,
+        windows,
+    ): (
+        Local<HashMap<Option<Entity>, HashMap<A, Instant>>>,
+        Option<ResMut<InputClaims>>,
+        Option<Res<AccessibilitySettings<A>>>,
+        Local<HashMap<Option<Entity>, AccessibilityLayer<A>>>,
+        EventWriter<ActionDoubleTapEvent<A>>,
+        Option<Res<GamepadSettingsOverride>>,
+        Local<HashMap<Option<Entity>, HashSet<AnalogButton>>>,
+        (
+            Option<Res<Touches>>,
+            Option<Res<TouchHistory>>,
+            Local<HashMap<Option<Entity>, DoubleTapTracker>>,
+            Option<Res<TapArbitration>>,
+        ),
+        Option<Res<MousePressHistory>>,
+        Local<HashSet<MouseButton>>,
+        Option<Res<CursorWorldSource>>,
+        Query<&Window, With<PrimaryWindow>>,
+    ),
 ) {
+    let claim_priority = input_claims
+        .as_ref()
+        .and_then(|input_claims| input_claims.priority::<A>());
+
     let gamepad_buttons = gamepad_buttons.into_inner();
     let gamepad_button_axes = gamepad_button_axes.into_inner();
     let gamepad_axes = gamepad_axes.into_inner();
@@ -88,15 +530,22 @@ pub fn update_action_state<A: Actionlike>(
     let keycodes = keycodes.map(|keycodes| keycodes.into_inner());
     let scan_codes = scan_codes.map(|scan_codes| scan_codes.into_inner());
     let mouse_buttons = mouse_buttons.map(|mouse_buttons| mouse_buttons.into_inner());
+    let gamepad_settings = gamepad_settings.map(|gamepad_settings| gamepad_settings.into_inner());
+    let touches = touches.map(|touches| touches.into_inner());
+    let touch_history = touch_history.map(|touch_history| touch_history.into_inner());
+    let mouse_presses = mouse_presses.map(|mouse_presses| mouse_presses.into_inner());
 
     let mouse_wheel: Option<Vec<MouseWheel>> = Some(mouse_wheel.read().cloned().collect());
     let mouse_motion: Vec<MouseMotion> = mouse_motion.read().cloned().collect();
 
+    // Mouse buttons as they were pressed, before the UI and egui could block them
+    let all_mouse_buttons = mouse_buttons;
+
     // If use clicks on a button, do not apply them to the game state
     #[cfg(all(feature = "ui", feature = "block_ui_interactions"))]
     let (mouse_buttons, mouse_wheel) = if interactions
         .iter()
-        .any(|&interaction| interaction != Interaction::None)
+        .any(|(&interaction, _)| interaction != Interaction::None)
     {
         (None, None)
     } else {
@@ -104,32 +553,88 @@ pub fn update_action_state<A: Actionlike>(
     };
 
     // If egui wants to own inputs, don't also apply them to the game state
+    // The actions they pressed are released, and are pressed again once egui lets go of the inputs
     #[cfg(feature = "egui")]
-    let (keycodes, scan_codes) = if maybe_egui
-        .iter_mut()
-        .any(|(_, mut ctx)| ctx.get_mut().wants_keyboard_input())
-    {
+    let egui_wants_input = egui_wants_input.as_deref().copied().unwrap_or_default();
+
+    #[cfg(feature = "egui")]
+    let (keycodes, scan_codes) = if egui_wants_input.keyboard {
         (None, None)
     } else {
         (keycodes, scan_codes)
     };
 
-    // `wants_pointer_input` sometimes returns `false` after clicking or holding a button over a widget,
-    // so `is_pointer_over_area` is also needed.
     #[cfg(feature = "egui")]
-    let (mouse_buttons, mouse_wheel) = if maybe_egui.iter_mut().any(|(_, mut ctx)| {
-        ctx.get_mut().is_pointer_over_area() || ctx.get_mut().wants_pointer_input()
-    }) {
+    let (mouse_buttons, mouse_wheel) = if egui_wants_input.pointer {
         (None, None)
     } else {
         (mouse_buttons, mouse_wheel)
     };
 
+    // Mouse buttons are still seen by the actions that click through the UI
+    #[cfg(feature = "ui")]
+    let (mouse_buttons, unblocked_mouse_buttons) =
+        if pointer_over_ui.is_some_and(|pointer_over_ui| pointer_over_ui.0) {
+            (None, mouse_buttons)
+        } else {
+            (mouse_buttons, None)
+        };
+
+    // Drags that started while the mouse buttons were blocked stay blocked until released,
+    // even if the cursor leaves the UI in the meantime
+    if let Some(all_mouse_buttons) = all_mouse_buttons {
+        mouse_presses_over_ui.retain(|&button| all_mouse_buttons.pressed(button));
+        for &button in all_mouse_buttons.get_just_pressed() {
+            if mouse_buttons.is_none() {
+                mouse_presses_over_ui.insert(button);
+            } else {
+                mouse_presses_over_ui.remove(&button);
+            }
+        }
+    }
+
+    let cursor_world_position = cursor_world_source.and_then(|source| source.0);
+    let primary_window = windows.get_single().ok();
+    let cursor_window_position = primary_window
+        .filter(|window| window.focused)
+        .and_then(Window::cursor_position);
+    let window_size = primary_window.map(|window| Vec2::new(window.width(), window.height()));
+    let cursor_grab_mode =
+        primary_window.map_or(CursorGrabMode::None, |window| window.cursor.grab_mode);
+
+    // we use None to represent the global ActionState
     let resources = input_map
         .zip(action_state)
-        .map(|(input_map, action_state)| (Mut::from(action_state), input_map.into_inner()));
+        .map(|(input_map, action_state)| {
+            (None, Mut::from(action_state), input_map.into_inner(), None)
+        });
+    let components = query
+        .iter_mut()
+        .map(|(entity, action_state, input_map, transform)| {
+            (Some(entity), action_state, input_map, transform)
+        });
 
-    for (mut action_state, input_map) in query.iter_mut().chain(resources) {
+    let mut visited = HashSet::new();
+    for (maybe_entity, mut action_state, input_map, transform) in components.chain(resources) {
+        visited.insert(maybe_entity);
+        // Analog buttons stay pressed until they fall to their release threshold,
+        // so we need to know which of them were pressed on the previous frame
+        let previously_held = held_analog_buttons
+            .remove(&maybe_entity)
+            .unwrap_or_default();
+        let touch_joystick_regions = input_map.touch_joystick_regions();
+        // Double taps are made across frames, so we need to remember their first taps
+        let bound_double_taps = input_map.double_taps();
+        let mut double_tap_tracker = double_tap_trackers
+            .remove(&maybe_entity)
+            .unwrap_or_default();
+        if let Some(touch_history) = touch_history {
+            let tap_arbitration = tap_arbitration.as_deref().copied().unwrap_or_default();
+            double_tap_tracker.update(&bound_double_taps, touch_history, tap_arbitration);
+        }
+        if !bound_double_taps.is_empty() {
+            double_tap_trackers.insert(maybe_entity, double_tap_tracker.clone());
+        }
         let input_streams = InputStreams {
             gamepad_buttons,
             gamepad_button_axes,
@@ -140,28 +645,610 @@ pub fn update_action_state<A: Actionlike>(
             mouse_buttons,
             mouse_wheel: mouse_wheel.clone(),
             mouse_motion: mouse_motion.clone(),
+            mouse_presses,
+            mouse_presses_over_ui: Some(&mouse_presses_over_ui),
+            cursor_world_position,
+            anchor_position: transform.map(|transform| transform.translation().truncate()),
+            cursor_window_position,
+            window_size,
+            cursor_grab_mode,
+            touches,
+            touch_history,
+            touch_joystick_regions: &touch_joystick_regions,
+            double_taps: Some(&double_tap_tracker),
             associated_gamepad: input_map.gamepad(),
+            gamepad_settings,
+            held_analog_buttons: Some(&previously_held),
         };
 
-        action_state.update(input_map.which_pressed(&input_streams, *clash_strategy));
+        let held: HashSet<AnalogButton> = input_map
+            .analog_buttons()
+            .into_iter()
+            .filter(|&analog_button| {
+                input_streams.button_pressed(InputKind::AnalogButton(analog_button))
+            })
+            .collect();
+        if !held.is_empty() {
+            held_analog_buttons.insert(maybe_entity, held);
+        }
+
+        // Only measured while the diagnostics are enabled
+        #[cfg(feature = "diagnostics")]
+        let evaluation_started = evaluation_time.is_some().then(Instant::now);
+
+        let mut action_data = input_map.which_pressed(&input_streams, *clash_strategy);
+
+        #[cfg(feature = "ui")]
+        if unblocked_mouse_buttons.is_some() && !input_map.actions_clicking_through_ui().is_empty()
+        {
+            let unblocked_streams = InputStreams {
+                mouse_buttons: unblocked_mouse_buttons,
+                mouse_presses_over_ui: None,
+                ..input_streams.clone()
+            };
+            let mut unblocked_action_data =
+                input_map.which_pressed(&unblocked_streams, *clash_strategy);
+            for action in input_map.actions_clicking_through_ui() {
+                if let Some(action_datum) = unblocked_action_data.remove(action) {
+                    action_data.insert(action.clone(), action_datum);
+                }
+            }
+        }
+
+        #[cfg(feature = "diagnostics")]
+        if let (Some(evaluation_time), Some(evaluation_started)) =
+            (evaluation_time.as_mut(), evaluation_started)
+        {
+            evaluation_time.elapsed += evaluation_started.elapsed();
+        }
+
+        let now = action_state
+            .last_tick_instant()
+            .unwrap_or_else(Instant::now);
+        let clash_strategy = input_map.clash_strategy().unwrap_or(*clash_strategy);
+        let backdated = if let ClashStrategy::DelayForChords { window } = clash_strategy {
+            input_map.delay_for_chords(
+                &mut action_data,
+                |action| action_state.pressed(action),
+                &input_streams,
+                pending_chords.entry(maybe_entity).or_default(),
+                window,
+                now,
+            )
+        } else {
+            pending_chords.remove(&maybe_entity);
+            Vec::new()
+        };
+
+        let suppressed = match (&mut input_claims, claim_priority) {
+            (Some(input_claims), Some(priority)) => {
+                input_map.respect_claims(&mut action_data, &input_streams, input_claims, priority)
+            }
+            _ => Vec::new(),
+        };
+
+        // Actions held down by `bevy::ui` buttons count as pressed,
+        // so that they are not released and pressed again on every frame that the button is held
+        #[cfg(feature = "ui")]
+        if let Some(entity) = maybe_entity {
+            for (_, action_state_driver) in interactions
+                .iter()
+                .filter(|(&interaction, _)| interaction == Interaction::Pressed)
+            {
+                let Some(action_state_driver) = action_state_driver else {
+                    continue;
+                };
+                if action_state_driver
+                    .targets
+                    .iter()
+                    .any(|&target| target == entity)
+                {
+                    let action_datum = action_data
+                        .entry(action_state_driver.action.clone())
+                        .or_default();
+                    if !action_datum.state.pressed() {
+                        action_datum.state = ButtonState::JustPressed;
+                        action_datum.value = 1.0;
+                    }
+                }
+            }
+        }
+
+        match accessibility_settings.as_deref() {
+            Some(settings) if !settings.is_empty() => {
+                let accessibility_layer = accessibility_layers.entry(maybe_entity).or_default();
+                for action in accessibility_layer.apply(
+                    settings,
+                    &mut action_data,
+                    |action| action_state.consumed(action),
+                    now,
+                ) {
+                    double_taps.send(ActionDoubleTapEvent {
+                        action,
+                        entity: maybe_entity,
+                    });
+                }
+            }
+            _ => {
+                accessibility_layers.remove(&maybe_entity);
+            }
+        }
+
+        action_state.update(action_data);
+        for (action, started) in backdated {
+            action_state.backdate_press(&action, started, now);
+        }
+        // Keys that are still held once the claim ends should not press the suppressed actions
+        for action in suppressed {
+            if let Some(action_datum) = action_state.action_data_mut(&action) {
+                action_datum.consumed = true;
+            }
+        }
+    }
+
+    // Forget the entities that were despawned, or that no longer have an `ActionState` or `InputMap`
+    pending_chords.retain(|maybe_entity, _| visited.contains(maybe_entity));
+    accessibility_layers.retain(|maybe_entity, _| visited.contains(maybe_entity));
+}
+
+/// Records whether any egui context wants the keyboard or the pointer in the [`EguiWantsInput`] resource
+#[cfg(feature = "egui")]
+pub fn update_egui_wants_input(
+    mut contexts: Query<&mut EguiContext>,
+    mut egui_wants_input: ResMut<EguiWantsInput>,
+) {
+    let mut wants_input = EguiWantsInput::default();
+    for mut context in contexts.iter_mut() {
+        let context = context.get_mut();
+        wants_input.keyboard |= context.wants_keyboard_input();
+        // `wants_pointer_input` sometimes returns `false` after clicking or holding a button over a widget,
+        // so `is_pointer_over_area` is also needed.
+        wants_input.pointer |= context.is_pointer_over_area() || context.wants_pointer_input();
+    }
+
+    egui_wants_input.set_if_neq(wants_input);
+}
+
+/// Inserts a default [`ActionState`] on each entity that has an [`InputMap`] but no [`ActionState`]
+pub fn insert_missing_action_states<A: Actionlike>(
+    mut commands: Commands,
+    query: Query<Entity, (With<InputMap<A>>, Without<ActionState<A>>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(ActionState::<A>::default());
+    }
+}
+
+/// Lets the [`ActionState`] of each entity whose [`PausedInputs`] were removed carry on from where it was frozen
+///
+/// See [`ActionState::resume_timings`] for more details.
+pub fn resume_action_states<A: Actionlike>(
+    mut resumed: RemovedComponents<PausedInputs>,
+    mut query: Query<&mut ActionState<A>, Without<PausedInputs>>,
+) {
+    for entity in resumed.read() {
+        if let Ok(mut action_state) = query.get_mut(entity) {
+            action_state.resume_timings();
+        }
+    }
+}
+
+/// Warns about entities that were given an [`ActionState`] without anything to press its actions
+///
+/// Entities with an [`InputMap`], an [`InputMapStack`] or an [`ActionStateSource`], or targeted by an [`ActionStateDriver`], are fine.
+pub fn warn_orphaned_action_states<A: Actionlike>(
+    query: Query<
+        Entity,
+        (
+            Added<ActionState<A>>,
+            Without<InputMap<A>>,
+            Without<InputMapStack<A>>,
+            Without<ActionStateSource>,
+        ),
+    >,
+    drivers: Query<&ActionStateDriver<A>>,
+) {
+    for entity in query.iter() {
+        let driven = drivers
+            .iter()
+            .any(|driver| driver.targets.iter().any(|&target| target == entity));
+        if !driven {
+            warn!(
+                "{entity:?} has an ActionState<{}> but no InputMap: its actions are never pressed by inputs",
+                A::short_type_path()
+            );
+        }
+    }
+}
+
+/// Warns about the actions that have no inputs bound to them, listing them along with the entity of the [`InputMap`]
+///
+/// Maps are checked when they are added or changed, including the [`InputMap`] resource.
+/// Actions in the [`AllowedUnboundActions`] resource are not reported.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually,
+/// typically to the `Update` schedule.
+pub fn warn_unbound_actions<A: Actionlike + Typed>(
+    query: Query<(Entity, &InputMap<A>), Changed<InputMap<A>>>,
+    input_map: Option<Res<InputMap<A>>>,
+    allowed: Option<Res<AllowedUnboundActions<A>>>,
+) {
+    let unbound_names = |input_map: &InputMap<A>| -> Vec<String> {
+        input_map
+            .unbound_actions()
+            .into_iter()
+            .filter(|action| !matches!(&allowed, Some(allowed) if allowed.0.contains(action)))
+            .filter_map(|action| match action.reflect_ref() {
+                ReflectRef::Enum(action) => Some(action.variant_name().to_string()),
+                _ => None,
+            })
+            .collect()
+    };
+
+    for (entity, input_map) in query.iter() {
+        let unbound = unbound_names(input_map);
+        if !unbound.is_empty() {
+            warn!(
+                unbound = ?unbound,
+                "The InputMap<{}> of {entity:?} has no inputs bound to some actions",
+                A::short_type_path()
+            );
+        }
+    }
+
+    if let Some(input_map) = input_map.filter(Res::is_changed) {
+        let unbound = unbound_names(&input_map);
+        if !unbound.is_empty() {
+            warn!(
+                unbound = ?unbound,
+                "The InputMap<{}> resource has no inputs bound to some actions",
+                A::short_type_path()
+            );
+        }
+    }
+}
+
+/// Copies the [`ActionState`] of the source of each [`ActionStateSource`] onto the entity that mirrors it
+///
+/// Only the actions that differ are copied, so mirrors are only marked as changed when their source's actions change.
+/// Mirrors whose source was lost are released, unless they are marked with [`KeepMirroredActionState`].
+pub fn relay_action_states<A: Actionlike>(
+    mut commands: Commands,
+    mut sources: Query<
+        (&mut ActionState<A>, Has<KeepMirroredActionState>),
+        Without<ActionStateSource>,
+    >,
+    mut mirrors: Query<(
+        Entity,
+        &ActionStateSource,
+        Option<&mut ActionState<A>>,
+        Has<KeepMirroredActionState>,
+    )>,
+    mut removed_sources: RemovedComponents<ActionStateSource>,
+) {
+    for (entity, &ActionStateSource(source), mirror, keep_state) in mirrors.iter_mut() {
+        match (sources.get(source), mirror) {
+            (Ok((source_state, _)), Some(mut mirror)) => {
+                if mirror.bypass_change_detection().mirror(source_state) {
+                    mirror.set_changed();
+                }
+            }
+            (Ok((source_state, _)), None) => {
+                commands.entity(entity).insert(source_state.clone());
+            }
+            (Err(_), Some(mut mirror)) if !keep_state => release_mirror(&mut mirror),
+            (Err(_), _) => (),
+        }
+    }
+
+    // Entities that stopped mirroring are no longer excluded from the sources
+    for entity in removed_sources.read() {
+        if let Ok((mut action_state, false)) = sources.get_mut(entity) {
+            release_mirror(&mut action_state);
+        }
+    }
+}
+
+/// Releases the actions of a mirror that lost its source, only triggering change detection once
+fn release_mirror<A: Actionlike>(action_state: &mut Mut<ActionState<A>>) {
+    if !action_state.get_pressed().is_empty() {
+        action_state.release_all();
+    }
+}
+
+/// Replaces the [`InputMap`] of each entity whose [`InputMapStack`] changed with the [`InputMapStack::effective_map`]
+///
+/// Actions that are no longer bound are released, as updating the [`ActionState`] only changes the bound actions.
+/// The gamepad of the [`InputMap`] is kept if the new map does not choose one.
+pub fn apply_input_map_stacks<A: Actionlike>(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &InputMapStack<A>,
+            Option<&mut InputMap<A>>,
+            Option<&mut ActionState<A>>,
+        ),
+        Changed<InputMapStack<A>>,
+    >,
+) {
+    for (entity, stack, input_map, action_state) in query.iter_mut() {
+        replace_input_map(
+            &mut commands,
+            entity,
+            stack.effective_map(),
+            input_map,
+            action_state,
+        );
+    }
+}
+
+/// Copies the [`SharedInputMap`] into the [`InputMap`] of each entity marked with [`UseSharedInputMap`]
+///
+/// Every marked entity is updated when the resource changes, and newly marked entities are updated right away.
+/// Like [`apply_input_map_stacks`], actions that are no longer bound are released,
+/// and the gamepad of the [`InputMap`] is kept if the shared map does not choose one.
+pub fn sync_shared_input_maps<A: Actionlike>(
+    mut commands: Commands,
+    shared_input_map: Option<Res<SharedInputMap<A>>>,
+    mut query: Query<(
+        Entity,
+        Ref<UseSharedInputMap>,
+        Option<&mut InputMap<A>>,
+        Option<&mut ActionState<A>>,
+    )>,
+) {
+    let Some(shared_input_map) = shared_input_map else {
+        return;
+    };
+    let resource_changed = shared_input_map.is_changed();
+
+    for (entity, marker, input_map, action_state) in query.iter_mut() {
+        if !resource_changed && !marker.is_added() {
+            continue;
+        }
+
+        replace_input_map(
+            &mut commands,
+            entity,
+            shared_input_map.0.clone(),
+            input_map,
+            action_state,
+        );
+    }
+}
+
+/// Replaces the [`InputMap`] of the `entity` with `new_map`, releasing the actions that are no longer bound
+fn replace_input_map<A: Actionlike>(
+    commands: &mut Commands,
+    entity: Entity,
+    mut new_map: InputMap<A>,
+    input_map: Option<Mut<InputMap<A>>>,
+    action_state: Option<Mut<ActionState<A>>>,
+) {
+    if let Some(mut action_state) = action_state {
+        for action in action_state.get_pressed() {
+            if new_map.get(&action).is_none() {
+                action_state.release(&action);
+            }
+        }
+    }
+
+    match input_map {
+        Some(mut input_map) => {
+            if let (None, Some(gamepad)) = (new_map.gamepad(), input_map.gamepad()) {
+                new_map.set_gamepad(gamepad);
+            }
+            input_map.set_if_neq(new_map);
+        }
+        None => {
+            commands.entity(entity).insert(new_map);
+        }
     }
 }
 
+/// Collects the pulses caused by each [`ActionRumble`] into the [`GamepadRumbles`] resource
+///
+/// The gamepad associated with the [`InputMap`] rumbles, or the first gamepad if there is none.
+/// Rumbles that last while actions are held are collected by [`collect_held_action_rumbles`] instead.
+#[cfg(feature = "rumble")]
+pub fn collect_action_rumbles<A: Actionlike>(
+    query: Query<(&ActionState<A>, &ActionRumble<A>, Option<&InputMap<A>>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    action_rumble: Option<Res<ActionRumble<A>>>,
+    input_map: Option<Res<InputMap<A>>>,
+    mut rumbles: ResMut<GamepadRumbles>,
+) {
+    for_each_action_rumble(
+        &query,
+        action_state,
+        action_rumble,
+        input_map,
+        |action_rumble, action_state, gamepad| {
+            action_rumble.collect_pulses(action_state, gamepad, &mut rumbles);
+        },
+    );
+}
+
+/// Collects the rumbles of the actions held according to each [`ActionRumble`] into the [`GamepadRumbles`] resource
+///
+/// This runs at the end of the frame, right before [`send_gamepad_rumbles`],
+/// so that the rumble stops on the same frame as the action is consumed or its entity is despawned.
+#[cfg(feature = "rumble")]
+pub fn collect_held_action_rumbles<A: Actionlike>(
+    query: Query<(&ActionState<A>, &ActionRumble<A>, Option<&InputMap<A>>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    action_rumble: Option<Res<ActionRumble<A>>>,
+    input_map: Option<Res<InputMap<A>>>,
+    mut rumbles: ResMut<GamepadRumbles>,
+) {
+    for_each_action_rumble(
+        &query,
+        action_state,
+        action_rumble,
+        input_map,
+        |action_rumble, action_state, gamepad| {
+            action_rumble.collect_held(action_state, gamepad, &mut rumbles);
+        },
+    );
+}
+
+/// Calls `collect` with each [`ActionRumble`], its [`ActionState`], and the gamepad that it rumbles
+#[cfg(feature = "rumble")]
+fn for_each_action_rumble<A: Actionlike>(
+    query: &Query<(&ActionState<A>, &ActionRumble<A>, Option<&InputMap<A>>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    action_rumble: Option<Res<ActionRumble<A>>>,
+    input_map: Option<Res<InputMap<A>>>,
+    mut collect: impl FnMut(&ActionRumble<A>, &ActionState<A>, Gamepad),
+) {
+    let gamepad = |input_map: Option<&InputMap<A>>| {
+        input_map
+            .and_then(InputMap::gamepad)
+            .unwrap_or(Gamepad::new(0))
+    };
+
+    if let (Some(action_state), Some(action_rumble)) = (action_state, action_rumble) {
+        collect(&action_rumble, &action_state, gamepad(input_map.as_deref()));
+    }
+
+    for (action_state, action_rumble, input_map) in query.iter() {
+        collect(action_rumble, action_state, gamepad(input_map));
+    }
+}
+
+/// Sends the [`GamepadRumbleRequest`]s needed to play the strongest of the [`GamepadRumbles`] on each gamepad
+///
+/// Rumbles are timed in real time, so that they don't last longer while the game is paused.
+#[cfg(feature = "rumble")]
+pub fn send_gamepad_rumbles(
+    real_time: Res<Time<Real>>,
+    mut rumbles: ResMut<GamepadRumbles>,
+    mut requests: EventWriter<GamepadRumbleRequest>,
+) {
+    requests.send_batch(rumbles.requests(real_time.delta()));
+}
+
+/// Forgets the inputs that were claimed during the previous frame
+///
+/// See [`InputClaims`] for more details.
+pub fn clear_input_claims(mut input_claims: ResMut<InputClaims>) {
+    input_claims.clear();
+}
+
+/// Logs the actions that are suppressed by clashes at the debug level, to help find out why a binding does not seem to work
+///
+/// The pressed actions of each [`InputMap`] are recomputed using [`InputMap::which_pressed_verbose`],
+/// so this system should only be used while debugging.
+/// Each action is logged at most once per second for as long as it is suppressed by the same action.
+#[cfg(feature = "clash_debug")]
+pub fn log_clash_suppressions<A: Actionlike>(
+    world: &mut World,
+    mut last_logged: Local<HashMap<(Option<Entity>, A, A), Instant>>,
+) {
+    const LOG_INTERVAL: Duration = Duration::from_secs(1);
+
+    let clash_strategy = world
+        .get_resource::<ClashStrategy>()
+        .copied()
+        .unwrap_or_default();
+    let now = Instant::now();
+
+    let mut query = world.query::<(Entity, &InputMap<A>)>();
+    let input_maps = query
+        .iter(world)
+        .map(|(entity, input_map)| (Some(entity), input_map))
+        .chain(
+            world
+                .get_resource::<InputMap<A>>()
+                .map(|input_map| (None, input_map)),
+        );
+
+    for (maybe_entity, input_map) in input_maps {
+        let input_streams = InputStreams::from_world(world, input_map.gamepad());
+        let (_, suppressed) = input_map.which_pressed_verbose(&input_streams, clash_strategy);
+
+        for record in suppressed {
+            let key = (maybe_entity, record.action, record.suppressed_by);
+            let recently_logged = last_logged
+                .get(&key)
+                .is_some_and(|logged| now.duration_since(*logged) < LOG_INTERVAL);
+            if recently_logged {
+                continue;
+            }
+
+            debug!(
+                "{:?} was suppressed by {:?}, which clashes with its pressed inputs {:?}",
+                key.1.as_reflect(),
+                key.2.as_reflect(),
+                record.inputs
+            );
+            last_logged.insert(key, now);
+        }
+    }
+
+    last_logged.retain(|_, logged| now.duration_since(*logged) < LOG_INTERVAL);
+}
+
 /// When a button with a component of type `A` is clicked, press the corresponding action in the [`ActionState`]
 ///
 /// The action triggered is determined by the variant stored in your UI-defined button.
+/// The action stays pressed for as long as the [`Interaction`] is [`Interaction::Pressed`],
+/// and is released once the pointer is let go, leaves the button, or the button is despawned.
+///
+/// Actions in the [`InputMap`] of the target are kept pressed by [`update_action_state`] instead,
+/// and are released by it once neither the button nor any of their inputs are pressed.
 #[cfg(feature = "ui")]
 pub fn update_action_state_from_interaction<A: Actionlike>(
-    ui_query: Query<(&Interaction, &ActionStateDriver<A>)>,
-    mut action_state_query: Query<&mut ActionState<A>>,
+    ui_query: Query<(Entity, &Interaction, &ActionStateDriver<A>)>,
+    mut action_state_query: Query<(&mut ActionState<A>, Option<&InputMap<A>>, Has<PausedInputs>)>,
+    mut held_buttons: Local<bevy::utils::HashMap<Entity, ActionStateDriver<A>>>,
 ) {
-    for (&interaction, action_state_driver) in ui_query.iter() {
-        if interaction == Interaction::Pressed {
-            for entity in action_state_driver.targets.iter() {
-                let mut action_state = action_state_query
-                    .get_mut(*entity)
-                    .expect("Entity does not exist, or does not have an `ActionState` component.");
-                action_state.press(&action_state_driver.action.clone());
+    // Buttons despawned while held never stop being pressed, so their actions are released here
+    let despawned: Vec<Entity> = held_buttons
+        .keys()
+        .filter(|&&button| !ui_query.contains(button))
+        .copied()
+        .collect();
+    for button in despawned {
+        let action_state_driver = held_buttons.remove(&button).unwrap();
+        let action = &action_state_driver.action;
+        for entity in action_state_driver.targets.iter() {
+            // The targets may have been despawned along with the button
+            let Ok((mut action_state, input_map, paused)) = action_state_query.get_mut(*entity)
+            else {
+                continue;
+            };
+            let driven_by_inputs =
+                input_map.is_some_and(|input_map| input_map.get(action).is_some());
+            if !paused && !driven_by_inputs {
+                action_state.release(action);
+            }
+        }
+    }
+
+    for (button, &interaction, action_state_driver) in ui_query.iter() {
+        let pressed = interaction == Interaction::Pressed;
+        if pressed {
+            held_buttons.insert(button, action_state_driver.clone());
+        } else if held_buttons.remove(&button).is_none() {
+            continue;
+        }
+
+        let action = &action_state_driver.action;
+        for entity in action_state_driver.targets.iter() {
+            let (mut action_state, input_map, paused) = action_state_query
+                .get_mut(*entity)
+                .expect("Entity does not exist, or does not have an `ActionState` component.");
+            if paused {
+                continue;
+            }
+            let driven_by_inputs =
+                input_map.is_some_and(|input_map| input_map.get(action).is_some());
+            if pressed {
+                action_state.press(action);
+            } else if !driven_by_inputs {
+                action_state.release(action);
             }
         }
     }
@@ -170,13 +1257,21 @@ pub fn update_action_state_from_interaction<A: Actionlike>(
 /// Generates an [`Events`] stream of [`ActionDiff`] from [`ActionState`]
 ///
 /// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+///
+/// By default, diffs are only sent when an action changes.
+/// Insert a [`DiffPolicy`] resource to periodically resend the state of held actions, or of the entire [`ActionState`].
 pub fn generate_action_diffs<A: Actionlike>(
     action_state: Option<ResMut<ActionState<A>>>,
     action_state_query: Query<(Entity, &ActionState<A>)>,
+    diff_policy: Option<Res<DiffPolicy>>,
     mut action_diffs: EventWriter<ActionDiffEvent<A>>,
     mut previous_values: Local<HashMap<A, HashMap<Option<Entity>, f32>>>,
     mut previous_axis_pairs: Local<HashMap<A, HashMap<Option<Entity>, Vec2>>>,
+    mut tick: Local<u32>,
 ) {
+    *tick = tick.wrapping_add(1);
+    let diff_policy = diff_policy.map(|policy| *policy).unwrap_or_default();
+
     // we use None to represent the global ActionState
     let action_state_iter = action_state_query
         .iter()
@@ -187,126 +1282,399 @@ pub fn generate_action_diffs<A: Actionlike>(
                 .map(|action_state| (None, action_state.as_ref())),
         );
     for (maybe_entity, action_state) in action_state_iter {
-        let mut diffs = vec![];
-        for action in action_state.get_just_pressed() {
-            let Some(action_data) = action_state.action_data(&action) else {
-                warn!("Action in ActionDiff has no data: was it generated correctly?");
-                continue;
-            };
+        let diffs = diffs_since_last_run(
+            &maybe_entity,
+            action_state,
+            diff_policy,
+            *tick,
+            &mut previous_values,
+            &mut previous_axis_pairs,
+        );
+        if !diffs.is_empty() {
+            action_diffs.send(ActionDiffEvent {
+                owner: maybe_entity,
+                action_diffs: diffs,
+            });
+        }
+    }
+}
 
-            match action_data.axis_pair {
-                Some(axis_pair) => {
-                    diffs.push(ActionDiff::AxisPairChanged {
+/// Generates an [`Events`] stream of [`ActionDiff`] from [`ActionState`], tagged with the [`DiffOwner`] id of their entity
+///
+/// This works like [`generate_action_diffs`], but identifies owners using an id that is stable across the network,
+/// rather than an [`Entity`].
+/// Entities with an [`ActionState`] but no [`DiffOwner<Id>`] component are ignored.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+pub fn generate_action_diffs_by_id<A: Actionlike, Id: DiffOwnerId>(
+    action_state: Option<ResMut<ActionState<A>>>,
+    action_state_query: Query<(&DiffOwner<Id>, &ActionState<A>)>,
+    diff_policy: Option<Res<DiffPolicy>>,
+    mut action_diffs: EventWriter<ActionDiffEvent<A, Id>>,
+    mut previous_values: Local<HashMap<A, HashMap<Option<Id>, f32>>>,
+    mut previous_axis_pairs: Local<HashMap<A, HashMap<Option<Id>, Vec2>>>,
+    mut tick: Local<u32>,
+) {
+    *tick = tick.wrapping_add(1);
+    let diff_policy = diff_policy.map(|policy| *policy).unwrap_or_default();
+
+    // we use None to represent the global ActionState
+    let action_state_iter = action_state_query
+        .iter()
+        .map(|(owner, action_state)| (Some(owner.0.clone()), action_state))
+        .chain(
+            action_state
+                .as_ref()
+                .map(|action_state| (None, action_state.as_ref())),
+        );
+    for (owner, action_state) in action_state_iter {
+        let diffs = diffs_since_last_run(
+            &owner,
+            action_state,
+            diff_policy,
+            *tick,
+            &mut previous_values,
+            &mut previous_axis_pairs,
+        );
+        if !diffs.is_empty() {
+            action_diffs.send(ActionDiffEvent {
+                owner,
+                action_diffs: diffs,
+            });
+        }
+    }
+}
+
+/// Computes the [`ActionDiff`]s needed to bring a mirror of the `owner`'s [`ActionState`] up to date,
+/// sorted by action
+fn diffs_since_last_run<A: Actionlike, Id: DiffOwnerId>(
+    owner: &Option<Id>,
+    action_state: &ActionState<A>,
+    diff_policy: DiffPolicy,
+    tick: u32,
+    previous_values: &mut HashMap<A, HashMap<Option<Id>, f32>>,
+    previous_axis_pairs: &mut HashMap<A, HashMap<Option<Id>, Vec2>>,
+) -> Vec<ActionDiff<A>> {
+    let mut diffs = vec![];
+    for action in action_state.get_just_pressed() {
+        let Some(action_data) = action_state.action_data(&action) else {
+            warn!("Action in ActionDiff has no data: was it generated correctly?");
+            continue;
+        };
+
+        match action_data.axis_pair {
+            Some(axis_pair) => {
+                diffs.push(ActionDiff::AxisPairChanged {
+                    action: action.clone(),
+                    axis_pair: axis_pair.into(),
+                    pressed: true,
+                });
+                previous_axis_pairs
+                    .raw_entry_mut()
+                    .from_key(&action)
+                    .or_insert_with(|| (action.clone(), HashMap::default()))
+                    .1
+                    .insert(owner.clone(), axis_pair.xy());
+            }
+            None => {
+                let value = action_data.value;
+
+                diffs.push(if value == 1. {
+                    ActionDiff::Pressed {
                         action: action.clone(),
-                        axis_pair: axis_pair.into(),
-                    });
-                    previous_axis_pairs
-                        .raw_entry_mut()
-                        .from_key(&action)
-                        .or_insert_with(|| (action.clone(), HashMap::default()))
-                        .1
-                        .insert(maybe_entity, axis_pair.xy());
-                }
-                None => {
-                    let value = action_data.value;
-
-                    diffs.push(if value == 1. {
-                        ActionDiff::Pressed {
-                            action: action.clone(),
-                        }
-                    } else {
-                        ActionDiff::ValueChanged {
-                            action: action.clone(),
-                            value,
-                        }
-                    });
-                    previous_values
-                        .raw_entry_mut()
-                        .from_key(&action)
-                        .or_insert_with(|| (action.clone(), HashMap::default()))
-                        .1
-                        .insert(maybe_entity, value);
-                }
+                    }
+                } else {
+                    ActionDiff::ValueChanged {
+                        action: action.clone(),
+                        value,
+                        pressed: true,
+                    }
+                });
+                previous_values
+                    .raw_entry_mut()
+                    .from_key(&action)
+                    .or_insert_with(|| (action.clone(), HashMap::default()))
+                    .1
+                    .insert(owner.clone(), value);
             }
         }
-        for action in action_state.get_pressed() {
-            if action_state.just_pressed(&action) {
-                continue;
-            }
+    }
+    for action in action_state.get_pressed() {
+        if action_state.just_pressed(&action) {
+            continue;
+        }
 
-            let Some(action_data) = action_state.action_data(&action) else {
-                warn!("Action in ActionState has no data: was it generated correctly?");
-                continue;
-            };
+        let Some(action_data) = action_state.action_data(&action) else {
+            warn!("Action in ActionState has no data: was it generated correctly?");
+            continue;
+        };
 
-            match action_data.axis_pair {
-                Some(axis_pair) => {
-                    let previous_axis_pairs = previous_axis_pairs.get_mut(&action).unwrap();
+        match action_data.axis_pair {
+            Some(axis_pair) => {
+                let previous_axis_pairs = previous_axis_pairs.get_mut(&action).unwrap();
 
-                    if let Some(previous_axis_pair) = previous_axis_pairs.get(&maybe_entity) {
-                        if *previous_axis_pair == axis_pair.xy() {
-                            continue;
-                        }
+                if let Some(previous_axis_pair) = previous_axis_pairs.get(owner) {
+                    if *previous_axis_pair == axis_pair.xy() {
+                        continue;
                     }
-                    diffs.push(ActionDiff::AxisPairChanged {
-                        action: action.clone(),
-                        axis_pair: axis_pair.into(),
-                    });
-                    previous_axis_pairs.insert(maybe_entity, axis_pair.xy());
                 }
-                None => {
-                    let value = action_data.value;
-                    let previous_values = previous_values.get_mut(&action).unwrap();
-
-                    if let Some(previous_value) = previous_values.get(&maybe_entity) {
-                        if *previous_value == value {
-                            continue;
-                        }
+                diffs.push(ActionDiff::AxisPairChanged {
+                    action: action.clone(),
+                    axis_pair: axis_pair.into(),
+                    pressed: true,
+                });
+                previous_axis_pairs.insert(owner.clone(), axis_pair.xy());
+            }
+            None => {
+                let value = action_data.value;
+                let previous_values = previous_values.get_mut(&action).unwrap();
+
+                if let Some(previous_value) = previous_values.get(owner) {
+                    if *previous_value == value {
+                        continue;
                     }
-                    diffs.push(ActionDiff::ValueChanged {
-                        action: action.clone(),
-                        value,
-                    });
-                    previous_values.insert(maybe_entity, value);
                 }
+                diffs.push(ActionDiff::ValueChanged {
+                    action: action.clone(),
+                    value,
+                    pressed: true,
+                });
+                previous_values.insert(owner.clone(), value);
             }
         }
-        for action in action_state.get_just_released() {
-            diffs.push(ActionDiff::Released {
-                action: action.clone(),
-            });
-            if let Some(previous_axes) = previous_axis_pairs.get_mut(&action) {
-                previous_axes.remove(&maybe_entity);
+    }
+    for action in action_state.get_just_released() {
+        diffs.push(ActionDiff::Released {
+            action: action.clone(),
+        });
+        if let Some(previous_axes) = previous_axis_pairs.get_mut(&action) {
+            previous_axes.remove(owner);
+        }
+        if let Some(previous_values) = previous_values.get_mut(&action) {
+            previous_values.remove(owner);
+        }
+    }
+    // Analog actions can have a value without being pressed.
+    // Released diffs reset the value and axis pair on the receiving end,
+    // so the previous values of actions that were just released were forgotten above, and read as zero.
+    for action in action_state.get_released() {
+        let Some(action_data) = action_state.action_data(&action) else {
+            continue;
+        };
+
+        match action_data.axis_pair {
+            Some(axis_pair) => {
+                let previous_axis_pair = previous_axis_pairs
+                    .get(&action)
+                    .and_then(|previous_axis_pairs| previous_axis_pairs.get(owner))
+                    .copied()
+                    .unwrap_or_default();
+                if axis_pair.xy() == previous_axis_pair {
+                    continue;
+                }
+
+                diffs.push(ActionDiff::AxisPairChanged {
+                    action: action.clone(),
+                    axis_pair: axis_pair.into(),
+                    pressed: false,
+                });
+                previous_axis_pairs
+                    .raw_entry_mut()
+                    .from_key(&action)
+                    .or_insert_with(|| (action.clone(), HashMap::default()))
+                    .1
+                    .insert(owner.clone(), axis_pair.xy());
             }
-            if let Some(previous_values) = previous_values.get_mut(&action) {
-                previous_values.remove(&maybe_entity);
+            None => {
+                let value = action_data.value;
+                let previous_value = previous_values
+                    .get(&action)
+                    .and_then(|previous_values| previous_values.get(owner))
+                    .copied()
+                    .unwrap_or_default();
+                if value == previous_value {
+                    continue;
+                }
+
+                diffs.push(ActionDiff::ValueChanged {
+                    action: action.clone(),
+                    value,
+                    pressed: false,
+                });
+                previous_values
+                    .raw_entry_mut()
+                    .from_key(&action)
+                    .or_insert_with(|| (action.clone(), HashMap::default()))
+                    .1
+                    .insert(owner.clone(), value);
             }
         }
-        if !diffs.is_empty() {
-            action_diffs.send(ActionDiffEvent {
-                owner: maybe_entity,
-                action_diffs: diffs,
-            });
+    }
+    if diff_policy.is_snapshot_tick(tick) {
+        diffs = action_state.summarize();
+    } else if diff_policy.is_keepalive_tick(tick) {
+        let keepalives: Vec<_> = action_state
+            .summarize()
+            .into_iter()
+            .filter(|summary| {
+                action_state.pressed(summary.action())
+                    && !diffs.iter().any(|diff| diff.action() == summary.action())
+            })
+            .collect();
+        diffs.extend(keepalives);
+    }
+    sort_by_action(&mut diffs);
+    diffs
+}
+
+/// Applies the [`ActionDiffEvent`]s to the [`ActionState`] of their owner
+///
+/// Owners are used as entities of this world, so they must have been mapped to them before the events are sent,
+/// or the entities must have been spawned with matching ids.
+/// Otherwise, use [`apply_action_diffs_by_id`] instead.
+/// Events for entities without an [`ActionState`] are skipped with a warning,
+/// while events without an owner are applied to the [`ActionState`] resource, if it exists.
+///
+/// This system is added by [`InputManagerPlugin::server`](crate::plugin::InputManagerPlugin::server), in [`InputManagerSystem::Update`](crate::plugin::InputManagerSystem::Update).
+pub fn apply_action_diffs<A: Actionlike>(
+    mut action_diff_events: EventReader<ActionDiffEvent<A>>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    mut action_state_query: Query<&mut ActionState<A>>,
+) {
+    let mut action_state = action_state.map(ResMut::into_inner);
+
+    for action_diff_event in action_diff_events.read() {
+        let action_state = match action_diff_event.owner {
+            Some(entity) => {
+                let Ok(action_state) = action_state_query.get_mut(entity) else {
+                    warn!("The owner {entity:?} of an ActionDiffEvent has no ActionState");
+                    continue;
+                };
+                action_state.into_inner()
+            }
+            None => match action_state.as_deref_mut() {
+                Some(action_state) => action_state,
+                None => continue,
+            },
+        };
+
+        for action_diff in action_diff_event.action_diffs.iter() {
+            action_state.apply_diff(action_diff);
+        }
+    }
+}
+
+/// Applies the [`ActionDiffEvent`]s tagged with a [`DiffOwner`] id to the [`ActionState`] of the matching entity
+///
+/// Ids are mapped back to local entities using the [`DiffOwnerMap<Id>`] resource.
+/// Events for ids that are missing from the map are skipped with a warning,
+/// while events without an owner are applied to the [`ActionState`] resource, if it exists.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+pub fn apply_action_diffs_by_id<A: Actionlike, Id: DiffOwnerId>(
+    owner_map: Res<DiffOwnerMap<Id>>,
+    mut action_diff_events: EventReader<ActionDiffEvent<A, Id>>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    mut action_state_query: Query<&mut ActionState<A>>,
+) {
+    let mut action_state = action_state.map(ResMut::into_inner);
+
+    for action_diff_event in action_diff_events.read() {
+        let action_state = match &action_diff_event.owner {
+            Some(id) => {
+                let Some(entity) = owner_map.get(id) else {
+                    warn!("No entity is mapped to the DiffOwner {id:?}: was it added to the DiffOwnerMap?");
+                    continue;
+                };
+                let Ok(action_state) = action_state_query.get_mut(entity) else {
+                    warn!("The entity mapped to the DiffOwner {id:?} has no ActionState");
+                    continue;
+                };
+                action_state.into_inner()
+            }
+            None => match action_state.as_deref_mut() {
+                Some(action_state) => action_state,
+                None => continue,
+            },
+        };
+
+        for action_diff in action_diff_event.action_diffs.iter() {
+            action_state.apply_diff(action_diff);
         }
     }
 }
 
-/// Release all inputs if the [`ToggleActions<A>`] resource exists and its `enabled` field is false.
+/// Releases the actions disabled by the [`ToggleActions<A>`] resource, and keeps them released while their inputs are held
+///
+/// Disabled actions are consumed, so they are not pressed again by held inputs once re-enabled.
+/// If [`ToggleActions::repress_on_enable`] is set, they are unconsumed when re-enabled instead.
 pub fn release_on_disable<A: Actionlike>(
     mut query: Query<&mut ActionState<A>>,
     resource: Option<ResMut<ActionState<A>>>,
     toggle_actions: Res<ToggleActions<A>>,
 ) {
-    if toggle_actions.is_changed() && !toggle_actions.enabled {
-        for mut action_state in query.iter_mut() {
-            action_state.release_all();
+    let action_states = query.iter_mut().chain(resource.map(Mut::from));
+
+    if toggle_actions.enabled {
+        if toggle_actions.is_changed() && toggle_actions.repress_on_enable {
+            for mut action_state in action_states {
+                let actions = match &toggle_actions.disable_during {
+                    Some(actions) => actions.clone(),
+                    None => action_state.keys(),
+                };
+                for action in actions {
+                    if let Some(action_datum) = action_state.action_data_mut(&action) {
+                        action_datum.consumed = false;
+                    }
+                }
+            }
         }
-        if let Some(mut action_state) = resource {
-            action_state.release_all();
+        return;
+    }
+
+    match &toggle_actions.disable_during {
+        // Inputs are not processed at all, so the actions only need to be released once
+        None if toggle_actions.is_changed() => {
+            for mut action_state in action_states {
+                for action in action_state.keys() {
+                    disable_action(&mut action_state, &action);
+                }
+            }
+        }
+        None => (),
+        Some(actions) => {
+            for mut action_state in action_states {
+                for action in actions {
+                    disable_action(&mut action_state, action);
+                }
+            }
         }
     }
 }
 
+/// Releases the `action`, and stops it from being pressed by held inputs
+fn disable_action<A: Actionlike>(action_state: &mut ActionState<A>, action: &A) {
+    if action_state.pressed(action) {
+        action_state.release(action);
+    }
+
+    match action_state.action_data_mut(action) {
+        Some(action_datum) => {
+            action_datum.consumed = true;
+            action_datum.value = 0.0;
+            action_datum.axis_pair = None;
+            action_datum.axis_triple = None;
+        }
+        None => action_state.set_action_data(
+            action.clone(),
+            ActionData {
+                consumed: true,
+                ..Default::default()
+            },
+        ),
+    }
+}
+
 /// Release all inputs when an [`InputMap<A>`] is removed to prevent them from being held forever.
 ///
 /// By default, [`InputManagerPlugin<A>`](crate::plugin::InputManagerPlugin) will run this on [`PostUpdate`](bevy::prelude::PostUpdate).
@@ -340,7 +1708,129 @@ pub fn release_on_input_map_removed<A: Actionlike>(
     }
 }
 
+/// Updates the [`WindowFocus`] resource from the [`WindowFocused`] events
+pub fn update_window_focus(
+    mut focus_events: EventReader<WindowFocused>,
+    mut window_focus: ResMut<WindowFocus>,
+) {
+    if let Some(event) = focus_events.read().last() {
+        window_focus.set_if_neq(WindowFocus {
+            focused: event.focused,
+        });
+    }
+}
+
+/// Records when each finger touched the screen in the [`TouchHistory`], along with the size of the primary window
+pub fn track_touches(
+    touches: Option<Res<Touches>>,
+    time: Option<Res<Time<Real>>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut touch_history: ResMut<TouchHistory>,
+) {
+    let Some(touches) = touches else {
+        return;
+    };
+
+    let now = time.map(|time| time.elapsed()).unwrap_or_default();
+    let window_size = windows
+        .get_single()
+        .ok()
+        .map(|window| Vec2::new(window.width(), window.height()));
+    touch_history.update(&touches, now, window_size);
+}
+
+/// Records when each mouse button was pressed in the [`MousePressHistory`], and how far the cursor moved since
+pub fn track_mouse_presses(
+    mouse_buttons: Option<Res<Input<MouseButton>>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    time: Option<Res<Time<Real>>>,
+    mut mouse_presses: ResMut<MousePressHistory>,
+) {
+    let motion: Vec2 = mouse_motion.read().map(|event| event.delta).sum();
+    let Some(mouse_buttons) = mouse_buttons else {
+        return;
+    };
+
+    let now = time.map(|time| time.elapsed()).unwrap_or_default();
+    mouse_presses.update(&mouse_buttons, motion, now);
+}
+
+/// Releases every action when the window loses focus, under [`FocusPolicy::ReleaseAll`](crate::plugin::FocusPolicy::ReleaseAll)
+///
+/// The actions stay released until the window regains focus, as [`update_action_state`] does not run meanwhile.
+pub fn release_on_focus_lost<A: Actionlike>(
+    window_focus: Res<WindowFocus>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    mut query: Query<&mut ActionState<A>>,
+) {
+    if !window_focus.is_changed() || window_focus.focused {
+        return;
+    }
+
+    if let Some(mut action_state) = action_state {
+        action_state.release_all();
+    }
+    for mut action_state in query.iter_mut() {
+        action_state.release_all();
+    }
+}
+
+/// Sends an [`AssociatedGamepadDisconnected`] event for each [`InputMap<A>`] reading a gamepad that was just disconnected
+///
+/// Bevy clears the inputs of disconnected gamepads, so [`update_action_state`] releases the actions they pressed on the same frame.
+pub fn notify_associated_gamepad_disconnected<A: Actionlike>(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    input_map: Option<Res<InputMap<A>>>,
+    query: Query<(Entity, &InputMap<A>)>,
+    mut disconnected_events: EventWriter<AssociatedGamepadDisconnected>,
+) {
+    for event in connection_events.read() {
+        if event.connection != GamepadConnection::Disconnected {
+            continue;
+        }
+
+        let gamepad = event.gamepad;
+        let reads_gamepad = |input_map: &InputMap<A>| match input_map.gamepad() {
+            Some(associated_gamepad) => associated_gamepad == gamepad,
+            // Maps without gamepad bindings, such as those of keyboard players, are unaffected
+            None => input_map
+                .iter()
+                .flat_map(|(_, inputs)| inputs)
+                .any(|input| {
+                    let raw_inputs = input.raw_inputs();
+                    !raw_inputs.gamepad_buttons.is_empty()
+                        || raw_inputs
+                            .axis_data
+                            .iter()
+                            .any(|(axis_type, _)| matches!(axis_type, AxisType::Gamepad(_)))
+                }),
+        };
+
+        if input_map.as_deref().is_some_and(reads_gamepad) {
+            disconnected_events.send(AssociatedGamepadDisconnected {
+                entity: None,
+                gamepad,
+            });
+        }
+        for (entity, input_map) in query.iter() {
+            if reads_gamepad(input_map) {
+                disconnected_events.send(AssociatedGamepadDisconnected {
+                    entity: Some(entity),
+                    gamepad,
+                });
+            }
+        }
+    }
+}
+
+/// Returns `false` while the [`WindowFocus`] resource says that the window is unfocused
+pub fn run_if_window_focused(window_focus: Option<Res<WindowFocus>>) -> bool {
+    !matches!(window_focus, Some(window_focus) if !window_focus.focused)
+}
+
 /// Uses the value of [`ToggleActions<A>`] to determine if input manager systems of type `A` should run.
+///
+/// The systems keep running while only some actions are disabled by [`ToggleActions::disable_during`].
 pub fn run_if_enabled<A: Actionlike>(toggle_actions: Res<ToggleActions<A>>) -> bool {
-    toggle_actions.enabled
+    toggle_actions.enabled || toggle_actions.disable_during.is_some()
 }
@@ -0,0 +1,1167 @@
+//! Tools for checking the correctness of code built on top of this crate.
+//!
+//! These are only available with the `test_utils` feature enabled,
+//! and are intended for use in your own tests, not in game code.
+
+use crate::action_diff::{action_order, ActionDiff, ActionDiffEvent};
+use crate::action_state::{ActionData, ActionState};
+use crate::axislike::DualAxisData;
+use crate::buttonlike::ButtonState;
+use crate::input_map::InputMap;
+use crate::input_mocking::InputScript;
+use crate::plugin::InputManagerPlugin;
+use crate::systems::generate_action_diffs;
+use crate::timing::ConsumePolicy;
+use crate::{Actionlike, InputManagerBundle};
+
+use bevy::app::{App, PostUpdate};
+use bevy::ecs::prelude::*;
+use bevy::input::InputPlugin;
+use bevy::math::Vec2;
+use bevy::prelude::MinimalPlugins;
+use bevy::utils::{Duration, HashMap, HashSet, Instant};
+use std::fmt::{Debug, Display};
+
+/// Checks that an [`ActionState`] can be faithfully reconstructed from the [`ActionDiff`](crate::action_diff::ActionDiff)s it generates.
+///
+/// A local [`App`] is created, containing a "source" entity with an [`InputManagerBundle`]
+/// and a "mirror" entity with only an [`ActionState`].
+/// The `app_setup` function is called on the app before anything runs,
+/// and returns the [`InputMap`] used by the source.
+///
+/// Each frame, the steps of the `input_script` are sent using [`MockInput`](crate::input_mocking::MockInput),
+/// the app is updated, and the [`ActionDiffEvent`]s generated by the source
+/// are applied to the mirror using [`ActionState::apply_diff`].
+/// The networked fields of both action states (the [`ButtonState`], value and axis pair of each action)
+/// are then compared.
+///
+/// # Panics
+///
+/// Panics on the first frame where the source and mirror diverge,
+/// reporting the action and field responsible.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::input_mocking::InputScript;
+/// use leafwing_input_manager::test_utils::assert_diff_roundtrip;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Run,
+///     Jump,
+/// }
+///
+/// assert_diff_roundtrip::<Action>(
+///     |_app| InputMap::new([(Action::Run, KeyCode::ShiftLeft), (Action::Jump, KeyCode::Space)]),
+///     InputScript::new()
+///         .press(KeyCode::ShiftLeft)
+///         .wait_frames(2)
+///         .press(KeyCode::Space)
+///         .wait_frames(1)
+///         .release(KeyCode::ShiftLeft)
+///         .release(KeyCode::Space)
+///         .wait_frames(1),
+/// );
+/// ```
+#[track_caller]
+pub fn assert_diff_roundtrip<A: Actionlike + Debug>(
+    app_setup: impl FnOnce(&mut App) -> InputMap<A>,
+    input_script: InputScript,
+) {
+    if let Err(divergence) = check_diff_roundtrip(app_setup, input_script) {
+        panic!("{divergence}");
+    }
+}
+
+/// Checks that an [`ActionState`] can be faithfully reconstructed from the [`ActionDiff`](crate::action_diff::ActionDiff)s it generates.
+///
+/// This is the non-panicking equivalent of [`assert_diff_roundtrip`]:
+/// see its documentation for more details.
+pub fn check_diff_roundtrip<A: Actionlike>(
+    app_setup: impl FnOnce(&mut App) -> InputMap<A>,
+    input_script: InputScript,
+) -> Result<(), DiffDivergence<A>> {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<A>::default(),
+    ))
+    .add_event::<ActionDiffEvent<A>>()
+    .add_systems(
+        PostUpdate,
+        (generate_action_diffs::<A>, apply_loopback_diffs::<A>).chain(),
+    );
+
+    let input_map = app_setup(&mut app);
+    let source = app
+        .world
+        .spawn(InputManagerBundle {
+            input_map,
+            action_state: ActionState::default(),
+        })
+        .id();
+    let mirror = app.world.spawn(ActionState::<A>::default()).id();
+    app.insert_resource(Loopback { source, mirror });
+
+    for (frame, steps) in input_script.frames().iter().enumerate() {
+        for step in steps {
+            step.apply(&mut app);
+        }
+        app.update();
+
+        let source_state = app.world.get::<ActionState<A>>(source).unwrap();
+        let mirror_state = app.world.get::<ActionState<A>>(mirror).unwrap();
+        if let Some(mismatch) = compare_networked_fields(source_state, mirror_state) {
+            return Err(DiffDivergence { frame, mismatch });
+        }
+    }
+
+    Ok(())
+}
+
+/// The first point at which a mirrored [`ActionState`] disagreed with its source
+///
+/// Returned by [`check_diff_roundtrip`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffDivergence<A: Actionlike> {
+    /// The index of the frame in the [`InputScript`] where the states diverged
+    pub frame: usize,
+    /// The field that did not match
+    pub mismatch: FieldMismatch<A>,
+}
+
+impl<A: Actionlike + Debug> Display for DiffDivergence<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ActionDiff round-trip diverged on frame {}: {}",
+            self.frame, self.mismatch
+        )
+    }
+}
+
+/// A networked field of an action that differs between two [`ActionState`]s
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldMismatch<A: Actionlike> {
+    /// The [`ButtonState`] of the `action` differs
+    State {
+        /// The action whose state differs
+        action: A,
+        /// The state found in the source
+        source: ButtonState,
+        /// The state found in the mirror
+        mirror: ButtonState,
+    },
+    /// The [`ActionState::value`] of the `action` differs
+    Value {
+        /// The action whose value differs
+        action: A,
+        /// The value found in the source
+        source: f32,
+        /// The value found in the mirror
+        mirror: f32,
+    },
+    /// The [`ActionState::axis_pair`] of the `action` differs
+    AxisPair {
+        /// The action whose axis pair differs
+        action: A,
+        /// The axis pair found in the source
+        source: Option<DualAxisData>,
+        /// The axis pair found in the mirror
+        mirror: Option<DualAxisData>,
+    },
+}
+
+impl<A: Actionlike + Debug> Display for FieldMismatch<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldMismatch::State {
+                action,
+                source,
+                mirror,
+            } => write!(
+                f,
+                "the state of {action:?} was {source:?} on the source, but {mirror:?} on the mirror"
+            ),
+            FieldMismatch::Value {
+                action,
+                source,
+                mirror,
+            } => write!(
+                f,
+                "the value of {action:?} was {source} on the source, but {mirror} on the mirror"
+            ),
+            FieldMismatch::AxisPair {
+                action,
+                source,
+                mirror,
+            } => write!(
+                f,
+                "the axis pair of {action:?} was {source:?} on the source, but {mirror:?} on the mirror"
+            ),
+        }
+    }
+}
+
+/// Compares the fields of two [`ActionState`]s that are transmitted by [`ActionDiff`](crate::action_diff::ActionDiff)s
+///
+/// Actions that have no data are treated as released, with a value of 0 and no axis pair.
+/// A missing axis pair is equivalent to a neutral one.
+///
+/// Returns the first mismatch found, if any.
+/// Actions are checked in the order in which they were declared,
+/// so for enum actions the same mismatch is reported on every run.
+#[must_use]
+pub fn compare_networked_fields<A: Actionlike>(
+    source: &ActionState<A>,
+    mirror: &ActionState<A>,
+) -> Option<FieldMismatch<A>> {
+    let actions: HashSet<A> = source.keys().into_iter().chain(mirror.keys()).collect();
+    let mut actions: Vec<A> = actions.into_iter().collect();
+    actions.sort_by_cached_key(action_order);
+
+    for action in actions {
+        let button_state = |action_state: &ActionState<A>| {
+            action_state
+                .action_data(&action)
+                .map(|action_data| action_data.state)
+                .unwrap_or_default()
+        };
+        let (source_state, mirror_state) = (button_state(source), button_state(mirror));
+        if source_state != mirror_state {
+            return Some(FieldMismatch::State {
+                action,
+                source: source_state,
+                mirror: mirror_state,
+            });
+        }
+
+        let (source_value, mirror_value) = (source.value(&action), mirror.value(&action));
+        if source_value != mirror_value {
+            return Some(FieldMismatch::Value {
+                action,
+                source: source_value,
+                mirror: mirror_value,
+            });
+        }
+
+        let (source_pair, mirror_pair) = (source.axis_pair(&action), mirror.axis_pair(&action));
+        if source_pair.unwrap_or_default() != mirror_pair.unwrap_or_default() {
+            return Some(FieldMismatch::AxisPair {
+                action,
+                source: source_pair,
+                mirror: mirror_pair,
+            });
+        }
+    }
+
+    None
+}
+
+/// A compact description of the [`ActionState`] an action is expected to be in
+///
+/// Only the fields that were explicitly set are checked:
+/// actions and fields that are not mentioned are ignored.
+/// Every mismatch is collected, rather than stopping at the first one,
+/// so a single failure shows everything that differs.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::test_utils::ExpectedState;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Run,
+///     Jump,
+/// }
+///
+/// let mut action_state = ActionState::<Action>::default();
+/// action_state.press(&Action::Jump);
+///
+/// ExpectedState::new()
+///     .just_pressed(Action::Jump)
+///     .released(Action::Run)
+///     .consumed(Action::Jump, false)
+///     .assert_matches(&action_state);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedState<A: Actionlike> {
+    expectations: Vec<(A, ExpectedField)>,
+    epsilon: f32,
+}
+
+impl<A: Actionlike> Default for ExpectedState<A> {
+    fn default() -> Self {
+        Self {
+            expectations: Vec::new(),
+            epsilon: ExpectedState::<A>::DEFAULT_EPSILON,
+        }
+    }
+}
+
+impl<A: Actionlike> ExpectedState<A> {
+    /// The tolerance used when comparing values and axis pairs, unless changed with [`ExpectedState::epsilon`]
+    pub const DEFAULT_EPSILON: f32 = 1e-5;
+
+    /// Creates an empty expectation, which matches any [`ActionState`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the tolerance used when comparing values and axis pairs
+    #[must_use]
+    pub fn epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Expects the [`ButtonState`] of the `action` to be exactly `state`
+    #[must_use]
+    pub fn state(mut self, action: A, state: ButtonState) -> Self {
+        self.expectations
+            .push((action, ExpectedField::State(state)));
+        self
+    }
+
+    /// Expects the `action` to be [`ButtonState::JustPressed`]
+    #[must_use]
+    pub fn just_pressed(self, action: A) -> Self {
+        self.state(action, ButtonState::JustPressed)
+    }
+
+    /// Expects the `action` to be [`ButtonState::Pressed`]
+    #[must_use]
+    pub fn pressed(self, action: A) -> Self {
+        self.state(action, ButtonState::Pressed)
+    }
+
+    /// Expects the `action` to be [`ButtonState::JustReleased`]
+    #[must_use]
+    pub fn just_released(self, action: A) -> Self {
+        self.state(action, ButtonState::JustReleased)
+    }
+
+    /// Expects the `action` to be [`ButtonState::Released`]
+    #[must_use]
+    pub fn released(self, action: A) -> Self {
+        self.state(action, ButtonState::Released)
+    }
+
+    /// Expects the [`ActionState::value`] of the `action` to be within the epsilon of `value`
+    #[must_use]
+    pub fn value(mut self, action: A, value: f32) -> Self {
+        self.expectations
+            .push((action, ExpectedField::Value(value)));
+        self
+    }
+
+    /// Expects the [`ActionState::axis_pair`] of the `action` to be within the epsilon of `axis_pair`
+    ///
+    /// A missing axis pair is treated as a neutral one.
+    #[must_use]
+    pub fn axis_pair(mut self, action: A, axis_pair: Vec2) -> Self {
+        self.expectations
+            .push((action, ExpectedField::AxisPair(axis_pair)));
+        self
+    }
+
+    /// Expects [`ActionState::consumed`] to return `consumed` for the `action`
+    #[must_use]
+    pub fn consumed(mut self, action: A, consumed: bool) -> Self {
+        self.expectations
+            .push((action, ExpectedField::Consumed(consumed)));
+        self
+    }
+
+    /// Compares the `action_state` against these expectations
+    ///
+    /// Returns every mismatching field, in the order the expectations were added.
+    pub fn check(&self, action_state: &ActionState<A>) -> Result<(), StateMismatches<A>> {
+        let mismatches: Vec<StateMismatch<A>> = self
+            .expectations
+            .iter()
+            .filter_map(|(action, expected)| {
+                let actual = expected.read(action_state, action);
+                (!expected.matches(&actual, self.epsilon)).then(|| StateMismatch {
+                    action: action.clone(),
+                    expected: expected.clone(),
+                    actual,
+                })
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(StateMismatches(mismatches))
+        }
+    }
+
+    /// Asserts that the `action_state` matches these expectations
+    ///
+    /// # Panics
+    ///
+    /// Panics if any field differs, listing every mismatching action and field.
+    #[track_caller]
+    pub fn assert_matches(&self, action_state: &ActionState<A>)
+    where
+        A: Debug,
+    {
+        if let Err(mismatches) = self.check(action_state) {
+            panic!("{mismatches}");
+        }
+    }
+}
+
+/// A single field checked by an [`ExpectedState`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpectedField {
+    /// The [`ButtonState`] of an action
+    State(ButtonState),
+    /// The [`ActionState::value`] of an action
+    Value(f32),
+    /// The [`ActionState::axis_pair`] of an action, with missing pairs treated as neutral
+    AxisPair(Vec2),
+    /// Whether or not an action is [consumed](ActionState::consumed)
+    Consumed(bool),
+}
+
+impl ExpectedField {
+    /// Reads the same field from the `action_state`
+    fn read<A: Actionlike>(&self, action_state: &ActionState<A>, action: &A) -> ExpectedField {
+        match self {
+            ExpectedField::State(_) => ExpectedField::State(
+                action_state
+                    .action_data(action)
+                    .map(|action_data| action_data.state)
+                    .unwrap_or_default(),
+            ),
+            ExpectedField::Value(_) => ExpectedField::Value(action_state.value(action)),
+            ExpectedField::AxisPair(_) => {
+                ExpectedField::AxisPair(action_state.axis_pair(action).unwrap_or_default().xy())
+            }
+            ExpectedField::Consumed(_) => ExpectedField::Consumed(action_state.consumed(action)),
+        }
+    }
+
+    fn matches(&self, actual: &ExpectedField, epsilon: f32) -> bool {
+        match (self, actual) {
+            (ExpectedField::Value(expected), ExpectedField::Value(actual)) => {
+                (expected - actual).abs() <= epsilon
+            }
+            (ExpectedField::AxisPair(expected), ExpectedField::AxisPair(actual)) => {
+                expected.abs_diff_eq(*actual, epsilon)
+            }
+            (expected, actual) => expected == actual,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ExpectedField::State(_) => "state",
+            ExpectedField::Value(_) => "value",
+            ExpectedField::AxisPair(_) => "axis_pair",
+            ExpectedField::Consumed(_) => "consumed",
+        }
+    }
+}
+
+impl Display for ExpectedField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectedField::State(state) => write!(f, "{state:?}"),
+            ExpectedField::Value(value) => write!(f, "{value}"),
+            ExpectedField::AxisPair(axis_pair) => write!(f, "{axis_pair}"),
+            ExpectedField::Consumed(consumed) => write!(f, "{consumed}"),
+        }
+    }
+}
+
+/// A field of an [`ActionState`] that did not match its [`ExpectedState`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateMismatch<A: Actionlike> {
+    /// The action whose field differs
+    pub action: A,
+    /// The expected value of the field
+    pub expected: ExpectedField,
+    /// The value of the field found in the [`ActionState`]
+    pub actual: ExpectedField,
+}
+
+impl<A: Actionlike + Debug> Display for StateMismatch<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?}.{}: expected {}, found {}",
+            self.action,
+            self.expected.name(),
+            self.expected,
+            self.actual
+        )
+    }
+}
+
+/// Every mismatch found by [`ExpectedState::check`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateMismatches<A: Actionlike>(pub Vec<StateMismatch<A>>);
+
+impl<A: Actionlike + Debug> Display for StateMismatches<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ActionState did not match the expected state ({} mismatches):",
+            self.0.len()
+        )?;
+        for mismatch in &self.0 {
+            write!(f, "\n  {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Applies a random sequence of operations to an [`ActionState`], checking its invariants after every step
+///
+/// The `steps` operations are generated deterministically from the `seed`,
+/// and are applied to a default [`ActionState`] through its public API:
+/// pressing, releasing and consuming actions, ticking time forward,
+/// calling [`ActionState::update_at`] with random values and axis pairs,
+/// applying random [`ActionDiff`]s and changing the [`ConsumePolicy`].
+/// Only the provided `actions` are used.
+///
+/// After each step, the following invariants are checked for every action:
+/// - a [`ButtonState::JustPressed`] action has a [`Timing::current_duration`](crate::timing::Timing::current_duration) of zero
+/// - a [consumed](ActionState::consumed) action is stored as released
+/// - an action whose timing is preserved by [`ConsumePolicy::PreserveTiming`] is consumed
+/// - while an action stays pressed, its [`ActionState::current_duration`] never decreases
+/// - a [consumed](ActionState::consumed) action is not pressed again until it has been released
+///
+/// # Panics
+///
+/// Panics if an invariant is violated, reporting the seed
+/// and the shortest sequence of operations found that still reproduces a violation.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::test_utils::fuzz_action_state;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+/// enum Action {
+///     Run,
+///     Jump,
+/// }
+///
+/// for seed in 0..10 {
+///     fuzz_action_state(&[Action::Run, Action::Jump], seed, 200);
+/// }
+/// ```
+#[track_caller]
+pub fn fuzz_action_state<A: Actionlike + Debug>(actions: &[A], seed: u64, steps: usize) {
+    if let Err(failure) = check_fuzz_action_state(actions, seed, steps) {
+        panic!("{failure}");
+    }
+}
+
+/// Applies a random sequence of operations to an [`ActionState`], checking its invariants after every step
+///
+/// This is the non-panicking equivalent of [`fuzz_action_state`]:
+/// see its documentation for more details.
+pub fn check_fuzz_action_state<A: Actionlike>(
+    actions: &[A],
+    seed: u64,
+    steps: usize,
+) -> Result<(), FuzzFailure<A>> {
+    assert!(!actions.is_empty(), "at least one action must be fuzzed");
+
+    let mut rng = FuzzRng::new(seed);
+    let operations: Vec<FuzzOperation<A>> = (0..steps)
+        .map(|_| FuzzOperation::random(&mut rng, actions))
+        .collect();
+
+    let Some((step, violation)) = run_fuzz_operations(actions, &operations) else {
+        return Ok(());
+    };
+
+    // Shrink the failing sequence by greedily removing operations that are not needed to fail
+    let mut operations = operations[..=step].to_vec();
+    let mut violation = violation;
+    let mut shrunk = true;
+    while shrunk {
+        shrunk = false;
+        for index in (0..operations.len()).rev() {
+            let mut candidate = operations.clone();
+            candidate.remove(index);
+            if let Some((step, candidate_violation)) = run_fuzz_operations(actions, &candidate) {
+                candidate.truncate(step + 1);
+                operations = candidate;
+                violation = candidate_violation;
+                shrunk = true;
+                break;
+            }
+        }
+    }
+
+    Err(FuzzFailure {
+        seed,
+        operations,
+        violation,
+    })
+}
+
+/// Applies the `operations` in order, returning the first step at which an invariant was violated
+fn run_fuzz_operations<A: Actionlike>(
+    actions: &[A],
+    operations: &[FuzzOperation<A>],
+) -> Option<(usize, InvariantViolation<A>)> {
+    let start = Instant::now();
+    let mut elapsed = Duration::ZERO;
+    let mut action_state = ActionState::<A>::default();
+
+    for (step, operation) in operations.iter().enumerate() {
+        let before = action_state.clone();
+        match operation {
+            FuzzOperation::Press(action) => action_state.press(action),
+            FuzzOperation::Release(action) => action_state.release(action),
+            FuzzOperation::Consume(action) => action_state.consume(action),
+            FuzzOperation::ReleaseAll => action_state.release_all(),
+            FuzzOperation::ConsumeAll => action_state.consume_all(),
+            FuzzOperation::Update {
+                action,
+                pressed,
+                value,
+                axis_pair,
+            } => {
+                let action_data = ActionData {
+                    state: if *pressed {
+                        ButtonState::JustPressed
+                    } else {
+                        ButtonState::Released
+                    },
+                    value: *value,
+                    axis_pair: axis_pair.map(DualAxisData::from_xy),
+                    ..Default::default()
+                };
+                action_state.update_at(
+                    HashMap::from([(action.clone(), action_data)]),
+                    start + elapsed,
+                );
+            }
+            FuzzOperation::ApplyDiff(action_diff) => action_state.apply_diff(action_diff),
+            FuzzOperation::SetConsumePolicy(consume_policy) => {
+                action_state.set_consume_policy(Some(*consume_policy));
+            }
+            FuzzOperation::Tick(delta) => {
+                let previous_instant = start + elapsed;
+                elapsed += *delta;
+                action_state.tick(start + elapsed, previous_instant);
+            }
+        }
+
+        for action in actions {
+            if let Some(violation) = check_invariants(&before, &action_state, action) {
+                return Some((step, violation));
+            }
+        }
+    }
+
+    None
+}
+
+/// Checks the invariants listed in [`fuzz_action_state`] for a single `action`
+fn check_invariants<A: Actionlike>(
+    before: &ActionState<A>,
+    after: &ActionState<A>,
+    action: &A,
+) -> Option<InvariantViolation<A>> {
+    let action = action.clone();
+
+    // The accessors are derived from the stored state, so the stored fields are checked against each other instead
+    if let Some(action_data) = after.action_data(&action) {
+        let duration = action_data.timing.current_duration;
+        if action_data.state == ButtonState::JustPressed && duration > Duration::ZERO {
+            return Some(InvariantViolation::JustPressedWithDuration { action, duration });
+        }
+
+        if action_data.consumed && action_data.state.pressed() {
+            return Some(InvariantViolation::ConsumedWhilePressed { action });
+        }
+
+        if action_data.timing_preserved && !action_data.consumed {
+            return Some(InvariantViolation::TimingPreservedWithoutConsumption { action });
+        }
+    }
+
+    if before.pressed(&action) && after.pressed(&action) {
+        let (before_duration, after_duration) = (
+            before.current_duration(&action),
+            after.current_duration(&action),
+        );
+        if after_duration < before_duration {
+            return Some(InvariantViolation::DurationDecreased {
+                action,
+                before: before_duration,
+                after: after_duration,
+            });
+        }
+    }
+
+    if before.consumed(&action) && after.pressed(&action) {
+        return Some(InvariantViolation::ConsumedRepressed { action });
+    }
+
+    None
+}
+
+/// A single operation applied to an [`ActionState`] by [`fuzz_action_state`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FuzzOperation<A: Actionlike> {
+    /// Calls [`ActionState::press`]
+    Press(A),
+    /// Calls [`ActionState::release`]
+    Release(A),
+    /// Calls [`ActionState::consume`]
+    Consume(A),
+    /// Calls [`ActionState::release_all`]
+    ReleaseAll,
+    /// Calls [`ActionState::consume_all`]
+    ConsumeAll,
+    /// Calls [`ActionState::update_at`] with a single [`ActionData`]
+    Update {
+        /// The action to update
+        action: A,
+        /// Are the inputs of this action pressed?
+        pressed: bool,
+        /// The value of the action
+        value: f32,
+        /// The axis pair of the action, if any
+        axis_pair: Option<Vec2>,
+    },
+    /// Calls [`ActionState::apply_diff`]
+    ApplyDiff(ActionDiff<A>),
+    /// Calls [`ActionState::set_consume_policy`]
+    SetConsumePolicy(ConsumePolicy),
+    /// Calls [`ActionState::tick`], advancing time by this [`Duration`]
+    Tick(Duration),
+}
+
+impl<A: Actionlike> FuzzOperation<A> {
+    /// Generates a random operation on one of the `actions`
+    fn random(rng: &mut FuzzRng, actions: &[A]) -> Self {
+        let action = actions[rng.below(actions.len() as u64) as usize].clone();
+        match rng.below(10) {
+            0 => FuzzOperation::Press(action),
+            1 => FuzzOperation::Release(action),
+            2 => FuzzOperation::Consume(action),
+            3 => FuzzOperation::ReleaseAll,
+            4 => FuzzOperation::ConsumeAll,
+            5 => FuzzOperation::Update {
+                action,
+                pressed: rng.below(2) == 0,
+                value: rng.unit(),
+                axis_pair: (rng.below(2) == 0).then(|| Vec2::new(rng.unit(), rng.unit())),
+            },
+            6 => FuzzOperation::ApplyDiff(match rng.below(4) {
+                0 => ActionDiff::Pressed { action },
+                1 => ActionDiff::Released { action },
+                2 => ActionDiff::ValueChanged {
+                    action,
+                    value: rng.unit(),
+                    pressed: rng.below(2) == 0,
+                },
+                _ => ActionDiff::AxisPairChanged {
+                    action,
+                    axis_pair: Vec2::new(rng.unit(), rng.unit()),
+                    pressed: rng.below(2) == 0,
+                },
+            }),
+            7 => FuzzOperation::SetConsumePolicy(if rng.below(2) == 0 {
+                ConsumePolicy::FlipTiming
+            } else {
+                ConsumePolicy::PreserveTiming
+            }),
+            _ => FuzzOperation::Tick(Duration::from_millis(rng.below(50))),
+        }
+    }
+}
+
+/// An invariant of [`ActionState`] that was broken during [`fuzz_action_state`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvariantViolation<A: Actionlike> {
+    /// The `action` was just pressed, but its timing was not reset when it was pressed
+    JustPressedWithDuration {
+        /// The action whose timing is inconsistent
+        action: A,
+        /// The current duration of the action
+        duration: Duration,
+    },
+    /// The `action` was consumed, but is stored as pressed
+    ConsumedWhilePressed {
+        /// The action whose state is inconsistent
+        action: A,
+    },
+    /// The timing of the `action` is preserved until it is released, but the action is not consumed
+    TimingPreservedWithoutConsumption {
+        /// The action whose state is inconsistent
+        action: A,
+    },
+    /// The current duration of the `action` decreased while it was held
+    DurationDecreased {
+        /// The action whose duration decreased
+        action: A,
+        /// The duration before the operation
+        before: Duration,
+        /// The duration after the operation
+        after: Duration,
+    },
+    /// The `action` was pressed again while consumed
+    ConsumedRepressed {
+        /// The consumed action
+        action: A,
+    },
+}
+
+impl<A: Actionlike + Debug> Display for InvariantViolation<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvariantViolation::JustPressedWithDuration { action, duration } => {
+                write!(f, "{action:?} was just pressed, but had been held for {duration:?}")
+            }
+            InvariantViolation::ConsumedWhilePressed { action } => {
+                write!(f, "{action:?} was consumed, but stored as pressed")
+            }
+            InvariantViolation::TimingPreservedWithoutConsumption { action } => {
+                write!(f, "{action:?} had its timing preserved without being consumed")
+            }
+            InvariantViolation::DurationDecreased {
+                action,
+                before,
+                after,
+            } => write!(
+                f,
+                "the current duration of {action:?} went from {before:?} to {after:?} while it was held"
+            ),
+            InvariantViolation::ConsumedRepressed { action } => {
+                write!(f, "{action:?} was pressed again while consumed")
+            }
+        }
+    }
+}
+
+/// Returned by [`check_fuzz_action_state`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzFailure<A: Actionlike> {
+    /// The seed that generated the failing sequence
+    pub seed: u64,
+    /// The shortest sequence of operations found that violates an invariant on its last step
+    pub operations: Vec<FuzzOperation<A>>,
+    /// The invariant that was violated
+    pub violation: InvariantViolation<A>,
+}
+
+impl<A: Actionlike + Debug> Display for FuzzFailure<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ActionState invariant violated with seed {}: {}\nminimal reproducing sequence:",
+            self.seed, self.violation
+        )?;
+        for (step, operation) in self.operations.iter().enumerate() {
+            write!(f, "\n  {step}: {operation:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A small deterministic random number generator, so that fuzzing sequences are reproducible from their seed
+///
+/// Uses the `SplitMix64` algorithm.
+struct FuzzRng(u64);
+
+impl FuzzRng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A random number in `0..bound`
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    /// A random multiple of 0.25 in `-1.0..=1.0`, so that failures are easy to read
+    fn unit(&mut self) -> f32 {
+        self.below(9) as f32 / 4.0 - 1.0
+    }
+}
+
+/// The entities used by [`check_diff_roundtrip`]
+#[derive(Resource)]
+struct Loopback {
+    source: Entity,
+    mirror: Entity,
+}
+
+/// Applies the diffs generated by the loopback source to the loopback mirror
+fn apply_loopback_diffs<A: Actionlike>(
+    loopback: Res<Loopback>,
+    mut action_diff_events: EventReader<ActionDiffEvent<A>>,
+    mut action_state_query: Query<&mut ActionState<A>>,
+) {
+    for action_diff_event in action_diff_events.read() {
+        if action_diff_event.owner != Some(loopback.source) {
+            continue;
+        }
+
+        let mut mirror = action_state_query.get_mut(loopback.mirror).unwrap();
+        for action_diff in action_diff_event.action_diffs.iter() {
+            mirror.apply_diff(action_diff);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::axislike::VirtualDPad;
+    use bevy::input::keyboard::KeyCode;
+    use bevy::prelude::Reflect;
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Run,
+        Jump,
+        Move,
+    }
+
+    fn input_map(_app: &mut App) -> InputMap<Action> {
+        let mut input_map = InputMap::new([
+            (Action::Run, KeyCode::ShiftLeft),
+            (Action::Jump, KeyCode::Space),
+        ]);
+        input_map.insert(Action::Move, VirtualDPad::arrow_keys());
+        input_map
+    }
+
+    #[test]
+    fn buttons_roundtrip() {
+        assert_diff_roundtrip(
+            input_map,
+            InputScript::new()
+                .press(KeyCode::ShiftLeft)
+                .wait_frames(3)
+                .press(KeyCode::Space)
+                .wait_frames(1)
+                .release(KeyCode::Space)
+                .release(KeyCode::ShiftLeft)
+                .wait_frames(2),
+        );
+    }
+
+    #[test]
+    fn axis_pairs_roundtrip() {
+        assert_diff_roundtrip(
+            input_map,
+            InputScript::new()
+                .press(KeyCode::Up)
+                .wait_frames(2)
+                .press(KeyCode::Right)
+                .wait_frames(2)
+                .release(KeyCode::Up)
+                .wait_frames(1)
+                .release(KeyCode::Right)
+                .wait_frames(1),
+        );
+    }
+
+    #[test]
+    fn mismatches_are_detected() {
+        let source = ActionState::<Action>::default();
+        let mut mirror = ActionState::<Action>::default();
+        assert_eq!(compare_networked_fields(&source, &mirror), None);
+
+        mirror.press(&Action::Jump);
+        assert_eq!(
+            compare_networked_fields(&source, &mirror),
+            Some(FieldMismatch::State {
+                action: Action::Jump,
+                source: ButtonState::Released,
+                mirror: ButtonState::JustPressed,
+            })
+        );
+
+        let mut source = mirror.clone();
+        source.action_data_mut(&Action::Jump).unwrap().value = 0.5;
+        assert_eq!(
+            compare_networked_fields(&source, &mirror),
+            Some(FieldMismatch::Value {
+                action: Action::Jump,
+                source: 0.5,
+                mirror: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn mismatches_are_reported_in_declaration_order() {
+        let source = ActionState::<Action>::default();
+        let mut mirror = ActionState::<Action>::default();
+        mirror.press(&Action::Move);
+        mirror.press(&Action::Jump);
+        mirror.press(&Action::Run);
+
+        assert_eq!(
+            compare_networked_fields(&source, &mirror),
+            Some(FieldMismatch::State {
+                action: Action::Run,
+                source: ButtonState::Released,
+                mirror: ButtonState::JustPressed,
+            })
+        );
+    }
+
+    #[test]
+    fn expected_state_reports_every_mismatch() {
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(&Action::Jump);
+        action_state.action_data_mut(&Action::Jump).unwrap().value = 1.0;
+        action_state.consume(&Action::Run);
+
+        let expected = ExpectedState::new()
+            .just_pressed(Action::Jump)
+            .value(Action::Jump, 1.0)
+            .consumed(Action::Run, true)
+            .axis_pair(Action::Move, Vec2::ZERO);
+        assert_eq!(expected.check(&action_state), Ok(()));
+
+        let mismatches = ExpectedState::new()
+            .released(Action::Jump)
+            .value(Action::Jump, 0.5)
+            .consumed(Action::Run, false)
+            .check(&action_state)
+            .unwrap_err();
+        assert_eq!(
+            mismatches.0,
+            vec![
+                StateMismatch {
+                    action: Action::Jump,
+                    expected: ExpectedField::State(ButtonState::Released),
+                    actual: ExpectedField::State(ButtonState::JustPressed),
+                },
+                StateMismatch {
+                    action: Action::Jump,
+                    expected: ExpectedField::Value(0.5),
+                    actual: ExpectedField::Value(1.0),
+                },
+                StateMismatch {
+                    action: Action::Run,
+                    expected: ExpectedField::Consumed(false),
+                    actual: ExpectedField::Consumed(true),
+                },
+            ]
+        );
+        assert_eq!(
+            mismatches.to_string(),
+            "ActionState did not match the expected state (3 mismatches):\n  \
+             Jump.state: expected Released, found JustPressed\n  \
+             Jump.value: expected 0.5, found 1\n  \
+             Run.consumed: expected false, found true"
+        );
+    }
+
+    #[test]
+    fn expected_state_uses_epsilon() {
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(&Action::Run);
+        action_state.action_data_mut(&Action::Run).unwrap().value = 0.7;
+
+        let expected = ExpectedState::new().value(Action::Run, 0.69);
+        assert!(expected.check(&action_state).is_err());
+        assert_eq!(expected.epsilon(0.02).check(&action_state), Ok(()));
+    }
+
+    #[test]
+    fn fuzzing_preserves_invariants() {
+        for seed in 0..20 {
+            fuzz_action_state(&[Action::Run, Action::Jump, Action::Move], seed, 500);
+        }
+    }
+
+    #[test]
+    fn invariants_check_the_stored_state() {
+        let before = ActionState::<Action>::default();
+        let mut after = ActionState::<Action>::default();
+        assert_eq!(check_invariants(&before, &after, &Action::Run), None);
+
+        let mut action_data = ActionData {
+            state: ButtonState::JustPressed,
+            ..Default::default()
+        };
+        action_data.timing.current_duration = Duration::from_millis(10);
+        after.set_action_data(Action::Run, action_data);
+        assert_eq!(
+            check_invariants(&before, &after, &Action::Run),
+            Some(InvariantViolation::JustPressedWithDuration {
+                action: Action::Run,
+                duration: Duration::from_millis(10),
+            })
+        );
+
+        after.set_action_data(
+            Action::Run,
+            ActionData {
+                state: ButtonState::Pressed,
+                consumed: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            check_invariants(&before, &after, &Action::Run),
+            Some(InvariantViolation::ConsumedWhilePressed {
+                action: Action::Run
+            })
+        );
+
+        after.set_action_data(
+            Action::Run,
+            ActionData {
+                timing_preserved: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            check_invariants(&before, &after, &Action::Run),
+            Some(InvariantViolation::TimingPreservedWithoutConsumption {
+                action: Action::Run
+            })
+        );
+    }
+
+    #[test]
+    fn fuzzing_is_deterministic() {
+        let mut first = FuzzRng::new(7);
+        let mut second = FuzzRng::new(7);
+        let actions = [Action::Run, Action::Jump];
+        for _ in 0..100 {
+            assert_eq!(
+                FuzzOperation::random(&mut first, &actions),
+                FuzzOperation::random(&mut second, &actions)
+            );
+        }
+    }
+}
@@ -1,8 +1,13 @@
 //! Information about when an action was pressed or released.
 
+use crate::Actionlike;
+
+use core::marker::PhantomData;
+
 use bevy::{
+    ecs::{entity::Entity, event::Event, system::Resource},
     reflect::Reflect,
-    utils::{Duration, Instant},
+    utils::{Duration, HashMap, Instant},
 };
 use serde::{Deserialize, Serialize};
 
@@ -10,11 +15,17 @@ use serde::{Deserialize, Serialize};
 ///
 /// This struct is principally used as a field on [`ActionData`](crate::action_state::ActionData),
 /// which itself lives inside an [`ActionState`](crate::action_state::ActionState).
-#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Reflect)]
+///
+/// The `pressed_at_raw` instant is ignored when comparing timings,
+/// so that equality does not depend on when exactly a press was observed, and survives serialization.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Reflect)]
 pub struct Timing {
     /// The [`Instant`] at which the button was pressed or released
     /// Recorded as the [`Time`](bevy::time::Time) at the start of the tick after the state last changed.
     /// If this is none, [`Timing::tick`] has not been called yet.
+    ///
+    /// Instants are only meaningful within a single process, so this is not serialized.
+    /// Deserialized timings instead resume from their `current_duration` on the next tick.
     #[serde(skip)]
     pub instant_started: Option<Instant>,
     /// The [`Duration`] for which the button has been pressed or released.
@@ -23,8 +34,44 @@ pub struct Timing {
     pub current_duration: Duration,
     /// The [`Duration`] for which the button was pressed or released before the state last changed.
     pub previous_duration: Duration,
+    /// The [`Instant`] at which the button was pressed, as observed when the press was recorded
+    ///
+    /// Unlike `instant_started`, this is not synchronized with the start of the frame.
+    /// It is `None` while the button is released.
+    /// See [`ActionState::sub_frame_offset`](crate::action_state::ActionState::sub_frame_offset) for its caveats.
+    #[serde(skip)]
+    pub pressed_at_raw: Option<Instant>,
+    /// The number of ticks for which the button has been pressed or released.
+    ///
+    /// This begins at 0 when the state changes, and is incremented by each call to [`Timing::tick`].
+    #[serde(default)]
+    pub current_frame_count: u32,
+    /// The number of ticks for which the button was pressed or released before the state last changed.
+    #[serde(default)]
+    pub previous_frame_count: u32,
+}
+
+impl PartialEq for Timing {
+    fn eq(&self, other: &Self) -> bool {
+        let Timing {
+            instant_started,
+            current_duration,
+            previous_duration,
+            pressed_at_raw: _,
+            current_frame_count,
+            previous_frame_count,
+        } = self;
+
+        *instant_started == other.instant_started
+            && *current_duration == other.current_duration
+            && *previous_duration == other.previous_duration
+            && *current_frame_count == other.current_frame_count
+            && *previous_frame_count == other.previous_frame_count
+    }
 }
 
+impl Eq for Timing {}
+
 impl PartialOrd for Timing {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         self.current_duration.partial_cmp(&other.current_duration)
@@ -32,36 +79,359 @@ impl PartialOrd for Timing {
 }
 
 impl Timing {
-    /// Advances the `current_duration` of this timer
+    /// Advances the `current_duration` and `current_frame_count` of this timer
     ///
-    /// If the `instant_started` is None, it will be set to the current time.
-    /// This design allows us to ensure that the timing is always synchronized with the start of each frame.
+    /// If the `instant_started` is None, it will be set to the current time, minus any `current_duration` that has already elapsed.
+    /// This design allows us to ensure that the timing is always synchronized with the start of each frame,
+    /// and lets deserialized timings carry on from where they were saved.
     pub fn tick(&mut self, current_instant: Instant, previous_instant: Instant) {
+        self.current_frame_count = self.current_frame_count.saturating_add(1);
         if let Some(instant_started) = self.instant_started {
             self.current_duration = current_instant - instant_started;
         } else {
-            self.current_duration = current_instant - previous_instant;
-            self.instant_started = Some(previous_instant);
+            // The duration is zero unless this timing was deserialized
+            self.current_duration += current_instant - previous_instant;
+            // If this would be before the earliest representable instant,
+            // the duration keeps accumulating on the following ticks instead
+            self.instant_started = current_instant.checked_sub(self.current_duration);
         }
     }
 
-    /// Flips the metaphorical hourglass, storing `current_duration` in `previous_duration` and resetting `instant_started`
+    /// Flips the metaphorical hourglass, storing `current_duration` in `previous_duration` and resetting `instant_started` and `pressed_at_raw`
     ///
+    /// The frame counts are flipped in the same way.
     /// This method is called whenever actions are pressed or released
     pub fn flip(&mut self) {
         self.previous_duration = self.current_duration;
         self.current_duration = Duration::ZERO;
+        self.previous_frame_count = self.current_frame_count;
+        self.current_frame_count = 0;
         self.instant_started = None;
+        self.pressed_at_raw = None;
+    }
+}
+
+/// The durations of the last few times an action was held, from oldest to newest
+///
+/// This is stored on the [`ActionData`](crate::action_state::ActionData) of actions that opt in using
+/// [`ActionState::track_recent_holds`](crate::action_state::ActionState::track_recent_holds),
+/// so actions that don't use it never allocate.
+/// Each hold is recorded when the action is released, or when it is consumed under [`ConsumePolicy::FlipTiming`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldHistory {
+    max_len: usize,
+    durations: Vec<Duration>,
+    release_instants: Vec<Option<Instant>>,
+}
+
+impl HoldHistory {
+    /// The number of holds remembered by [`ActionState::track_recent_holds`](crate::action_state::ActionState::track_recent_holds)
+    pub const DEFAULT_LEN: usize = 4;
+
+    /// Creates an empty history that remembers the last `max_len` holds
+    #[must_use]
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            max_len,
+            durations: Vec::with_capacity(max_len),
+            release_instants: Vec::with_capacity(max_len),
+        }
+    }
+
+    /// The number of holds that are remembered
+    #[must_use]
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// The durations of the remembered holds, from oldest to newest
+    #[must_use]
+    pub fn durations(&self) -> &[Duration] {
+        &self.durations
+    }
+
+    /// The instants at which each of the remembered holds ended, in the same order as [`HoldHistory::durations`]
+    ///
+    /// Like [`Timing::instant_started`], these are synchronized with the start of the tick in which the action was released.
+    /// They are [`None`] for holds that ended before they were ever ticked.
+    #[must_use]
+    pub fn release_instants(&self) -> &[Option<Instant>] {
+        &self.release_instants
+    }
+
+    /// Records the hold described by the `timing` of an action that is being released
+    ///
+    /// The oldest hold is forgotten once there are more than [`HoldHistory::max_len`].
+    pub fn record(&mut self, timing: &Timing) {
+        if self.max_len == 0 {
+            return;
+        }
+
+        if self.durations.len() == self.max_len {
+            self.durations.remove(0);
+            self.release_instants.remove(0);
+        }
+
+        self.durations.push(timing.current_duration);
+        self.release_instants.push(
+            timing
+                .instant_started
+                .map(|instant_started| instant_started + timing.current_duration),
+        );
+    }
+
+    /// Forgets all of the remembered holds
+    pub fn clear(&mut self) {
+        self.durations.clear();
+        self.release_instants.clear();
     }
 }
 
+/// The clock used to measure the durations of actions
+///
+/// Insert this as a resource to choose how [`tick_action_state`](crate::systems::tick_action_state) measures time.
+/// If it does not exist, [`TickClock::RealTime`] is used.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TickClock {
+    /// Measures durations using [`Time<Real>`](bevy::time::Real), the wall clock at the start of each frame
+    #[default]
+    RealTime,
+    /// Measures durations using [`Time<Virtual>`](bevy::time::Virtual), which can be paused and scaled
+    Virtual,
+    /// Measures durations using a synthetic instant, which only moves when [`TickClock::advance`] is called
+    ///
+    /// This is intended for dedicated servers that simulate at their own pace, and may fast-forward several ticks at once.
+    /// In this mode, the [`Timing::instant_started`] of each action lies on the same synthetic timeline as `now`:
+    /// it never moves backwards, but is unrelated to the wall clock, so only compare it to other instants from this clock.
+    Manual {
+        /// The current instant, as read by the next tick
+        now: Instant,
+    },
+}
+
+impl TickClock {
+    /// Creates a [`TickClock::Manual`] that starts at the current instant
+    #[must_use]
+    pub fn manual() -> Self {
+        Self::Manual {
+            now: Instant::now(),
+        }
+    }
+
+    /// Moves a [`TickClock::Manual`] forwards by `duration`
+    ///
+    /// The elapsed time is measured by the next tick.
+    /// This has no effect on the other clocks, which follow Bevy's [`Time`](bevy::time::Time).
+    pub fn advance(&mut self, duration: Duration) {
+        if let TickClock::Manual { now } = self {
+            *now += duration;
+        }
+    }
+}
+
+/// What happens when an [`ActionState`](crate::action_state::ActionState) is ticked more than once in the same frame?
+///
+/// Extra ticks clear the `just_pressed` and `just_released` values before they can be read,
+/// which is easy to cause by accident and miserable to debug.
+/// This resource is read by [`tick_action_state`](crate::systems::tick_action_state),
+/// which passes it to [`ActionState::tick_in_frame`](crate::action_state::ActionState::tick_in_frame).
+#[non_exhaustive]
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DoubleTickPolicy {
+    /// Extra ticks in the same frame are ignored
+    #[default]
+    Skip,
+    /// Extra ticks in the same frame are applied, but a warning is logged in debug builds
+    Warn,
+    /// Extra ticks in the same frame are applied silently
+    Allow,
+}
+
+/// What happens to the [`Timing`] of an action when it is consumed by [`ActionState::consume`](crate::action_state::ActionState::consume)?
+///
+/// Set the policy of every [`ActionState<A>`](crate::action_state::ActionState) using [`InputManagerPlugin::with_consume_policy`](crate::plugin::InputManagerPlugin::with_consume_policy),
+/// which is applied when they are ticked by [`tick_action_state`](crate::systems::tick_action_state).
+/// Individual states can override it using [`ActionState::set_consume_policy`](crate::action_state::ActionState::set_consume_policy).
+///
+/// Durations never advance while an action is consumed.
+/// Under either policy, once a consumed action is released, its `previous_duration` is the duration for which it was held before being consumed.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Reflect)]
+pub enum ConsumePolicy {
+    /// The timing is flipped when the action is consumed, as if it had been released
+    ///
+    /// The `current_duration` restarts from zero, and the hold duration is moved to `previous_duration` straight away.
+    #[default]
+    FlipTiming,
+    /// The timing is frozen when the action is consumed, and only flipped once the action is really released
+    ///
+    /// The `current_duration` keeps the hold duration while the action is consumed,
+    /// and `previous_duration` is left untouched until the release.
+    PreserveTiming,
+}
+
+/// The [`ConsumePolicy`] of the [`ActionState`](crate::action_state::ActionState)s of the [`Actionlike`] type `A`, read by [`tick_action_state`](crate::systems::tick_action_state)
+///
+/// Without this resource, states use [`ConsumePolicy::FlipTiming`].
+/// This resource is inserted by [`InputManagerPlugin::with_consume_policy`](crate::plugin::InputManagerPlugin::with_consume_policy).
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct ActionConsumePolicy<A: Actionlike> {
+    policy: ConsumePolicy,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> ActionConsumePolicy<A> {
+    /// Creates a new resource, setting the `policy` of every [`ActionState<A>`](crate::action_state::ActionState)
+    #[must_use]
+    pub fn new(policy: ConsumePolicy) -> Self {
+        Self {
+            policy,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The [`ConsumePolicy`] of every [`ActionState<A>`](crate::action_state::ActionState) that does not override it
+    #[must_use]
+    pub fn get(&self) -> ConsumePolicy {
+        self.policy
+    }
+}
+
+/// Should the durations of an action follow the relative speed of [`Time<Virtual>`](bevy::time::Virtual)?
+///
+/// This only matters when durations are measured using [`TickClock::Virtual`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+pub enum TimeScalePolicy {
+    /// Durations follow virtual time, so they slow down with the game and stop while it is paused
+    #[default]
+    Scaled,
+    /// Durations follow [`Time<Real>`](bevy::time::Real), which is useful for menus and other UI actions
+    Unscaled,
+}
+
+/// The [`TimeScalePolicy`] of each action, read by [`tick_action_state`](crate::systems::tick_action_state)
+///
+/// Actions that are not in this map are [`TimeScalePolicy::Scaled`].
+/// This resource is inserted by [`InputManagerPlugin::with_time_scale_policy`](crate::plugin::InputManagerPlugin::with_time_scale_policy).
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct TimeScalePolicies<A: Actionlike> {
+    policies: HashMap<A, TimeScalePolicy>,
+}
+
+impl<A: Actionlike> Default for TimeScalePolicies<A> {
+    fn default() -> Self {
+        Self {
+            policies: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> TimeScalePolicies<A> {
+    /// Sets the [`TimeScalePolicy`] of the `action`
+    pub fn insert(&mut self, action: A, policy: TimeScalePolicy) -> &mut Self {
+        self.policies.insert(action, policy);
+        self
+    }
+
+    /// The [`TimeScalePolicy`] of the `action`
+    #[must_use]
+    pub fn get(&self, action: &A) -> TimeScalePolicy {
+        self.policies.get(action).copied().unwrap_or_default()
+    }
+}
+
+/// Durations at which an [`ActionHoldEvent`] is sent while an action is held
+///
+/// Insert this as a resource to receive events such as "held for 0.5 seconds", rather than polling
+/// [`ActionState::current_duration`](crate::action_state::ActionState::current_duration) every frame.
+/// Each threshold fires once per press, and is re-armed when the action is released.
+/// Consumed actions never fire thresholds.
+///
+/// Thresholds of [`Duration::ZERO`] are ignored: use [`ActionState::just_pressed`](crate::action_state::ActionState::just_pressed) instead.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct HoldThresholds<A: Actionlike> {
+    thresholds: HashMap<A, Vec<Duration>>,
+}
+
+impl<A: Actionlike> Default for HoldThresholds<A> {
+    fn default() -> Self {
+        Self {
+            thresholds: HashMap::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> HoldThresholds<A> {
+    /// Sets the `thresholds` at which the `action` sends an [`ActionHoldEvent`], replacing any existing thresholds
+    pub fn insert(
+        &mut self,
+        action: A,
+        thresholds: impl IntoIterator<Item = Duration>,
+    ) -> &mut Self {
+        let mut thresholds: Vec<Duration> = thresholds.into_iter().collect();
+        thresholds.sort();
+        thresholds.dedup();
+        self.thresholds.insert(action, thresholds);
+        self
+    }
+
+    /// Removes all thresholds for the `action`
+    pub fn remove(&mut self, action: &A) -> &mut Self {
+        self.thresholds.remove(action);
+        self
+    }
+
+    /// The thresholds of the `action`, in ascending order
+    #[must_use]
+    pub fn get(&self, action: &A) -> &[Duration] {
+        self.thresholds.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterates over each action and its thresholds
+    pub fn iter(&self) -> impl Iterator<Item = (&A, &[Duration])> {
+        self.thresholds
+            .iter()
+            .map(|(action, thresholds)| (action, thresholds.as_slice()))
+    }
+
+    /// The thresholds that were crossed when a hold went from the `previous` duration to the `current` duration
+    pub fn crossed(
+        &self,
+        action: &A,
+        previous: Duration,
+        current: Duration,
+    ) -> impl Iterator<Item = Duration> + '_ {
+        self.get(action)
+            .iter()
+            .copied()
+            .filter(move |threshold| previous < *threshold && *threshold <= current)
+    }
+}
+
+/// Sent when a held action crosses one of its [`HoldThresholds`]
+///
+/// These are sent by [`tick_action_state`](crate::systems::tick_action_state).
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct ActionHoldEvent<A: Actionlike> {
+    /// The action that was held
+    pub action: A,
+    /// The entity whose [`ActionState`](crate::action_state::ActionState) holds the action,
+    /// or `None` if the `ActionState` is a resource
+    pub entity: Option<Entity>,
+    /// The threshold that was crossed
+    pub threshold: Duration,
+}
+
 #[cfg(test)]
 mod tests {
     use crate as leafwing_input_manager;
     use bevy::prelude::Reflect;
     use leafwing_input_manager_macros::Actionlike;
+    use serde::{Deserialize, Serialize};
 
-    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    #[derive(
+        Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect, Serialize, Deserialize,
+    )]
     enum Action {
         Run,
         Jump,
@@ -92,6 +462,20 @@ mod tests {
         assert!(!action_state.just_pressed(&Action::Jump));
     }
 
+    #[test]
+    fn raw_press_instants_are_not_compared() {
+        use crate::action_state::ActionState;
+        use bevy::utils::{Duration, Instant};
+
+        let now = Instant::now();
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press_at(&Action::Run, now);
+        let mut later_action_state = ActionState::<Action>::default();
+        later_action_state.press_at(&Action::Run, now + Duration::from_millis(3));
+
+        assert_eq!(action_state, later_action_state);
+    }
+
     #[test]
     fn durations() {
         use crate::action_state::ActionState;
@@ -148,4 +532,106 @@ mod tests {
         assert_eq!(action_state.current_duration(&Action::Jump), Duration::ZERO);
         assert_eq!(action_state.previous_duration(&Action::Jump), t2 - t0);
     }
+
+    #[test]
+    fn frame_counts() {
+        use crate::action_state::ActionState;
+        use bevy::utils::Instant;
+
+        let mut action_state = ActionState::<Action>::default();
+        let now = Instant::now();
+
+        // Actions start with no frames counted
+        assert_eq!(action_state.current_frame_count(&Action::Jump), 0);
+        assert_eq!(action_state.previous_frame_count(&Action::Jump), 0);
+
+        // Pressing resets the count, which then advances with each tick
+        action_state.press(&Action::Jump);
+        assert_eq!(action_state.current_frame_count(&Action::Jump), 0);
+        for frame in 1..=3 {
+            action_state.tick(now, now);
+            assert_eq!(action_state.current_frame_count(&Action::Jump), frame);
+        }
+
+        // Consuming releases the action, and the count does not advance while consumed
+        action_state.consume(&Action::Jump);
+        assert_eq!(action_state.previous_frame_count(&Action::Jump), 3);
+        action_state.tick(now, now);
+        assert_eq!(action_state.current_frame_count(&Action::Jump), 0);
+
+        // Once released, the count advances again
+        action_state.release(&Action::Jump);
+        assert_eq!(action_state.previous_frame_count(&Action::Jump), 3);
+        action_state.tick(now, now);
+        action_state.tick(now, now);
+        assert_eq!(action_state.current_frame_count(&Action::Jump), 2);
+
+        // And pressing again swaps the released count
+        action_state.press(&Action::Jump);
+        assert_eq!(action_state.current_frame_count(&Action::Jump), 0);
+        assert_eq!(action_state.previous_frame_count(&Action::Jump), 2);
+    }
+
+    #[test]
+    fn durations_survive_serialization() {
+        use crate::action_state::ActionState;
+        use bevy::utils::{Duration, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+
+        // Holding an action for two seconds, then saving
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::from_secs(1);
+        let t2 = t1 + Duration::from_secs(1);
+        action_state.press(&Action::Run);
+        action_state.tick(t1, t0);
+        action_state.tick(t2, t1);
+        assert_eq!(
+            action_state.current_duration(&Action::Run),
+            Duration::from_secs(2)
+        );
+        let bytes = bincode::serialize(&action_state).unwrap();
+
+        // Loading in a new process, whose instants are unrelated to the old ones
+        let mut action_state: ActionState<Action> = bincode::deserialize(&bytes).unwrap();
+        assert!(action_state.pressed(&Action::Run));
+        assert_eq!(action_state.instant_started(&Action::Run), None);
+        assert_eq!(
+            action_state.current_duration(&Action::Run),
+            Duration::from_secs(2)
+        );
+
+        // The hold carries on from its saved duration
+        let s0 = Instant::now() + Duration::from_secs(3600);
+        let s1 = s0 + Duration::from_millis(16);
+        action_state.tick(s1, s0);
+        assert_eq!(
+            action_state.current_duration(&Action::Run),
+            Duration::from_millis(2016)
+        );
+        assert_eq!(
+            action_state.instant_started(&Action::Run),
+            Some(s1 - Duration::from_millis(2016))
+        );
+
+        let s2 = s1 + Duration::from_millis(16);
+        action_state.tick(s2, s1);
+        assert_eq!(
+            action_state.current_duration(&Action::Run),
+            Duration::from_millis(2032)
+        );
+
+        // Released actions are unaffected, and restart from zero when pressed
+        assert_eq!(action_state.current_duration(&Action::Jump), Duration::ZERO);
+        action_state.release(&Action::Run);
+        assert_eq!(
+            action_state.previous_duration(&Action::Run),
+            Duration::from_millis(2032)
+        );
+        action_state.tick(s2 + Duration::from_millis(16), s2);
+        assert_eq!(
+            action_state.current_duration(&Action::Run),
+            Duration::from_millis(16)
+        );
+    }
 }
@@ -0,0 +1,1009 @@
+//! Gestures made on the touch screen, such as taps, double taps, multi-finger taps, long presses, swipes and pinches, and virtual joysticks
+//!
+//! Bind a [`TouchGesture`] with [`InputKind::Touch`] like any other button,
+//! or restrict it to a part of the screen with [`TouchGesture::within_rect`].
+//! Bind a [`TouchJoystick`] with [`InputKind::TouchJoystick`] like a [`DualAxis`](crate::axislike::DualAxis).
+//! Gestures are read from Bevy's [`Touches`] resource,
+//! and timed using the [`TouchHistory`] resource, which is kept up to date by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+//! Whether single taps wait to see if they are the start of a [`TouchGesture::DoubleTap`] is set by the [`TapArbitration`] resource.
+
+use crate::axislike::{DeadZoneShape, DualAxis, DualAxisData};
+use crate::user_input::InputKind;
+
+use bevy::ecs::system::Resource;
+use bevy::input::touch::{Touch, Touches};
+use bevy::math::{Rect, Vec2};
+use bevy::reflect::Reflect;
+use bevy::utils::{Duration, FloatOrd, HashMap};
+use serde::{Deserialize, Serialize};
+
+/// A gesture made on the touch screen
+///
+/// # Example
+/// ```rust
+/// use bevy::math::Rect;
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::Duration;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::touch::{SwipeDirection, TouchGesture};
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+/// enum Action {
+///     Select,
+///     Inspect,
+///     Jump,
+///     NextPage,
+///     Zoom,
+///     Undo,
+///     Redo,
+///     Dash,
+/// }
+///
+/// let input_map = InputMap::new([
+///     (Action::Select, TouchGesture::Tap),
+///     (
+///         Action::Inspect,
+///         TouchGesture::LongPress {
+///             min_duration: Duration::from_millis(500),
+///         },
+///     ),
+///     (Action::NextPage, TouchGesture::swipe(SwipeDirection::Left)),
+///     (Action::Zoom, TouchGesture::pinch()),
+///     (Action::Undo, TouchGesture::multi_tap(2)),
+///     (Action::Redo, TouchGesture::multi_tap(3)),
+///     (Action::Dash, TouchGesture::double_tap()),
+/// ])
+/// // Only taps on the right half of the screen jump
+/// .insert(
+///     Action::Jump,
+///     TouchGesture::Tap.within_rect(Rect::new(0.5, 0.0, 1.0, 1.0)),
+/// )
+/// .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub enum TouchGesture {
+    /// A finger lifted from the screen shortly after touching it, without moving much
+    ///
+    /// Pressed on the frame the finger is lifted, if it touched the screen for at most [`TouchGesture::TAP_MAX_DURATION`],
+    /// and moved at most [`TouchGesture::MAX_MOVEMENT`] away from where it touched it.
+    ///
+    /// Fingers that tap the screen together make a [`TouchGesture::MultiTap`] instead.
+    /// So while other fingers are on the screen with it, the tap is withheld until it is known whether they all tap it,
+    /// which is at most [`TouchGesture::TAP_MAX_DURATION`] after the first of them touched the screen.
+    Tap,
+    /// Two [`TouchGesture::Tap`]s in quick succession, close to each other
+    ///
+    /// Pressed on the frame the second tap is performed, if it was made within `max_interval` of the first tap,
+    /// and at most `max_movement` away from where the first tap touched the screen.
+    /// Only taps that touched the screen within the `region` count, if any, and each of them starts at most one double tap.
+    /// Whether the single taps are also pressed is set by the [`TapArbitration`] resource.
+    /// Create it with [`TouchGesture::double_tap`] to use the default thresholds.
+    DoubleTap {
+        /// The longest time between the two taps
+        max_interval: Duration,
+        /// How far apart the two taps can touch the screen, in logical pixels
+        max_movement: f32,
+        /// The part of the screen the taps must touch, in normalized coordinates
+        ///
+        /// `(0, 0)` is the top left corner of the window and `(1, 1)` its bottom right corner.
+        /// Each region is tracked on its own, so double taps can be made on several regions at the same time.
+        region: Option<Rect>,
+    },
+    /// Several fingers that touched the screen together, then were lifted without moving much
+    ///
+    /// Pressed on the frame the last of them is lifted, if each of them would have been a [`TouchGesture::Tap`],
+    /// and at most `max_duration` passed from the first of them touching the screen to the last of them being lifted.
+    /// The single-finger taps of these fingers aren't pressed.
+    /// Create it with [`TouchGesture::multi_tap`] to use the default thresholds.
+    MultiTap {
+        /// How many fingers must tap the screen together, at least two
+        fingers: u8,
+        /// How far apart the fingers can touch the screen, in logical pixels
+        max_spread: f32,
+        /// The longest the fingers can touch the screen, from the first of them touching it to the last of them being lifted
+        max_duration: Duration,
+    },
+    /// A finger held on the screen without moving much
+    ///
+    /// Pressed once the finger has touched the screen for `min_duration`, so the action is `just_pressed` at that moment.
+    /// It stays pressed until the finger is lifted, or moves further than [`TouchGesture::MAX_MOVEMENT`] away from where it touched the screen.
+    LongPress {
+        /// How long the finger must touch the screen before the gesture is pressed
+        min_duration: Duration,
+    },
+    /// A finger quickly moved across the screen in a `direction`, then lifted
+    ///
+    /// Pressed on the frame the finger is lifted, if it moved at least `min_distance` away from where it touched the screen within `max_duration`.
+    /// Fingers that touched the screen within the region of a [`TouchJoystick`] bound in the same [`InputMap`](crate::input_map::InputMap)
+    /// belong to that joystick, and don't swipe.
+    /// Create it with [`TouchGesture::swipe`] to use the default thresholds.
+    Swipe {
+        /// The direction the finger must move in
+        direction: SwipeDirection,
+        /// How far the finger must move from where it touched the screen, in logical pixels
+        min_distance: f32,
+        /// The longest the finger can touch the screen
+        max_duration: Duration,
+        /// How many degrees away from a diagonal a swipe can be to press both of the directions it is between
+        ///
+        /// If [`None`], diagonal swipes only press the direction they are closest to.
+        diagonal_tolerance: Option<f32>,
+    },
+    /// Two fingers moved closer together or further apart
+    ///
+    /// Its value is how much further apart the two fingers that touched the screen first moved on this frame, in logical pixels, times the `sensitivity`:
+    /// positive while spreading them, and negative while pinching them.
+    /// Pressed while its value isn't zero, so it can be bound to the same action as a [`SingleAxis::mouse_wheel_y`](crate::axislike::SingleAxis::mouse_wheel_y).
+    /// Create it with [`TouchGesture::pinch`] to use the default sensitivity.
+    Pinch {
+        /// How much the value changes for each logical pixel the fingers move closer together or further apart
+        sensitivity: f32,
+    },
+}
+
+impl TouchGesture {
+    /// The longest a finger can touch the screen for its release to be a [`TouchGesture::Tap`]
+    pub const TAP_MAX_DURATION: Duration = Duration::from_millis(300);
+
+    /// How far a finger can move from where it touched the screen while tapping or long pressing, in logical pixels
+    pub const MAX_MOVEMENT: f32 = 20.0;
+
+    /// The default `min_distance` of a [`TouchGesture::Swipe`], in logical pixels
+    pub const SWIPE_MIN_DISTANCE: f32 = 50.0;
+
+    /// The default `max_duration` of a [`TouchGesture::Swipe`]
+    pub const SWIPE_MAX_DURATION: Duration = Duration::from_millis(500);
+
+    /// The default `sensitivity` of a [`TouchGesture::Pinch`]
+    pub const PINCH_SENSITIVITY: f32 = 0.01;
+
+    /// The default `max_spread` of a [`TouchGesture::MultiTap`], in logical pixels
+    pub const MULTI_TAP_MAX_SPREAD: f32 = 300.0;
+
+    /// The default `max_interval` of a [`TouchGesture::DoubleTap`]
+    pub const DOUBLE_TAP_MAX_INTERVAL: Duration = Duration::from_millis(300);
+
+    /// The default `max_movement` of a [`TouchGesture::DoubleTap`], in logical pixels
+    pub const DOUBLE_TAP_MAX_MOVEMENT: f32 = 50.0;
+
+    /// Creates a [`TouchGesture::Swipe`] in the `direction`, with the default thresholds
+    ///
+    /// Diagonal swipes only press the direction they are closest to.
+    #[must_use]
+    pub const fn swipe(direction: SwipeDirection) -> TouchGesture {
+        TouchGesture::Swipe {
+            direction,
+            min_distance: Self::SWIPE_MIN_DISTANCE,
+            max_duration: Self::SWIPE_MAX_DURATION,
+            diagonal_tolerance: None,
+        }
+    }
+
+    /// Creates a [`TouchGesture::DoubleTap`] anywhere on the screen, with the default thresholds
+    #[must_use]
+    pub const fn double_tap() -> TouchGesture {
+        TouchGesture::DoubleTap {
+            max_interval: Self::DOUBLE_TAP_MAX_INTERVAL,
+            max_movement: Self::DOUBLE_TAP_MAX_MOVEMENT,
+            region: None,
+        }
+    }
+
+    /// Creates a [`TouchGesture::MultiTap`] with the given number of `fingers`, and the default thresholds
+    ///
+    /// The fingers can touch the screen up to [`TouchGesture::MULTI_TAP_MAX_SPREAD`] apart,
+    /// and must all be lifted within [`TouchGesture::TAP_MAX_DURATION`] of the first of them touching it.
+    #[must_use]
+    pub const fn multi_tap(fingers: u8) -> TouchGesture {
+        TouchGesture::MultiTap {
+            fingers,
+            max_spread: Self::MULTI_TAP_MAX_SPREAD,
+            max_duration: Self::TAP_MAX_DURATION,
+        }
+    }
+
+    /// Creates a [`TouchGesture::Pinch`] with the default sensitivity
+    #[must_use]
+    pub const fn pinch() -> TouchGesture {
+        TouchGesture::Pinch {
+            sensitivity: Self::PINCH_SENSITIVITY,
+        }
+    }
+
+    /// Only performs this gesture with fingers that touched the screen within the `rect`
+    ///
+    /// The `rect` is in normalized coordinates, where `(0, 0)` is the top left corner of the window and `(1, 1)` its bottom right corner.
+    #[must_use]
+    pub fn within_rect(self, rect: Rect) -> InputKind {
+        InputKind::TouchWithin(self, TouchRegion(rect))
+    }
+
+    /// Is this gesture performed by any finger on the screen, that touched it within the `region` if any?
+    ///
+    /// Fingers that touched the screen within any of the `joystick_regions` belong to a [`TouchJoystick`], and don't swipe.
+    /// The `double_taps` remember the first taps of the [`TouchGesture::DoubleTap`]s bound in the same input map.
+    /// Without a [`TouchHistory`], every finger is treated as if it just touched the screen, taps are never withheld,
+    /// and multi-finger taps and gestures restricted to a `region` are never performed.
+    /// Without a [`DoubleTapTracker`], taps are never delayed, and double taps are never performed.
+    #[must_use]
+    pub fn performed(
+        self,
+        touches: &Touches,
+        history: Option<&TouchHistory>,
+        region: Option<TouchRegion>,
+        joystick_regions: &[Rect],
+        double_taps: Option<&DoubleTapTracker>,
+    ) -> bool {
+        let in_region =
+            |start_position: Vec2| touched_within_region(start_position, region, history);
+        match (self, history) {
+            (TouchGesture::Pinch { .. }, _) => {
+                return self.value(touches, history, region, joystick_regions, double_taps) != 0.0;
+            }
+            (TouchGesture::DoubleTap { .. }, _) => {
+                return double_taps.is_some_and(|double_taps| double_taps.performed(self));
+            }
+            // The history knows which taps aren't part of a multi-finger tap, and the tracker which of them are delayed
+            (TouchGesture::Tap, Some(history)) => {
+                let taps = double_taps.map_or(history.taps(), DoubleTapTracker::taps);
+                return taps.iter().any(|&position| in_region(position));
+            }
+            (
+                TouchGesture::MultiTap {
+                    fingers,
+                    max_spread,
+                    max_duration,
+                },
+                Some(history),
+            ) => {
+                return history.multi_tap().is_some_and(|multi_tap| {
+                    multi_tap.start_positions.len() == usize::from(fingers)
+                        && multi_tap.spread() <= max_spread
+                        && multi_tap.duration <= max_duration
+                        && multi_tap
+                            .start_positions
+                            .iter()
+                            .all(|&position| in_region(position))
+                });
+            }
+            _ => (),
+        }
+
+        let on_joystick = |touch: &Touch| {
+            joystick_regions.iter().any(|&joystick_region| {
+                touched_within_region(
+                    touch.start_position(),
+                    Some(TouchRegion(joystick_region)),
+                    history,
+                )
+            })
+        };
+        let duration =
+            |touch: &Touch| history.map_or(Duration::ZERO, |history| history.duration(touch.id()));
+
+        let held = touches.iter().map(|touch| (touch, false));
+        let released = touches.iter_just_released().map(|touch| (touch, true));
+        held.chain(released)
+            .filter(|(touch, _)| in_region(touch.start_position()))
+            .filter(|(touch, _)| !matches!(self, TouchGesture::Swipe { .. }) || !on_joystick(touch))
+            .any(|(touch, released)| self.performed_by(touch, released, duration(touch)))
+    }
+
+    /// The value of this gesture, performed by the fingers on the screen that touched it within the `region` if any
+    ///
+    /// This is the value of a [`TouchGesture::Pinch`], and `1.0` or `0.0` for the other gestures, depending on whether they are [`performed`](TouchGesture::performed).
+    /// Without a [`TouchHistory`], fingers are treated as if they didn't move since the previous frame, so pinches are zero.
+    #[must_use]
+    pub fn value(
+        self,
+        touches: &Touches,
+        history: Option<&TouchHistory>,
+        region: Option<TouchRegion>,
+        joystick_regions: &[Rect],
+        double_taps: Option<&DoubleTapTracker>,
+    ) -> f32 {
+        let TouchGesture::Pinch { sensitivity } = self else {
+            return if self.performed(touches, history, region, joystick_regions, double_taps) {
+                1.0
+            } else {
+                0.0
+            };
+        };
+
+        let mut fingers: Vec<&Touch> = touches
+            .iter()
+            .filter(|touch| touched_within_region(touch.start_position(), region, history))
+            .collect();
+        fingers.sort_by_key(|touch| touch.id());
+        let [first, second, ..] = fingers[..] else {
+            return 0.0;
+        };
+
+        let previous_position = |touch: &Touch| {
+            history
+                .and_then(|history| history.previous_position(touch.id()))
+                .unwrap_or(touch.position())
+        };
+        let distance = first.position().distance(second.position());
+        let previous_distance = previous_position(first).distance(previous_position(second));
+        (distance - previous_distance) * sensitivity
+    }
+
+    /// Is this gesture performed by the `touch`, which was `released` on this frame or is still held, and has touched the screen for `duration`?
+    fn performed_by(self, touch: &Touch, released: bool, duration: Duration) -> bool {
+        let distance = touch.distance().length();
+
+        match self {
+            TouchGesture::Tap => {
+                released && duration <= Self::TAP_MAX_DURATION && distance <= Self::MAX_MOVEMENT
+            }
+            TouchGesture::LongPress { min_duration } => {
+                !released && duration >= min_duration && distance <= Self::MAX_MOVEMENT
+            }
+            TouchGesture::Swipe {
+                direction,
+                min_distance,
+                max_duration,
+                diagonal_tolerance,
+            } => {
+                let angle = touch
+                    .distance()
+                    .angle_between(direction.screen_direction())
+                    .abs()
+                    .to_degrees();
+
+                released
+                    && duration <= max_duration
+                    && distance >= min_distance
+                    && angle < 45.0 + diagonal_tolerance.unwrap_or_default()
+            }
+            // Made by several taps or fingers
+            TouchGesture::DoubleTap { .. }
+            | TouchGesture::MultiTap { .. }
+            | TouchGesture::Pinch { .. } => false,
+        }
+    }
+}
+
+/// Did a finger that touched the screen at `start_position` touch it within the `region`, if any?
+///
+/// Without the size of the window in the `history`, no finger touched it within a region.
+fn touched_within_region(
+    start_position: Vec2,
+    region: Option<TouchRegion>,
+    history: Option<&TouchHistory>,
+) -> bool {
+    let Some(TouchRegion(rect)) = region else {
+        return true;
+    };
+    history
+        .and_then(|history| history.normalize(start_position))
+        .is_some_and(|position| rect.contains(position))
+}
+
+impl Eq for TouchGesture {}
+impl std::hash::Hash for TouchGesture {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self {
+            TouchGesture::Tap => (),
+            TouchGesture::DoubleTap {
+                max_interval,
+                max_movement,
+                region,
+            } => {
+                max_interval.hash(state);
+                FloatOrd(*max_movement).hash(state);
+                region.map(TouchRegion).hash(state);
+            }
+            TouchGesture::MultiTap {
+                fingers,
+                max_spread,
+                max_duration,
+            } => {
+                fingers.hash(state);
+                FloatOrd(*max_spread).hash(state);
+                max_duration.hash(state);
+            }
+            TouchGesture::LongPress { min_duration } => min_duration.hash(state),
+            TouchGesture::Swipe {
+                direction,
+                min_distance,
+                max_duration,
+                diagonal_tolerance,
+            } => {
+                direction.hash(state);
+                FloatOrd(*min_distance).hash(state);
+                max_duration.hash(state);
+                diagonal_tolerance.map(FloatOrd).hash(state);
+            }
+            TouchGesture::Pinch { sensitivity } => FloatOrd(*sensitivity).hash(state),
+        }
+    }
+}
+
+/// The direction of a [`TouchGesture::Swipe`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum SwipeDirection {
+    /// Towards the top of the screen
+    Up,
+    /// Towards the bottom of the screen
+    Down,
+    /// Towards the left of the screen
+    Left,
+    /// Towards the right of the screen
+    Right,
+}
+
+impl SwipeDirection {
+    /// The unit vector pointing in this direction, in screen coordinates where the y axis points down
+    #[must_use]
+    pub fn screen_direction(self) -> Vec2 {
+        match self {
+            SwipeDirection::Up => Vec2::NEG_Y,
+            SwipeDirection::Down => Vec2::Y,
+            SwipeDirection::Left => Vec2::NEG_X,
+            SwipeDirection::Right => Vec2::X,
+        }
+    }
+}
+
+/// How [`TouchGesture::Tap`]s are pressed when a [`TouchGesture::DoubleTap`] is bound in the same [`InputMap`](crate::input_map::InputMap)
+///
+/// This resource is added by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin), with [`TapArbitration::PressBoth`].
+#[derive(
+    Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize,
+)]
+pub enum TapArbitration {
+    /// Taps are pressed as soon as they are made, including the two taps of each double tap
+    #[default]
+    PressBoth,
+    /// Taps within the region of a double tap are delayed until its `max_interval` elapsed without a second tap,
+    /// and aren't pressed if they turn out to be part of a double tap
+    ///
+    /// Taps elsewhere are pressed as soon as they are made.
+    DelayTaps,
+}
+
+/// The first taps of the [`TouchGesture::DoubleTap`]s bound in an input map, waiting for their second tap
+///
+/// Each double tap is tracked on its own, so that double taps can be made on several regions at the same time.
+/// This is kept for each input map by [`update_action_state`](crate::systems::update_action_state),
+/// and updated each frame before the actions are evaluated.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DoubleTapTracker {
+    /// When and where the first tap of each double tap was made
+    first_taps: HashMap<TouchGesture, FirstTap>,
+    /// The double taps performed on this frame
+    performed: Vec<TouchGesture>,
+    /// Where the fingers whose [`TouchGesture::Tap`]s are pressed on this frame touched the screen, in logical pixels
+    taps: Vec<Vec2>,
+}
+
+impl DoubleTapTracker {
+    /// Pairs the taps of this frame with the first taps of the `double_taps`, and decides which single taps are pressed according to the `arbitration`
+    ///
+    /// Gestures other than [`TouchGesture::DoubleTap`] in `double_taps` are ignored.
+    pub fn update(
+        &mut self,
+        double_taps: &[TouchGesture],
+        history: &TouchHistory,
+        arbitration: TapArbitration,
+    ) {
+        let now = history.now();
+        self.performed.clear();
+        self.first_taps
+            .retain(|double_tap, _| double_taps.contains(double_tap));
+
+        // First taps that were never followed by a second tap
+        let mut lone_taps = Vec::new();
+        let mut in_any_region = Vec::new();
+        for &double_tap in double_taps {
+            let TouchGesture::DoubleTap {
+                max_interval,
+                max_movement,
+                region,
+            } = double_tap
+            else {
+                continue;
+            };
+            in_any_region.push(region);
+
+            let taps: Vec<Vec2> = history
+                .taps()
+                .iter()
+                .copied()
+                .filter(|&position| {
+                    touched_within_region(position, region.map(TouchRegion), Some(history))
+                })
+                .collect();
+            let first_tap = self.first_taps.remove(&double_tap).filter(|first_tap| {
+                let pending = now.saturating_sub(first_tap.time) <= max_interval;
+                if !pending {
+                    lone_taps.push(first_tap.position);
+                }
+                pending
+            });
+
+            match first_tap {
+                Some(first_tap)
+                    if taps
+                        .iter()
+                        .any(|position| position.distance(first_tap.position) <= max_movement) =>
+                {
+                    self.performed.push(double_tap);
+                }
+                _ => match (first_tap, taps.last()) {
+                    // A tap too far from the first tap starts a new double tap instead
+                    (first_tap, Some(&position)) => {
+                        lone_taps.extend(first_tap.map(|first_tap| first_tap.position));
+                        self.first_taps.insert(
+                            double_tap,
+                            FirstTap {
+                                position,
+                                time: now,
+                            },
+                        );
+                    }
+                    (Some(first_tap), None) => {
+                        self.first_taps.insert(double_tap, first_tap);
+                    }
+                    (None, None) => (),
+                },
+            }
+        }
+
+        self.taps = match arbitration {
+            TapArbitration::PressBoth => history.taps().to_vec(),
+            TapArbitration::DelayTaps => history
+                .taps()
+                .iter()
+                .copied()
+                .filter(|&position| {
+                    !in_any_region.iter().any(|region| {
+                        touched_within_region(position, region.map(TouchRegion), Some(history))
+                    })
+                })
+                .chain(lone_taps)
+                .collect(),
+        };
+    }
+
+    /// Is the `double_tap` performed on this frame?
+    #[must_use]
+    pub fn performed(&self, double_tap: TouchGesture) -> bool {
+        self.performed.contains(&double_tap)
+    }
+
+    /// Where the fingers whose [`TouchGesture::Tap`]s are pressed on this frame touched the screen, in logical pixels
+    ///
+    /// Under [`TapArbitration::DelayTaps`], this includes the first taps of the double taps that were never followed by a second tap.
+    #[must_use]
+    pub fn taps(&self) -> &[Vec2] {
+        &self.taps
+    }
+}
+
+/// When and where the first tap of a [`TouchGesture::DoubleTap`] was made
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FirstTap {
+    /// Where the finger touched the screen, in logical pixels
+    position: Vec2,
+    /// The time at which the tap was performed
+    time: Duration,
+}
+
+/// A virtual joystick on the touch screen, such as for twin-stick controls on mobile
+///
+/// A finger that touches the screen within the `region` anchors the joystick where it touched it,
+/// and moving that finger away from its anchor pushes the joystick, up to its full extent at `radius` logical pixels away.
+/// The joystick returns to rest once the anchoring finger is lifted.
+/// Other fingers don't affect the joystick: fingers that touched the screen elsewhere are ignored,
+/// and so are fingers that touched the `region` while another finger that had touched it was still on the screen.
+///
+/// Like a [`DualAxis`], the joystick is pressed while it is pushed outside of its `deadzone`,
+/// and its position is read with [`ActionState::axis_pair`](crate::action_state::ActionState::axis_pair).
+/// Up is positive on the y axis, like on gamepad sticks.
+///
+/// # Example
+/// ```rust
+/// use bevy::math::Rect;
+/// use bevy::prelude::Reflect;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::touch::TouchJoystick;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+/// enum Action {
+///     Move,
+///     Aim,
+/// }
+///
+/// // Move by touching the left half of the screen, and aim by touching its right half
+/// let input_map = InputMap::new([
+///     (Action::Move, TouchJoystick::new(Rect::new(0.0, 0.0, 0.5, 1.0), 75.0)),
+///     (Action::Aim, TouchJoystick::new(Rect::new(0.5, 0.0, 1.0, 1.0), 75.0)),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct TouchJoystick {
+    /// The part of the screen where a finger can anchor the joystick, in normalized coordinates
+    ///
+    /// `(0, 0)` is the top left corner of the window and `(1, 1)` its bottom right corner.
+    pub region: Rect,
+    /// How far the anchoring finger must move to fully push the joystick, in logical pixels
+    pub radius: f32,
+    /// The shape of the deadzone
+    pub deadzone: DeadZoneShape,
+}
+
+impl TouchJoystick {
+    /// Creates a [`TouchJoystick`] anchored within the `region`, fully pushed `radius` logical pixels away from its anchor
+    ///
+    /// The deadzone is the [`DualAxis::DEFAULT_DEADZONE_SHAPE`].
+    #[must_use]
+    pub fn new(region: Rect, radius: f32) -> TouchJoystick {
+        TouchJoystick {
+            region,
+            radius,
+            deadzone: DualAxis::DEFAULT_DEADZONE_SHAPE,
+        }
+    }
+
+    /// Returns this [`TouchJoystick`] with the `deadzone` set to the specified value
+    #[must_use]
+    pub fn with_deadzone(mut self, deadzone: DeadZoneShape) -> TouchJoystick {
+        self.deadzone = deadzone;
+        self
+    }
+
+    /// The finger anchoring this joystick, if any
+    ///
+    /// This is the finger on the screen that touched it within the `region` while no other finger that had touched the `region` was on the screen.
+    #[must_use]
+    pub fn anchoring_touch<'a>(
+        &self,
+        touches: &'a Touches,
+        history: &TouchHistory,
+    ) -> Option<&'a Touch> {
+        let in_region = |position: Vec2| {
+            history
+                .normalize(position)
+                .is_some_and(|position| self.region.contains(position))
+        };
+
+        touches
+            .iter()
+            .filter(|touch| {
+                in_region(touch.start_position())
+                    && !history
+                        .earlier_start_positions(touch.id())
+                        .iter()
+                        .any(|&position| in_region(position))
+            })
+            // Only fingers that touched the region on the same frame can compete
+            .min_by_key(|touch| touch.id())
+    }
+
+    /// The position of this joystick, or [`None`] if it isn't anchored or is within its deadzone
+    #[must_use]
+    pub fn axis_pair(&self, touches: &Touches, history: &TouchHistory) -> Option<DualAxisData> {
+        let touch = self.anchoring_touch(touches, history)?;
+        let offset = (touch.position() - touch.start_position()) / self.radius;
+        // Screen coordinates point down
+        let offset = Vec2::new(offset.x, -offset.y).clamp_length_max(1.0);
+
+        self.deadzone.deadzone_input_value(offset.x, offset.y)
+    }
+}
+
+impl PartialEq for TouchJoystick {
+    fn eq(&self, other: &Self) -> bool {
+        TouchRegion(self.region) == TouchRegion(other.region)
+            && FloatOrd(self.radius) == FloatOrd(other.radius)
+            && self.deadzone == other.deadzone
+    }
+}
+impl Eq for TouchJoystick {}
+impl std::hash::Hash for TouchJoystick {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        TouchRegion(self.region).hash(state);
+        FloatOrd(self.radius).hash(state);
+        self.deadzone.hash(state);
+    }
+}
+
+/// A part of the screen, in normalized coordinates where `(0, 0)` is the top left corner of the window and `(1, 1)` its bottom right corner
+///
+/// Created by [`TouchGesture::within_rect`].
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct TouchRegion(pub Rect);
+
+impl TouchRegion {
+    /// Is the normalized `position` within this region?
+    #[must_use]
+    pub fn contains(&self, position: Vec2) -> bool {
+        self.0.contains(position)
+    }
+}
+
+impl PartialEq for TouchRegion {
+    fn eq(&self, other: &Self) -> bool {
+        let corners = |region: &TouchRegion| {
+            [region.0.min, region.0.max].map(|corner| [FloatOrd(corner.x), FloatOrd(corner.y)])
+        };
+        corners(self) == corners(other)
+    }
+}
+impl Eq for TouchRegion {}
+impl std::hash::Hash for TouchRegion {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for corner in [self.0.min, self.0.max] {
+            FloatOrd(corner.x).hash(state);
+            FloatOrd(corner.y).hash(state);
+        }
+    }
+}
+
+/// When and where each finger on the screen touched it, and the size of the window it touched, used to recognize [`TouchGesture`]s and anchor [`TouchJoystick`]s
+///
+/// Fingers that are on the screen together are grouped, to tell [`TouchGesture::MultiTap`]s apart from single-finger [`TouchGesture::Tap`]s.
+///
+/// This resource is added by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) on the client,
+/// and is updated by [`track_touches`](crate::systems::track_touches) before the [`ActionState`](crate::action_state::ActionState)s are updated.
+/// Durations are measured in real time, so gestures can still be made while virtual time is paused.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct TouchHistory {
+    /// The fingers on the screen, and those lifted on this frame, by touch id
+    touches: HashMap<u64, TouchRecord>,
+    /// The time of the last update
+    now: Duration,
+    /// The logical size of the primary window, if any
+    window_size: Option<Vec2>,
+    /// The fingers that have been on the screen together since it was last untouched, which may be tapping it together
+    group: Option<TouchGroup>,
+    /// Where the fingers whose taps are performed on this frame touched the screen, in logical pixels
+    taps: Vec<Vec2>,
+    /// The fingers that tapped the screen together, if the last of them was lifted on this frame
+    multi_tap: Option<MultiTapRecord>,
+}
+
+impl TouchHistory {
+    /// Records the fingers that touched the screen, forgets those that were lifted before this frame, and stores the size of the window
+    ///
+    /// `now` is the time elapsed since the app started.
+    pub fn update(&mut self, touches: &Touches, now: Duration, window_size: Option<Vec2>) {
+        self.now = now;
+        self.window_size = window_size;
+        self.touches
+            .retain(|&id, _| touches.get_pressed(id).is_some() || touches.just_released(id));
+
+        let earlier_start_positions: Vec<Vec2> = self
+            .touches
+            .values()
+            .map(|record| record.start_position)
+            .collect();
+        for touch in touches.iter().chain(touches.iter_just_released()) {
+            let record = self
+                .touches
+                .entry(touch.id())
+                .or_insert_with(|| TouchRecord {
+                    started: now,
+                    start_position: touch.start_position(),
+                    earlier_start_positions: earlier_start_positions.clone(),
+                    previous_position: touch.position(),
+                    position: touch.position(),
+                });
+            record.previous_position = record.position;
+            record.position = touch.position();
+        }
+
+        self.update_taps(touches, now);
+    }
+
+    /// Groups the fingers that are on the screen together, and decides which of their taps are performed on this frame
+    ///
+    /// Taps are withheld while the fingers of their group may still all tap the screen together,
+    /// and are dropped if they do.
+    fn update_taps(&mut self, touches: &Touches, now: Duration) {
+        self.taps.clear();
+        self.multi_tap = None;
+
+        // A group ends once all of its fingers were lifted
+        if self.group.as_ref().is_some_and(TouchGroup::ended) {
+            self.group = None;
+        }
+
+        for touch in touches.iter().chain(touches.iter_just_released()) {
+            let group = self.group.get_or_insert_with(|| TouchGroup {
+                started: now,
+                fingers: HashMap::new(),
+                resolved: false,
+            });
+            let finger = group
+                .fingers
+                .entry(touch.id())
+                .or_insert_with(|| GroupedTouch {
+                    start_position: touch.start_position(),
+                    distance: 0.0,
+                    released: false,
+                    tapped: false,
+                });
+            finger.distance = touch.distance().length();
+
+            if touches.just_released(touch.id()) {
+                let duration = self
+                    .touches
+                    .get(&touch.id())
+                    .map_or(Duration::ZERO, |record| now.saturating_sub(record.started));
+                finger.released = true;
+                finger.tapped = duration <= TouchGesture::TAP_MAX_DURATION
+                    && finger.distance <= TouchGesture::MAX_MOVEMENT;
+            }
+        }
+
+        let Some(group) = &mut self.group else {
+            return;
+        };
+        let tapped_fingers = |group: &TouchGroup, only_just_released: bool| -> Vec<Vec2> {
+            group
+                .fingers
+                .iter()
+                .filter(|(&id, finger)| {
+                    finger.tapped && (!only_just_released || touches.just_released(id))
+                })
+                .map(|(_, finger)| finger.start_position)
+                .collect()
+        };
+
+        let ended = group.ended();
+        let all_tapped = group.fingers.values().all(|finger| finger.tapped);
+        if ended && all_tapped && group.fingers.len() >= 2 {
+            self.multi_tap = Some(MultiTapRecord {
+                start_positions: tapped_fingers(group, false),
+                duration: now.saturating_sub(group.started),
+            });
+        }
+
+        if group.resolved {
+            self.taps = tapped_fingers(group, true);
+            return;
+        }
+
+        // Held fingers that didn't move much may still tap the screen
+        let may_tap_together = now.saturating_sub(group.started) <= TouchGesture::TAP_MAX_DURATION
+            && group.fingers.values().all(|finger| {
+                finger.tapped || (!finger.released && finger.distance <= TouchGesture::MAX_MOVEMENT)
+            });
+        if ended && may_tap_together && group.fingers.len() >= 2 {
+            // The fingers tapped the screen together, so none of them tapped it on its own
+        } else if ended || !may_tap_together {
+            group.resolved = true;
+            self.taps = tapped_fingers(group, false);
+        }
+    }
+
+    /// Where the fingers whose [`TouchGesture::Tap`]s are performed on this frame touched the screen, in logical pixels
+    ///
+    /// This includes the taps that were withheld until it was known that their fingers didn't all tap the screen together.
+    #[must_use]
+    pub fn taps(&self) -> &[Vec2] {
+        &self.taps
+    }
+
+    /// The fingers that tapped the screen together, if the last of them was lifted on this frame
+    #[must_use]
+    pub fn multi_tap(&self) -> Option<&MultiTapRecord> {
+        self.multi_tap.as_ref()
+    }
+
+    /// How long the finger with the given `id` has touched the screen, or zero if it isn't known
+    #[must_use]
+    pub fn duration(&self, id: u64) -> Duration {
+        self.touches.get(&id).map_or(Duration::ZERO, |record| {
+            self.now.saturating_sub(record.started)
+        })
+    }
+
+    /// Where the other fingers that were already on the screen when the finger with the given `id` touched it had touched it, in logical pixels
+    #[must_use]
+    pub fn earlier_start_positions(&self, id: u64) -> &[Vec2] {
+        self.touches
+            .get(&id)
+            .map_or(&[], |record| &record.earlier_start_positions)
+    }
+
+    /// Where the finger with the given `id` was on the previous frame, in logical pixels, or [`None`] if it isn't known
+    ///
+    /// Unlike [`Touch::previous_position`], this is the same as its current position when it didn't move on this frame.
+    #[must_use]
+    pub fn previous_position(&self, id: u64) -> Option<Vec2> {
+        self.touches.get(&id).map(|record| record.previous_position)
+    }
+
+    /// The time of the last update, elapsed since the app started
+    #[must_use]
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// The logical size of the primary window, or [`None`] if there isn't any
+    #[must_use]
+    pub fn window_size(&self) -> Option<Vec2> {
+        self.window_size
+    }
+
+    /// Converts a `position` in logical pixels to normalized coordinates, where `(0, 0)` is the top left corner of the window and `(1, 1)` its bottom right corner
+    ///
+    /// Returns [`None`] if the size of the window isn't known.
+    #[must_use]
+    pub fn normalize(&self, position: Vec2) -> Option<Vec2> {
+        self.window_size
+            .filter(|size| size.x > 0.0 && size.y > 0.0)
+            .map(|size| position / size)
+    }
+}
+
+/// Fingers that tapped the screen together, recorded by the [`TouchHistory`] on the frame the last of them was lifted
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiTapRecord {
+    /// Where each of the fingers touched the screen, in logical pixels
+    pub start_positions: Vec<Vec2>,
+    /// The time from the first of the fingers touching the screen to the last of them being lifted
+    pub duration: Duration,
+}
+
+impl MultiTapRecord {
+    /// The largest distance between the positions where two of the fingers touched the screen, in logical pixels
+    #[must_use]
+    pub fn spread(&self) -> f32 {
+        self.start_positions
+            .iter()
+            .flat_map(|a| self.start_positions.iter().map(move |b| a.distance(*b)))
+            .fold(0.0, f32::max)
+    }
+}
+
+/// Fingers that have been on the screen together since it was last untouched
+#[derive(Debug, Clone, PartialEq)]
+struct TouchGroup {
+    /// The time at which the first of the fingers touched the screen
+    started: Duration,
+    /// The fingers of the group, by touch id
+    fingers: HashMap<u64, GroupedTouch>,
+    /// Whether it is known that the fingers don't all tap the screen together, so that their taps are no longer withheld
+    resolved: bool,
+}
+
+impl TouchGroup {
+    /// Were all of the fingers lifted?
+    fn ended(&self) -> bool {
+        self.fingers.values().all(|finger| finger.released)
+    }
+}
+
+/// A finger of a [`TouchGroup`]
+#[derive(Debug, Clone, PartialEq)]
+struct GroupedTouch {
+    /// Where the finger touched the screen, in logical pixels
+    start_position: Vec2,
+    /// How far the finger is from where it touched the screen, in logical pixels
+    distance: f32,
+    /// Whether the finger was lifted
+    released: bool,
+    /// Whether the finger was lifted as a [`TouchGesture::Tap`]
+    tapped: bool,
+}
+
+/// When and where a finger touched the screen
+#[derive(Debug, Clone, PartialEq)]
+struct TouchRecord {
+    /// The time at which the finger touched the screen
+    started: Duration,
+    /// Where the finger touched the screen, in logical pixels
+    start_position: Vec2,
+    /// Where the other fingers that were already on the screen had touched it, in logical pixels
+    earlier_start_positions: Vec<Vec2>,
+    /// Where the finger was on the previous frame, in logical pixels
+    previous_position: Vec2,
+    /// Where the finger was on the last update, in logical pixels
+    position: Vec2,
+}
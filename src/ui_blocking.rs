@@ -0,0 +1,79 @@
+//! Stops clicks on the UI from also pressing actions bound to mouse buttons
+//!
+//! Add the [`UiBlockingPlugin`], and mouse buttons are ignored by every [`InputMap`](crate::input_map::InputMap)
+//! while the pointer is over a UI node with an [`Interaction`], or a node marked with [`BlocksGameInput`].
+//! Actions that should still be pressed by clicks on the UI can opt out using
+//! [`InputMap::set_clicks_through_ui`](crate::input_map::InputMap::set_clicks_through_ui).
+//!
+//! This is less aggressive than the `block_ui_interactions` feature, which also ignores the mouse wheel,
+//! and ignores mouse input for every action while any [`Interaction`] is not [`Interaction::None`].
+
+use crate::plugin::InputManagerSystem;
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::ecs::prelude::*;
+use bevy::render::view::ViewVisibility;
+use bevy::ui::{Interaction, RelativeCursorPosition, UiSystem};
+
+/// Keeps the [`PointerOverUi`] resource up to date, so that mouse buttons are ignored while the pointer is over the UI
+///
+/// See the [module documentation](self) for more details.
+pub struct UiBlockingPlugin;
+
+impl Plugin for UiBlockingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PointerOverUi>().add_systems(
+            PreUpdate,
+            (
+                track_ui_blockers.before(UiSystem::Focus),
+                update_pointer_over_ui
+                    .after(UiSystem::Focus)
+                    .before(InputManagerSystem::Update),
+            ),
+        );
+    }
+}
+
+/// Marks a UI node that stops mouse buttons from pressing actions while the pointer is over it, like nodes with an [`Interaction`]
+///
+/// This is useful for panels and other nodes that are not interactive themselves.
+/// A [`RelativeCursorPosition`] is added to these nodes by the [`UiBlockingPlugin`], to track the pointer.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlocksGameInput;
+
+/// Is the pointer over a UI node that blocks mouse buttons?
+///
+/// This resource is updated by the [`UiBlockingPlugin`] before the [`ActionState`](crate::action_state::ActionState)s are updated.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PointerOverUi(pub bool);
+
+/// Adds a [`RelativeCursorPosition`] to every node marked with [`BlocksGameInput`] that does not have one yet
+pub fn track_ui_blockers(
+    mut commands: Commands,
+    query: Query<Entity, (With<BlocksGameInput>, Without<RelativeCursorPosition>)>,
+) {
+    for entity in query.iter() {
+        commands
+            .entity(entity)
+            .insert(RelativeCursorPosition::default());
+    }
+}
+
+/// Updates the [`PointerOverUi`] resource from the [`Interaction`]s and the nodes marked with [`BlocksGameInput`]
+///
+/// Hidden nodes never block mouse buttons.
+pub fn update_pointer_over_ui(
+    interactions: Query<&Interaction>,
+    blockers: Query<(&RelativeCursorPosition, Option<&ViewVisibility>), With<BlocksGameInput>>,
+    mut pointer_over_ui: ResMut<PointerOverUi>,
+) {
+    let over_interaction = interactions
+        .iter()
+        .any(|&interaction| interaction != Interaction::None);
+    let over_blocker = blockers.iter().any(|(cursor_position, view_visibility)| {
+        let hidden = matches!(view_visibility, Some(view_visibility) if !view_visibility.get());
+        !hidden && cursor_position.mouse_over()
+    });
+
+    pointer_over_ui.set_if_neq(PointerOverUi(over_interaction || over_blocker));
+}
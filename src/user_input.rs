@@ -6,11 +6,17 @@ use bevy::reflect::Reflect;
 use bevy::utils::HashSet;
 use serde::{Deserialize, Serialize};
 
-use crate::axislike::VirtualAxis;
+use crate::axislike::{EitherDualAxis, TripleAxis, VirtualAxis};
+use crate::cursor_world::CursorRelative;
+#[cfg(feature = "gamepad_extras")]
+use crate::gamepad_extras::GamepadExtra;
+use crate::mouse_gestures::{DragAxis, MouseClick, MouseDrag};
 use crate::scan_codes::QwertyScanCode;
+use crate::screen_edge::ScreenEdge;
+use crate::touch::{TouchGesture, TouchJoystick, TouchRegion};
 use crate::{
-    axislike::{AxisType, DualAxis, SingleAxis, VirtualDPad},
-    buttonlike::{MouseMotionDirection, MouseWheelDirection},
+    axislike::{AxisType, DualAxis, MouseMotionAxisType, SingleAxis, VirtualDPad},
+    buttonlike::{AnalogButton, MouseMotionDirection, MouseWheelDirection},
 };
 
 /// Some combination of user input, which may cross input-mode boundaries.
@@ -33,6 +39,8 @@ pub enum UserInput {
     VirtualDPad(VirtualDPad),
     /// A virtual axis that you can get a [`SingleAxis`] from
     VirtualAxis(VirtualAxis),
+    /// Two [`DualAxis`], only one of which is read at a time
+    EitherDualAxis(EitherDualAxis),
 }
 
 impl UserInput {
@@ -70,12 +78,14 @@ impl UserInput {
     /// - A [`Single`][UserInput::Single] input returns 1
     /// - A [`Chord`][UserInput::Chord] returns the number of buttons in the chord
     /// - A [`VirtualDPad`][UserInput::VirtualDPad] returns 1
+    /// - An [`EitherDualAxis`][UserInput::EitherDualAxis] returns 1
     pub fn len(&self) -> usize {
         match self {
             UserInput::Single(_) => 1,
             UserInput::Chord(button_set) => button_set.len(),
             UserInput::VirtualDPad { .. } => 1,
             UserInput::VirtualAxis { .. } => 1,
+            UserInput::EitherDualAxis { .. } => 1,
         }
     }
 
@@ -119,6 +129,7 @@ impl UserInput {
                 down,
                 left,
                 right,
+                ..
             }) => {
                 let mut n_matching = 0;
                 for button in buttons.iter() {
@@ -143,6 +154,10 @@ impl UserInput {
 
                 n_matching
             }
+            UserInput::EitherDualAxis(EitherDualAxis { first, second, .. }) => [first, second]
+                .into_iter()
+                .filter(|dual_axis| buttons.contains(&InputKind::DualAxis(**dual_axis)))
+                .count(),
         }
     }
 
@@ -160,10 +175,44 @@ impl UserInput {
                         .axis_data
                         .push((dual_axis.y.axis_type, dual_axis.y.value));
                 }
+                InputKind::TripleAxis(triple_axis) => {
+                    for single_axis in [triple_axis.x, triple_axis.y, triple_axis.z] {
+                        raw_inputs
+                            .axis_data
+                            .push((single_axis.axis_type, single_axis.value));
+                    }
+                }
                 InputKind::SingleAxis(single_axis) => raw_inputs
                     .axis_data
                     .push((single_axis.axis_type, single_axis.value)),
                 InputKind::GamepadButton(button) => raw_inputs.gamepad_buttons.push(button),
+                InputKind::AnalogButton(button) => {
+                    raw_inputs.gamepad_buttons.push(button.button_type)
+                }
+                // The button depends on the kind of gamepad
+                #[cfg(feature = "gamepad_extras")]
+                InputKind::GamepadExtra(_) => (),
+                InputKind::Touch(gesture) | InputKind::TouchWithin(gesture, _) => {
+                    raw_inputs.touch_gestures.push(gesture)
+                }
+                InputKind::TouchJoystick(joystick) => raw_inputs.touch_joysticks.push(joystick),
+                InputKind::CursorRelative(cursor_relative) => {
+                    raw_inputs.cursor_relative.push(cursor_relative)
+                }
+                InputKind::ScreenEdge(screen_edge) => raw_inputs.screen_edges.push(screen_edge),
+                InputKind::MouseClick(MouseClick { button, .. })
+                | InputKind::MouseDrag(MouseDrag { button, .. }) => {
+                    raw_inputs.mouse_buttons.push(button)
+                }
+                InputKind::DragAxis(drag_axis) => {
+                    raw_inputs.mouse_buttons.push(drag_axis.button);
+                    raw_inputs
+                        .axis_data
+                        .push((AxisType::MouseMotion(MouseMotionAxisType::X), None));
+                    raw_inputs
+                        .axis_data
+                        .push((AxisType::MouseMotion(MouseMotionAxisType::Y), None));
+                }
                 InputKind::Keyboard(button) => raw_inputs.keycodes.push(button),
                 InputKind::KeyLocation(scan_code) => raw_inputs.scan_codes.push(scan_code),
                 InputKind::Modifier(modifier) => {
@@ -186,10 +235,47 @@ impl UserInput {
                                 .axis_data
                                 .push((dual_axis.y.axis_type, dual_axis.y.value));
                         }
+                        InputKind::TripleAxis(triple_axis) => {
+                            for single_axis in [triple_axis.x, triple_axis.y, triple_axis.z] {
+                                raw_inputs
+                                    .axis_data
+                                    .push((single_axis.axis_type, single_axis.value));
+                            }
+                        }
                         InputKind::SingleAxis(single_axis) => raw_inputs
                             .axis_data
                             .push((single_axis.axis_type, single_axis.value)),
                         InputKind::GamepadButton(button) => raw_inputs.gamepad_buttons.push(button),
+                        InputKind::AnalogButton(button) => {
+                            raw_inputs.gamepad_buttons.push(button.button_type)
+                        }
+                        #[cfg(feature = "gamepad_extras")]
+                        InputKind::GamepadExtra(_) => (),
+                        InputKind::Touch(gesture) | InputKind::TouchWithin(gesture, _) => {
+                            raw_inputs.touch_gestures.push(gesture)
+                        }
+                        InputKind::TouchJoystick(joystick) => {
+                            raw_inputs.touch_joysticks.push(joystick)
+                        }
+                        InputKind::CursorRelative(cursor_relative) => {
+                            raw_inputs.cursor_relative.push(cursor_relative)
+                        }
+                        InputKind::ScreenEdge(screen_edge) => {
+                            raw_inputs.screen_edges.push(screen_edge)
+                        }
+                        InputKind::MouseClick(MouseClick { button, .. })
+                        | InputKind::MouseDrag(MouseDrag { button, .. }) => {
+                            raw_inputs.mouse_buttons.push(button)
+                        }
+                        InputKind::DragAxis(drag_axis) => {
+                            raw_inputs.mouse_buttons.push(drag_axis.button);
+                            raw_inputs
+                                .axis_data
+                                .push((AxisType::MouseMotion(MouseMotionAxisType::X), None));
+                            raw_inputs
+                                .axis_data
+                                .push((AxisType::MouseMotion(MouseMotionAxisType::Y), None));
+                        }
                         InputKind::Keyboard(button) => raw_inputs.keycodes.push(button),
                         InputKind::KeyLocation(scan_code) => raw_inputs.scan_codes.push(scan_code),
                         InputKind::Modifier(modifier) => {
@@ -208,6 +294,7 @@ impl UserInput {
                 down,
                 left,
                 right,
+                ..
             }) => {
                 for button in [up, down, left, right] {
                     match *button {
@@ -219,10 +306,47 @@ impl UserInput {
                                 .axis_data
                                 .push((dual_axis.y.axis_type, dual_axis.y.value));
                         }
+                        InputKind::TripleAxis(triple_axis) => {
+                            for single_axis in [triple_axis.x, triple_axis.y, triple_axis.z] {
+                                raw_inputs
+                                    .axis_data
+                                    .push((single_axis.axis_type, single_axis.value));
+                            }
+                        }
                         InputKind::SingleAxis(single_axis) => raw_inputs
                             .axis_data
                             .push((single_axis.axis_type, single_axis.value)),
                         InputKind::GamepadButton(button) => raw_inputs.gamepad_buttons.push(button),
+                        InputKind::AnalogButton(button) => {
+                            raw_inputs.gamepad_buttons.push(button.button_type)
+                        }
+                        #[cfg(feature = "gamepad_extras")]
+                        InputKind::GamepadExtra(_) => (),
+                        InputKind::Touch(gesture) | InputKind::TouchWithin(gesture, _) => {
+                            raw_inputs.touch_gestures.push(gesture)
+                        }
+                        InputKind::TouchJoystick(joystick) => {
+                            raw_inputs.touch_joysticks.push(joystick)
+                        }
+                        InputKind::CursorRelative(cursor_relative) => {
+                            raw_inputs.cursor_relative.push(cursor_relative)
+                        }
+                        InputKind::ScreenEdge(screen_edge) => {
+                            raw_inputs.screen_edges.push(screen_edge)
+                        }
+                        InputKind::MouseClick(MouseClick { button, .. })
+                        | InputKind::MouseDrag(MouseDrag { button, .. }) => {
+                            raw_inputs.mouse_buttons.push(button)
+                        }
+                        InputKind::DragAxis(drag_axis) => {
+                            raw_inputs.mouse_buttons.push(drag_axis.button);
+                            raw_inputs
+                                .axis_data
+                                .push((AxisType::MouseMotion(MouseMotionAxisType::X), None));
+                            raw_inputs
+                                .axis_data
+                                .push((AxisType::MouseMotion(MouseMotionAxisType::Y), None));
+                        }
                         InputKind::Keyboard(button) => raw_inputs.keycodes.push(button),
                         InputKind::KeyLocation(scan_code) => raw_inputs.scan_codes.push(scan_code),
                         InputKind::Modifier(modifier) => {
@@ -248,10 +372,47 @@ impl UserInput {
                                 .axis_data
                                 .push((dual_axis.y.axis_type, dual_axis.y.value));
                         }
+                        InputKind::TripleAxis(triple_axis) => {
+                            for single_axis in [triple_axis.x, triple_axis.y, triple_axis.z] {
+                                raw_inputs
+                                    .axis_data
+                                    .push((single_axis.axis_type, single_axis.value));
+                            }
+                        }
                         InputKind::SingleAxis(single_axis) => raw_inputs
                             .axis_data
                             .push((single_axis.axis_type, single_axis.value)),
                         InputKind::GamepadButton(button) => raw_inputs.gamepad_buttons.push(button),
+                        InputKind::AnalogButton(button) => {
+                            raw_inputs.gamepad_buttons.push(button.button_type)
+                        }
+                        #[cfg(feature = "gamepad_extras")]
+                        InputKind::GamepadExtra(_) => (),
+                        InputKind::Touch(gesture) | InputKind::TouchWithin(gesture, _) => {
+                            raw_inputs.touch_gestures.push(gesture)
+                        }
+                        InputKind::TouchJoystick(joystick) => {
+                            raw_inputs.touch_joysticks.push(joystick)
+                        }
+                        InputKind::CursorRelative(cursor_relative) => {
+                            raw_inputs.cursor_relative.push(cursor_relative)
+                        }
+                        InputKind::ScreenEdge(screen_edge) => {
+                            raw_inputs.screen_edges.push(screen_edge)
+                        }
+                        InputKind::MouseClick(MouseClick { button, .. })
+                        | InputKind::MouseDrag(MouseDrag { button, .. }) => {
+                            raw_inputs.mouse_buttons.push(button)
+                        }
+                        InputKind::DragAxis(drag_axis) => {
+                            raw_inputs.mouse_buttons.push(drag_axis.button);
+                            raw_inputs
+                                .axis_data
+                                .push((AxisType::MouseMotion(MouseMotionAxisType::X), None));
+                            raw_inputs
+                                .axis_data
+                                .push((AxisType::MouseMotion(MouseMotionAxisType::Y), None));
+                        }
                         InputKind::Keyboard(button) => raw_inputs.keycodes.push(button),
                         InputKind::KeyLocation(scan_code) => raw_inputs.scan_codes.push(scan_code),
                         InputKind::Modifier(modifier) => {
@@ -265,6 +426,16 @@ impl UserInput {
                     }
                 }
             }
+            UserInput::EitherDualAxis(EitherDualAxis { first, second, .. }) => {
+                for dual_axis in [first, second] {
+                    raw_inputs
+                        .axis_data
+                        .push((dual_axis.x.axis_type, dual_axis.x.value));
+                    raw_inputs
+                        .axis_data
+                        .push((dual_axis.y.axis_type, dual_axis.y.value));
+                }
+            }
         };
 
         raw_inputs
@@ -283,6 +454,61 @@ impl From<DualAxis> for UserInput {
     }
 }
 
+impl From<TripleAxis> for UserInput {
+    fn from(input: TripleAxis) -> Self {
+        UserInput::Single(InputKind::TripleAxis(input))
+    }
+}
+
+#[cfg(feature = "gamepad_extras")]
+impl From<GamepadExtra> for UserInput {
+    fn from(input: GamepadExtra) -> Self {
+        UserInput::Single(InputKind::GamepadExtra(input))
+    }
+}
+
+impl From<TouchGesture> for UserInput {
+    fn from(input: TouchGesture) -> Self {
+        UserInput::Single(InputKind::Touch(input))
+    }
+}
+
+impl From<MouseClick> for UserInput {
+    fn from(input: MouseClick) -> Self {
+        UserInput::Single(InputKind::MouseClick(input))
+    }
+}
+
+impl From<CursorRelative> for UserInput {
+    fn from(input: CursorRelative) -> Self {
+        UserInput::Single(InputKind::CursorRelative(input))
+    }
+}
+
+impl From<ScreenEdge> for UserInput {
+    fn from(input: ScreenEdge) -> Self {
+        UserInput::Single(InputKind::ScreenEdge(input))
+    }
+}
+
+impl From<DragAxis> for UserInput {
+    fn from(input: DragAxis) -> Self {
+        UserInput::Single(InputKind::DragAxis(input))
+    }
+}
+
+impl From<MouseDrag> for UserInput {
+    fn from(input: MouseDrag) -> Self {
+        UserInput::Single(InputKind::MouseDrag(input))
+    }
+}
+
+impl From<TouchJoystick> for UserInput {
+    fn from(input: TouchJoystick) -> Self {
+        UserInput::Single(InputKind::TouchJoystick(input))
+    }
+}
+
 impl From<SingleAxis> for UserInput {
     fn from(input: SingleAxis) -> Self {
         UserInput::Single(InputKind::SingleAxis(input))
@@ -301,12 +527,24 @@ impl From<VirtualAxis> for UserInput {
     }
 }
 
+impl From<EitherDualAxis> for UserInput {
+    fn from(input: EitherDualAxis) -> Self {
+        UserInput::EitherDualAxis(input)
+    }
+}
+
 impl From<GamepadButtonType> for UserInput {
     fn from(input: GamepadButtonType) -> Self {
         UserInput::Single(InputKind::GamepadButton(input))
     }
 }
 
+impl From<AnalogButton> for UserInput {
+    fn from(input: AnalogButton) -> Self {
+        UserInput::Single(InputKind::AnalogButton(input))
+    }
+}
+
 impl From<KeyCode> for UserInput {
     fn from(input: KeyCode) -> Self {
         UserInput::Single(InputKind::Keyboard(input))
@@ -362,10 +600,20 @@ impl From<Modifier> for UserInput {
 pub enum InputKind {
     /// A button on a gamepad
     GamepadButton(GamepadButtonType),
+    /// A button on a gamepad with an analog value, such as a trigger, pressed according to its own thresholds
+    AnalogButton(AnalogButton),
     /// A single axis of continuous motion
     SingleAxis(SingleAxis),
     /// Two paired axes of continuous motion
     DualAxis(DualAxis),
+    /// Three axes of continuous motion, such as a gyroscope
+    TripleAxis(TripleAxis),
+    /// An extra button found on some gamepads, such as a touchpad or a back button
+    ///
+    /// It is read from the button that reports it on the [`GamepadKind`](crate::gamepad_kind::GamepadKind) of each gamepad,
+    /// and isn't pressed on gamepads that don't have it.
+    #[cfg(feature = "gamepad_extras")]
+    GamepadExtra(GamepadExtra),
     /// A logical key on the keyboard.
     ///
     /// The actual (physical) key that has to be pressed depends on the keyboard layout.
@@ -386,6 +634,24 @@ pub enum InputKind {
     MouseWheel(MouseWheelDirection),
     /// A discretized mouse movement
     MouseMotion(MouseMotionDirection),
+    /// A mouse button released without moving the cursor much
+    MouseClick(MouseClick),
+    /// A mouse button held while moving the cursor, read like a [`DualAxis`]
+    MouseDrag(MouseDrag),
+    /// The mouse motion on each frame, read like a [`DualAxis`] only while a mouse button is held
+    DragAxis(DragAxis),
+    /// The direction from the entity with the input map to the cursor, read like a [`DualAxis`]
+    CursorRelative(CursorRelative),
+    /// The edges of the window that the cursor is near, read like a [`DualAxis`]
+    ScreenEdge(ScreenEdge),
+    /// A gesture made on the touch screen, such as a tap or a pinch
+    Touch(TouchGesture),
+    /// A gesture made by fingers that touched the screen within a region of it
+    ///
+    /// Created using [`TouchGesture::within_rect`].
+    TouchWithin(TouchGesture, TouchRegion),
+    /// A virtual joystick on the touch screen, anchored by a finger within a region of it
+    TouchJoystick(TouchJoystick),
 }
 
 impl From<DualAxis> for InputKind {
@@ -394,12 +660,73 @@ impl From<DualAxis> for InputKind {
     }
 }
 
+impl From<TripleAxis> for InputKind {
+    fn from(input: TripleAxis) -> Self {
+        InputKind::TripleAxis(input)
+    }
+}
+
+#[cfg(feature = "gamepad_extras")]
+impl From<GamepadExtra> for InputKind {
+    fn from(input: GamepadExtra) -> Self {
+        InputKind::GamepadExtra(input)
+    }
+}
+
+impl From<TouchGesture> for InputKind {
+    fn from(input: TouchGesture) -> Self {
+        InputKind::Touch(input)
+    }
+}
+
+impl From<MouseClick> for InputKind {
+    fn from(input: MouseClick) -> Self {
+        InputKind::MouseClick(input)
+    }
+}
+
+impl From<CursorRelative> for InputKind {
+    fn from(input: CursorRelative) -> Self {
+        InputKind::CursorRelative(input)
+    }
+}
+
+impl From<ScreenEdge> for InputKind {
+    fn from(input: ScreenEdge) -> Self {
+        InputKind::ScreenEdge(input)
+    }
+}
+
+impl From<DragAxis> for InputKind {
+    fn from(input: DragAxis) -> Self {
+        InputKind::DragAxis(input)
+    }
+}
+
+impl From<MouseDrag> for InputKind {
+    fn from(input: MouseDrag) -> Self {
+        InputKind::MouseDrag(input)
+    }
+}
+
+impl From<TouchJoystick> for InputKind {
+    fn from(input: TouchJoystick) -> Self {
+        InputKind::TouchJoystick(input)
+    }
+}
+
 impl From<SingleAxis> for InputKind {
     fn from(input: SingleAxis) -> Self {
         InputKind::SingleAxis(input)
     }
 }
 
+impl From<AnalogButton> for InputKind {
+    fn from(input: AnalogButton) -> Self {
+        InputKind::AnalogButton(input)
+    }
+}
+
 impl From<GamepadButtonType> for InputKind {
     fn from(input: GamepadButtonType) -> Self {
         InputKind::GamepadButton(input)
@@ -496,6 +823,14 @@ pub struct RawInputs {
     pub mouse_motion: Vec<MouseMotionDirection>,
     /// Gamepad buttons, independent of a [`Gamepad`](bevy::input::gamepad::Gamepad)
     pub gamepad_buttons: Vec<GamepadButtonType>,
+    /// Touch gestures, independent of the region of the screen they are restricted to
+    pub touch_gestures: Vec<TouchGesture>,
+    /// Virtual joysticks on the touch screen
+    pub touch_joysticks: Vec<TouchJoystick>,
+    /// Directions from the entity to the cursor
+    pub cursor_relative: Vec<CursorRelative>,
+    /// Edges of the window near the cursor
+    pub screen_edges: Vec<ScreenEdge>,
     /// Axis-like data
     ///
     /// The `f32` stores the magnitude of the axis motion, and is only used for input mocking.
@@ -0,0 +1,215 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::accessibility::{
+    AccessibilitySettings, AccessibilityTransform, ActionDoubleTapEvent,
+};
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::timing::TickClock;
+use serde::{Deserialize, Serialize};
+use serde_test::{assert_tokens, Token};
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Action {
+    Aim,
+    Block,
+    Dash,
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default()
+            .with_accessibility(Action::Aim, AccessibilityTransform::HoldToToggle)
+            .with_accessibility(
+                Action::Block,
+                AccessibilityTransform::TapToHoldFor(Duration::from_millis(500)),
+            )
+            .with_accessibility(
+                Action::Dash,
+                AccessibilityTransform::SlowedDoubleTapWindow(Duration::from_millis(800)),
+            ),
+    ))
+    .insert_resource(TickClock::manual())
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (Action::Aim, KeyCode::ShiftLeft),
+        (Action::Block, KeyCode::B),
+        (Action::Dash, KeyCode::Space),
+    ]));
+    app.update();
+    app
+}
+
+fn advance_frame(app: &mut App) {
+    app.world.resource_mut::<TickClock>().advance(FRAME);
+    app.update();
+}
+
+fn tap(app: &mut App, key: KeyCode) {
+    app.send_input(key);
+    advance_frame(app);
+    app.release_input(key);
+    advance_frame(app);
+}
+
+fn action_state(app: &App) -> &ActionState<Action> {
+    app.world.resource::<ActionState<Action>>()
+}
+
+fn double_taps(app: &mut App) -> Vec<Action> {
+    app.world
+        .resource_mut::<Events<ActionDoubleTapEvent<Action>>>()
+        .drain()
+        .map(|event| event.action)
+        .collect()
+}
+
+#[test]
+fn hold_to_toggle() {
+    let mut app = create_app();
+    tap(&mut app, KeyCode::ShiftLeft);
+    for _ in 0..3 {
+        assert!(action_state(&app).pressed(&Action::Aim));
+        advance_frame(&mut app);
+    }
+
+    app.send_input(KeyCode::ShiftLeft);
+    advance_frame(&mut app);
+    assert!(action_state(&app).just_released(&Action::Aim));
+
+    // Releasing the key doesn't toggle the action back on
+    app.release_input(KeyCode::ShiftLeft);
+    advance_frame(&mut app);
+    assert!(action_state(&app).released(&Action::Aim));
+}
+
+#[test]
+fn consuming_toggles_off() {
+    let mut app = create_app();
+    tap(&mut app, KeyCode::ShiftLeft);
+    app.world
+        .resource_mut::<ActionState<Action>>()
+        .consume(&Action::Aim);
+
+    advance_frame(&mut app);
+    advance_frame(&mut app);
+    assert!(action_state(&app).released(&Action::Aim));
+    assert!(!action_state(&app).consumed(&Action::Aim));
+
+    // The next press toggles the action on, rather than off
+    app.send_input(KeyCode::ShiftLeft);
+    advance_frame(&mut app);
+    assert!(action_state(&app).just_pressed(&Action::Aim));
+}
+
+#[test]
+fn tap_to_hold() {
+    let mut app = create_app();
+    app.send_input(KeyCode::B);
+    advance_frame(&mut app);
+    assert!(action_state(&app).just_pressed(&Action::Block));
+    app.release_input(KeyCode::B);
+
+    // The tap holds the action for 500 ms, from the tick of the press
+    for _ in 0..4 {
+        advance_frame(&mut app);
+        assert!(action_state(&app).pressed(&Action::Block));
+    }
+    advance_frame(&mut app);
+    assert!(action_state(&app).just_released(&Action::Block));
+
+    // Holding the key for longer keeps the action pressed for as long as it's held
+    app.send_input(KeyCode::B);
+    for _ in 0..8 {
+        advance_frame(&mut app);
+        assert!(action_state(&app).pressed(&Action::Block));
+    }
+    app.release_input(KeyCode::B);
+    advance_frame(&mut app);
+    assert!(action_state(&app).just_released(&Action::Block));
+}
+
+#[test]
+fn consuming_cancels_tap_to_hold() {
+    let mut app = create_app();
+    tap(&mut app, KeyCode::B);
+    assert!(action_state(&app).pressed(&Action::Block));
+    app.world
+        .resource_mut::<ActionState<Action>>()
+        .consume(&Action::Block);
+
+    advance_frame(&mut app);
+    assert!(action_state(&app).released(&Action::Block));
+    assert!(!action_state(&app).consumed(&Action::Block));
+
+    tap(&mut app, KeyCode::B);
+    assert!(action_state(&app).pressed(&Action::Block));
+}
+
+#[test]
+fn slowed_double_tap_window() {
+    let mut app = create_app();
+
+    // Taps 400 ms apart are slow, but still within the window
+    tap(&mut app, KeyCode::Space);
+    advance_frame(&mut app);
+    advance_frame(&mut app);
+    assert!(double_taps(&mut app).is_empty());
+    tap(&mut app, KeyCode::Space);
+    assert_eq!(double_taps(&mut app), [Action::Dash]);
+
+    // The action itself is pressed and released as usual
+    assert!(action_state(&app).released(&Action::Dash));
+
+    // Taps that are too far apart are not double taps
+    tap(&mut app, KeyCode::Space);
+    for _ in 0..8 {
+        advance_frame(&mut app);
+    }
+    tap(&mut app, KeyCode::Space);
+    assert!(double_taps(&mut app).is_empty());
+}
+
+#[test]
+fn settings_serialize_separately() {
+    let mut settings = AccessibilitySettings::default();
+    settings.insert(Action::Aim, AccessibilityTransform::HoldToToggle);
+
+    assert_tokens(
+        &settings,
+        &[
+            Token::Struct {
+                name: "AccessibilitySettings",
+                len: 1,
+            },
+            Token::Str("transforms"),
+            Token::Map { len: Some(1) },
+            Token::UnitVariant {
+                name: "Action",
+                variant: "Aim",
+            },
+            Token::UnitVariant {
+                name: "AccessibilityTransform",
+                variant: "HoldToToggle",
+            },
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    // The input map is left untouched
+    let app = create_app();
+    assert_eq!(
+        app.world.resource::<InputMap<Action>>(),
+        &InputMap::new([
+            (Action::Aim, KeyCode::ShiftLeft),
+            (Action::Block, KeyCode::B),
+            (Action::Dash, KeyCode::Space),
+        ])
+    );
+}
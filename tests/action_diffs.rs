@@ -1,6 +1,12 @@
 use bevy::{input::InputPlugin, prelude::*};
-use leafwing_input_manager::action_diff::{ActionDiff, ActionDiffEvent};
-use leafwing_input_manager::{axislike::DualAxisData, prelude::*, systems::generate_action_diffs};
+use leafwing_input_manager::action_diff::{
+    ActionDiff, ActionDiffEvent, DiffOwner, DiffOwnerMap, DiffPolicy,
+};
+use leafwing_input_manager::systems::{
+    apply_action_diffs_by_id, generate_action_diffs, generate_action_diffs_by_id,
+};
+use leafwing_input_manager::{axislike::DualAxisData, prelude::*};
+use serde::{Deserialize, Serialize};
 
 #[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
 enum Action {
@@ -24,6 +30,10 @@ fn pay_da_bills(
                 mutation(action_state);
             } else if counter.0 > 1 {
                 action_state.release(&Action::PayTheBills);
+                // Released inputs have no value, as they would when read from an input device
+                let action_data = action_state.action_data_mut(&Action::PayTheBills).unwrap();
+                action_data.value = 0.;
+                action_data.axis_pair = None;
             }
             counter.0 += 1;
         }
@@ -110,12 +120,20 @@ fn assert_action_diff_received(app: &mut App, action_diff_event: ActionDiffEvent
             assert!(action_state.value(&action) == 0.);
             assert!(action_state.axis_pair(&action).is_none());
         }
-        ActionDiff::ValueChanged { action, value } => {
-            assert!(action_state.pressed(&action));
+        ActionDiff::ValueChanged {
+            action,
+            value,
+            pressed,
+        } => {
+            assert_eq!(action_state.pressed(&action), pressed);
             assert!(action_state.value(&action) == value);
         }
-        ActionDiff::AxisPairChanged { action, axis_pair } => {
-            assert!(action_state.pressed(&action));
+        ActionDiff::AxisPairChanged {
+            action,
+            axis_pair,
+            pressed,
+        } => {
+            assert_eq!(action_state.pressed(&action), pressed);
             match action_state.axis_pair(&action) {
                 Some(axis_pair_data) => {
                     assert!(axis_pair_data.xy() == axis_pair);
@@ -217,9 +235,14 @@ fn generate_value_action_diffs() {
         assert_eq!(action_diff_event.owner, Some(entity));
         assert_eq!(action_diff_event.action_diffs.len(), 1);
         match action_diff_event.action_diffs.first().unwrap().clone() {
-            ActionDiff::ValueChanged { action, value } => {
+            ActionDiff::ValueChanged {
+                action,
+                value,
+                pressed,
+            } => {
                 assert_eq!(action, Action::PayTheBills);
                 assert_eq!(value, input_value);
+                assert!(pressed);
             }
             ActionDiff::Released { .. } => {
                 panic!("Expected a `ValueChanged` variant got a `Released` variant")
@@ -285,8 +308,13 @@ fn generate_axis_action_diffs() {
         assert_eq!(action_diff_event.owner, Some(entity));
         assert_eq!(action_diff_event.action_diffs.len(), 1);
         match action_diff_event.action_diffs.first().unwrap().clone() {
-            ActionDiff::AxisPairChanged { action, axis_pair } => {
+            ActionDiff::AxisPairChanged {
+                action,
+                axis_pair,
+                pressed,
+            } => {
                 assert_eq!(action, Action::PayTheBills);
+                assert!(pressed);
                 assert_eq!(axis_pair, input_axis_pair);
             }
             ActionDiff::Released { .. } => {
@@ -375,6 +403,7 @@ fn process_value_action_diff() {
         action_diffs: vec![ActionDiff::ValueChanged {
             action: Action::PayTheBills,
             value: 0.5,
+            pressed: true,
         }],
     };
     send_action_diff(&mut app, action_diff_event.clone());
@@ -410,6 +439,7 @@ fn process_axis_action_diff() {
         action_diffs: vec![ActionDiff::AxisPairChanged {
             action: Action::PayTheBills,
             axis_pair: Vec2 { x: 1., y: 0. },
+            pressed: true,
         }],
     };
     send_action_diff(&mut app, action_diff_event.clone());
@@ -430,3 +460,454 @@ fn process_axis_action_diff() {
 
     assert_action_diff_received(&mut app, action_diff_event);
 }
+
+#[derive(Resource, Default)]
+struct HoldTheBills(bool);
+
+fn hold_da_bills(hold: Res<HoldTheBills>, mut action_state_query: Query<&mut ActionState<Action>>) {
+    let mut action_state = action_state_query.single_mut();
+    if hold.0 {
+        action_state.press(&Action::PayTheBills);
+        action_state
+            .action_data_mut(&Action::PayTheBills)
+            .unwrap()
+            .value = 1.;
+    } else {
+        action_state.release(&Action::PayTheBills);
+        action_state
+            .action_data_mut(&Action::PayTheBills)
+            .unwrap()
+            .value = 0.;
+    }
+}
+
+fn set_pressed(app: &mut App, pressed: bool) {
+    app.world.resource_mut::<HoldTheBills>().0 = pressed;
+}
+
+fn assert_single_diff(app: &mut App, expected: ActionDiff<Action>) {
+    assert_action_diff_created(app, |action_diff_event| {
+        assert_eq!(action_diff_event.action_diffs, vec![expected.clone()]);
+    });
+}
+
+#[test]
+fn generate_keepalive_action_diffs() {
+    let mut app = create_app();
+    app.insert_resource(DiffPolicy::KeepaliveEvery(2))
+        .init_resource::<HoldTheBills>()
+        .add_systems(Update, hold_da_bills)
+        .add_systems(PostUpdate, generate_action_diffs::<Action>);
+    let pressed = ActionDiff::Pressed {
+        action: Action::PayTheBills,
+    };
+
+    set_pressed(&mut app, true);
+    app.update();
+    assert_single_diff(&mut app, pressed.clone());
+
+    // Keepalive
+    app.update();
+    assert_single_diff(&mut app, pressed.clone());
+
+    app.update();
+    assert_has_no_action_diffs(&mut app);
+
+    // Keepalive
+    app.update();
+    assert_single_diff(&mut app, pressed);
+
+    // Released actions are not kept alive
+    set_pressed(&mut app, false);
+    app.update();
+    assert_single_diff(
+        &mut app,
+        ActionDiff::Released {
+            action: Action::PayTheBills,
+        },
+    );
+
+    app.update();
+    assert_has_no_action_diffs(&mut app);
+
+    app.update();
+    assert_has_no_action_diffs(&mut app);
+}
+
+#[test]
+fn generate_snapshot_action_diffs() {
+    let mut app = create_app();
+    app.insert_resource(DiffPolicy::FullEvery(2))
+        .init_resource::<HoldTheBills>()
+        .add_systems(Update, hold_da_bills)
+        .add_systems(PostUpdate, generate_action_diffs::<Action>);
+    let pressed = ActionDiff::Pressed {
+        action: Action::PayTheBills,
+    };
+    let released = ActionDiff::Released {
+        action: Action::PayTheBills,
+    };
+
+    set_pressed(&mut app, true);
+    app.update();
+    assert_single_diff(&mut app, pressed.clone());
+
+    // Snapshot
+    app.update();
+    assert_single_diff(&mut app, pressed);
+
+    set_pressed(&mut app, false);
+    app.update();
+    assert_single_diff(&mut app, released.clone());
+
+    // Snapshots include released actions
+    app.update();
+    assert_single_diff(&mut app, released);
+
+    app.update();
+    assert_has_no_action_diffs(&mut app);
+}
+
+#[test]
+fn process_redundant_pressed_action_diffs() {
+    let mut app = create_app();
+    let entity = app
+        .world
+        .query_filtered::<Entity, With<ActionState<Action>>>()
+        .single(&app.world);
+    app.add_systems(PreUpdate, process_action_diffs::<Action>);
+
+    let action_diff_event = ActionDiffEvent {
+        owner: Some(entity),
+        action_diffs: vec![ActionDiff::Pressed {
+            action: Action::PayTheBills,
+        }],
+    };
+    send_action_diff(&mut app, action_diff_event.clone());
+    app.update();
+    send_action_diff(&mut app, action_diff_event.clone());
+    app.update();
+
+    let mut action_state_query = app.world.query::<&ActionState<Action>>();
+    let action_state = action_state_query.single(&app.world);
+    assert!(action_state.pressed(&Action::PayTheBills));
+    assert!(!action_state.just_pressed(&Action::PayTheBills));
+}
+
+#[test]
+fn generate_released_value_action_diffs() {
+    let mut app = create_app();
+    app.add_systems(PostUpdate, generate_action_diffs::<Action>);
+
+    // A throttle that sits below its press threshold
+    let mut action_state_query = app.world.query::<&mut ActionState<Action>>();
+    let mut action_state = action_state_query.single_mut(&mut app.world);
+    action_state.release(&Action::PayTheBills);
+    action_state
+        .action_data_mut(&Action::PayTheBills)
+        .unwrap()
+        .value = 0.4;
+
+    app.update();
+    assert_single_diff(
+        &mut app,
+        ActionDiff::ValueChanged {
+            action: Action::PayTheBills,
+            value: 0.4,
+            pressed: false,
+        },
+    );
+
+    app.update();
+    assert_has_no_action_diffs(&mut app);
+}
+
+#[test]
+fn generate_value_action_diffs_for_actions_released_with_a_value() {
+    let mut app = create_app();
+    app.add_systems(PostUpdate, generate_action_diffs::<Action>);
+
+    app.add_systems(
+        Update,
+        |mut action_state_query: Query<&mut ActionState<Action>>, mut counter: Local<Counter>| {
+            let mut action_state = action_state_query.single_mut();
+            if counter.0 == 0 {
+                action_state.press(&Action::PayTheBills);
+                action_state
+                    .action_data_mut(&Action::PayTheBills)
+                    .unwrap()
+                    .value = 1.;
+            } else if counter.0 == 1 {
+                // The throttle falls back below its press threshold, but doesn't return to zero
+                action_state.release(&Action::PayTheBills);
+                action_state
+                    .action_data_mut(&Action::PayTheBills)
+                    .unwrap()
+                    .value = 0.4;
+            }
+            counter.0 += 1;
+        },
+    );
+
+    app.update();
+    assert_single_diff(
+        &mut app,
+        ActionDiff::Pressed {
+            action: Action::PayTheBills,
+        },
+    );
+
+    app.update();
+    assert_action_diff_created(&mut app, |action_diff_event| {
+        // The release resets the value of the mirror, so the value is sent again
+        assert_eq!(
+            action_diff_event.action_diffs,
+            vec![
+                ActionDiff::Released {
+                    action: Action::PayTheBills,
+                },
+                ActionDiff::ValueChanged {
+                    action: Action::PayTheBills,
+                    value: 0.4,
+                    pressed: false,
+                },
+            ]
+        );
+    });
+
+    app.update();
+    assert_has_no_action_diffs(&mut app);
+}
+
+#[test]
+fn generate_released_axis_pair_action_diffs() {
+    let mut app = create_app();
+    app.add_systems(PostUpdate, generate_action_diffs::<Action>);
+
+    // A stick that moves within its deadzone
+    let mut action_state_query = app.world.query::<&mut ActionState<Action>>();
+    let mut action_state = action_state_query.single_mut(&mut app.world);
+    action_state.release(&Action::PayTheBills);
+    action_state
+        .action_data_mut(&Action::PayTheBills)
+        .unwrap()
+        .axis_pair = Some(DualAxisData::new(0.1, 0.0));
+
+    app.update();
+    assert_single_diff(
+        &mut app,
+        ActionDiff::AxisPairChanged {
+            action: Action::PayTheBills,
+            axis_pair: Vec2::new(0.1, 0.0),
+            pressed: false,
+        },
+    );
+
+    app.update();
+    assert_has_no_action_diffs(&mut app);
+}
+
+#[test]
+fn process_released_axis_pair_action_diff() {
+    let mut action_state = ActionState::<Action>::default();
+    action_state.apply_diff(&ActionDiff::AxisPairChanged {
+        action: Action::PayTheBills,
+        axis_pair: Vec2::new(0.1, 0.0),
+        pressed: false,
+    });
+
+    assert!(action_state.released(&Action::PayTheBills));
+    assert_eq!(
+        action_state.axis_pair(&Action::PayTheBills),
+        Some(DualAxisData::new(0.1, 0.0))
+    );
+}
+
+#[test]
+fn process_released_value_action_diff() {
+    let mut app = create_app();
+    let entity = app
+        .world
+        .query_filtered::<Entity, With<ActionState<Action>>>()
+        .single(&app.world);
+    app.add_systems(PreUpdate, process_action_diffs::<Action>);
+
+    let action_diff_event = ActionDiffEvent {
+        owner: Some(entity),
+        action_diffs: vec![ActionDiff::ValueChanged {
+            action: Action::PayTheBills,
+            value: 0.4,
+            pressed: false,
+        }],
+    };
+    send_action_diff(&mut app, action_diff_event.clone());
+
+    app.update();
+
+    assert_action_diff_received(&mut app, action_diff_event);
+}
+
+#[test]
+fn deserialize_value_changed_without_pressed() {
+    use serde_test::{assert_de_tokens, Token};
+
+    #[derive(
+        Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash, Serialize, Deserialize,
+    )]
+    enum Throttle {
+        Accelerate,
+    }
+
+    assert_de_tokens(
+        &ActionDiff::ValueChanged {
+            action: Throttle::Accelerate,
+            value: 0.5,
+            pressed: true,
+        },
+        &[
+            Token::Enum { name: "ActionDiff" },
+            Token::Str("ValueChanged"),
+            Token::Struct {
+                name: "ValueChanged",
+                len: 2,
+            },
+            Token::Str("action"),
+            Token::Enum { name: "Throttle" },
+            Token::Str("Accelerate"),
+            Token::Unit,
+            Token::Str("value"),
+            Token::F32(0.5),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn deserialize_axis_pair_changed_without_pressed() {
+    use serde_test::{assert_de_tokens, Token};
+
+    #[derive(
+        Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash, Serialize, Deserialize,
+    )]
+    enum Stick {
+        Aim,
+    }
+
+    assert_de_tokens(
+        &ActionDiff::AxisPairChanged {
+            action: Stick::Aim,
+            axis_pair: Vec2::new(0.5, 1.0),
+            pressed: true,
+        },
+        &[
+            Token::Enum { name: "ActionDiff" },
+            Token::Str("AxisPairChanged"),
+            Token::Struct {
+                name: "AxisPairChanged",
+                len: 2,
+            },
+            Token::Str("action"),
+            Token::Enum { name: "Stick" },
+            Token::Str("Aim"),
+            Token::Unit,
+            Token::Str("axis_pair"),
+            Token::TupleStruct {
+                name: "Vec2",
+                len: 2,
+            },
+            Token::F32(0.5),
+            Token::F32(1.0),
+            Token::TupleStructEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn generate_action_diffs_by_owner_id() {
+    let mut app = create_app();
+    app.add_event::<ActionDiffEvent<Action, u64>>()
+        .add_systems(
+            Update,
+            pay_da_bills(|mut action_state| {
+                action_state
+                    .action_data_mut(&Action::PayTheBills)
+                    .unwrap()
+                    .value = 1.;
+            }),
+        )
+        .add_systems(PostUpdate, generate_action_diffs_by_id::<Action, u64>);
+
+    // Only entities with a `DiffOwner` are tracked
+    app.update();
+    assert!(get_events::<ActionDiffEvent<Action, u64>>(&app).is_empty());
+
+    let entity = app
+        .world
+        .query_filtered::<Entity, With<ActionState<Action>>>()
+        .single(&app.world);
+    app.world.entity_mut(entity).insert(DiffOwner(7_u64));
+    app.world
+        .resource_mut::<Events<ActionDiffEvent<Action, u64>>>()
+        .clear();
+    app.world
+        .get_mut::<ActionState<Action>>(entity)
+        .unwrap()
+        .release(&Action::PayTheBills);
+    app.update();
+
+    let action_diff_events = get_events::<ActionDiffEvent<Action, u64>>(&app);
+    let action_diff_event = action_diff_events
+        .get_reader()
+        .read(action_diff_events)
+        .next()
+        .cloned()
+        .unwrap();
+    assert_eq!(
+        action_diff_event,
+        ActionDiffEvent {
+            owner: Some(7),
+            action_diffs: vec![ActionDiff::Pressed {
+                action: Action::PayTheBills,
+            }],
+        }
+    );
+}
+
+#[test]
+fn apply_action_diffs_by_owner_id() {
+    let mut app = create_app();
+    app.add_event::<ActionDiffEvent<Action, u64>>()
+        .init_resource::<DiffOwnerMap<u64>>()
+        .add_systems(PreUpdate, apply_action_diffs_by_id::<Action, u64>);
+
+    let entity = app
+        .world
+        .query_filtered::<Entity, With<ActionState<Action>>>()
+        .single(&app.world);
+    let other_entity = app.world.spawn(ActionState::<Action>::default()).id();
+    app.world
+        .resource_mut::<DiffOwnerMap<u64>>()
+        .insert(7, other_entity);
+
+    let pressed = |owner| ActionDiffEvent {
+        owner: Some(owner),
+        action_diffs: vec![ActionDiff::Pressed {
+            action: Action::PayTheBills,
+        }],
+    };
+    // Unmapped ids are ignored
+    get_events_mut::<ActionDiffEvent<Action, u64>>(&mut app).send(pressed(3));
+    app.update();
+    for entity in [entity, other_entity] {
+        let action_state = app.world.get::<ActionState<Action>>(entity).unwrap();
+        assert!(action_state.released(&Action::PayTheBills));
+    }
+
+    get_events_mut::<ActionDiffEvent<Action, u64>>(&mut app).send(pressed(7));
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(other_entity).unwrap();
+    assert!(action_state.pressed(&Action::PayTheBills));
+    let action_state = app.world.get::<ActionState<Action>>(entity).unwrap();
+    assert!(action_state.released(&Action::PayTheBills));
+}
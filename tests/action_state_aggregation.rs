@@ -0,0 +1,136 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::plugin::ActionStateAggregation;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+    Throttle,
+}
+
+fn create_app(aggregation: ActionStateAggregation) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default().aggregate_action_state(aggregation),
+    ));
+    app.update();
+    app
+}
+
+fn spawn_player(app: &mut App, jump: KeyCode, primary: bool) -> Entity {
+    let mut input_map = InputMap::new([(Action::Jump, jump)]);
+    input_map.insert(
+        Action::Throttle,
+        SingleAxis::symmetric(GamepadAxisType::RightZ, 0.0),
+    );
+    let mut player = app.world.spawn(InputManagerBundle::<Action> {
+        input_map,
+        ..default()
+    });
+    if primary {
+        player.insert(PrimaryInputSource);
+    }
+    player.id()
+}
+
+fn resource(app: &App) -> &ActionState<Action> {
+    app.world.resource::<ActionState<Action>>()
+}
+
+#[test]
+fn all_entities_pressed_wins() {
+    let mut app = create_app(ActionStateAggregation::AllEntities);
+    spawn_player(&mut app, KeyCode::Space, false);
+    spawn_player(&mut app, KeyCode::Return, false);
+    app.update();
+    assert!(resource(&app).released(&Action::Jump));
+
+    app.send_input(KeyCode::Return);
+    app.update();
+    assert!(resource(&app).just_pressed(&Action::Jump));
+
+    // Pressing the action for a second entity does not press it again
+    app.send_input(KeyCode::Space);
+    app.update();
+    assert!(resource(&app).pressed(&Action::Jump));
+    assert!(!resource(&app).just_pressed(&Action::Jump));
+
+    app.release_input(KeyCode::Return);
+    app.update();
+    assert!(resource(&app).pressed(&Action::Jump));
+
+    app.release_input(KeyCode::Space);
+    app.update();
+    assert!(resource(&app).just_released(&Action::Jump));
+}
+
+#[test]
+fn all_entities_value_max() {
+    let mut app = create_app(ActionStateAggregation::AllEntities);
+    let players = [
+        spawn_player(&mut app, KeyCode::Space, false),
+        spawn_player(&mut app, KeyCode::Return, false),
+    ];
+    app.update();
+
+    // Both players read the same gamepad axis, so give them different values directly
+    let mut query = app.world.query::<&mut ActionState<Action>>();
+    for (player, value) in players.into_iter().zip([0.3, -0.8]) {
+        let mut action_state = query.get_mut(&mut app.world, player).unwrap();
+        action_state.press(&Action::Throttle);
+        action_state
+            .action_data_mut(&Action::Throttle)
+            .unwrap()
+            .value = value;
+    }
+    app.world
+        .run_system_once(leafwing_input_manager::systems::aggregate_action_states::<Action>);
+
+    assert!(resource(&app).pressed(&Action::Throttle));
+    assert_eq!(resource(&app).value(&Action::Throttle), -0.8);
+}
+
+#[test]
+fn primary_entity_only() {
+    let mut app = create_app(ActionStateAggregation::PrimaryEntity);
+    spawn_player(&mut app, KeyCode::Space, true);
+    spawn_player(&mut app, KeyCode::Return, false);
+    app.update();
+
+    app.send_input(KeyCode::Return);
+    app.update();
+    assert!(resource(&app).released(&Action::Jump));
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    assert!(resource(&app).just_pressed(&Action::Jump));
+
+    app.release_input(KeyCode::Space);
+    app.update();
+    assert!(resource(&app).just_released(&Action::Jump));
+}
+
+#[test]
+fn no_entities_releases_everything() {
+    for aggregation in [
+        ActionStateAggregation::AllEntities,
+        ActionStateAggregation::PrimaryEntity,
+    ] {
+        let mut app = create_app(aggregation);
+        let player = spawn_player(&mut app, KeyCode::Space, true);
+        app.send_input(KeyCode::Space);
+        app.update();
+        assert!(resource(&app).pressed(&Action::Jump));
+
+        app.world.despawn(player);
+        app.update();
+        assert!(resource(&app).just_released(&Action::Jump));
+
+        app.update();
+        assert!(resource(&app).released(&Action::Jump));
+    }
+}
@@ -0,0 +1,153 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::action_state::KeepMirroredActionState;
+use leafwing_input_manager::plugin::InputManagerSystem;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::systems::relay_action_states;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Fire,
+}
+
+#[derive(Resource, Default)]
+struct SeenPresses(Vec<Entity>);
+
+fn record_presses(query: Query<(Entity, &ActionState<Action>)>, mut seen: ResMut<SeenPresses>) {
+    for (entity, action_state) in query.iter() {
+        if action_state.just_pressed(&Action::Fire) {
+            seen.0.push(entity);
+        }
+    }
+}
+
+/// Spawns a player with a weapon child that mirrors its actions
+fn test_app() -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .init_resource::<SeenPresses>()
+    .add_systems(PreUpdate, record_presses.after(InputManagerSystem::Relay));
+
+    let player = app
+        .world
+        .spawn(InputManagerBundle::<Action> {
+            input_map: InputMap::new([(Action::Fire, MouseButton::Left)]),
+            ..default()
+        })
+        .id();
+    let weapon = app.world.spawn(ActionStateSource(player)).id();
+    app.world.entity_mut(player).add_child(weapon);
+    app.update();
+    (app, player, weapon)
+}
+
+fn action_state(app: &App, entity: Entity) -> &ActionState<Action> {
+    app.world.get::<ActionState<Action>>(entity).unwrap()
+}
+
+#[test]
+fn mirror_sees_the_same_presses_on_the_same_frame() {
+    let (mut app, player, weapon) = test_app();
+    assert!(app.world.get::<ActionState<Action>>(weapon).is_some());
+
+    app.send_input(MouseButton::Left);
+    app.update();
+    let seen = &app.world.resource::<SeenPresses>().0;
+    assert_eq!(seen.len(), 2);
+    assert!(seen.contains(&player));
+    assert!(seen.contains(&weapon));
+
+    app.update();
+    assert!(action_state(&app, weapon).pressed(&Action::Fire));
+    assert!(!action_state(&app, weapon).just_pressed(&Action::Fire));
+
+    app.release_input(MouseButton::Left);
+    app.update();
+    assert!(action_state(&app, player).just_released(&Action::Fire));
+    assert!(action_state(&app, weapon).just_released(&Action::Fire));
+}
+
+#[test]
+fn mirror_is_released_when_source_is_despawned() {
+    let (mut app, player, weapon) = test_app();
+    app.send_input(MouseButton::Left);
+    app.update();
+
+    app.world.entity_mut(player).remove_children(&[weapon]);
+    app.world.despawn(player);
+    app.update();
+    assert!(action_state(&app, weapon).just_released(&Action::Fire));
+
+    // The mirror is no longer updated
+    app.update();
+    assert!(action_state(&app, weapon).released(&Action::Fire));
+    assert!(!action_state(&app, weapon).just_released(&Action::Fire));
+}
+
+#[test]
+fn mirror_is_released_when_relay_is_removed() {
+    let (mut app, player, weapon) = test_app();
+    app.send_input(MouseButton::Left);
+    app.update();
+
+    app.world.entity_mut(weapon).remove::<ActionStateSource>();
+    app.update();
+    assert!(action_state(&app, player).pressed(&Action::Fire));
+    assert!(action_state(&app, weapon).just_released(&Action::Fire));
+}
+
+#[test]
+fn mirror_can_keep_its_state() {
+    let (mut app, player, weapon) = test_app();
+    app.world.entity_mut(weapon).insert(KeepMirroredActionState);
+    app.send_input(MouseButton::Left);
+    app.update();
+
+    app.world.entity_mut(player).remove_children(&[weapon]);
+    app.world.despawn(player);
+    app.release_input(MouseButton::Left);
+    app.update();
+    app.update();
+    assert!(action_state(&app, weapon).pressed(&Action::Fire));
+}
+
+#[test]
+fn mirror_only_changes_with_its_source() {
+    #[derive(Resource, Default)]
+    struct MirrorChanges(usize);
+
+    fn count_mirror_changes(
+        query: Query<(), (Changed<ActionState<Action>>, With<ActionStateSource>)>,
+        mut changes: ResMut<MirrorChanges>,
+    ) {
+        changes.0 += query.iter().count();
+    }
+
+    // Without the plugin, nothing but the relay writes to the mirror
+    let mut app = App::new();
+    app.init_resource::<MirrorChanges>().add_systems(
+        Update,
+        (relay_action_states::<Action>, count_mirror_changes).chain(),
+    );
+    let player = app.world.spawn(ActionState::<Action>::default()).id();
+    app.world.spawn(ActionStateSource(player));
+    app.update();
+    app.update();
+    app.world.resource_mut::<MirrorChanges>().0 = 0;
+
+    app.update();
+    app.update();
+    assert_eq!(app.world.resource::<MirrorChanges>().0, 0);
+
+    app.world
+        .get_mut::<ActionState<Action>>(player)
+        .unwrap()
+        .press(&Action::Fire);
+    app.update();
+    app.update();
+    assert_eq!(app.world.resource::<MirrorChanges>().0, 1);
+}
@@ -0,0 +1,127 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::charges::Charges;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::timing::TickClock;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Dodge,
+    Jump,
+}
+
+#[derive(Component)]
+struct Player;
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .insert_resource(TickClock::manual());
+
+    let mut charges = Charges::default();
+    charges.set(Action::Dodge, 2, Duration::from_secs(3));
+
+    app.world.spawn((
+        InputManagerBundle::with_map(InputMap::new([
+            (Action::Dodge, KeyCode::ShiftLeft),
+            (Action::Jump, KeyCode::Space),
+        ])),
+        charges,
+        Player,
+    ));
+
+    // Initializing
+    app.update();
+    app
+}
+
+fn player_state(app: &mut App) -> (&ActionState<Action>, &Charges<Action>) {
+    app.world
+        .query_filtered::<(&ActionState<Action>, &Charges<Action>), With<Player>>()
+        .single(&app.world)
+}
+
+fn dodge(app: &mut App) {
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+}
+
+fn stop_dodging(app: &mut App) {
+    app.release_input(KeyCode::ShiftLeft);
+    app.update();
+}
+
+#[test]
+fn full_charges_let_presses_through() {
+    let mut app = create_app();
+    let (_, charges) = player_state(&mut app);
+    assert_eq!(charges.available(&Action::Dodge), 2);
+
+    dodge(&mut app);
+    let (action_state, charges) = player_state(&mut app);
+    assert!(action_state.just_pressed(&Action::Dodge));
+    assert_eq!(charges.available(&Action::Dodge), 1);
+
+    // Holding the action doesn't spend more charges
+    app.update();
+    let (action_state, charges) = player_state(&mut app);
+    assert!(action_state.pressed(&Action::Dodge));
+    assert_eq!(charges.available(&Action::Dodge), 1);
+}
+
+#[test]
+fn empty_charges_swallow_presses() {
+    let mut app = create_app();
+    for _ in 0..2 {
+        dodge(&mut app);
+        stop_dodging(&mut app);
+    }
+
+    dodge(&mut app);
+    let (action_state, charges) = player_state(&mut app);
+    assert!(!action_state.pressed(&Action::Dodge));
+    assert!(action_state.consumed(&Action::Dodge));
+    assert_eq!(charges.available(&Action::Dodge), 0);
+
+    // Actions without charges are unaffected
+    app.send_input(KeyCode::Space);
+    app.update();
+    let (action_state, _) = player_state(&mut app);
+    assert!(action_state.just_pressed(&Action::Jump));
+}
+
+#[test]
+fn charges_are_regained_over_time() {
+    let mut app = create_app();
+    for _ in 0..2 {
+        dodge(&mut app);
+        stop_dodging(&mut app);
+    }
+
+    // Mid-recharge, there is still no charge to spend
+    app.advance_time(Duration::from_secs(2));
+    dodge(&mut app);
+    let (action_state, charges) = player_state(&mut app);
+    assert!(!action_state.pressed(&Action::Dodge));
+    assert_eq!(
+        charges.get(&Action::Dodge).unwrap().until_next_charge(),
+        Duration::from_secs(1)
+    );
+    stop_dodging(&mut app);
+
+    // The recharge uses the same clock as the durations of the actions
+    app.advance_time(Duration::from_secs(1));
+    app.update();
+    let (_, charges) = player_state(&mut app);
+    assert_eq!(charges.available(&Action::Dodge), 1);
+
+    dodge(&mut app);
+    let (action_state, charges) = player_state(&mut app);
+    assert!(action_state.just_pressed(&Action::Dodge));
+    assert_eq!(charges.available(&Action::Dodge), 0);
+}
@@ -43,6 +43,10 @@ impl Action {
 }
 
 fn spawn_input_map(mut commands: Commands) {
+    commands.spawn(test_input_map());
+}
+
+fn test_input_map() -> InputMap<Action> {
     use Action::*;
     use KeyCode::*;
 
@@ -57,7 +61,7 @@ fn spawn_input_map(mut commands: Commands) {
     input_map.insert_chord(AltOne, [AltLeft, Key1]);
     input_map.insert_chord(CtrlAltOne, [ControlLeft, AltLeft, Key1]);
 
-    commands.spawn(input_map);
+    input_map
 }
 
 trait ClashTestExt {
@@ -199,3 +203,838 @@ fn action_order_clash_handling() {
     app.assert_input_map_actions_eq(ClashStrategy::PressAll, [Two, TwoAndThree]);
     app.assert_input_map_actions_eq(ClashStrategy::PrioritizeLongest, [TwoAndThree]);
 }
+
+#[test]
+fn simulated_clash_handling() {
+    use Action::*;
+    use KeyCode::*;
+
+    let input_map = test_input_map();
+    let simulated_actions = |pressed: &[KeyCode], clash_strategy: ClashStrategy| {
+        let pressed: Vec<UserInput> = pressed.iter().map(|&key| key.into()).collect();
+        let report = input_map.simulate_clashes(&pressed, clash_strategy);
+        HashSet::<Action>::from_iter(report.pressed)
+    };
+
+    for (pressed, press_all, prioritize_longest) in [
+        (vec![Key1, Key2], vec![One, Two, OneAndTwo], vec![OneAndTwo]),
+        (
+            vec![Key1, Key2, Key3],
+            vec![One, Two, OneAndTwo, TwoAndThree, OneAndTwoAndThree],
+            vec![OneAndTwoAndThree],
+        ),
+        (
+            vec![Key1, Key2, Key3, ControlLeft],
+            vec![One, Two, OneAndTwo, TwoAndThree, OneAndTwoAndThree, CtrlOne],
+            vec![CtrlOne, OneAndTwoAndThree],
+        ),
+        (
+            vec![Key1, ControlLeft, AltLeft],
+            vec![One, CtrlOne, AltOne, CtrlAltOne],
+            vec![CtrlAltOne],
+        ),
+        (vec![Key3, Key2], vec![Two, TwoAndThree], vec![TwoAndThree]),
+    ] {
+        assert_eq!(
+            simulated_actions(&pressed, ClashStrategy::PressAll),
+            HashSet::from_iter(press_all),
+            "PressAll with {pressed:?}"
+        );
+        assert_eq!(
+            simulated_actions(&pressed, ClashStrategy::PrioritizeLongest),
+            HashSet::from_iter(prioritize_longest),
+            "PrioritizeLongest with {pressed:?}"
+        );
+    }
+
+    // The report explains which actions were suppressed, and by whom
+    let report = input_map.simulate_clashes(
+        &[UserInput::chord([ControlLeft, Key1])],
+        ClashStrategy::PrioritizeLongest,
+    );
+    assert_eq!(report.pressed, [CtrlOne]);
+    assert_eq!(report.suppressed_by(&One), [&CtrlOne]);
+    assert!(!report.is_suppressed(&CtrlOne));
+
+    let suppressed = report
+        .suppressed
+        .iter()
+        .find(|suppressed| suppressed.action == One)
+        .unwrap();
+    assert_eq!(suppressed.inputs, [UserInput::from(Key1)]);
+
+    let report = input_map.simulate_clashes(&[Key1.into()], ClashStrategy::PrioritizeLongest);
+    assert_eq!(report.pressed, [One]);
+    assert!(report.suppressed.is_empty());
+}
+
+#[derive(
+    Actionlike,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Reflect,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+enum FileAction {
+    Save,
+    SaveAs,
+    SaveAll,
+}
+
+#[test]
+fn binding_order_clash_handling() {
+    use FileAction::*;
+    use KeyCode::*;
+
+    fn pressed_actions(app: &App, bindings: &[(FileAction, UserInput)]) -> Vec<FileAction> {
+        let input_map: InputMap<FileAction> = bindings.iter().cloned().collect();
+        let input_streams = InputStreams::from_world(&app.world, None);
+
+        [Save, SaveAs, SaveAll]
+            .into_iter()
+            .filter(|action| {
+                input_map.pressed(action, &input_streams, ClashStrategy::PrioritizeFirst)
+            })
+            .collect()
+    }
+
+    let save = (Save, UserInput::from(S));
+    let save_as = (SaveAs, UserInput::chord([ControlLeft, S]));
+    let save_all = (SaveAll, UserInput::chord([ControlLeft, ShiftLeft, S]));
+
+    let mut app = test_app();
+    app.send_input(ControlLeft);
+    app.send_input(ShiftLeft);
+    app.send_input(S);
+    app.update();
+
+    // All three bindings are pressed, so the first one wins
+    let in_order = [save.clone(), save_as.clone(), save_all.clone()];
+    assert_eq!(pressed_actions(&app, &in_order), [Save]);
+    let reversed = [save_all.clone(), save_as.clone(), save.clone()];
+    assert_eq!(pressed_actions(&app, &reversed), [SaveAll]);
+    let shuffled = [save_as.clone(), save.clone(), save_all.clone()];
+    assert_eq!(pressed_actions(&app, &shuffled), [SaveAs]);
+
+    // Bindings that are not pressed don't take part in the clash
+    app.release_input(ShiftLeft);
+    app.update();
+    assert_eq!(pressed_actions(&app, &reversed), [SaveAs]);
+    assert_eq!(pressed_actions(&app, &in_order), [Save]);
+
+    // Clearing and reinserting a binding moves it to the back
+    let mut input_map: InputMap<FileAction> = in_order.iter().cloned().collect();
+    input_map.clear_action(&Save);
+    input_map.insert(save.0, save.1.clone());
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(input_map.pressed(&SaveAs, &input_streams, ClashStrategy::PrioritizeFirst));
+    assert!(!input_map.pressed(&Save, &input_streams, ClashStrategy::PrioritizeFirst));
+}
+
+#[test]
+fn binding_order_survives_serialization() {
+    use FileAction::*;
+    use KeyCode::*;
+
+    fn pressed_actions(app: &App, input_map: &InputMap<FileAction>) -> Vec<FileAction> {
+        let input_streams = InputStreams::from_world(&app.world, None);
+
+        [Save, SaveAs, SaveAll]
+            .into_iter()
+            .filter(|action| {
+                input_map.pressed(action, &input_streams, ClashStrategy::PrioritizeFirst)
+            })
+            .collect()
+    }
+
+    // Inserted in the reverse of the declaration order, so falling back to it would pick another winner
+    let mut input_map = InputMap::default();
+    input_map
+        .insert_chord(SaveAll, [ControlLeft, ShiftLeft, S])
+        .insert_chord(SaveAs, [ControlLeft, S])
+        .insert(Save, S);
+
+    let mut app = test_app();
+    app.send_input(ControlLeft);
+    app.send_input(ShiftLeft);
+    app.send_input(S);
+    app.update();
+    assert_eq!(pressed_actions(&app, &input_map), [SaveAll]);
+
+    let bytes = bincode::serialize(&input_map).unwrap();
+    let deserialized: InputMap<FileAction> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(pressed_actions(&app, &deserialized), [SaveAll]);
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum EditorAction {
+    Save,
+    SaveAs,
+    Zoom,
+    ZoomFast,
+}
+
+#[test]
+fn subset_chord_clash_handling() {
+    use leafwing_input_manager::user_input::InputKind;
+    use EditorAction::*;
+    use KeyCode::*;
+
+    let mut input_map = InputMap::default();
+    input_map.insert_modified(Save, Modifier::Control, S);
+    input_map.insert_chord(SaveAs, [ControlLeft, ShiftLeft, S]);
+    input_map.insert_modified(Zoom, Modifier::Control, MouseWheelDirection::Up);
+    input_map.insert_chord(
+        ZoomFast,
+        [
+            InputKind::Modifier(Modifier::Control),
+            InputKind::Modifier(Modifier::Shift),
+            InputKind::MouseWheel(MouseWheelDirection::Up),
+        ],
+    );
+
+    let mut app = test_app();
+    let pressed_actions = |app: &mut App, clash_strategy: ClashStrategy| {
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        [Save, SaveAs, Zoom, ZoomFast]
+            .into_iter()
+            .filter(|action| input_map.pressed(action, &input_streams, clash_strategy))
+            .collect::<Vec<_>>()
+    };
+
+    app.send_input(ControlLeft);
+    app.send_input(S);
+    assert_eq!(
+        pressed_actions(&mut app, ClashStrategy::PrioritizeLongest),
+        [Save]
+    );
+
+    // The superset chord wins over its subset
+    app.send_input(ShiftLeft);
+    assert_eq!(
+        pressed_actions(&mut app, ClashStrategy::PrioritizeLongest),
+        [SaveAs]
+    );
+    assert_eq!(
+        pressed_actions(&mut app, ClashStrategy::PressAll),
+        [Save, SaveAs]
+    );
+
+    // Including for chords with non-keyboard members
+    app.release_input(S);
+    app.send_input(MouseWheelDirection::Up);
+    assert_eq!(
+        pressed_actions(&mut app, ClashStrategy::PrioritizeLongest),
+        [ZoomFast]
+    );
+
+    app.release_input(ShiftLeft);
+    app.send_input(MouseWheelDirection::Up);
+    assert_eq!(
+        pressed_actions(&mut app, ClashStrategy::PrioritizeLongest),
+        [Zoom]
+    );
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum MoveAction {
+    Move,
+    Interact,
+    Sprint,
+}
+
+#[test]
+fn virtual_dpad_clash_handling() {
+    use KeyCode::*;
+    use MoveAction::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<MoveAction>::default())
+        .init_resource::<ActionState<MoveAction>>();
+
+    let mut input_map = InputMap::default();
+    input_map.insert(
+        Move,
+        VirtualDPad {
+            up: W.into(),
+            down: S.into(),
+            left: A.into(),
+            right: D.into(),
+            normalize_diagonals: false,
+        },
+    );
+    input_map.insert(Interact, W);
+    input_map.insert_chord(Sprint, [ShiftLeft, W]);
+    app.insert_resource(input_map);
+    app.update();
+
+    let pressed_actions = |app: &App| {
+        let action_state = app.world.resource::<ActionState<MoveAction>>();
+        [Move, Interact, Sprint]
+            .into_iter()
+            .filter(|action| action_state.pressed(action))
+            .collect::<Vec<_>>()
+    };
+    let move_axis_pair = |app: &App| {
+        app.world
+            .resource::<ActionState<MoveAction>>()
+            .axis_pair(&Move)
+            .unwrap()
+            .xy()
+    };
+
+    // The dpad contains W, so it wins over the single key
+    app.send_input(W);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Move]);
+    assert_eq!(move_axis_pair(&app), Vec2::Y);
+
+    app.send_input(D);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Move]);
+    assert_eq!(move_axis_pair(&app), Vec2::new(1.0, 1.0));
+
+    // Chords beat the dpad they overlap with
+    app.release_input(D);
+    app.send_input(ShiftLeft);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Sprint]);
+
+    // Other strategies are unaffected
+    app.insert_resource(ClashStrategy::PressAll);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Move, Interact, Sprint]);
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum DebugAction {
+    Screenshot,
+    DebugCapture,
+}
+
+#[test]
+fn clash_exempt_actions() {
+    use DebugAction::*;
+    use KeyCode::*;
+
+    let mut app = test_app();
+    app.send_input(ControlLeft);
+    app.send_input(F12);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let pressed_actions = |input_map: &InputMap<DebugAction>| {
+        [Screenshot, DebugCapture]
+            .into_iter()
+            .filter(|action| {
+                input_map.pressed(action, &input_streams, ClashStrategy::PrioritizeLongest)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut input_map = InputMap::default();
+    input_map.insert(Screenshot, F12);
+    input_map.insert_chord(DebugCapture, [ControlLeft, F12]);
+    assert_eq!(pressed_actions(&input_map), [DebugCapture]);
+
+    // Exempt actions are never suppressed
+    input_map.set_clash_exempt(&Screenshot, true);
+    assert_eq!(pressed_actions(&input_map), [Screenshot, DebugCapture]);
+
+    // Exempt actions still suppress others by default
+    input_map.set_clash_exempt(&Screenshot, false);
+    input_map.set_clash_exempt(&DebugCapture, true);
+    assert_eq!(pressed_actions(&input_map), [DebugCapture]);
+
+    // Unless they are configured not to
+    input_map.set_clash_exempt_blocks(&DebugCapture, false);
+    assert_eq!(pressed_actions(&input_map), [Screenshot, DebugCapture]);
+
+    // Exempt actions are only pressed by their own inputs
+    app.release_input(F12);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    assert!(!input_map.pressed(
+        &DebugCapture,
+        &input_streams,
+        ClashStrategy::PrioritizeLongest
+    ));
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum SaveAction {
+    Crouch,
+    Save,
+}
+
+#[test]
+fn chord_grace_window() {
+    use bevy::utils::Duration;
+    use leafwing_input_manager::timing::TickClock;
+    use KeyCode::*;
+    use SaveAction::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<SaveAction>::default())
+        .insert_resource(TickClock::manual())
+        .insert_resource(ClashStrategy::DelayForChords {
+            window: Duration::from_millis(100),
+        })
+        .init_resource::<ActionState<SaveAction>>();
+
+    let mut input_map = InputMap::default();
+    input_map.insert(Crouch, S);
+    input_map.insert_chord(Save, [ControlLeft, S]);
+    app.insert_resource(input_map);
+    app.update();
+
+    let wait = |app: &mut App, millis: u64| {
+        app.world
+            .resource_mut::<TickClock>()
+            .advance(Duration::from_millis(millis));
+        app.update();
+    };
+    let pressed_actions = |app: &App| {
+        let action_state = app.world.resource::<ActionState<SaveAction>>();
+        [Crouch, Save]
+            .into_iter()
+            .filter(|action| action_state.pressed(action))
+            .collect::<Vec<_>>()
+    };
+
+    // The chord completes within the window, so only the chord fires
+    app.send_input(S);
+    wait(&mut app, 16);
+    assert_eq!(pressed_actions(&app), []);
+
+    app.send_input(ControlLeft);
+    wait(&mut app, 50);
+    assert_eq!(pressed_actions(&app), [Save]);
+
+    wait(&mut app, 200);
+    assert_eq!(pressed_actions(&app), [Save]);
+
+    app.release_input(ControlLeft);
+    app.release_input(S);
+    wait(&mut app, 16);
+    assert_eq!(pressed_actions(&app), []);
+
+    // The chord is not completed, so the single key fires late, as if it was pressed on time
+    app.send_input(S);
+    wait(&mut app, 16);
+    assert_eq!(pressed_actions(&app), []);
+
+    wait(&mut app, 50);
+    assert_eq!(pressed_actions(&app), []);
+
+    wait(&mut app, 60);
+    let action_state = app.world.resource::<ActionState<SaveAction>>();
+    assert!(action_state.just_pressed(&Crouch));
+    assert_eq!(
+        action_state.current_duration(&Crouch),
+        Duration::from_millis(110)
+    );
+
+    wait(&mut app, 10);
+    let action_state = app.world.resource::<ActionState<SaveAction>>();
+    assert!(action_state.pressed(&Crouch));
+    assert!(!action_state.just_pressed(&Crouch));
+    assert_eq!(
+        action_state.current_duration(&Crouch),
+        Duration::from_millis(120)
+    );
+
+    // Held single keys are not withheld again, even when a chord completes
+    app.send_input(ControlLeft);
+    wait(&mut app, 16);
+    assert_eq!(pressed_actions(&app), [Save]);
+
+    // Taps that are shorter than the window are still pressed, for a single frame
+    app.release_input(ControlLeft);
+    app.release_input(S);
+    wait(&mut app, 16);
+    app.send_input(S);
+    wait(&mut app, 16);
+    assert_eq!(pressed_actions(&app), []);
+
+    app.release_input(S);
+    wait(&mut app, 16);
+    assert_eq!(pressed_actions(&app), [Crouch]);
+
+    wait(&mut app, 16);
+    assert_eq!(pressed_actions(&app), []);
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum GameAction {
+    Pause,
+    Jump,
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum MenuAction {
+    Close,
+}
+
+#[test]
+fn input_claims_across_action_types() {
+    use leafwing_input_manager::clashing_inputs::InputClaims;
+    use leafwing_input_manager::plugin::UpdateActionStateSet;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<GameAction>::default().claim_inputs(0))
+        .add_plugins(InputManagerPlugin::<MenuAction>::default().claim_inputs(1))
+        .configure_sets(
+            PreUpdate,
+            UpdateActionStateSet::<MenuAction>::default()
+                .before(UpdateActionStateSet::<GameAction>::default()),
+        );
+
+    let player = app
+        .world
+        .spawn((
+            InputManagerBundle::<GameAction> {
+                input_map: InputMap::new([
+                    (GameAction::Pause, KeyCode::Escape),
+                    (GameAction::Jump, KeyCode::Space),
+                ]),
+                ..Default::default()
+            },
+            InputManagerBundle::<MenuAction> {
+                input_map: InputMap::new([(MenuAction::Close, KeyCode::Escape)]),
+                ..Default::default()
+            },
+        ))
+        .id();
+    app.update();
+
+    let pressed = |app: &App| {
+        let game = app.world.get::<ActionState<GameAction>>(player).unwrap();
+        let menu = app.world.get::<ActionState<MenuAction>>(player).unwrap();
+        (
+            game.pressed(&GameAction::Pause),
+            game.pressed(&GameAction::Jump),
+            menu.pressed(&MenuAction::Close),
+        )
+    };
+
+    // The menu claims Escape, but not Space
+    app.send_input(KeyCode::Escape);
+    app.send_input(KeyCode::Space);
+    app.update();
+    assert_eq!(pressed(&app), (false, true, true));
+
+    // Once the menu is closed, Escape does not leak into gameplay until it is pressed again
+    app.insert_resource(ToggleActions::<MenuAction>::DISABLED);
+    app.update();
+    assert_eq!(pressed(&app), (false, true, false));
+
+    app.release_input(KeyCode::Escape);
+    app.update();
+    app.send_input(KeyCode::Escape);
+    app.update();
+    assert_eq!(pressed(&app), (true, true, false));
+
+    // Action types with the same priority do not hide inputs from each other
+    app.insert_resource(ToggleActions::<MenuAction>::ENABLED);
+    app.world
+        .resource_mut::<InputClaims>()
+        .set_priority::<GameAction>(1);
+    app.release_input(KeyCode::Escape);
+    app.update();
+    app.send_input(KeyCode::Escape);
+    app.update();
+    assert_eq!(pressed(&app), (true, true, true));
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum LayoutAction {
+    Backward,
+    Strafe,
+    Save,
+}
+
+#[test]
+fn logical_physical_clash_handling() {
+    use leafwing_input_manager::user_input::InputKind;
+    use KeyCode::*;
+    use LayoutAction::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<LayoutAction>::default())
+        .init_resource::<ActionState<LayoutAction>>();
+
+    // Logical and physical bindings of the same keys
+    let mut input_map = InputMap::default();
+    input_map.insert(Backward, S);
+    input_map.insert(Strafe, QwertyScanCode::S);
+    input_map.insert_chord(
+        Save,
+        [InputKind::from(QwertyScanCode::ControlLeft), S.into()],
+    );
+    app.insert_resource(input_map);
+    app.update();
+
+    let pressed_actions = |app: &App| {
+        let action_state = app.world.resource::<ActionState<LayoutAction>>();
+        [Backward, Strafe, Save]
+            .into_iter()
+            .filter(|action| action_state.pressed(action))
+            .collect::<Vec<_>>()
+    };
+
+    // A real keystroke presses both the key and its location,
+    // which are tied, so the action that is declared first wins
+    app.send_input(S);
+    app.send_input(QwertyScanCode::S);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Backward]);
+
+    // The mixed chord beats both of the single keys
+    app.send_input(ControlLeft);
+    app.send_input(QwertyScanCode::ControlLeft);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Save]);
+
+    // Single keys of the same length are resolved by binding order
+    app.insert_resource(ClashStrategy::PrioritizeFirst);
+    app.release_input(ControlLeft);
+    app.release_input(QwertyScanCode::ControlLeft);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Backward]);
+
+    // Without the location, only the logical key is pressed
+    app.release_input(QwertyScanCode::S);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Backward]);
+
+    // Simulated logical keys press their location on the QWERTY layout too
+    let report = app
+        .world
+        .resource::<InputMap<LayoutAction>>()
+        .simulate_clashes(&[S.into()], ClashStrategy::PrioritizeFirst);
+    assert_eq!(report.pressed, [Backward]);
+    assert_eq!(report.suppressed_by(&Strafe), [&Backward]);
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum FlightAction {
+    Throttle,
+    Pitch,
+}
+
+#[test]
+fn tied_clash_resolution_is_deterministic() {
+    use FlightAction::*;
+    use KeyCode::*;
+
+    let mut app = App::new();
+    app.add_plugins(InputPlugin);
+    app.send_input(W);
+    app.update();
+    let input_streams = InputStreams::from_world(&app.world, None);
+
+    // Both axes contain W, so they clash with the same length
+    let bindings = [
+        (Throttle, UserInput::from(VirtualAxis::ws())),
+        (Pitch, UserInput::from(VirtualAxis::from_keys(Up, W))),
+    ];
+
+    for reverse_insertion in [false, true] {
+        for _ in 0..100 {
+            // Rebuilding the map each time shuffles the order in which it stores its bindings
+            let mut input_map = InputMap::default();
+            if reverse_insertion {
+                input_map.insert_multiple(bindings.iter().rev().cloned());
+            } else {
+                input_map.insert_multiple(bindings.iter().cloned());
+            }
+
+            for _ in 0..10 {
+                let action_data =
+                    input_map.which_pressed(&input_streams, ClashStrategy::PrioritizeLongest);
+                assert!(action_data[&Throttle].state.pressed());
+                assert!(!action_data[&Pitch].state.pressed());
+            }
+        }
+    }
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum StealthAction {
+    Sprint,
+    Sneak,
+    Crouch,
+}
+
+#[test]
+fn clash_strategy_precedence() {
+    use KeyCode::*;
+    use StealthAction::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<StealthAction>::default())
+        .init_resource::<ActionState<StealthAction>>()
+        .insert_resource(ClashStrategy::PrioritizeLongest);
+
+    let mut input_map = InputMap::new([(Sprint, ShiftLeft), (Crouch, ControlLeft)]);
+    input_map.insert_chord(Sneak, [ShiftLeft, ControlLeft]);
+    app.insert_resource(input_map);
+
+    app.send_input(ShiftLeft);
+    app.send_input(ControlLeft);
+
+    let pressed_actions = |app: &mut App| {
+        app.update();
+        let action_state = app.world.resource::<ActionState<StealthAction>>();
+        [Sprint, Sneak, Crouch]
+            .into_iter()
+            .filter(|action| action_state.pressed(action))
+            .collect::<Vec<_>>()
+    };
+
+    // The resource is used by default
+    assert_eq!(pressed_actions(&mut app), [Sneak]);
+
+    // Overrides only apply to clashes among their own actions
+    app.world
+        .resource_mut::<InputMap<StealthAction>>()
+        .set_clash_override([Sprint, Sneak], ClashStrategy::PressAll);
+    assert_eq!(pressed_actions(&mut app), [Sprint, Sneak]);
+
+    // The strategy of the map replaces the resource, but not the overrides
+    app.world
+        .resource_mut::<InputMap<StealthAction>>()
+        .set_clash_strategy(ClashStrategy::PressAll)
+        .set_clash_override([Sneak, Sprint], ClashStrategy::PrioritizeLongest);
+    assert_eq!(pressed_actions(&mut app), [Sneak, Crouch]);
+
+    app.world
+        .resource_mut::<InputMap<StealthAction>>()
+        .remove_clash_override([Sprint, Sneak]);
+    assert_eq!(pressed_actions(&mut app), [Sprint, Sneak, Crouch]);
+
+    app.world
+        .resource_mut::<InputMap<StealthAction>>()
+        .clear_clash_strategy();
+    assert_eq!(pressed_actions(&mut app), [Sneak]);
+}
+
+#[test]
+fn suppression_records_match_resolution() {
+    use Action::*;
+    use KeyCode::*;
+
+    let mut app = test_app();
+    app.send_input(Key1);
+    app.send_input(Key2);
+    app.send_input(ControlLeft);
+    app.update();
+
+    let input_map = test_input_map();
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let (action_data, suppressed) =
+        input_map.which_pressed_verbose(&input_streams, ClashStrategy::PrioritizeLongest);
+    assert_eq!(
+        action_data,
+        input_map.which_pressed(&input_streams, ClashStrategy::PrioritizeLongest)
+    );
+
+    // Exactly the actions that would have been pressed without clash handling are reported
+    let unresolved = input_map.which_pressed(&input_streams, ClashStrategy::PressAll);
+    for action in Action::variants() {
+        let lost_clash = unresolved[action].state.pressed() && action_data[action].state.released();
+        let reported = suppressed.iter().any(|record| &record.action == action);
+        assert_eq!(lost_clash, reported, "{action:?}");
+    }
+
+    for record in &suppressed {
+        assert!(action_data[&record.suppressed_by].state.pressed());
+        assert!(!record.inputs.is_empty());
+        for input in &record.inputs {
+            assert!(input_streams.input_pressed(input));
+            assert!(input_map.get(&record.action).unwrap().contains(input));
+        }
+    }
+
+    let suppressors_of = |action: Action| {
+        let mut suppressors: Vec<Action> = suppressed
+            .iter()
+            .filter(|record| record.action == action)
+            .map(|record| record.suppressed_by)
+            .collect();
+        suppressors.sort_by_key(|action| format!("{action:?}"));
+        suppressors
+    };
+    assert_eq!(suppressors_of(One), [CtrlOne, OneAndTwo]);
+    assert_eq!(suppressors_of(Two), [OneAndTwo]);
+}
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum CharacterAction {
+    Number,
+    Symbol,
+    ShiftNumber,
+}
+
+#[test]
+fn shifted_character_clash_handling() {
+    use CharacterAction::*;
+    use KeyCode::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<CharacterAction>::default())
+        .init_resource::<ActionState<CharacterAction>>();
+
+    // `At` is typed with Shift + 2 on the QWERTY layout
+    let mut input_map = InputMap::default();
+    input_map.insert(Number, Key2);
+    input_map.insert(Symbol, At);
+    app.insert_resource(input_map);
+    app.update();
+
+    let pressed_actions = |app: &App| {
+        let action_state = app.world.resource::<ActionState<CharacterAction>>();
+        [Number, Symbol, ShiftNumber]
+            .into_iter()
+            .filter(|action| action_state.pressed(action))
+            .collect::<Vec<_>>()
+    };
+
+    // The shifted character is an implicit chord, so it beats the key that types it
+    app.send_input(ShiftLeft);
+    app.send_input(Key2);
+    app.send_input(At);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Symbol]);
+
+    // The explicit chord is the same length, so both are pressed
+    app.world
+        .resource_mut::<InputMap<CharacterAction>>()
+        .insert_modified(ShiftNumber, Modifier::Shift, Key2);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Symbol, ShiftNumber]);
+
+    // Without Shift, only the number is pressed
+    app.release_input(ShiftLeft);
+    app.release_input(At);
+    app.update();
+    assert_eq!(pressed_actions(&app), [Number]);
+}
@@ -0,0 +1,100 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::cooldowns::Cooldowns;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::timing::TickClock;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Dash,
+    Jump,
+}
+
+#[derive(Component)]
+struct Player;
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .insert_resource(TickClock::manual());
+
+    let mut cooldowns = Cooldowns::default();
+    cooldowns.set(Action::Dash, Duration::from_secs(1));
+
+    app.world.spawn((
+        InputManagerBundle {
+            input_map: InputMap::new([
+                (Action::Dash, KeyCode::ShiftLeft),
+                (Action::Jump, KeyCode::Space),
+            ]),
+            ..default()
+        },
+        cooldowns,
+        Player,
+    ));
+
+    // Initializing
+    app.update();
+    app
+}
+
+fn player_state(app: &mut App) -> (&ActionState<Action>, &Cooldowns<Action>) {
+    app.world
+        .query_filtered::<(&ActionState<Action>, &Cooldowns<Action>), With<Player>>()
+        .single(&app.world)
+}
+
+#[test]
+fn presses_on_cooldown_are_blocked() {
+    let mut app = create_app();
+
+    // Dashing starts the cooldown
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    let (action_state, cooldowns) = player_state(&mut app);
+    assert!(action_state.just_pressed(&Action::Dash));
+    assert_eq!(cooldowns.remaining(&Action::Dash), Duration::from_secs(1));
+
+    app.release_input(KeyCode::ShiftLeft);
+    app.update();
+
+    // The cooldown counts down using the tick clock
+    app.advance_time(Duration::from_millis(400));
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    let (action_state, cooldowns) = player_state(&mut app);
+    assert_eq!(
+        cooldowns.remaining(&Action::Dash),
+        Duration::from_millis(600)
+    );
+    assert!(!action_state.pressed(&Action::Dash));
+    assert!(action_state.blocked_by_cooldown(&Action::Dash));
+
+    // Actions without a cooldown are unaffected
+    app.send_input(KeyCode::Space);
+    app.update();
+    let (action_state, _) = player_state(&mut app);
+    assert!(action_state.just_pressed(&Action::Jump));
+    assert!(!action_state.blocked_by_cooldown(&Action::Dash));
+
+    // The blocked press stays swallowed while held, even once the cooldown is over
+    app.advance_time(Duration::from_millis(600));
+    app.update();
+    let (action_state, cooldowns) = player_state(&mut app);
+    assert!(cooldowns.ready(&Action::Dash));
+    assert!(!action_state.pressed(&Action::Dash));
+
+    // Pressing it again works
+    app.release_input(KeyCode::ShiftLeft);
+    app.update();
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    let (action_state, cooldowns) = player_state(&mut app);
+    assert!(action_state.just_pressed(&Action::Dash));
+    assert_eq!(cooldowns.remaining(&Action::Dash), Duration::from_secs(1));
+}
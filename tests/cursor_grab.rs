@@ -0,0 +1,101 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, PrimaryWindow};
+use leafwing_input_manager::input_map::CursorGrabCondition;
+use leafwing_input_manager::input_mocking::MockInput;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Look,
+    Point,
+    Shoot,
+}
+
+fn test_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .init_resource::<ActionState<Action>>();
+
+    let mut input_map = InputMap::new([(Action::Shoot, MouseButton::Left)]);
+    input_map
+        .only_when_cursor_locked(Action::Look, DualAxis::mouse_motion())
+        .only_when_cursor_free(Action::Point, DualAxis::mouse_motion());
+    app.insert_resource(input_map);
+
+    let window = app.world.spawn((Window::default(), PrimaryWindow)).id();
+    app.update();
+    (app, window)
+}
+
+fn set_grab_mode(app: &mut App, window: Entity, grab_mode: CursorGrabMode) {
+    app.world
+        .get_mut::<Window>(window)
+        .unwrap()
+        .cursor
+        .grab_mode = grab_mode;
+}
+
+fn pressed(app: &App, action: Action) -> bool {
+    app.world.resource::<ActionState<Action>>().pressed(&action)
+}
+
+#[test]
+fn mouse_motion_points_while_the_cursor_is_free() {
+    let (mut app, _) = test_app();
+
+    app.send_mouse_motion(Vec2::new(5.0, 0.0));
+    app.update();
+    assert!(pressed(&app, Action::Point));
+    assert!(!pressed(&app, Action::Look));
+}
+
+#[test]
+fn mouse_motion_looks_around_while_the_cursor_is_grabbed() {
+    let (mut app, window) = test_app();
+
+    for grab_mode in [CursorGrabMode::Locked, CursorGrabMode::Confined] {
+        set_grab_mode(&mut app, window, grab_mode);
+        app.send_mouse_motion(Vec2::new(5.0, 0.0));
+        app.update();
+        assert!(pressed(&app, Action::Look));
+        assert!(!pressed(&app, Action::Point));
+    }
+
+    // Releasing the cursor, such as to open a menu, points again
+    set_grab_mode(&mut app, window, CursorGrabMode::None);
+    app.send_mouse_motion(Vec2::new(5.0, 0.0));
+    app.update();
+    assert!(!pressed(&app, Action::Look));
+    assert!(pressed(&app, Action::Point));
+}
+
+#[test]
+fn unconditioned_bindings_ignore_the_cursor() {
+    let (mut app, window) = test_app();
+
+    app.send_input(MouseButton::Left);
+    app.update();
+    assert!(pressed(&app, Action::Shoot));
+
+    set_grab_mode(&mut app, window, CursorGrabMode::Locked);
+    app.update();
+    assert!(pressed(&app, Action::Shoot));
+}
+
+#[test]
+fn conditions_are_forgotten_with_their_bindings() {
+    let mut input_map = InputMap::<Action>::default();
+    input_map.only_when_cursor_locked(Action::Look, DualAxis::mouse_motion());
+    input_map.remove(&Action::Look, DualAxis::mouse_motion());
+
+    input_map.insert(Action::Look, DualAxis::mouse_motion());
+    assert_eq!(
+        input_map.cursor_grab_condition(&Action::Look, &DualAxis::mouse_motion().into()),
+        CursorGrabCondition::Always
+    );
+}
@@ -0,0 +1,85 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::axislike::DualAxisData;
+use leafwing_input_manager::cursor_world::{CursorRelative, CursorWorldSource};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Aim,
+}
+
+fn test_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ));
+
+    let player = app
+        .world
+        .spawn((
+            InputManagerBundle::with_map(InputMap::new([(
+                Action::Aim,
+                CursorRelative::new(100.0),
+            )])),
+            GlobalTransform::from_xyz(50.0, 50.0, 0.0),
+        ))
+        .id();
+    app.update();
+    (app, player)
+}
+
+fn aim(app: &App, player: Entity) -> Option<DualAxisData> {
+    app.world
+        .get::<ActionState<Action>>(player)
+        .unwrap()
+        .axis_pair(&Action::Aim)
+}
+
+#[test]
+fn aims_from_the_entity_towards_the_cursor() {
+    let (mut app, player) = test_app();
+
+    // Half of the maximum distance
+    app.insert_resource(CursorWorldSource(Some(Vec2::new(50.0, 100.0))));
+    app.update();
+    assert!(app
+        .world
+        .get::<ActionState<Action>>(player)
+        .unwrap()
+        .pressed(&Action::Aim));
+    assert_eq!(aim(&app, player), Some(DualAxisData::new(0.0, 0.5)));
+
+    // Further than the maximum distance
+    app.insert_resource(CursorWorldSource(Some(Vec2::new(-250.0, 50.0))));
+    app.update();
+    assert_eq!(aim(&app, player), Some(DualAxisData::new(-1.0, 0.0)));
+
+    // Following the entity
+    app.world
+        .entity_mut(player)
+        .insert(GlobalTransform::from_xyz(-250.0, 0.0, 0.0));
+    app.update();
+    assert_eq!(aim(&app, player), Some(DualAxisData::new(0.0, 0.5)));
+}
+
+#[test]
+fn reads_nothing_without_a_cursor_or_a_transform() {
+    let (mut app, player) = test_app();
+
+    // The cursor is outside of the window
+    app.update();
+    assert_eq!(aim(&app, player), None);
+    assert!(!app
+        .world
+        .get::<ActionState<Action>>(player)
+        .unwrap()
+        .pressed(&Action::Aim));
+
+    app.insert_resource(CursorWorldSource(Some(Vec2::new(50.0, 100.0))));
+    app.world.entity_mut(player).remove::<GlobalTransform>();
+    app.update();
+    assert_eq!(aim(&app, player), None);
+}
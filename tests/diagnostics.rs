@@ -0,0 +1,84 @@
+use bevy::diagnostic::{DiagnosticId, DiagnosticsStore};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::diagnostics::InputManagerDiagnosticsPlugin;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Run,
+    Jump,
+}
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum MenuAction {
+    Select,
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+        InputManagerDiagnosticsPlugin::<Action>::default(),
+    ));
+    app.world.spawn(InputManagerBundle {
+        input_map: InputMap::new([
+            (Action::Run, KeyCode::ShiftLeft),
+            (Action::Jump, KeyCode::Space),
+        ]),
+        ..default()
+    });
+    app.insert_resource(InputMap::new([(Action::Jump, KeyCode::Space)]))
+        .init_resource::<ActionState<Action>>();
+    app
+}
+
+fn measurement(app: &App, id: DiagnosticId) -> f64 {
+    app.world
+        .resource::<DiagnosticsStore>()
+        .get(id)
+        .and_then(|diagnostic| diagnostic.value())
+        .unwrap()
+}
+
+#[test]
+fn pressed_actions_are_counted() {
+    let mut app = test_app();
+    app.send_input(KeyCode::Space);
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+
+    // Jump is pressed on both the entity and the resource
+    let pressed = InputManagerDiagnosticsPlugin::<Action>::pressed_actions();
+    let just_pressed = InputManagerDiagnosticsPlugin::<Action>::just_pressed_actions();
+    assert_eq!(measurement(&app, pressed), 3.0);
+    assert_eq!(measurement(&app, just_pressed), 3.0);
+
+    app.release_input(KeyCode::ShiftLeft);
+    app.update();
+    assert_eq!(measurement(&app, pressed), 2.0);
+    assert_eq!(measurement(&app, just_pressed), 0.0);
+}
+
+#[test]
+fn evaluation_time_is_measured() {
+    let mut app = test_app();
+    app.update();
+
+    let evaluation_time = InputManagerDiagnosticsPlugin::<Action>::evaluation_time();
+    assert!(measurement(&app, evaluation_time) >= 0.0);
+}
+
+#[test]
+fn action_types_have_distinct_diagnostics() {
+    assert_ne!(
+        InputManagerDiagnosticsPlugin::<Action>::pressed_actions(),
+        InputManagerDiagnosticsPlugin::<MenuAction>::pressed_actions()
+    );
+    assert_ne!(
+        InputManagerDiagnosticsPlugin::<Action>::pressed_actions(),
+        InputManagerDiagnosticsPlugin::<Action>::just_pressed_actions()
+    );
+}
@@ -0,0 +1,108 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::plugin::{EguiWantsInput, InputManagerSystem};
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::systems::update_egui_wants_input;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Forward,
+    Shoot,
+}
+
+/// Stands in for egui, which would otherwise need a window to run in
+#[derive(Resource, Default)]
+struct FakeEgui(EguiWantsInput);
+
+fn fake_egui(fake_egui: Res<FakeEgui>, mut egui_wants_input: ResMut<EguiWantsInput>) {
+    *egui_wants_input = fake_egui.0;
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .init_resource::<FakeEgui>()
+    .add_systems(
+        PreUpdate,
+        fake_egui
+            .after(update_egui_wants_input)
+            .before(InputManagerSystem::Update),
+    )
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (Action::Forward, UserInput::from(KeyCode::W)),
+        (Action::Shoot, MouseButton::Left.into()),
+    ]));
+    app.update();
+    app
+}
+
+fn action_state(app: &App) -> &ActionState<Action> {
+    app.world.resource::<ActionState<Action>>()
+}
+
+#[test]
+fn keyboard_is_handed_to_egui_and_back() {
+    let mut app = test_app();
+    app.send_input(KeyCode::W);
+    app.send_input(MouseButton::Left);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Forward));
+
+    // A text field gains focus while W is held
+    app.world.resource_mut::<FakeEgui>().0.keyboard = true;
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Forward));
+    // The pointer is not affected
+    assert!(action_state(&app).pressed(&Action::Shoot));
+
+    app.update();
+    assert!(action_state(&app).released(&Action::Forward));
+    assert!(!action_state(&app).just_released(&Action::Forward));
+
+    // Once the text field loses focus, the held key moves the player again
+    app.world.resource_mut::<FakeEgui>().0.keyboard = false;
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Forward));
+}
+
+#[test]
+fn pointer_is_handed_to_egui_and_back() {
+    let mut app = test_app();
+    app.send_input(KeyCode::W);
+    app.send_input(MouseButton::Left);
+    app.update();
+
+    app.world.resource_mut::<FakeEgui>().0.pointer = true;
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Shoot));
+    assert!(action_state(&app).pressed(&Action::Forward));
+
+    app.world.resource_mut::<FakeEgui>().0.pointer = false;
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Shoot));
+}
+
+#[test]
+fn no_egui_context_wants_nothing() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ));
+    app.insert_resource(EguiWantsInput {
+        keyboard: true,
+        pointer: true,
+    });
+    app.update();
+
+    assert_eq!(
+        *app.world.resource::<EguiWantsInput>(),
+        EguiWantsInput::default()
+    );
+}
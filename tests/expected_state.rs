@@ -0,0 +1,83 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::test_utils::ExpectedState;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+    Run,
+    Move,
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>();
+
+    let mut input_map = InputMap::new([
+        (Action::Jump, KeyCode::Space),
+        (Action::Run, KeyCode::ShiftLeft),
+    ]);
+    input_map.insert(Action::Move, VirtualDPad::arrow_keys());
+    app.insert_resource(input_map);
+
+    app
+}
+
+#[test]
+fn action_state_matches_expected_snapshot() {
+    let mut app = test_app();
+
+    app.send_input(KeyCode::Space);
+    app.send_input(KeyCode::Right);
+    app.update();
+
+    ExpectedState::new()
+        .just_pressed(Action::Jump)
+        .value(Action::Jump, 1.0)
+        .released(Action::Run)
+        .just_pressed(Action::Move)
+        .axis_pair(Action::Move, Vec2::X)
+        .assert_matches(app.world.resource::<ActionState<Action>>());
+
+    app.world
+        .resource_mut::<ActionState<Action>>()
+        .consume(&Action::Jump);
+    app.update();
+
+    ExpectedState::new()
+        .released(Action::Jump)
+        .consumed(Action::Jump, true)
+        .pressed(Action::Move)
+        .axis_pair(Action::Move, Vec2::X)
+        .assert_matches(app.world.resource::<ActionState<Action>>());
+
+    app.release_input(KeyCode::Space);
+    app.release_input(KeyCode::Right);
+    app.update();
+
+    ExpectedState::new()
+        .released(Action::Jump)
+        .consumed(Action::Jump, false)
+        .just_released(Action::Move)
+        .axis_pair(Action::Move, Vec2::ZERO)
+        .assert_matches(app.world.resource::<ActionState<Action>>());
+}
+
+#[test]
+#[should_panic(expected = "3 mismatches")]
+fn mismatched_snapshot_lists_every_field() {
+    let mut app = test_app();
+
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+
+    ExpectedState::new()
+        .just_pressed(Action::Jump)
+        .value(Action::Run, 0.0)
+        .released(Action::Run)
+        .assert_matches(app.world.resource::<ActionState<Action>>());
+}
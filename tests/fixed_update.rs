@@ -0,0 +1,117 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Duration;
+use leafwing_input_manager::plugin::InputManagerSystem;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+}
+
+/// The number of times each edge was seen in `FixedUpdate`
+#[derive(Resource, Default, Debug, PartialEq)]
+struct EdgeCounts {
+    just_pressed: usize,
+    just_released: usize,
+    fixed_steps: usize,
+}
+
+fn count_edges(
+    action_state: Option<Res<FixedActionState<Action>>>,
+    mut edge_counts: ResMut<EdgeCounts>,
+) {
+    let Some(action_state) = action_state else {
+        return;
+    };
+
+    edge_counts.fixed_steps += 1;
+    if action_state.just_pressed(&Action::Jump) {
+        edge_counts.just_pressed += 1;
+    }
+    if action_state.just_released(&Action::Jump) {
+        edge_counts.just_released += 1;
+    }
+}
+
+fn create_app(frame_time: Duration, fixed_timestep: Duration) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default().use_fixed_action_state(),
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(frame_time))
+    .insert_resource(Time::<Fixed>::from_duration(fixed_timestep))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::<Action>::new([(Action::Jump, KeyCode::Space)]))
+    .init_resource::<EdgeCounts>()
+    .add_systems(
+        FixedUpdate,
+        count_edges.after(InputManagerSystem::FixedTick),
+    );
+
+    app.update();
+    app
+}
+
+/// Presses the action, holds it for `held_frames`, then releases it and waits for `released_frames`
+fn press_and_release(app: &mut App, held_frames: usize, released_frames: usize) {
+    app.send_input(KeyCode::Space);
+    for _ in 0..held_frames {
+        app.update();
+    }
+    app.release_input(KeyCode::Space);
+    for _ in 0..released_frames {
+        app.update();
+    }
+}
+
+#[test]
+fn several_frames_per_fixed_step() {
+    let mut app = create_app(Duration::from_millis(10), Duration::from_millis(25));
+    press_and_release(&mut app, 10, 10);
+
+    let edge_counts = app.world.resource::<EdgeCounts>();
+    assert!(edge_counts.fixed_steps < 20, "{edge_counts:?}");
+    assert_eq!(edge_counts.just_pressed, 1);
+    assert_eq!(edge_counts.just_released, 1);
+}
+
+#[test]
+fn several_fixed_steps_per_frame() {
+    let mut app = create_app(Duration::from_millis(50), Duration::from_millis(10));
+    press_and_release(&mut app, 3, 3);
+
+    let edge_counts = app.world.resource::<EdgeCounts>();
+    assert!(edge_counts.fixed_steps > 6, "{edge_counts:?}");
+    assert_eq!(edge_counts.just_pressed, 1);
+    assert_eq!(edge_counts.just_released, 1);
+}
+
+#[test]
+fn taps_between_fixed_steps() {
+    let mut app = create_app(Duration::from_millis(10), Duration::from_millis(100));
+    press_and_release(&mut app, 1, 30);
+
+    let edge_counts = app.world.resource::<EdgeCounts>();
+    assert_eq!(edge_counts.just_pressed, 1);
+    assert_eq!(edge_counts.just_released, 1);
+
+    // Once both edges have been observed, the fixed state catches up
+    let action_state = app.world.resource::<FixedActionState<Action>>();
+    assert!(action_state.released(&Action::Jump));
+}
+
+#[test]
+fn fixed_action_state_follows_action_state() {
+    let mut app = create_app(Duration::from_millis(10), Duration::from_millis(10));
+    app.send_input(KeyCode::Space);
+    app.update();
+    app.update();
+
+    let action_state = app.world.resource::<FixedActionState<Action>>();
+    assert!(action_state.pressed(&Action::Jump));
+    assert_eq!(action_state.value(&Action::Jump), 1.0);
+}
@@ -0,0 +1,247 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::gamepad_assignment::{
+    AwaitingGamepad, GamepadAssigned, GamepadAssignmentPlugin, GamepadAssignments,
+    PlayerGamepadLost,
+};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+}
+
+const PAD_1: Gamepad = Gamepad { id: 0 };
+const PAD_2: Gamepad = Gamepad { id: 1 };
+
+fn gamepad_player() -> InputManagerBundle<Action> {
+    InputManagerBundle {
+        input_map: InputMap::new([(Action::Jump, GamepadButtonType::South)]),
+        ..default()
+    }
+}
+
+fn spawn_player(commands: &mut Commands, _gamepad: Gamepad) -> Entity {
+    commands.spawn(gamepad_player()).id()
+}
+
+fn test_app(plugin: GamepadAssignmentPlugin<Action>) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+        plugin,
+    ));
+    app.connect_gamepad(PAD_1);
+    app.connect_gamepad(PAD_2);
+    app.update();
+    app
+}
+
+fn join(app: &mut App, gamepad: Gamepad) {
+    app.send_gamepad_input(gamepad, GamepadButtonType::Start);
+    app.update();
+    app.release_input_as_gamepad(GamepadButtonType::Start, Some(gamepad));
+    app.update();
+}
+
+fn assigned_events(app: &App) -> Vec<GamepadAssigned> {
+    let events = app.world.resource::<Events<GamepadAssigned>>();
+    events.get_reader().read(events).copied().collect()
+}
+
+#[test]
+fn awaiting_players_are_claimed_in_order() {
+    let mut app = test_app(GamepadAssignmentPlugin::default());
+    let first = app
+        .world
+        .spawn((gamepad_player(), AwaitingGamepad::<Action>::default()))
+        .id();
+    let second = app
+        .world
+        .spawn((gamepad_player(), AwaitingGamepad::<Action>::default()))
+        .id();
+
+    join(&mut app, PAD_2);
+    let assignments = app.world.resource::<GamepadAssignments<Action>>();
+    assert_eq!(assignments.entity(PAD_2), Some(first));
+    assert_eq!(assignments.gamepad(second), None);
+    assert_eq!(
+        app.world.get::<InputMap<Action>>(first).unwrap().gamepad(),
+        Some(PAD_2)
+    );
+    assert!(app.world.get::<AwaitingGamepad<Action>>(first).is_none());
+    assert_eq!(
+        assigned_events(&app),
+        [GamepadAssigned {
+            entity: first,
+            gamepad: PAD_2
+        }]
+    );
+
+    // Joining again with an assigned gamepad does nothing
+    join(&mut app, PAD_2);
+    let assignments = app.world.resource::<GamepadAssignments<Action>>();
+    assert_eq!(assignments.gamepad(second), None);
+
+    join(&mut app, PAD_1);
+    let assignments = app.world.resource::<GamepadAssignments<Action>>();
+    assert_eq!(assignments.entity(PAD_1), Some(second));
+
+    // Each player only reads their own gamepad
+    app.send_gamepad_input(PAD_1, GamepadButtonType::South);
+    app.update();
+    let pressed = |entity| {
+        app.world
+            .get::<ActionState<Action>>(entity)
+            .unwrap()
+            .pressed(&Action::Jump)
+    };
+    assert!(!pressed(first));
+    assert!(pressed(second));
+}
+
+#[test]
+fn spawner_creates_players() {
+    let mut app = test_app(
+        GamepadAssignmentPlugin::default()
+            .with_join_button(GamepadButtonType::South)
+            .with_spawner(spawn_player),
+    );
+
+    app.send_gamepad_input(PAD_1, GamepadButtonType::Start);
+    app.update();
+    assert!(app
+        .world
+        .resource::<GamepadAssignments<Action>>()
+        .entity(PAD_1)
+        .is_none());
+
+    app.send_gamepad_input(PAD_1, GamepadButtonType::South);
+    app.update();
+    app.update();
+    let player = app
+        .world
+        .resource::<GamepadAssignments<Action>>()
+        .entity(PAD_1)
+        .unwrap();
+    assert_eq!(
+        app.world.get::<InputMap<Action>>(player).unwrap().gamepad(),
+        Some(PAD_1)
+    );
+}
+
+#[test]
+fn disconnecting_frees_the_gamepad() {
+    let mut app = test_app(GamepadAssignmentPlugin::default());
+    let player = app
+        .world
+        .spawn((gamepad_player(), AwaitingGamepad::<Action>::default()))
+        .id();
+    join(&mut app, PAD_1);
+
+    app.disconnect_gamepad(PAD_1);
+    app.update();
+    let assignments = app.world.resource::<GamepadAssignments<Action>>();
+    assert_eq!(assignments.entity(PAD_1), None);
+    assert!(app.world.get::<AwaitingGamepad<Action>>(player).is_some());
+    let events = app.world.resource::<Events<PlayerGamepadLost>>();
+    let lost: Vec<_> = events.get_reader().read(events).copied().collect();
+    assert_eq!(
+        lost,
+        [PlayerGamepadLost {
+            entity: player,
+            gamepad: PAD_1
+        }]
+    );
+
+    // The player does not start reading other gamepads
+    app.send_gamepad_input(PAD_2, GamepadButtonType::South);
+    app.update();
+    assert!(app
+        .world
+        .get::<ActionState<Action>>(player)
+        .unwrap()
+        .released(&Action::Jump));
+
+    // But can be claimed again
+    join(&mut app, PAD_2);
+    let assignments = app.world.resource::<GamepadAssignments<Action>>();
+    assert_eq!(assignments.entity(PAD_2), Some(player));
+}
+
+#[test]
+fn keyboard_player_is_unaffected() {
+    let mut app = test_app(GamepadAssignmentPlugin::default().with_spawner(spawn_player));
+    let keyboard_player = app
+        .world
+        .spawn(InputManagerBundle::<Action> {
+            input_map: InputMap::new([(Action::Jump, KeyCode::Space)]),
+            ..default()
+        })
+        .id();
+
+    join(&mut app, PAD_1);
+    let assignments = app.world.resource::<GamepadAssignments<Action>>();
+    assert_ne!(assignments.entity(PAD_1), Some(keyboard_player));
+    assert_eq!(assignments.gamepad(keyboard_player), None);
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    assert!(app
+        .world
+        .get::<ActionState<Action>>(keyboard_player)
+        .unwrap()
+        .pressed(&Action::Jump));
+}
+
+#[test]
+fn template_is_cloned_for_each_gamepad() {
+    fn spawn_empty_player(commands: &mut Commands, _gamepad: Gamepad) -> Entity {
+        commands.spawn(ActionState::<Action>::default()).id()
+    }
+
+    let mut template = InputMap::default();
+    template
+        .insert_chord(
+            Action::Jump,
+            [GamepadButtonType::LeftTrigger, GamepadButtonType::South],
+        )
+        .insert(Action::Jump, KeyCode::Space)
+        .set_gamepad(PAD_1);
+    let mut app = test_app(
+        GamepadAssignmentPlugin::default()
+            .with_spawner(spawn_empty_player)
+            .with_template(template),
+    );
+
+    join(&mut app, PAD_2);
+    let player = app
+        .world
+        .resource::<GamepadAssignments<Action>>()
+        .entity(PAD_2)
+        .unwrap();
+    let input_map = app.world.get::<InputMap<Action>>(player).unwrap();
+    assert_eq!(input_map.gamepad(), Some(PAD_2));
+    assert_eq!(
+        input_map.get(&Action::Jump),
+        Some(&vec![UserInput::chord([
+            GamepadButtonType::LeftTrigger,
+            GamepadButtonType::South
+        ])])
+    );
+
+    // The player's chord reads their own gamepad, but not the gamepad of the template
+    app.send_gamepad_input(PAD_1, GamepadButtonType::LeftTrigger);
+    app.send_gamepad_input(PAD_1, GamepadButtonType::South);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.released(&Action::Jump));
+
+    app.send_gamepad_input(PAD_2, GamepadButtonType::LeftTrigger);
+    app.send_gamepad_input(PAD_2, GamepadButtonType::South);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.pressed(&Action::Jump));
+}
@@ -1,10 +1,18 @@
 use bevy::input::gamepad::{
-    GamepadAxisChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+    ButtonSettings, GamepadAxisChangedEvent, GamepadEvent, GamepadSettings,
 };
 use bevy::input::InputPlugin;
 use bevy::prelude::*;
+use bevy::utils::HashSet;
 use leafwing_input_manager::axislike::{AxisType, DeadZoneShape, DualAxisData};
+use leafwing_input_manager::buttonlike::ButtonState;
+use leafwing_input_manager::gamepad_aliases::GamepadInputAliases;
+use leafwing_input_manager::gamepad_kind::GamepadKind;
+use leafwing_input_manager::gamepad_settings::{GamepadPreferences, GamepadSettingsOverride};
+use leafwing_input_manager::input_mocking::Stick;
+use leafwing_input_manager::plugin::AssociatedGamepadDisconnected;
 use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::user_input::InputKind;
 
 #[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
 enum ButtonlikeTestAction {
@@ -31,14 +39,8 @@ fn test_app() -> App {
         .init_resource::<ActionState<AxislikeTestAction>>();
 
     // WARNING: you MUST register your gamepad during tests, or all gamepad input mocking will fail
-    let mut gamepad_events = app.world.resource_mut::<Events<GamepadEvent>>();
-    gamepad_events.send(GamepadEvent::Connection(GamepadConnectionEvent {
-        // This MUST be consistent with any other mocked events
-        gamepad: Gamepad { id: 1 },
-        connection: GamepadConnection::Connected(GamepadInfo {
-            name: "TestController".into(),
-        }),
-    }));
+    // This MUST be consistent with any other mocked events
+    app.connect_gamepad(Gamepad { id: 1 });
 
     // Ensure that the gamepad is picked up by the appropriate system
     app.update();
@@ -487,7 +489,6 @@ fn test_zero_ellipse() {
 }
 
 #[test]
-#[ignore = "Input mocking is subtly broken: https://github.com/Leafwing-Studios/leafwing-input-manager/issues/407"]
 fn game_pad_virtualdpad() {
     let mut app = test_app();
     app.insert_resource(InputMap::new([(
@@ -509,3 +510,701 @@ fn game_pad_virtualdpad() {
         DualAxisData::new(-1.0, 0.0)
     );
 }
+
+#[test]
+fn game_pad_stick_mocking() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::XY,
+        DualAxis::left_stick().with_deadzone(DeadZoneShape::Cross {
+            horizontal_width: 0.0,
+            vertical_width: 0.0,
+        }),
+    )]));
+
+    for value in [
+        Vec2::new(0.25, 0.0),
+        Vec2::new(0.5, -0.5),
+        Vec2::new(-0.75, 1.0),
+    ] {
+        app.send_stick(Stick::Left, value);
+        app.update();
+        let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+        assert!(action_state.pressed(&AxislikeTestAction::XY));
+        assert_eq!(
+            action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+            DualAxisData::from_xy(value)
+        );
+
+        // The stick stays where it was left
+        app.update();
+        let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+        assert_eq!(
+            action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+            DualAxisData::from_xy(value)
+        );
+    }
+
+    // Single axes can be moved on their own
+    app.send_axis_values(GamepadAxisType::LeftStickY, 0.0);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(-0.75, 0.0)
+    );
+
+    // Moving the other stick leaves this one alone
+    app.send_dual_axis(DualAxis::right_stick(), Vec2::new(1.0, 1.0));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(-0.75, 0.0)
+    );
+
+    app.reset_inputs();
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(&AxislikeTestAction::XY));
+}
+
+#[test]
+fn game_pad_disconnect_releases_actions() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<ButtonlikeTestAction>::default())
+        .init_resource::<ActionState<ButtonlikeTestAction>>()
+        .insert_resource(InputMap::new([(
+            ButtonlikeTestAction::Up,
+            GamepadButtonType::DPadUp,
+        )]));
+
+    let gamepad = Gamepad { id: 0 };
+    app.connect_gamepad(gamepad);
+    app.update();
+    assert!(app.world.resource::<Gamepads>().contains(gamepad));
+
+    app.send_input(GamepadButtonType::DPadUp);
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(&ButtonlikeTestAction::Up));
+
+    // The button is still held on the next frame
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(&ButtonlikeTestAction::Up));
+
+    app.disconnect_gamepad(gamepad);
+    app.update();
+    assert!(!app.world.resource::<Gamepads>().contains(gamepad));
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(&ButtonlikeTestAction::Up));
+}
+
+#[test]
+fn game_pad_disconnect_mid_hold() {
+    let mut app = test_app();
+    let gamepad = Gamepad { id: 1 };
+    let mut input_map = InputMap::new([
+        (
+            ButtonlikeTestAction::Up,
+            UserInput::from(GamepadButtonType::South),
+        ),
+        (
+            ButtonlikeTestAction::Down,
+            UserInput::from(GamepadButtonType::East),
+        ),
+        (ButtonlikeTestAction::Down, UserInput::from(KeyCode::S)),
+    ]);
+    input_map.set_gamepad(gamepad);
+    let entity = app
+        .world
+        .spawn(InputManagerBundle::with_map(input_map))
+        .id();
+    // Keyboard players don't read any gamepad, so they are not notified
+    app.world
+        .spawn(InputManagerBundle::with_map(InputMap::new([(
+            ButtonlikeTestAction::Up,
+            KeyCode::W,
+        )])));
+
+    app.send_input(GamepadButtonType::South);
+    app.send_input(GamepadButtonType::East);
+    app.send_input(KeyCode::S);
+    app.update();
+    app.update();
+
+    let action_state = app
+        .world
+        .get::<ActionState<ButtonlikeTestAction>>(entity)
+        .unwrap();
+    assert!(action_state.pressed(&ButtonlikeTestAction::Up));
+    assert!(!action_state.just_pressed(&ButtonlikeTestAction::Up));
+
+    app.disconnect_gamepad(gamepad);
+    app.update();
+
+    let action_state = app
+        .world
+        .get::<ActionState<ButtonlikeTestAction>>(entity)
+        .unwrap();
+    assert!(action_state.just_released(&ButtonlikeTestAction::Up));
+    // Still held with the keyboard
+    assert!(action_state.pressed(&ButtonlikeTestAction::Down));
+
+    let events: Vec<_> = app
+        .world
+        .resource_mut::<Events<AssociatedGamepadDisconnected>>()
+        .drain()
+        .collect();
+    assert_eq!(
+        events,
+        [AssociatedGamepadDisconnected {
+            entity: Some(entity),
+            gamepad,
+        }]
+    );
+}
+
+#[test]
+fn stick_click_with_direction_chord() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([
+        // Sprint
+        (
+            ButtonlikeTestAction::Up,
+            UserInput::chord([
+                InputKind::from(GamepadButtonType::LeftThumb),
+                SingleAxis::left_stick_y().above(0.5).into(),
+            ]),
+        ),
+        // Crouch
+        (
+            ButtonlikeTestAction::Down,
+            UserInput::from(GamepadButtonType::LeftThumb),
+        ),
+        // Walk
+        (
+            ButtonlikeTestAction::Left,
+            UserInput::from(SingleAxis::left_stick_y().above(0.1)),
+        ),
+    ]));
+
+    // Click without direction
+    app.send_input(GamepadButtonType::LeftThumb);
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(!action_state.pressed(&ButtonlikeTestAction::Up));
+    assert!(action_state.pressed(&ButtonlikeTestAction::Down));
+
+    // Direction without click
+    app.release_input(GamepadButtonType::LeftThumb);
+    app.send_stick(Stick::Left, Vec2::new(0.0, 0.75));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(!action_state.pressed(&ButtonlikeTestAction::Up));
+    assert!(action_state.pressed(&ButtonlikeTestAction::Left));
+
+    // Both: the chord wins the clashes with crouching and walking, and its value comes from the stick
+    app.send_input(GamepadButtonType::LeftThumb);
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(&ButtonlikeTestAction::Up));
+    assert!(!action_state.pressed(&ButtonlikeTestAction::Down));
+    assert!(!action_state.pressed(&ButtonlikeTestAction::Left));
+    assert_eq!(action_state.value(&ButtonlikeTestAction::Up), 0.5);
+
+    // Pulling the stick back below the threshold releases the chord
+    app.send_stick(Stick::Left, Vec2::new(0.0, 0.25));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(!action_state.pressed(&ButtonlikeTestAction::Up));
+    assert!(action_state.pressed(&ButtonlikeTestAction::Down));
+}
+
+#[test]
+fn gamepad_settings_override() {
+    let mut app = test_app();
+    let gamepad = Gamepad { id: 1 };
+    app.insert_resource(InputMap::new([
+        (
+            ButtonlikeTestAction::Up,
+            SingleAxis::left_stick_y().above(0.1),
+        ),
+        (
+            ButtonlikeTestAction::Down,
+            SingleAxis::left_stick_y().below(-0.1),
+        ),
+    ]));
+    let mut settings = GamepadSettingsOverride::default();
+    settings.default.stick_deadzone = 0.3;
+    app.insert_resource(settings);
+
+    // Inside the global deadzone, although outside the deadzone of the binding
+    app.send_stick(Stick::Left, Vec2::new(0.0, 0.25));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(&ButtonlikeTestAction::Up));
+
+    // The binding applies to the adjusted value: (0.4 - 0.3) / 0.7 is above 0.1
+    app.send_stick(Stick::Left, Vec2::new(0.0, 0.4));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(&ButtonlikeTestAction::Up));
+
+    // The entry of the gamepad replaces the default entry
+    app.world
+        .resource_mut::<GamepadSettingsOverride>()
+        .gamepads
+        .insert(
+            gamepad,
+            GamepadPreferences {
+                stick_deadzone: 0.1,
+                inverted_axes: HashSet::from_iter([GamepadAxisType::LeftStickY]),
+                ..Default::default()
+            },
+        );
+    app.send_stick(Stick::Left, Vec2::new(0.0, 0.25));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(&ButtonlikeTestAction::Up));
+    assert!(action_state.pressed(&ButtonlikeTestAction::Down));
+}
+
+#[test]
+fn gamepad_settings_trigger_threshold() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        ButtonlikeTestAction::Up,
+        GamepadButtonType::RightTrigger2,
+    )]));
+    let mut settings = GamepadSettingsOverride::default();
+    settings.default.trigger_threshold = Some(0.2);
+    app.insert_resource(settings);
+
+    let trigger = GamepadButton::new(Gamepad { id: 1 }, GamepadButtonType::RightTrigger2);
+    app.world
+        .resource_mut::<Axis<GamepadButton>>()
+        .set(trigger, 0.1);
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(&ButtonlikeTestAction::Up));
+
+    // Bevy only presses the trigger from 0.75 on
+    app.world
+        .resource_mut::<Axis<GamepadButton>>()
+        .set(trigger, 0.3);
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(&ButtonlikeTestAction::Up));
+}
+
+#[test]
+fn dpad_preset() {
+    let mut app = test_app();
+    let mut input_map = InputMap::new([(AxislikeTestAction::XY, DualAxis::dpad())]);
+    input_map.set_gamepad(Gamepad { id: 1 });
+    app.insert_resource(input_map);
+
+    app.send_input(GamepadButtonType::DPadUp);
+    app.send_input(GamepadButtonType::DPadRight);
+    app.update();
+
+    // Diagonals are normalized, so they don't move faster than a single direction
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    let diagonal = action_state.axis_pair(&AxislikeTestAction::XY).unwrap();
+    assert!((diagonal.length() - 1.0).abs() < 1e-6);
+    assert!((diagonal.x() - diagonal.y()).abs() < 1e-6);
+    assert!(diagonal.x() > 0.0);
+
+    app.release_input(GamepadButtonType::DPadUp);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(1.0, 0.0)
+    );
+
+    assert_eq!(UserInput::from(DualAxis::dpad()).to_string(), "D-Pad");
+}
+
+#[test]
+fn virtual_dpad_diagonals_are_not_normalized_by_default() {
+    let mut app = test_app();
+    let mut input_map = InputMap::new([(AxislikeTestAction::XY, VirtualDPad::dpad())]);
+    input_map.set_gamepad(Gamepad { id: 1 });
+    app.insert_resource(input_map);
+
+    app.send_input(GamepadButtonType::DPadUp);
+    app.send_input(GamepadButtonType::DPadRight);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(1.0, 1.0)
+    );
+}
+
+fn either_stick(policy: DualAxisPolicy) -> EitherDualAxis {
+    let no_deadzone = DeadZoneShape::Cross {
+        horizontal_width: 0.0,
+        vertical_width: 0.0,
+    };
+
+    EitherDualAxis::new(
+        DualAxis::left_stick().with_deadzone(no_deadzone),
+        DualAxis::right_stick().with_deadzone(no_deadzone),
+        policy,
+    )
+}
+
+#[test]
+fn either_stick_largest_magnitude() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::XY,
+        either_stick(DualAxisPolicy::LargestMagnitude),
+    )]));
+
+    app.send_stick(Stick::Left, Vec2::new(0.5, 0.0));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.just_pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.5, 0.0)
+    );
+
+    // The sticks are not added up: the one pushed the furthest wins
+    app.send_stick(Stick::Right, Vec2::new(0.0, -0.8));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, -0.8)
+    );
+    assert_eq!(action_state.value(&AxislikeTestAction::XY), 0.8);
+
+    // Letting go of the left stick mid-hold doesn't release the action
+    app.send_stick(Stick::Left, Vec2::ZERO);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    assert!(!action_state.just_pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, -0.8)
+    );
+
+    app.send_stick(Stick::Right, Vec2::ZERO);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.just_released(&AxislikeTestAction::XY));
+}
+
+#[test]
+fn either_stick_prefer_first_active() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::XY,
+        either_stick(DualAxisPolicy::PreferFirstActive),
+    )]));
+
+    app.send_stick(Stick::Right, Vec2::new(0.0, 1.0));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.just_pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, 1.0)
+    );
+
+    // The first stick takes over as soon as it is active, even when pushed less far
+    app.send_stick(Stick::Left, Vec2::new(0.3, 0.0));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    assert!(!action_state.just_pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.3, 0.0)
+    );
+
+    // And hands back to the second stick without a centered frame in between
+    app.send_stick(Stick::Left, Vec2::ZERO);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    assert!(!action_state.just_pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, 1.0)
+    );
+
+    app.send_stick(Stick::Right, Vec2::ZERO);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.just_released(&AxislikeTestAction::XY));
+}
+
+fn button_state(
+    action_state: &ActionState<ButtonlikeTestAction>,
+    action: ButtonlikeTestAction,
+) -> ButtonState {
+    action_state
+        .action_data(&action)
+        .map_or(ButtonState::Released, |action_data| action_data.state)
+}
+
+#[test]
+fn soft_and_full_trigger_pull() {
+    use ButtonState::*;
+
+    let mut app = test_app();
+    // Aim with the trigger pulled halfway, and fire with it pulled all the way
+    app.insert_resource(InputMap::new([
+        (ButtonlikeTestAction::Up, AnalogButton::left_trigger(0.3)),
+        (ButtonlikeTestAction::Down, AnalogButton::left_trigger(0.8)),
+    ]));
+
+    // The soft pull isn't suppressed by the full pull under the default `ClashStrategy::PrioritizeLongest`
+    for (value, soft, full) in [
+        (0.0, Released, Released),
+        (0.4, JustPressed, Released),
+        (0.9, Pressed, JustPressed),
+        (0.4, Pressed, JustReleased),
+        (0.0, JustReleased, Released),
+    ] {
+        app.send_button_value(GamepadButtonType::LeftTrigger2, value);
+        app.update();
+
+        let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+        assert_eq!(
+            button_state(action_state, ButtonlikeTestAction::Up),
+            soft,
+            "soft pull at {value}"
+        );
+        assert_eq!(
+            button_state(action_state, ButtonlikeTestAction::Down),
+            full,
+            "full pull at {value}"
+        );
+        if full.pressed() {
+            assert_eq!(action_state.value(&ButtonlikeTestAction::Down), value);
+        }
+        if soft.pressed() {
+            assert_eq!(action_state.value(&ButtonlikeTestAction::Up), value);
+        }
+    }
+}
+
+#[test]
+fn analog_button_hysteresis() {
+    use ButtonState::*;
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        ButtonlikeTestAction::Down,
+        AnalogButton::left_trigger(0.8).with_release_threshold(0.7),
+    )]));
+
+    // Hovering around either threshold doesn't flicker
+    for (value, expected) in [
+        (0.75, Released),
+        (0.8, JustPressed),
+        (0.75, Pressed),
+        (0.85, Pressed),
+        (0.71, Pressed),
+        (0.7, JustReleased),
+        (0.75, Released),
+        (0.8, JustPressed),
+    ] {
+        app.send_button_value(GamepadButtonType::LeftTrigger2, value);
+        app.update();
+
+        let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+        assert_eq!(
+            button_state(action_state, ButtonlikeTestAction::Down),
+            expected,
+            "trigger at {value}"
+        );
+    }
+}
+
+#[test]
+fn gamepad_button_analog_value() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        ButtonlikeTestAction::Up,
+        GamepadButtonType::RightTrigger2,
+    )]));
+    app.world
+        .resource_mut::<GamepadSettings>()
+        .button_settings
+        .insert(
+            GamepadButton::new(Gamepad { id: 1 }, GamepadButtonType::RightTrigger2),
+            ButtonSettings::new(0.5, 0.4).unwrap(),
+        );
+
+    // Below the press threshold, the value is reported while the action is released
+    app.send_button_value(GamepadButtonType::RightTrigger2, 0.37);
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(&ButtonlikeTestAction::Up));
+    assert_eq!(action_state.value(&ButtonlikeTestAction::Up), 0.37);
+
+    app.send_button_value(GamepadButtonType::RightTrigger2, 0.6);
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(&ButtonlikeTestAction::Up));
+    assert_eq!(action_state.value(&ButtonlikeTestAction::Up), 0.6);
+
+    app.send_button_value(GamepadButtonType::RightTrigger2, 0.0);
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(&ButtonlikeTestAction::Up));
+    assert_eq!(action_state.value(&ButtonlikeTestAction::Up), 0.0);
+}
+
+#[test]
+fn other_button_and_axis() {
+    let mut aliases = GamepadInputAliases::default();
+    aliases
+        .alias_button("TrimUp", GamepadButtonType::Other(21))
+        .alias_axis("Throttle", GamepadAxisType::Other(21));
+
+    // Bindings written in a configuration file keep their codes through parsing and serialization
+    let button = aliases.parse_button("GamepadButton(21)").unwrap();
+    let axis = aliases.parse_axis("Throttle").unwrap();
+    let bytes = bincode::serialize(&UserInput::from(button)).unwrap();
+    let input: UserInput = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(input, UserInput::from(GamepadButtonType::Other(21)));
+    assert_eq!(input.to_string(), "Button 21");
+    assert_eq!(aliases.name(&input, GamepadKind::Generic), "TrimUp");
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(ButtonlikeTestAction::Up, input)]));
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::X,
+        SingleAxis::symmetric(axis, 0.0),
+    )]));
+
+    app.send_input(GamepadButtonType::Other(21));
+    app.send_axis_values(axis, 0.8);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(&ButtonlikeTestAction::Up));
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::X));
+    assert_eq!(action_state.value(&AxislikeTestAction::X), 0.8);
+
+    app.release_input(GamepadButtonType::Other(21));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(&ButtonlikeTestAction::Up));
+}
+
+const GYRO_PITCH: GamepadAxisType = GamepadAxisType::Other(10);
+const GYRO_YAW: GamepadAxisType = GamepadAxisType::Other(11);
+const GYRO_ROLL: GamepadAxisType = GamepadAxisType::Other(12);
+
+fn send_gyro(app: &mut App, pitch: f32, yaw: f32, roll: f32) {
+    app.send_input(TripleAxis::from_value(
+        GYRO_PITCH,
+        GYRO_YAW,
+        GYRO_ROLL,
+        Vec3::new(pitch, yaw, roll),
+    ));
+}
+
+#[test]
+fn gyro_aiming() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::XY,
+        TripleAxis::gyro(GYRO_PITCH, GYRO_YAW, GYRO_ROLL)
+            .with_sensitivity(2.0, 1.0, 0.5)
+            .inverted_y(),
+    )]));
+
+    send_gyro(&mut app, 0.25, 0.5, -0.5);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state
+            .axis_triple(&AxislikeTestAction::XY)
+            .unwrap()
+            .xyz(),
+        Vec3::new(0.5, -0.5, -0.25)
+    );
+    assert_eq!(
+        action_state.value(&AxislikeTestAction::XY),
+        Vec3::new(0.5, -0.5, -0.25).length()
+    );
+
+    send_gyro(&mut app, 0.0, 0.0, 0.0);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state
+            .axis_triple(&AxislikeTestAction::XY)
+            .unwrap()
+            .xyz(),
+        Vec3::ZERO
+    );
+}
+
+#[test]
+fn gyro_gated_by_trigger() {
+    let mut app = test_app();
+    let gyro = TripleAxis::gyro(GYRO_PITCH, GYRO_YAW, GYRO_ROLL);
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::XY,
+        UserInput::chord([
+            InputKind::from(AnalogButton::left_trigger(0.2)),
+            InputKind::from(gyro),
+        ]),
+    )]));
+
+    // Without pulling the trigger, the gyro is ignored
+    send_gyro(&mut app, 0.3, 0.4, 0.0);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(&AxislikeTestAction::XY));
+    assert_eq!(action_state.axis_triple(&AxislikeTestAction::XY), None);
+
+    // A soft pull is enough to aim
+    app.send_button_value(GamepadButtonType::LeftTrigger2, 0.3);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state
+            .axis_triple(&AxislikeTestAction::XY)
+            .unwrap()
+            .xyz(),
+        Vec3::new(0.3, 0.4, 0.0)
+    );
+    assert_eq!(action_state.value(&AxislikeTestAction::XY), 0.5);
+
+    app.send_button_value(GamepadButtonType::LeftTrigger2, 0.0);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(&AxislikeTestAction::XY));
+    assert_eq!(action_state.axis_triple(&AxislikeTestAction::XY), None);
+}
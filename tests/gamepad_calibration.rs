@@ -0,0 +1,152 @@
+use bevy::ecs::event::ManualEventReader;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Duration;
+use leafwing_input_manager::gamepad_calibration::{
+    CalibrationComplete, CalibrationPhase, CalibrationSettings, FinishCalibration,
+    GamepadCalibrationPlugin, GamepadCalibrator, StartCalibration,
+};
+use leafwing_input_manager::gamepad_settings::{AxisCalibration, GamepadSettingsOverride};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Steer,
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+const PAD: Gamepad = Gamepad { id: 0 };
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+        GamepadCalibrationPlugin::default().with_settings(CalibrationSettings {
+            rest_duration: Duration::from_millis(500),
+            sample_duration: Duration::from_secs(3),
+            clipped_fraction: 0.05,
+        }),
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([(
+        Action::Steer,
+        SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.0),
+    )]));
+
+    app.connect_gamepad(PAD);
+    app.update();
+    app
+}
+
+fn phase(app: &App) -> Option<CalibrationPhase> {
+    app.world.resource::<GamepadCalibrator>().phase(PAD)
+}
+
+fn steer(app: &mut App, value: f32) -> f32 {
+    app.send_gamepad_axis(PAD, GamepadAxisType::LeftStickX, value);
+    app.update();
+    app.world
+        .resource::<ActionState<Action>>()
+        .value(&Action::Steer)
+}
+
+#[test]
+fn calibration_rescales_the_axes() {
+    let mut app = test_app();
+    let mut reader = ManualEventReader::<CalibrationComplete>::default();
+
+    app.world.send_event(StartCalibration { gamepad: PAD });
+    // The stick drifts to the right while at rest
+    while phase(&app) != Some(CalibrationPhase::Sampling) {
+        app.send_gamepad_axis(PAD, GamepadAxisType::LeftStickX, 0.25);
+        app.update();
+    }
+
+    // The stick doesn't reach its ends, and spikes once in each direction
+    let values = [1.0]
+        .into_iter()
+        .chain([0.75; 10])
+        .chain([-1.0])
+        .chain([-0.5; 10]);
+    for value in values {
+        assert_eq!(phase(&app), Some(CalibrationPhase::Sampling));
+        app.send_gamepad_axis(PAD, GamepadAxisType::LeftStickX, value);
+        app.update();
+    }
+    app.send_gamepad_axis(PAD, GamepadAxisType::LeftStickX, 0.25);
+    while phase(&app) != Some(CalibrationPhase::Done) {
+        app.update();
+    }
+
+    app.world.send_event(FinishCalibration { gamepad: PAD });
+    app.update();
+    assert_eq!(phase(&app), None);
+
+    let events = app.world.resource::<Events<CalibrationComplete>>();
+    let complete: Vec<&CalibrationComplete> = reader.read(events).collect();
+    assert_eq!(complete.len(), 1);
+    assert_eq!(complete[0].gamepad, PAD);
+    assert_eq!(complete[0].report.name, "TestController");
+    assert_eq!(complete[0].report.clipped_samples, 2);
+    let calibration = AxisCalibration {
+        min: -0.5,
+        center: 0.25,
+        max: 0.75,
+    };
+    assert_eq!(
+        complete[0].report.calibration.axes[&GamepadAxisType::LeftStickX],
+        calibration
+    );
+
+    let settings = app.world.resource::<GamepadSettingsOverride>();
+    assert_eq!(
+        settings.calibrations["TestController"].axes[&GamepadAxisType::LeftStickX],
+        calibration
+    );
+
+    assert_eq!(steer(&mut app, 0.25), 0.0);
+    assert_eq!(steer(&mut app, 0.5), 0.5);
+    assert_eq!(steer(&mut app, 0.75), 1.0);
+    assert_eq!(steer(&mut app, -0.125), -0.5);
+
+    // The calibration still applies once the gamepad is reconnected with another id
+    app.disconnect_gamepad(PAD);
+    app.update();
+    let reconnected = Gamepad { id: 3 };
+    app.connect_gamepad(reconnected);
+    app.update();
+
+    app.send_gamepad_axis(reconnected, GamepadAxisType::LeftStickX, 0.75);
+    app.update();
+    assert_eq!(
+        app.world
+            .resource::<ActionState<Action>>()
+            .value(&Action::Steer),
+        1.0
+    );
+}
+
+#[test]
+fn disconnected_gamepads_are_not_calibrated() {
+    let mut app = test_app();
+
+    app.world.send_event(StartCalibration { gamepad: PAD });
+    app.update();
+    assert_eq!(phase(&app), Some(CalibrationPhase::Resting));
+
+    app.disconnect_gamepad(PAD);
+    app.update();
+    app.update();
+    assert_eq!(phase(&app), None);
+
+    app.world.send_event(FinishCalibration { gamepad: PAD });
+    app.update();
+    assert!(app
+        .world
+        .get_resource::<GamepadSettingsOverride>()
+        .is_none());
+}
@@ -0,0 +1,119 @@
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::gamepad_extras::GamepadExtra;
+use leafwing_input_manager::gamepad_kind::GamepadKind;
+use leafwing_input_manager::input_capture::{InputCapture, InputCapturePlugin};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Map,
+    Dodge,
+}
+
+const DUALSENSE: Gamepad = Gamepad { id: 0 };
+const STEAM_DECK: Gamepad = Gamepad { id: 1 };
+const GENERIC: Gamepad = Gamepad { id: 2 };
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+        InputCapturePlugin,
+    ))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (Action::Map, GamepadExtra::TouchpadClick),
+        (Action::Dodge, GamepadExtra::BackLeft),
+    ]));
+
+    for (gamepad, name) in [
+        (DUALSENSE, "DualSense Wireless Controller"),
+        (STEAM_DECK, "Steam Deck"),
+        (GENERIC, "Logitech Dual Action"),
+    ] {
+        app.world
+            .send_event(GamepadEvent::Connection(GamepadConnectionEvent {
+                gamepad,
+                connection: GamepadConnection::Connected(GamepadInfo { name: name.into() }),
+            }));
+    }
+    app.update();
+    app
+}
+
+fn pressed(app: &App, action: Action) -> bool {
+    app.world.resource::<ActionState<Action>>().pressed(&action)
+}
+
+#[test]
+fn extra_buttons_are_read_from_their_code_on_each_kind_of_gamepad() {
+    let mut app = test_app();
+
+    // Unknown gamepads can't press extra buttons
+    app.send_gamepad_input(GENERIC, GamepadButtonType::Other(20));
+    app.send_gamepad_input(GENERIC, GamepadButtonType::Other(17));
+    app.update();
+    assert!(!pressed(&app, Action::Map));
+    assert!(!pressed(&app, Action::Dodge));
+
+    // The Steam Deck doesn't have a touchpad click at this code
+    app.send_gamepad_input(STEAM_DECK, GamepadButtonType::Other(20));
+    app.update();
+    assert!(!pressed(&app, Action::Map));
+
+    app.send_gamepad_input(DUALSENSE, GamepadButtonType::Other(20));
+    app.send_gamepad_input(STEAM_DECK, GamepadButtonType::Other(17));
+    app.update();
+    assert!(pressed(&app, Action::Map));
+    assert!(pressed(&app, Action::Dodge));
+
+    // The associated gamepad is the only one read
+    app.world
+        .resource_mut::<InputMap<Action>>()
+        .set_gamepad(DUALSENSE);
+    app.update();
+    assert!(pressed(&app, Action::Map));
+    assert!(!pressed(&app, Action::Dodge));
+}
+
+#[test]
+fn extra_buttons_are_named_for_their_gamepad() {
+    let input = UserInput::from(GamepadExtra::BackLeft);
+    assert_eq!(input.name(GamepadKind::SteamDeck), "L4");
+    assert_eq!(input.name(GamepadKind::Xbox), "P3");
+    assert_eq!(input.name(GamepadKind::Generic), "BackLeft");
+}
+
+#[test]
+fn captured_extra_buttons_are_normalized() {
+    let mut app = test_app();
+
+    app.insert_resource(InputCapture::gamepad_only(DUALSENSE));
+    app.send_gamepad_input(DUALSENSE, GamepadButtonType::Other(20));
+    app.update();
+    assert_eq!(
+        app.world
+            .resource::<InputCapture>()
+            .captured()
+            .unwrap()
+            .input,
+        GamepadExtra::TouchpadClick.into()
+    );
+
+    // Unknown codes are captured as they are
+    app.insert_resource(InputCapture::gamepad_only(GENERIC));
+    app.send_gamepad_input(GENERIC, GamepadButtonType::Other(20));
+    app.update();
+    assert_eq!(
+        app.world
+            .resource::<InputCapture>()
+            .captured()
+            .unwrap()
+            .input,
+        GamepadButtonType::Other(20).into()
+    );
+}
@@ -0,0 +1,120 @@
+use bevy::input::keyboard::KeyCode;
+use bevy::input::Input;
+use bevy::prelude::*;
+use leafwing_input_manager::action_diff::{ActionDiff, ActionDiffEvent};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+    Throttle,
+}
+
+/// A dedicated server, without `InputPlugin` or any input resources
+fn server_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputManagerPlugin::<Action>::server().apply_action_diffs(true),
+    ));
+    app.update();
+    app
+}
+
+fn send_diffs(app: &mut App, owner: Option<Entity>, action_diffs: Vec<ActionDiff<Action>>) {
+    app.world.send_event(ActionDiffEvent {
+        owner,
+        action_diffs,
+    });
+}
+
+#[test]
+fn server_runs_without_input_resources() {
+    let mut app = server_app();
+    app.update();
+    assert!(!app.world.contains_resource::<Input<KeyCode>>());
+}
+
+#[test]
+fn diffs_are_mirrored_onto_entities() {
+    let mut app = server_app();
+    let player = app.world.spawn(ActionState::<Action>::default()).id();
+    let other_player = app.world.spawn(ActionState::<Action>::default()).id();
+
+    send_diffs(
+        &mut app,
+        Some(player),
+        vec![
+            ActionDiff::Pressed {
+                action: Action::Jump,
+            },
+            ActionDiff::ValueChanged {
+                action: Action::Throttle,
+                value: 0.5,
+                pressed: true,
+            },
+        ],
+    );
+    app.update();
+
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(&Action::Jump));
+    assert_eq!(action_state.value(&Action::Throttle), 0.5);
+    let other_action_state = app.world.get::<ActionState<Action>>(other_player).unwrap();
+    assert!(other_action_state.released(&Action::Jump));
+
+    // The server ticks the mirrored states
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.pressed(&Action::Jump));
+    assert!(!action_state.just_pressed(&Action::Jump));
+
+    send_diffs(
+        &mut app,
+        Some(player),
+        vec![ActionDiff::Released {
+            action: Action::Jump,
+        }],
+    );
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_released(&Action::Jump));
+}
+
+#[test]
+fn diffs_without_owner_go_to_the_resource() {
+    let mut app = server_app();
+    app.init_resource::<ActionState<Action>>();
+
+    send_diffs(
+        &mut app,
+        None,
+        vec![ActionDiff::Pressed {
+            action: Action::Jump,
+        }],
+    );
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(&Action::Jump));
+}
+
+#[test]
+fn diffs_are_not_applied_by_default() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputManagerPlugin::<Action>::server()))
+        .add_event::<ActionDiffEvent<Action>>();
+    let player = app.world.spawn(ActionState::<Action>::default()).id();
+
+    send_diffs(
+        &mut app,
+        Some(player),
+        vec![ActionDiff::Pressed {
+            action: Action::Jump,
+        }],
+    );
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.released(&Action::Jump));
+}
@@ -0,0 +1,119 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::input_capture::{
+    CapturedInput, InputCapture, InputCapturePlugin, StickCapture,
+};
+use leafwing_input_manager::prelude::*;
+
+const PAD_1: Gamepad = Gamepad { id: 0 };
+const PAD_2: Gamepad = Gamepad { id: 1 };
+
+fn test_app(capture: InputCapture) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, InputCapturePlugin));
+    app.connect_gamepad(PAD_1);
+    app.connect_gamepad(PAD_2);
+    app.update();
+    app.insert_resource(capture);
+    app
+}
+
+fn captured(app: &App) -> Option<CapturedInput> {
+    app.world.resource::<InputCapture>().captured().cloned()
+}
+
+#[test]
+fn any_device_is_captured() {
+    let mut app = test_app(InputCapture::any());
+
+    app.send_input(KeyCode::F);
+    app.update();
+    assert_eq!(
+        captured(&app),
+        Some(CapturedInput {
+            input: KeyCode::F.into(),
+            gamepad: None,
+        })
+    );
+
+    // Later inputs are ignored until the captured input is taken
+    app.send_gamepad_input(PAD_2, GamepadButtonType::North);
+    app.update();
+    assert_eq!(captured(&app).unwrap().input, KeyCode::F.into());
+
+    app.world.resource_mut::<InputCapture>().take();
+    app.send_gamepad_input(PAD_2, GamepadButtonType::South);
+    app.update();
+    assert_eq!(
+        captured(&app),
+        Some(CapturedInput {
+            input: GamepadButtonType::South.into(),
+            gamepad: Some(PAD_2),
+        })
+    );
+}
+
+#[test]
+fn only_the_filtered_gamepad_is_captured() {
+    let mut app = test_app(InputCapture::gamepad_only(PAD_2));
+
+    app.send_input(KeyCode::F);
+    app.send_input(MouseButton::Left);
+    app.send_gamepad_input(PAD_1, GamepadButtonType::South);
+    app.send_gamepad_axis(PAD_1, GamepadAxisType::LeftStickX, 1.0);
+    app.send_gamepad_button_value(PAD_1, GamepadButtonType::RightTrigger2, 1.0);
+    app.update();
+    assert_eq!(captured(&app), None);
+
+    app.send_gamepad_input(PAD_2, GamepadButtonType::East);
+    app.update();
+    assert_eq!(
+        captured(&app),
+        Some(CapturedInput {
+            input: GamepadButtonType::East.into(),
+            gamepad: Some(PAD_2),
+        })
+    );
+}
+
+#[test]
+fn triggers_are_captured_past_the_threshold() {
+    let mut app = test_app(InputCapture::gamepad_only(PAD_2));
+
+    app.send_gamepad_button_value(PAD_2, GamepadButtonType::RightTrigger2, 0.3);
+    app.update();
+    assert_eq!(captured(&app), None);
+
+    // Not far enough for Bevy to press the button, but past the threshold of the capture
+    app.send_gamepad_button_value(PAD_2, GamepadButtonType::RightTrigger2, 0.6);
+    app.update();
+    assert_eq!(
+        captured(&app).unwrap().input,
+        GamepadButtonType::RightTrigger2.into()
+    );
+}
+
+#[test]
+fn sticks_are_captured_past_the_threshold() {
+    let mut app = test_app(InputCapture::gamepad_only(PAD_2));
+
+    app.send_gamepad_axis(PAD_2, GamepadAxisType::RightStickY, 0.4);
+    app.update();
+    assert_eq!(captured(&app), None);
+
+    app.send_gamepad_axis(PAD_2, GamepadAxisType::RightStickY, 0.8);
+    app.update();
+    assert_eq!(
+        captured(&app).unwrap().input,
+        DualAxis::right_stick().into()
+    );
+
+    let mut app =
+        test_app(InputCapture::gamepad_only(PAD_2).with_stick_capture(StickCapture::SingleAxis));
+    app.send_gamepad_axis(PAD_2, GamepadAxisType::LeftStickX, -0.8);
+    app.update();
+    assert_eq!(
+        captured(&app).unwrap().input,
+        SingleAxis::negative_only(GamepadAxisType::LeftStickX, -DualAxis::DEFAULT_DEADZONE).into()
+    );
+}
@@ -0,0 +1,141 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::input_map_stack::InputMapStack;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Walk,
+    Accelerate,
+    Interact,
+}
+
+fn on_foot() -> InputMap<Action> {
+    InputMap::new([(Action::Walk, KeyCode::W), (Action::Interact, KeyCode::E)])
+}
+
+fn in_vehicle() -> InputMap<Action> {
+    InputMap::new([
+        (Action::Accelerate, KeyCode::W),
+        (Action::Interact, KeyCode::E),
+    ])
+}
+
+fn test_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ));
+    let entity = app
+        .world
+        .spawn((
+            ActionState::<Action>::default(),
+            InputMapStack::new(on_foot()),
+        ))
+        .id();
+    app.update();
+    (app, entity)
+}
+
+fn action_state(app: &App, entity: Entity) -> &ActionState<Action> {
+    app.world.get::<ActionState<Action>>(entity).unwrap()
+}
+
+fn stack(app: &mut App, entity: Entity) -> Mut<'_, InputMapStack<Action>> {
+    app.world.get_mut::<InputMapStack<Action>>(entity).unwrap()
+}
+
+#[test]
+fn input_map_is_inserted_from_stack() {
+    let (app, entity) = test_app();
+    assert_eq!(app.world.get::<InputMap<Action>>(entity), Some(&on_foot()));
+}
+
+#[test]
+fn pushing_releases_unreachable_actions() {
+    let (mut app, entity) = test_app();
+    app.send_input(KeyCode::W);
+    app.update();
+    assert!(action_state(&app, entity).just_pressed(&Action::Walk));
+
+    stack(&mut app, entity).push(in_vehicle());
+    app.update();
+    assert!(action_state(&app, entity).just_released(&Action::Walk));
+    assert!(action_state(&app, entity).just_pressed(&Action::Accelerate));
+    assert_eq!(
+        app.world.get::<InputMap<Action>>(entity),
+        Some(&in_vehicle())
+    );
+
+    // Walking stays released while in the vehicle
+    app.update();
+    assert!(action_state(&app, entity).released(&Action::Walk));
+    assert!(!action_state(&app, entity).just_released(&Action::Walk));
+}
+
+#[test]
+fn popping_restores_the_previous_map() {
+    let (mut app, entity) = test_app();
+    stack(&mut app, entity).push(in_vehicle());
+    app.update();
+
+    app.send_input(KeyCode::W);
+    app.update();
+    assert!(action_state(&app, entity).pressed(&Action::Accelerate));
+
+    assert_eq!(stack(&mut app, entity).pop(), Some(in_vehicle()));
+    app.update();
+    assert!(action_state(&app, entity).just_released(&Action::Accelerate));
+    assert!(action_state(&app, entity).just_pressed(&Action::Walk));
+    assert_eq!(app.world.get::<InputMap<Action>>(entity), Some(&on_foot()));
+}
+
+#[test]
+fn actions_bound_in_both_layers_stay_pressed() {
+    let (mut app, entity) = test_app();
+    app.send_input(KeyCode::E);
+    app.update();
+    assert!(action_state(&app, entity).just_pressed(&Action::Interact));
+
+    // Pressing E to enter the vehicle does not also press E to leave it
+    stack(&mut app, entity).push(in_vehicle());
+    app.update();
+    assert!(action_state(&app, entity).pressed(&Action::Interact));
+    assert!(!action_state(&app, entity).just_pressed(&Action::Interact));
+}
+
+#[test]
+fn passthrough_layer_keeps_lower_bindings() {
+    let (mut app, entity) = test_app();
+    stack(&mut app, entity).push_passthrough(InputMap::new([(Action::Interact, KeyCode::F)]));
+    app.update();
+
+    app.send_input(KeyCode::W);
+    app.send_input(KeyCode::E);
+    app.update();
+    assert!(action_state(&app, entity).pressed(&Action::Walk));
+    assert!(action_state(&app, entity).released(&Action::Interact));
+
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(action_state(&app, entity).pressed(&Action::Interact));
+}
+
+#[test]
+fn gamepad_of_the_input_map_is_kept() {
+    let (mut app, entity) = test_app();
+    let gamepad = Gamepad { id: 1 };
+    app.world
+        .get_mut::<InputMap<Action>>(entity)
+        .unwrap()
+        .set_gamepad(gamepad);
+
+    stack(&mut app, entity).push(in_vehicle());
+    app.update();
+    assert_eq!(
+        app.world.get::<InputMap<Action>>(entity).unwrap().gamepad(),
+        Some(gamepad)
+    );
+}
@@ -0,0 +1,43 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+}
+
+#[derive(Component)]
+struct Player;
+
+fn spawn_player(mut commands: Commands) {
+    commands
+        .spawn(Player)
+        .insert_input_manager(InputMap::new([(Action::Jump, KeyCode::Space)]));
+}
+
+#[test]
+fn input_manager_is_inserted_through_commands() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ));
+    app.world.run_system_once(spawn_player);
+
+    let player = app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&app.world);
+    assert_eq!(
+        app.world.get::<InputMap<Action>>(player),
+        Some(&InputMap::new([(Action::Jump, KeyCode::Space)]))
+    );
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(&Action::Jump));
+}
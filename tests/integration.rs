@@ -246,3 +246,781 @@ fn duration() {
         .resource::<ActionState<Action>>()
         .pressed(&Action::PayRespects));
 }
+
+#[test]
+fn durations_pause_with_virtual_time() {
+    use bevy::input::InputPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use bevy::utils::Duration;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default().use_virtual_time())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )))
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(Action::PayRespects, KeyCode::F)]));
+
+    // Initializing
+    app.update();
+
+    // Press and hold
+    app.send_input(KeyCode::F);
+    app.update();
+    for _ in 0..3 {
+        app.update();
+    }
+    let action_state = app.world.resource::<ActionState<Action>>();
+    let held_duration = action_state.current_duration(&Action::PayRespects);
+    assert!(held_duration > Duration::ZERO);
+
+    // Pausing stops the duration from accumulating
+    app.world.resource_mut::<Time<Virtual>>().pause();
+    for _ in 0..5 {
+        app.update();
+        let action_state = app.world.resource::<ActionState<Action>>();
+        assert!(action_state.pressed(&Action::PayRespects));
+        assert_eq!(
+            action_state.current_duration(&Action::PayRespects),
+            held_duration
+        );
+    }
+
+    // Unpausing resumes the duration
+    app.world.resource_mut::<Time<Virtual>>().unpause();
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.current_duration(&Action::PayRespects) > held_duration);
+
+    // Releasing while paused is still only just released for a single tick
+    app.world.resource_mut::<Time<Virtual>>().pause();
+    app.reset_inputs();
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_released(&Action::PayRespects));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released(&Action::PayRespects));
+    assert!(!action_state.just_released(&Action::PayRespects));
+}
+
+#[test]
+fn frame_counts_from_inputs() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_mocking::InputScript;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(Action::PayRespects, KeyCode::F)]));
+
+    // Initializing
+    app.update();
+
+    // Press, then hold for three more frames
+    app.run_script(InputScript::new().press(KeyCode::F).wait_frames(3));
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(action_state.current_frame_count(&Action::PayRespects), 3);
+
+    // Release
+    app.run_script(InputScript::new().release(KeyCode::F));
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_released(&Action::PayRespects));
+    assert_eq!(action_state.current_frame_count(&Action::PayRespects), 0);
+    // The tick at the start of this frame counted towards the hold
+    assert_eq!(action_state.previous_frame_count(&Action::PayRespects), 4);
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(action_state.current_frame_count(&Action::PayRespects), 1);
+}
+
+#[test]
+fn hold_thresholds() {
+    use bevy::input::InputPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use bevy::utils::Duration;
+    use leafwing_input_manager::timing::{ActionHoldEvent, HoldThresholds};
+
+    fn crossed_thresholds(app: &mut App) -> Vec<Duration> {
+        app.world
+            .resource_mut::<Events<ActionHoldEvent<Action>>>()
+            .drain()
+            .map(|event| {
+                assert_eq!(event.action, Action::PayRespects);
+                assert_eq!(event.entity, None);
+                event.threshold
+            })
+            .collect()
+    }
+
+    let mut app = App::new();
+    let mut hold_thresholds = HoldThresholds::<Action>::default();
+    hold_thresholds.insert(
+        Action::PayRespects,
+        [
+            Duration::from_millis(250),
+            Duration::from_millis(500),
+            Duration::from_secs(1),
+        ],
+    );
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .insert_resource(hold_thresholds)
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(Action::PayRespects, KeyCode::F)]));
+
+    // Initializing
+    app.update();
+
+    // Holding for 700 ms crosses the first two thresholds, once each
+    app.send_input(KeyCode::F);
+    let mut crossed = Vec::new();
+    for _ in 0..8 {
+        app.update();
+        crossed.extend(crossed_thresholds(&mut app));
+    }
+    assert_eq!(
+        crossed,
+        [Duration::from_millis(250), Duration::from_millis(500)]
+    );
+
+    // Releasing before the third threshold re-arms them
+    app.release_input(KeyCode::F);
+    app.advance_frames(10);
+    assert!(crossed_thresholds(&mut app).is_empty());
+
+    app.send_input(KeyCode::F);
+    app.advance_frames(4);
+    assert_eq!(crossed_thresholds(&mut app), [Duration::from_millis(250)]);
+
+    // Consumed actions do not fire
+    app.world
+        .resource_mut::<ActionState<Action>>()
+        .consume(&Action::PayRespects);
+    app.advance_frames(10);
+    assert!(crossed_thresholds(&mut app).is_empty());
+}
+
+#[test]
+fn manual_tick_clock_fast_forward() {
+    use bevy::utils::Duration;
+    use leafwing_input_manager::timing::TickClock;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::server())
+        .insert_resource(TickClock::manual())
+        .init_resource::<ActionState<Action>>();
+
+    // Initializing
+    app.update();
+
+    // Pressed actions are timed from the next tick
+    app.world
+        .resource_mut::<ActionState<Action>>()
+        .press(&Action::PayRespects);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(
+        action_state.current_duration(&Action::PayRespects),
+        Duration::ZERO
+    );
+
+    // Catching up on 10 simulated seconds in a single frame
+    app.world
+        .resource_mut::<TickClock>()
+        .advance(Duration::from_secs(10));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(
+        action_state.current_duration(&Action::PayRespects),
+        Duration::from_secs(10)
+    );
+
+    // Frames without simulated time don't change the durations, however long they take
+    std::thread::sleep(Duration::from_millis(5));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(
+        action_state.current_duration(&Action::PayRespects),
+        Duration::from_secs(10)
+    );
+
+    // Advancing several times accumulates
+    let mut clock = app.world.resource_mut::<TickClock>();
+    clock.advance(Duration::from_millis(250));
+    clock.advance(Duration::from_millis(250));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(
+        action_state.current_duration(&Action::PayRespects),
+        Duration::from_millis(10_500)
+    );
+
+    // Releasing stores the simulated duration of the press
+    app.world
+        .resource_mut::<ActionState<Action>>()
+        .release(&Action::PayRespects);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(
+        action_state.previous_duration(&Action::PayRespects),
+        Duration::from_millis(10_500)
+    );
+    assert_eq!(
+        action_state.current_duration(&Action::PayRespects),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn double_ticks_are_skipped() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::plugin::InputManagerSystem;
+    use leafwing_input_manager::systems::tick_action_state;
+    use leafwing_input_manager::timing::DoubleTickPolicy;
+
+    #[derive(Resource, Default)]
+    struct JustPressedCount(usize);
+
+    fn count_just_pressed(
+        action_state: Res<ActionState<Action>>,
+        mut count: ResMut<JustPressedCount>,
+    ) {
+        if action_state.just_pressed(&Action::PayRespects) {
+            count.0 += 1;
+        }
+    }
+
+    let mut app = App::new();
+
+    // An accidental second tick, after the inputs were read
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(
+            PreUpdate,
+            tick_action_state::<Action>.after(InputManagerSystem::Update),
+        )
+        .add_systems(Update, count_just_pressed)
+        .init_resource::<JustPressedCount>()
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(Action::PayRespects, KeyCode::F)]));
+
+    // Initializing
+    app.update();
+
+    // The press survives until the next frame
+    app.send_input(KeyCode::F);
+    app.update();
+    assert_eq!(app.world.resource::<JustPressedCount>().0, 1);
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(action_state.ticks_this_frame(), 1);
+    app.update();
+    assert_eq!(app.world.resource::<JustPressedCount>().0, 1);
+
+    // Allowing extra ticks restores the old behavior
+    *app.world.resource_mut::<DoubleTickPolicy>() = DoubleTickPolicy::Allow;
+    app.world
+        .resource_mut::<ActionState<Action>>()
+        .release(&Action::PayRespects);
+    app.reset_inputs();
+    app.update();
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(&Action::PayRespects));
+    assert!(!action_state.just_pressed(&Action::PayRespects));
+    assert_eq!(action_state.ticks_this_frame(), 2);
+}
+
+#[test]
+fn unscaled_actions_ignore_relative_speed() {
+    use bevy::input::InputPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use bevy::utils::Duration;
+    use leafwing_input_manager::timing::TimeScalePolicy;
+
+    #[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+    enum TimedAction {
+        Charge,
+        Menu,
+    }
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(
+            InputManagerPlugin::<TimedAction>::default()
+                .use_virtual_time()
+                .with_time_scale_policy(TimedAction::Menu, TimeScalePolicy::Unscaled),
+        )
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .init_resource::<ActionState<TimedAction>>()
+        .insert_resource(InputMap::<TimedAction>::new([
+            (TimedAction::Charge, KeyCode::C),
+            (TimedAction::Menu, KeyCode::M),
+        ]));
+
+    // Initializing
+    app.update();
+
+    // Bullet time
+    app.world
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(0.5);
+
+    app.send_input(KeyCode::C);
+    app.send_input(KeyCode::M);
+    app.update();
+    for _ in 0..10 {
+        app.update();
+    }
+
+    // Scaled actions are slowed down along with the game
+    let action_state = app.world.resource::<ActionState<TimedAction>>();
+    let charge_duration = action_state.current_duration(&TimedAction::Charge);
+    let menu_duration = action_state.current_duration(&TimedAction::Menu);
+    assert_eq!(menu_duration, Duration::from_secs(1));
+    assert!(
+        (charge_duration.as_secs_f32() - 0.5).abs() < 0.001,
+        "{charge_duration:?}"
+    );
+
+    // And stop while it is paused, unlike unscaled actions
+    app.world.resource_mut::<Time<Virtual>>().pause();
+    for _ in 0..5 {
+        app.update();
+    }
+    let action_state = app.world.resource::<ActionState<TimedAction>>();
+    assert_eq!(
+        action_state.current_duration(&TimedAction::Charge),
+        charge_duration
+    );
+    assert_eq!(
+        action_state.current_duration(&TimedAction::Menu),
+        Duration::from_millis(1500)
+    );
+}
+
+#[test]
+fn idle_duration_from_inputs() {
+    use bevy::input::InputPlugin;
+    use bevy::utils::Duration;
+    use leafwing_input_manager::timing::TickClock;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .insert_resource(TickClock::manual())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(Action::PayRespects, KeyCode::F)]));
+
+    // Initializing
+    app.update();
+
+    let wait = |app: &mut App, duration: Duration| {
+        app.world.resource_mut::<TickClock>().advance(duration);
+        app.update();
+        app.world.resource::<ActionState<Action>>().idle_duration()
+    };
+
+    assert_eq!(
+        wait(&mut app, Duration::from_secs(30)),
+        Duration::from_secs(30)
+    );
+    assert_eq!(
+        wait(&mut app, Duration::from_secs(30)),
+        Duration::from_secs(60)
+    );
+
+    // Pressing the key resets the idle duration
+    app.send_input(KeyCode::F);
+    assert_eq!(wait(&mut app, Duration::from_secs(1)), Duration::ZERO);
+
+    // Holding it does not count as activity
+    assert_eq!(
+        wait(&mut app, Duration::from_secs(5)),
+        Duration::from_secs(5)
+    );
+
+    // Releasing it does
+    app.release_input(KeyCode::F);
+    assert_eq!(wait(&mut app, Duration::from_secs(1)), Duration::ZERO);
+    assert_eq!(
+        wait(&mut app, Duration::from_secs(10)),
+        Duration::from_secs(10)
+    );
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.released_for_at_least(&Action::PayRespects, Duration::from_secs(10)));
+    assert!(!action_state.released_for_at_least(&Action::PayRespects, Duration::from_secs(11)));
+}
+
+#[test]
+fn reset_inputs_clears_every_device() {
+    use bevy::input::mouse::MouseScrollUnit;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_mocking::Stick;
+
+    #[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+    enum EveryInput {
+        Key,
+        KeyLocation,
+        Click,
+        Scroll,
+        Look,
+        Jump,
+        Move,
+        Throttle,
+    }
+    let actions = [
+        EveryInput::Key,
+        EveryInput::KeyLocation,
+        EveryInput::Click,
+        EveryInput::Scroll,
+        EveryInput::Look,
+        EveryInput::Jump,
+        EveryInput::Move,
+        EveryInput::Throttle,
+    ];
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<EveryInput>::default())
+        .init_resource::<ActionState<EveryInput>>()
+        .insert_resource(InputMap::<EveryInput>::new([
+            (EveryInput::Key, UserInput::from(KeyCode::F)),
+            (EveryInput::KeyLocation, QwertyScanCode::G.into()),
+            (EveryInput::Click, MouseButton::Left.into()),
+            (EveryInput::Scroll, DualAxis::mouse_wheel().into()),
+            (EveryInput::Look, DualAxis::mouse_motion().into()),
+            (EveryInput::Jump, GamepadButtonType::South.into()),
+            (EveryInput::Move, DualAxis::left_stick().into()),
+            (
+                EveryInput::Throttle,
+                SingleAxis::symmetric(GamepadAxisType::RightZ, 0.1).into(),
+            ),
+        ]));
+
+    app.connect_gamepad(Gamepad { id: 0 });
+    app.update();
+
+    let mock_every_input = |app: &mut App| {
+        app.send_input(KeyCode::F);
+        app.send_input(QwertyScanCode::G);
+        app.send_input(MouseButton::Left);
+        app.send_mouse_wheel(1.0, 1.0, MouseScrollUnit::Line);
+        app.send_mouse_motion(Vec2::new(1.0, 1.0));
+        app.send_input(GamepadButtonType::South);
+        app.send_stick(Stick::Left, Vec2::new(1.0, 0.0));
+        app.send_axis_values(GamepadAxisType::RightZ, 1.0);
+    };
+
+    mock_every_input(&mut app);
+    app.update();
+    let action_state = app.world.resource::<ActionState<EveryInput>>();
+    assert_eq!(action_state.get_pressed().len(), actions.len());
+
+    // Inputs that are still pending must not leak past the reset either
+    mock_every_input(&mut app);
+    app.reset_inputs();
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<EveryInput>>();
+    for action in actions {
+        assert!(
+            action_state.released(&action),
+            "{action:?} is still pressed"
+        );
+        assert_eq!(action_state.value(&action), 0.0, "{action:?} has a value");
+        if let Some(axis_pair) = action_state.axis_pair(&action) {
+            assert_eq!(axis_pair.xy(), Vec2::ZERO, "{action:?} has an axis pair");
+        }
+    }
+    assert!(!app.any_pressed());
+}
+
+#[test]
+fn double_tap_with_mocked_time() {
+    use bevy::input::InputPlugin;
+    use bevy::utils::Duration;
+    use leafwing_input_manager::timing::TickClock;
+
+    const DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(200);
+    let gap = Duration::from_millis(150);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .insert_resource(TickClock::manual())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(Action::PayRespects, KeyCode::F)]));
+
+    // Initializing
+    app.update();
+
+    // First tap
+    app.send_input(KeyCode::F);
+    app.update();
+    app.advance_time(gap);
+    app.release_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_released(&Action::PayRespects));
+    assert_eq!(action_state.previous_duration(&Action::PayRespects), gap);
+
+    // Second tap, after exactly the same gap
+    app.advance_time(gap);
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(&Action::PayRespects));
+    assert_eq!(action_state.previous_duration(&Action::PayRespects), gap);
+    assert!(action_state.previous_duration(&Action::PayRespects) < DOUBLE_TAP_WINDOW);
+
+    // Frames without mocked time don't count, however long they take
+    std::thread::sleep(Duration::from_millis(5));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(
+        action_state.current_duration(&Action::PayRespects),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn hold_for_frames_with_fixed_delta() {
+    use bevy::input::InputPlugin;
+    use bevy::utils::Duration;
+    use leafwing_input_manager::timing::TickClock;
+
+    let frame_time = Duration::from_millis(16);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .insert_resource(TickClock::manual())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(Action::PayRespects, KeyCode::F)]));
+
+    // Initializing
+    app.advance_frames(1);
+
+    // The press is seen on the first frame, and timed from the second one onwards
+    app.send_input(KeyCode::F);
+    app.advance_frames_with(30, frame_time);
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(&Action::PayRespects));
+    assert_eq!(
+        action_state.current_duration(&Action::PayRespects),
+        frame_time * 29
+    );
+    assert_eq!(action_state.current_frame_count(&Action::PayRespects), 29);
+
+    app.release_input(KeyCode::F);
+    app.advance_frames_with(1, frame_time);
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_released(&Action::PayRespects));
+    assert_eq!(
+        action_state.previous_duration(&Action::PayRespects),
+        frame_time * 30
+    );
+}
+
+#[test]
+fn world_advance_frames_runs_main_schedule() {
+    use bevy::input::InputPlugin;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(Action::PayRespects, KeyCode::F)]));
+    app.update();
+
+    app.world.send_input(KeyCode::F);
+    app.world.advance_frames(2);
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(&Action::PayRespects));
+    assert!(!action_state.just_pressed(&Action::PayRespects));
+}
+
+#[test]
+#[cfg(feature = "ui")]
+fn action_state_driver_holds_and_releases() {
+    use bevy::input::InputPlugin;
+    use bevy::utils::Duration;
+    use leafwing_input_manager::timing::TickClock;
+
+    #[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+    enum OverlayAction {
+        Mapped,
+        Unmapped,
+    }
+
+    #[derive(Component)]
+    struct MappedButton;
+
+    #[derive(Component)]
+    struct UnmappedButton;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<OverlayAction>::default())
+        .insert_resource(TickClock::manual());
+
+    let player = app
+        .world
+        .spawn(InputManagerBundle::<OverlayAction> {
+            input_map: InputMap::new([(OverlayAction::Mapped, KeyCode::Space)]),
+            ..Default::default()
+        })
+        .id();
+    for (action, marker) in [
+        (OverlayAction::Mapped, app.world.spawn(MappedButton).id()),
+        (
+            OverlayAction::Unmapped,
+            app.world.spawn(UnmappedButton).id(),
+        ),
+    ] {
+        app.world
+            .entity_mut(marker)
+            .insert(Interaction::None)
+            .insert(ActionStateDriver {
+                action,
+                targets: player.into(),
+            });
+    }
+    app.update();
+
+    // Holding the buttons keeps both actions pressed, without pressing them again
+    app.click_button::<MappedButton>();
+    app.click_button::<UnmappedButton>();
+    app.advance_frames_with(3, Duration::from_millis(10));
+    let action_state = app.world.get::<ActionState<OverlayAction>>(player).unwrap();
+    for action in [OverlayAction::Mapped, OverlayAction::Unmapped] {
+        assert!(action_state.pressed(&action), "{action:?}");
+        assert!(!action_state.just_pressed(&action), "{action:?}");
+        assert_eq!(
+            action_state.current_duration(&action),
+            Duration::from_millis(20),
+            "{action:?}"
+        );
+    }
+
+    // Moving the pointer away while held releases both actions
+    app.hover_button::<MappedButton>();
+    app.hover_button::<UnmappedButton>();
+    app.update();
+    let action_state = app.world.get::<ActionState<OverlayAction>>(player).unwrap();
+    assert!(action_state.just_released(&OverlayAction::Mapped));
+    assert!(action_state.just_released(&OverlayAction::Unmapped));
+
+    app.update();
+    let action_state = app.world.get::<ActionState<OverlayAction>>(player).unwrap();
+    assert!(action_state.released(&OverlayAction::Mapped));
+    assert!(action_state.released(&OverlayAction::Unmapped));
+}
+
+#[test]
+#[cfg(feature = "ui")]
+fn action_state_driver_releases_despawned_buttons() {
+    use bevy::input::InputPlugin;
+
+    #[derive(Component)]
+    struct ButtonMarker;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+
+    let player = app
+        .world
+        .spawn(InputManagerBundle::<Action>::default())
+        .id();
+    let button = app
+        .world
+        .spawn((
+            ButtonMarker,
+            Interaction::None,
+            ActionStateDriver {
+                action: Action::PayRespects,
+                targets: player.into(),
+            },
+        ))
+        .id();
+    app.update();
+
+    app.click_button::<ButtonMarker>();
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.pressed(&Action::PayRespects));
+
+    // Despawning the button while it is held releases its action
+    app.world.despawn(button);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_released(&Action::PayRespects));
+
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.released(&Action::PayRespects));
+}
+
+#[test]
+fn consume_policy_is_set_per_action_type() {
+    use leafwing_input_manager::timing::ConsumePolicy;
+
+    #[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+    enum OtherAction {
+        Wave,
+    }
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(
+            InputManagerPlugin::<Action>::server()
+                .with_consume_policy(ConsumePolicy::PreserveTiming),
+        )
+        .add_plugins(InputManagerPlugin::<OtherAction>::server())
+        .init_resource::<ActionState<Action>>()
+        .init_resource::<ActionState<OtherAction>>();
+
+    app.update();
+
+    assert_eq!(
+        app.world.resource::<ActionState<Action>>().consume_policy(),
+        ConsumePolicy::PreserveTiming
+    );
+    assert_eq!(
+        app.world
+            .resource::<ActionState<OtherAction>>()
+            .consume_policy(),
+        ConsumePolicy::FlipTiming
+    );
+}
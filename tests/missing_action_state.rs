@@ -0,0 +1,66 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+}
+
+fn test_app(plugin: InputManagerPlugin<Action>) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, plugin));
+    let entity = app
+        .world
+        .spawn(InputMap::new([(Action::Jump, KeyCode::Space)]))
+        .id();
+    app.update();
+    (app, entity)
+}
+
+#[test]
+fn action_state_is_inserted_for_input_map() {
+    let (mut app, entity) = test_app(InputManagerPlugin::default());
+    assert!(app.world.get::<ActionState<Action>>(entity).is_some());
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(entity).unwrap();
+    assert!(action_state.just_pressed(&Action::Jump));
+}
+
+#[test]
+fn existing_action_state_is_kept() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ));
+    let mut action_state = ActionState::<Action>::default();
+    action_state.consume(&Action::Jump);
+    let entity = app
+        .world
+        .spawn(InputManagerBundle {
+            action_state,
+            input_map: InputMap::new([(Action::Jump, KeyCode::Space)]),
+        })
+        .id();
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(entity).unwrap();
+    assert!(action_state.released(&Action::Jump));
+}
+
+#[test]
+fn insertion_can_be_disabled() {
+    let (app, entity) = test_app(InputManagerPlugin::default().insert_missing_action_states(false));
+    assert!(app.world.get::<ActionState<Action>>(entity).is_none());
+}
+
+#[test]
+fn server_inserts_action_states() {
+    let (app, entity) = test_app(InputManagerPlugin::server());
+    assert!(app.world.get::<ActionState<Action>>(entity).is_some());
+}
@@ -0,0 +1,145 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Duration;
+use leafwing_input_manager::axislike::DualAxisData;
+use leafwing_input_manager::mouse_gestures::{DragAxis, MouseClick, MouseDrag};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Select,
+    BoxSelect,
+    Orbit,
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (
+            Action::Select,
+            UserInput::from(MouseClick::new(MouseButton::Left)),
+        ),
+        (Action::BoxSelect, MouseDrag::new(MouseButton::Left).into()),
+        (
+            Action::Orbit,
+            DragAxis::new(MouseButton::Right)
+                .with_sensitivity(0.5)
+                .with_inverted_y(true)
+                .into(),
+        ),
+    ]));
+    app.update();
+    app
+}
+
+fn action_state(app: &App) -> &ActionState<Action> {
+    app.world.resource::<ActionState<Action>>()
+}
+
+#[test]
+fn stationary_clicks_are_pressed_on_release() {
+    let mut app = test_app();
+
+    app.send_input(MouseButton::Left);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Select));
+
+    // A little jitter is still a click
+    app.send_mouse_motion(Vec2::new(2.0, 1.0));
+    app.update();
+    app.release_input(MouseButton::Left);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::BoxSelect));
+
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::BoxSelect));
+}
+
+#[test]
+fn drags_are_pressed_once_the_cursor_moves_far_enough() {
+    let mut app = test_app();
+
+    app.send_input(MouseButton::Left);
+    app.update();
+    app.send_mouse_motion(Vec2::new(20.0, 0.0));
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::BoxSelect));
+    assert_eq!(
+        action_state(&app).axis_pair(&Action::BoxSelect).unwrap(),
+        DualAxisData::new(20.0, 0.0)
+    );
+
+    // Moving back doesn't turn the drag into a click
+    app.send_mouse_motion(Vec2::new(-20.0, 0.0));
+    app.update();
+    assert!(action_state(&app).pressed(&Action::BoxSelect));
+    assert_eq!(
+        action_state(&app).axis_pair(&Action::BoxSelect).unwrap(),
+        DualAxisData::new(0.0, 0.0)
+    );
+
+    app.release_input(MouseButton::Left);
+    app.update();
+    assert!(action_state(&app).just_released(&Action::BoxSelect));
+    assert!(!action_state(&app).pressed(&Action::Select));
+}
+
+#[test]
+fn slow_clicks_are_not_clicks() {
+    let mut app = test_app();
+
+    app.send_input(MouseButton::Left);
+    for _ in 0..6 {
+        app.update();
+    }
+    app.release_input(MouseButton::Left);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::BoxSelect));
+}
+
+#[test]
+fn drag_axes_only_read_mouse_motion_while_their_button_is_held() {
+    let mut app = test_app();
+
+    app.send_mouse_motion(Vec2::new(10.0, 10.0));
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Orbit));
+    assert_eq!(action_state(&app).axis_pair(&Action::Orbit), None);
+
+    app.send_input(MouseButton::Right);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Orbit));
+    assert_eq!(
+        action_state(&app).axis_pair(&Action::Orbit),
+        Some(DualAxisData::new(0.0, 0.0))
+    );
+
+    // Only the motion of this frame is read, scaled by the sensitivity, with the y axis inverted
+    app.send_mouse_motion(Vec2::new(10.0, 4.0));
+    app.send_mouse_motion(Vec2::new(10.0, 4.0));
+    app.update();
+    assert!(action_state(&app).pressed(&Action::Orbit));
+    assert_eq!(
+        action_state(&app).axis_pair(&Action::Orbit),
+        Some(DualAxisData::new(10.0, -4.0))
+    );
+
+    app.release_input(MouseButton::Right);
+    app.send_mouse_motion(Vec2::new(10.0, 4.0));
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Orbit));
+    assert_eq!(action_state(&app).axis_pair(&Action::Orbit), None);
+}
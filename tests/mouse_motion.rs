@@ -352,3 +352,49 @@ fn mouse_drag() {
         Some(DualAxisData::new(5.0, 0.0))
     );
 }
+
+#[test]
+fn mouse_motion_accumulates() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::XY,
+        DualAxis::mouse_motion(),
+    )]));
+
+    // Both movements happen during the same frame
+    app.send_mouse_motion(Vec2::new(3.0, 4.0));
+    app.send_mouse_motion(Vec2::new(1.0, -2.0));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(4.0, 2.0)
+    );
+
+    // The movement does not carry over into the next frame
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, 0.0)
+    );
+}
+
+#[test]
+fn reset_mouse_discards_motion() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::XY,
+        DualAxis::mouse_motion(),
+    )]));
+
+    app.send_mouse_motion(Vec2::new(3.0, 4.0));
+    app.reset_mouse();
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(&AxislikeTestAction::XY));
+}
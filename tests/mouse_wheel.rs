@@ -314,3 +314,29 @@ fn mouse_wheel_virtualdpad() {
         DualAxisData::new(0.0, -1.0)
     );
 }
+
+#[test]
+fn mouse_wheel_accumulates() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        AxislikeTestAction::XY,
+        DualAxis::mouse_wheel(),
+    )]));
+
+    // Both scrolls happen during the same frame
+    app.send_mouse_wheel(0.0, 1.0, MouseScrollUnit::Line);
+    app.send_mouse_wheel(-2.0, 1.0, MouseScrollUnit::Line);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(&AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(&AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(-2.0, 2.0)
+    );
+
+    // The scrolling does not carry over into the next frame
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(&AxislikeTestAction::XY));
+}
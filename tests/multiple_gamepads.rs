@@ -0,0 +1,179 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::axislike::DualAxisData;
+use leafwing_input_manager::input_map::GamepadScope;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+    Move,
+    Pause,
+}
+
+#[derive(Component)]
+struct Player(usize);
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+
+    for id in 0..2 {
+        let gamepad = Gamepad { id };
+        app.connect_gamepad(gamepad);
+
+        let mut input_map = InputMap::new([
+            (Action::Jump, UserInput::from(GamepadButtonType::South)),
+            (
+                Action::Move,
+                DualAxis::left_stick()
+                    .with_deadzone(DeadZoneShape::Cross {
+                        horizontal_width: 0.0,
+                        vertical_width: 0.0,
+                    })
+                    .into(),
+            ),
+        ]);
+        input_map.set_gamepad(gamepad);
+
+        app.world.spawn((
+            Player(id),
+            InputManagerBundle {
+                input_map,
+                ..default()
+            },
+        ));
+    }
+    app.update();
+
+    app
+}
+
+fn action_state(app: &mut App, player: usize) -> ActionState<Action> {
+    let mut query = app.world.query::<(&Player, &ActionState<Action>)>();
+    query
+        .iter(&app.world)
+        .find(|(Player(id), _)| *id == player)
+        .map(|(_, action_state)| action_state.clone())
+        .unwrap()
+}
+
+#[test]
+fn gamepad_buttons_only_press_their_gamepad() {
+    let mut app = test_app();
+
+    app.send_gamepad_input(Gamepad { id: 1 }, GamepadButtonType::South);
+    app.update();
+
+    assert!(action_state(&mut app, 0).released(&Action::Jump));
+    assert!(action_state(&mut app, 1).pressed(&Action::Jump));
+}
+
+#[test]
+fn gamepad_axes_only_move_their_gamepad() {
+    let mut app = test_app();
+
+    app.send_gamepad_axis(Gamepad { id: 1 }, GamepadAxisType::LeftStickX, 0.5);
+    app.update();
+
+    assert!(action_state(&mut app, 0).released(&Action::Move));
+    assert_eq!(
+        action_state(&mut app, 0).axis_pair(&Action::Move).unwrap(),
+        DualAxisData::new(0.0, 0.0)
+    );
+    assert!(action_state(&mut app, 1).pressed(&Action::Move));
+    assert_eq!(
+        action_state(&mut app, 1).axis_pair(&Action::Move).unwrap(),
+        DualAxisData::new(0.5, 0.0)
+    );
+}
+
+#[test]
+fn untargeted_gamepad_input_uses_a_single_gamepad() {
+    let mut app = test_app();
+
+    app.send_input(GamepadButtonType::South);
+    app.update();
+
+    let jumping_players = (0..2)
+        .filter(|player| action_state(&mut app, *player).pressed(&Action::Jump))
+        .count();
+    assert_eq!(jumping_players, 1);
+}
+
+#[test]
+fn cloned_input_map_reads_its_own_gamepad() {
+    let mut app = test_app();
+
+    let mut input_map = InputMap::default();
+    input_map
+        .insert_chord(
+            Action::Jump,
+            [GamepadButtonType::LeftTrigger, GamepadButtonType::South],
+        )
+        .set_gamepad(Gamepad { id: 0 });
+    let cloned = input_map.clone_for_gamepad(Gamepad { id: 1 });
+    assert_eq!(cloned.gamepad(), Some(Gamepad { id: 1 }));
+    assert_eq!(cloned.get(&Action::Jump), input_map.get(&Action::Jump));
+
+    for (id, input_map) in [(0, input_map), (1, cloned)] {
+        let mut query = app.world.query::<(&Player, &mut InputMap<Action>)>();
+        for (Player(player), mut player_input_map) in query.iter_mut(&mut app.world) {
+            if *player == id {
+                *player_input_map = input_map.clone();
+            }
+        }
+    }
+
+    app.send_gamepad_input(Gamepad { id: 1 }, GamepadButtonType::LeftTrigger);
+    app.send_gamepad_input(Gamepad { id: 1 }, GamepadButtonType::South);
+    app.update();
+
+    assert!(action_state(&mut app, 0).released(&Action::Jump));
+    assert!(action_state(&mut app, 1).pressed(&Action::Jump));
+}
+
+#[test]
+fn scoped_bindings_read_other_gamepads() {
+    let mut app = test_app();
+
+    let mut query = app.world.query::<(&Player, &mut InputMap<Action>)>();
+    for (Player(player), mut input_map) in query.iter_mut(&mut app.world) {
+        if *player == 0 {
+            input_map
+                .insert_scoped(Action::Pause, GamepadButtonType::Start, GamepadScope::Any)
+                .set_gamepad_scope(
+                    &Action::Jump,
+                    GamepadButtonType::South,
+                    GamepadScope::Specific(Gamepad { id: 1 }),
+                );
+        }
+    }
+
+    // The unassociated gamepad can't move the player, but can pause the game
+    app.send_gamepad_axis(Gamepad { id: 1 }, GamepadAxisType::LeftStickX, 0.5);
+    app.send_gamepad_input(Gamepad { id: 1 }, GamepadButtonType::Start);
+    app.update();
+
+    assert!(action_state(&mut app, 0).released(&Action::Move));
+    assert!(action_state(&mut app, 0).pressed(&Action::Pause));
+
+    // The associated gamepad still moves the player
+    app.send_gamepad_axis(Gamepad { id: 0 }, GamepadAxisType::LeftStickX, 0.5);
+    app.update();
+
+    assert!(action_state(&mut app, 0).pressed(&Action::Move));
+
+    // The specific gamepad replaces the associated gamepad
+    app.send_gamepad_input(Gamepad { id: 0 }, GamepadButtonType::South);
+    app.update();
+
+    assert!(action_state(&mut app, 0).released(&Action::Jump));
+
+    app.send_gamepad_input(Gamepad { id: 1 }, GamepadButtonType::South);
+    app.update();
+
+    assert!(action_state(&mut app, 0).pressed(&Action::Jump));
+}
@@ -0,0 +1,106 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::timing::TickClock;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Run,
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+
+fn test_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .insert_resource(TickClock::manual());
+    let entity = app
+        .world
+        .spawn(InputManagerBundle {
+            input_map: InputMap::new([(Action::Run, KeyCode::ShiftLeft)]),
+            ..default()
+        })
+        .id();
+    app.update();
+    (app, entity)
+}
+
+fn advance_frame(app: &mut App) {
+    app.world.resource_mut::<TickClock>().advance(FRAME);
+    app.update();
+}
+
+fn action_state(app: &App, entity: Entity) -> &ActionState<Action> {
+    app.world.get::<ActionState<Action>>(entity).unwrap()
+}
+
+#[test]
+fn paused_hold_keeps_its_duration() {
+    let (mut app, entity) = test_app();
+    app.send_input(KeyCode::ShiftLeft);
+    advance_frame(&mut app);
+    advance_frame(&mut app);
+    advance_frame(&mut app);
+    let held_for = action_state(&app, entity).current_duration(&Action::Run);
+    assert!(held_for > Duration::ZERO);
+
+    app.world.entity_mut(entity).insert(PausedInputs);
+    for _ in 0..5 {
+        advance_frame(&mut app);
+        let action_state = action_state(&app, entity);
+        assert!(action_state.pressed(&Action::Run));
+        assert_eq!(action_state.current_duration(&Action::Run), held_for);
+    }
+
+    // The hold carries on from where it was frozen, without being pressed again
+    app.world.entity_mut(entity).remove::<PausedInputs>();
+    advance_frame(&mut app);
+    let action_state = action_state(&app, entity);
+    assert!(action_state.pressed(&Action::Run));
+    assert!(!action_state.just_pressed(&Action::Run));
+    assert_eq!(
+        action_state.current_duration(&Action::Run),
+        held_for + FRAME
+    );
+}
+
+#[test]
+fn paused_edges_are_preserved() {
+    let (mut app, entity) = test_app();
+    app.send_input(KeyCode::ShiftLeft);
+    advance_frame(&mut app);
+    assert!(action_state(&app, entity).just_pressed(&Action::Run));
+
+    app.world.entity_mut(entity).insert(PausedInputs);
+    advance_frame(&mut app);
+    advance_frame(&mut app);
+    assert!(action_state(&app, entity).just_pressed(&Action::Run));
+
+    app.world.entity_mut(entity).remove::<PausedInputs>();
+    advance_frame(&mut app);
+    let action_state = action_state(&app, entity);
+    assert!(action_state.pressed(&Action::Run));
+    assert!(!action_state.just_pressed(&Action::Run));
+}
+
+#[test]
+fn inputs_changed_while_paused_apply_on_resume() {
+    let (mut app, entity) = test_app();
+    app.send_input(KeyCode::ShiftLeft);
+    advance_frame(&mut app);
+    advance_frame(&mut app);
+
+    app.world.entity_mut(entity).insert(PausedInputs);
+    app.release_input(KeyCode::ShiftLeft);
+    advance_frame(&mut app);
+    assert!(action_state(&app, entity).pressed(&Action::Run));
+
+    app.world.entity_mut(entity).remove::<PausedInputs>();
+    advance_frame(&mut app);
+    assert!(action_state(&app, entity).just_released(&Action::Run));
+}
@@ -0,0 +1,188 @@
+use bevy::input::touch::{ForceTouch, TouchPhase};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::axislike::PenAxisType;
+use leafwing_input_manager::input_mocking::{MockInput, MOCK_PEN_ID};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    BrushSize,
+    Shade,
+}
+
+fn test_app(input_map: InputMap<Action>) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(input_map);
+    app.update();
+    app
+}
+
+fn value(app: &App, action: Action) -> f32 {
+    app.world.resource::<ActionState<Action>>().value(&action)
+}
+
+#[test]
+fn pressure_is_read_as_a_value() {
+    let mut app = test_app(InputMap::new([(
+        Action::BrushSize,
+        SingleAxis::pen_pressure(),
+    )]));
+
+    app.send_input(SingleAxis::from_value(PenAxisType::Pressure, 0.5));
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(&Action::BrushSize));
+    assert_eq!(value(&app, Action::BrushSize), 0.5);
+
+    app.send_input(SingleAxis::from_value(PenAxisType::Pressure, 0.75));
+    app.update();
+    assert_eq!(value(&app, Action::BrushSize), 0.75);
+
+    app.release_touch(MOCK_PEN_ID);
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .released(&Action::BrushSize));
+    assert_eq!(value(&app, Action::BrushSize), 0.0);
+}
+
+#[test]
+fn calibrated_pressure_is_relative_to_the_maximum_force() {
+    let mut app = test_app(InputMap::new([
+        (Action::BrushSize, SingleAxis::pen_pressure()),
+        (Action::Shade, SingleAxis::pen_tilt()),
+    ]));
+
+    app.world.send_event(TouchInput {
+        phase: TouchPhase::Started,
+        position: Vec2::ZERO,
+        force: Some(ForceTouch::Calibrated {
+            force: 1.0,
+            max_possible_force: 4.0,
+            altitude_angle: Some(std::f64::consts::FRAC_PI_4),
+        }),
+        id: 0,
+    });
+    app.update();
+    assert_eq!(value(&app, Action::BrushSize), 0.25);
+    assert_eq!(value(&app, Action::Shade), 0.5);
+}
+
+#[test]
+fn tilt_is_read_from_the_altitude_of_the_pen() {
+    let mut app = test_app(InputMap::new([(Action::Shade, SingleAxis::pen_tilt())]));
+
+    // Upright
+    app.send_input(SingleAxis::from_value(PenAxisType::Tilt, 0.0));
+    app.update();
+    assert_eq!(value(&app, Action::Shade), 0.0);
+
+    // Flat on the screen
+    app.send_input(SingleAxis::from_value(PenAxisType::Tilt, 1.0));
+    app.update();
+    assert_eq!(value(&app, Action::Shade), 1.0);
+}
+
+#[test]
+fn tilt_is_read_as_a_dual_axis() {
+    let mut app = test_app(InputMap::new([(Action::Shade, DualAxis::pen_tilt())]));
+
+    app.send_input(DualAxis::from_value(
+        PenAxisType::TiltX,
+        PenAxisType::TiltY,
+        0.5,
+        0.0,
+    ));
+    app.update();
+    let axis_pair = app
+        .world
+        .resource::<ActionState<Action>>()
+        .axis_pair(&Action::Shade)
+        .unwrap();
+    // The direction the pen leans towards is unknown, so it is read as leaning to the right
+    assert!((axis_pair.x() - 0.5).abs() < 1e-6);
+    assert_eq!(axis_pair.y(), 0.0);
+    assert!((value(&app, Action::Shade) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn devices_without_a_maximum_force_read_the_raw_force() {
+    let mut app = test_app(InputMap::new([(
+        Action::BrushSize,
+        SingleAxis::pen_pressure(),
+    )]));
+
+    for max_possible_force in [0.0, f64::NAN, f64::INFINITY] {
+        app.world.send_event(TouchInput {
+            phase: TouchPhase::Started,
+            position: Vec2::ZERO,
+            force: Some(ForceTouch::Calibrated {
+                force: 0.5,
+                max_possible_force,
+                altitude_angle: None,
+            }),
+            id: 0,
+        });
+        app.update();
+        assert_eq!(value(&app, Action::BrushSize), 0.5, "{max_possible_force}");
+    }
+
+    assert_eq!(
+        PenAxisType::Pressure.value(ForceTouch::Calibrated {
+            force: f64::NAN,
+            max_possible_force: 1.0,
+            altitude_angle: None,
+        }),
+        0.0
+    );
+}
+
+#[test]
+fn pressure_goes_through_the_deadzone_and_sensitivity() {
+    let mut app = test_app(InputMap::new([(
+        Action::BrushSize,
+        SingleAxis::pen_pressure()
+            .with_deadzone(0.2)
+            .with_sensitivity(2.0),
+    )]));
+
+    app.send_input(SingleAxis::from_value(PenAxisType::Pressure, 0.1));
+    app.update();
+    assert!(!app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(&Action::BrushSize));
+    assert_eq!(value(&app, Action::BrushSize), 0.0);
+
+    // Rescaled from the edge of the deadzone, then doubled
+    app.send_input(SingleAxis::from_value(PenAxisType::Pressure, 0.6));
+    app.update();
+    assert!((value(&app, Action::BrushSize) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn touches_without_force_read_nothing() {
+    let mut app = test_app(InputMap::new([
+        (Action::BrushSize, SingleAxis::pen_pressure()),
+        (Action::Shade, SingleAxis::pen_tilt()),
+    ]));
+
+    app.send_touch(0, Vec2::new(100.0, 100.0));
+    app.update();
+    assert_eq!(value(&app, Action::BrushSize), 0.0);
+    assert_eq!(value(&app, Action::Shade), 0.0);
+    assert!(!app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(&Action::BrushSize));
+}
@@ -0,0 +1,219 @@
+use bevy::ecs::event::ManualEventReader;
+use bevy::input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Duration;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::rumble::{ActionRumble, ActionRumbleEffect, GamepadRumbles};
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Shoot,
+    Brace,
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+const SHOT: Duration = Duration::from_millis(200);
+const BRACE: GamepadRumbleIntensity = GamepadRumbleIntensity::weak_motor(0.3);
+
+fn create_app(gamepad: Option<Gamepad>) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+
+    let mut input_map =
+        InputMap::new([(Action::Shoot, KeyCode::Space), (Action::Brace, KeyCode::B)]);
+    if let Some(gamepad) = gamepad {
+        input_map.set_gamepad(gamepad);
+    }
+
+    let mut action_rumble = ActionRumble::default();
+    action_rumble
+        .insert(
+            Action::Shoot,
+            ActionRumbleEffect::on_just_pressed(GamepadRumbleIntensity::STRONG_MAX, SHOT),
+        )
+        .insert(Action::Brace, ActionRumbleEffect::while_pressed(BRACE));
+
+    app.world
+        .spawn((InputManagerBundle::with_map(input_map), action_rumble));
+    app.update();
+    app
+}
+
+/// A rumble request, as the gamepad and the duration and intensity that were added, or `None` if the rumble was stopped
+type Request = (Gamepad, Option<(Duration, GamepadRumbleIntensity)>);
+
+fn update(app: &mut App, reader: &mut ManualEventReader<GamepadRumbleRequest>) -> Vec<Request> {
+    app.update();
+    reader
+        .read(app.world.resource::<Events<GamepadRumbleRequest>>())
+        .map(|request| match *request {
+            GamepadRumbleRequest::Add {
+                duration,
+                intensity,
+                gamepad,
+            } => (gamepad, Some((duration, intensity))),
+            GamepadRumbleRequest::Stop { gamepad } => (gamepad, None),
+        })
+        .collect()
+}
+
+#[test]
+fn just_pressed_actions_pulse() {
+    let gamepad = Gamepad::new(1);
+    let mut app = create_app(Some(gamepad));
+    let mut reader = ManualEventReader::default();
+
+    app.send_input(KeyCode::Space);
+    assert_eq!(
+        update(&mut app, &mut reader),
+        [(gamepad, Some((SHOT, GamepadRumbleIntensity::STRONG_MAX)))]
+    );
+
+    // Holding the action doesn't rumble again
+    assert!(update(&mut app, &mut reader).is_empty());
+    assert_eq!(update(&mut app, &mut reader), [(gamepad, None)]);
+    assert!(update(&mut app, &mut reader).is_empty());
+}
+
+#[test]
+fn held_actions_rumble_until_released() {
+    let mut app = create_app(None);
+    let mut reader = ManualEventReader::default();
+
+    // Maps without a gamepad rumble the first one
+    let gamepad = Gamepad::new(0);
+    app.send_input(KeyCode::B);
+    assert_eq!(
+        update(&mut app, &mut reader),
+        [(gamepad, Some((Duration::from_secs(1), BRACE)))]
+    );
+
+    // The rumble is renewed before it runs out
+    let mut renewals = 0;
+    for _ in 0..20 {
+        for (requested, _) in update(&mut app, &mut reader) {
+            assert_eq!(requested, gamepad);
+            renewals += 1;
+        }
+        assert_eq!(
+            app.world.resource::<GamepadRumbles>().intensity(gamepad),
+            Some(BRACE)
+        );
+    }
+
+    assert!(renewals > 0);
+
+    app.release_input(KeyCode::B);
+    assert_eq!(update(&mut app, &mut reader), [(gamepad, None)]);
+}
+
+#[test]
+fn overlapping_rumbles_use_the_strongest_intensities() {
+    let gamepad = Gamepad::new(0);
+    let mut app = create_app(None);
+    let mut reader = ManualEventReader::default();
+
+    app.send_input(KeyCode::B);
+    update(&mut app, &mut reader);
+
+    app.send_input(KeyCode::Space);
+    update(&mut app, &mut reader);
+    assert_eq!(
+        app.world.resource::<GamepadRumbles>().intensity(gamepad),
+        Some(GamepadRumbleIntensity {
+            strong_motor: 1.0,
+            weak_motor: 0.3,
+        })
+    );
+
+    // Once the shot is over, the weaker rumble carries on
+    update(&mut app, &mut reader);
+    update(&mut app, &mut reader);
+    assert_eq!(
+        app.world.resource::<GamepadRumbles>().intensity(gamepad),
+        Some(BRACE)
+    );
+}
+
+#[test]
+fn consumed_actions_stop_rumbling() {
+    let gamepad = Gamepad::new(0);
+    let mut app = create_app(None);
+    let mut reader = ManualEventReader::default();
+
+    app.send_input(KeyCode::B);
+    update(&mut app, &mut reader);
+
+    // Consumed by the game while the key is still held
+    app.add_systems(Update, |mut query: Query<&mut ActionState<Action>>| {
+        for mut action_state in query.iter_mut() {
+            action_state.consume(&Action::Brace);
+        }
+    });
+    assert_eq!(update(&mut app, &mut reader), [(gamepad, None)]);
+    assert!(update(&mut app, &mut reader).is_empty());
+}
+
+#[test]
+fn despawned_entities_stop_rumbling() {
+    let gamepad = Gamepad::new(0);
+    let mut app = create_app(None);
+    let mut reader = ManualEventReader::default();
+
+    app.send_input(KeyCode::B);
+    update(&mut app, &mut reader);
+
+    // Despawned by the game while the key is still held
+    app.add_systems(
+        Update,
+        |mut commands: Commands, query: Query<Entity, With<ActionRumble<Action>>>| {
+            for entity in query.iter() {
+                commands.entity(entity).despawn();
+            }
+        },
+    );
+    assert_eq!(update(&mut app, &mut reader), [(gamepad, None)]);
+    assert!(update(&mut app, &mut reader).is_empty());
+}
+
+#[test]
+fn held_actions_of_several_entities_use_the_strongest_intensity() {
+    let gamepad = Gamepad::new(0);
+    let mut app = create_app(None);
+    let mut reader = ManualEventReader::default();
+
+    // A second player on the same gamepad, holding a stronger rumble with another key
+    let strong = GamepadRumbleIntensity::weak_motor(0.8);
+    let mut action_rumble = ActionRumble::default();
+    action_rumble.insert(Action::Brace, ActionRumbleEffect::while_pressed(strong));
+    let second = app
+        .world
+        .spawn((
+            InputManagerBundle::with_map(InputMap::new([(Action::Brace, KeyCode::V)])),
+            action_rumble,
+        ))
+        .id();
+
+    app.send_input(KeyCode::B);
+    app.send_input(KeyCode::V);
+    update(&mut app, &mut reader);
+    assert_eq!(
+        app.world.resource::<GamepadRumbles>().intensity(gamepad),
+        Some(strong)
+    );
+
+    // The weaker rumble carries on once the stronger one is despawned
+    app.world.despawn(second);
+    update(&mut app, &mut reader);
+    assert_eq!(
+        app.world.resource::<GamepadRumbles>().intensity(gamepad),
+        Some(BRACE)
+    );
+}
@@ -0,0 +1,120 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResolution};
+use leafwing_input_manager::axislike::DualAxisData;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::screen_edge::ScreenEdge;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Pan,
+}
+
+fn test_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (Action::Pan, UserInput::from(VirtualDPad::arrow_keys())),
+        (Action::Pan, ScreenEdge::new(20.0).into()),
+    ]));
+
+    let window = app
+        .world
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(800.0, 600.0),
+                focused: true,
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    app.update();
+    (app, window)
+}
+
+fn move_cursor(app: &mut App, window: Entity, position: Option<Vec2>) {
+    app.world
+        .get_mut::<Window>(window)
+        .unwrap()
+        .set_cursor_position(position);
+}
+
+fn pan(app: &App) -> Option<DualAxisData> {
+    app.world
+        .resource::<ActionState<Action>>()
+        .axis_pair(&Action::Pan)
+}
+
+#[test]
+fn points_towards_the_edges_near_the_cursor() {
+    let (mut app, window) = test_app();
+
+    move_cursor(&mut app, window, Some(Vec2::new(400.0, 300.0)));
+    app.update();
+    assert!(!app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(&Action::Pan));
+    assert_eq!(pan(&app), Some(DualAxisData::new(0.0, 0.0)));
+
+    move_cursor(&mut app, window, Some(Vec2::new(790.0, 300.0)));
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(&Action::Pan));
+    assert_eq!(pan(&app), Some(DualAxisData::new(1.0, 0.0)));
+
+    // The top of the window is up
+    move_cursor(&mut app, window, Some(Vec2::new(400.0, 5.0)));
+    app.update();
+    assert_eq!(pan(&app), Some(DualAxisData::new(0.0, 1.0)));
+
+    // Corners produce diagonals
+    move_cursor(&mut app, window, Some(Vec2::new(10.0, 595.0)));
+    app.update();
+    assert_eq!(pan(&app), Some(DualAxisData::new(-1.0, -1.0)));
+}
+
+#[test]
+fn reads_nothing_outside_of_the_window_or_while_it_is_unfocused() {
+    let (mut app, window) = test_app();
+
+    move_cursor(&mut app, window, Some(Vec2::new(790.0, 300.0)));
+    app.world.get_mut::<Window>(window).unwrap().focused = false;
+    app.update();
+    assert!(!app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(&Action::Pan));
+
+    app.world.get_mut::<Window>(window).unwrap().focused = true;
+    move_cursor(&mut app, window, None);
+    app.update();
+    assert!(!app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(&Action::Pan));
+}
+
+#[test]
+fn adds_up_with_the_keyboard() {
+    let (mut app, window) = test_app();
+
+    move_cursor(&mut app, window, Some(Vec2::new(790.0, 300.0)));
+    app.send_input(KeyCode::Up);
+    app.update();
+    assert_eq!(pan(&app), Some(DualAxisData::new(1.0, 1.0)));
+
+    // Pushing against the edge cancels it out
+    app.release_input(KeyCode::Up);
+    app.send_input(KeyCode::Left);
+    app.update();
+    assert_eq!(pan(&app), Some(DualAxisData::new(0.0, 0.0)));
+}
@@ -0,0 +1,93 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::input_map::{SharedInputMap, UseSharedInputMap};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+    Shoot,
+}
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .insert_resource(SharedInputMap(InputMap::new([(
+        Action::Jump,
+        KeyCode::Space,
+    )])));
+    app
+}
+
+fn input_map(app: &App, entity: Entity) -> &InputMap<Action> {
+    app.world.get::<InputMap<Action>>(entity).unwrap()
+}
+
+#[test]
+fn shared_input_map_is_copied_to_marked_entities() {
+    let mut app = create_app();
+    let first = app
+        .world
+        .spawn((InputManagerBundle::<Action>::default(), UseSharedInputMap))
+        .id();
+    // The input map is inserted if it is missing
+    let second = app.world.spawn(UseSharedInputMap).id();
+    let unmarked = app
+        .world
+        .spawn(InputManagerBundle::with_map(InputMap::new([(
+            Action::Jump,
+            KeyCode::W,
+        )])))
+        .id();
+    app.update();
+
+    let shared = InputMap::new([(Action::Jump, KeyCode::Space)]);
+    assert_eq!(input_map(&app, first), &shared);
+    assert_eq!(input_map(&app, second), &shared);
+
+    // Changing the settings applies to every marked entity
+    let changed = InputMap::new([(Action::Jump, KeyCode::Up), (Action::Shoot, KeyCode::F)]);
+    app.world.resource_mut::<SharedInputMap<Action>>().0 = changed.clone();
+    app.update();
+
+    assert_eq!(input_map(&app, first), &changed);
+    assert_eq!(input_map(&app, second), &changed);
+    assert_eq!(
+        input_map(&app, unmarked),
+        &InputMap::new([(Action::Jump, KeyCode::W)])
+    );
+
+    // The copies are only made when the resource changes
+    app.world
+        .get_mut::<InputMap<Action>>(first)
+        .unwrap()
+        .insert(Action::Shoot, KeyCode::G);
+    app.update();
+    assert_ne!(input_map(&app, first), &changed);
+}
+
+#[test]
+fn entities_marked_later_receive_the_shared_input_map() {
+    let mut app = create_app();
+    app.update();
+
+    // Such as a player that respawns
+    let player = app
+        .world
+        .spawn((InputManagerBundle::<Action>::default(), UseSharedInputMap))
+        .id();
+    app.update();
+    assert_eq!(
+        input_map(&app, player),
+        &InputMap::new([(Action::Jump, KeyCode::Space)])
+    );
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(&Action::Jump));
+}
@@ -0,0 +1,161 @@
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Duration;
+use leafwing_input_manager::plugin::InputManagerSystem;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::timing::DoubleTickPolicy;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Jump,
+}
+
+/// What the user system saw of the action, each time it ran
+#[derive(Resource, Default, Debug, PartialEq)]
+struct Observations(Vec<(bool, Duration)>);
+
+fn observe(action_state: Res<ActionState<Action>>, mut observations: ResMut<Observations>) {
+    observations.0.push((
+        action_state.just_pressed(&Action::Jump),
+        action_state.current_duration(&Action::Jump),
+    ));
+}
+
+#[test]
+fn user_system_between_update_and_tick() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([(Action::Jump, KeyCode::Space)]))
+    .init_resource::<Observations>()
+    .add_systems(
+        PreUpdate,
+        observe
+            .after(InputManagerSystem::Update)
+            .before(InputManagerSystem::ReleaseOnDisable),
+    );
+    app.update();
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    let observations = &app.world.resource::<Observations>().0;
+    // The press has been seen, but not ticked yet
+    assert_eq!(observations.last(), Some(&(true, Duration::ZERO)));
+
+    app.update();
+    let observations = &app.world.resource::<Observations>().0;
+    assert!(!observations.last().unwrap().0);
+}
+
+#[test]
+fn custom_schedule() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default().in_schedule(FixedUpdate),
+    ))
+    // Two fixed timesteps run each frame
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        20,
+    )))
+    .insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(10)))
+    .insert_resource(DoubleTickPolicy::Allow)
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([(Action::Jump, KeyCode::Space)]))
+    .init_resource::<Observations>()
+    .add_systems(
+        FixedUpdate,
+        observe
+            .after(InputManagerSystem::Update)
+            .before(InputManagerSystem::ReleaseOnDisable),
+    );
+    app.update();
+    app.update();
+    app.world.resource_mut::<Observations>().0.clear();
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    app.update();
+
+    let just_pressed: Vec<bool> = app
+        .world
+        .resource::<Observations>()
+        .0
+        .iter()
+        .map(|&(just_pressed, _)| just_pressed)
+        .collect();
+    assert_eq!(just_pressed, [true, false, false, false]);
+}
+
+/// A custom schedule that runs twice per frame, like a sub-stepped simulation
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct SubStep;
+
+fn run_sub_steps(world: &mut World) {
+    world.run_schedule(SubStep);
+    world.run_schedule(SubStep);
+}
+
+fn sub_stepped_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default().in_schedule(SubStep),
+    ))
+    .init_schedule(SubStep)
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([(Action::Jump, KeyCode::Space)]))
+    .init_resource::<Observations>()
+    .add_systems(Update, run_sub_steps)
+    .add_systems(
+        SubStep,
+        observe
+            .after(InputManagerSystem::Update)
+            .before(InputManagerSystem::ReleaseOnDisable),
+    );
+    app.update();
+    app
+}
+
+fn observed_just_pressed(app: &App) -> Vec<bool> {
+    app.world
+        .resource::<Observations>()
+        .0
+        .iter()
+        .map(|&(just_pressed, _)| just_pressed)
+        .collect()
+}
+
+#[test]
+fn sub_stepped_schedule_keeps_edges_for_the_frame() {
+    let mut app = sub_stepped_app();
+    app.world.resource_mut::<Observations>().0.clear();
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    app.update();
+
+    // Edges are cleared by the first run of each frame only
+    assert_eq!(observed_just_pressed(&app), [true, true, false, false]);
+}
+
+#[test]
+fn sub_stepped_schedule_with_double_ticks() {
+    let mut app = sub_stepped_app();
+    app.insert_resource(DoubleTickPolicy::Allow);
+    app.world.resource_mut::<Observations>().0.clear();
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    app.update();
+
+    assert_eq!(observed_just_pressed(&app), [true, false, false, false]);
+}
@@ -0,0 +1,125 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Run,
+    Pause,
+}
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (Action::Run, KeyCode::ShiftLeft),
+        (Action::Pause, KeyCode::Escape),
+    ]));
+    app.update();
+    app
+}
+
+fn action_state(app: &App) -> &ActionState<Action> {
+    app.world.resource::<ActionState<Action>>()
+}
+
+fn set_enabled(app: &mut App, enabled: bool) {
+    app.world.resource_mut::<ToggleActions<Action>>().enabled = enabled;
+}
+
+#[test]
+fn disable_while_held() {
+    let mut app = create_app();
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Run));
+
+    set_enabled(&mut app, false);
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Run));
+
+    // The release is only seen once, even though the key is still held
+    app.update();
+    assert!(action_state(&app).released(&Action::Run));
+    assert!(!action_state(&app).just_released(&Action::Run));
+}
+
+#[test]
+fn re_enable_while_held() {
+    let mut app = create_app();
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    set_enabled(&mut app, false);
+    app.update();
+    app.update();
+
+    set_enabled(&mut app, true);
+    app.update();
+    app.update();
+    assert!(action_state(&app).released(&Action::Run));
+    assert!(!action_state(&app).just_pressed(&Action::Run));
+
+    // Pressing the key again works as usual
+    app.release_input(KeyCode::ShiftLeft);
+    app.update();
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Run));
+}
+
+#[test]
+fn repress_on_enable() {
+    let mut app = create_app();
+    app.world
+        .resource_mut::<ToggleActions<Action>>()
+        .repress_on_enable = true;
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    set_enabled(&mut app, false);
+    app.update();
+
+    set_enabled(&mut app, true);
+    app.update();
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Run));
+}
+
+#[test]
+fn disable_some_actions() {
+    let mut app = create_app();
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+
+    let mut toggle_actions = app.world.resource_mut::<ToggleActions<Action>>();
+    toggle_actions.enabled = false;
+    toggle_actions.disable_during = Some(vec![Action::Run]);
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Run));
+
+    // Other actions keep working
+    app.send_input(KeyCode::Escape);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Pause));
+    assert!(action_state(&app).released(&Action::Run));
+
+    // Disabled actions stay released, even when their inputs are pressed again
+    app.release_input(KeyCode::ShiftLeft);
+    app.update();
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    assert!(action_state(&app).released(&Action::Run));
+    assert!(!action_state(&app).just_released(&Action::Run));
+
+    set_enabled(&mut app, true);
+    app.update();
+    app.release_input(KeyCode::ShiftLeft);
+    app.update();
+    app.send_input(KeyCode::ShiftLeft);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Run));
+}
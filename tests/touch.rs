@@ -0,0 +1,661 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::InputPlugin;
+use bevy::math::Rect;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Duration;
+use bevy::window::{PrimaryWindow, WindowResolution};
+use leafwing_input_manager::axislike::DualAxisData;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::touch::{SwipeDirection, TapArbitration, TouchGesture, TouchJoystick};
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Select,
+    Inspect,
+    Jump,
+    Move,
+    Aim,
+    SwipeLeft,
+    SwipeRight,
+    SwipeUp,
+    SwipeDown,
+    Zoom,
+    Undo,
+    Redo,
+    Dash,
+    Dodge,
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (Action::Select, UserInput::from(TouchGesture::Tap)),
+        (
+            Action::Inspect,
+            TouchGesture::LongPress {
+                min_duration: Duration::from_millis(500),
+            }
+            .into(),
+        ),
+        (
+            Action::Jump,
+            TouchGesture::Tap
+                .within_rect(Rect::new(0.5, 0.0, 1.0, 1.0))
+                .into(),
+        ),
+        (
+            Action::Move,
+            TouchJoystick::new(Rect::new(0.0, 0.0, 0.5, 1.0), 100.0)
+                .with_deadzone(DualAxis::ZERO_DEADZONE_SHAPE)
+                .into(),
+        ),
+        (
+            Action::Aim,
+            TouchJoystick::new(Rect::new(0.5, 0.0, 1.0, 1.0), 100.0)
+                .with_deadzone(DualAxis::ZERO_DEADZONE_SHAPE)
+                .into(),
+        ),
+        (Action::Undo, TouchGesture::multi_tap(2).into()),
+        (Action::Redo, TouchGesture::multi_tap(3).into()),
+    ]));
+
+    app.world.spawn((
+        Window {
+            resolution: WindowResolution::new(800.0, 600.0),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+fn action_state(app: &App) -> &ActionState<Action> {
+    app.world.resource::<ActionState<Action>>()
+}
+
+#[test]
+fn taps_are_pressed_for_a_single_frame_on_release() {
+    let mut app = test_app();
+
+    app.send_touch(0, Vec2::new(100.0, 100.0));
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Select));
+
+    // Fingers can wobble a little while tapping
+    app.move_touch(0, Vec2::new(110.0, 105.0));
+    app.update();
+    app.release_touch(0);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::Inspect));
+
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Select));
+}
+
+#[test]
+fn long_presses_are_pressed_once_their_duration_elapses() {
+    let mut app = test_app();
+
+    app.send_touch(0, Vec2::new(100.0, 100.0));
+    for _ in 0..5 {
+        app.update();
+        assert!(!action_state(&app).pressed(&Action::Inspect));
+    }
+
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Inspect));
+    app.update();
+    assert!(action_state(&app).pressed(&Action::Inspect));
+    assert!(!action_state(&app).just_pressed(&Action::Inspect));
+
+    // Lifting the finger after a long press isn't a tap
+    app.release_touch(0);
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Inspect));
+    assert!(!action_state(&app).pressed(&Action::Select));
+}
+
+#[test]
+fn fingers_that_move_too_far_make_no_gesture() {
+    let mut app = test_app();
+
+    app.send_touch(0, Vec2::new(100.0, 100.0));
+    app.update();
+    app.move_touch(0, Vec2::new(150.0, 100.0));
+    app.update();
+    app.release_touch(0);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Select));
+
+    app.send_touch(1, Vec2::new(100.0, 100.0));
+    app.update();
+    app.move_touch(1, Vec2::new(100.0, 150.0));
+    for _ in 0..10 {
+        app.update();
+        assert!(!action_state(&app).pressed(&Action::Inspect));
+    }
+}
+
+#[test]
+fn gestures_can_be_restricted_to_a_region() {
+    let mut app = test_app();
+
+    // Left half of the screen
+    app.send_touch(0, Vec2::new(200.0, 300.0));
+    app.update();
+    app.release_touch(0);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::Jump));
+
+    // Right half of the screen
+    app.send_touch(1, Vec2::new(600.0, 300.0));
+    app.update();
+    app.release_touch(1);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Select));
+    assert!(action_state(&app).just_pressed(&Action::Jump));
+}
+
+#[test]
+fn fingers_tapping_together_only_make_a_multi_finger_tap() {
+    let mut app = test_app();
+
+    app.send_touch(0, Vec2::new(300.0, 300.0));
+    app.send_touch(1, Vec2::new(400.0, 300.0));
+    app.update();
+
+    // The first finger to be lifted could still be part of a two-finger tap
+    app.release_touch(0);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::Undo));
+
+    app.release_touch(1);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Undo));
+    assert!(!action_state(&app).pressed(&Action::Redo));
+    assert!(!action_state(&app).pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::Jump));
+
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Undo));
+    assert!(!action_state(&app).pressed(&Action::Select));
+}
+
+#[test]
+fn three_fingers_tapping_together_make_a_three_finger_tap() {
+    let mut app = test_app();
+
+    app.send_touch(0, Vec2::new(300.0, 300.0));
+    app.send_touch(1, Vec2::new(400.0, 300.0));
+    app.update();
+    app.send_touch(2, Vec2::new(500.0, 300.0));
+    app.update();
+    app.release_touch(0);
+    app.release_touch(1);
+    app.release_touch(2);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Redo));
+    assert!(!action_state(&app).pressed(&Action::Undo));
+    assert!(!action_state(&app).pressed(&Action::Select));
+
+    // Fingers that touch the screen too far apart don't tap it together
+    app.send_touch(3, Vec2::new(100.0, 300.0));
+    app.send_touch(4, Vec2::new(700.0, 300.0));
+    app.update();
+    app.release_touch(3);
+    app.release_touch(4);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Undo));
+}
+
+#[test]
+fn a_lingering_finger_releases_the_withheld_taps() {
+    let mut app = test_app();
+
+    app.send_touch(0, Vec2::new(300.0, 300.0));
+    app.send_touch(1, Vec2::new(400.0, 300.0));
+    app.update();
+    app.release_touch(0);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Select));
+
+    // Once the second finger has been held for longer than a tap, the first finger tapped on its own
+    let mut tapped = false;
+    for _ in 0..3 {
+        app.update();
+        tapped |= action_state(&app).just_pressed(&Action::Select);
+    }
+    assert!(tapped);
+
+    app.release_touch(1);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Undo));
+    assert!(!action_state(&app).pressed(&Action::Select));
+}
+
+fn double_tap_app(arbitration: TapArbitration) -> App {
+    let mut app = test_app();
+    let double_tap = |region| TouchGesture::DoubleTap {
+        max_interval: Duration::from_millis(500),
+        max_movement: 50.0,
+        region: Some(region),
+    };
+    app.insert_resource(arbitration)
+        .insert_resource(InputMap::new([
+            (Action::Select, UserInput::from(TouchGesture::Tap)),
+            // Left half of the screen
+            (
+                Action::Dash,
+                double_tap(Rect::new(0.0, 0.0, 0.5, 1.0)).into(),
+            ),
+            // Right half of the screen
+            (
+                Action::Dodge,
+                double_tap(Rect::new(0.5, 0.0, 1.0, 1.0)).into(),
+            ),
+        ]));
+    app
+}
+
+fn tap(app: &mut App, id: u64, position: Vec2) {
+    app.send_touch(id, position);
+    app.update();
+    app.release_touch(id);
+    app.update();
+}
+
+#[test]
+fn double_taps_can_press_both_taps() {
+    let mut app = double_tap_app(TapArbitration::PressBoth);
+
+    tap(&mut app, 0, Vec2::new(200.0, 300.0));
+    assert!(action_state(&app).just_pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::Dash));
+
+    // The second tap is a new finger
+    tap(&mut app, 1, Vec2::new(220.0, 310.0));
+    assert!(action_state(&app).just_pressed(&Action::Select));
+    assert!(action_state(&app).just_pressed(&Action::Dash));
+    assert!(!action_state(&app).pressed(&Action::Dodge));
+
+    // A third tap starts a new double tap
+    tap(&mut app, 2, Vec2::new(200.0, 300.0));
+    assert!(action_state(&app).just_pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::Dash));
+
+    // Taps too far apart aren't a double tap
+    tap(&mut app, 3, Vec2::new(300.0, 300.0));
+    assert!(!action_state(&app).pressed(&Action::Dash));
+}
+
+#[test]
+fn double_taps_can_delay_single_taps() {
+    let mut app = double_tap_app(TapArbitration::DelayTaps);
+
+    tap(&mut app, 0, Vec2::new(200.0, 300.0));
+    assert!(!action_state(&app).pressed(&Action::Select));
+    tap(&mut app, 1, Vec2::new(220.0, 310.0));
+    assert!(action_state(&app).just_pressed(&Action::Dash));
+    assert!(!action_state(&app).pressed(&Action::Select));
+
+    // Neither tap of the double tap is pressed later on
+    for _ in 0..6 {
+        app.update();
+        assert!(!action_state(&app).pressed(&Action::Select));
+    }
+
+    // A lone tap is pressed once its interval elapsed without a second tap
+    tap(&mut app, 2, Vec2::new(600.0, 300.0));
+    let mut frames_until_tapped = 0;
+    while !action_state(&app).pressed(&Action::Select) {
+        assert!(frames_until_tapped < 10, "the tap was never pressed");
+        app.update();
+        frames_until_tapped += 1;
+    }
+    // On the first frame more than 500 milliseconds after the tap
+    assert_eq!(frames_until_tapped, 6);
+    assert!(action_state(&app).just_pressed(&Action::Select));
+    assert!(!action_state(&app).pressed(&Action::Dodge));
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Select));
+}
+
+#[test]
+fn double_taps_are_tracked_for_each_region() {
+    let mut app = double_tap_app(TapArbitration::PressBoth);
+
+    tap(&mut app, 0, Vec2::new(200.0, 300.0));
+    tap(&mut app, 1, Vec2::new(600.0, 300.0));
+    tap(&mut app, 2, Vec2::new(200.0, 300.0));
+    assert!(action_state(&app).just_pressed(&Action::Dash));
+    assert!(!action_state(&app).pressed(&Action::Dodge));
+    tap(&mut app, 3, Vec2::new(600.0, 300.0));
+    assert!(action_state(&app).just_pressed(&Action::Dodge));
+    assert!(!action_state(&app).pressed(&Action::Dash));
+}
+
+fn axis_pair(app: &App, action: Action) -> DualAxisData {
+    action_state(app).axis_pair(&action).unwrap()
+}
+
+#[test]
+fn joysticks_follow_the_finger_that_anchored_them() {
+    let mut app = test_app();
+
+    app.send_touch(0, Vec2::new(200.0, 300.0));
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Move));
+    assert_eq!(axis_pair(&app, Action::Move), DualAxisData::new(0.0, 0.0));
+
+    app.move_touch(0, Vec2::new(250.0, 300.0));
+    app.update();
+    assert!(action_state(&app).pressed(&Action::Move));
+    assert_eq!(axis_pair(&app, Action::Move), DualAxisData::new(0.5, 0.0));
+
+    // Up is positive, and the joystick can't be pushed further than its radius
+    app.move_touch(0, Vec2::new(200.0, 100.0));
+    app.update();
+    assert_eq!(axis_pair(&app, Action::Move), DualAxisData::new(0.0, 1.0));
+
+    // Another finger in the same region doesn't take over the joystick
+    app.send_touch(1, Vec2::new(100.0, 500.0));
+    app.update();
+    app.move_touch(1, Vec2::new(300.0, 500.0));
+    app.update();
+    assert_eq!(axis_pair(&app, Action::Move), DualAxisData::new(0.0, 1.0));
+
+    // Lifting the anchoring finger returns the joystick to rest
+    app.release_touch(0);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Move));
+    assert_eq!(axis_pair(&app, Action::Move), DualAxisData::new(0.0, 0.0));
+
+    // A finger touching the region once it is free anchors the joystick again
+    app.release_touch(1);
+    app.update();
+    app.send_touch(2, Vec2::new(100.0, 100.0));
+    app.update();
+    app.move_touch(2, Vec2::new(50.0, 100.0));
+    app.update();
+    assert_eq!(axis_pair(&app, Action::Move), DualAxisData::new(-0.5, 0.0));
+}
+
+#[test]
+fn joysticks_are_moved_independently() {
+    let mut app = test_app();
+
+    app.send_touch(0, Vec2::new(200.0, 300.0));
+    app.send_touch(1, Vec2::new(600.0, 300.0));
+    app.update();
+    app.move_touch(0, Vec2::new(200.0, 350.0));
+    app.move_touch(1, Vec2::new(700.0, 300.0));
+    app.update();
+    assert_eq!(axis_pair(&app, Action::Move), DualAxisData::new(0.0, -0.5));
+    assert_eq!(axis_pair(&app, Action::Aim), DualAxisData::new(1.0, 0.0));
+
+    // A finger dragged into the region of another joystick stays with its own joystick
+    app.move_touch(0, Vec2::new(500.0, 300.0));
+    app.update();
+    assert_eq!(axis_pair(&app, Action::Move), DualAxisData::new(1.0, 0.0));
+    assert_eq!(axis_pair(&app, Action::Aim), DualAxisData::new(1.0, 0.0));
+
+    app.release_touch(1);
+    app.update();
+    assert!(action_state(&app).pressed(&Action::Move));
+    assert!(!action_state(&app).pressed(&Action::Aim));
+}
+
+fn swipe_app() -> App {
+    let mut app = test_app();
+    app.insert_resource(
+        InputMap::new([
+            (Action::SwipeLeft, TouchGesture::swipe(SwipeDirection::Left)),
+            (
+                Action::SwipeRight,
+                TouchGesture::swipe(SwipeDirection::Right),
+            ),
+            (Action::SwipeDown, TouchGesture::swipe(SwipeDirection::Down)),
+            (
+                Action::SwipeUp,
+                TouchGesture::Swipe {
+                    direction: SwipeDirection::Up,
+                    min_distance: TouchGesture::SWIPE_MIN_DISTANCE,
+                    max_duration: TouchGesture::SWIPE_MAX_DURATION,
+                    diagonal_tolerance: Some(15.0),
+                },
+            ),
+        ])
+        // Bottom left quarter of the screen
+        .insert(
+            Action::Move,
+            TouchJoystick::new(Rect::new(0.0, 0.5, 0.5, 1.0), 100.0),
+        )
+        .build(),
+    );
+    app
+}
+
+/// Moves a finger from `from` to `to`, lifting it after it touched the screen for `frames` frames
+fn swipe(app: &mut App, id: u64, from: Vec2, to: Vec2, frames: u32) {
+    app.send_touch(id, from);
+    app.update();
+    app.move_touch(id, to);
+    for _ in 1..frames {
+        app.update();
+    }
+    app.release_touch(id);
+    app.update();
+}
+
+#[test]
+fn swipes_must_move_far_enough() {
+    let mut app = swipe_app();
+
+    swipe(
+        &mut app,
+        0,
+        Vec2::new(400.0, 150.0),
+        Vec2::new(351.0, 150.0),
+        2,
+    );
+    assert!(!action_state(&app).pressed(&Action::SwipeLeft));
+
+    swipe(
+        &mut app,
+        1,
+        Vec2::new(400.0, 150.0),
+        Vec2::new(350.0, 150.0),
+        2,
+    );
+    assert!(action_state(&app).just_pressed(&Action::SwipeLeft));
+    assert!(!action_state(&app).pressed(&Action::SwipeRight));
+
+    app.update();
+    assert!(action_state(&app).just_released(&Action::SwipeLeft));
+}
+
+#[test]
+fn swipes_must_be_quick_enough() {
+    let mut app = swipe_app();
+
+    // 600 milliseconds
+    swipe(
+        &mut app,
+        0,
+        Vec2::new(400.0, 150.0),
+        Vec2::new(500.0, 150.0),
+        6,
+    );
+    assert!(!action_state(&app).pressed(&Action::SwipeRight));
+
+    // 500 milliseconds
+    swipe(
+        &mut app,
+        1,
+        Vec2::new(400.0, 150.0),
+        Vec2::new(500.0, 150.0),
+        5,
+    );
+    assert!(action_state(&app).just_pressed(&Action::SwipeRight));
+}
+
+#[test]
+fn diagonal_swipes_press_their_dominant_direction_unless_tolerated() {
+    let mut app = swipe_app();
+
+    // Mostly to the right, and close enough to the diagonal for swipes up
+    swipe(
+        &mut app,
+        0,
+        Vec2::new(400.0, 150.0),
+        Vec2::new(460.0, 110.0),
+        2,
+    );
+    assert!(action_state(&app).just_pressed(&Action::SwipeRight));
+    assert!(action_state(&app).just_pressed(&Action::SwipeUp));
+
+    // Mostly up, and swipes to the right have no tolerance
+    swipe(
+        &mut app,
+        1,
+        Vec2::new(400.0, 150.0),
+        Vec2::new(440.0, 90.0),
+        2,
+    );
+    assert!(!action_state(&app).pressed(&Action::SwipeRight));
+    assert!(action_state(&app).just_pressed(&Action::SwipeUp));
+
+    // Mostly down, and too far from the diagonal for swipes up
+    swipe(
+        &mut app,
+        2,
+        Vec2::new(400.0, 150.0),
+        Vec2::new(440.0, 210.0),
+        2,
+    );
+    assert!(action_state(&app).just_pressed(&Action::SwipeDown));
+    assert!(!action_state(&app).pressed(&Action::SwipeUp));
+    assert!(!action_state(&app).pressed(&Action::SwipeRight));
+}
+
+#[test]
+fn swipes_starting_on_a_joystick_belong_to_the_joystick() {
+    let mut app = swipe_app();
+
+    app.send_touch(0, Vec2::new(200.0, 450.0));
+    app.update();
+    app.move_touch(0, Vec2::new(100.0, 450.0));
+    app.update();
+    assert!(action_state(&app).pressed(&Action::Move));
+    app.release_touch(0);
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::SwipeLeft));
+
+    // The same swipe above the joystick
+    swipe(
+        &mut app,
+        1,
+        Vec2::new(200.0, 150.0),
+        Vec2::new(100.0, 150.0),
+        2,
+    );
+    assert!(action_state(&app).just_pressed(&Action::SwipeLeft));
+}
+
+fn pinch_app() -> App {
+    let mut app = test_app();
+    app.insert_resource(
+        InputMap::new([(
+            Action::Zoom,
+            UserInput::from(TouchGesture::Pinch { sensitivity: 0.01 }),
+        )])
+        .insert(Action::Zoom, SingleAxis::mouse_wheel_y())
+        .build(),
+    );
+    app
+}
+
+fn zoom(app: &App) -> f32 {
+    action_state(app).value(&Action::Zoom)
+}
+
+#[test]
+fn spreading_fingers_zooms_in() {
+    let mut app = pinch_app();
+
+    app.send_touch(0, Vec2::new(300.0, 300.0));
+    app.send_touch(1, Vec2::new(500.0, 300.0));
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Zoom));
+
+    // 20 logical pixels further apart on each frame
+    for frame in 1..=3 {
+        let offset = 10.0 * frame as f32;
+        app.move_touch(0, Vec2::new(300.0 - offset, 300.0));
+        app.move_touch(1, Vec2::new(500.0 + offset, 300.0));
+        app.update();
+        assert!(action_state(&app).pressed(&Action::Zoom));
+        assert!((zoom(&app) - 0.2).abs() < 1e-4, "{}", zoom(&app));
+    }
+
+    // Fingers that stop moving stop zooming
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Zoom));
+    assert_eq!(zoom(&app), 0.0);
+}
+
+#[test]
+fn pinching_fingers_zooms_out() {
+    let mut app = pinch_app();
+
+    app.send_touch(0, Vec2::new(300.0, 300.0));
+    app.send_touch(1, Vec2::new(500.0, 300.0));
+    app.update();
+    app.move_touch(0, Vec2::new(350.0, 300.0));
+    app.update();
+    assert!((zoom(&app) + 0.5).abs() < 1e-4, "{}", zoom(&app));
+}
+
+#[test]
+fn pinches_need_two_fingers() {
+    let mut app = pinch_app();
+
+    app.send_touch(0, Vec2::new(300.0, 300.0));
+    app.update();
+    app.move_touch(0, Vec2::new(200.0, 300.0));
+    app.update();
+    assert!(!action_state(&app).pressed(&Action::Zoom));
+    assert_eq!(zoom(&app), 0.0);
+}
+
+#[test]
+fn pinches_add_up_with_the_mouse_wheel() {
+    let mut app = pinch_app();
+
+    app.send_touch(0, Vec2::new(300.0, 300.0));
+    app.send_touch(1, Vec2::new(500.0, 300.0));
+    app.update();
+    app.move_touch(1, Vec2::new(600.0, 300.0));
+    app.world.send_event(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: 1.0,
+        window: Entity::PLACEHOLDER,
+    });
+    app.update();
+    assert!((zoom(&app) - 2.0).abs() < 1e-4, "{}", zoom(&app));
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "ui")]
+
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use leafwing_input_manager::mouse_gestures::DragAxis;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::ui_blocking::{BlocksGameInput, PointerOverUi, UiBlockingPlugin};
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Fire,
+    Look,
+    Jump,
+    Orbit,
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default(),
+        UiBlockingPlugin,
+    ))
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (Action::Fire, UserInput::from(MouseButton::Left)),
+        (Action::Look, MouseButton::Right.into()),
+        (Action::Jump, KeyCode::Space.into()),
+        (Action::Orbit, DragAxis::new(MouseButton::Middle).into()),
+    ]));
+    app.world
+        .resource_mut::<InputMap<Action>>()
+        .set_clicks_through_ui(&Action::Look, true);
+    app.update();
+    app
+}
+
+/// A full-screen node, with the pointer over it
+///
+/// The cursor position is normally computed by `bevy::ui` from the layout of the node.
+fn full_screen_node() -> RelativeCursorPosition {
+    RelativeCursorPosition {
+        normalized_visible_node_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+        normalized: Some(Vec2::new(0.5, 0.5)),
+    }
+}
+
+fn click_everything(app: &mut App) {
+    app.send_input(MouseButton::Left);
+    app.send_input(MouseButton::Right);
+    app.send_input(KeyCode::Space);
+    app.update();
+}
+
+fn pressed(app: &App, action: Action) -> bool {
+    app.world.resource::<ActionState<Action>>().pressed(&action)
+}
+
+#[test]
+fn blocking_node_swallows_clicks() {
+    let mut app = test_app();
+    let node = app.world.spawn((full_screen_node(), BlocksGameInput)).id();
+
+    click_everything(&mut app);
+    assert_eq!(*app.world.resource::<PointerOverUi>(), PointerOverUi(true));
+    assert!(!pressed(&app, Action::Fire));
+    // Keys and actions that click through the UI are not affected
+    assert!(pressed(&app, Action::Jump));
+    assert!(pressed(&app, Action::Look));
+
+    // Once the pointer leaves the node, clicks press actions again
+    app.world
+        .get_mut::<RelativeCursorPosition>(node)
+        .unwrap()
+        .normalized = Some(Vec2::new(1.5, 0.5));
+    app.update();
+    assert!(pressed(&app, Action::Fire));
+}
+
+#[test]
+fn marker_less_node_does_not_swallow_clicks() {
+    let mut app = test_app();
+    app.world.spawn(full_screen_node());
+
+    click_everything(&mut app);
+    assert_eq!(*app.world.resource::<PointerOverUi>(), PointerOverUi(false));
+    assert!(pressed(&app, Action::Fire));
+    assert!(pressed(&app, Action::Look));
+}
+
+#[test]
+fn interactions_swallow_clicks() {
+    let mut app = test_app();
+    app.world.spawn(Interaction::Hovered);
+
+    click_everything(&mut app);
+    assert_eq!(*app.world.resource::<PointerOverUi>(), PointerOverUi(true));
+    assert!(!pressed(&app, Action::Fire));
+}
+
+#[test]
+fn blocking_nodes_track_the_pointer() {
+    let mut app = test_app();
+    let node = app.world.spawn(BlocksGameInput).id();
+    app.update();
+
+    assert!(app.world.get::<RelativeCursorPosition>(node).is_some());
+    assert_eq!(*app.world.resource::<PointerOverUi>(), PointerOverUi(false));
+}
+
+#[test]
+fn drags_that_start_over_the_ui_stay_with_it() {
+    let mut app = test_app();
+    let node = app.world.spawn((full_screen_node(), BlocksGameInput)).id();
+
+    app.send_input(MouseButton::Middle);
+    app.update();
+
+    // Dragging off the node doesn't orbit
+    app.world
+        .get_mut::<RelativeCursorPosition>(node)
+        .unwrap()
+        .normalized = Some(Vec2::new(1.5, 0.5));
+    app.send_mouse_motion(Vec2::new(10.0, 0.0));
+    app.update();
+    assert_eq!(*app.world.resource::<PointerOverUi>(), PointerOverUi(false));
+    assert!(!pressed(&app, Action::Orbit));
+
+    // Drags that start off the node orbit
+    app.release_input(MouseButton::Middle);
+    app.update();
+    app.send_input(MouseButton::Middle);
+    app.update();
+    app.send_mouse_motion(Vec2::new(10.0, 0.0));
+    app.update();
+    assert!(pressed(&app, Action::Orbit));
+}
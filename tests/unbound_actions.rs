@@ -0,0 +1,93 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::utils::tracing::field::{Field, Visit};
+use bevy::utils::tracing::span::{Attributes, Id, Record};
+use bevy::utils::tracing::{self, Event, Metadata, Subscriber};
+use leafwing_input_manager::input_map::AllowedUnboundActions;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::systems::warn_unbound_actions;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Run,
+    Jump,
+    Grapple,
+}
+
+/// The `unbound` field of every event that was logged
+#[derive(Clone, Default)]
+struct CapturedWarnings(Arc<Mutex<Vec<String>>>);
+
+impl Visit for CapturedWarnings {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "unbound" {
+            self.0.lock().unwrap().push(format!("{value:?}"));
+        }
+    }
+}
+
+impl Subscriber for CapturedWarnings {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        event.record(&mut self.clone());
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+fn captured_warnings(world: &mut World) -> Vec<String> {
+    let captured = CapturedWarnings::default();
+    tracing::subscriber::with_default(captured.clone(), || {
+        world.run_system_once(warn_unbound_actions::<Action>);
+    });
+    let warnings = captured.0.lock().unwrap().clone();
+    warnings
+}
+
+#[test]
+fn missing_variant_is_reported() {
+    let mut world = World::new();
+    world.spawn(InputMap::new([
+        (Action::Run, KeyCode::ShiftLeft),
+        (Action::Jump, KeyCode::Space),
+    ]));
+
+    assert_eq!(captured_warnings(&mut world), vec![r#"["Grapple"]"#]);
+}
+
+#[test]
+fn fully_bound_map_is_not_reported() {
+    let mut world = World::new();
+    world.insert_resource(InputMap::new([
+        (Action::Run, KeyCode::ShiftLeft),
+        (Action::Jump, KeyCode::Space),
+        (Action::Grapple, KeyCode::G),
+    ]));
+
+    assert!(captured_warnings(&mut world).is_empty());
+}
+
+#[test]
+fn allowed_actions_are_not_reported() {
+    let mut world = World::new();
+    world.insert_resource(AllowedUnboundActions::new([Action::Grapple]));
+    world.insert_resource(InputMap::new([(Action::Jump, KeyCode::Space)]));
+
+    assert_eq!(captured_warnings(&mut world), vec![r#"["Run"]"#]);
+}
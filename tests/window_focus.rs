@@ -0,0 +1,160 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use bevy::window::WindowFocused;
+use leafwing_input_manager::plugin::{FocusPolicy, WindowFocus};
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::timing::TickClock;
+
+#[derive(Actionlike, Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash)]
+enum Action {
+    Run,
+    Jump,
+}
+
+fn test_app(focus_policy: FocusPolicy) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        InputManagerPlugin::<Action>::default().on_focus_lost(focus_policy),
+    ))
+    .add_event::<WindowFocused>()
+    .insert_resource(TickClock::manual())
+    .init_resource::<ActionState<Action>>()
+    .insert_resource(InputMap::new([
+        (Action::Run, KeyCode::W),
+        (Action::Jump, KeyCode::Space),
+    ]));
+    app.update();
+    app
+}
+
+fn set_focus(app: &mut App, focused: bool) {
+    app.world.send_event(WindowFocused {
+        window: Entity::PLACEHOLDER,
+        focused,
+    });
+}
+
+fn action_state(app: &App) -> &ActionState<Action> {
+    app.world.resource::<ActionState<Action>>()
+}
+
+#[test]
+fn release_all_releases_held_actions() {
+    let mut app = test_app(FocusPolicy::ReleaseAll);
+    app.send_input(KeyCode::W);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Run));
+
+    // The key-up never arrives, as the window is unfocused
+    set_focus(&mut app, false);
+    app.update();
+    assert!(!app.world.resource::<WindowFocus>().focused);
+    assert!(action_state(&app).just_released(&Action::Run));
+
+    app.update();
+    assert!(action_state(&app).released(&Action::Run));
+    assert!(!action_state(&app).just_released(&Action::Run));
+
+    // Once focused again, the actions follow the input resources
+    app.release_input(KeyCode::W);
+    set_focus(&mut app, true);
+    app.update();
+    assert!(action_state(&app).released(&Action::Run));
+    assert!(!action_state(&app).just_pressed(&Action::Run));
+}
+
+#[test]
+fn release_all_resynchronizes_held_keys() {
+    let mut app = test_app(FocusPolicy::ReleaseAll);
+    app.send_input(KeyCode::W);
+    app.update();
+
+    set_focus(&mut app, false);
+    app.update();
+    assert!(action_state(&app).released(&Action::Run));
+
+    set_focus(&mut app, true);
+    app.update();
+    assert!(action_state(&app).just_pressed(&Action::Run));
+}
+
+#[test]
+fn freeze_keeps_actions_until_focused() {
+    let mut app = test_app(FocusPolicy::Freeze);
+    app.send_input(KeyCode::W);
+    app.update();
+
+    set_focus(&mut app, false);
+    app.release_input(KeyCode::W);
+    app.send_input(KeyCode::Space);
+    app.update();
+    // The actions are not ticked either, so the press from the last focused frame is still fresh
+    assert!(action_state(&app).just_pressed(&Action::Run));
+    assert!(action_state(&app).released(&Action::Jump));
+
+    set_focus(&mut app, true);
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Run));
+    assert!(action_state(&app).just_pressed(&Action::Jump));
+}
+
+#[test]
+fn freeze_keeps_durations_until_focused() {
+    let frame_time = Duration::from_millis(10);
+    let mut app = test_app(FocusPolicy::Freeze);
+    app.send_input(KeyCode::W);
+    app.advance_frames_with(3, frame_time);
+    assert_eq!(
+        action_state(&app).current_duration(&Action::Run),
+        frame_time * 2
+    );
+
+    app.send_input(KeyCode::Space);
+    app.advance_frames_with(1, frame_time);
+    assert!(action_state(&app).just_pressed(&Action::Jump));
+
+    set_focus(&mut app, false);
+    app.advance_frames_with(5, frame_time);
+    assert!(action_state(&app).just_pressed(&Action::Jump));
+    assert_eq!(
+        action_state(&app).current_duration(&Action::Run),
+        frame_time * 3
+    );
+    assert_eq!(
+        action_state(&app).current_duration(&Action::Jump),
+        Duration::ZERO
+    );
+
+    // The time spent unfocused is skipped
+    set_focus(&mut app, true);
+    app.advance_frames_with(1, frame_time);
+    assert!(action_state(&app).pressed(&Action::Jump));
+    assert!(!action_state(&app).just_pressed(&Action::Jump));
+    assert_eq!(
+        action_state(&app).current_duration(&Action::Run),
+        frame_time * 4
+    );
+    assert_eq!(
+        action_state(&app).current_duration(&Action::Jump),
+        frame_time
+    );
+}
+
+#[test]
+fn ignore_keeps_updating() {
+    let mut app = test_app(FocusPolicy::Ignore);
+    assert!(!app.world.contains_resource::<WindowFocus>());
+
+    app.send_input(KeyCode::W);
+    app.update();
+    set_focus(&mut app, false);
+    app.update();
+    assert!(action_state(&app).pressed(&Action::Run));
+
+    app.release_input(KeyCode::W);
+    app.update();
+    assert!(action_state(&app).just_released(&Action::Run));
+}